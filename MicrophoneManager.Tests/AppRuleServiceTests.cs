@@ -0,0 +1,209 @@
+using MicrophoneManager.Tests.Fakes;
+using MicrophoneManager.WinUI.Models;
+using MicrophoneManager.WinUI.Services;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for switching the default microphone while ruled applications run.
+/// </summary>
+public class AppRuleServiceTests
+{
+    private readonly HashSet<string> _running = new(StringComparer.OrdinalIgnoreCase) { "explorer" };
+
+    private static FakeAudioDeviceService CreateAudioService()
+    {
+        return FakeAudioDeviceService.WithMicrophones(
+            "mic-1",
+            new FakeAudioDeviceService.FakeMicrophone("mic-1", "Webcam"),
+            new FakeAudioDeviceService.FakeMicrophone("mic-2", "XLR Interface"),
+            new FakeAudioDeviceService.FakeMicrophone("mic-3", "Headset"));
+    }
+
+    private AppRuleService CreateService(FakeAudioDeviceService audioService, InMemoryPreferencesStore? preferences = null)
+    {
+        return new AppRuleService(audioService, preferences ?? new InMemoryPreferencesStore(), () => _running.ToList());
+    }
+
+    [Fact]
+    public void RunningApplication_SwitchesAndRestoresOnExit()
+    {
+        // Arrange
+        var audioService = CreateAudioService();
+        using var service = CreateService(audioService);
+        service.SaveRule(new AppRule("obs64.exe", "mic-2"));
+
+        // Act
+        _running.Add("obs64");
+        service.Evaluate();
+        var whileRunning = audioService.DefaultConsoleId;
+        _running.Remove("obs64");
+        service.Evaluate();
+
+        // Assert
+        Assert.Equal("mic-2", whileRunning);
+        Assert.Equal("mic-1", audioService.DefaultConsoleId);
+        Assert.Equal("mic-1", audioService.DefaultCommunicationsId);
+        Assert.Null(service.ActiveRule);
+    }
+
+    [Fact]
+    public void Rule_OnlySetsItsRole()
+    {
+        // Arrange
+        var audioService = CreateAudioService();
+        using var service = CreateService(audioService);
+        _running.Add("Teams");
+        service.SaveRule(new AppRule("teams.exe", "mic-3", AppRuleRole.Communications));
+
+        // Act
+        service.Evaluate();
+
+        // Assert
+        Assert.Equal("mic-1", audioService.DefaultConsoleId);
+        Assert.Equal("mic-3", audioService.DefaultCommunicationsId);
+    }
+
+    [Fact]
+    public void LatestStartedApplication_Wins_ThenHandsBack()
+    {
+        // Arrange
+        var audioService = CreateAudioService();
+        using var service = CreateService(audioService);
+        service.SaveRule(new AppRule("obs64", "mic-2"));
+        service.SaveRule(new AppRule("discord", "mic-3"));
+
+        // Act & Assert
+        _running.Add("discord");
+        service.Evaluate();
+        Assert.Equal("mic-3", audioService.DefaultConsoleId);
+
+        _running.Add("obs64");
+        service.Evaluate();
+        Assert.Equal("mic-2", audioService.DefaultConsoleId);
+
+        _running.Remove("obs64");
+        service.Evaluate();
+        Assert.Equal("mic-3", audioService.DefaultConsoleId);
+
+        _running.Remove("discord");
+        service.Evaluate();
+        Assert.Equal("mic-1", audioService.DefaultConsoleId);
+    }
+
+    [Fact]
+    public void Exit_KeepsDefaultTheUserChoseMeanwhile()
+    {
+        // Arrange
+        var audioService = CreateAudioService();
+        using var service = CreateService(audioService);
+        service.SaveRule(new AppRule("obs64", "mic-2"));
+        _running.Add("obs64");
+        service.Evaluate();
+
+        // Act
+        audioService.SetDefaultMicrophone("mic-3");
+        _running.Remove("obs64");
+        service.Evaluate();
+
+        // Assert
+        Assert.Equal("mic-3", audioService.DefaultConsoleId);
+    }
+
+    [Fact]
+    public void Exit_WithoutRestore_LeavesDefault()
+    {
+        // Arrange
+        var audioService = CreateAudioService();
+        using var service = CreateService(audioService);
+        service.SaveRule(new AppRule("obs64", "mic-2", RestoreOnExit: false));
+        _running.Add("obs64");
+        service.Evaluate();
+
+        // Act
+        _running.Remove("obs64");
+        service.Evaluate();
+
+        // Assert
+        Assert.Equal("mic-2", audioService.DefaultConsoleId);
+    }
+
+    [Fact]
+    public void Rule_ForDisconnectedMicrophone_IsSkipped()
+    {
+        // Arrange
+        var audioService = CreateAudioService();
+        using var service = CreateService(audioService);
+        service.SaveRule(new AppRule("obs64", "mic-9"));
+
+        // Act
+        _running.Add("obs64");
+        service.Evaluate();
+
+        // Assert
+        Assert.Equal("mic-1", audioService.DefaultConsoleId);
+        Assert.Null(service.ActiveRule);
+    }
+
+    [Fact]
+    public void SaveRule_ReplacesRuleForSameApplication_AndPersists()
+    {
+        // Arrange
+        var preferences = new InMemoryPreferencesStore();
+        using var service = CreateService(CreateAudioService(), preferences);
+        service.SaveRule(new AppRule("obs64.exe", "mic-2"));
+
+        // Act
+        service.SaveRule(new AppRule("OBS64", "mic-3", AppRuleRole.Both));
+
+        // Assert
+        using var reloaded = CreateService(CreateAudioService(), preferences);
+        var rule = Assert.Single(reloaded.GetRules());
+        Assert.Equal("mic-3", rule.DeviceKey);
+        Assert.Equal(AppRuleRole.Both, rule.Role);
+    }
+
+    [Fact]
+    public void RemoveRule_WhileActive_Restores()
+    {
+        // Arrange
+        var audioService = CreateAudioService();
+        using var service = CreateService(audioService);
+        service.SaveRule(new AppRule("obs64", "mic-2"));
+        _running.Add("obs64");
+        service.Evaluate();
+
+        // Act
+        var removed = service.RemoveRule("obs64.exe");
+
+        // Assert
+        Assert.True(removed);
+        Assert.Empty(service.GetRules());
+        Assert.Equal("mic-1", audioService.DefaultConsoleId);
+    }
+
+    [Fact]
+    public void UnchangedApplications_DoNotEnumerateMicrophones_UntilDevicesChange()
+    {
+        // Arrange
+        var audioService = CreateAudioService();
+        using var service = CreateService(audioService);
+        service.SaveRule(new AppRule("obs64", "mic-9"));
+        _running.Add("obs64");
+        service.Evaluate();
+        var enumerations = audioService.EnumerationCount;
+
+        // Act
+        _running.Add("notepad");
+        service.Evaluate();
+        var afterUnrelatedStart = audioService.EnumerationCount;
+        audioService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-9", "USB Microphone"));
+        audioService.RaiseDevicesChanged("mic-9", DeviceChangeKind.Added);
+        service.Evaluate();
+
+        // Assert
+        Assert.Equal(enumerations, afterUnrelatedStart);
+        Assert.Equal("mic-9", audioService.DefaultConsoleId);
+    }
+}
@@ -0,0 +1,105 @@
+using MicrophoneManager.Tests.Fakes;
+using MicrophoneManager.WinUI.Services;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for the theme and flyout size chosen in settings.
+/// </summary>
+public class AppearanceServiceTests
+{
+    [Fact]
+    public void Defaults_FollowWindowsAtNormalSize()
+    {
+        // Arrange
+        var service = new AppearanceService(new InMemoryPreferencesStore());
+
+        // Act & Assert
+        Assert.Equal(AppTheme.System, service.Theme);
+        Assert.Equal(100, service.ScalePercent);
+        Assert.Equal(1.0, service.Scale);
+    }
+
+    [Theory]
+    [InlineData("Dark", AppTheme.Dark)]
+    [InlineData("light", AppTheme.Light)]
+    [InlineData("5", AppTheme.System)]
+    [InlineData("Purple", AppTheme.System)]
+    public void Theme_ParsesStoredValue(string stored, AppTheme expected)
+    {
+        // Arrange
+        var preferences = new InMemoryPreferencesStore();
+        preferences.SetString(AppearanceService.ThemePreference, stored);
+
+        // Act & Assert
+        Assert.Equal(expected, new AppearanceService(preferences).Theme);
+    }
+
+    [Theory]
+    [InlineData("150", 150)]
+    [InlineData("50", 100)]
+    [InlineData("400", 100)]
+    [InlineData("big", 100)]
+    public void ScalePercent_IgnoresValuesOutOfRange(string stored, int expected)
+    {
+        // Arrange
+        var preferences = new InMemoryPreferencesStore();
+        preferences.SetString(AppearanceService.ScalePreference, stored);
+
+        // Act & Assert
+        Assert.Equal(expected, new AppearanceService(preferences).ScalePercent);
+    }
+
+    [Fact]
+    public void ScalePercent_ClampsAndRaisesChanged()
+    {
+        // Arrange
+        var preferences = new InMemoryPreferencesStore();
+        var service = new AppearanceService(preferences);
+        var changes = 0;
+        service.Changed += (s, e) => changes++;
+
+        // Act
+        service.ScalePercent = 250;
+
+        // Assert
+        Assert.Equal(200, service.ScalePercent);
+        Assert.Equal("200", preferences.GetString(AppearanceService.ScalePreference));
+        Assert.Equal(1, changes);
+    }
+
+    [Fact]
+    public void Defaults_ClearThePreferences()
+    {
+        // Arrange
+        var preferences = new InMemoryPreferencesStore();
+        var service = new AppearanceService(preferences) { Theme = AppTheme.Dark, ScalePercent = 125 };
+
+        // Act
+        service.Theme = AppTheme.System;
+        service.ScalePercent = 100;
+
+        // Assert
+        Assert.Null(preferences.GetString(AppearanceService.ThemePreference));
+        Assert.Null(preferences.GetString(AppearanceService.ScalePreference));
+    }
+
+    [Fact]
+    public void IsCompact_IsStoredAndRaisesChangedOnce()
+    {
+        // Arrange
+        var preferences = new InMemoryPreferencesStore();
+        var service = new AppearanceService(preferences);
+        var changes = 0;
+        service.Changed += (s, e) => changes++;
+
+        // Act
+        service.IsCompact = true;
+        service.IsCompact = true;
+
+        // Assert
+        Assert.True(new AppearanceService(preferences).IsCompact);
+        Assert.Equal(1, changes);
+    }
+}
@@ -0,0 +1,34 @@
+using Xunit;
+using MicrophoneManager.WinUI.Services;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for AudioDeviceService logic that doesn't need real endpoints.
+/// </summary>
+public class AudioDeviceServiceTests
+{
+    [Fact]
+    public void IsExternalVolumeChange_TreatsPolledDifferencesAsExternal()
+    {
+        // Arrange & Act
+        var polled = AudioDeviceService.IsExternalVolumeChange(null);
+        var firstReading = AudioDeviceService.IsExternalVolumeChange(null, isFirstReading: true);
+
+        // Assert - our own writes update the poll's baseline, so the poll only finds other software's
+        Assert.True(polled);
+        Assert.False(firstReading);
+    }
+
+    [Fact]
+    public void IsExternalVolumeChange_UsesTheCallbackEventContext()
+    {
+        // Arrange & Act
+        var ours = AudioDeviceService.IsExternalVolumeChange(AudioDeviceService.VolumeEventContext);
+        var theirs = AudioDeviceService.IsExternalVolumeChange(Guid.NewGuid());
+
+        // Assert
+        Assert.False(ours);
+        Assert.True(theirs);
+    }
+}
@@ -0,0 +1,51 @@
+using Xunit;
+using MicrophoneManager.WinUI.Services;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for mapping Core Audio HRESULTs to AudioErrorCode.
+/// </summary>
+public class AudioErrorCodeTests
+{
+    [Theory]
+    [InlineData(0x80070490u, AudioErrorCode.DeviceNotFound)]
+    [InlineData(0x88890004u, AudioErrorCode.DeviceInvalidated)]
+    [InlineData(0x8889000Au, AudioErrorCode.DeviceInUseExclusive)]
+    [InlineData(0x8889000Eu, AudioErrorCode.ExclusiveModeNotAllowed)]
+    [InlineData(0x80070005u, AudioErrorCode.AccessDenied)]
+    [InlineData(0x88890010u, AudioErrorCode.ServiceNotRunning)]
+    [InlineData(0x80131505u, AudioErrorCode.Timeout)]
+    [InlineData(0x80004005u, AudioErrorCode.ComError)]
+    public void FromHResult_MapsKnownCodes(uint hResult, AudioErrorCode expected)
+    {
+        // Arrange & Act
+        var code = AudioErrorCodes.FromHResult(unchecked((int)hResult));
+
+        // Assert
+        Assert.Equal(expected, code);
+    }
+
+    [Fact]
+    public void OperationFailedEventArgs_ExposesMappedCode()
+    {
+        // Arrange
+        var args = new AudioDeviceService.AudioOperationFailedEventArgs("set volume", "mic-1", unchecked((int)0x88890004), "Device invalidated");
+
+        // Act & Assert
+        Assert.Equal(AudioErrorCode.DeviceInvalidated, args.Code);
+        Assert.True(AudioErrorCodes.IsTransient(args.Code));
+        Assert.Contains("(DeviceInvalidated)", args.Details);
+    }
+
+    [Fact]
+    public void IsTransient_IsFalseWhenExclusiveModeIsNotAllowed()
+    {
+        // Arrange
+        var code = AudioErrorCodes.FromHResult(unchecked((int)0x8889000E));
+
+        // Act & Assert - a policy setting, so retrying can't help
+        Assert.False(AudioErrorCodes.IsTransient(code));
+        Assert.True(AudioErrorCodes.IsTransient(AudioErrorCode.DeviceInUseExclusive));
+    }
+}
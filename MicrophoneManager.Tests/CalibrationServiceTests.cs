@@ -0,0 +1,124 @@
+using MicrophoneManager.Tests.Fakes;
+using MicrophoneManager.WinUI.Models;
+using MicrophoneManager.WinUI.Services;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for working out a volume and boost from a calibration run.
+/// </summary>
+public class CalibrationServiceTests
+{
+    private static readonly VolumeRange Range = new(-65.25f, 0f, 0.03125f);
+
+    // Speech peaks sit this far above its RMS
+    private const double CrestDb = 6;
+
+    // Speech at the given RMS with pauses at -70 dBFS in between, like someone reading aloud
+    private static List<double> Speech(double speechDb)
+    {
+        return Enumerable.Repeat(-70.0, 30).Concat(Enumerable.Repeat(speechDb, 70)).ToList();
+    }
+
+    private static List<double> Peaks(List<double> rms, double? peakDb = null)
+    {
+        var peaks = rms.Select(db => db + CrestDb).ToList();
+        if (peakDb != null) peaks[^1] = peakDb.Value;
+        return peaks;
+    }
+
+    [Fact]
+    public void QuietSpeech_RecommendsMoreVolume()
+    {
+        // Act
+        var speech = Speech(-30);
+        var result = CalibrationService.Analyze(speech, Peaks(speech), -20f, Range, boost: null);
+
+        // Assert
+        Assert.True(result.HeardSpeech);
+        Assert.Equal(-70, result.NoiseFloorDb);
+        Assert.Equal(-24, result.PeakDb);
+        Assert.Equal(-30, result.AverageDb, precision: 1);
+        Assert.Equal(-8f, result.RecommendedVolumeDb);
+        Assert.True(result.IsChangeRecommended);
+    }
+
+    [Fact]
+    public void VolumeAtMaximum_TakesTheRestFromBoost()
+    {
+        // Act
+        var speech = Speech(-35);
+        var result = CalibrationService.Analyze(speech, Peaks(speech), 0f, Range, new MicrophoneBoost(10, 0, 30, 10));
+
+        // Assert
+        Assert.Equal(30f, result.RecommendedBoostDb);
+        Assert.Equal(-3f, result.RecommendedVolumeDb);
+    }
+
+    [Fact]
+    public void LoudPeaks_LimitTheGain()
+    {
+        // Act
+        var speech = Speech(-30);
+        var result = CalibrationService.Analyze(speech, Peaks(speech, peakDb: -6), -20f, Range, boost: null);
+
+        // Assert - three dB up keeps the peak at -3 dBFS
+        Assert.Equal(-17f, result.RecommendedVolumeDb);
+    }
+
+    [Fact]
+    public void Silence_RecommendsNothing()
+    {
+        // Act
+        var silence = Enumerable.Repeat(-80.0, 100).ToList();
+        var result = CalibrationService.Analyze(silence, Peaks(silence), -20f, Range, boost: null);
+
+        // Assert
+        Assert.False(result.HeardSpeech);
+        Assert.False(result.IsChangeRecommended);
+    }
+
+    [Fact]
+    public void Apply_SetsBoostAndVolume()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        var microphone = new FakeAudioDeviceService.FakeMicrophone("mic-1", "Headset") { Boost = new MicrophoneBoost(10, 0, 30, 10) };
+        fakeService.AddOrUpdateMicrophone(microphone);
+        var service = new CalibrationService(fakeService);
+        var result = new CalibrationResult(-35, -30, -70, 0f, -3f, 10f, 30f);
+
+        // Act
+        var applied = service.Apply("mic-1", result);
+
+        // Assert
+        Assert.True(applied);
+        Assert.Equal(30f, microphone.Boost!.LevelDb);
+        Assert.Equal(-3f, microphone.VolumeDb, precision: 3);
+    }
+
+    [Fact]
+    public async Task MeasureAsync_AveragesRmsAndTakesThePeakFromPeaks()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Headset") { VolumeScalar = 0.5 });
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-2", "Webcam"));
+        var service = new CalibrationService(fakeService);
+
+        // Act
+        var measuring = service.MeasureAsync("mic-1", TimeSpan.FromMilliseconds(200));
+        foreach (var level in Speech(-24))
+        {
+            fakeService.RaiseInputLevelChanged("mic-1", 50, level + CrestDb, rmsDbFs: level);
+            fakeService.RaiseInputLevelChanged("mic-2", 100, 0);
+        }
+        var result = await measuring;
+
+        // Assert
+        Assert.NotNull(result);
+        Assert.Equal(-24, result!.AverageDb, precision: 1);
+        Assert.Equal(-18, result.PeakDb);
+    }
+}
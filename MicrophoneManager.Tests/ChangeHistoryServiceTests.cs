@@ -0,0 +1,205 @@
+using MicrophoneManager.Tests.Fakes;
+using MicrophoneManager.WinUI.Models;
+using MicrophoneManager.WinUI.Services;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for the change history: which changes are recorded, who they're attributed to, and persistence.
+/// </summary>
+public class ChangeHistoryServiceTests : IDisposable
+{
+    private readonly string _directory = Path.Combine(Path.GetTempPath(), "MicManagerTests", Guid.NewGuid().ToString("N"));
+    private DateTime _now = new(2024, 3, 1, 9, 0, 0, DateTimeKind.Utc);
+
+    public void Dispose()
+    {
+        try { Directory.Delete(_directory, recursive: true); } catch { }
+    }
+
+    private static FakeAudioDeviceService CreateAudioService()
+    {
+        return FakeAudioDeviceService.WithMicrophones(
+            "mic-1",
+            new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic") { VolumeScalar = 0.4 },
+            new FakeAudioDeviceService.FakeMicrophone("mic-2", "Headset"));
+    }
+
+    private ChangeHistoryService CreateService(FakeAudioDeviceService audioService, string? filePath = null)
+    {
+        return new ChangeHistoryService(audioService, filePath, () => _now, () => "Discord");
+    }
+
+    [Fact]
+    public void DefaultChange_FromApp_IsAttributedToApp()
+    {
+        // Arrange
+        var audioService = CreateAudioService();
+        using var service = CreateService(audioService);
+
+        // Act
+        audioService.SetDefaultMicrophone("mic-2");
+
+        // Assert
+        var entries = service.GetEntries();
+        Assert.Equal(2, entries.Count);
+        Assert.All(entries, e => Assert.Equal(ChangeHistorySource.App, e.Source));
+        Assert.All(entries, e => Assert.Null(e.Culprit));
+        Assert.Contains(entries, e => e.Kind == ChangeHistoryKind.DefaultDevice && e.DeviceName == "Headset" && e.OldValue == "Desk Mic");
+        Assert.Contains(entries, e => e.Kind == ChangeHistoryKind.DefaultCommunicationsDevice);
+    }
+
+    [Fact]
+    public void DefaultChange_FromElsewhere_IsExternalWithCulprit()
+    {
+        // Arrange
+        var audioService = CreateAudioService();
+        using var service = CreateService(audioService);
+
+        // Act
+        audioService.SimulateExternalDefaultChange("mic-2");
+
+        // Assert
+        var entry = service.GetEntries().First(e => e.Kind == ChangeHistoryKind.DefaultDevice);
+        Assert.Equal(ChangeHistorySource.External, entry.Source);
+        Assert.Equal("Discord", entry.Culprit);
+        Assert.Equal("External (Discord)", entry.SourceText);
+    }
+
+    [Fact]
+    public void ExternalSwitchBackToDeviceChosenEarlier_IsExternal()
+    {
+        // Arrange
+        var audioService = CreateAudioService();
+        using var service = CreateService(audioService);
+        audioService.SetDefaultMicrophone("mic-2");
+        audioService.SimulateExternalDefaultChange("mic-1");
+        audioService.Now += TimeSpan.FromMinutes(5);
+
+        // Act
+        audioService.SimulateExternalDefaultChange("mic-2");
+
+        // Assert
+        var entry = service.GetEntries().First(e => e.Kind == ChangeHistoryKind.DefaultDevice);
+        Assert.Equal("Headset", entry.DeviceName);
+        Assert.Equal(ChangeHistorySource.External, entry.Source);
+    }
+
+    [Fact]
+    public void VolumeAndMute_AreRecordedSeparatelyWithSource()
+    {
+        // Arrange
+        var audioService = CreateAudioService();
+        using var service = CreateService(audioService);
+
+        // Act
+        audioService.RaiseMicrophoneVolumeChanged("mic-1", 0.4f, true);
+        _now = _now.AddMinutes(1);
+        audioService.RaiseMicrophoneVolumeChanged("mic-1", 0.65f, true, changedExternally: true);
+
+        // Assert - newest first
+        var entries = service.GetEntries();
+        Assert.Equal(2, entries.Count);
+        Assert.Equal(ChangeHistoryKind.Volume, entries[0].Kind);
+        Assert.Equal(ChangeHistorySource.External, entries[0].Source);
+        Assert.Equal("Volume 40% → 65%", entries[0].Description);
+        Assert.Equal(ChangeHistoryKind.Mute, entries[1].Kind);
+        Assert.Equal(ChangeHistorySource.App, entries[1].Source);
+        Assert.Equal("Muted", entries[1].NewValue);
+    }
+
+    [Fact]
+    public void VolumeDrag_IsCoalescedIntoOneEntry()
+    {
+        // Arrange
+        var audioService = CreateAudioService();
+        using var service = CreateService(audioService);
+
+        // Act
+        audioService.RaiseMicrophoneVolumeChanged("mic-1", 0.5f, false);
+        _now = _now.AddMilliseconds(500);
+        audioService.RaiseMicrophoneVolumeChanged("mic-1", 0.6f, false);
+        _now = _now.AddMilliseconds(500);
+        audioService.RaiseMicrophoneVolumeChanged("mic-1", 0.7f, false);
+
+        // Assert
+        var entry = Assert.Single(service.GetEntries());
+        Assert.Equal("40%", entry.OldValue);
+        Assert.Equal("70%", entry.NewValue);
+    }
+
+    [Fact]
+    public void VolumeDraggedBack_LeavesNoEntry()
+    {
+        // Arrange
+        var audioService = CreateAudioService();
+        using var service = CreateService(audioService);
+
+        // Act
+        audioService.RaiseMicrophoneVolumeChanged("mic-1", 0.5f, false);
+        audioService.RaiseMicrophoneVolumeChanged("mic-1", 0.4f, false);
+
+        // Assert
+        Assert.Empty(service.GetEntries());
+    }
+
+    [Fact]
+    public void History_IsBoundedToCapacity()
+    {
+        // Arrange
+        var audioService = CreateAudioService();
+        using var service = CreateService(audioService);
+
+        // Act
+        for (var i = 0; i <= ChangeHistoryService.Capacity; i++)
+        {
+            audioService.RaiseMicrophoneVolumeChanged("mic-1", 0.4f, i % 2 == 0);
+        }
+
+        // Assert
+        Assert.Equal(ChangeHistoryService.Capacity, service.GetEntries().Count);
+    }
+
+    [Fact]
+    public void GetEntries_FiltersBySinceAndLimit()
+    {
+        // Arrange
+        var audioService = CreateAudioService();
+        using var service = CreateService(audioService);
+        audioService.RaiseMicrophoneVolumeChanged("mic-1", 0.4f, true);
+        var since = _now;
+        _now = _now.AddMinutes(1);
+        audioService.RaiseMicrophoneVolumeChanged("mic-1", 0.4f, false);
+        _now = _now.AddMinutes(1);
+        audioService.RaiseMicrophoneVolumeChanged("mic-1", 0.4f, true);
+
+        // Act & Assert
+        Assert.Equal(2, service.GetEntries(sinceUtc: since).Count);
+        Assert.Equal("Muted", Assert.Single(service.GetEntries(limit: 1)).NewValue);
+    }
+
+    [Fact]
+    public void Flush_PersistsHistoryForNextStart()
+    {
+        // Arrange
+        var audioService = CreateAudioService();
+        var path = Path.Combine(_directory, ChangeHistoryService.FileName);
+        using (var service = CreateService(audioService, path))
+        {
+            audioService.SimulateExternalDefaultChange("mic-2");
+            audioService.RaiseMicrophoneVolumeChanged("mic-1", 0.4f, true);
+
+            // Act
+            service.Flush();
+        }
+
+        // Assert
+        using var reloaded = CreateService(CreateAudioService(), path);
+        var entries = reloaded.GetEntries();
+        Assert.Equal(3, entries.Count);
+        Assert.Equal(ChangeHistoryKind.Mute, entries[0].Kind);
+        Assert.Equal("Discord", entries[1].Culprit);
+        Assert.Contains("\"source\":\"External\"", File.ReadAllText(path));
+    }
+}
@@ -0,0 +1,121 @@
+using MicrophoneManager.Tests.Fakes;
+using MicrophoneManager.WinUI.Models;
+using MicrophoneManager.WinUI.Services;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for parsing and running command-line commands.
+/// </summary>
+public class CliCommandRunnerTests
+{
+    private static FakeAudioDeviceService CreateService()
+    {
+        return FakeAudioDeviceService.WithMicrophones(
+            "mic-1",
+            new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic"),
+            new FakeAudioDeviceService.FakeMicrophone("mic-2", "Microphone (Blue Yeti)"));
+    }
+
+    [Fact]
+    public void TryParse_ReadsCommandAndOptions()
+    {
+        // Act
+        var parsed = CliCommand.TryParse(new[] { "--list", "--json" }, out var command, out var error);
+
+        // Assert
+        Assert.True(parsed);
+        Assert.Null(error);
+        Assert.Equal(new CliCommand(CliAction.List, null, true), command);
+    }
+
+    [Fact]
+    public void TryParse_RejectsTwoCommands()
+    {
+        // Act
+        var parsed = CliCommand.TryParse(new[] { "--mute", "--unmute" }, out _, out var error);
+
+        // Assert
+        Assert.False(parsed);
+        Assert.NotNull(error);
+    }
+
+    [Fact]
+    public void TryParse_SetDefaultNeedsName()
+    {
+        // Act
+        var parsed = CliCommand.TryParse(new[] { "--set-default" }, out _, out var error);
+
+        // Assert
+        Assert.False(parsed);
+        Assert.NotNull(error);
+    }
+
+    [Fact]
+    public void Run_MuteTargetsDefaultMicrophone()
+    {
+        // Arrange
+        var fakeService = CreateService();
+        var output = new StringWriter();
+        var runner = new CliCommandRunner(fakeService, output, new StringWriter());
+
+        // Act
+        var exitCode = runner.Run(new CliCommand(CliAction.Mute));
+
+        // Assert
+        Assert.Equal(CliCommandRunner.ExitSuccess, exitCode);
+        Assert.True(fakeService.IsMuted("mic-1"));
+        Assert.False(fakeService.IsMuted("mic-2"));
+        Assert.Contains("Desk Mic is muted", output.ToString());
+    }
+
+    [Fact]
+    public void Run_SetDefaultMatchesPartialName()
+    {
+        // Arrange
+        var fakeService = CreateService();
+        var runner = new CliCommandRunner(fakeService, new StringWriter(), new StringWriter());
+
+        // Act
+        var exitCode = runner.Run(new CliCommand(CliAction.SetDefault, "Yeti"));
+
+        // Assert
+        Assert.Equal(CliCommandRunner.ExitSuccess, exitCode);
+        Assert.Equal("mic-2", fakeService.DefaultConsoleId);
+        Assert.Equal("mic-2", fakeService.DefaultCommunicationsId);
+    }
+
+    [Fact]
+    public void Run_UnknownDeviceReturnsNotFound()
+    {
+        // Arrange
+        var fakeService = CreateService();
+        var error = new StringWriter();
+        var runner = new CliCommandRunner(fakeService, new StringWriter(), error);
+
+        // Act
+        var exitCode = runner.Run(new CliCommand(CliAction.Toggle, "Nonexistent"));
+
+        // Assert
+        Assert.Equal(CliCommandRunner.ExitDeviceNotFound, exitCode);
+        Assert.Contains("Nonexistent", error.ToString());
+    }
+
+    [Fact]
+    public void Run_ListJsonIncludesEveryMicrophone()
+    {
+        // Arrange
+        var fakeService = CreateService();
+        var output = new StringWriter();
+        var runner = new CliCommandRunner(fakeService, output, new StringWriter());
+
+        // Act
+        runner.Run(new CliCommand(CliAction.List, Json: true));
+
+        // Assert
+        using var json = System.Text.Json.JsonDocument.Parse(output.ToString());
+        Assert.Equal(2, json.RootElement.GetArrayLength());
+        Assert.Contains(json.RootElement.EnumerateArray(), e => e.GetProperty("name").GetString() == "Desk Mic" && e.GetProperty("isDefault").GetBoolean());
+    }
+}
@@ -0,0 +1,129 @@
+using MicrophoneManager.Tests.Fakes;
+using MicrophoneManager.WinUI.Services;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for counting full-scale peaks on the default microphone.
+/// </summary>
+public class ClippingServiceTests
+{
+    private DateTime _now = new(2026, 10, 17, 9, 0, 0, DateTimeKind.Utc);
+
+    private static FakeAudioDeviceService CreateAudioService()
+    {
+        return FakeAudioDeviceService.WithMicrophones(
+            "mic-1",
+            new FakeAudioDeviceService.FakeMicrophone("mic-1", "Headset"),
+            new FakeAudioDeviceService.FakeMicrophone("mic-2", "XLR Interface"));
+    }
+
+    private ClippingService CreateService(FakeAudioDeviceService audioService)
+    {
+        return new ClippingService(audioService, new InMemoryPreferencesStore(), () => _now);
+    }
+
+    private void PeakAfter(FakeAudioDeviceService audioService, string deviceId, int milliseconds, double dbFs = 0)
+    {
+        _now += TimeSpan.FromMilliseconds(milliseconds);
+        audioService.RaiseInputLevelChanged(deviceId, 100, dbFs);
+    }
+
+    [Fact]
+    public void Burst_CountsOnce()
+    {
+        // Arrange
+        var audioService = CreateAudioService();
+        using var service = CreateService(audioService);
+        var clips = new List<ClippingService.ClipEventArgs>();
+        service.Clipped += (s, e) => clips.Add(e);
+
+        // Act - one burst on consecutive buffers, then another a moment later
+        PeakAfter(audioService, "mic-1", 0);
+        PeakAfter(audioService, "mic-1", 10);
+        PeakAfter(audioService, "mic-1", 10);
+        PeakAfter(audioService, "mic-1", 500, dbFs: -0.2);
+
+        // Assert
+        Assert.Equal(2, service.GetClipCount("mic-1"));
+        Assert.Equal(new[] { 1, 2 }, clips.Select(c => c.ClipCount));
+        Assert.Equal("Headset", clips[0].DeviceName);
+    }
+
+    [Fact]
+    public void QuietPeaksAndOtherMicrophones_AreIgnored()
+    {
+        // Arrange
+        var audioService = CreateAudioService();
+        using var service = CreateService(audioService);
+
+        // Act
+        PeakAfter(audioService, "mic-1", 0, dbFs: -3);
+        PeakAfter(audioService, "mic-2", 500);
+
+        // Assert
+        Assert.Equal(0, service.GetClipCount("mic-1"));
+        Assert.Equal(0, service.GetClipCount("mic-2"));
+    }
+
+    [Fact]
+    public void FollowsDefaultMicrophone()
+    {
+        // Arrange
+        var audioService = CreateAudioService();
+        using var service = CreateService(audioService);
+
+        // Act
+        audioService.SetDefaultMicrophone("mic-2");
+        PeakAfter(audioService, "mic-1", 0);
+        PeakAfter(audioService, "mic-2", 500);
+
+        // Assert
+        Assert.Equal(0, service.GetClipCount("mic-1"));
+        Assert.Equal(1, service.GetClipCount("mic-2"));
+    }
+
+    [Fact]
+    public void RepeatedClipping_AlertsOncePerCooldown()
+    {
+        // Arrange
+        var audioService = CreateAudioService();
+        using var service = CreateService(audioService);
+        var alerts = 0;
+        service.ClippingAlert += (s, e) => alerts++;
+
+        // Act & Assert
+        PeakAfter(audioService, "mic-1", 0);
+        PeakAfter(audioService, "mic-1", 1000);
+        Assert.Equal(0, alerts);
+
+        PeakAfter(audioService, "mic-1", 1000);
+        Assert.Equal(1, alerts);
+
+        for (var i = 0; i < 6; i++) PeakAfter(audioService, "mic-1", 1000);
+        Assert.Equal(1, alerts);
+
+        _now += TimeSpan.FromMinutes(5);
+        for (var i = 0; i < 3; i++) PeakAfter(audioService, "mic-1", 1000);
+        Assert.Equal(2, alerts);
+    }
+
+    [Fact]
+    public void ResetCount_StartsOver()
+    {
+        // Arrange
+        var audioService = CreateAudioService();
+        using var service = CreateService(audioService);
+        PeakAfter(audioService, "mic-1", 0);
+        var reset = false;
+        service.CountsReset += (s, e) => reset = true;
+
+        // Act
+        service.ResetCount("mic-1");
+
+        // Assert
+        Assert.True(reset);
+        Assert.Equal(0, service.GetClipCount("mic-1"));
+    }
+}
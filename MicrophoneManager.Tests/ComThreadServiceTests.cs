@@ -0,0 +1,75 @@
+using Xunit;
+using MicrophoneManager.WinUI.Services;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for ComThreadService deadlines and cancellation of queued COM work.
+/// </summary>
+public class ComThreadServiceTests
+{
+    #region Deadlines
+
+    [Fact]
+    public async Task InvokeWithDeadlineAsync_ReturnsResult_WhenWorkFinishesInTime()
+    {
+        // Arrange
+        using var comThread = new ComThreadService();
+
+        // Act
+        var result = await comThread.InvokeWithDeadlineAsync(() => 42, TimeSpan.FromSeconds(5));
+
+        // Assert
+        Assert.Equal(42, result);
+    }
+
+    [Fact]
+    public async Task InvokeWithDeadlineAsync_ThrowsTimeout_WhenWorkerIsBlocked()
+    {
+        // Arrange
+        using var comThread = new ComThreadService();
+        using var release = new ManualResetEventSlim(false);
+        var blocker = comThread.InvokeAsync(() => release.Wait());
+
+        // Act
+        var queued = comThread.InvokeWithDeadlineAsync(() => 1, TimeSpan.FromMilliseconds(50));
+
+        // Assert
+        await Assert.ThrowsAsync<TimeoutException>(() => queued);
+        release.Set();
+        await blocker;
+    }
+
+    #endregion
+
+    #region Cancellation
+
+    [Fact]
+    public async Task CancelPending_CancelsQueuedWorkWithoutRunningIt()
+    {
+        // Arrange
+        using var comThread = new ComThreadService();
+        using var release = new ManualResetEventSlim(false);
+        using var started = new ManualResetEventSlim(false);
+        var ran = false;
+        var blocker = comThread.InvokeAsync(() =>
+        {
+            started.Set();
+            release.Wait();
+        });
+        started.Wait();
+        var queued = comThread.InvokeAsync(() => ran = true);
+
+        // Act
+        var canceled = comThread.CancelPending();
+        release.Set();
+        await blocker;
+
+        // Assert
+        Assert.Equal(1, canceled);
+        await Assert.ThrowsAnyAsync<OperationCanceledException>(() => queued);
+        Assert.False(ran);
+    }
+
+    #endregion
+}
@@ -0,0 +1,78 @@
+using Xunit;
+using MicrophoneManager.Tests.Fakes;
+using MicrophoneManager.WinUI.Services;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for CommunicationsDuckingService.
+/// </summary>
+public class CommunicationsDuckingServiceTests
+{
+    [Fact]
+    public void GetPreference_ReturnsWindowsDefaultWhenUnsetOrUnknown()
+    {
+        // Arrange
+        var store = new FakeCommunicationsDuckingStore();
+        var service = new CommunicationsDuckingService(new InMemoryPreferencesStore(), store);
+
+        // Act & Assert
+        Assert.Equal(CommunicationsDuckingService.DefaultPreference, service.GetPreference());
+        store.Value = 42;
+        Assert.Equal(CommunicationsDuckingService.DefaultPreference, service.GetPreference());
+        Assert.True(service.IsDuckingEnabled());
+    }
+
+    [Fact]
+    public void ToggleDucking_RestoresTheLevelInUseBeforeTurningOff()
+    {
+        // Arrange
+        var store = new FakeCommunicationsDuckingStore { Value = (int)CommunicationsDuckingPreference.MuteOtherSounds };
+        var service = new CommunicationsDuckingService(new InMemoryPreferencesStore(), store);
+
+        // Act
+        var off = service.ToggleDucking();
+        var offValue = store.Value;
+        var on = service.ToggleDucking();
+
+        // Assert
+        Assert.False(off);
+        Assert.Equal((int)CommunicationsDuckingPreference.DoNothing, offValue);
+        Assert.True(on);
+        Assert.Equal((int)CommunicationsDuckingPreference.MuteOtherSounds, store.Value);
+    }
+
+    [Fact]
+    public void ToggleDucking_TurnsOnWithWindowsDefaultWhenNoLevelWasSaved()
+    {
+        // Arrange
+        var store = new FakeCommunicationsDuckingStore { Value = (int)CommunicationsDuckingPreference.DoNothing };
+        var service = new CommunicationsDuckingService(new InMemoryPreferencesStore(), store);
+
+        // Act
+        var enabled = service.ToggleDucking();
+
+        // Assert
+        Assert.True(enabled);
+        Assert.Equal((int)CommunicationsDuckingService.DefaultPreference, store.Value);
+    }
+
+    [Fact]
+    public void ToggleDucking_ReportsTheStoredStateWhenTheWriteIsRefused()
+    {
+        // Arrange
+        var store = new FakeCommunicationsDuckingStore
+        {
+            Value = (int)CommunicationsDuckingPreference.ReduceBy50Percent,
+            IsReadOnly = true
+        };
+        var service = new CommunicationsDuckingService(new InMemoryPreferencesStore(), store);
+
+        // Act
+        var enabled = service.ToggleDucking();
+
+        // Assert
+        Assert.True(enabled);
+        Assert.Equal((int)CommunicationsDuckingPreference.ReduceBy50Percent, store.Value);
+    }
+}
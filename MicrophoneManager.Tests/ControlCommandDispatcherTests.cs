@@ -0,0 +1,238 @@
+using System.Text.Json;
+using MicrophoneManager.Tests.Fakes;
+using MicrophoneManager.WinUI.Models;
+using MicrophoneManager.WinUI.Services;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for the JSON commands external tools send over the control pipe.
+/// </summary>
+public class ControlCommandDispatcherTests
+{
+    private static FakeAudioDeviceService CreateService()
+    {
+        return FakeAudioDeviceService.WithMicrophones(
+            "mic-1",
+            new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic"),
+            new FakeAudioDeviceService.FakeMicrophone("mic-2", "Microphone (Blue Yeti)"));
+    }
+
+    [Fact]
+    public async Task ToggleMute_WithoutDevice_TargetsDefault()
+    {
+        // Arrange
+        var fakeService = CreateService();
+        var dispatcher = new ControlCommandDispatcher(fakeService);
+
+        // Act
+        var reply = await dispatcher.DispatchJsonAsync("""{"command":"toggleMute"}""");
+
+        // Assert
+        using var json = JsonDocument.Parse(reply);
+        Assert.True(json.RootElement.GetProperty("ok").GetBoolean());
+        Assert.True(json.RootElement.GetProperty("data").GetProperty("defaultDevice").GetProperty("isMuted").GetBoolean());
+        Assert.True(fakeService.IsMuted("mic-1"));
+    }
+
+    [Fact]
+    public async Task SetDefault_MatchesDeviceByName()
+    {
+        // Arrange
+        var fakeService = CreateService();
+        var dispatcher = new ControlCommandDispatcher(fakeService);
+
+        // Act
+        var response = await dispatcher.DispatchAsync(new ControlRequest { Command = "setDefault", Device = "yeti" });
+
+        // Assert
+        Assert.True(response.Ok);
+        Assert.Equal("mic-2", fakeService.DefaultConsoleId);
+    }
+
+    [Fact]
+    public async Task Alias_IsReportedAndMatched()
+    {
+        // Arrange
+        var fakeService = CreateService();
+        var aliases = new DeviceAliasService(new InMemoryPreferencesStore());
+        aliases.SetAlias("mic-2", "Podcast");
+        var dispatcher = new ControlCommandDispatcher(fakeService, aliasService: aliases);
+
+        // Act
+        var response = await dispatcher.DispatchAsync(new ControlRequest { Command = "setDefault", Device = "podcast" });
+
+        // Assert
+        Assert.True(response.Ok);
+        Assert.Equal("mic-2", fakeService.DefaultConsoleId);
+        var device = Assert.IsType<ControlState>(response.Data).DefaultDevice!;
+        Assert.Equal("Podcast", device.DisplayName);
+        Assert.Equal("Microphone (Blue Yeti)", device.Name);
+    }
+
+    [Fact]
+    public void HiddenDevice_IsStillListedWithHiddenFlag()
+    {
+        // Arrange
+        var fakeService = CreateService();
+        var hidden = new HiddenDeviceService(new InMemoryPreferencesStore());
+        hidden.SetHidden("mic-2", true);
+        using var dispatcher = new ControlCommandDispatcher(fakeService, hiddenDeviceService: hidden);
+
+        // Act
+        var state = dispatcher.GetState();
+
+        // Assert
+        Assert.Equal(2, state.Devices.Count);
+        Assert.True(state.Devices.Single(d => d.Id == "mic-2").Hidden);
+        Assert.False(state.DefaultDevice!.Hidden);
+    }
+
+    [Fact]
+    public async Task UnknownCommand_ReturnsError()
+    {
+        // Arrange
+        var dispatcher = new ControlCommandDispatcher(CreateService());
+
+        // Act
+        var response = await dispatcher.DispatchAsync(new ControlRequest { Command = "launchRockets" });
+
+        // Assert
+        Assert.False(response.Ok);
+        Assert.Contains("launchRockets", response.Error);
+    }
+
+    [Fact]
+    public async Task MalformedJson_ReturnsError()
+    {
+        // Arrange
+        var dispatcher = new ControlCommandDispatcher(CreateService());
+
+        // Act
+        var reply = await dispatcher.DispatchJsonAsync("{not json");
+
+        // Assert
+        using var json = JsonDocument.Parse(reply);
+        Assert.False(json.RootElement.GetProperty("ok").GetBoolean());
+    }
+
+    [Fact]
+    public async Task ApplyProfile_RestoresSavedState()
+    {
+        // Arrange
+        var fakeService = CreateService();
+        var profileService = new ProfileService(fakeService, new InMemoryPreferencesStore());
+        profileService.SaveProfile("Desk");
+        fakeService.SetDefaultMicrophone("mic-2");
+        var dispatcher = new ControlCommandDispatcher(fakeService, profileService);
+
+        // Act
+        var response = await dispatcher.DispatchAsync(new ControlRequest { Command = "applyProfile", Profile = "desk" });
+
+        // Assert
+        Assert.True(response.Ok);
+        Assert.Equal("mic-1", fakeService.DefaultConsoleId);
+    }
+
+    [Fact]
+    public async Task History_ReturnsRecordedChanges()
+    {
+        // Arrange
+        var fakeService = CreateService();
+        using var history = new ChangeHistoryService(fakeService, null, () => DateTime.UtcNow, () => null);
+        var dispatcher = new ControlCommandDispatcher(fakeService, historyService: history);
+        fakeService.SimulateExternalDefaultChange("mic-2");
+
+        // Act
+        var reply = await dispatcher.DispatchJsonAsync("""{"command":"history","limit":1}""");
+
+        // Assert
+        using var json = JsonDocument.Parse(reply);
+        var entry = Assert.Single(json.RootElement.GetProperty("data").EnumerateArray());
+        Assert.Equal("mic-2", entry.GetProperty("deviceId").GetString());
+        Assert.Equal("External", entry.GetProperty("source").GetString());
+    }
+
+    [Fact]
+    public async Task SetRule_AddsRuleForMatchedDevice()
+    {
+        // Arrange
+        var fakeService = CreateService();
+        using var rules = new AppRuleService(fakeService, new InMemoryPreferencesStore(), () => Array.Empty<string>());
+        var dispatcher = new ControlCommandDispatcher(fakeService, ruleService: rules);
+
+        // Act
+        var response = await dispatcher.DispatchAsync(new ControlRequest { Command = "setRule", Process = "obs64.exe", Device = "Yeti", Role = "both" });
+        var invalid = await dispatcher.DispatchAsync(new ControlRequest { Command = "setRule", Process = "obs64.exe", Device = "Yeti", Role = "sideways" });
+
+        // Assert
+        Assert.True(response.Ok);
+        Assert.False(invalid.Ok);
+        var rule = Assert.Single(rules.GetRules());
+        Assert.Equal("mic-2", rule.DeviceKey);
+        Assert.Equal(AppRuleRole.Both, rule.Role);
+        Assert.True(rule.RestoreOnExit);
+    }
+
+    [Fact]
+    public void Clipping_IsPublishedAsEvent()
+    {
+        // Arrange
+        var fakeService = CreateService();
+        using var clipping = new ClippingService(fakeService, new InMemoryPreferencesStore());
+        using var dispatcher = new ControlCommandDispatcher(fakeService, clippingService: clipping);
+        ControlEvent? published = null;
+        dispatcher.EventRaised += (s, e) => published = e;
+
+        // Act
+        fakeService.RaiseInputLevelChanged("mic-1", 100, 0);
+
+        // Assert
+        Assert.NotNull(published);
+        Assert.Equal("clipping", published!.Name);
+        var json = JsonSerializer.Serialize(ControlResponse.FromEvent(published), ControlJson.Options);
+        Assert.Contains("\"event\":\"clipping\"", json);
+        Assert.Contains("\"clipCount\":1", json);
+    }
+
+    [Fact]
+    public void RaiseStateChanged_PublishesCurrentState()
+    {
+        // Arrange
+        var fakeService = CreateService();
+        using var dispatcher = new ControlCommandDispatcher(fakeService);
+        ControlState? published = null;
+        dispatcher.StateChanged += (s, state) => published = state;
+        fakeService.SetMute("mic-1", true);
+
+        // Act
+        dispatcher.RaiseStateChanged();
+
+        // Assert
+        Assert.NotNull(published);
+        Assert.Equal("mic-1", published!.DefaultDevice?.Id);
+        Assert.True(published.DefaultDevice!.IsMuted);
+        Assert.Equal(2, published.Devices.Count);
+    }
+
+    [Fact]
+    public async Task PipeReply_ParsesBackIntoStateAndDeviceList()
+    {
+        // Arrange
+        var fakeService = CreateService();
+        using var dispatcher = new ControlCommandDispatcher(fakeService);
+
+        // Act
+        var state = PipeControlChannel.Parse(await dispatcher.DispatchJsonAsync("""{"command":"mute","device":"yeti"}"""), "mute");
+        var list = PipeControlChannel.Parse(await dispatcher.DispatchJsonAsync("""{"command":"list"}"""), "list");
+        var failed = PipeControlChannel.Parse(await dispatcher.DispatchJsonAsync("""{"command":"mute","device":"nothing"}"""), "mute");
+
+        // Assert
+        var devices = Assert.IsType<List<ControlDevice>>(list.Data);
+        Assert.True(devices.Single(d => d.Id == "mic-2").IsMuted);
+        Assert.Equal("mic-1", Assert.IsType<ControlState>(state.Data).DefaultDevice?.Id);
+        Assert.False(failed.Ok);
+        Assert.False(string.IsNullOrEmpty(failed.Error));
+    }
+}
@@ -0,0 +1,72 @@
+using MicrophoneManager.WinUI.Services;
+using Microsoft.Extensions.Logging;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for crash report contents and the next-start marker. Minidumps aren't written here.
+/// </summary>
+public class CrashReporterTests : IDisposable
+{
+    private readonly string _directory = Path.Combine(Path.GetTempPath(), "MicManagerTests", Guid.NewGuid().ToString("N"));
+
+    public void Dispose()
+    {
+        try { Directory.Delete(_directory, recursive: true); } catch { }
+    }
+
+    [Fact]
+    public void WriteReport_IncludesExceptionAndRecentLog()
+    {
+        // Arrange
+        using var log = new RollingFileLoggerProvider(Path.Combine(_directory, "Logs"));
+        log.CreateLogger("AudioDeviceService").LogWarning("SetMute failed for mic-1");
+        CrashReporter.AttachLog(log);
+
+        // Act
+        var folder = CrashReporter.WriteReport(new InvalidOperationException("boom"), Path.Combine(_directory, "Crashes"), includeDump: false);
+
+        // Assert
+        Assert.NotNull(folder);
+        var report = File.ReadAllText(Path.Combine(folder!, "crash.txt"));
+        Assert.Contains("InvalidOperationException: boom", report);
+        Assert.Contains("SetMute failed for mic-1", report);
+    }
+
+    [Fact]
+    public void TakePendingReport_ReturnsReportOnce()
+    {
+        // Arrange
+        var crashes = Path.Combine(_directory, "Crashes");
+        var folder = CrashReporter.WriteReport(new Exception("boom"), crashes, includeDump: false);
+
+        // Act
+        var first = CrashReporter.TakePendingReport(crashes);
+        var second = CrashReporter.TakePendingReport(crashes);
+
+        // Assert
+        Assert.Equal(folder, first);
+        Assert.Null(second);
+    }
+
+    [Fact]
+    public void RecentEntries_KeepOnlyTheNewest()
+    {
+        // Arrange
+        using var log = new RollingFileLoggerProvider(Path.Combine(_directory, "Logs"));
+        var logger = log.CreateLogger("Test");
+
+        // Act
+        for (var i = 0; i < RollingFileLoggerProvider.RecentCapacity + 10; i++)
+        {
+            logger.LogWarning("entry {Index}", i);
+        }
+
+        // Assert
+        var recent = log.GetRecentEntries();
+        Assert.Equal(RollingFileLoggerProvider.RecentCapacity, recent.Count);
+        Assert.Contains("entry 10", recent[0]);
+        Assert.Contains($"entry {RollingFileLoggerProvider.RecentCapacity + 9}", recent[^1]);
+    }
+}
@@ -0,0 +1,152 @@
+using MicrophoneManager.Tests.Fakes;
+using MicrophoneManager.WinUI.Services;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for keeping the locked microphone as the Windows default.
+/// </summary>
+public class DefaultDeviceLockServiceTests
+{
+    private static FakeAudioDeviceService CreateService()
+    {
+        return FakeAudioDeviceService.WithMicrophones(
+            "mic-1",
+            new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic"),
+            new FakeAudioDeviceService.FakeMicrophone("mic-2", "Headset"));
+    }
+
+    [Fact]
+    public void ExternalChange_IsRevertedAndReported()
+    {
+        // Arrange
+        var fakeService = CreateService();
+        var service = new DefaultDeviceLockService(fakeService, new InMemoryPreferencesStore(), () => "game");
+        service.SetLocked(true);
+        DefaultDeviceLockService.DefaultRevertedEventArgs? reverted = null;
+        service.DefaultReverted += (s, e) => reverted = e;
+
+        // Act
+        fakeService.SimulateExternalDefaultChange("mic-2");
+
+        // Assert
+        Assert.Equal("mic-1", fakeService.DefaultConsoleId);
+        Assert.Equal("mic-1", fakeService.DefaultCommunicationsId);
+        Assert.NotNull(reverted);
+        Assert.Equal("Headset", reverted!.ReplacedByName);
+        Assert.Equal("game", reverted.LikelyCulprit);
+    }
+
+    [Fact]
+    public void Revert_ReportsDevicesByAlias()
+    {
+        // Arrange
+        var fakeService = CreateService();
+        var preferences = new InMemoryPreferencesStore();
+        var aliases = new DeviceAliasService(preferences);
+        aliases.SetAlias("mic-1", "Studio");
+        var service = new DefaultDeviceLockService(fakeService, preferences, () => null, aliasService: aliases);
+        service.SetLocked(true);
+        DefaultDeviceLockService.DefaultRevertedEventArgs? reverted = null;
+        service.DefaultReverted += (s, e) => reverted = e;
+
+        // Act
+        fakeService.SimulateExternalDefaultChange("mic-2");
+
+        // Assert
+        Assert.NotNull(reverted);
+        Assert.Equal("Studio", reverted!.LockedDeviceName);
+        Assert.Equal("Headset", reverted.ReplacedByName);
+    }
+
+    [Fact]
+    public void ChangeFromThisApp_MovesLock()
+    {
+        // Arrange
+        var fakeService = CreateService();
+        var service = new DefaultDeviceLockService(fakeService, new InMemoryPreferencesStore(), () => null);
+        service.SetLocked(true);
+
+        // Act
+        fakeService.SetDefaultMicrophone("mic-2");
+
+        // Assert
+        Assert.Equal("mic-2", fakeService.DefaultConsoleId);
+        Assert.Equal("mic-2", service.LockedDeviceKey);
+    }
+
+    [Fact]
+    public void ExternalSwitchToDeviceOnceChosenInApp_IsStillReverted()
+    {
+        // Arrange
+        var fakeService = CreateService();
+        fakeService.SetDefaultMicrophone("mic-2");
+        fakeService.Now += TimeSpan.FromMinutes(10);
+        fakeService.SimulateExternalDefaultChange("mic-1");
+        var service = new DefaultDeviceLockService(fakeService, new InMemoryPreferencesStore(), () => "game");
+        service.SetLocked(true);
+        fakeService.Now += TimeSpan.FromMinutes(10);
+
+        // Act
+        fakeService.SimulateExternalDefaultChange("mic-2");
+
+        // Assert
+        Assert.Equal("mic-1", fakeService.DefaultConsoleId);
+        Assert.Equal("mic-1", service.LockedDeviceKey);
+    }
+
+    [Fact]
+    public void LockedDeviceReturning_TakesDefaultBackQuietly()
+    {
+        // Arrange
+        var fakeService = CreateService();
+        var service = new DefaultDeviceLockService(fakeService, new InMemoryPreferencesStore(), () => null);
+        service.SetLocked(true);
+        var desk = new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic");
+        fakeService.RemoveMicrophone("mic-1");
+        fakeService.SimulateExternalDefaultChange("mic-2");
+        var reverted = false;
+        service.DefaultReverted += (s, e) => reverted = true;
+
+        // Act
+        fakeService.AddOrUpdateMicrophone(desk);
+        fakeService.RaiseDevicesChanged();
+
+        // Assert
+        Assert.Equal("mic-1", fakeService.DefaultConsoleId);
+        Assert.False(reverted);
+    }
+
+    [Fact]
+    public void Unlocked_LeavesExternalChangeAlone()
+    {
+        // Arrange
+        var fakeService = CreateService();
+        using var service = new DefaultDeviceLockService(fakeService, new InMemoryPreferencesStore(), () => null);
+
+        // Act
+        fakeService.SimulateExternalDefaultChange("mic-2");
+
+        // Assert
+        Assert.Equal("mic-2", fakeService.DefaultConsoleId);
+        Assert.False(service.IsLocked);
+    }
+
+    [Fact]
+    public async Task Resume_TakesTheDefaultBack()
+    {
+        // Arrange - the power service rebuilds a different audio service, so only Resumed reaches the lock
+        var fakeService = CreateService();
+        var powerEvents = new PowerEventService(new FakeAudioDeviceService()) { ResumeSettleDelay = TimeSpan.Zero };
+        using var service = new DefaultDeviceLockService(fakeService, new InMemoryPreferencesStore(), () => null, powerEvents);
+        service.SetLocked(true);
+        fakeService.DefaultConsoleId = "mic-2";
+
+        // Act
+        await powerEvents.OnResumeAsync();
+
+        // Assert
+        Assert.Equal("mic-1", fakeService.DefaultConsoleId);
+    }
+}
@@ -0,0 +1,94 @@
+using MicrophoneManager.Tests.Fakes;
+using MicrophoneManager.WinUI.Models;
+using MicrophoneManager.WinUI.Services;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for user-chosen device names.
+/// </summary>
+public class DeviceAliasServiceTests
+{
+    private static MicrophoneDevice Device(string id, string name, string? stableId = null) =>
+        new() { Id = id, Name = name, StableId = stableId };
+
+    [Fact]
+    public void DisplayNameFor_UsesAliasWhenSet()
+    {
+        // Arrange
+        var service = new DeviceAliasService(new InMemoryPreferencesStore());
+        var device = Device("mic-1", "Microphone (3- USB Audio Device)");
+
+        // Act
+        service.SetAlias("mic-1", "Desk Mic");
+
+        // Assert
+        Assert.Equal("Desk Mic", service.DisplayNameFor(device));
+        Assert.Equal("Headset", service.DisplayNameFor(Device("mic-2", "Headset")));
+    }
+
+    [Fact]
+    public void Alias_IsKeyedByStableId()
+    {
+        // Arrange
+        var service = new DeviceAliasService(new InMemoryPreferencesStore());
+        service.SetAlias("{container}|usb|yeti|microphone", "Yeti");
+
+        // Act - same device, new endpoint ID after a driver reinstall
+        var name = service.DisplayNameFor(Device("mic-9", "Microphone (Yeti)", "{container}|usb|yeti|microphone"));
+
+        // Assert
+        Assert.Equal("Yeti", name);
+    }
+
+    [Theory]
+    [InlineData("")]
+    [InlineData("   ")]
+    [InlineData(null)]
+    [InlineData("Microphone (Yeti)")]
+    public void SetAlias_BlankOrDriverName_ClearsAlias(string? alias)
+    {
+        // Arrange
+        var preferences = new InMemoryPreferencesStore();
+        var service = new DeviceAliasService(preferences);
+        service.SetAlias("mic-1", "Yeti");
+
+        // Act
+        service.SetAlias("mic-1", alias, "Microphone (Yeti)");
+
+        // Assert
+        Assert.Null(service.GetAlias("mic-1"));
+        Assert.Empty(preferences.GetStringList(DeviceAliasService.AliasesPreference));
+    }
+
+    [Fact]
+    public void SetAlias_KeepsEqualsSignsAndRaisesChangedOnce()
+    {
+        // Arrange
+        var service = new DeviceAliasService(new InMemoryPreferencesStore());
+        var raised = 0;
+        service.AliasesChanged += (s, e) => raised++;
+
+        // Act
+        service.SetAlias("mic-1", "  A=B  ");
+        service.SetAlias("mic-1", "A=B");
+
+        // Assert
+        Assert.Equal("A=B", service.GetAlias("mic-1"));
+        Assert.Equal(1, raised);
+    }
+
+    [Fact]
+    public void FindByAlias_MatchesExactlyIgnoringCase()
+    {
+        // Arrange
+        var service = new DeviceAliasService(new InMemoryPreferencesStore());
+        service.SetAlias("mic-2", "Desk Mic");
+        var devices = new[] { Device("mic-1", "Headset"), Device("mic-2", "Microphone (USB)") };
+
+        // Act & Assert
+        Assert.Equal("mic-2", service.FindByAlias(devices, "desk mic")?.Id);
+        Assert.Null(service.FindByAlias(devices, "Desk"));
+    }
+}
@@ -0,0 +1,64 @@
+using MicrophoneManager.WinUI.Models;
+using MicrophoneManager.WinUI.Services;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for the WAVEFORMATEXTENSIBLE blobs written as an endpoint's default format.
+/// </summary>
+public class DeviceFormatBlobTests
+{
+    [Fact]
+    public void BuildDeviceFormat_Puts24BitInA32BitContainer()
+    {
+        // Act
+        var blob = DeviceFormatBlob.BuildDeviceFormat(new DeviceFormat(48000, 24, 2));
+
+        // Assert
+        Assert.Equal(DeviceFormatBlob.Size, blob.Length);
+        Assert.Equal(8, BitConverter.ToUInt16(blob, 12));       // nBlockAlign
+        Assert.Equal(48000 * 8, BitConverter.ToInt32(blob, 8)); // nAvgBytesPerSec
+        Assert.Equal(32, BitConverter.ToUInt16(blob, 14));      // wBitsPerSample
+        Assert.Equal(24, BitConverter.ToUInt16(blob, 18));      // wValidBitsPerSample
+        Assert.Equal(new Guid("00000001-0000-0010-8000-00aa00389b71"), new Guid(blob.AsSpan(24, 16)));
+    }
+
+    [Fact]
+    public void BuildMixFormat_IsFloatAtTheSameRateAndChannels()
+    {
+        // Act
+        var blob = DeviceFormatBlob.BuildMixFormat(new DeviceFormat(96000, 16, 6));
+
+        // Assert
+        Assert.Equal(6, BitConverter.ToUInt16(blob, 2));
+        Assert.Equal(96000, BitConverter.ToInt32(blob, 4));
+        Assert.Equal(32, BitConverter.ToUInt16(blob, 14));
+        Assert.Equal(0x3Fu, BitConverter.ToUInt32(blob, 20));
+        Assert.Equal(new Guid("00000003-0000-0010-8000-00aa00389b71"), new Guid(blob.AsSpan(24, 16)));
+    }
+
+    [Theory]
+    [InlineData(1, 0x4u)]
+    [InlineData(2, 0x3u)]
+    [InlineData(4, 0x33u)]
+    [InlineData(6, 0x3Fu)]
+    [InlineData(8, 0x63Fu)]
+    [InlineData(12, 0u)]
+    public void GetChannelMask_UsesStandardLayouts(int channels, uint expected)
+    {
+        // Act & Assert
+        Assert.Equal(expected, DeviceFormatBlob.GetChannelMask(channels));
+    }
+
+    [Fact]
+    public void Read_ReportsValidBitsOfWhatWasBuilt()
+    {
+        // Arrange
+        var format = new DeviceFormat(44100, 24, 2);
+
+        // Act & Assert
+        Assert.Equal(format, DeviceFormatBlob.Read(DeviceFormatBlob.BuildDeviceFormat(format)));
+        Assert.Null(DeviceFormatBlob.Read(new byte[8]));
+    }
+}
@@ -0,0 +1,61 @@
+using MicrophoneManager.Tests.Fakes;
+using MicrophoneManager.WinUI.Services;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for grouping the endpoints of one physical device.
+/// </summary>
+public class DeviceGroupServiceTests
+{
+    [Fact]
+    public void KeyFor_IgnoresMissingAndEmptyContainers()
+    {
+        // Arrange
+        var id = Guid.NewGuid();
+
+        // Act & Assert
+        Assert.Equal(id.ToString("B"), DeviceGroupService.KeyFor(id));
+        Assert.Null(DeviceGroupService.KeyFor(null));
+        Assert.Null(DeviceGroupService.KeyFor(Guid.Empty));
+    }
+
+    [Theory]
+    [InlineData("Elgato Wave", "Elgato Wave: Microphone, Monitor Mix")]
+    [InlineData(null, "Microphone, Monitor Mix")]
+    [InlineData(" ", "Microphone, Monitor Mix")]
+    public void GetTitle_NamesAdapterThenEndpoints(string? adapter, string expected)
+    {
+        // Act & Assert
+        Assert.Equal(expected, DeviceGroupService.GetTitle(adapter, new[] { "Microphone", "Monitor Mix" }));
+    }
+
+    [Fact]
+    public void SetCollapsed_IsStoredAndRaisesChangedOnce()
+    {
+        // Arrange
+        var preferences = new InMemoryPreferencesStore();
+        var service = new DeviceGroupService(preferences);
+        var changes = 0;
+        service.Changed += (s, e) => changes++;
+
+        // Act
+        service.SetCollapsed("{group}", true);
+        service.SetCollapsed("{GROUP}", true);
+
+        // Assert
+        Assert.True(new DeviceGroupService(preferences).IsCollapsed("{group}"));
+        Assert.Equal(1, changes);
+    }
+
+    [Fact]
+    public void IsEnabled_DefaultsToOn()
+    {
+        // Arrange
+        var service = new DeviceGroupService(new InMemoryPreferencesStore());
+
+        // Act & Assert
+        Assert.True(service.IsEnabled);
+    }
+}
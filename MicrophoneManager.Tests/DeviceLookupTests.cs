@@ -0,0 +1,135 @@
+using MicrophoneManager.Tests.Fakes;
+using MicrophoneManager.WinUI.Models;
+using MicrophoneManager.WinUI.Services;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for resolving microphones by friendly name and by stable identity.
+/// </summary>
+public class DeviceLookupTests
+{
+    private static FakeAudioDeviceService CreateService()
+    {
+        return FakeAudioDeviceService.WithMicrophones(
+            null,
+            new FakeAudioDeviceService.FakeMicrophone("{0.0.1.00000000}.{a}", "Microphone (Blue Yeti)"),
+            new FakeAudioDeviceService.FakeMicrophone("{0.0.1.00000000}.{b}", "Headset Microphone (Jabra Evolve2)"),
+            new FakeAudioDeviceService.FakeMicrophone("{0.0.1.00000000}.{c}", "Microphone"));
+    }
+
+    [Fact]
+    public void FindMicrophone_PrefersExactNameOverSubstring()
+    {
+        // Arrange
+        var fakeService = CreateService();
+
+        // Act
+        var match = fakeService.FindMicrophone("microphone");
+
+        // Assert
+        Assert.Equal("{0.0.1.00000000}.{c}", match?.Id);
+    }
+
+    [Fact]
+    public void FindMicrophone_MatchesCaseInsensitiveSubstring()
+    {
+        // Arrange
+        var fakeService = CreateService();
+
+        // Act
+        var match = fakeService.FindMicrophone("blue yeti");
+
+        // Assert
+        Assert.Equal("{0.0.1.00000000}.{a}", match?.Id);
+    }
+
+    [Fact]
+    public void FindMicrophone_SupportsRegex()
+    {
+        // Arrange
+        var fakeService = CreateService();
+
+        // Act
+        var match = fakeService.FindMicrophone(@"jabra\s+evolve\d", useRegex: true);
+
+        // Assert
+        Assert.Equal("{0.0.1.00000000}.{b}", match?.Id);
+    }
+
+    [Fact]
+    public void FindMicrophone_ReportsInvalidRegexInsteadOfThrowing()
+    {
+        // Arrange
+        var fakeService = CreateService();
+
+        // Act
+        var match = fakeService.FindMicrophone("jabra(", useRegex: true, out var error);
+
+        // Assert
+        Assert.Null(match);
+        Assert.StartsWith("Invalid pattern", error);
+    }
+
+    [Fact]
+    public void FindMicrophone_ReturnsNull_WhenNothingMatches()
+    {
+        // Arrange
+        var fakeService = CreateService();
+
+        // Act & Assert
+        Assert.Null(fakeService.FindMicrophone("Rode NT-USB"));
+    }
+
+    [Fact]
+    public void ResolveStableId_FindsDeviceAfterEndpointIdChanges()
+    {
+        // Arrange - same physical headset before and after a driver reinstall
+        var containerId = Guid.NewGuid();
+        DeviceProperties PropertiesFor(string deviceId) => new()
+        {
+            DeviceId = deviceId,
+            ContainerId = containerId,
+            EnumeratorName = "USB",
+            AdapterName = "Jabra Link 380",
+            EndpointDescription = "Microphone"
+        };
+
+        var before = new FakeAudioDeviceService();
+        before.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("old-id", "Headset") { Properties = PropertiesFor("old-id") });
+        var stableId = before.GetMicrophones().Single().StableId!;
+
+        var after = new FakeAudioDeviceService();
+        after.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("new-id", "Headset") { Properties = PropertiesFor("new-id") });
+
+        // Act
+        var resolved = after.ResolveStableId(stableId);
+
+        // Assert
+        Assert.Equal("new-id", resolved);
+    }
+
+    [Fact]
+    public void StableId_TellsBuiltInEndpointsApartByJack()
+    {
+        // Arrange - a front and rear mic jack on the same onboard codec
+        DeviceProperties PropertiesFor(string deviceId, string location) => new()
+        {
+            DeviceId = deviceId,
+            ContainerId = DeviceIdentity.MachineContainerId,
+            EnumeratorName = "HDAUDIO",
+            AdapterName = "Realtek High Definition Audio",
+            EndpointDescription = "Microphone",
+            Jack = new JackInfo(0xFF80C0, location, "3.5 mm", true)
+        };
+
+        // Act
+        var front = PropertiesFor("mic-1", "Front panel").StableId;
+        var rear = PropertiesFor("mic-2", "Rear panel").StableId;
+
+        // Assert
+        Assert.NotNull(front);
+        Assert.NotEqual(front, rear);
+    }
+}
@@ -0,0 +1,156 @@
+using MicrophoneManager.Tests.Fakes;
+using MicrophoneManager.WinUI.Services;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for pinning microphones and the user's list order.
+/// </summary>
+public class DeviceOrderServiceTests
+{
+    [Fact]
+    public void Sort_PutsPinnedFirstThenUserOrderThenTheRest()
+    {
+        // Arrange
+        var service = new DeviceOrderService(new InMemoryPreferencesStore());
+        service.Move("realtek-2", -1, new[] { "realtek-1", "realtek-2", "yeti", "headset" });
+        service.SetPinned("yeti", true);
+
+        // Act
+        var sorted = service.Sort(new[] { "realtek-1", "realtek-2", "headset", "yeti", "webcam" }, k => k);
+
+        // Assert
+        Assert.Equal(new[] { "yeti", "realtek-2", "realtek-1", "headset", "webcam" }, sorted);
+    }
+
+    [Fact]
+    public void Move_DoesNotCrossBetweenPinnedAndUnpinned()
+    {
+        // Arrange
+        var preferences = new InMemoryPreferencesStore();
+        var service = new DeviceOrderService(preferences);
+        service.SetPinned("yeti", true);
+
+        // Act
+        service.Move("headset", -1, new[] { "yeti", "headset" });
+
+        // Assert
+        Assert.Empty(preferences.GetStringList(DeviceOrderService.DeviceOrderPreference));
+    }
+
+    [Fact]
+    public void Move_KeepsDisconnectedDevicesInTheSavedOrder()
+    {
+        // Arrange
+        var preferences = new InMemoryPreferencesStore();
+        var service = new DeviceOrderService(preferences);
+        service.Move("b", -1, new[] { "a", "b", "usb" });
+        var raised = 0;
+        service.OrderChanged += (s, e) => raised++;
+
+        // Act - the USB mic is unplugged, so it isn't listed
+        service.Move("a", -1, new[] { "b", "a" });
+
+        // Assert
+        Assert.Equal(new[] { "a", "b", "usb" }, preferences.GetStringList(DeviceOrderService.DeviceOrderPreference));
+        Assert.Equal(1, raised);
+    }
+
+    private sealed record Device(string Key, string Name, int DefaultRank = 2);
+
+    private static IReadOnlyList<string> SortForDisplay(DeviceOrderService service, params Device[] devices)
+    {
+        return service.SortForDisplay(devices, d => d.Key, d => d.Name, d => d.DefaultRank).Select(d => d.Key).ToList();
+    }
+
+    [Fact]
+    public void SortForDisplay_ByNameKeepsPinnedFirst()
+    {
+        // Arrange
+        var service = new DeviceOrderService(new InMemoryPreferencesStore()) { SortMode = DeviceSortMode.Name };
+        service.SetPinned("yeti", true);
+
+        // Act
+        var sorted = SortForDisplay(service, new Device("realtek", "Microphone (Realtek)"), new Device("yeti", "Yeti"), new Device("headset", "Headset"));
+
+        // Assert
+        Assert.Equal(new[] { "yeti", "headset", "realtek" }, sorted);
+    }
+
+    [Fact]
+    public void SortForDisplay_DefaultFirstThenCommunicationsThenUserOrder()
+    {
+        // Arrange
+        var service = new DeviceOrderService(new InMemoryPreferencesStore()) { SortMode = DeviceSortMode.DefaultFirst };
+
+        // Act
+        var sorted = SortForDisplay(service, new Device("a", "A"), new Device("b", "B", 1), new Device("c", "C", 0));
+
+        // Assert
+        Assert.Equal(new[] { "c", "b", "a" }, sorted);
+    }
+
+    [Fact]
+    public void SortForDisplay_LastUsedFirst()
+    {
+        // Arrange
+        var now = new DateTime(2026, 10, 17, 9, 0, 0, DateTimeKind.Utc);
+        var preferences = new InMemoryPreferencesStore();
+        var service = new DeviceOrderService(preferences, () => now) { SortMode = DeviceSortMode.LastUsed };
+        service.RecordUsed("headset");
+        now = now.AddHours(1);
+        service.RecordUsed("yeti");
+
+        // Act - a new instance reads the saved times
+        var sorted = SortForDisplay(new DeviceOrderService(preferences), new Device("realtek", "Realtek"), new Device("headset", "Headset"), new Device("yeti", "Yeti"));
+
+        // Assert
+        Assert.Equal(new[] { "yeti", "headset", "realtek" }, sorted);
+    }
+
+    [Fact]
+    public void SortForDisplay_ActivityFadesAfterTheDeviceGoesQuiet()
+    {
+        // Arrange
+        var now = new DateTime(2026, 10, 17, 9, 0, 0, DateTimeKind.Utc);
+        var service = new DeviceOrderService(new InMemoryPreferencesStore(), () => now) { SortMode = DeviceSortMode.Activity };
+        service.RecordLevel("headset", 80);
+        now = now.AddMinutes(2);
+        service.RecordLevel("yeti", 30);
+
+        // Act
+        var sorted = SortForDisplay(service, new Device("headset", "Headset"), new Device("yeti", "Yeti"));
+
+        // Assert - 80 halved four times is 5, below the yeti's 30
+        Assert.Equal(new[] { "yeti", "headset" }, sorted);
+    }
+
+    [Fact]
+    public void Sort_IgnoresSortModeSoCyclingStaysPut()
+    {
+        // Arrange
+        var service = new DeviceOrderService(new InMemoryPreferencesStore()) { SortMode = DeviceSortMode.Name };
+
+        // Act
+        var sorted = service.Sort(new[] { "b", "a" }, k => k);
+
+        // Assert
+        Assert.Equal(new[] { "b", "a" }, sorted);
+    }
+
+    [Theory]
+    [InlineData("LastUsed", DeviceSortMode.LastUsed)]
+    [InlineData("name", DeviceSortMode.Name)]
+    [InlineData("9", DeviceSortMode.Custom)]
+    [InlineData("Random", DeviceSortMode.Custom)]
+    public void SortMode_ParsesStoredValue(string stored, DeviceSortMode expected)
+    {
+        // Arrange
+        var preferences = new InMemoryPreferencesStore();
+        preferences.SetString(DeviceOrderService.SortModePreference, stored);
+
+        // Act & Assert
+        Assert.Equal(expected, new DeviceOrderService(preferences).SortMode);
+    }
+}
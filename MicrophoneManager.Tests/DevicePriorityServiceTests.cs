@@ -0,0 +1,131 @@
+using MicrophoneManager.Tests.Fakes;
+using MicrophoneManager.WinUI.Models;
+using MicrophoneManager.WinUI.Services;
+using NAudio.CoreAudioApi;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for the preferred-microphone list and automatic failover.
+/// </summary>
+public class DevicePriorityServiceTests
+{
+    private const string HeadsetId = "{0.0.1.00000000}.{headset}";
+    private const string UsbId = "{0.0.1.00000000}.{usb}";
+    private const string BuiltInId = "{0.0.1.00000000}.{builtin}";
+
+    private static (FakeAudioDeviceService Service, DevicePriorityService Priority) Create(bool autoFailover = true)
+    {
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone(UsbId, "USB Microphone"));
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone(BuiltInId, "Microphone Array"));
+        fakeService.DefaultConsoleId = BuiltInId;
+        fakeService.DefaultCommunicationsId = BuiltInId;
+
+        var priority = new DevicePriorityService(fakeService, new InMemoryPreferencesStore());
+        priority.Prefer(HeadsetId);
+        priority.Prefer(UsbId);
+        priority.Prefer(BuiltInId);
+        priority.IsAutoFailoverEnabled = autoFailover;
+        return (fakeService, priority);
+    }
+
+    [Fact]
+    public void Move_ReordersAndClampsToListBounds()
+    {
+        // Arrange
+        var (_, priority) = Create();
+
+        // Act
+        priority.Move(BuiltInId, -1);
+        priority.Move(HeadsetId, -5);
+
+        // Assert
+        Assert.Equal(new[] { HeadsetId, BuiltInId, UsbId }, priority.GetPriorityList());
+        Assert.Equal(2, priority.GetRank(BuiltInId));
+    }
+
+    [Fact]
+    public void DefaultRemoved_FailsOverToNextPreferredMicrophone()
+    {
+        // Arrange
+        var (fakeService, _) = Create();
+        fakeService.DefaultConsoleId = UsbId;
+
+        // Act
+        fakeService.RemoveMicrophone(UsbId);
+        fakeService.RaiseDevicesChanged(UsbId, DeviceChangeKind.Removed);
+
+        // Assert
+        Assert.Equal(BuiltInId, fakeService.GetDefaultDeviceId(Role.Console));
+    }
+
+    [Fact]
+    public void HigherPriorityMicrophoneAdded_BecomesDefault()
+    {
+        // Arrange
+        var (fakeService, _) = Create();
+        fakeService.DefaultConsoleId = UsbId;
+
+        // Act
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone(HeadsetId, "Headset Microphone"));
+        fakeService.RaiseDevicesChanged(HeadsetId, DeviceChangeKind.Added);
+
+        // Assert
+        Assert.Equal(HeadsetId, fakeService.GetDefaultDeviceId(Role.Console));
+        Assert.Equal(HeadsetId, fakeService.GetDefaultDeviceId(Role.Communications));
+    }
+
+    [Fact]
+    public void AutoFailoverDisabled_LeavesDefaultAlone()
+    {
+        // Arrange
+        var (fakeService, _) = Create(autoFailover: false);
+
+        // Act
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone(HeadsetId, "Headset Microphone"));
+        fakeService.RaiseDevicesChanged(HeadsetId, DeviceChangeKind.Added);
+
+        // Assert
+        Assert.Equal(BuiltInId, fakeService.GetDefaultDeviceId(Role.Console));
+    }
+
+    [Fact]
+    public void ManualDefaultChange_IsNotOverridden()
+    {
+        // Arrange
+        var (fakeService, _) = Create();
+        fakeService.DefaultConsoleId = UsbId;
+        fakeService.SetDefaultMicrophone(BuiltInId);
+        fakeService.RaiseDefaultDeviceChanged();
+
+        // Act
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("{0.0.1.00000000}.{cable}", "Virtual Cable"));
+        fakeService.RaiseDevicesChanged("{0.0.1.00000000}.{cable}", DeviceChangeKind.Added);
+        fakeService.RaiseDevicesChanged(UsbId, DeviceChangeKind.StateChanged);
+
+        // Assert
+        Assert.Equal(BuiltInId, fakeService.GetDefaultDeviceId(Role.Console));
+    }
+
+    [Fact]
+    public void DefaultRemovedAfterWindowsPickedAnother_StillFailsOver()
+    {
+        // Arrange
+        var (fakeService, _) = Create();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("{0.0.1.00000000}.{cable}", "Virtual Cable"));
+        fakeService.RaiseDevicesChanged("{0.0.1.00000000}.{cable}", DeviceChangeKind.Added);
+        fakeService.SetDefaultMicrophone(UsbId);
+        fakeService.RaiseDefaultDeviceChanged();
+
+        // Act
+        fakeService.RemoveMicrophone(UsbId);
+        fakeService.SetDefaultMicrophone("{0.0.1.00000000}.{cable}");
+        fakeService.RaiseDefaultDeviceChanged();
+        fakeService.RaiseDevicesChanged(UsbId, DeviceChangeKind.Removed);
+
+        // Assert
+        Assert.Equal(BuiltInId, fakeService.GetDefaultDeviceId(Role.Console));
+    }
+}
@@ -7,16 +7,44 @@ namespace MicrophoneManager.Tests.Fakes;
 public class FakeAudioDeviceService : IAudioDeviceService
 {
     private readonly Dictionary<string, FakeMicrophone> _microphones = new();
+    private readonly Dictionary<string, FakeMicrophone> _outputs = new();
 
     public string? DefaultConsoleId { get; set; }
     public string? DefaultCommunicationsId { get; set; }
+    public string? DefaultOutputId { get; set; }
+    public Dictionary<string, List<CaptureSession>> CaptureSessions { get; } = new();
+    public MicTestStatus MicTestResult { get; set; } = MicTestStatus.Completed;
+    public List<string> MicTestDeviceIds { get; } = new();
+    public List<string> MicTestReplayDeviceIds { get; } = new();
+    public double[] MicTestWaveform { get; set; } = Array.Empty<double>();
+    public Dictionary<string, string> ActiveRecordings { get; } = new();
+
+    /// <summary>
+    /// A service holding <paramref name="microphones"/>, with <paramref name="defaultId"/> as the
+    /// default for both roles (or no default when null).
+    /// </summary>
+    public static FakeAudioDeviceService WithMicrophones(string? defaultId, params FakeMicrophone[] microphones)
+    {
+        var fakeService = new FakeAudioDeviceService();
+        foreach (var microphone in microphones)
+        {
+            fakeService.AddOrUpdateMicrophone(microphone);
+        }
+
+        fakeService.DefaultConsoleId = defaultId;
+        fakeService.DefaultCommunicationsId = defaultId;
+        return fakeService;
+    }
 
     public event EventHandler? DevicesChanged;
     public event EventHandler? DefaultDeviceChanged;
+    public event EventHandler? OutputDevicesChanged;
     public event EventHandler<AudioDeviceService.DefaultMicrophoneVolumeChangedEventArgs>? DefaultMicrophoneVolumeChanged;
     public event EventHandler<AudioDeviceService.MicrophoneVolumeChangedEventArgs>? MicrophoneVolumeChanged;
     public event EventHandler<AudioDeviceService.MicrophoneInputLevelChangedEventArgs>? MicrophoneInputLevelChanged;
     public event EventHandler<AudioDeviceService.MicrophoneFormatChangedEventArgs>? MicrophoneFormatChanged;
+    public event EventHandler<AudioDeviceService.AudioOperationFailedEventArgs>? OperationFailed;
+    public event EventHandler<AudioDeviceService.CaptureSessionChangedEventArgs>? CaptureSessionChanged;
 
     public void AddOrUpdateMicrophone(FakeMicrophone microphone)
     {
@@ -30,11 +58,30 @@ public class FakeAudioDeviceService : IAudioDeviceService
 
     public List<MicrophoneDevice> GetMicrophones()
     {
+        return GetMicrophonesWithStates(DeviceState.Active);
+    }
+
+    /// <summary>
+    /// Number of full enumerations, so tests can check that single-device changes avoid them.
+    /// </summary>
+    public int EnumerationCount { get; private set; }
+
+    public List<MicrophoneDevice> GetMicrophonesWithStates(DeviceState states)
+    {
+        EnumerationCount++;
         return _microphones.Values
-            .Select(m => m.ToSnapshot(m.Id == DefaultConsoleId, m.Id == DefaultCommunicationsId))
+            .Where(m => (m.State & states) != 0)
+            .Select(m => m.ToSnapshot(m.Id == DefaultConsoleId, m.Id == DefaultCommunicationsId, IsDeviceInUse(m.Id)))
             .ToList();
     }
 
+    public MicrophoneDevice? GetMicrophone(string deviceId)
+    {
+        return _microphones.TryGetValue(deviceId, out var mic)
+            ? mic.ToSnapshot(mic.Id == DefaultConsoleId, mic.Id == DefaultCommunicationsId, IsDeviceInUse(mic.Id))
+            : null;
+    }
+
     public string? GetDefaultDeviceId(Role role)
     {
         return role == Role.Console ? DefaultConsoleId : DefaultCommunicationsId;
@@ -45,6 +92,26 @@ public class FakeAudioDeviceService : IAudioDeviceService
         return GetMicrophones().FirstOrDefault(m => m.Id == DefaultConsoleId);
     }
 
+    /// <summary>
+    /// Time used to age <see cref="LastRequestedDefaultId"/> out, like the real service does.
+    /// </summary>
+    public DateTime Now { get; set; } = new(2026, 1, 1, 9, 0, 0, DateTimeKind.Utc);
+
+    private (string Id, DateTime At)? _requestedDefault;
+
+    public string? LastRequestedDefaultId =>
+        _requestedDefault is { } requested && Now - requested.At <= AudioDeviceService.RequestedDefaultWindow ? requested.Id : null;
+
+    /// <summary>
+    /// Changes the default the way another application would, without going through this service.
+    /// </summary>
+    public void SimulateExternalDefaultChange(string deviceId)
+    {
+        DefaultConsoleId = deviceId;
+        DefaultCommunicationsId = deviceId;
+        DefaultDeviceChanged?.Invoke(this, EventArgs.Empty);
+    }
+
     public bool SetDefaultMicrophone(string deviceId)
     {
         var consoleSuccess = SetMicrophoneForRole(deviceId, Role.Console);
@@ -59,6 +126,7 @@ public class FakeAudioDeviceService : IAudioDeviceService
         if (role == Role.Console)
         {
             DefaultConsoleId = deviceId;
+            _requestedDefault = (deviceId, Now);
         }
         else if (role == Role.Communications)
         {
@@ -91,6 +159,27 @@ public class FakeAudioDeviceService : IAudioDeviceService
         }
     }
 
+    // The fake uses a linear taper across VolumeRange, the same estimate VolumeRange.DbAt makes
+    public float? GetVolumeDb(string deviceId)
+    {
+        return _microphones.TryGetValue(deviceId, out var mic) ? mic.VolumeDb : null;
+    }
+
+    public VolumeRange? GetVolumeRange(string deviceId)
+    {
+        return _microphones.TryGetValue(deviceId, out var mic) ? mic.VolumeRange : null;
+    }
+
+    public bool SetVolumeDb(string deviceId, float volumeDb)
+    {
+        if (!_microphones.TryGetValue(deviceId, out var mic)) return false;
+
+        var range = mic.VolumeRange;
+        var clamped = Math.Clamp(volumeDb, range.MinDb, range.MaxDb);
+        mic.VolumeScalar = (clamped - range.MinDb) / (range.MaxDb - range.MinDb);
+        return true;
+    }
+
     public bool ToggleMute(string deviceId)
     {
         if (!_microphones.TryGetValue(deviceId, out var mic)) return false;
@@ -104,6 +193,31 @@ public class FakeAudioDeviceService : IAudioDeviceService
         return _microphones.TryGetValue(deviceId, out var mic) && mic.IsMuted;
     }
 
+    public bool SetMute(string deviceId, bool muted)
+    {
+        if (!_microphones.TryGetValue(deviceId, out var mic)) return false;
+
+        mic.IsMuted = muted;
+        return true;
+    }
+
+    public int SetAllMicrophonesMuted(bool muted)
+    {
+        var active = _microphones.Values.Where(m => m.State == DeviceState.Active).ToList();
+        foreach (var mic in active)
+        {
+            mic.IsMuted = muted;
+        }
+
+        return active.Count;
+    }
+
+    public bool AreAllMicrophonesMuted()
+    {
+        var active = _microphones.Values.Where(m => m.State == DeviceState.Active).ToList();
+        return active.Count > 0 && active.All(m => m.IsMuted);
+    }
+
     public bool ToggleDefaultMicrophoneMute()
     {
         var defaultId = DefaultConsoleId;
@@ -128,11 +242,303 @@ public class FakeAudioDeviceService : IAudioDeviceService
         return IsMuted(defaultId);
     }
 
+    public MicrophonePrivacyStatus PrivacyStatus { get; set; } = MicrophonePrivacyStatus.Allowed;
+
+    public MicrophonePrivacyStatus GetPrivacyStatus()
+    {
+        return PrivacyStatus;
+    }
+
+    public DeviceProperties? GetDeviceProperties(string deviceId)
+    {
+        return _microphones.TryGetValue(deviceId, out var mic) ? mic.Properties : null;
+    }
+
+    public ListenSettings? GetListenSettings(string deviceId)
+    {
+        return _microphones.TryGetValue(deviceId, out var mic) ? mic.Listen : null;
+    }
+
+    public Task<bool> SetListenAsync(string deviceId, bool enabled, string? targetRenderId = null, CancellationToken cancellationToken = default)
+    {
+        if (!_microphones.TryGetValue(deviceId, out var mic)) return Task.FromResult(false);
+
+        mic.Listen = new ListenSettings(enabled, targetRenderId);
+        return Task.FromResult(true);
+    }
+
+    public DeviceFormat? GetDeviceDefaultFormat(string deviceId)
+    {
+        return _microphones.TryGetValue(deviceId, out var mic) ? mic.DefaultFormat : null;
+    }
+
+    public Task<List<DeviceFormat>> GetSupportedFormatsAsync(string deviceId, CancellationToken cancellationToken = default)
+    {
+        return Task.FromResult(_microphones.TryGetValue(deviceId, out var mic)
+            ? new List<DeviceFormat>(mic.SupportedFormats)
+            : new List<DeviceFormat>());
+    }
+
+    public Task<bool> SetDeviceDefaultFormatAsync(string deviceId, DeviceFormat format, CancellationToken cancellationToken = default)
+    {
+        if (!_microphones.TryGetValue(deviceId, out var mic)) return Task.FromResult(false);
+
+        mic.DefaultFormat = format;
+        mic.FormatTag = format.ToString();
+        return Task.FromResult(true);
+    }
+
+    public MicrophoneBoost? GetMicrophoneBoost(string deviceId)
+    {
+        return _microphones.TryGetValue(deviceId, out var mic) ? mic.Boost : null;
+    }
+
+    public bool SetMicrophoneBoost(string deviceId, float levelDb)
+    {
+        if (!_microphones.TryGetValue(deviceId, out var mic) || mic.Boost == null) return false;
+
+        mic.Boost = mic.Boost with { LevelDb = Math.Clamp(levelDb, mic.Boost.MinDb, mic.Boost.MaxDb) };
+        return true;
+    }
+
+    public bool? GetAutoGainControl(string deviceId)
+    {
+        return _microphones.TryGetValue(deviceId, out var mic) ? mic.AutoGainEnabled : null;
+    }
+
+    public DeviceCapabilities GetCapabilities(string deviceId)
+    {
+        if (!_microphones.TryGetValue(deviceId, out var mic)) return DeviceCapabilities.None;
+
+        var capabilities = DeviceCapabilities.CaptureSessions;
+        if (mic.Boost != null) capabilities |= DeviceCapabilities.Boost;
+        if (mic.AutoGainEnabled != null) capabilities |= DeviceCapabilities.AutoGainControl;
+        if (mic.AudioEffects.Count > 0) capabilities |= DeviceCapabilities.AudioEffects;
+        if (mic.State == DeviceState.Active) capabilities |= DeviceCapabilities.Recording;
+        if (_outputs.Count > 0) capabilities |= DeviceCapabilities.RenderDevices;
+        return capabilities;
+    }
+
+    public List<AudioEffect> GetAudioEffects(string deviceId)
+    {
+        return _microphones.TryGetValue(deviceId, out var mic) ? mic.AudioEffects.ToList() : new List<AudioEffect>();
+    }
+
+    public bool SetAudioEffectEnabled(string deviceId, Guid effectId, bool enabled)
+    {
+        if (!_microphones.TryGetValue(deviceId, out var mic)) return false;
+
+        var index = mic.AudioEffects.FindIndex(e => e.Id == effectId);
+        if (index < 0 || !mic.AudioEffects[index].CanToggle) return false;
+
+        mic.AudioEffects[index] = mic.AudioEffects[index] with { IsEnabled = enabled };
+        return true;
+    }
+
+    public bool SetAutoGainControl(string deviceId, bool enabled)
+    {
+        if (!_microphones.TryGetValue(deviceId, out var mic) || mic.AutoGainEnabled == null) return false;
+
+        mic.AutoGainEnabled = enabled;
+        return true;
+    }
+
+    public Task<MicTestStatus> RunMicTestAsync(string deviceId, TimeSpan duration, IProgress<MicTestStatus>? progress = null, CancellationToken cancellationToken = default)
+    {
+        MicTestDeviceIds.Add(deviceId);
+        progress?.Report(MicTestStatus.Recording);
+        progress?.Report(MicTestStatus.PlayingBack);
+        progress?.Report(MicTestResult);
+        return Task.FromResult(MicTestResult);
+    }
+
+    public Task<MicTestStatus> ReplayMicTestAsync(string deviceId, IProgress<MicTestStatus>? progress = null, CancellationToken cancellationToken = default)
+    {
+        if (!MicTestDeviceIds.Contains(deviceId)) return Task.FromResult(MicTestStatus.Failed);
+
+        MicTestReplayDeviceIds.Add(deviceId);
+        progress?.Report(MicTestStatus.PlayingBack);
+        progress?.Report(MicTestResult);
+        return Task.FromResult(MicTestResult);
+    }
+
+    public double[] GetMicTestWaveform(string deviceId)
+    {
+        return MicTestDeviceIds.Contains(deviceId) ? MicTestWaveform : Array.Empty<double>();
+    }
+
+    public bool StartRecording(string deviceId, string filePath, int bitsPerSample = 16)
+    {
+        if (ActiveRecordings.ContainsKey(deviceId)) return false;
+
+        ActiveRecordings[deviceId] = filePath;
+        return true;
+    }
+
+    public Task<RecordingResult?> StopRecordingAsync(string deviceId)
+    {
+        if (!ActiveRecordings.Remove(deviceId, out var filePath)) return Task.FromResult<RecordingResult?>(null);
+
+        return Task.FromResult<RecordingResult?>(new RecordingResult(deviceId, filePath, TimeSpan.FromSeconds(12)));
+    }
+
+    public bool IsRecording(string deviceId) => ActiveRecordings.ContainsKey(deviceId);
+
+    public List<CaptureSession> GetCaptureSessions(string deviceId)
+    {
+        return CaptureSessions.TryGetValue(deviceId, out var sessions)
+            ? sessions.Where(s => !AudioDeviceService.IsOwnSession(s.ProcessId)).ToList()
+            : new List<CaptureSession>();
+    }
+
+    public bool IsDeviceExclusivelyLocked(string deviceId)
+    {
+        return _microphones.TryGetValue(deviceId, out var mic) && mic.IsExclusivelyLocked;
+    }
+
+    public bool IsDeviceInUse(string deviceId)
+    {
+        return CaptureSessions.TryGetValue(deviceId, out var sessions) && sessions.Any(s => s.IsActive && !AudioDeviceService.IsOwnSession(s.ProcessId));
+    }
+
+    public bool SetCaptureSessionMute(string deviceId, string sessionInstanceId, bool muted)
+    {
+        return ReplaceCaptureSession(deviceId, sessionInstanceId, s => new CaptureSession
+        {
+            SessionInstanceId = s.SessionInstanceId,
+            DeviceId = s.DeviceId,
+            ProcessId = s.ProcessId,
+            ProcessName = s.ProcessName,
+            DisplayName = s.DisplayName,
+            IsActive = s.IsActive,
+            IsMuted = muted,
+            Volume = s.Volume
+        });
+    }
+
+    public bool SetCaptureSessionVolume(string deviceId, string sessionInstanceId, float volume)
+    {
+        return ReplaceCaptureSession(deviceId, sessionInstanceId, s => new CaptureSession
+        {
+            SessionInstanceId = s.SessionInstanceId,
+            DeviceId = s.DeviceId,
+            ProcessId = s.ProcessId,
+            ProcessName = s.ProcessName,
+            DisplayName = s.DisplayName,
+            IsActive = s.IsActive,
+            IsMuted = s.IsMuted,
+            Volume = Math.Clamp(volume, 0.0f, 1.0f)
+        });
+    }
+
+    private bool ReplaceCaptureSession(string deviceId, string sessionInstanceId, Func<CaptureSession, CaptureSession> update)
+    {
+        if (!CaptureSessions.TryGetValue(deviceId, out var sessions)) return false;
+
+        var index = sessions.FindIndex(s => s.SessionInstanceId == sessionInstanceId);
+        if (index < 0) return false;
+
+        sessions[index] = update(sessions[index]);
+        return true;
+    }
+
+    public void AddOrUpdateOutputDevice(FakeMicrophone output)
+    {
+        _outputs[output.Id] = output;
+    }
+
+    public List<OutputDevice> GetOutputDevices()
+    {
+        return _outputs.Values
+            .Select(o => new OutputDevice
+            {
+                Id = o.Id,
+                Name = o.Name,
+                IsDefault = o.Id == DefaultOutputId,
+                IsDefaultCommunication = o.Id == DefaultOutputId,
+                IsMuted = o.IsMuted,
+                VolumeLevel = (float)o.VolumeScalar,
+                PeakLevelPercent = o.InputLevelPercent
+            })
+            .ToList();
+    }
+
+    public string? GetDefaultOutputDeviceId(Role role)
+    {
+        return DefaultOutputId;
+    }
+
+    public void SetOutputVolumeLevelScalar(string deviceId, float volumeLevelScalar)
+    {
+        if (_outputs.TryGetValue(deviceId, out var output))
+        {
+            output.VolumeScalar = Math.Clamp(volumeLevelScalar, 0.0f, 1.0f);
+        }
+    }
+
+    public bool ToggleOutputMute(string deviceId)
+    {
+        if (!_outputs.TryGetValue(deviceId, out var output)) return false;
+
+        output.IsMuted = !output.IsMuted;
+        return output.IsMuted;
+    }
+
+    public double GetInputPeakDb(string deviceId)
+    {
+        return _microphones.TryGetValue(deviceId, out var microphone) ? microphone.InputPeakDb : -96;
+    }
+
+    public double GetInputRmsDb(string deviceId)
+    {
+        return _microphones.TryGetValue(deviceId, out var microphone) ? microphone.InputRmsDb : -96;
+    }
+
+    public double[] GetInputChannelPeaksDb(string deviceId)
+    {
+        return _microphones.TryGetValue(deviceId, out var microphone) ? microphone.ChannelPeaksDb : Array.Empty<double>();
+    }
+
+    public double GetOutputPeakLevel(string deviceId)
+    {
+        return _outputs.TryGetValue(deviceId, out var output) ? output.InputLevelPercent : 0;
+    }
+
+    public Task<bool> SetDefaultOutputDeviceAsync(string deviceId, CancellationToken cancellationToken = default)
+    {
+        if (!_outputs.ContainsKey(deviceId)) return Task.FromResult(false);
+
+        DefaultOutputId = deviceId;
+        OutputDevicesChanged?.Invoke(this, EventArgs.Empty);
+        return Task.FromResult(true);
+    }
+
     public void RaiseDevicesChanged()
     {
         DevicesChanged?.Invoke(this, EventArgs.Empty);
     }
 
+    public void RaiseDevicesChanged(string deviceId, DeviceChangeKind kind)
+    {
+        RaiseDevicesChanged(new DeviceChange(deviceId, kind));
+    }
+
+    public void RaiseDevicesChanged(params DeviceChange[] changes)
+    {
+        DevicesChanged?.Invoke(this, new AudioDeviceService.DevicesChangedEventArgs(changes));
+    }
+
+    /// <summary>
+    /// Number of <see cref="RecoverAfterResume"/> calls.
+    /// </summary>
+    public int ResumeRecoveryCount { get; private set; }
+
+    public void RecoverAfterResume()
+    {
+        ResumeRecoveryCount++;
+        DevicesChanged?.Invoke(this, EventArgs.Empty);
+    }
+
     public void RaiseDefaultDeviceChanged()
     {
         DefaultDeviceChanged?.Invoke(this, EventArgs.Empty);
@@ -149,18 +555,23 @@ public class FakeAudioDeviceService : IAudioDeviceService
             new AudioDeviceService.MicrophoneVolumeChangedEventArgs(deviceId, volumeLevelScalar, isMuted));
     }
 
-    public void RaiseMicrophoneVolumeChanged(string deviceId, float volumeLevelScalar, bool isMuted)
+    public void RaiseMicrophoneVolumeChanged(string deviceId, float volumeLevelScalar, bool isMuted, bool changedExternally = false)
     {
         MicrophoneVolumeChanged?.Invoke(
             this,
-            new AudioDeviceService.MicrophoneVolumeChangedEventArgs(deviceId, volumeLevelScalar, isMuted));
+            new AudioDeviceService.MicrophoneVolumeChangedEventArgs(
+                deviceId,
+                volumeLevelScalar,
+                isMuted,
+                changedExternally ? Guid.NewGuid() : AudioDeviceService.VolumeEventContext,
+                changedExternally));
     }
 
-    public void RaiseInputLevelChanged(string deviceId, double inputPercent, double inputDbFs)
+    public void RaiseInputLevelChanged(string deviceId, double inputPercent, double inputDbFs, double? rmsDbFs = null)
     {
         MicrophoneInputLevelChanged?.Invoke(
             this,
-            new AudioDeviceService.MicrophoneInputLevelChangedEventArgs(deviceId, inputPercent, inputDbFs));
+            new AudioDeviceService.MicrophoneInputLevelChangedEventArgs(deviceId, inputPercent, inputDbFs, rmsDbFs));
     }
 
     public void RaiseFormatChanged(string deviceId, string formatTag)
@@ -170,6 +581,25 @@ public class FakeAudioDeviceService : IAudioDeviceService
             new AudioDeviceService.MicrophoneFormatChangedEventArgs(deviceId, formatTag));
     }
 
+    public void RaiseCaptureSessionChanged(string deviceId, string sessionInstanceId, uint processId, CaptureSessionChangeKind kind)
+    {
+        CaptureSessionChanged?.Invoke(
+            this,
+            new AudioDeviceService.CaptureSessionChangedEventArgs(deviceId, sessionInstanceId, processId, kind));
+    }
+
+    public AudioDeviceService.AudioOperationFailedEventArgs? LastError { get; private set; }
+
+    public void RaiseOperationFailed(string operation, string? deviceId, int hResult, string message, string? deviceName = null)
+    {
+        LastError = new AudioDeviceService.AudioOperationFailedEventArgs(operation, deviceId, hResult, message)
+        {
+            DeviceName = deviceName,
+            ExceptionType = "COMException"
+        };
+        OperationFailed?.Invoke(this, LastError);
+    }
+
     // Async methods - in tests, these just wrap synchronous versions
     public Task<List<MicrophoneDevice>> GetMicrophonesAsync(CancellationToken cancellationToken = default)
     {
@@ -201,6 +631,14 @@ public class FakeAudioDeviceService : IAudioDeviceService
         return Task.FromResult(ToggleDefaultMicrophoneMute());
     }
 
+    public Task<bool> SetDeviceEnabledAsync(string deviceId, bool enabled, CancellationToken cancellationToken = default)
+    {
+        if (!_microphones.TryGetValue(deviceId, out var mic)) return Task.FromResult(false);
+
+        mic.State = enabled ? DeviceState.Active : DeviceState.Disabled;
+        return Task.FromResult(true);
+    }
+
     public void Dispose()
     {
     }
@@ -217,10 +655,26 @@ public class FakeAudioDeviceService : IAudioDeviceService
         public string Name { get; }
         public bool IsMuted { get; set; }
         public double VolumeScalar { get; set; } = 1.0;
+        public VolumeRange VolumeRange { get; set; } = new(-65.25f, 0f, 0.03125f);
+        public float VolumeDb => (float)(VolumeRange.MinDb + VolumeScalar * (VolumeRange.MaxDb - VolumeRange.MinDb));
         public string FormatTag { get; set; } = "48 kHz 24-bit Stereo";
         public double InputLevelPercent { get; set; }
-
-        public MicrophoneDevice ToSnapshot(bool isDefault, bool isDefaultCommunication)
+        public double InputPeakDb { get; set; } = -96;
+
+        public double InputRmsDb { get; set; } = -96;
+
+        public double[] ChannelPeaksDb { get; set; } = Array.Empty<double>();
+        public MicrophoneBoost? Boost { get; set; }
+        public bool? AutoGainEnabled { get; set; }
+        public List<AudioEffect> AudioEffects { get; set; } = new();
+        public DeviceState State { get; set; } = DeviceState.Active;
+        public DeviceProperties? Properties { get; set; }
+        public ListenSettings Listen { get; set; } = new(false, null);
+        public DeviceFormat? DefaultFormat { get; set; } = new(48000, 24, 2);
+        public List<DeviceFormat> SupportedFormats { get; set; } = new();
+        public bool IsExclusivelyLocked { get; set; }
+
+        public MicrophoneDevice ToSnapshot(bool isDefault, bool isDefaultCommunication, bool isInUse = false)
         {
             return new MicrophoneDevice
             {
@@ -230,8 +684,16 @@ public class FakeAudioDeviceService : IAudioDeviceService
                 IsDefault = isDefault,
                 IsDefaultCommunication = isDefaultCommunication,
                 VolumeLevel = (float)VolumeScalar,
+                VolumeDb = VolumeDb,
+                VolumeRange = VolumeRange,
                 FormatTag = FormatTag,
-                InputLevelPercent = InputLevelPercent
+                InputLevelPercent = InputLevelPercent,
+                Boost = Boost,
+                AutoGainEnabled = AutoGainEnabled,
+                IsInUse = isInUse,
+                StableId = Properties?.StableId,
+                ContainerId = Properties?.ContainerId,
+                State = State
             };
         }
     }
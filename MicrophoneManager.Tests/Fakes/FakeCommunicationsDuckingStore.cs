@@ -0,0 +1,23 @@
+using MicrophoneManager.WinUI.Services;
+
+namespace MicrophoneManager.Tests.Fakes;
+
+/// <summary>
+/// Ducking preference kept in memory instead of the registry.
+/// </summary>
+public class FakeCommunicationsDuckingStore : ICommunicationsDuckingStore
+{
+    public int? Value { get; set; }
+
+    /// <summary>
+    /// When true, writes are ignored, as when registry access is restricted.
+    /// </summary>
+    public bool IsReadOnly { get; set; }
+
+    public int? Read() => Value;
+
+    public void Write(int value)
+    {
+        if (!IsReadOnly) Value = value;
+    }
+}
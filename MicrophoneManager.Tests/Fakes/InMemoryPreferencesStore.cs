@@ -0,0 +1,34 @@
+using MicrophoneManager.WinUI.Services;
+
+namespace MicrophoneManager.Tests.Fakes;
+
+public class InMemoryPreferencesStore : IPreferencesStore
+{
+    public Dictionary<string, object> Values { get; } = new();
+
+    public string? GetString(string name) => Values.TryGetValue(name, out var value) ? value as string : null;
+
+    public void SetString(string name, string? value)
+    {
+        if (value == null)
+        {
+            Values.Remove(name);
+        }
+        else
+        {
+            Values[name] = value;
+        }
+    }
+
+    public IReadOnlyList<string> GetStringList(string name) =>
+        Values.TryGetValue(name, out var value) && value is string[] list ? list : Array.Empty<string>();
+
+    public void SetStringList(string name, IReadOnlyList<string> values) => Values[name] = values.ToArray();
+
+    public bool GetBool(string name, bool defaultValue) =>
+        Values.TryGetValue(name, out var value) && value is bool flag ? flag : defaultValue;
+
+    public void SetBool(string name, bool value) => Values[name] = value;
+
+    public IReadOnlyDictionary<string, object> GetAll() => new Dictionary<string, object>(Values);
+}
@@ -0,0 +1,52 @@
+using MicrophoneManager.Tests.Fakes;
+using MicrophoneManager.WinUI.Models;
+using MicrophoneManager.WinUI.Services;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for hiding microphones from the app's lists.
+/// </summary>
+public class HiddenDeviceServiceTests
+{
+    [Fact]
+    public void SetHidden_AddsAndRemovesOnce()
+    {
+        // Arrange
+        var preferences = new InMemoryPreferencesStore();
+        var service = new HiddenDeviceService(preferences);
+        var raised = 0;
+        service.HiddenDevicesChanged += (s, e) => raised++;
+
+        // Act
+        service.SetHidden("mic-1", true);
+        service.SetHidden("MIC-1", true);
+        var hiddenAfterAdd = service.IsHidden("mic-1");
+        service.SetHidden("mic-1", false);
+
+        // Assert
+        Assert.True(hiddenAfterAdd);
+        Assert.False(service.IsHidden("mic-1"));
+        Assert.Empty(preferences.GetStringList(HiddenDeviceService.HiddenDevicesPreference));
+        Assert.Equal(2, raised);
+    }
+
+    [Fact]
+    public void ShouldShow_ListsHiddenDevicesOnlyWhenShowHiddenIsOn()
+    {
+        // Arrange
+        var service = new HiddenDeviceService(new InMemoryPreferencesStore());
+        var cable = new MicrophoneDevice { Id = "mic-2", Name = "CABLE Output", StableId = "{cable}|vb|cable|output" };
+        service.SetHidden("{cable}|vb|cable|output", true);
+
+        // Act
+        var shownNormally = service.ShouldShow(cable);
+        service.ShowHidden = true;
+
+        // Assert
+        Assert.False(shownNormally);
+        Assert.True(service.ShouldShow(cable));
+        Assert.True(service.IsHidden(cable));
+    }
+}
@@ -0,0 +1,190 @@
+using System.Diagnostics;
+using MicrophoneManager.Tests.Fakes;
+using MicrophoneManager.WinUI.Models;
+using MicrophoneManager.WinUI.Services;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for which audio events start a hook and how its command is built. No processes are started.
+/// </summary>
+public class HookServiceTests
+{
+    private static FakeAudioDeviceService CreateService()
+    {
+        return FakeAudioDeviceService.WithMicrophones(
+            null,
+            new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic"),
+            new FakeAudioDeviceService.FakeMicrophone("mic-2", "Blue Yeti"));
+    }
+
+    private static HookService CreateHooks(FakeAudioDeviceService fakeService, InMemoryPreferencesStore preferences, List<ProcessStartInfo> started)
+    {
+        return new HookService(fakeService, preferences, (startInfo, timeout) =>
+        {
+            started.Add(startInfo);
+            return Task.CompletedTask;
+        });
+    }
+
+    [Fact]
+    public void Mute_RunsOnMuteHookWithDeviceInEnvironment()
+    {
+        // Arrange
+        var fakeService = CreateService();
+        var preferences = new InMemoryPreferencesStore();
+        preferences.SetString(HookService.OnMutePreference, "light.exe --off");
+        var started = new List<ProcessStartInfo>();
+        using var hooks = CreateHooks(fakeService, preferences, started);
+
+        // Act
+        fakeService.RaiseMicrophoneVolumeChanged("mic-2", 1f, isMuted: true, changedExternally: true);
+
+        // Assert
+        var startInfo = Assert.Single(started);
+        Assert.Equal("/d /c light.exe --off", startInfo.Arguments);
+        Assert.Equal("mute", startInfo.Environment["MICMANAGER_EVENT"]);
+        Assert.Equal("mic-2", startInfo.Environment["MICMANAGER_DEVICE_ID"]);
+        Assert.Equal("Blue Yeti", startInfo.Environment["MICMANAGER_DEVICE_NAME"]);
+    }
+
+    [Fact]
+    public void VolumeChangeWithoutMuteFlip_RunsNothing()
+    {
+        // Arrange
+        var fakeService = CreateService();
+        var preferences = new InMemoryPreferencesStore();
+        preferences.SetString(HookService.OnMutePreference, "echo muted");
+        preferences.SetString(HookService.OnUnmutePreference, "echo unmuted");
+        var started = new List<ProcessStartInfo>();
+        using var hooks = CreateHooks(fakeService, preferences, started);
+
+        // Act
+        fakeService.RaiseMicrophoneVolumeChanged("mic-1", 0.4f, isMuted: false);
+
+        // Assert
+        Assert.Empty(started);
+    }
+
+    [Fact]
+    public void Unmute_RunsOnlyOnceItWasMuted()
+    {
+        // Arrange
+        var fakeService = CreateService();
+        var preferences = new InMemoryPreferencesStore();
+        preferences.SetString(HookService.OnUnmutePreference, "echo {event}");
+        var started = new List<ProcessStartInfo>();
+        using var hooks = CreateHooks(fakeService, preferences, started);
+
+        // Act
+        fakeService.RaiseMicrophoneVolumeChanged("mic-1", 1f, isMuted: true);
+        fakeService.RaiseMicrophoneVolumeChanged("mic-1", 1f, isMuted: false);
+
+        // Assert
+        Assert.EndsWith("echo unmute", Assert.Single(started).Arguments);
+    }
+
+    [Fact]
+    public void FirstReadingOfNewDevice_RunsNothing()
+    {
+        // Arrange
+        var fakeService = CreateService();
+        var preferences = new InMemoryPreferencesStore();
+        preferences.SetString(HookService.OnMutePreference, "echo muted");
+        preferences.SetString(HookService.OnUnmutePreference, "echo unmuted");
+        var started = new List<ProcessStartInfo>();
+        using var hooks = CreateHooks(fakeService, preferences, started);
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-3", "USB Mic") { IsMuted = true });
+
+        // Act
+        fakeService.RaiseMicrophoneVolumeChanged("mic-3", 1f, isMuted: true);
+        var afterFirstReading = started.Count;
+        fakeService.RaiseMicrophoneVolumeChanged("mic-3", 1f, isMuted: false);
+
+        // Assert
+        Assert.Equal(0, afterFirstReading);
+        Assert.EndsWith("echo unmuted", Assert.Single(started).Arguments);
+    }
+
+    [Fact]
+    public void DeviceUnplugged_RunsRemovedHookWithItsOldName()
+    {
+        // Arrange
+        var fakeService = CreateService();
+        var preferences = new InMemoryPreferencesStore();
+        preferences.SetString(HookService.OnDeviceRemovedPreference, "notify-removed.cmd");
+        var started = new List<ProcessStartInfo>();
+        using var hooks = CreateHooks(fakeService, preferences, started);
+
+        // Act
+        fakeService.RemoveMicrophone("mic-2");
+        fakeService.RaiseDevicesChanged("mic-2", DeviceChangeKind.StateChanged);
+
+        // Assert
+        Assert.Equal("Blue Yeti", Assert.Single(started).Environment["MICMANAGER_DEVICE_NAME"]);
+    }
+
+    [Fact]
+    public void RepeatedClipping_RunsOnClippingHook()
+    {
+        // Arrange
+        var fakeService = CreateService();
+        fakeService.DefaultConsoleId = "mic-2";
+        var preferences = new InMemoryPreferencesStore();
+        preferences.SetString(HookService.OnClippingPreference, "notify clipping");
+        var started = new List<ProcessStartInfo>();
+        var now = new DateTime(2026, 10, 17, 9, 0, 0, DateTimeKind.Utc);
+        using var clipping = new ClippingService(fakeService, preferences, () => now);
+        using var hooks = new HookService(fakeService, preferences, (startInfo, timeout) =>
+        {
+            started.Add(startInfo);
+            return Task.CompletedTask;
+        }, clipping);
+
+        // Act
+        for (var i = 0; i < 3; i++)
+        {
+            now += TimeSpan.FromSeconds(1);
+            fakeService.RaiseInputLevelChanged("mic-2", 100, 0);
+        }
+
+        // Assert
+        var startInfo = Assert.Single(started);
+        Assert.Equal("clipping", startInfo.Environment["MICMANAGER_EVENT"]);
+        Assert.Equal("Blue Yeti", startInfo.Environment["MICMANAGER_DEVICE_NAME"]);
+    }
+
+    [Fact]
+    public void HostileDeviceName_NeverReachesCommandLine()
+    {
+        // Arrange
+        const string hostile = "Mic\" & calc.exe | del /q %USERPROFILE% ^> out";
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", hostile));
+        var preferences = new InMemoryPreferencesStore();
+        preferences.SetString(HookService.OnMutePreference, "say \"{name}\" {id} {event}");
+        var started = new List<ProcessStartInfo>();
+        using var hooks = CreateHooks(fakeService, preferences, started);
+
+        // Act
+        fakeService.RaiseMicrophoneVolumeChanged("mic-1", 1f, isMuted: true);
+
+        // Assert
+        var startInfo = Assert.Single(started);
+        Assert.Equal("/d /c say \"{name}\" {id} mute", startInfo.Arguments);
+        Assert.Equal(hostile, startInfo.Environment["MICMANAGER_DEVICE_NAME"]);
+    }
+
+    [Fact]
+    public void Timeout_FallsBackToDefaultForBadValues()
+    {
+        // Arrange
+        var preferences = new InMemoryPreferencesStore();
+        preferences.SetString(HookService.TimeoutPreference, "-5");
+        using var hooks = new HookService(CreateService(), preferences);
+
+        // Act & Assert
+        Assert.Equal(TimeSpan.FromSeconds(HookService.DefaultTimeoutSeconds), hooks.Timeout);
+    }
+}
@@ -0,0 +1,149 @@
+using MicrophoneManager.Tests.Fakes;
+using MicrophoneManager.WinUI.Models;
+using MicrophoneManager.WinUI.Services;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for hotkey binding text and persistence. Registration itself needs a window and isn't covered.
+/// </summary>
+public class HotkeyServiceTests
+{
+    [Theory]
+    [InlineData("Ctrl+Alt+M", HotkeyModifiers.Control | HotkeyModifiers.Alt, 0x4Du)]
+    [InlineData("Win+Shift+F9", HotkeyModifiers.Windows | HotkeyModifiers.Shift, 0x78u)]
+    [InlineData("Ctrl+Alt+Up", HotkeyModifiers.Control | HotkeyModifiers.Alt, 0x26u)]
+    [InlineData("Pause", HotkeyModifiers.None, 0x13u)]
+    public void HotkeyBinding_RoundTripsThroughText(string text, HotkeyModifiers modifiers, uint virtualKey)
+    {
+        // Act
+        var parsed = HotkeyBinding.TryParse(text, out var binding);
+
+        // Assert
+        Assert.True(parsed);
+        Assert.Equal(new HotkeyBinding(modifiers, virtualKey), binding);
+        Assert.Equal(text, binding!.ToString());
+    }
+
+    [Theory]
+    [InlineData("")]
+    [InlineData("Ctrl+Alt")]
+    [InlineData("Ctrl+M+N")]
+    [InlineData("Ctrl+Banana")]
+    public void HotkeyBinding_RejectsInvalidText(string text)
+    {
+        Assert.False(HotkeyBinding.TryParse(text, out _));
+    }
+
+    [Fact]
+    public void SetBinding_PersistsChangesAndKeepsDefaultsForOtherActions()
+    {
+        // Arrange
+        var preferences = new InMemoryPreferencesStore();
+        var service = new HotkeyService(preferences);
+
+        // Act
+        service.SetBinding(HotkeyAction.ToggleMute, new HotkeyBinding(HotkeyModifiers.Control | HotkeyModifiers.Shift, 'K'));
+        service.SetBinding(HotkeyAction.OpenFlyout, null);
+        var reloaded = new HotkeyService(preferences).GetBindings();
+
+        // Assert
+        Assert.Equal("Ctrl+Shift+K", reloaded[HotkeyAction.ToggleMute]?.ToString());
+        Assert.Null(reloaded[HotkeyAction.OpenFlyout]);
+        Assert.Equal(HotkeyService.DefaultBindings[HotkeyAction.VolumeUp], reloaded[HotkeyAction.VolumeUp]);
+    }
+
+    [Fact]
+    public void ResetToDefaults_RestoresEveryDefaultBinding()
+    {
+        // Arrange
+        var service = new HotkeyService(new InMemoryPreferencesStore());
+        service.SetBinding(HotkeyAction.MuteAll, null);
+
+        // Act
+        service.ResetToDefaults();
+
+        // Assert
+        Assert.All(HotkeyService.DefaultBindings, d => Assert.Equal(d.Value, service.GetBindings()[d.Key]));
+    }
+
+    [Fact]
+    public void HotkeyCapture_CollectsModifiersUntilAKeyIsPressed()
+    {
+        // Arrange
+        var capture = new HotkeyCapture();
+
+        // Act
+        capture.KeyDown(0xA2); // Left Ctrl
+        capture.KeyDown(0x12); // Alt
+        var preview = capture.Preview;
+        var complete = capture.KeyDown('K');
+
+        // Assert
+        Assert.Equal("Ctrl+Alt+…", preview);
+        Assert.True(complete);
+        Assert.Equal(new HotkeyBinding(HotkeyModifiers.Control | HotkeyModifiers.Alt, 'K'), capture.Binding);
+        Assert.Equal("Ctrl+Alt+K", capture.Preview);
+    }
+
+    [Fact]
+    public void HotkeyCapture_ForgetsReleasedModifiers()
+    {
+        // Arrange
+        var capture = new HotkeyCapture();
+        capture.KeyDown(0x10); // Shift
+        capture.KeyDown(0x11); // Ctrl
+
+        // Act
+        capture.KeyUp(0x10);
+        capture.KeyDown(0x78); // F9
+
+        // Assert
+        Assert.Equal("Ctrl+F9", capture.Binding?.ToString());
+    }
+
+    [Fact]
+    public void HotkeyCapture_EscapeAloneCancels()
+    {
+        // Arrange
+        var capture = new HotkeyCapture();
+
+        // Act
+        var complete = capture.KeyDown(0x1B);
+
+        // Assert
+        Assert.True(complete);
+        Assert.True(capture.IsCancelled);
+        Assert.Null(capture.Binding);
+    }
+
+    [Theory]
+    [InlineData(HotkeyModifiers.Control | HotkeyModifiers.Alt, 0x4Du, true)]
+    [InlineData(HotkeyModifiers.Windows, 0x4Du, true)]
+    [InlineData(HotkeyModifiers.None, 0x78u, true)]
+    [InlineData(HotkeyModifiers.Shift, 0x4Du, false)]
+    [InlineData(HotkeyModifiers.None, 0x26u, false)]
+    public void HotkeyCapture_ValidateRequiresACommandModifierForTypingKeys(HotkeyModifiers modifiers, uint virtualKey, bool valid)
+    {
+        Assert.Equal(valid, HotkeyCapture.Validate(new HotkeyBinding(modifiers, virtualKey)) == null);
+    }
+
+    [Fact]
+    public void GetConflict_ReportsAnotherActionWithTheSameKeys()
+    {
+        // Arrange
+        var service = new HotkeyService(new InMemoryPreferencesStore());
+        var muteAll = HotkeyService.DefaultBindings[HotkeyAction.MuteAll];
+
+        // Act
+        var conflict = service.GetConflict(HotkeyAction.ToggleMute, muteAll);
+        var ownBinding = service.GetConflict(HotkeyAction.MuteAll, muteAll);
+        var unused = service.GetConflict(HotkeyAction.ToggleMute, new HotkeyBinding(HotkeyModifiers.Control | HotkeyModifiers.Alt, 'Q'));
+
+        // Assert
+        Assert.Equal("Already used for Mute all microphones.", conflict);
+        Assert.Null(ownBinding);
+        Assert.Null(unused);
+    }
+}
@@ -0,0 +1,80 @@
+using MicrophoneManager.WinUI.Services;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for the ring buffer behind the level history sparkline.
+/// </summary>
+public class LevelHistoryTests
+{
+    private static readonly DateTime Start = new(2024, 1, 1, 12, 0, 0, DateTimeKind.Utc);
+
+    [Fact]
+    public void Add_KeepsTheLoudestReadingOfEachSlot()
+    {
+        // Arrange
+        var history = new LevelHistory();
+
+        // Act
+        history.Add(-40, Start);
+        history.Add(-20, Start.AddMilliseconds(30));
+        history.Add(-50, Start.AddMilliseconds(60));
+        history.Add(-96, Start.AddMilliseconds(100));
+
+        // Assert
+        Assert.Equal(new[] { -20.0, -96.0 }, history.ToArray());
+    }
+
+    [Fact]
+    public void Add_DropsTheOldestSlotOnceFull()
+    {
+        // Arrange
+        var history = new LevelHistory();
+
+        // Act
+        for (var i = 0; i < LevelHistory.Capacity + 5; i++)
+        {
+            history.Add(-(i % 96), Start + (LevelHistory.SlotLength * i));
+        }
+
+        // Assert
+        var levels = history.ToArray();
+        Assert.Equal(LevelHistory.Capacity, levels.Length);
+        Assert.Equal(-5, levels[0]);
+        Assert.Equal(-((LevelHistory.Capacity + 4) % 96), levels[^1]);
+    }
+
+    [Fact]
+    public void Version_MovesOnOnlyWithANewSlot()
+    {
+        // Arrange
+        var history = new LevelHistory();
+        history.Add(-40, Start);
+        var version = history.Version;
+
+        // Act
+        history.Add(-30, Start.AddMilliseconds(50));
+        var sameSlot = history.Version;
+        history.Add(-30, Start.AddMilliseconds(150));
+
+        // Assert
+        Assert.Equal(version, sameSlot);
+        Assert.NotEqual(version, history.Version);
+    }
+
+    [Fact]
+    public void Clear_EmptiesTheHistory()
+    {
+        // Arrange
+        var history = new LevelHistory();
+        history.Add(-40, Start);
+
+        // Act
+        history.Clear();
+
+        // Assert
+        Assert.Empty(history.ToArray());
+        Assert.Equal(0, history.Count);
+    }
+}
@@ -0,0 +1,251 @@
+using MicrophoneManager.Tests.Fakes;
+using MicrophoneManager.WinUI.Models;
+using MicrophoneManager.WinUI.Services;
+using MicrophoneManager.WinUI.ViewModels;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for reading microphone levels off the UI thread and handing them to the flyout.
+/// </summary>
+public class LevelMeterServiceTests
+{
+    private static FakeAudioDeviceService CreateAudioService()
+    {
+        return FakeAudioDeviceService.WithMicrophones(
+            "mic-1",
+            new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic") { InputPeakDb = -12, InputRmsDb = -24 },
+            new FakeAudioDeviceService.FakeMicrophone("mic-2", "Headset") { InputPeakDb = -40, InputRmsDb = -50 });
+    }
+
+    [Fact]
+    public void Poll_PublishesEveryMicrophone()
+    {
+        // Arrange
+        using var service = new LevelMeterService(CreateAudioService());
+        service.SetDevices(new[] { "mic-1", "mic-2" });
+
+        // Act
+        service.Poll();
+
+        // Assert
+        Assert.True(service.Snapshot.TryGetReading("mic-1", out var desk));
+        Assert.Equal(new MeterReading(-12, -24), desk);
+        Assert.True(service.Snapshot.TryGetReading("mic-2", out var headset));
+        Assert.Equal(new MeterReading(-40, -50), headset);
+    }
+
+    [Fact]
+    public void Poll_UsesTheDevicesTheListHandsOver()
+    {
+        // Arrange
+        var audioService = CreateAudioService();
+        using var service = new LevelMeterService(audioService);
+        using var viewModel = new MicrophoneListViewModel(audioService, levelMeter: service);
+        audioService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-3", "Webcam") { InputPeakDb = -30 });
+        audioService.RaiseDevicesChanged("mic-3", DeviceChangeKind.Added);
+        var enumerations = audioService.EnumerationCount;
+
+        // Act
+        service.Poll();
+        service.Poll();
+
+        // Assert
+        Assert.True(service.Snapshot.TryGetReading("mic-3", out var webcam));
+        Assert.Equal(-30, webcam.PeakDb);
+        Assert.Equal(enumerations, audioService.EnumerationCount);
+    }
+
+    [Fact]
+    public void LastViewerGone_StopsPollingAndClearsLevels()
+    {
+        // Arrange
+        using var service = new LevelMeterService(CreateAudioService());
+        service.AddViewer();
+        service.AddViewer();
+        service.Poll();
+
+        // Act & Assert
+        service.RemoveViewer();
+        Assert.True(service.IsPolling);
+
+        service.RemoveViewer();
+        Assert.False(service.IsPolling);
+        Assert.False(service.Snapshot.TryGetReading("mic-1", out _));
+    }
+
+    [Fact]
+    public void ListViewModel_TakesLevelsFromTheSnapshot()
+    {
+        // Arrange
+        var audioService = CreateAudioService();
+        audioService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-2", "Headset") { InputPeakDb = -40, IsMuted = true });
+        using var service = new LevelMeterService(audioService);
+        var viewModel = new MicrophoneListViewModel(audioService, levelMeter: service);
+        viewModel.SetMeteringEnabled(true);
+        service.Poll();
+
+        // Act
+        viewModel.RefreshMeters();
+
+        // Assert
+        var desk = viewModel.Microphones.Single(m => m.Id == "mic-1");
+        var headset = viewModel.Microphones.Single(m => m.Id == "mic-2");
+        Assert.Equal(-12, desk.InputLevelDbFs, precision: 3);
+        Assert.Equal(-24, desk.RmsLevelDbFs, precision: 3);
+        Assert.Equal(0, headset.InputLevelPercent);
+        Assert.True(service.IsPolling);
+
+        viewModel.Dispose();
+        Assert.False(service.IsPolling);
+    }
+
+    [Fact]
+    public void Poll_CarriesChannelPeaksOfMultiChannelMicrophonesOnly()
+    {
+        // Arrange
+        var audioService = CreateAudioService();
+        audioService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic") { InputPeakDb = -12, ChannelPeaksDb = new[] { -12.0, -96.0 } });
+        audioService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-2", "Headset") { InputPeakDb = -40, ChannelPeaksDb = new[] { -40.0 } });
+        using var service = new LevelMeterService(audioService);
+        service.SetDevices(new[] { "mic-1", "mic-2" });
+
+        // Act
+        service.Poll();
+
+        // Assert
+        Assert.True(service.Snapshot.TryGetReading("mic-1", out var desk));
+        Assert.Equal(new[] { -12.0, -96.0 }, desk.ChannelPeaksDb);
+        Assert.True(service.Snapshot.TryGetReading("mic-2", out var headset));
+        Assert.Null(headset.ChannelPeaksDb);
+    }
+
+    [Fact]
+    public void ListViewModel_ShowsOneMeterPerChannel()
+    {
+        // Arrange
+        var audioService = CreateAudioService();
+        audioService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic") { InputPeakDb = -12, ChannelPeaksDb = new[] { -12.0, -96.0 } });
+        using var service = new LevelMeterService(audioService);
+        var viewModel = new MicrophoneListViewModel(audioService, levelMeter: service);
+        viewModel.SetMeteringEnabled(true);
+        service.Poll();
+
+        // Act
+        viewModel.RefreshMeters();
+
+        // Assert
+        var desk = viewModel.Microphones.Single(m => m.Id == "mic-1");
+        var headset = viewModel.Microphones.Single(m => m.Id == "mic-2");
+        Assert.True(desk.IsChannelMetersShown);
+        Assert.False(desk.IsMonoMeterShown);
+        Assert.Equal(new[] { "L", "R" }, desk.ChannelMeters.Select(c => c.Label));
+        Assert.Equal(-12, desk.ChannelMeters[0].LevelDbFs, precision: 3);
+        Assert.Equal(0, desk.ChannelMeters[1].VisiblePercent);
+        Assert.False(headset.HasChannelMeters);
+        Assert.True(headset.IsMonoMeterShown);
+
+        viewModel.Dispose();
+    }
+
+    [Fact]
+    public void Poll_AddsToEachMicrophonesHistory()
+    {
+        // Arrange
+        using var service = new LevelMeterService(CreateAudioService());
+        service.SetDevices(new[] { "mic-1", "mic-2" });
+
+        // Act
+        service.Poll();
+
+        // Assert
+        Assert.Equal(new[] { -12.0 }, service.GetHistory("mic-1")!.ToArray());
+        Assert.Equal(new[] { -40.0 }, service.GetHistory("mic-2")!.ToArray());
+        Assert.Null(service.GetHistory("mic-3"));
+    }
+
+    [Fact]
+    public void LastViewerGone_ClearsTheHistory()
+    {
+        // Arrange
+        using var service = new LevelMeterService(CreateAudioService());
+        service.SetDevices(new[] { "mic-1" });
+        service.AddViewer();
+        service.Poll();
+
+        // Act
+        service.RemoveViewer();
+
+        // Assert
+        Assert.Empty(service.GetHistory("mic-1")!.ToArray());
+    }
+
+    [Fact]
+    public void ListViewModel_CopiesTheHistoryOnlyWhenShown()
+    {
+        // Arrange
+        var audioService = CreateAudioService();
+        var preferences = new InMemoryPreferencesStore();
+        var meterSettings = new MeterSettingsService(preferences);
+        using var service = new LevelMeterService(audioService);
+        var viewModel = new MicrophoneListViewModel(audioService, meterSettings: meterSettings, levelMeter: service);
+        viewModel.SetMeteringEnabled(true);
+        service.Poll();
+        viewModel.RefreshMeters();
+        var desk = viewModel.Microphones.Single(m => m.Id == "mic-1");
+        Assert.Empty(desk.HistoryDbFs);
+
+        // Act
+        meterSettings.ShowHistory = true;
+        viewModel.RefreshMeters();
+
+        // Assert
+        Assert.True(desk.IsHistoryShown);
+        Assert.Equal(new[] { -12.0 }, desk.HistoryDbFs);
+
+        viewModel.Dispose();
+    }
+
+    [Theory]
+    [InlineData(0, 2, "L")]
+    [InlineData(1, 2, "R")]
+    [InlineData(3, 6, "4")]
+    public void ChannelLabel_IsLeftRightForStereoOtherwiseNumbered(int channel, int count, string expected)
+    {
+        // Act & Assert
+        Assert.Equal(expected, ChannelMeterViewModel.GetLabel(channel, count));
+    }
+
+    [Theory]
+    [InlineData(null, LevelMeterService.VisibleRate)]
+    [InlineData("60", 60)]
+    [InlineData("500", LevelMeterService.VisibleRate)]
+    public void Rate_ComesFromThePreference(string? stored, int expected)
+    {
+        // Arrange
+        var preferences = new InMemoryPreferencesStore();
+        preferences.SetString(LevelMeterService.RatePreference, stored);
+
+        // Act
+        using var service = new LevelMeterService(CreateAudioService(), preferences);
+
+        // Assert
+        Assert.Equal(expected, service.Rate);
+    }
+
+    [Fact]
+    public void Rate_IsClampedWhenSet()
+    {
+        // Arrange
+        var preferences = new InMemoryPreferencesStore();
+        using var service = new LevelMeterService(CreateAudioService(), preferences);
+
+        // Act
+        service.Rate = 5;
+
+        // Assert
+        Assert.Equal(LevelMeterService.MinRate, service.Rate);
+        Assert.Equal("20", preferences.GetString(LevelMeterService.RatePreference));
+    }
+}
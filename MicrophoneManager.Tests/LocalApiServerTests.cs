@@ -0,0 +1,95 @@
+using System.Text;
+using MicrophoneManager.Tests.Fakes;
+using MicrophoneManager.WinUI.Services;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for the local API's settings and token checks. Listening itself needs http.sys and isn't covered here.
+/// </summary>
+public class LocalApiServerTests
+{
+    private static LocalApiServer CreateServer(InMemoryPreferencesStore preferences)
+    {
+        return new LocalApiServer(new ControlCommandDispatcher(new FakeAudioDeviceService()), preferences);
+    }
+
+    [Fact]
+    public void Token_IsCreatedOnceAndPersisted()
+    {
+        // Arrange
+        var preferences = new InMemoryPreferencesStore();
+        var server = CreateServer(preferences);
+
+        // Act
+        var first = server.Token;
+        var second = server.Token;
+
+        // Assert
+        Assert.False(string.IsNullOrEmpty(first));
+        Assert.Equal(first, second);
+        Assert.Equal(first, preferences.GetString(LocalApiServer.TokenPreference));
+    }
+
+    [Fact]
+    public void IsAuthorized_AcceptsBearerHeaderOrQueryToken()
+    {
+        // Arrange
+        var server = CreateServer(new InMemoryPreferencesStore());
+        var token = server.Token;
+
+        // Act & Assert
+        Assert.True(server.IsAuthorized($"Bearer {token}", null));
+        Assert.True(server.IsAuthorized(null, token));
+        Assert.False(server.IsAuthorized("Bearer wrong", null));
+        Assert.False(server.IsAuthorized(null, null));
+    }
+
+    [Fact]
+    public void RegenerateToken_RejectsOldToken()
+    {
+        // Arrange
+        var server = CreateServer(new InMemoryPreferencesStore());
+        var oldToken = server.Token;
+
+        // Act
+        var newToken = server.RegenerateToken();
+
+        // Assert
+        Assert.NotEqual(oldToken, newToken);
+        Assert.False(server.IsAuthorized(null, oldToken));
+    }
+
+    [Fact]
+    public void Port_FallsBackToDefaultWhenInvalid()
+    {
+        // Arrange
+        var preferences = new InMemoryPreferencesStore();
+        var server = CreateServer(preferences);
+
+        // Act
+        preferences.SetString(LocalApiServer.PortPreference, "not a port");
+
+        // Assert
+        Assert.Equal(LocalApiServer.DefaultPort, server.Port);
+        Assert.False(server.IsEnabled);
+    }
+
+    [Fact]
+    public async Task ReadBodyAsync_StopsAtTheLimitWithoutALength()
+    {
+        // Arrange
+        var small = new MemoryStream(Encoding.UTF8.GetBytes("""{"command":"state"}"""));
+        var large = new MemoryStream(new byte[1024 * 1024]);
+
+        // Act
+        var smallBody = await LocalApiServer.ReadBodyAsync(small, CancellationToken.None);
+        var largeBody = await LocalApiServer.ReadBodyAsync(large, CancellationToken.None);
+
+        // Assert
+        Assert.Equal("""{"command":"state"}""", smallBody);
+        Assert.Null(largeBody);
+        Assert.True(large.Position < large.Length);
+    }
+}
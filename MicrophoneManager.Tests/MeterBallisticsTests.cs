@@ -0,0 +1,102 @@
+using MicrophoneManager.Tests.Fakes;
+using MicrophoneManager.WinUI.Services;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for how the level meter rises and falls, and where its settings come from.
+/// </summary>
+public class MeterBallisticsTests
+{
+    [Fact]
+    public void ZeroAttack_FollowsIncreasesAtOnce()
+    {
+        // Arrange
+        var ballistics = new MeterBallistics(attackMs: 0, decayMs: 300);
+
+        // Act
+        var level = ballistics.Update(-12, elapsedMs: 8);
+
+        // Assert
+        Assert.Equal(-12, level);
+    }
+
+    [Fact]
+    public void Attack_RisesOverTheTimeConstant()
+    {
+        // Arrange
+        var ballistics = new MeterBallistics(attackMs: 100, decayMs: 300);
+        ballistics.Update(-60, elapsedMs: 10_000);
+
+        // Act - one time constant covers 63% of the distance
+        var level = ballistics.Update(-20, elapsedMs: 100);
+
+        // Assert
+        Assert.Equal(-60 + 40 * (1 - Math.Exp(-1)), level, precision: 6);
+    }
+
+    [Fact]
+    public void Decay_FallsOverTheTimeConstant()
+    {
+        // Arrange
+        var ballistics = new MeterBallistics(attackMs: 0, decayMs: 300);
+        ballistics.Update(-10, elapsedMs: 0);
+
+        // Act
+        var afterOneStep = ballistics.Update(-70, elapsedMs: 30);
+        var afterLong = ballistics.Update(-70, elapsedMs: 10_000);
+
+        // Assert
+        Assert.InRange(afterOneStep, -70, -10);
+        Assert.Equal(-70, afterLong, precision: 3);
+    }
+
+    [Fact]
+    public void Settings_DefaultToPeakWithInstantAttack()
+    {
+        // Arrange
+        var settings = new MeterSettingsService(new InMemoryPreferencesStore());
+
+        // Act & Assert
+        Assert.Equal(MeterMode.Peak, settings.Mode);
+        Assert.Equal(0, settings.AttackMs);
+        Assert.Equal(300, settings.DecayMs);
+    }
+
+    [Theory]
+    [InlineData("Rms", "50", MeterMode.Rms, 50)]
+    [InlineData("peakandrms", "-5", MeterMode.PeakAndRms, 0)]
+    [InlineData("Loudness", "abc", MeterMode.Peak, 0)]
+    public void Settings_ParseStoredValues(string mode, string attack, MeterMode expectedMode, int expectedAttack)
+    {
+        // Arrange
+        var preferences = new InMemoryPreferencesStore();
+        preferences.SetString(MeterSettingsService.ModePreference, mode);
+        preferences.SetString(MeterSettingsService.AttackPreference, attack);
+
+        // Act
+        var settings = new MeterSettingsService(preferences);
+
+        // Assert
+        Assert.Equal(expectedMode, settings.Mode);
+        Assert.Equal(expectedAttack, settings.AttackMs);
+    }
+
+    [Fact]
+    public void Settings_RaiseChanged()
+    {
+        // Arrange
+        var settings = new MeterSettingsService(new InMemoryPreferencesStore());
+        var changes = 0;
+        settings.Changed += (s, e) => changes++;
+
+        // Act
+        settings.Mode = MeterMode.Rms;
+        settings.DecayMs = 600;
+
+        // Assert
+        Assert.Equal(2, changes);
+        Assert.Equal(600, settings.DecayMs);
+    }
+}
@@ -0,0 +1,55 @@
+using MicrophoneManager.WinUI.Services;
+using NAudio.Wave;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for the outline drawn of a microphone test recording.
+/// </summary>
+public class MicTestWaveformTests
+{
+    [Fact]
+    public void Add_KeepsTheLoudestSampleOfEachSlotAcrossChannels()
+    {
+        // Arrange: 1 kHz stereo 16-bit, so a 50 ms slot is 50 frames
+        var waveform = new MicTestWaveform(new WaveFormat(1000, 16, 2));
+        var samples = new short[150 * 2];
+        samples[10 * 2 + 1] = 16384;
+        samples[60 * 2] = -32768;
+        var buffer = new byte[samples.Length * 2];
+        Buffer.BlockCopy(samples, 0, buffer, 0, buffer.Length);
+
+        // Act
+        waveform.Add(buffer, buffer.Length);
+
+        // Assert
+        Assert.Equal(new[] { 0.5, 1.0, 0.0 }, waveform.ToArray());
+    }
+
+    [Fact]
+    public void Add_ReadsFloatSamplesAndCarriesPartialSlotsOver()
+    {
+        // Arrange
+        var waveform = new MicTestWaveform(WaveFormat.CreateIeeeFloatWaveFormat(1000, 1));
+        var first = new float[30];
+        first[5] = 0.25f;
+        var second = new float[30];
+
+        // Act
+        waveform.Add(ToBytes(first), first.Length * 4);
+        var partial = waveform.ToArray();
+        waveform.Add(ToBytes(second), second.Length * 4);
+
+        // Assert
+        Assert.Empty(partial);
+        Assert.Equal(new[] { 0.25 }, waveform.ToArray());
+    }
+
+    private static byte[] ToBytes(float[] samples)
+    {
+        var bytes = new byte[samples.Length * 4];
+        Buffer.BlockCopy(samples, 0, bytes, 0, bytes.Length);
+        return bytes;
+    }
+}
@@ -1,4 +1,6 @@
+using MicrophoneManager.WinUI.Models;
 using MicrophoneManager.Tests.Fakes;
+using MicrophoneManager.WinUI.Services;
 using MicrophoneManager.WinUI.ViewModels;
 using Xunit;
 
@@ -84,6 +86,24 @@ public class MicrophoneListViewModelTests
         Assert.True(viewModel.PeakMicInputLevelPercent >= 70);
     }
 
+    [Fact]
+    public void RmsMeterMode_ShowsTheRmsLevel()
+    {
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic"));
+        fakeService.DefaultConsoleId = "mic-1";
+        var meterSettings = new MeterSettingsService(new InMemoryPreferencesStore());
+
+        var viewModel = new MicrophoneListViewModel(fakeService, meterSettings: meterSettings);
+        meterSettings.Mode = MeterMode.Rms;
+        fakeService.RaiseInputLevelChanged("mic-1", 70, -6, rmsDbFs: -18);
+
+        var entry = viewModel.Microphones.Single();
+        Assert.Equal(MeterMode.Rms, entry.MeterMode);
+        Assert.Equal(-18, entry.RmsLevelDbFs, precision: 3);
+        Assert.Equal(-18, entry.MeterLevelDbFs, precision: 3);
+    }
+
     [Fact]
     public void PerDeviceVolumeEventsUpdateNonDefaultEntries()
     {
@@ -229,6 +249,145 @@ public class MicrophoneListViewModelTests
         Assert.Equal("mic-2", viewModel.SelectedMicrophone?.Id);
     }
 
+    [Fact]
+    public void SingleDeviceChanges_PatchListWithoutFullEnumeration()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic"));
+        fakeService.DefaultConsoleId = "mic-1";
+
+        var viewModel = new MicrophoneListViewModel(fakeService);
+        var enumerations = fakeService.EnumerationCount;
+
+        // Act - plug in, rename, then unplug a USB microphone; also a render endpoint event
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-2", "USB Microphone"));
+        fakeService.RaiseDevicesChanged("mic-2", DeviceChangeKind.Added);
+        Assert.Contains(viewModel.Microphones, m => m.Id == "mic-2");
+
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-2", "Podcast Mic"));
+        fakeService.RaiseDevicesChanged("mic-2", DeviceChangeKind.PropertyChanged);
+        Assert.Equal("Podcast Mic", viewModel.Microphones.Single(m => m.Id == "mic-2").Name);
+
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-2", "Podcast Mic")
+        {
+            State = NAudio.CoreAudioApi.DeviceState.Unplugged
+        });
+        fakeService.RaiseDevicesChanged("mic-2", DeviceChangeKind.StateChanged);
+        fakeService.RaiseDevicesChanged("spk-1", DeviceChangeKind.Added);
+
+        // Assert
+        Assert.Single(viewModel.Microphones);
+        Assert.Equal(enumerations, fakeService.EnumerationCount);
+    }
+
+    [Fact]
+    public void CoalescedChangeSet_AppliesEveryDeviceInOneEvent()
+    {
+        // Arrange - a USB hub reset removes one mic and brings two back in a single burst
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic"));
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-2", "Hub Mic A"));
+        fakeService.DefaultConsoleId = "mic-1";
+
+        var viewModel = new MicrophoneListViewModel(fakeService);
+        var collectionChanges = 0;
+        viewModel.Microphones.CollectionChanged += (_, _) => collectionChanges++;
+
+        // Act
+        fakeService.RemoveMicrophone("mic-2");
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-3", "Hub Mic B"));
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-4", "Hub Mic C"));
+        fakeService.RaiseDevicesChanged(
+            new DeviceChange("mic-2", DeviceChangeKind.Removed),
+            new DeviceChange("mic-3", DeviceChangeKind.Added),
+            new DeviceChange("mic-4", DeviceChangeKind.Added));
+
+        // Assert
+        Assert.Equal(new[] { "mic-1", "mic-3", "mic-4" }, viewModel.Microphones.Select(m => m.Id).ToArray());
+        Assert.Equal(3, collectionChanges);
+    }
+
+    [Fact]
+    public void DefaultDeviceChangedEvent_MovesFlagsWithoutFullEnumeration()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic"));
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-2", "Headset") { VolumeScalar = 0.4 });
+        fakeService.DefaultConsoleId = "mic-1";
+        fakeService.DefaultCommunicationsId = "mic-1";
+
+        var viewModel = new MicrophoneListViewModel(fakeService);
+        var enumerations = fakeService.EnumerationCount;
+
+        // Act
+        fakeService.DefaultConsoleId = "mic-2";
+        fakeService.DefaultCommunicationsId = "mic-2";
+        fakeService.RaiseDefaultDeviceChanged();
+
+        // Assert
+        Assert.False(viewModel.Microphones.Single(m => m.Id == "mic-1").IsDefault);
+        Assert.True(viewModel.Microphones.Single(m => m.Id == "mic-2").IsDefaultCommunication);
+        Assert.Equal("mic-2", viewModel.SelectedMicrophone?.Id);
+        Assert.Equal(40.0, viewModel.CurrentMicLevelPercent);
+        Assert.Equal(enumerations, fakeService.EnumerationCount);
+    }
+
+    [Fact]
+    public void ShowInactiveDevices_ListsUnpluggedMicrophones()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic"));
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-2", "USB Microphone")
+        {
+            State = NAudio.CoreAudioApi.DeviceState.Unplugged
+        });
+        fakeService.DefaultConsoleId = "mic-1";
+
+        var viewModel = new MicrophoneListViewModel(fakeService);
+        Assert.Single(viewModel.Microphones);
+
+        // Act
+        viewModel.ShowInactiveDevices = true;
+
+        // Assert
+        Assert.Equal(2, viewModel.Microphones.Count);
+        var unplugged = viewModel.Microphones.First(m => m.Id == "mic-2");
+        Assert.False(unplugged.IsActive);
+        Assert.Equal("Unplugged", unplugged.StateText);
+        Assert.True(viewModel.Microphones.First(m => m.Id == "mic-1").IsActive);
+    }
+
+    [Fact]
+    public async Task ToggleEnabled_DisablesAndReEnablesDevice()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic"));
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-2", "Laptop Mic"));
+        fakeService.DefaultConsoleId = "mic-1";
+
+        var viewModel = new MicrophoneListViewModel(fakeService) { ShowInactiveDevices = true };
+        var laptop = viewModel.Microphones.First(m => m.Id == "mic-2");
+
+        // Act - disable
+        await laptop.ToggleEnabledCommand.ExecuteAsync(null);
+        fakeService.RaiseDevicesChanged();
+
+        // Assert
+        Assert.Equal("Disabled", laptop.StateText);
+        Assert.Equal("Enable device", laptop.EnableToggleText);
+
+        // Act - re-enable
+        await laptop.ToggleEnabledCommand.ExecuteAsync(null);
+        fakeService.RaiseDevicesChanged();
+
+        // Assert
+        Assert.True(laptop.IsActive);
+    }
+
     #endregion
 
     #region Edge Cases: Empty Device List
@@ -315,187 +474,1021 @@ public class MicrophoneListViewModelTests
         Assert.Equal(1.0f, mic.VolumeLevel, 3);
     }
 
-    #endregion
-
-    #region FR-020, FR-021: External Change Sync
-
     [Fact]
-    public void ExternalVolumeChange_UpdatesSliderWithoutFeedbackLoop()
+    public void SetVolumeDb_ClampsToRangeAndShowsDbText()
     {
-        // Arrange - FR-020: System MUST handle volume changes made by external applications
+        // Arrange
         var fakeService = new FakeAudioDeviceService();
         fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic")
         {
+            VolumeRange = new VolumeRange(-40f, 0f, 0.5f),
             VolumeScalar = 0.5
         });
-        fakeService.DefaultConsoleId = "mic-1";
 
         var viewModel = new MicrophoneListViewModel(fakeService);
-        Assert.Equal(50, viewModel.CurrentMicLevelPercent);
+        var entry = viewModel.Microphones.Single();
+        Assert.Equal("-20 dB", entry.VolumeDbText);
+        Assert.Equal(-40f, fakeService.GetMicrophones().Single().VolumeRange!.MinDb);
 
-        // Act - simulate Windows Settings changing volume
-        fakeService.RaiseDefaultVolumeChanged("mic-1", 0.75f, false);
+        // Act
+        var success = fakeService.SetVolumeDb("mic-1", 12f);
+        var clampedDb = fakeService.GetVolumeDb("mic-1");
+        entry.VolumePercent = 25;
+
+        // Assert - 12 dB is above the range, so the level stops at its 0 dB maximum
+        Assert.True(success);
+        Assert.Equal(0f, clampedDb!.Value, 3);
+
+        // ...and the slider at a quarter of the range reads as -30 dB
+        Assert.Equal("-30 dB", entry.VolumeDbText);
+        Assert.Equal(-30f, fakeService.GetVolumeDb("mic-1")!.Value, 3);
+    }
+
+    #endregion
+
+    #region Microphone Boost
+
+    [Fact]
+    public void Boost_HiddenWhenDriverHasNoBoostControl()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic"));
+
+        // Act
+        var viewModel = new MicrophoneListViewModel(fakeService);
 
         // Assert
-        Assert.Equal(75, viewModel.CurrentMicLevelPercent);
+        Assert.False(viewModel.Microphones.Single().HasBoost);
     }
 
     [Fact]
-    public void ExternalMuteChange_UpdatesMuteState()
+    public void BoostSlider_WritesBoostToService()
     {
-        // Arrange - FR-021: System MUST detect and reflect mute state changes made by external applications
+        // Arrange
         var fakeService = new FakeAudioDeviceService();
         fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic")
         {
-            IsMuted = false
+            Boost = new MicrophoneBoost(0, 0, 30, 10)
         });
-        fakeService.DefaultConsoleId = "mic-1";
 
         var viewModel = new MicrophoneListViewModel(fakeService);
-        Assert.False(viewModel.IsMuted);
+        var entry = viewModel.Microphones.Single();
+        Assert.True(entry.HasBoost);
+        Assert.Equal(30, entry.BoostMaxDb);
 
-        // Act - simulate external mute
-        fakeService.RaiseDefaultVolumeChanged("mic-1", 0.5f, true);
+        // Act
+        entry.BoostDb = 20;
 
         // Assert
-        Assert.True(viewModel.IsMuted);
+        Assert.Equal(20f, fakeService.GetMicrophoneBoost("mic-1")!.LevelDb, 3);
+        Assert.Equal("Boost +20 dB", entry.BoostText);
     }
 
-    #endregion
+    [Fact]
+    public void AutoGainCheckbox_WritesAgcToService()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Line In")
+        {
+            AutoGainEnabled = true
+        });
 
-    #region Dispose Pattern
+        var viewModel = new MicrophoneListViewModel(fakeService);
+        var entry = viewModel.Microphones.Single();
+        Assert.True(entry.HasAutoGain);
+        Assert.True(entry.IsAutoGainEnabled);
+
+        // Act
+        entry.IsAutoGainEnabled = false;
+
+        // Assert
+        Assert.False(fakeService.GetAutoGainControl("mic-1"));
+    }
 
     [Fact]
-    public void Dispose_DoesNotThrow()
+    public void ToggleAudioEffect_FlipsEffectAndSkipsReadOnlyEffects()
     {
         // Arrange
+        var noiseSuppression = new AudioEffect(Guid.NewGuid(), "Noise suppression", IsEnabled: false, CanToggle: true);
+        var echoCancellation = new AudioEffect(Guid.NewGuid(), "Echo cancellation", IsEnabled: true, CanToggle: false);
         var fakeService = new FakeAudioDeviceService();
-        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic"));
-        fakeService.DefaultConsoleId = "mic-1";
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Array Microphone")
+        {
+            AudioEffects = new List<AudioEffect> { noiseSuppression, echoCancellation }
+        });
 
         var viewModel = new MicrophoneListViewModel(fakeService);
+        var entry = viewModel.Microphones.Single();
 
-        // Act & Assert - should not throw
-        var exception = Record.Exception(() => viewModel.Dispose());
-        Assert.Null(exception);
+        // Act
+        entry.ToggleAudioEffectCommand.Execute(noiseSuppression);
+        entry.ToggleAudioEffectCommand.Execute(echoCancellation);
+
+        // Assert
+        var effects = entry.GetAudioEffects();
+        Assert.True(effects.Single(e => e.Id == noiseSuppression.Id).IsEnabled);
+        Assert.True(effects.Single(e => e.Id == echoCancellation.Id).IsEnabled);
+        Assert.Null(viewModel.ErrorMessage);
     }
 
     [Fact]
-    public void AfterDispose_EventsDoNotCrash()
+    public void Supports_ReflectsDeviceCapabilities()
     {
         // Arrange
         var fakeService = new FakeAudioDeviceService();
-        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic"));
-        fakeService.DefaultConsoleId = "mic-1";
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "USB Microphone"));
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-2", "Array Microphone")
+        {
+            Boost = new MicrophoneBoost(10f, 0f, 30f, 10f),
+            AudioEffects = new List<AudioEffect> { new(Guid.NewGuid(), "Noise suppression", true, true) }
+        });
 
+        // Act
         var viewModel = new MicrophoneListViewModel(fakeService);
-        viewModel.Dispose();
+        var usb = viewModel.Microphones.Single(m => m.Id == "mic-1");
+        var array = viewModel.Microphones.Single(m => m.Id == "mic-2");
 
-        // Act - events raised after dispose should not crash
-        var exception = Record.Exception(() =>
+        // Assert
+        Assert.False(usb.Supports(DeviceCapabilities.Boost));
+        Assert.False(usb.Supports(DeviceCapabilities.AudioEffects));
+        Assert.True(usb.Supports(DeviceCapabilities.Recording));
+        Assert.True(array.Supports(DeviceCapabilities.Boost));
+        Assert.True(array.Supports(DeviceCapabilities.AudioEffects));
+    }
+
+    [Fact]
+    public void DetailsText_SummarizesDeviceProperties()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Headset Mic")
         {
-            fakeService.RaiseDevicesChanged();
-            fakeService.RaiseDefaultDeviceChanged();
-            fakeService.RaiseDefaultVolumeChanged("mic-1", 0.5f, false);
+            Properties = new DeviceProperties
+            {
+                DeviceId = "mic-1",
+                ContainerId = Guid.NewGuid(),
+                FormFactor = "Headset",
+                EnumeratorName = "USB",
+                AdapterName = "Jabra Link 380"
+            }
         });
 
+        // Act
+        var viewModel = new MicrophoneListViewModel(fakeService);
+
         // Assert
-        Assert.Null(exception);
+        Assert.Equal("Headset · USB · Jabra Link 380", viewModel.Microphones.Single().DetailsText);
     }
 
-    #endregion
+    [Fact]
+    public void DetailsText_FollowsPropertyChangesOnRefresh()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        var microphone = new FakeAudioDeviceService.FakeMicrophone("mic-1", "Headset Mic")
+        {
+            Properties = new DeviceProperties { DeviceId = "mic-1", FormFactor = "Microphone", EnumeratorName = "USB" }
+        };
+        fakeService.AddOrUpdateMicrophone(microphone);
+        var viewModel = new MicrophoneListViewModel(fakeService);
+        var entry = viewModel.Microphones.Single();
+        var changed = new List<string?>();
+        entry.PropertyChanged += (s, e) => changed.Add(e.PropertyName);
 
-    #region FR-023: Audio Format Change Sync
+        // Act - the driver now reports it as a headset
+        microphone.Properties = new DeviceProperties { DeviceId = "mic-1", FormFactor = "Headset", EnumeratorName = "USB" };
+        viewModel.RefreshDevices();
+
+        // Assert
+        Assert.Equal("Headset · USB", entry.DetailsText);
+        Assert.Contains(nameof(MicrophoneEntryViewModel.DetailsText), changed);
+        Assert.Contains(nameof(MicrophoneEntryViewModel.NameToolTip), changed);
+    }
 
     [Fact]
-    public void FormatChangedEvent_UpdatesDeviceFormatTag()
+    public async Task TestMicrophone_RunsTestOnThatDevice()
     {
-        // Arrange - FR-023: System MUST detect and reflect audio format changes
+        // Arrange
         var fakeService = new FakeAudioDeviceService();
-        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic")
-        {
-            FormatTag = "48 kHz 24-bit Stereo"
-        });
-        fakeService.DefaultConsoleId = "mic-1";
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic"));
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-2", "Headset"));
 
         var viewModel = new MicrophoneListViewModel(fakeService);
-        Assert.Equal("48 kHz 24-bit Stereo", viewModel.Microphones.First().FormatTag);
+        var headset = viewModel.Microphones.First(m => m.Id == "mic-2");
 
-        // Act - simulate external format change
-        fakeService.RaiseFormatChanged("mic-1", "96 kHz 32-bit Stereo");
+        // Act
+        await headset.TestMicrophoneCommand.ExecuteAsync(null);
 
         // Assert
-        Assert.Equal("96 kHz 32-bit Stereo", viewModel.Microphones.First().FormatTag);
+        Assert.Equal(new[] { "mic-2" }, fakeService.MicTestDeviceIds);
+        Assert.Equal(MicTestStatus.Completed, headset.MicTestStatus);
+        Assert.Equal("Test complete", headset.MicTestText);
     }
 
     [Fact]
-    public void FormatChangedEvent_OnlyUpdatesMatchingDevice()
+    public async Task TestMicrophone_ShowsErrorWhenTestFails()
     {
         // Arrange
-        var fakeService = new FakeAudioDeviceService();
-        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic")
-        {
-            FormatTag = "48 kHz 24-bit Stereo"
-        });
-        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-2", "Headset")
-        {
-            FormatTag = "44.1 kHz 16-bit Mono"
-        });
-        fakeService.DefaultConsoleId = "mic-1";
+        var fakeService = new FakeAudioDeviceService { MicTestResult = MicTestStatus.Failed };
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic"));
 
         var viewModel = new MicrophoneListViewModel(fakeService);
 
-        // Act - change format for mic-2 only
-        fakeService.RaiseFormatChanged("mic-2", "48 kHz 16-bit Stereo");
+        // Act
+        await viewModel.Microphones.Single().TestMicrophoneCommand.ExecuteAsync(null);
 
-        // Assert - only mic-2 should change
-        Assert.Equal("48 kHz 24-bit Stereo", viewModel.Microphones.First(m => m.Id == "mic-1").FormatTag);
-        Assert.Equal("48 kHz 16-bit Stereo", viewModel.Microphones.First(m => m.Id == "mic-2").FormatTag);
+        // Assert
+        Assert.True(viewModel.HasError);
+        Assert.Equal("Mic test failed", viewModel.ErrorMessage);
     }
 
-    #endregion
+    [Fact]
+    public async Task TestMicrophone_KeepsTheWaveformAndCanReplay()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService { MicTestWaveform = new[] { 0.1, 0.5, 0.2 } };
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic"));
 
-    #region Error Message Infrastructure
+        var viewModel = new MicrophoneListViewModel(fakeService);
+        var desk = viewModel.Microphones.Single();
+        await desk.TestMicrophoneCommand.ExecuteAsync(null);
+
+        // Act
+        await desk.ReplayMicTestCommand.ExecuteAsync(null);
+
+        // Assert
+        Assert.Equal(new[] { "mic-1" }, fakeService.MicTestReplayDeviceIds);
+        Assert.Equal(new[] { 0.1, 0.5, 0.2 }, desk.MicTestWaveform);
+        Assert.True(desk.IsMicTestPanelShown);
+        Assert.True(desk.CanReplayMicTest);
+
+        desk.CloseMicTestCommand.Execute(null);
+        Assert.False(desk.IsMicTestPanelShown);
+        Assert.False(desk.IsMicTestVisible);
+    }
 
     [Fact]
-    public void ShowError_SetsErrorMessageAndHasError()
+    public async Task ReplayMicTest_ShowsErrorWithoutARecording()
     {
         // Arrange
         var fakeService = new FakeAudioDeviceService();
         fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic"));
-        fakeService.DefaultConsoleId = "mic-1";
 
         var viewModel = new MicrophoneListViewModel(fakeService);
-        Assert.False(viewModel.HasError);
-        Assert.Null(viewModel.ErrorMessage);
 
         // Act
-        viewModel.ShowError("Test error message");
+        await viewModel.Microphones.Single().ReplayMicTestCommand.ExecuteAsync(null);
 
         // Assert
         Assert.True(viewModel.HasError);
-        Assert.Equal("Test error message", viewModel.ErrorMessage);
+        Assert.Empty(fakeService.MicTestReplayDeviceIds);
     }
 
     [Fact]
-    public void DismissError_ClearsErrorMessage()
+    public async Task ToggleRecording_StartsThenStopsAndReportsFile()
     {
         // Arrange
         var fakeService = new FakeAudioDeviceService();
         fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic"));
-        fakeService.DefaultConsoleId = "mic-1";
 
         var viewModel = new MicrophoneListViewModel(fakeService);
-        viewModel.ShowError("Test error");
-        Assert.True(viewModel.HasError);
+        var entry = viewModel.Microphones.Single();
 
-        // Act
-        viewModel.DismissError();
+        // Act - start
+        await entry.ToggleRecordingCommand.ExecuteAsync(null);
 
         // Assert
-        Assert.False(viewModel.HasError);
+        Assert.True(entry.IsRecording);
+        Assert.True(fakeService.IsRecording("mic-1"));
+        Assert.EndsWith(".wav", fakeService.ActiveRecordings["mic-1"]);
+        Assert.Equal("Stop recording", entry.RecordingToggleText);
+
+        // Act - stop
+        await entry.ToggleRecordingCommand.ExecuteAsync(null);
+
+        // Assert
+        Assert.False(entry.IsRecording);
+        Assert.False(fakeService.IsRecording("mic-1"));
+        Assert.StartsWith("Saved Desk Mic ", entry.RecordingText);
+        Assert.EndsWith("(0:12)", entry.RecordingText);
+    }
+
+    [Fact]
+    public async Task ToggleListen_EnablesPlaybackThroughDefaultOutput()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Line In"));
+
+        var viewModel = new MicrophoneListViewModel(fakeService);
+        var entry = viewModel.Microphones.Single();
+        Assert.False(entry.IsListening);
+
+        // Act
+        await entry.ToggleListenCommand.ExecuteAsync(null);
+
+        // Assert
+        Assert.True(entry.IsListening);
+        Assert.Equal(new ListenSettings(true, null), fakeService.GetListenSettings("mic-1"));
+        Assert.Equal("Stop listening to this device", entry.ListenToggleText);
+    }
+
+    [Fact]
+    public async Task SetDefaultFormat_WritesFormatAndKeepsChannels()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic")
+        {
+            DefaultFormat = new DeviceFormat(44100, 16, 1)
+        });
+
+        var viewModel = new MicrophoneListViewModel(fakeService);
+        var entry = viewModel.Microphones.Single();
+
+        // Act
+        await entry.SetDefaultFormatCommand.ExecuteAsync("48000:24");
+
+        // Assert
+        Assert.Equal(new DeviceFormat(48000, 24, 1), fakeService.GetDeviceDefaultFormat("mic-1"));
+        Assert.Equal("48 kHz 24-bit Mono", entry.FormatTag);
+    }
+
+    [Fact]
+    public async Task GetSupportedFormatsAsync_ReturnsProbedFormatsWithCommandParameters()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic")
+        {
+            SupportedFormats = { new DeviceFormat(44100, 16, 2), new DeviceFormat(48000, 24, 2) }
+        });
+
+        var viewModel = new MicrophoneListViewModel(fakeService);
+        var entry = viewModel.Microphones.Single();
+
+        // Act
+        var formats = await entry.GetSupportedFormatsAsync();
+
+        // Assert
+        Assert.Equal(new[] { "44.1 kHz 16-bit", "48 kHz 24-bit" }, formats.Select(f => f.RateAndDepthLabel));
+        Assert.Equal("48000:24", formats[1].CommandParameter);
+        Assert.Equal(formats[1], entry.GetDefaultFormat());
+    }
+
+    #endregion
+
+    #region FR-020, FR-021: External Change Sync
+
+    [Fact]
+    public void ExternalVolumeChange_UpdatesSliderWithoutFeedbackLoop()
+    {
+        // Arrange - FR-020: System MUST handle volume changes made by external applications
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic")
+        {
+            VolumeScalar = 0.5
+        });
+        fakeService.DefaultConsoleId = "mic-1";
+
+        var viewModel = new MicrophoneListViewModel(fakeService);
+        Assert.Equal(50, viewModel.CurrentMicLevelPercent);
+
+        // Act - simulate Windows Settings changing volume
+        fakeService.RaiseDefaultVolumeChanged("mic-1", 0.75f, false);
+
+        // Assert
+        Assert.Equal(75, viewModel.CurrentMicLevelPercent);
+    }
+
+    [Fact]
+    public void ExternalMuteChange_UpdatesMuteState()
+    {
+        // Arrange - FR-021: System MUST detect and reflect mute state changes made by external applications
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic")
+        {
+            IsMuted = false
+        });
+        fakeService.DefaultConsoleId = "mic-1";
+
+        var viewModel = new MicrophoneListViewModel(fakeService);
+        Assert.False(viewModel.IsMuted);
+
+        // Act - simulate external mute
+        fakeService.RaiseDefaultVolumeChanged("mic-1", 0.5f, true);
+
+        // Assert
+        Assert.True(viewModel.IsMuted);
+    }
+
+    #endregion
+
+    #region Dispose Pattern
+
+    [Fact]
+    public void Dispose_DoesNotThrow()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic"));
+        fakeService.DefaultConsoleId = "mic-1";
+
+        var viewModel = new MicrophoneListViewModel(fakeService);
+
+        // Act & Assert - should not throw
+        var exception = Record.Exception(() => viewModel.Dispose());
+        Assert.Null(exception);
+    }
+
+    [Fact]
+    public void AfterDispose_EventsDoNotCrash()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic"));
+        fakeService.DefaultConsoleId = "mic-1";
+
+        var viewModel = new MicrophoneListViewModel(fakeService);
+        viewModel.Dispose();
+
+        // Act - events raised after dispose should not crash
+        var exception = Record.Exception(() =>
+        {
+            fakeService.RaiseDevicesChanged();
+            fakeService.RaiseDefaultDeviceChanged();
+            fakeService.RaiseDefaultVolumeChanged("mic-1", 0.5f, false);
+        });
+
+        // Assert
+        Assert.Null(exception);
+    }
+
+    #endregion
+
+    #region FR-023: Audio Format Change Sync
+
+    [Fact]
+    public void FormatChangedEvent_UpdatesDeviceFormatTag()
+    {
+        // Arrange - FR-023: System MUST detect and reflect audio format changes
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic")
+        {
+            FormatTag = "48 kHz 24-bit Stereo"
+        });
+        fakeService.DefaultConsoleId = "mic-1";
+
+        var viewModel = new MicrophoneListViewModel(fakeService);
+        Assert.Equal("48 kHz 24-bit Stereo", viewModel.Microphones.First().FormatTag);
+
+        // Act - simulate external format change
+        fakeService.RaiseFormatChanged("mic-1", "96 kHz 32-bit Stereo");
+
+        // Assert
+        Assert.Equal("96 kHz 32-bit Stereo", viewModel.Microphones.First().FormatTag);
+    }
+
+    [Fact]
+    public void FormatChangedEvent_OnlyUpdatesMatchingDevice()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic")
+        {
+            FormatTag = "48 kHz 24-bit Stereo"
+        });
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-2", "Headset")
+        {
+            FormatTag = "44.1 kHz 16-bit Mono"
+        });
+        fakeService.DefaultConsoleId = "mic-1";
+
+        var viewModel = new MicrophoneListViewModel(fakeService);
+
+        // Act - change format for mic-2 only
+        fakeService.RaiseFormatChanged("mic-2", "48 kHz 16-bit Stereo");
+
+        // Assert - only mic-2 should change
+        Assert.Equal("48 kHz 24-bit Stereo", viewModel.Microphones.First(m => m.Id == "mic-1").FormatTag);
+        Assert.Equal("48 kHz 16-bit Stereo", viewModel.Microphones.First(m => m.Id == "mic-2").FormatTag);
+    }
+
+    #endregion
+
+    #region Capture Sessions
+
+    [Fact]
+    public void RefreshDevices_ShowsActiveCaptureSessionsPerDevice()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic"));
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-2", "Headset"));
+        fakeService.DefaultConsoleId = "mic-1";
+        fakeService.CaptureSessions["mic-1"] = new List<CaptureSession>
+        {
+            new() { SessionInstanceId = "s1", DeviceId = "mic-1", ProcessId = 10, ProcessName = "Teams", IsActive = true },
+            new() { SessionInstanceId = "s2", DeviceId = "mic-1", ProcessId = 11, ProcessName = "obs64", IsActive = true },
+            new() { SessionInstanceId = "s3", DeviceId = "mic-1", ProcessId = 12, ProcessName = "Idle", IsActive = false }
+        };
+
+        // Act
+        var viewModel = new MicrophoneListViewModel(fakeService);
+
+        // Assert
+        var mic1 = viewModel.Microphones.First(m => m.Id == "mic-1");
+        var mic2 = viewModel.Microphones.First(m => m.Id == "mic-2");
+        Assert.True(mic1.HasActiveSessions);
+        Assert.Equal("In use by Teams, obs64", mic1.ActiveSessionsText);
+        Assert.True(mic1.IsInUse);
+        Assert.False(mic2.HasActiveSessions);
+        Assert.False(mic2.IsInUse);
+    }
+
+    [Fact]
+    public void RefreshSessions_ClearsLabelWhenSessionsEnd()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic"));
+        fakeService.CaptureSessions["mic-1"] = new List<CaptureSession>
+        {
+            new() { SessionInstanceId = "s1", DeviceId = "mic-1", ProcessId = 10, ProcessName = "Zoom", IsActive = true }
+        };
+
+        var viewModel = new MicrophoneListViewModel(fakeService);
+        Assert.True(viewModel.Microphones.Single().HasActiveSessions);
+
+        // Act
+        fakeService.CaptureSessions.Clear();
+        viewModel.RefreshSessions();
+
+        // Assert
+        Assert.False(viewModel.Microphones.Single().HasActiveSessions);
+        Assert.False(viewModel.Microphones.Single().IsInUse);
+        Assert.False(fakeService.IsDeviceInUse("mic-1"));
+    }
+
+    [Fact]
+    public void RefreshSessions_IgnoresOurOwnMeterCapture()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic"));
+        fakeService.CaptureSessions["mic-1"] = new List<CaptureSession>
+        {
+            new() { SessionInstanceId = "s1", DeviceId = "mic-1", ProcessId = (uint)Environment.ProcessId, ProcessName = "MicrophoneManager.WinUI", IsActive = true }
+        };
+
+        // Act
+        var viewModel = new MicrophoneListViewModel(fakeService);
+
+        // Assert
+        var entry = viewModel.Microphones.Single();
+        Assert.False(entry.IsInUse);
+        Assert.False(entry.HasActiveSessions);
+        Assert.False(fakeService.IsDeviceInUse("mic-1"));
+    }
+
+    [Fact]
+    public void RefreshSessions_ReportsExclusiveModeLockWithOwner()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Audio Interface"));
+        var viewModel = new MicrophoneListViewModel(fakeService);
+        Assert.False(viewModel.Microphones.Single().IsExclusivelyLocked);
+
+        // Act
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Audio Interface") { IsExclusivelyLocked = true });
+        fakeService.CaptureSessions["mic-1"] = new List<CaptureSession>
+        {
+            new() { SessionInstanceId = "s1", DeviceId = "mic-1", ProcessId = 10, ProcessName = "Reaper.exe", IsActive = true }
+        };
+        viewModel.RefreshSessions();
+
+        // Assert
+        var entry = viewModel.Microphones.Single();
+        Assert.True(entry.IsExclusivelyLocked);
+        Assert.Equal("Locked in exclusive mode (Reaper.exe)", entry.ExclusiveLockText);
+    }
+
+    [Fact]
+    public void CaptureSessionChanged_UpdatesInUseWithoutWaitingForRefresh()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic"));
+        var viewModel = new MicrophoneListViewModel(fakeService);
+        Assert.False(viewModel.Microphones.Single().IsInUse);
+
+        // Act
+        fakeService.CaptureSessions["mic-1"] = new List<CaptureSession>
+        {
+            new() { SessionInstanceId = "s1", DeviceId = "mic-1", ProcessId = 10, ProcessName = "Discord.exe", IsActive = true }
+        };
+        fakeService.RaiseCaptureSessionChanged("mic-1", "s1", 10, CaptureSessionChangeKind.Activated);
+
+        // Assert
+        Assert.True(viewModel.Microphones.Single().IsInUse);
+        Assert.Equal("In use by Discord.exe", viewModel.Microphones.Single().ActiveSessionsText);
+    }
+
+    [Fact]
+    public void SessionToggleMute_MutesOnlyThatApplication()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic"));
+        fakeService.CaptureSessions["mic-1"] = new List<CaptureSession>
+        {
+            new() { SessionInstanceId = "s1", DeviceId = "mic-1", ProcessId = 10, ProcessName = "Teams", IsActive = true },
+            new() { SessionInstanceId = "s2", DeviceId = "mic-1", ProcessId = 11, ProcessName = "obs64", IsActive = true }
+        };
+
+        var viewModel = new MicrophoneListViewModel(fakeService);
+        var mic = viewModel.Microphones.Single();
+        var teams = mic.Sessions.Single(s => s.SessionInstanceId == "s1");
+
+        // Act
+        teams.ToggleMuteCommand.Execute(null);
+        teams.VolumePercent = 40;
+        viewModel.RefreshSessions();
+
+        // Assert
+        var sessions = fakeService.CaptureSessions["mic-1"];
+        Assert.True(sessions.Single(s => s.SessionInstanceId == "s1").IsMuted);
+        Assert.Equal(0.4f, sessions.Single(s => s.SessionInstanceId == "s1").Volume, 3);
+        Assert.False(sessions.Single(s => s.SessionInstanceId == "s2").IsMuted);
+        Assert.False(fakeService.GetMicrophones().Single().IsMuted);
+        Assert.Same(teams, mic.Sessions.Single(s => s.SessionInstanceId == "s1"));
+        Assert.True(teams.IsMuted);
+    }
+
+    #endregion
+
+    #region Device Aliases
+
+    [Fact]
+    public void CommitRename_SavesAliasAndUpdatesDisplayName()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Microphone (3- USB Audio Device)"));
+        var aliases = new DeviceAliasService(new InMemoryPreferencesStore());
+        var viewModel = new MicrophoneListViewModel(fakeService, aliasService: aliases);
+        var entry = viewModel.Microphones.Single();
+
+        // Act
+        viewModel.BeginRenameCommand.Execute(entry);
+        entry.RenameText = "Desk Mic";
+        viewModel.CommitRenameCommand.Execute(entry);
+
+        // Assert
+        Assert.False(entry.IsRenaming);
+        Assert.Equal("Desk Mic", aliases.GetAlias("mic-1"));
+        Assert.Equal("Desk Mic", entry.DisplayName);
+        Assert.Equal("Microphone (3- USB Audio Device)", entry.Name);
+    }
+
+    [Fact]
+    public void CancelRename_LeavesNameUnchanged()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Headset"));
+        var aliases = new DeviceAliasService(new InMemoryPreferencesStore());
+        var viewModel = new MicrophoneListViewModel(fakeService, aliasService: aliases);
+        var entry = viewModel.Microphones.Single();
+
+        // Act
+        viewModel.BeginRenameCommand.Execute(entry);
+        entry.RenameText = "Something else";
+        viewModel.CancelRenameCommand.Execute(entry);
+        viewModel.CommitRenameCommand.Execute(entry);
+
+        // Assert
+        Assert.Null(aliases.GetAlias("mic-1"));
+        Assert.Equal("Headset", entry.DisplayName);
+    }
+
+    [Fact]
+    public void HiddenDevice_IsListedOnlyWhileShowingHidden()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Headset"));
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-2", "CABLE Output"));
+        var hidden = new HiddenDeviceService(new InMemoryPreferencesStore());
+        var viewModel = new MicrophoneListViewModel(fakeService, hiddenDeviceService: hidden);
+
+        // Act
+        viewModel.ToggleHiddenCommand.Execute(viewModel.Microphones.Single(m => m.Id == "mic-2"));
+        var listedWhileHidden = viewModel.Microphones.Select(m => m.Id).ToList();
+        hidden.ShowHidden = true;
+
+        // Assert
+        Assert.Equal(new[] { "mic-1" }, listedWhileHidden);
+        Assert.Equal(2, viewModel.Microphones.Count);
+        Assert.True(viewModel.Microphones.Single(m => m.Id == "mic-2").IsHidden);
+    }
+
+    [Fact]
+    public void PinAndMove_ReorderEntries()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Microphone (Realtek)"));
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-2", "Line In (Realtek)"));
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-3", "Yeti"));
+        var order = new DeviceOrderService(new InMemoryPreferencesStore());
+        var viewModel = new MicrophoneListViewModel(fakeService, orderService: order);
+
+        // Act
+        viewModel.TogglePinnedCommand.Execute(viewModel.Microphones.Single(m => m.Id == "mic-3"));
+        viewModel.MoveUpCommand.Execute(viewModel.Microphones.Single(m => m.Id == "mic-2"));
+
+        // Assert
+        Assert.Equal(new[] { "mic-3", "mic-2", "mic-1" }, viewModel.Microphones.Select(m => m.Id));
+        Assert.True(viewModel.Microphones[0].IsPinned);
+    }
+
+    [Fact]
+    public void SetSortMode_ResortsEntriesAndDisablesMoving()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Yeti"));
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-2", "Headset"));
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-3", "Microphone (Realtek)"));
+        fakeService.DefaultConsoleId = "mic-3";
+        var viewModel = new MicrophoneListViewModel(fakeService, orderService: new DeviceOrderService(new InMemoryPreferencesStore()));
+
+        // Act
+        viewModel.SetSortModeCommand.Execute(DeviceSortMode.Name);
+        var byName = viewModel.Microphones.Select(m => m.Id).ToList();
+        viewModel.SetSortModeCommand.Execute(DeviceSortMode.DefaultFirst);
+
+        // Assert
+        Assert.Equal(new[] { "mic-2", "mic-3", "mic-1" }, byName);
+        Assert.Equal("mic-3", viewModel.Microphones[0].Id);
+        Assert.False(viewModel.CanMoveEntries);
+    }
+
+    #endregion
+
+    #region View Density
+
+    [Fact]
+    public void ToggleCompact_SwitchesEveryRowAndIsRemembered()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Yeti") { Boost = new MicrophoneBoost(10f, 0f, 30f, 10f) });
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-2", "Headset"));
+        var preferences = new InMemoryPreferencesStore();
+        var viewModel = new MicrophoneListViewModel(fakeService, appearance: new AppearanceService(preferences));
+
+        // Act
+        viewModel.ToggleCompactCommand.Execute(null);
+        var reopened = new MicrophoneListViewModel(fakeService, appearance: new AppearanceService(preferences));
+
+        // Assert
+        Assert.True(viewModel.IsCompact);
+        Assert.All(viewModel.Microphones, m => Assert.False(m.IsDetailed));
+        Assert.False(viewModel.Microphones.Single(m => m.Id == "mic-1").IsBoostShown);
+        Assert.All(reopened.Microphones, m => Assert.True(m.IsCompact));
+    }
+
+    #endregion
+
+    #region Device Groups
+
+    private static readonly Guid WaveContainer = new("6a1c2f0e-3b4d-4e5f-8a9b-0c1d2e3f4a5b");
+
+    private static FakeAudioDeviceService.FakeMicrophone WaveEndpoint(string id, string endpoint) =>
+        new(id, $"{endpoint} (Elgato Wave)")
+        {
+            Properties = new DeviceProperties
+            {
+                DeviceId = id,
+                ContainerId = WaveContainer,
+                AdapterName = "Elgato Wave",
+                EndpointDescription = endpoint
+            }
+        };
+
+    [Fact]
+    public void Groups_EndpointsOfOneDeviceUnderOneHeader()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(WaveEndpoint("mic-1", "Microphone"));
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-2", "Yeti"));
+        fakeService.AddOrUpdateMicrophone(WaveEndpoint("mic-3", "Monitor Mix"));
+        var groups = new DeviceGroupService(new InMemoryPreferencesStore());
+
+        // Act
+        var viewModel = new MicrophoneListViewModel(fakeService, groupService: groups);
+
+        // Assert
+        Assert.Equal(new[] { "mic-1", "mic-3", "mic-2" }, viewModel.Microphones.Select(m => m.Id));
+        Assert.Equal("Elgato Wave: Microphone, Monitor Mix", viewModel.Microphones[0].GroupTitle);
+        Assert.False(viewModel.Microphones[1].IsGroupStart);
+        Assert.False(viewModel.Microphones[2].IsGroupStart);
+    }
+
+    [Fact]
+    public void Groups_NotShownWhenTurnedOff()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(WaveEndpoint("mic-1", "Microphone"));
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-2", "Yeti"));
+        fakeService.AddOrUpdateMicrophone(WaveEndpoint("mic-3", "Monitor Mix"));
+        var groups = new DeviceGroupService(new InMemoryPreferencesStore());
+        var viewModel = new MicrophoneListViewModel(fakeService, groupService: groups);
+
+        // Act
+        groups.IsEnabled = false;
+
+        // Assert
+        Assert.All(viewModel.Microphones, m => Assert.False(m.IsGroupStart));
+        Assert.All(viewModel.Microphones, m => Assert.True(m.IsCardShown));
+    }
+
+    [Fact]
+    public void ToggleGroupCollapsed_HidesMembersAndIsRemembered()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(WaveEndpoint("mic-1", "Microphone"));
+        fakeService.AddOrUpdateMicrophone(WaveEndpoint("mic-2", "Monitor Mix"));
+        var preferences = new InMemoryPreferencesStore();
+        var viewModel = new MicrophoneListViewModel(fakeService, groupService: new DeviceGroupService(preferences));
+
+        // Act
+        viewModel.ToggleGroupCollapsedCommand.Execute(viewModel.Microphones[0]);
+        var reopened = new MicrophoneListViewModel(fakeService, groupService: new DeviceGroupService(preferences));
+
+        // Assert
+        Assert.All(viewModel.Microphones, m => Assert.False(m.IsCardShown));
+        Assert.True(viewModel.Microphones[0].IsGroupCollapsed);
+        Assert.All(reopened.Microphones, m => Assert.True(m.IsInCollapsedGroup));
+    }
+
+    [Fact]
+    public void ToggleGroupMute_MutesEveryEndpointThenUnmutes()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(WaveEndpoint("mic-1", "Microphone"));
+        fakeService.AddOrUpdateMicrophone(WaveEndpoint("mic-2", "Monitor Mix"));
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-3", "Yeti"));
+        var viewModel = new MicrophoneListViewModel(fakeService, groupService: new DeviceGroupService(new InMemoryPreferencesStore()));
+        var header = viewModel.Microphones[0];
+
+        // Act
+        viewModel.ToggleGroupMuteCommand.Execute(header);
+        var mutedAll = new[] { "mic-1", "mic-2", "mic-3" }.Select(fakeService.IsMuted).ToArray();
+        viewModel.ToggleGroupMuteCommand.Execute(header);
+
+        // Assert
+        Assert.Equal(new[] { true, true, false }, mutedAll);
+        Assert.False(fakeService.IsMuted("mic-1"));
+        Assert.False(fakeService.IsMuted("mic-2"));
+        Assert.False(header.IsGroupMuted);
+    }
+
+    #endregion
+
+    #region Undo
+
+    [Fact]
+    public async Task Undo_RevertsSetBoth()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Microphone (Realtek)"));
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-2", "Yeti"));
+        fakeService.DefaultConsoleId = "mic-1";
+        fakeService.DefaultCommunicationsId = "mic-1";
+        var viewModel = new MicrophoneListViewModel(fakeService, undoService: new UndoService(fakeService));
+        await viewModel.Microphones.Single(m => m.Id == "mic-2").SetBothCommand.ExecuteAsync(null);
+
+        // Act
+        Assert.True(viewModel.CanUndo);
+        await viewModel.UndoCommand.ExecuteAsync(null);
+
+        // Assert
+        Assert.Equal("mic-1", fakeService.DefaultConsoleId);
+        Assert.Equal("mic-1", fakeService.DefaultCommunicationsId);
+        Assert.False(viewModel.CanUndo);
+    }
+
+    #endregion
+
+    #region Error Message Infrastructure
+
+    [Fact]
+    public void PrivacyWarning_ShownWhenWindowsBlocksDesktopApps()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic"));
+        var viewModel = new MicrophoneListViewModel(fakeService);
+        Assert.False(viewModel.HasPrivacyWarning);
+
+        // Act
+        fakeService.PrivacyStatus = new MicrophonePrivacyStatus { IsDesktopAppAccessAllowed = false };
+        viewModel.RefreshDevices();
+
+        // Assert
+        Assert.True(viewModel.HasPrivacyWarning);
+        Assert.Equal("Desktop apps are blocked from the microphone in Windows privacy settings", viewModel.PrivacyWarning);
+    }
+
+    [Fact]
+    public void ShowError_SetsErrorMessageAndHasError()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic"));
+        fakeService.DefaultConsoleId = "mic-1";
+
+        var viewModel = new MicrophoneListViewModel(fakeService);
+        Assert.False(viewModel.HasError);
+        Assert.Null(viewModel.ErrorMessage);
+
+        // Act
+        viewModel.ShowError("Test error message");
+
+        // Assert
+        Assert.True(viewModel.HasError);
+        Assert.Equal("Test error message", viewModel.ErrorMessage);
+    }
+
+    [Fact]
+    public void DismissError_ClearsErrorMessage()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic"));
+        fakeService.DefaultConsoleId = "mic-1";
+
+        var viewModel = new MicrophoneListViewModel(fakeService);
+        viewModel.ShowError("Test error");
+        Assert.True(viewModel.HasError);
+
+        // Act
+        viewModel.DismissError();
+
+        // Assert
+        Assert.False(viewModel.HasError);
         Assert.Null(viewModel.ErrorMessage);
     }
 
+    [Fact]
+    public void OperationFailedEvent_ShowsErrorWithHResult()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic"));
+        fakeService.DefaultConsoleId = "mic-1";
+
+        var viewModel = new MicrophoneListViewModel(fakeService);
+
+        // Act - service reports a swallowed volume failure
+        fakeService.RaiseOperationFailed("set volume", "mic-1", unchecked((int)0x88890004), "Device invalidated");
+
+        // Assert
+        Assert.True(viewModel.HasError);
+        Assert.Equal("Failed to set volume (0x88890004)", viewModel.ErrorMessage);
+    }
+
+    [Fact]
+    public void OperationFailedEvent_NamesDeviceAndKeepsStructuredDetails()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic"));
+
+        var viewModel = new MicrophoneListViewModel(fakeService);
+
+        // Act
+        fakeService.RaiseOperationFailed("set default device", "mic-1", unchecked((int)0x80070005), "Access is denied", "Desk Mic");
+
+        // Assert
+        Assert.Equal("Failed to set default device on Desk Mic (0x80070005)", viewModel.ErrorMessage);
+        var lastError = fakeService.LastError;
+        Assert.NotNull(lastError);
+        Assert.Equal(unchecked((int)0x80070005), lastError!.HResult);
+        Assert.Contains("HRESULT: 0x80070005", lastError.Details);
+        Assert.Contains("Device: Desk Mic [mic-1]", lastError.Details);
+    }
+
     #endregion
 }
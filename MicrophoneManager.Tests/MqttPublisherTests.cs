@@ -0,0 +1,95 @@
+using MicrophoneManager.Tests.Fakes;
+using MicrophoneManager.WinUI.Services;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for MQTT command parsing and topic settings. Broker connections aren't covered here.
+/// </summary>
+public class MqttPublisherTests
+{
+    [Theory]
+    [InlineData("toggle", "toggleMute")]
+    [InlineData(" MUTE ", "mute")]
+    [InlineData("ON", "mute")]
+    [InlineData("off", "unmute")]
+    public void ParseCommand_MapsPlainPayloads(string payload, string expectedCommand)
+    {
+        // Act
+        var request = MqttPublisher.ParseCommand(payload);
+
+        // Assert
+        Assert.Equal(expectedCommand, request?.Command);
+    }
+
+    [Fact]
+    public void ParseCommand_AcceptsJsonRequest()
+    {
+        // Act
+        var request = MqttPublisher.ParseCommand("""{"command":"setDefault","device":"Yeti"}""");
+
+        // Assert
+        Assert.Equal("setDefault", request?.Command);
+        Assert.Equal("Yeti", request?.Device);
+    }
+
+    [Fact]
+    public void ParseCommand_IgnoresUnknownPayload()
+    {
+        // Act & Assert
+        Assert.Null(MqttPublisher.ParseCommand("dance"));
+        Assert.Null(MqttPublisher.ParseCommand("{broken"));
+    }
+
+    [Fact]
+    public void Topics_UseDefaultsUntilConfigured()
+    {
+        // Arrange
+        var preferences = new InMemoryPreferencesStore();
+        using var publisher = new MqttPublisher(new ControlCommandDispatcher(new FakeAudioDeviceService()), preferences);
+
+        // Act
+        preferences.SetString(MqttPublisher.MuteTopicPreference, "office/onair");
+
+        // Assert
+        Assert.Equal("office/onair", publisher.MuteTopic);
+        Assert.Equal(MqttPublisher.DefaultCommandTopic, publisher.CommandTopic);
+        Assert.Equal(MqttPublisher.DefaultPort, publisher.Port);
+    }
+
+    [Fact]
+    public void UseTls_IsOnOnceCredentialsAreSet()
+    {
+        // Arrange
+        var preferences = new InMemoryPreferencesStore();
+        using var publisher = new MqttPublisher(new ControlCommandDispatcher(new FakeAudioDeviceService()), preferences);
+        Assert.False(publisher.UseTls);
+
+        // Act
+        preferences.SetString(MqttPublisher.UsernamePreference, "studio");
+
+        // Assert
+        Assert.True(publisher.UseTls);
+        Assert.Equal(MqttPublisher.DefaultTlsPort, publisher.Port);
+
+        preferences.SetBool(MqttPublisher.TlsPreference, false);
+        Assert.False(publisher.UseTls);
+        Assert.Equal(MqttPublisher.DefaultPort, publisher.Port);
+    }
+
+    [Fact]
+    public void Start_WithoutBroker_StaysDisconnected()
+    {
+        // Arrange
+        var preferences = new InMemoryPreferencesStore();
+        preferences.SetBool(MqttPublisher.EnabledPreference, true);
+        using var publisher = new MqttPublisher(new ControlCommandDispatcher(new FakeAudioDeviceService()), preferences);
+
+        // Act
+        publisher.Start();
+
+        // Assert
+        Assert.False(publisher.IsConnected);
+    }
+}
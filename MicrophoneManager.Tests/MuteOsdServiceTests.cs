@@ -0,0 +1,104 @@
+using MicrophoneManager.Tests.Fakes;
+using MicrophoneManager.WinUI.Services;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for the settings of the on-screen banner shown when mute is toggled.
+/// </summary>
+public class MuteOsdServiceTests
+{
+    [Fact]
+    public void Defaults_AreOnTopCenterAndDefaultDuration()
+    {
+        // Arrange
+        var service = new MuteOsdService(new InMemoryPreferencesStore());
+
+        // Act & Assert
+        Assert.True(service.IsEnabled);
+        Assert.Equal(OsdPosition.TopCenter, service.Position);
+        Assert.Equal(MuteOsdService.DefaultDurationMs, service.DurationMs);
+    }
+
+    [Fact]
+    public void Settings_AreStoredAndRaiseChanged()
+    {
+        // Arrange
+        var preferences = new InMemoryPreferencesStore();
+        var service = new MuteOsdService(preferences);
+        var changes = 0;
+        service.Changed += (s, e) => changes++;
+
+        // Act
+        service.IsEnabled = false;
+        service.IsEnabled = false;
+        service.Position = OsdPosition.BottomRight;
+        service.DurationMs = 3000;
+
+        // Assert
+        var reloaded = new MuteOsdService(preferences);
+        Assert.False(reloaded.IsEnabled);
+        Assert.Equal(OsdPosition.BottomRight, reloaded.Position);
+        Assert.Equal(3000, reloaded.DurationMs);
+        Assert.Equal(3, changes);
+    }
+
+    [Theory]
+    [InlineData(100, MuteOsdService.MinDurationMs)]
+    [InlineData(60000, MuteOsdService.MaxDurationMs)]
+    public void DurationMs_IsClamped(int value, int expected)
+    {
+        // Arrange
+        var service = new MuteOsdService(new InMemoryPreferencesStore());
+
+        // Act
+        service.DurationMs = value;
+
+        // Assert
+        Assert.Equal(expected, service.DurationMs);
+    }
+
+    [Fact]
+    public void Defaults_AreNotWrittenToPreferences()
+    {
+        // Arrange
+        var preferences = new InMemoryPreferencesStore();
+        var service = new MuteOsdService(preferences);
+        service.Position = OsdPosition.TopLeft;
+        service.DurationMs = 2000;
+
+        // Act
+        service.Position = OsdPosition.TopCenter;
+        service.DurationMs = MuteOsdService.DefaultDurationMs;
+
+        // Assert
+        Assert.Null(preferences.GetString(MuteOsdService.PositionPreference));
+        Assert.Null(preferences.GetString(MuteOsdService.DurationPreference));
+    }
+
+    [Theory]
+    [InlineData("bottomleft", OsdPosition.BottomLeft)]
+    [InlineData("Sideways", OsdPosition.TopCenter)]
+    [InlineData("42", OsdPosition.TopCenter)]
+    public void Position_ParsesStoredValue(string stored, OsdPosition expected)
+    {
+        // Arrange
+        var preferences = new InMemoryPreferencesStore();
+        preferences.SetString(MuteOsdService.PositionPreference, stored);
+
+        // Act & Assert
+        Assert.Equal(expected, new MuteOsdService(preferences).Position);
+    }
+
+    [Theory]
+    [InlineData(true, false, "Muted")]
+    [InlineData(false, false, "Live")]
+    [InlineData(true, true, "All microphones muted")]
+    [InlineData(false, true, "All microphones live")]
+    public void GetMessage_DescribesNewState(bool isMuted, bool allMicrophones, string expected)
+    {
+        // Act & Assert
+        Assert.Equal(expected, MuteOsdService.GetMessage(isMuted, allMicrophones));
+    }
+}
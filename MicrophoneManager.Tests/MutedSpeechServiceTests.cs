@@ -0,0 +1,153 @@
+using MicrophoneManager.Tests.Fakes;
+using MicrophoneManager.WinUI.Services;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for noticing speech on the muted default microphone.
+/// </summary>
+public class MutedSpeechServiceTests
+{
+    private DateTime _now = new(2026, 10, 17, 9, 0, 0, DateTimeKind.Utc);
+
+    private static (FakeAudioDeviceService AudioService, FakeAudioDeviceService.FakeMicrophone Microphone) CreateAudioService()
+    {
+        var fakeService = new FakeAudioDeviceService();
+        var microphone = new FakeAudioDeviceService.FakeMicrophone("mic-1", "Headset") { IsMuted = true };
+        fakeService.AddOrUpdateMicrophone(microphone);
+        fakeService.DefaultConsoleId = "mic-1";
+        fakeService.DefaultCommunicationsId = "mic-1";
+        return (fakeService, microphone);
+    }
+
+    private MutedSpeechService CreateService(FakeAudioDeviceService audioService, InMemoryPreferencesStore? preferences = null)
+    {
+        return new MutedSpeechService(audioService, preferences ?? new InMemoryPreferencesStore(), () => _now);
+    }
+
+    // Samples every 100 ms for the given time
+    private void SampleFor(MutedSpeechService service, TimeSpan duration)
+    {
+        for (var elapsed = TimeSpan.Zero; elapsed <= duration; elapsed += TimeSpan.FromMilliseconds(100))
+        {
+            service.Sample();
+            _now += TimeSpan.FromMilliseconds(100);
+        }
+    }
+
+    [Fact]
+    public void SustainedSpeech_WhileMuted_RaisesAlert()
+    {
+        // Arrange
+        var (audioService, microphone) = CreateAudioService();
+        using var service = CreateService(audioService);
+        MutedSpeechService.MutedSpeechEventArgs? alert = null;
+        service.SpeechWhileMuted += (s, e) => alert = e;
+        microphone.InputPeakDb = -20;
+
+        // Act
+        SampleFor(service, TimeSpan.FromSeconds(1));
+
+        // Assert
+        Assert.NotNull(alert);
+        Assert.Equal("mic-1", alert!.DeviceId);
+        Assert.Equal("Headset", alert.DeviceName);
+    }
+
+    [Fact]
+    public void ShortNoise_DoesNotAlert()
+    {
+        // Arrange
+        var (audioService, microphone) = CreateAudioService();
+        using var service = CreateService(audioService);
+        var alerts = 0;
+        service.SpeechWhileMuted += (s, e) => alerts++;
+
+        // Act - a cough, then silence
+        microphone.InputPeakDb = -10;
+        SampleFor(service, TimeSpan.FromMilliseconds(300));
+        microphone.InputPeakDb = -70;
+        SampleFor(service, TimeSpan.FromSeconds(1));
+
+        // Assert
+        Assert.Equal(0, alerts);
+    }
+
+    [Fact]
+    public void Unmuted_DoesNotAlert()
+    {
+        // Arrange
+        var (audioService, microphone) = CreateAudioService();
+        microphone.IsMuted = false;
+        microphone.InputPeakDb = -20;
+        using var service = CreateService(audioService);
+        var alerts = 0;
+        service.SpeechWhileMuted += (s, e) => alerts++;
+
+        // Act
+        SampleFor(service, TimeSpan.FromSeconds(2));
+
+        // Assert
+        Assert.Equal(0, alerts);
+    }
+
+    [Fact]
+    public void Cooldown_SuppressesRepeatAlerts_UntilUnmuted()
+    {
+        // Arrange
+        var (audioService, microphone) = CreateAudioService();
+        using var service = CreateService(audioService);
+        var alerts = 0;
+        service.SpeechWhileMuted += (s, e) => alerts++;
+        microphone.InputPeakDb = -20;
+
+        // Act & Assert
+        SampleFor(service, TimeSpan.FromSeconds(5));
+        Assert.Equal(1, alerts);
+
+        microphone.IsMuted = false;
+        service.Sample();
+        microphone.IsMuted = true;
+        SampleFor(service, TimeSpan.FromSeconds(1));
+        Assert.Equal(2, alerts);
+    }
+
+    [Fact]
+    public void ConfiguredThreshold_IsUsed()
+    {
+        // Arrange
+        var (audioService, microphone) = CreateAudioService();
+        var preferences = new InMemoryPreferencesStore();
+        preferences.SetString(MutedSpeechService.ThresholdPreference, "-15");
+        using var service = CreateService(audioService, preferences);
+        var alerts = 0;
+        service.SpeechWhileMuted += (s, e) => alerts++;
+        microphone.InputPeakDb = -20;
+
+        // Act
+        SampleFor(service, TimeSpan.FromSeconds(2));
+
+        // Assert
+        Assert.Equal(-15, service.ThresholdDb);
+        Assert.Equal(0, alerts);
+    }
+
+    [Fact]
+    public void Disabled_DoesNotAlert()
+    {
+        // Arrange
+        var (audioService, microphone) = CreateAudioService();
+        using var service = CreateService(audioService);
+        service.IsEnabled = false;
+        var alerts = 0;
+        service.SpeechWhileMuted += (s, e) => alerts++;
+        microphone.InputPeakDb = -20;
+
+        // Act
+        SampleFor(service, TimeSpan.FromSeconds(2));
+
+        // Assert
+        Assert.Equal(0, alerts);
+    }
+}
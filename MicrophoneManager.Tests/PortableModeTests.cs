@@ -0,0 +1,33 @@
+using MicrophoneManager.WinUI.Services;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for detecting portable mode from the command line.
+/// </summary>
+public class PortableModeTests
+{
+    [Fact]
+    public void Initialize_RemovesSwitchAndEnables()
+    {
+        // Act
+        var remaining = PortableMode.Initialize(new[] { "--portable", "--mute" });
+
+        // Assert
+        Assert.True(PortableMode.IsEnabled);
+        Assert.Equal(new[] { "--mute" }, remaining);
+        Assert.Equal(Path.Combine(PortableMode.Directory, "config.toml"), PortableMode.ConfigPath);
+    }
+
+    [Fact]
+    public void Initialize_WithoutSwitchOrMarker_StaysInstalled()
+    {
+        // Act
+        var remaining = PortableMode.Initialize(new[] { "--list" });
+
+        // Assert
+        Assert.Equal(new[] { "--list" }, remaining);
+        Assert.Equal(File.Exists(Path.Combine(PortableMode.Directory, PortableMode.MarkerFileName)), PortableMode.IsEnabled);
+    }
+}
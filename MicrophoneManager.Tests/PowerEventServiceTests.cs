@@ -0,0 +1,39 @@
+using MicrophoneManager.Tests.Fakes;
+using MicrophoneManager.WinUI.Services;
+using MicrophoneManager.WinUI.ViewModels;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for refreshing state after resume from sleep.
+/// </summary>
+public class PowerEventServiceTests
+{
+    [Fact]
+    public async Task OnResume_RebuildsAudioServiceAndRefreshesTray()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic"));
+        fakeService.DefaultConsoleId = "mic-1";
+        var trayViewModel = new TrayViewModel(fakeService, _ => { });
+
+        // The mic was muted while asleep and no notification made it through
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic") { IsMuted = true });
+        Assert.False(trayViewModel.IsMuted);
+
+        var service = new PowerEventService(fakeService) { ResumeSettleDelay = TimeSpan.Zero };
+        var resumed = false;
+        service.Resumed += (s, e) => resumed = true;
+
+        // Act
+        await service.OnResumeAsync();
+
+        // Assert
+        Assert.Equal(1, fakeService.ResumeRecoveryCount);
+        Assert.True(resumed);
+        Assert.True(trayViewModel.IsMuted);
+        Assert.Equal("Desk Mic (Muted)", trayViewModel.TooltipText);
+    }
+}
@@ -0,0 +1,118 @@
+using MicrophoneManager.Tests.Fakes;
+using MicrophoneManager.WinUI.Services;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for the preferences schema version and each migration step.
+/// </summary>
+public class PreferencesMigratorTests
+{
+    [Fact]
+    public void Migrate_UnversionedStore_RunsAllStepsAndRecordsVersion()
+    {
+        // Arrange
+        var preferences = new InMemoryPreferencesStore();
+        var order = new List<int>();
+        var migrator = new PreferencesMigrator(new[]
+        {
+            new PreferencesMigrator.Migration(1, "one", _ => order.Add(1)),
+            new PreferencesMigrator.Migration(2, "two", _ => order.Add(2)),
+        });
+
+        // Act
+        var ran = migrator.Migrate(preferences);
+
+        // Assert
+        Assert.Equal(new[] { 1, 2 }, order);
+        Assert.Equal(new[] { "one", "two" }, ran);
+        Assert.Equal(2, PreferencesMigrator.GetVersion(preferences));
+    }
+
+    [Fact]
+    public void Migrate_SkipsStepsAlreadyApplied()
+    {
+        // Arrange
+        var preferences = new InMemoryPreferencesStore();
+        PreferencesMigrator.SetVersion(preferences, 1);
+        var order = new List<int>();
+        var migrator = new PreferencesMigrator(new[]
+        {
+            new PreferencesMigrator.Migration(1, "one", _ => order.Add(1)),
+            new PreferencesMigrator.Migration(2, "two", _ => order.Add(2)),
+        });
+
+        // Act
+        migrator.Migrate(preferences);
+        migrator.Migrate(preferences);
+
+        // Assert
+        Assert.Equal(new[] { 2 }, order);
+    }
+
+    [Fact]
+    public void Migrate_FailedStep_ResumesFromIt()
+    {
+        // Arrange
+        var preferences = new InMemoryPreferencesStore();
+        var failSecond = true;
+        var order = new List<int>();
+        var migrator = new PreferencesMigrator(new[]
+        {
+            new PreferencesMigrator.Migration(1, "one", _ => order.Add(1)),
+            new PreferencesMigrator.Migration(2, "two", _ =>
+            {
+                if (failSecond) throw new InvalidOperationException("disk full");
+                order.Add(2);
+            }),
+        });
+
+        // Act
+        Assert.Throws<InvalidOperationException>(() => migrator.Migrate(preferences));
+        failSecond = false;
+        migrator.Migrate(preferences);
+
+        // Assert
+        Assert.Equal(new[] { 1, 2 }, order);
+        Assert.Equal(2, PreferencesMigrator.GetVersion(preferences));
+    }
+
+    [Fact]
+    public void Migrate_NewerStore_IsLeftAlone()
+    {
+        // Arrange
+        var preferences = new InMemoryPreferencesStore();
+        PreferencesMigrator.SetVersion(preferences, PreferencesMigrator.CurrentVersion + 1);
+        preferences.SetString(MqttPublisher.EnabledPreference, "true");
+
+        // Act
+        var ran = new PreferencesMigrator().Migrate(preferences);
+
+        // Assert
+        Assert.Empty(ran);
+        Assert.Equal("true", preferences.GetString(MqttPublisher.EnabledPreference));
+        Assert.Equal(PreferencesMigrator.CurrentVersion + 1, PreferencesMigrator.GetVersion(preferences));
+    }
+
+    [Fact]
+    public void Version1_ConvertsTextBooleans()
+    {
+        // Arrange
+        var preferences = new InMemoryPreferencesStore();
+        preferences.SetString(MqttPublisher.EnabledPreference, " True ");
+        preferences.SetString(SessionLockService.MuteOnLockPreference, "0");
+        preferences.SetString(DevicePriorityService.AutoFailoverPreference, "maybe");
+        preferences.SetString(MqttPublisher.HostPreference, "1");
+
+        // Act
+        new PreferencesMigrator().Migrate(preferences);
+
+        // Assert
+        Assert.Equal(true, preferences.Values[MqttPublisher.EnabledPreference]);
+        Assert.Equal(false, preferences.Values[SessionLockService.MuteOnLockPreference]);
+        Assert.Equal("maybe", preferences.GetString(DevicePriorityService.AutoFailoverPreference));
+        Assert.Equal("1", preferences.GetString(MqttPublisher.HostPreference));
+        Assert.Equal(PreferencesMigrator.CurrentVersion, PreferencesMigrator.GetVersion(preferences));
+    }
+}
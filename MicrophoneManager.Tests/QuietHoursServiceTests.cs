@@ -0,0 +1,97 @@
+using MicrophoneManager.Tests.Fakes;
+using MicrophoneManager.WinUI.Models;
+using MicrophoneManager.WinUI.Services;
+using MicrophoneManager.WinUI.ViewModels;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for scheduled quiet hours: muting, unmuting, manual override and the tray indicator.
+/// </summary>
+public class QuietHoursServiceTests
+{
+    private DateTime _now = new(2026, 3, 2, 21, 0, 0);
+
+    private (FakeAudioDeviceService Service, QuietHoursService QuietHours) Create()
+    {
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic"));
+        fakeService.DefaultConsoleId = "mic-1";
+
+        var quietHours = new QuietHoursService(fakeService, new InMemoryPreferencesStore(), () => _now);
+        quietHours.SetWindows(new[] { new QuietHoursWindow(new TimeOnly(22, 0), new TimeOnly(9, 0)) });
+        return (fakeService, quietHours);
+    }
+
+    [Theory]
+    [InlineData("22:00-07:00", 23, true)]
+    [InlineData("22:00-07:00", 6, true)]
+    [InlineData("22:00-07:00", 12, false)]
+    [InlineData("12:00-13:00", 12, true)]
+    [InlineData("12:00-13:00", 13, false)]
+    public void QuietHoursWindow_ContainsHandlesMidnight(string text, int hour, bool expected)
+    {
+        Assert.True(QuietHoursWindow.TryParse(text, out var window));
+        Assert.Equal(expected, window!.Contains(new DateTime(2026, 3, 2, hour, 0, 0)));
+    }
+
+    [Fact]
+    public void Evaluate_MutesDuringWindowAndUnmutesAfterwards()
+    {
+        // Arrange
+        var (fakeService, quietHours) = Create();
+        Assert.False(fakeService.IsMuted("mic-1"));
+
+        // Act
+        _now = new DateTime(2026, 3, 2, 22, 0, 30);
+        quietHours.Evaluate();
+        var mutedAtNight = fakeService.IsMuted("mic-1");
+        var quietUntil = quietHours.QuietUntil;
+
+        _now = new DateTime(2026, 3, 3, 9, 0, 30);
+        quietHours.Evaluate();
+
+        // Assert
+        Assert.True(mutedAtNight);
+        Assert.Equal(new DateTime(2026, 3, 3, 9, 0, 0), quietUntil);
+        Assert.False(fakeService.IsMuted("mic-1"));
+        Assert.False(quietHours.IsActive);
+    }
+
+    [Fact]
+    public void ManualUnmute_OverridesUntilWindowEnds()
+    {
+        // Arrange
+        var (fakeService, quietHours) = Create();
+        _now = new DateTime(2026, 3, 2, 23, 0, 0);
+        quietHours.Evaluate();
+
+        // Act
+        fakeService.SetMute("mic-1", false);
+        fakeService.RaiseDefaultVolumeChanged("mic-1", 1.0f, isMuted: false);
+        _now = _now.AddMinutes(30);
+        quietHours.Evaluate();
+
+        // Assert
+        Assert.True(quietHours.IsOverridden);
+        Assert.False(quietHours.IsActive);
+        Assert.False(fakeService.IsMuted("mic-1"));
+    }
+
+    [Fact]
+    public void TrayTooltip_ShowsQuietHoursEnd()
+    {
+        // Arrange
+        var (fakeService, quietHours) = Create();
+        var trayViewModel = new TrayViewModel(fakeService, _ => { }, quietHoursService: quietHours);
+
+        // Act
+        _now = new DateTime(2026, 3, 2, 22, 30, 0);
+        quietHours.Evaluate();
+
+        // Assert
+        Assert.True(trayViewModel.IsMuted);
+        Assert.Equal($"Desk Mic (Muted) - quiet hours until {new DateTime(2026, 3, 3, 9, 0, 0):t}", trayViewModel.TooltipText);
+    }
+}
@@ -0,0 +1,104 @@
+using MicrophoneManager.Tests.Fakes;
+using MicrophoneManager.WinUI.Services;
+using Microsoft.Extensions.Logging;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for the rolling log file: level filtering, scopes and rollover.
+/// </summary>
+public class RollingFileLoggerProviderTests : IDisposable
+{
+    private readonly string _directory = Path.Combine(Path.GetTempPath(), "MicManagerTests", Guid.NewGuid().ToString("N"));
+
+    public void Dispose()
+    {
+        try { Directory.Delete(_directory, recursive: true); } catch { }
+    }
+
+    [Fact]
+    public void Log_WritesEntriesAtOrAboveThePreferredLevel()
+    {
+        // Arrange
+        var preferences = new InMemoryPreferencesStore();
+        preferences.SetString(RollingFileLoggerProvider.LogLevelPreference, "Warning");
+        using var provider = new RollingFileLoggerProvider(_directory, preferences);
+        var logger = provider.CreateLogger("MicrophoneManager.WinUI.Services.AudioDeviceService");
+
+        // Act
+        logger.LogInformation("quiet");
+        logger.LogWarning("SetMute failed for {DeviceId}", "mic-1");
+        provider.Flush(TimeSpan.FromSeconds(5));
+
+        // Assert
+        var text = ReadLog(provider);
+        Assert.DoesNotContain("quiet", text);
+        Assert.Contains("[WRN]", text);
+        Assert.Contains("AudioDeviceService: SetMute failed for mic-1", text);
+    }
+
+    [Fact]
+    public void MinimumLevel_ChangesApplyAndPersist()
+    {
+        // Arrange
+        var preferences = new InMemoryPreferencesStore();
+        using var provider = new RollingFileLoggerProvider(_directory, preferences);
+        var logger = provider.CreateLogger("Test");
+
+        // Act
+        provider.MinimumLevel = LogLevel.Trace;
+        logger.LogTrace("detail");
+        provider.Flush(TimeSpan.FromSeconds(5));
+
+        // Assert
+        Assert.Contains("[TRC]", ReadLog(provider));
+        Assert.Equal("Trace", preferences.GetString(RollingFileLoggerProvider.LogLevelPreference));
+    }
+
+    [Fact]
+    public void Log_IncludesScopes()
+    {
+        // Arrange
+        using var provider = new RollingFileLoggerProvider(_directory);
+        provider.SetScopeProvider(new LoggerExternalScopeProvider());
+        var logger = provider.CreateLogger("ComThreadService");
+
+        // Act
+        using (logger.BeginScope("COM {Operation}", "SetDefaultEndpoint"))
+        {
+            logger.LogWarning("slow");
+        }
+        provider.Flush(TimeSpan.FromSeconds(5));
+
+        // Assert
+        Assert.Contains("ComThreadService => COM SetDefaultEndpoint: slow", ReadLog(provider));
+    }
+
+    [Fact]
+    public void Log_RollsOverWhenTheFileIsFull()
+    {
+        // Arrange
+        using var provider = new RollingFileLoggerProvider(_directory);
+        var logger = provider.CreateLogger("Test");
+        var line = new string('x', 64 * 1024);
+
+        // Act
+        for (var i = 0; i < RollingFileLoggerProvider.MaxFileBytes / line.Length + 2; i++)
+        {
+            logger.LogWarning(line);
+        }
+        provider.Flush(TimeSpan.FromSeconds(5));
+
+        // Assert
+        Assert.True(File.Exists(Path.Combine(_directory, "MicrophoneManager.1.log")));
+        Assert.True(new FileInfo(provider.CurrentFilePath).Length < RollingFileLoggerProvider.MaxFileBytes);
+    }
+
+    private static string ReadLog(RollingFileLoggerProvider provider)
+    {
+        using var stream = new FileStream(provider.CurrentFilePath, FileMode.Open, FileAccess.Read, FileShare.ReadWrite | FileShare.Delete);
+        using var reader = new StreamReader(stream);
+        return reader.ReadToEnd();
+    }
+}
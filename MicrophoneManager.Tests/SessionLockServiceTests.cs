@@ -0,0 +1,53 @@
+using MicrophoneManager.Tests.Fakes;
+using MicrophoneManager.WinUI.Services;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for muting on workstation lock and restoring on unlock.
+/// </summary>
+public class SessionLockServiceTests
+{
+    private static FakeAudioDeviceService CreateService()
+    {
+        return FakeAudioDeviceService.WithMicrophones(
+            "mic-1",
+            new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic"),
+            new FakeAudioDeviceService.FakeMicrophone("mic-2", "Headset") { IsMuted = true });
+    }
+
+    [Fact]
+    public void LockThenUnlock_MutesAllAndRestoresPreviousStates()
+    {
+        // Arrange
+        var fakeService = CreateService();
+        var service = new SessionLockService(fakeService, new InMemoryPreferencesStore()) { IsMuteOnLockEnabled = true };
+
+        // Act
+        service.OnSessionLocked();
+        var mutedWhileLocked = fakeService.IsMuted("mic-1");
+        service.OnSessionUnlocked();
+
+        // Assert
+        Assert.True(mutedWhileLocked);
+        Assert.False(fakeService.IsMuted("mic-1"));
+        Assert.True(fakeService.IsMuted("mic-2"));
+        Assert.False(service.IsMutedForLock);
+    }
+
+    [Fact]
+    public void Lock_DoesNothingWhenPreferenceIsOff()
+    {
+        // Arrange
+        var fakeService = CreateService();
+        var service = new SessionLockService(fakeService, new InMemoryPreferencesStore());
+
+        // Act
+        service.OnSessionLocked();
+
+        // Assert
+        Assert.False(fakeService.IsMuted("mic-1"));
+        Assert.False(service.IsMutedForLock);
+    }
+}
@@ -0,0 +1,144 @@
+using System.Text.Json;
+using MicrophoneManager.Tests.Fakes;
+using MicrophoneManager.WinUI.Services;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for exporting preferences to JSON and validating them on the way back in.
+/// </summary>
+public class SettingsTransferServiceTests
+{
+    private static InMemoryPreferencesStore CreatePreferences()
+    {
+        var preferences = new InMemoryPreferencesStore();
+        preferences.SetString(ProfileService.ProfilesPreference, """[{"Name":"Desk"}]""");
+        preferences.SetStringList(HotkeyService.HotkeysPreference, new[] { "ToggleMute=Ctrl+Alt+M" });
+        preferences.SetStringList(DevicePriorityService.PriorityListPreference, new[] { "mic-1", "mic-2" });
+        preferences.SetBool(DevicePriorityService.AutoFailoverPreference, true);
+        preferences.SetString(RollingFileLoggerProvider.LogLevelPreference, "Debug");
+        preferences.SetString(MqttPublisher.PasswordPreference, "hunter2");
+        preferences.SetString(LocalApiServer.TokenPreference, "secret-token");
+        return preferences;
+    }
+
+    [Fact]
+    public void Export_ThenImport_RestoresEverythingButSecrets()
+    {
+        // Arrange
+        var json = new SettingsTransferService(CreatePreferences()).Export();
+        var target = new InMemoryPreferencesStore();
+
+        // Act
+        var ok = new SettingsTransferService(target).TryImport(json, out var imported, out var error);
+
+        // Assert
+        Assert.True(ok, error);
+        Assert.Equal(5, imported);
+        Assert.Equal(new[] { "mic-1", "mic-2" }, target.GetStringList(DevicePriorityService.PriorityListPreference));
+        Assert.Equal(new[] { "ToggleMute=Ctrl+Alt+M" }, target.GetStringList(HotkeyService.HotkeysPreference));
+        Assert.True(target.GetBool(DevicePriorityService.AutoFailoverPreference, false));
+        Assert.Equal("Debug", target.GetString(RollingFileLoggerProvider.LogLevelPreference));
+        Assert.Equal("Desk", JsonDocument.Parse(target.GetString(ProfileService.ProfilesPreference)!).RootElement[0].GetProperty("Name").GetString());
+        Assert.Null(target.GetString(MqttPublisher.PasswordPreference));
+        Assert.Null(target.GetString(LocalApiServer.TokenPreference));
+    }
+
+    [Fact]
+    public void Import_SkipsHooksServersAndRemoteControl()
+    {
+        // Arrange
+        var source = CreatePreferences();
+        source.SetString(HookService.OnMutePreference, "calc.exe");
+        source.SetString(UpdateService.FeedUrlPreference, "https://example.com/feed");
+        source.SetString(UpdateService.ProxyPreference, "http://proxy.example.com:8080");
+        source.SetBool(MqttPublisher.EnabledPreference, true);
+        source.SetString(MqttPublisher.HostPreference, "broker.example.com");
+        source.SetString(MqttPublisher.CommandTopicPreference, "mic/set");
+        source.SetBool(LocalApiServer.EnabledPreference, true);
+        source.SetString(LocalApiServer.PortPreference, "8765");
+        var json = new SettingsTransferService(source).Export();
+        var target = new InMemoryPreferencesStore();
+
+        // Act
+        var ok = new SettingsTransferService(target).TryImport(json, out var imported, out var skipped, out var error);
+
+        // Assert
+        Assert.True(ok, error);
+        Assert.Equal(5, imported);
+        Assert.Equal(8, skipped.Count);
+        Assert.Contains(HookService.OnMutePreference, skipped);
+        Assert.Contains(UpdateService.FeedUrlPreference, skipped);
+        Assert.Contains(UpdateService.ProxyPreference, skipped);
+        Assert.Contains(MqttPublisher.EnabledPreference, skipped);
+        Assert.Contains(MqttPublisher.HostPreference, skipped);
+        Assert.Contains(MqttPublisher.CommandTopicPreference, skipped);
+        Assert.Contains(LocalApiServer.EnabledPreference, skipped);
+        Assert.Contains(LocalApiServer.PortPreference, skipped);
+        Assert.Null(target.GetString(HookService.OnMutePreference));
+        Assert.Null(target.GetString(UpdateService.FeedUrlPreference));
+        Assert.Null(target.GetString(UpdateService.ProxyPreference));
+        Assert.False(target.GetBool(MqttPublisher.EnabledPreference, false));
+        Assert.Null(target.GetString(MqttPublisher.HostPreference));
+        Assert.False(target.GetBool(LocalApiServer.EnabledPreference, false));
+    }
+
+    [Fact]
+    public void Export_EmbedsProfilesAsJson()
+    {
+        // Act
+        using var json = JsonDocument.Parse(new SettingsTransferService(CreatePreferences()).Export());
+
+        // Assert
+        Assert.Equal(SettingsTransferService.FormatName, json.RootElement.GetProperty("format").GetString());
+        Assert.Equal(JsonValueKind.Array, json.RootElement.GetProperty("profiles").ValueKind);
+        Assert.False(json.RootElement.GetProperty("preferences").TryGetProperty(LocalApiServer.TokenPreference, out _));
+    }
+
+    [Fact]
+    public void Import_NewerVersion_IsRejected()
+    {
+        // Arrange
+        var target = new InMemoryPreferencesStore();
+        var json = $$"""{"format":"{{SettingsTransferService.FormatName}}","version":{{SettingsTransferService.CurrentVersion + 1}},"preferences":{"MqttHost":"x"}}""";
+
+        // Act
+        var ok = new SettingsTransferService(target).TryImport(json, out _, out var error);
+
+        // Assert
+        Assert.False(ok);
+        Assert.Contains("newer version", error);
+        Assert.Empty(target.Values);
+    }
+
+    [Fact]
+    public void Import_BadValue_WritesNothing()
+    {
+        // Arrange
+        var target = new InMemoryPreferencesStore();
+        var json = $$"""{"format":"{{SettingsTransferService.FormatName}}","version":1,"preferences":{"MqttHost":"x","MqttPort":1883}}""";
+
+        // Act
+        var ok = new SettingsTransferService(target).TryImport(json, out _, out var error);
+
+        // Assert
+        Assert.False(ok);
+        Assert.Contains("MqttPort", error);
+        Assert.Empty(target.Values);
+    }
+
+    [Theory]
+    [InlineData("{not json")]
+    [InlineData("""{"format":"SomethingElse","version":1}""")]
+    [InlineData("""{"format":"MicrophoneManagerSettings"}""")]
+    public void Import_ForeignFile_IsRejected(string json)
+    {
+        // Act
+        var ok = SettingsTransferService.TryRead(json, out _, out _, out var error);
+
+        // Assert
+        Assert.False(ok);
+        Assert.NotNull(error);
+    }
+}
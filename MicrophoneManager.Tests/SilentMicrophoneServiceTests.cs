@@ -0,0 +1,137 @@
+using MicrophoneManager.Tests.Fakes;
+using MicrophoneManager.WinUI.Models;
+using MicrophoneManager.WinUI.Services;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for warning about a default microphone that records only silence.
+/// </summary>
+public class SilentMicrophoneServiceTests
+{
+    private DateTime _now = new(2026, 10, 17, 9, 0, 0, DateTimeKind.Utc);
+
+    private static (FakeAudioDeviceService AudioService, FakeAudioDeviceService.FakeMicrophone Microphone) CreateAudioService(bool recording = true)
+    {
+        var fakeService = new FakeAudioDeviceService();
+        var microphone = new FakeAudioDeviceService.FakeMicrophone("mic-1", "XLR Interface") { InputPeakDb = -96 };
+        fakeService.AddOrUpdateMicrophone(microphone);
+        fakeService.DefaultConsoleId = "mic-1";
+        fakeService.DefaultCommunicationsId = "mic-1";
+        if (recording)
+        {
+            fakeService.CaptureSessions["mic-1"] = new List<CaptureSession>
+            {
+                new() { SessionInstanceId = "session-1", DeviceId = "mic-1", ProcessId = 4242, ProcessName = "Teams.exe", IsActive = true },
+            };
+        }
+
+        return (fakeService, microphone);
+    }
+
+    private SilentMicrophoneService CreateService(FakeAudioDeviceService audioService, InMemoryPreferencesStore? preferences = null)
+    {
+        return new SilentMicrophoneService(audioService, preferences ?? new InMemoryPreferencesStore(), () => _now);
+    }
+
+    // Samples once a second for the given number of seconds
+    private void SampleFor(SilentMicrophoneService service, int seconds)
+    {
+        for (var i = 0; i <= seconds; i++)
+        {
+            service.Sample();
+            _now += TimeSpan.FromSeconds(1);
+        }
+    }
+
+    [Fact]
+    public void SilenceWhileRecording_WarnsOnce()
+    {
+        // Arrange
+        var (audioService, _) = CreateAudioService();
+        using var service = CreateService(audioService);
+        var warnings = new List<SilentMicrophoneService.SilentMicrophoneEventArgs>();
+        service.MicrophoneSilent += (s, e) => warnings.Add(e);
+
+        // Act
+        SampleFor(service, 90);
+
+        // Assert
+        var warning = Assert.Single(warnings);
+        Assert.Equal("mic-1", warning.DeviceId);
+        Assert.Equal("Teams.exe", warning.RecordingApplication);
+        Assert.Equal(TimeSpan.FromSeconds(30), warning.SilentFor);
+    }
+
+    [Fact]
+    public void SignalInBetween_RestartsThePeriod()
+    {
+        // Arrange
+        var (audioService, microphone) = CreateAudioService();
+        using var service = CreateService(audioService);
+        var warnings = 0;
+        service.MicrophoneSilent += (s, e) => warnings++;
+
+        // Act
+        SampleFor(service, 20);
+        microphone.InputPeakDb = -45;
+        service.Sample();
+        microphone.InputPeakDb = -96;
+        SampleFor(service, 20);
+
+        // Assert
+        Assert.Equal(0, warnings);
+    }
+
+    [Fact]
+    public void NobodyRecording_DoesNotWarn()
+    {
+        // Arrange
+        var (audioService, _) = CreateAudioService(recording: false);
+        using var service = CreateService(audioService);
+        var warnings = 0;
+        service.MicrophoneSilent += (s, e) => warnings++;
+
+        // Act
+        SampleFor(service, 60);
+
+        // Assert
+        Assert.Equal(0, warnings);
+    }
+
+    [Fact]
+    public void Muted_DoesNotWarn()
+    {
+        // Arrange
+        var (audioService, microphone) = CreateAudioService();
+        microphone.IsMuted = true;
+        using var service = CreateService(audioService);
+        var warnings = 0;
+        service.MicrophoneSilent += (s, e) => warnings++;
+
+        // Act
+        SampleFor(service, 60);
+
+        // Assert
+        Assert.Equal(0, warnings);
+    }
+
+    [Fact]
+    public void ConfiguredPeriod_IsUsed()
+    {
+        // Arrange
+        var (audioService, _) = CreateAudioService();
+        var preferences = new InMemoryPreferencesStore();
+        preferences.SetString(SilentMicrophoneService.SilencePreference, "10");
+        using var service = CreateService(audioService, preferences);
+        var warnings = 0;
+        service.MicrophoneSilent += (s, e) => warnings++;
+
+        // Act
+        SampleFor(service, 10);
+
+        // Assert
+        Assert.Equal(1, warnings);
+    }
+}
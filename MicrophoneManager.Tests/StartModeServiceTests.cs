@@ -0,0 +1,77 @@
+using MicrophoneManager.Tests.Fakes;
+using MicrophoneManager.WinUI.Services;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for what's shown at start and where the docked window reopens.
+/// </summary>
+public class StartModeServiceTests
+{
+    [Fact]
+    public void Mode_DefaultsToTray()
+    {
+        // Arrange
+        var service = new StartModeService(new InMemoryPreferencesStore());
+
+        // Act & Assert
+        Assert.Equal(StartMode.Tray, service.Mode);
+        Assert.Null(service.DockedPosition);
+    }
+
+    [Theory]
+    [InlineData("Docked", StartMode.Docked)]
+    [InlineData("flyout", StartMode.Flyout)]
+    [InlineData("7", StartMode.Tray)]
+    [InlineData("Sideways", StartMode.Tray)]
+    public void Mode_ParsesStoredValue(string stored, StartMode expected)
+    {
+        // Arrange
+        var preferences = new InMemoryPreferencesStore();
+        preferences.SetString(StartModeService.StartModePreference, stored);
+
+        // Act & Assert
+        Assert.Equal(expected, new StartModeService(preferences).Mode);
+    }
+
+    [Fact]
+    public void Mode_TrayClearsThePreference()
+    {
+        // Arrange
+        var preferences = new InMemoryPreferencesStore();
+        var service = new StartModeService(preferences) { Mode = StartMode.Docked };
+
+        // Act
+        service.Mode = StartMode.Tray;
+
+        // Assert
+        Assert.Null(preferences.GetString(StartModeService.StartModePreference));
+    }
+
+    [Fact]
+    public void DockedPosition_RoundTripsIncludingNegativeCoordinates()
+    {
+        // Arrange - a monitor left of the primary one
+        var preferences = new InMemoryPreferencesStore();
+        var service = new StartModeService(preferences);
+
+        // Act
+        service.DockedPosition = (-1800, 240);
+
+        // Assert
+        Assert.Equal("-1800,240", preferences.GetString(StartModeService.DockedPositionPreference));
+        Assert.Equal((-1800, 240), new StartModeService(preferences).DockedPosition);
+    }
+
+    [Fact]
+    public void DockedPosition_IgnoresMalformedValue()
+    {
+        // Arrange
+        var preferences = new InMemoryPreferencesStore();
+        preferences.SetString(StartModeService.DockedPositionPreference, "100;200");
+
+        // Act & Assert
+        Assert.Null(new StartModeService(preferences).DockedPosition);
+    }
+}
@@ -1,4 +1,5 @@
 using Xunit;
+using MicrophoneManager.Tests.Fakes;
 using MicrophoneManager.WinUI.Services;
 
 namespace MicrophoneManager.Tests;
@@ -18,7 +19,7 @@ public class StartupServiceTests
     {
         // Arrange & Act & Assert
         // This test verifies the method doesn't throw when accessing registry
-        var exception = Record.Exception(() => StartupService.IsStartupEnabled());
+        var exception = Record.Exception(() => StartupService.IsStartupEnabled(new InMemoryPreferencesStore()));
         Assert.Null(exception);
     }
 
@@ -26,7 +27,7 @@ public class StartupServiceTests
     public void IsStartupEnabled_ReturnsBooleanValue()
     {
         // Arrange & Act
-        var result = StartupService.IsStartupEnabled();
+        var result = StartupService.IsStartupEnabled(new InMemoryPreferencesStore());
 
         // Assert - should return a valid boolean (no exceptions)
         Assert.IsType<bool>(result);
@@ -43,7 +44,7 @@ public class StartupServiceTests
         var exception = Record.Exception(() =>
         {
             // Store original state
-            var originalState = StartupService.IsStartupEnabled();
+            var originalState = StartupService.IsStartupEnabled(new InMemoryPreferencesStore());
             
             // Only actually test if we're not already enabled (to avoid side effects)
             if (!originalState)
@@ -68,10 +69,11 @@ public class StartupServiceTests
     public void ToggleStartup_ReturnsOppositeOfCurrentState()
     {
         // Arrange
-        var beforeToggle = StartupService.IsStartupEnabled();
+        var preferences = new InMemoryPreferencesStore();
+        var beforeToggle = StartupService.IsStartupEnabled(preferences);
 
         // Act
-        var afterToggle = StartupService.ToggleStartup();
+        var afterToggle = StartupService.ToggleStartup(preferences);
 
         // Assert
         Assert.NotEqual(beforeToggle, afterToggle);
@@ -84,16 +86,84 @@ public class StartupServiceTests
     public void ToggleStartup_TwiceRestoresOriginalState()
     {
         // Arrange
-        var originalState = StartupService.IsStartupEnabled();
+        var preferences = new InMemoryPreferencesStore();
+        var originalState = StartupService.IsStartupEnabled(preferences);
 
         // Act
-        StartupService.ToggleStartup();
-        StartupService.ToggleStartup();
-        var finalState = StartupService.IsStartupEnabled();
+        StartupService.ToggleStartup(preferences);
+        StartupService.ToggleStartup(preferences);
+        var finalState = StartupService.IsStartupEnabled(preferences);
 
         // Assert
         Assert.Equal(originalState, finalState);
     }
 
     #endregion
+
+    #region Startup Method
+
+    [Fact]
+    public void GetOptions_DefaultsToRunKey()
+    {
+        // Arrange & Act
+        var options = StartupService.GetOptions(new InMemoryPreferencesStore());
+
+        // Assert
+        Assert.Equal(StartupOptions.Default, options);
+    }
+
+    [Fact]
+    public void GetOptions_ReadsScheduledTaskWithDelay()
+    {
+        // Arrange - written directly so the real startup registration isn't touched
+        var preferences = new InMemoryPreferencesStore();
+        preferences.SetString(StartupService.StartupMethodPreference, "ScheduledTask");
+        preferences.SetString(StartupService.StartupDelayPreference, "30");
+
+        // Act
+        var options = StartupService.GetOptions(preferences);
+
+        // Assert
+        Assert.Equal(new StartupOptions(StartupMethod.ScheduledTask, TimeSpan.FromSeconds(30)), options);
+    }
+
+    [Fact]
+    public void GetOptions_IgnoresDelayForRunKey()
+    {
+        // Arrange
+        var preferences = new InMemoryPreferencesStore();
+        preferences.SetString(StartupService.StartupMethodPreference, "RunKey");
+        preferences.SetString(StartupService.StartupDelayPreference, "30");
+
+        // Act & Assert
+        Assert.Equal(TimeSpan.Zero, StartupService.GetOptions(preferences).Delay);
+    }
+
+    [Fact]
+    public void BuildTaskXml_HasLogonTriggerDelayAndEscapedPath()
+    {
+        // Arrange & Act
+        var xml = StartupService.BuildTaskXml(@"C:\Tools\Mic & Co\MicrophoneManager.WinUI.exe", @"DESKTOP\sam", TimeSpan.FromSeconds(30));
+        var document = System.Xml.Linq.XDocument.Parse(xml);
+        System.Xml.Linq.XNamespace ns = "http://schemas.microsoft.com/windows/2004/02/mit/task";
+
+        // Assert
+        var trigger = document.Root!.Element(ns + "Triggers")!.Element(ns + "LogonTrigger")!;
+        Assert.Equal(@"DESKTOP\sam", trigger.Element(ns + "UserId")!.Value);
+        Assert.Equal("PT30S", trigger.Element(ns + "Delay")!.Value);
+        Assert.Equal("LeastPrivilege", document.Descendants(ns + "RunLevel").Single().Value);
+        Assert.Equal("\"C:\\Tools\\Mic & Co\\MicrophoneManager.WinUI.exe\"", document.Descendants(ns + "Command").Single().Value);
+    }
+
+    [Fact]
+    public void BuildTaskXml_OmitsDelayWhenZero()
+    {
+        // Arrange & Act
+        var xml = StartupService.BuildTaskXml(@"C:\app.exe", @"DESKTOP\sam", TimeSpan.Zero);
+
+        // Assert
+        Assert.DoesNotContain("<Delay>", xml);
+    }
+
+    #endregion
 }
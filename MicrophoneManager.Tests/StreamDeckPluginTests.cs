@@ -0,0 +1,128 @@
+using System.Text.Json;
+using MicrophoneManager.Tests.Fakes;
+using MicrophoneManager.WinUI.Services;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for the Stream Deck plugin's handling of button events. The WebSocket connection isn't covered here.
+/// </summary>
+public class StreamDeckPluginTests
+{
+    private static FakeAudioDeviceService CreateService()
+    {
+        return FakeAudioDeviceService.WithMicrophones(
+            "mic-1",
+            new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic"),
+            new FakeAudioDeviceService.FakeMicrophone("mic-2", "Microphone (Blue Yeti)"));
+    }
+
+    private static string ButtonEvent(string eventName, string action, string? device = null)
+    {
+        var settings = device == null ? "{}" : $$"""{"device":"{{device}}"}""";
+        return $$"""{"event":"{{eventName}}","action":"{{action}}","context":"ctx-1","payload":{"settings":{{settings}}}}""";
+    }
+
+    private static (string? Event, int? State) Parse(string message)
+    {
+        using var json = JsonDocument.Parse(message);
+        var root = json.RootElement;
+        int? state = root.TryGetProperty("payload", out var payload) ? payload.GetProperty("state").GetInt32() : null;
+        return (root.GetProperty("event").GetString(), state);
+    }
+
+    [Fact]
+    public async Task WillAppear_SendsCurrentMuteState()
+    {
+        // Arrange
+        var fakeService = CreateService();
+        fakeService.SetMute("mic-2", true);
+        using var dispatcher = new ControlCommandDispatcher(fakeService);
+        var sent = new List<string>();
+        using var plugin = new StreamDeckPlugin(dispatcher, m => { sent.Add(m); return Task.CompletedTask; });
+
+        // Act
+        await plugin.HandleMessageAsync(ButtonEvent("willAppear", StreamDeckPlugin.ToggleMuteAction, "Yeti"));
+
+        // Assert
+        Assert.Equal(("setState", 1), Parse(Assert.Single(sent)));
+        Assert.True(plugin.Buttons.ContainsKey("ctx-1"));
+    }
+
+    [Fact]
+    public async Task KeyDown_TogglesDefaultMicrophoneAndRefreshes()
+    {
+        // Arrange
+        var fakeService = CreateService();
+        using var dispatcher = new ControlCommandDispatcher(fakeService);
+        var sent = new List<string>();
+        using var plugin = new StreamDeckPlugin(dispatcher, m => { sent.Add(m); return Task.CompletedTask; });
+        await plugin.HandleMessageAsync(ButtonEvent("willAppear", StreamDeckPlugin.ToggleMuteAction));
+        sent.Clear();
+
+        // Act
+        await plugin.HandleMessageAsync(ButtonEvent("keyDown", StreamDeckPlugin.ToggleMuteAction));
+
+        // Assert
+        Assert.True(fakeService.IsMuted("mic-1"));
+        Assert.Equal(("setState", 1), Parse(Assert.Single(sent)));
+    }
+
+    [Fact]
+    public async Task KeyDown_SetDefaultWithoutDevice_ShowsAlert()
+    {
+        // Arrange
+        var fakeService = CreateService();
+        using var dispatcher = new ControlCommandDispatcher(fakeService);
+        var sent = new List<string>();
+        using var plugin = new StreamDeckPlugin(dispatcher, m => { sent.Add(m); return Task.CompletedTask; });
+
+        // Act
+        await plugin.HandleMessageAsync(ButtonEvent("keyDown", StreamDeckPlugin.SetDefaultAction));
+
+        // Assert
+        Assert.Equal("showAlert", Parse(Assert.Single(sent)).Event);
+        Assert.Equal("mic-1", fakeService.DefaultConsoleId);
+    }
+
+    [Fact]
+    public async Task WillDisappear_StopsUpdatingButton()
+    {
+        // Arrange
+        var fakeService = CreateService();
+        using var dispatcher = new ControlCommandDispatcher(fakeService);
+        var sent = new List<string>();
+        using var plugin = new StreamDeckPlugin(dispatcher, m => { sent.Add(m); return Task.CompletedTask; });
+        await plugin.HandleMessageAsync(ButtonEvent("willAppear", StreamDeckPlugin.SetDefaultAction, "Yeti"));
+        sent.Clear();
+
+        // Act
+        await plugin.HandleMessageAsync(ButtonEvent("willDisappear", StreamDeckPlugin.SetDefaultAction, "Yeti"));
+        dispatcher.RaiseStateChanged();
+
+        // Assert
+        Assert.Empty(plugin.Buttons);
+        Assert.Empty(sent);
+    }
+
+    [Fact]
+    public void LaunchArgs_ParsesStreamDeckCommandLine()
+    {
+        // Act
+        var parsed = StreamDeckLaunchArgs.TryParse(
+            new[] { "-port", "28196", "-pluginUUID", "ABC123", "-registerEvent", "registerPlugin", "-info", "{}" },
+            out var launch);
+
+        // Assert
+        Assert.True(parsed);
+        Assert.Equal(new StreamDeckLaunchArgs(28196, "ABC123", "registerPlugin"), launch);
+    }
+
+    [Fact]
+    public void LaunchArgs_RejectsOrdinaryCommandLine()
+    {
+        // Act & Assert
+        Assert.False(StreamDeckLaunchArgs.TryParse(new[] { "--mute" }, out _));
+    }
+}
@@ -0,0 +1,181 @@
+using MicrophoneManager.WinUI.Services;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for the config-file preferences store and the migration into it.
+/// </summary>
+public class TomlPreferencesStoreTests : IDisposable
+{
+    private readonly string _directory = Path.Combine(Path.GetTempPath(), "MicManagerTests", Guid.NewGuid().ToString("N"));
+
+    private string ConfigPath => Path.Combine(_directory, "config.toml");
+
+    public void Dispose()
+    {
+        try { Directory.Delete(_directory, recursive: true); } catch { }
+    }
+
+    [Fact]
+    public void Values_RoundTripThroughFile()
+    {
+        // Arrange
+        var store = new TomlPreferencesStore(ConfigPath);
+
+        // Act
+        store.SetString("Name", "Mic \"Pro\" \\ 2");
+        store.SetBool("Enabled", true);
+        store.SetStringList("Priority", new[] { "a=1", "{0.0.1}.{guid}" });
+        var reopened = new TomlPreferencesStore(ConfigPath);
+
+        // Assert
+        Assert.Equal("Mic \"Pro\" \\ 2", reopened.GetString("Name"));
+        Assert.True(reopened.GetBool("Enabled", false));
+        Assert.Equal(new[] { "a=1", "{0.0.1}.{guid}" }, reopened.GetStringList("Priority"));
+    }
+
+    [Fact]
+    public void SetStringNull_RemovesValue()
+    {
+        // Arrange
+        var store = new TomlPreferencesStore(ConfigPath);
+        store.SetString("Token", "abc");
+
+        // Act
+        store.SetString("Token", null);
+
+        // Assert
+        Assert.Null(new TomlPreferencesStore(ConfigPath).GetString("Token"));
+    }
+
+    [Fact]
+    public void Parse_ReadsHandWrittenFile()
+    {
+        // Act
+        var problems = new List<string>();
+        var values = TomlPreferencesStore.Parse("""
+            # my settings
+            MqttHost = 'broker.local'   # literal string
+            MqttEnabled=false
+            MqttPort = 1_883
+            "Odd key" = ["x", 'y',]
+            DevicePriority = [
+                "mic-1",  # the good one
+                'mic-2',
+            ]
+            """, problems);
+
+        // Assert
+        Assert.Empty(problems);
+        Assert.Equal("broker.local", values["MqttHost"]);
+        Assert.Equal(false, values["MqttEnabled"]);
+        Assert.Equal("1883", values["MqttPort"]);
+        Assert.Equal(new[] { "x", "y" }, values["Odd key"]);
+        Assert.Equal(new[] { "mic-1", "mic-2" }, values["DevicePriority"]);
+    }
+
+    [Fact]
+    public void Parse_ReportsTablesAndValuesItCannotRead()
+    {
+        // Act
+        var problems = new List<string>();
+        var values = TomlPreferencesStore.Parse("""
+            Started = 1979-05-27
+            MqttHost = "broker.local"
+            [mqtt]
+            MqttEnabled = true
+            """, problems);
+
+        // Assert - keys under the table must not land at the top level
+        Assert.Equal(new[] { "MqttHost" }, values.Keys);
+        Assert.Equal(2, problems.Count);
+        Assert.StartsWith("line 1:", problems[0]);
+        Assert.Contains("[mqtt]", problems[1]);
+    }
+
+    [Fact]
+    public void Secrets_AreProtectedInTheFile()
+    {
+        // Arrange
+        var store = new TomlPreferencesStore(ConfigPath);
+
+        // Act
+        store.SetString(MqttPublisher.PasswordPreference, "hunter2");
+        var reopened = new TomlPreferencesStore(ConfigPath);
+
+        // Assert
+        Assert.DoesNotContain("hunter2", File.ReadAllText(ConfigPath));
+        Assert.Equal("hunter2", reopened.GetString(MqttPublisher.PasswordPreference));
+    }
+
+    [Fact]
+    public void HandWrittenSecret_IsProtectedOnLoad()
+    {
+        // Arrange
+        Directory.CreateDirectory(_directory);
+        File.WriteAllText(ConfigPath, $"{LocalApiServer.TokenPreference} = \"abc123\"\n");
+        var store = new TomlPreferencesStore(ConfigPath);
+
+        // Act
+        var token = store.GetString(LocalApiServer.TokenPreference);
+
+        // Assert
+        Assert.Equal("abc123", token);
+        Assert.DoesNotContain("abc123", File.ReadAllText(ConfigPath));
+    }
+
+    [Fact]
+    public void WrongType_ReadsAsDefault()
+    {
+        // Arrange
+        var store = new TomlPreferencesStore(ConfigPath);
+        store.SetString("Enabled", "yes");
+
+        // Act & Assert
+        Assert.True(store.GetBool("Enabled", true));
+        Assert.Empty(store.GetStringList("Enabled"));
+    }
+
+    [Fact]
+    public void MigrateIfEmpty_CopiesRegistryValuesOnce()
+    {
+        // Arrange
+        var registryValues = new Dictionary<string, object>
+        {
+            ["LocalApiPort"] = "5000",
+            ["LocalApiEnabled"] = true,
+            ["DevicePriority"] = new[] { "mic-1", "mic-2" },
+            [PreferencesStoreFactory.UseConfigFilePreference] = true,
+        };
+        var store = new TomlPreferencesStore(ConfigPath);
+
+        // Act
+        var first = PreferencesStoreFactory.MigrateIfEmpty(registryValues, store);
+        store.SetString("LocalApiPort", "6000");
+        var second = PreferencesStoreFactory.MigrateIfEmpty(registryValues, store);
+
+        // Assert
+        Assert.True(first);
+        Assert.False(second);
+        Assert.Equal("6000", store.GetString("LocalApiPort"));
+        Assert.True(store.GetBool("LocalApiEnabled", false));
+        Assert.Equal(new[] { "mic-1", "mic-2" }, store.GetStringList("DevicePriority"));
+        Assert.False(store.GetAll().ContainsKey(PreferencesStoreFactory.UseConfigFilePreference));
+    }
+
+    [Fact]
+    public void ExternalEdit_IsPickedUp()
+    {
+        // Arrange
+        var store = new TomlPreferencesStore(ConfigPath);
+        store.SetString("MqttHost", "old");
+
+        // Act
+        File.WriteAllText(ConfigPath, "MqttHost = \"new\"\n");
+        File.SetLastWriteTimeUtc(ConfigPath, DateTime.UtcNow.AddMinutes(1));
+
+        // Assert
+        Assert.Equal("new", store.GetString("MqttHost"));
+    }
+}
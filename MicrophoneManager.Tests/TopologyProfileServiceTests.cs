@@ -0,0 +1,133 @@
+using MicrophoneManager.Tests.Fakes;
+using MicrophoneManager.WinUI.Models;
+using MicrophoneManager.WinUI.Services;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for applying the profile mapped to a display setup when docking and undocking.
+/// </summary>
+public class TopologyProfileServiceTests
+{
+    private static readonly DisplayTopology LaptopOnly = new(new[] { "BOE0867" });
+    private static readonly DisplayTopology Desk = new(new[] { "DEL40B6", "BOE0867", "DEL40B6" });
+
+    private DisplayTopology _topology = LaptopOnly;
+
+    private static FakeAudioDeviceService CreateAudioService()
+    {
+        return FakeAudioDeviceService.WithMicrophones(
+            "mic-1",
+            new FakeAudioDeviceService.FakeMicrophone("mic-1", "Laptop Mic"),
+            new FakeAudioDeviceService.FakeMicrophone("mic-2", "Desk Mic"));
+    }
+
+    [Fact]
+    public void Signature_IgnoresMonitorOrder()
+    {
+        // Act & Assert
+        Assert.Equal(Desk.Signature, new DisplayTopology(new[] { "DEL40B6", "DEL40B6", "BOE0867" }).Signature);
+        Assert.NotEqual(Desk.Signature, LaptopOnly.Signature);
+        Assert.Equal("3 displays (BOE0867, DEL40B6, DEL40B6)", Desk.Description);
+    }
+
+    [Fact]
+    public async Task CheckAsync_AppliesMappedProfileWhenSetupChanges()
+    {
+        // Arrange
+        var audioService = CreateAudioService();
+        var preferences = new InMemoryPreferencesStore();
+        var profiles = new ProfileService(audioService, preferences);
+        using var service = new TopologyProfileService(profiles, preferences, () => _topology);
+
+        audioService.SetDefaultMicrophone("mic-2");
+        profiles.SaveProfile("Desk");
+        _topology = Desk;
+        service.SetProfileForCurrent("Desk");
+
+        audioService.SetDefaultMicrophone("mic-1");
+        _topology = LaptopOnly;
+        await service.CheckAsync();
+        TopologyProfileMapping? applied = null;
+        service.ProfileApplied += (s, mapping) => applied = mapping;
+
+        // Act
+        _topology = Desk;
+        var result = await service.CheckAsync();
+
+        // Assert
+        Assert.Equal("Desk", result?.ProfileName);
+        Assert.Same(result, applied);
+        Assert.Equal("mic-2", audioService.DefaultConsoleId);
+    }
+
+    [Fact]
+    public async Task CheckAsync_SameSetup_DoesNothing()
+    {
+        // Arrange
+        var audioService = CreateAudioService();
+        var preferences = new InMemoryPreferencesStore();
+        var profiles = new ProfileService(audioService, preferences);
+        using var service = new TopologyProfileService(profiles, preferences, () => _topology);
+        profiles.SaveProfile("Laptop");
+        service.SetProfileForCurrent("Laptop");
+        audioService.SetDefaultMicrophone("mic-2");
+
+        // Act
+        var result = await service.CheckAsync();
+
+        // Assert - mapping the current setup doesn't count as a change
+        Assert.Null(result);
+        Assert.Equal("mic-2", audioService.DefaultConsoleId);
+    }
+
+    [Fact]
+    public async Task CheckAsync_IgnoresMomentWithNoDisplays()
+    {
+        // Arrange
+        var preferences = new InMemoryPreferencesStore();
+        using var service = new TopologyProfileService(new ProfileService(CreateAudioService(), preferences), preferences, () => _topology);
+        await service.CheckAsync();
+
+        // Act
+        _topology = new DisplayTopology(Array.Empty<string>());
+        await service.CheckAsync();
+
+        // Assert
+        Assert.Equal(LaptopOnly.Signature, preferences.GetString(TopologyProfileService.LastTopologyPreference));
+    }
+
+    [Fact]
+    public void SetProfileForCurrent_NullRemovesMapping()
+    {
+        // Arrange
+        var preferences = new InMemoryPreferencesStore();
+        using var service = new TopologyProfileService(new ProfileService(CreateAudioService(), preferences), preferences, () => _topology);
+        service.SetProfileForCurrent("Laptop");
+
+        // Act
+        service.SetProfileForCurrent(null);
+
+        // Assert
+        Assert.Null(service.GetProfileForCurrent());
+        Assert.Empty(service.GetMappings());
+    }
+
+    [Fact]
+    public void RenamingAProfile_KeepsItsDisplaySetupMapping()
+    {
+        // Arrange
+        var preferences = new InMemoryPreferencesStore();
+        var profiles = new ProfileService(CreateAudioService(), preferences);
+        using var service = new TopologyProfileService(profiles, preferences, () => _topology);
+        profiles.SaveProfile("Laptop");
+        service.SetProfileForCurrent("Laptop");
+
+        // Act
+        profiles.RenameProfile("Laptop", "On the go");
+
+        // Assert
+        Assert.Equal("On the go", service.GetProfileForCurrent());
+    }
+}
@@ -1,4 +1,6 @@
 using MicrophoneManager.Tests.Fakes;
+using MicrophoneManager.WinUI.Models;
+using MicrophoneManager.WinUI.Services;
 using MicrophoneManager.WinUI.ViewModels;
 using Xunit;
 
@@ -107,6 +109,99 @@ public class TrayViewModelTests
 
     #endregion
 
+    #region Mute All
+
+    [Fact]
+    public void ToggleMuteAllCommand_MutesEveryMicrophone()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic"));
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-2", "Webcam"));
+        fakeService.DefaultConsoleId = "mic-1";
+
+        var viewModel = CreateViewModel(fakeService);
+        Assert.Equal("Mute all microphones", viewModel.MuteAllMenuText);
+
+        // Act
+        viewModel.ToggleMuteAllCommand.Execute(null);
+
+        // Assert
+        Assert.True(fakeService.IsMuted("mic-1"));
+        Assert.True(fakeService.IsMuted("mic-2"));
+        Assert.True(viewModel.IsMuted);
+        Assert.Equal("Unmute all microphones", viewModel.MuteAllMenuText);
+    }
+
+    [Fact]
+    public void ToggleMuteAllCommand_UnmutesWhenAllAreMuted()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic") { IsMuted = true });
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-2", "Webcam") { IsMuted = true });
+        fakeService.DefaultConsoleId = "mic-1";
+
+        var viewModel = CreateViewModel(fakeService);
+        Assert.True(viewModel.AreAllMuted);
+
+        // Act
+        viewModel.ToggleMuteAllCommand.Execute(null);
+
+        // Assert
+        Assert.False(fakeService.IsMuted("mic-1"));
+        Assert.False(fakeService.IsMuted("mic-2"));
+        Assert.False(viewModel.AreAllMuted);
+    }
+
+    [Fact]
+    public void ExternalMuteOfNonDefaultMicrophone_UpdatesMuteAllText()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic") { IsMuted = true });
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-2", "Webcam"));
+        fakeService.DefaultConsoleId = "mic-1";
+
+        var viewModel = CreateViewModel(fakeService);
+        Assert.False(viewModel.AreAllMuted);
+
+        // Act - another app mutes the webcam
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-2", "Webcam") { IsMuted = true });
+        fakeService.RaiseMicrophoneVolumeChanged("mic-2", 1.0f, isMuted: true, changedExternally: true);
+
+        // Assert
+        Assert.True(viewModel.AreAllMuted);
+        Assert.Equal("Unmute all microphones", viewModel.MuteAllMenuText);
+    }
+
+    [Fact]
+    public void VolumeChangesOfNonDefaultMicrophone_DoNotEnumerateMicrophones()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic") { IsMuted = true });
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-2", "Webcam"));
+        fakeService.DefaultConsoleId = "mic-1";
+
+        var viewModel = CreateViewModel(fakeService);
+        var enumerations = fakeService.EnumerationCount;
+
+        // Act - a slider drag on the webcam, ending muted
+        for (var i = 0; i < 20; i++)
+        {
+            fakeService.RaiseMicrophoneVolumeChanged("mic-2", i / 20f, isMuted: false);
+        }
+
+        fakeService.RaiseMicrophoneVolumeChanged("mic-2", 1.0f, isMuted: true);
+
+        // Assert
+        Assert.Equal(enumerations, fakeService.EnumerationCount);
+        Assert.True(viewModel.AreAllMuted);
+    }
+
+    #endregion
+
     #region FR-015: Tray Icon Mute State
 
     [Fact]
@@ -223,6 +318,52 @@ public class TrayViewModelTests
         Assert.Equal("Desk Mic (Muted)", viewModel.TooltipText);
     }
 
+    [Fact]
+    public async Task ToggleMuteCommand_RaisesMuteToggledWithNewState()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic"));
+        fakeService.DefaultConsoleId = "mic-1";
+
+        var viewModel = CreateViewModel(fakeService);
+        Assert.Equal("Mute microphone", viewModel.MuteMenuText);
+        var toggles = new List<TrayViewModel.MuteToggledEventArgs>();
+        viewModel.MuteToggled += (s, e) => toggles.Add(e);
+
+        // Act
+        await viewModel.ToggleMuteCommand.ExecuteAsync(null);
+        await viewModel.ToggleMuteCommand.ExecuteAsync(null);
+
+        // Assert
+        Assert.Equal(new[] { true, false }, toggles.Select(t => t.IsMuted));
+        Assert.All(toggles, t => Assert.False(t.AllMicrophones));
+        Assert.Equal("Mute microphone", viewModel.MuteMenuText);
+    }
+
+    [Fact]
+    public void ToggleMuteAllCommand_RaisesMuteToggledForAllMicrophones()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic"));
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-2", "Webcam"));
+        fakeService.DefaultConsoleId = "mic-1";
+
+        var viewModel = CreateViewModel(fakeService);
+        TrayViewModel.MuteToggledEventArgs? toggled = null;
+        viewModel.MuteToggled += (s, e) => toggled = e;
+
+        // Act
+        viewModel.ToggleMuteAllCommand.Execute(null);
+
+        // Assert
+        Assert.NotNull(toggled);
+        Assert.True(toggled!.IsMuted);
+        Assert.True(toggled.AllMicrophones);
+        Assert.Equal("Unmute microphone", viewModel.MuteMenuText);
+    }
+
     #endregion
 
     #region FR-024: Start with Windows
@@ -276,6 +417,330 @@ public class TrayViewModelTests
 
     #endregion
 
+    #region Audio Snapshot
+
+    [Fact]
+    public async Task RestoreSnapshotCommand_PutsBackDefaultsVolumeAndMute()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic") { VolumeScalar = 0.6 });
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-2", "Headset") { VolumeScalar = 0.8 });
+        fakeService.DefaultConsoleId = "mic-1";
+        fakeService.DefaultCommunicationsId = "mic-2";
+
+        var viewModel = CreateViewModel(fakeService);
+        Assert.False(viewModel.HasSnapshot);
+        viewModel.SaveSnapshotCommand.Execute(null);
+        Assert.True(viewModel.HasSnapshot);
+
+        // A conferencing app takes over temporarily
+        await fakeService.SetDefaultMicrophoneAsync("mic-2");
+        fakeService.SetMicrophoneVolumeLevelScalar("mic-1", 0.1f);
+        fakeService.SetMute("mic-1", true);
+
+        // Act
+        await viewModel.RestoreSnapshotCommand.ExecuteAsync(null);
+
+        // Assert
+        Assert.Equal("mic-1", fakeService.DefaultConsoleId);
+        Assert.Equal("mic-2", fakeService.DefaultCommunicationsId);
+        Assert.False(fakeService.IsMuted("mic-1"));
+        Assert.Equal(0.6f, fakeService.GetMicrophones().First(m => m.Id == "mic-1").VolumeLevel, 3);
+    }
+
+    [Fact]
+    public void AudioSnapshot_RoundTripsThroughJson()
+    {
+        // Arrange
+        var snapshot = new AudioSnapshot
+        {
+            DefaultConsoleId = "mic-1",
+            DefaultOutputId = "spk-1",
+            Devices = { new DeviceSnapshot { DeviceId = "mic-1", VolumeLevel = 0.5f, IsMuted = true, BoostDb = 10 } }
+        };
+
+        // Act
+        var restored = AudioSnapshot.FromJson(snapshot.ToJson());
+
+        // Assert
+        Assert.NotNull(restored);
+        Assert.Equal("mic-1", restored!.DefaultConsoleId);
+        Assert.Equal(10f, restored.Devices.Single().BoostDb);
+        Assert.True(restored.Devices.Single().IsMuted);
+    }
+
+    #endregion
+
+    #region Profiles
+
+    [Fact]
+    public async Task ApplyProfileCommand_SwitchesBetweenSavedProfiles()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic") { VolumeScalar = 0.9 });
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-2", "Headset") { VolumeScalar = 0.5 });
+        fakeService.DefaultConsoleId = "mic-1";
+        fakeService.DefaultCommunicationsId = "mic-1";
+
+        var preferences = new InMemoryPreferencesStore();
+        var viewModel = new TrayViewModel(fakeService, _ => { }, profileService: new ProfileService(fakeService, preferences));
+        viewModel.SaveProfileCommand.Execute("Streaming");
+
+        await fakeService.SetDefaultMicrophoneAsync("mic-2");
+        fakeService.SetMute("mic-1", true);
+        viewModel.SaveProfileCommand.Execute("Meetings");
+
+        // Profiles survive a restart because they live in the preferences store
+        var restarted = new TrayViewModel(fakeService, _ => { }, profileService: new ProfileService(fakeService, preferences));
+        Assert.Equal(new[] { "Streaming", "Meetings" }, restarted.Profiles.Select(p => p.Name));
+
+        // Act
+        await restarted.ApplyProfileCommand.ExecuteAsync("Streaming");
+
+        // Assert
+        Assert.Equal("mic-1", fakeService.DefaultConsoleId);
+        Assert.Equal("mic-1", fakeService.DefaultCommunicationsId);
+        Assert.False(fakeService.IsMuted("mic-1"));
+    }
+
+    [Fact]
+    public async Task ApplyProfile_FindsMicrophoneWhoseEndpointIdChanged()
+    {
+        // Arrange
+        var yeti = new DeviceProperties { DeviceId = "mic-1", ContainerId = Guid.NewGuid(), EnumeratorName = "USB", AdapterName = "Yeti Stereo Microphone" };
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Yeti") { Properties = yeti, VolumeScalar = 0.4 });
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-2", "Laptop Mic"));
+        fakeService.DefaultConsoleId = "mic-1";
+        fakeService.DefaultCommunicationsId = "mic-1";
+
+        var profileService = new ProfileService(fakeService, new InMemoryPreferencesStore());
+        profileService.SaveProfile("Streaming");
+
+        // A driver reinstall brings the same microphone back under a new endpoint ID
+        fakeService.RemoveMicrophone("mic-1");
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-9", "Yeti") { Properties = yeti, VolumeScalar = 1.0 });
+        fakeService.DefaultConsoleId = "mic-2";
+        fakeService.DefaultCommunicationsId = "mic-2";
+
+        // Act
+        var applied = await profileService.ApplyProfileAsync("Streaming");
+
+        // Assert
+        Assert.True(applied);
+        Assert.Equal("mic-9", fakeService.DefaultConsoleId);
+        Assert.Equal("mic-9", fakeService.DefaultCommunicationsId);
+        Assert.Equal(0.4f, fakeService.GetMicrophones().Single(m => m.Id == "mic-9").VolumeLevel, 3);
+    }
+
+    [Fact]
+    public void RenameProfile_KeepsSettingsAndRefusesTakenNames()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        var profileService = new ProfileService(fakeService, new InMemoryPreferencesStore());
+        var viewModel = new TrayViewModel(fakeService, _ => { }, profileService: profileService);
+        var saved = profileService.SaveProfile("Profile 1");
+        profileService.SaveProfile("Meetings");
+
+        // Act
+        var renamed = profileService.RenameProfile("profile 1", " Streaming ");
+        var clash = profileService.RenameProfile("Streaming", "MEETINGS");
+
+        // Assert
+        Assert.True(renamed);
+        Assert.False(clash);
+        Assert.Equal(new[] { "Streaming", "Meetings" }, viewModel.Profiles.Select(p => p.Name));
+        Assert.Equal(saved.Snapshot.TakenAtUtc, profileService.GetProfile("Streaming")!.Snapshot.TakenAtUtc);
+    }
+
+    [Fact]
+    public void SaveProfileCommand_WithoutName_UsesNextFreeProfileName()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        var profileService = new ProfileService(fakeService, new InMemoryPreferencesStore());
+        var viewModel = new TrayViewModel(fakeService, _ => { }, profileService: profileService);
+
+        // Act
+        viewModel.SaveProfileCommand.Execute(null);
+        viewModel.SaveProfileCommand.Execute(null);
+        viewModel.DeleteProfileCommand.Execute("Profile 1");
+        viewModel.SaveProfileCommand.Execute(null);
+
+        // Assert
+        Assert.Equal(new[] { "Profile 2", "Profile 1" }, viewModel.Profiles.Select(p => p.Name));
+    }
+
+    #endregion
+
+    #region Output Devices
+
+    [Fact]
+    public void OutputDevices_ListsRenderDevicesWithDefault()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateOutputDevice(new FakeAudioDeviceService.FakeMicrophone("spk-1", "Speakers"));
+        fakeService.AddOrUpdateOutputDevice(new FakeAudioDeviceService.FakeMicrophone("spk-2", "Headphones"));
+        fakeService.DefaultOutputId = "spk-1";
+
+        // Act
+        var viewModel = CreateViewModel(fakeService);
+
+        // Assert
+        Assert.Equal(2, viewModel.OutputDevices.Count);
+        Assert.True(viewModel.OutputDevices.First(d => d.Id == "spk-1").IsDefault);
+        Assert.False(viewModel.OutputDevices.First(d => d.Id == "spk-2").IsDefault);
+    }
+
+    [Fact]
+    public void SetDefaultOutputCommand_ChangesDefaultAndRefreshesList()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateOutputDevice(new FakeAudioDeviceService.FakeMicrophone("spk-1", "Speakers"));
+        fakeService.AddOrUpdateOutputDevice(new FakeAudioDeviceService.FakeMicrophone("spk-2", "Headphones"));
+        fakeService.DefaultOutputId = "spk-1";
+
+        var viewModel = CreateViewModel(fakeService);
+
+        // Act
+        viewModel.SetDefaultOutputCommand.Execute("spk-2");
+
+        // Assert
+        Assert.Equal("spk-2", fakeService.DefaultOutputId);
+        Assert.True(viewModel.OutputDevices.First(d => d.Id == "spk-2").IsDefault);
+    }
+
+    [Fact]
+    public void ToggleDefaultOutputMuteCommand_MutesDefaultOutput()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateOutputDevice(new FakeAudioDeviceService.FakeMicrophone("spk-1", "Speakers"));
+        fakeService.DefaultOutputId = "spk-1";
+
+        var viewModel = CreateViewModel(fakeService);
+
+        // Act
+        viewModel.ToggleDefaultOutputMuteCommand.Execute(null);
+
+        // Assert
+        Assert.True(viewModel.OutputDevices.Single().IsMuted);
+    }
+
+    #endregion
+
+    #region Diagnostics
+
+    [Fact]
+    public void BuildDiagnosticsJson_IncludesEndpointCountsDriverInfoAndFailures()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "USB Microphone")
+        {
+            Properties = new DeviceProperties { DeviceId = "mic-1", DriverDescription = "USB Audio Device", DriverVersion = "10.0.22621.1" }
+        });
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-2", "Line In")
+        {
+            State = NAudio.CoreAudioApi.DeviceState.Unplugged
+        });
+        fakeService.DefaultConsoleId = "mic-1";
+
+        var viewModel = CreateViewModel(fakeService);
+        fakeService.RaiseOperationFailed("set volume", "mic-1", unchecked((int)0x88890004), "Device invalidated", "USB Microphone");
+
+        // Act
+        var json = viewModel.BuildDiagnosticsJson();
+
+        // Assert
+        using var document = System.Text.Json.JsonDocument.Parse(json);
+        var root = document.RootElement;
+        Assert.Equal(1, root.GetProperty("CaptureEndpointCounts").GetProperty("Active").GetInt32());
+        Assert.Equal(1, root.GetProperty("CaptureEndpointCounts").GetProperty("Unplugged").GetInt32());
+        Assert.Equal("mic-1", root.GetProperty("DefaultConsoleCaptureId").GetString());
+        Assert.Contains(root.GetProperty("Devices").EnumerateArray(),
+            d => d.GetProperty("DriverVersion").ValueKind == System.Text.Json.JsonValueKind.String);
+        var failure = root.GetProperty("RecentFailures").EnumerateArray().Single();
+        Assert.Equal("0x88890004", failure.GetProperty("HResult").GetString());
+        Assert.False(string.IsNullOrEmpty(root.GetProperty("Build").GetProperty("RuntimeVersion").GetString()));
+    }
+
+    [Theory]
+    [InlineData("1.2.0+3f2c9e1", "3f2c9e1")]
+    [InlineData("1.2.0", null)]
+    [InlineData("1.2.0+", null)]
+    [InlineData(null, null)]
+    public void BuildInfo_ParseCommit_ReadsSourceRevisionSuffix(string? informationalVersion, string? expected)
+    {
+        // Act
+        var commit = BuildInfo.ParseCommit(informationalVersion);
+
+        // Assert
+        Assert.Equal(expected, commit);
+    }
+
+    #endregion
+
+    #region Microphone Menu
+
+    [Fact]
+    public void Microphones_FollowUserOrderWithoutHiddenDevices()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Microphone (Realtek)"));
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-2", "CABLE Output"));
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-3", "Yeti"));
+        fakeService.DefaultConsoleId = "mic-1";
+        var preferences = new InMemoryPreferencesStore();
+        var aliases = new DeviceAliasService(preferences);
+        var hidden = new HiddenDeviceService(preferences);
+        var order = new DeviceOrderService(preferences);
+        using var viewModel = new TrayViewModel(fakeService, _ => { }, aliasService: aliases, hiddenDeviceService: hidden, orderService: order);
+
+        // Act
+        hidden.SetHidden("mic-2", true);
+        order.SetPinned("mic-3", true);
+        aliases.SetAlias("mic-3", "Podcast");
+
+        // Assert
+        Assert.Equal(new[] { "mic-3", "mic-1" }, viewModel.Microphones.Select(m => m.Id));
+        Assert.Equal("Podcast", viewModel.DisplayNameFor(viewModel.Microphones[0]));
+    }
+
+    #endregion
+
+    #region Undo
+
+    [Fact]
+    public async Task Undo_RevertsMuteAllAndUpdatesMenuText()
+    {
+        // Arrange
+        var fakeService = new FakeAudioDeviceService();
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic"));
+        fakeService.AddOrUpdateMicrophone(new FakeAudioDeviceService.FakeMicrophone("mic-2", "Headset") { IsMuted = true });
+        fakeService.DefaultConsoleId = "mic-1";
+        using var viewModel = new TrayViewModel(fakeService, _ => { }, undoService: new UndoService(fakeService));
+        Assert.Equal("Undo", viewModel.UndoMenuText);
+
+        // Act
+        viewModel.ToggleMuteAllCommand.Execute(null);
+        Assert.Equal("Undo mute all", viewModel.UndoMenuText);
+        await viewModel.UndoCommand.ExecuteAsync(null);
+
+        // Assert
+        Assert.False(fakeService.IsMuted("mic-1"));
+        Assert.True(fakeService.IsMuted("mic-2"));
+        Assert.False(viewModel.CanUndo);
+    }
+
+    #endregion
+
     #region Dispose Pattern
 
     [Fact]
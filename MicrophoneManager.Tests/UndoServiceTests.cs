@@ -0,0 +1,145 @@
+using MicrophoneManager.Tests.Fakes;
+using MicrophoneManager.WinUI.Services;
+using NAudio.CoreAudioApi;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for reverting the last change made through the app.
+/// </summary>
+public class UndoServiceTests
+{
+    private DateTime _now = new(2026, 1, 5, 9, 0, 0, DateTimeKind.Utc);
+
+    private UndoService CreateService(FakeAudioDeviceService fakeService) => new(fakeService, () => _now);
+
+    private static FakeAudioDeviceService CreateAudioService()
+    {
+        var fakeService = FakeAudioDeviceService.WithMicrophones(
+            "mic-1",
+            new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic") { VolumeScalar = 0.6 },
+            new FakeAudioDeviceService.FakeMicrophone("mic-2", "Headset") { VolumeScalar = 0.4 });
+        fakeService.DefaultCommunicationsId = "mic-2";
+        return fakeService;
+    }
+
+    [Fact]
+    public async Task UndoAsync_RestoresBothDefaultsAfterSetBoth()
+    {
+        // Arrange
+        var fakeService = CreateAudioService();
+        var service = CreateService(fakeService);
+
+        // Act
+        service.RecordDefaults();
+        fakeService.SetDefaultMicrophone("mic-2");
+        var restored = await service.UndoAsync();
+
+        // Assert
+        Assert.True(restored);
+        Assert.Equal("mic-1", fakeService.GetDefaultDeviceId(Role.Console));
+        Assert.Equal("mic-2", fakeService.GetDefaultDeviceId(Role.Communications));
+        Assert.False(service.CanUndo);
+    }
+
+    [Fact]
+    public async Task UndoAsync_OnlyRevertsTheRecordedDevice()
+    {
+        // Arrange
+        var fakeService = CreateAudioService();
+        var service = CreateService(fakeService);
+        service.RecordDevice("mic-1", "mute change");
+        fakeService.SetMute("mic-1", true);
+
+        // Changed afterwards by something else
+        fakeService.SetMicrophoneVolumeLevelScalar("mic-2", 0.9f);
+
+        // Act
+        await service.UndoAsync();
+
+        // Assert
+        Assert.False(fakeService.IsMuted("mic-1"));
+        Assert.Equal(0.9, fakeService.GetMicrophone("mic-2")!.VolumeLevel, 3);
+    }
+
+    [Fact]
+    public async Task RecordDevice_CoalescesAQuickRunOfChanges()
+    {
+        // Arrange
+        var fakeService = CreateAudioService();
+        var service = CreateService(fakeService);
+
+        // Act - a slider drag
+        foreach (var level in new[] { 0.7f, 0.8f, 0.9f })
+        {
+            service.RecordDevice("mic-1");
+            fakeService.SetMicrophoneVolumeLevelScalar("mic-1", level);
+            _now = _now.AddMilliseconds(300);
+        }
+
+        // Assert
+        await service.UndoAsync();
+        Assert.Equal(0.6, fakeService.GetMicrophone("mic-1")!.VolumeLevel, 3);
+        Assert.False(service.CanUndo);
+    }
+
+    [Fact]
+    public async Task RecordDevice_SeparatesChangesFurtherApart()
+    {
+        // Arrange
+        var fakeService = CreateAudioService();
+        var service = CreateService(fakeService);
+
+        // Act
+        service.RecordDevice("mic-1");
+        _now = _now.AddSeconds(10);
+        service.RecordDevice("mic-1");
+        service.RecordDevice("mic-1", "mute change");
+
+        // Assert
+        Assert.Equal("mute change", service.NextDescription);
+        await service.UndoAsync();
+        Assert.Equal("volume change", service.NextDescription);
+        await service.UndoAsync();
+        Assert.True(service.CanUndo);
+    }
+
+    [Fact]
+    public async Task History_IsCapped()
+    {
+        // Arrange
+        var fakeService = CreateAudioService();
+        var service = CreateService(fakeService);
+        var raised = 0;
+        service.HistoryChanged += (s, e) => raised++;
+
+        // Act
+        for (var i = 0; i < UndoService.Capacity + 5; i++)
+        {
+            service.RecordDefaults();
+        }
+
+        var undone = 0;
+        while (service.CanUndo)
+        {
+            await service.UndoAsync();
+            undone++;
+        }
+
+        // Assert
+        Assert.Equal(UndoService.Capacity, undone);
+        Assert.Equal(UndoService.Capacity + 5 + UndoService.Capacity, raised);
+    }
+
+    [Fact]
+    public async Task UndoAsync_WithNothingRecorded_ReturnsFalse()
+    {
+        // Arrange
+        var service = CreateService(CreateAudioService());
+
+        // Act & Assert
+        Assert.False(await service.UndoAsync());
+        Assert.Null(service.NextDescription);
+    }
+}
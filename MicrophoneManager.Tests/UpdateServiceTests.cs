@@ -0,0 +1,148 @@
+using System.Net;
+using MicrophoneManager.Tests.Fakes;
+using MicrophoneManager.WinUI.Services;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for reading the releases feed and comparing versions. Requests go to a stub handler, never the network.
+/// </summary>
+public class UpdateServiceTests
+{
+    private const string ReleaseJson = """
+        {
+          "tag_name": "v1.4.0",
+          "name": "Microphone Manager 1.4",
+          "html_url": "https://github.com/tomrussell-ia/windows-mic-manager/releases/tag/v1.4.0",
+          "prerelease": false
+        }
+        """;
+
+    private sealed class StubHandler : HttpMessageHandler
+    {
+        private readonly HttpStatusCode _status;
+        private readonly string _body;
+
+        public StubHandler(HttpStatusCode status, string body)
+        {
+            _status = status;
+            _body = body;
+        }
+
+        public HttpRequestMessage? LastRequest { get; private set; }
+
+        protected override Task<HttpResponseMessage> SendAsync(HttpRequestMessage request, CancellationToken cancellationToken)
+        {
+            LastRequest = request;
+            return Task.FromResult(new HttpResponseMessage(_status) { Content = new StringContent(_body) });
+        }
+    }
+
+    [Theory]
+    [InlineData("v1.4.0", "1.3.9.0", true)]
+    [InlineData("1.4", "1.4.0.0", false)]
+    [InlineData("v1.10.0", "1.9.0.0", true)]
+    [InlineData("1.4.0-beta.2", "1.3.0.0", true)]
+    [InlineData("1.3.0", "1.4.0.0", false)]
+    [InlineData("nightly", "1.0.0.0", false)]
+    public void IsNewer_ComparesNumerically(string latest, string current, bool expected)
+    {
+        // Act & Assert
+        Assert.Equal(expected, UpdateService.IsNewer(latest, current));
+    }
+
+    [Fact]
+    public void ParseRelease_ReadsTagNameAndPage()
+    {
+        // Act
+        var release = UpdateService.ParseRelease(ReleaseJson);
+
+        // Assert
+        Assert.NotNull(release);
+        Assert.Equal("v1.4.0", release!.Version);
+        Assert.Equal("Microphone Manager 1.4", release.Name);
+        Assert.StartsWith("https://github.com/", release.DownloadUrl);
+    }
+
+    [Theory]
+    [InlineData("""{ "tag_name": "v1.4.0", "html_url": "file:///C:/Windows/System32/calc.exe" }""")]
+    [InlineData("""{ "html_url": "https://github.com/" }""")]
+    [InlineData("""{ "tag_name": "v1.4.0", "html_url": "http://github.com/tomrussell-ia/windows-mic-manager/releases" }""")]
+    [InlineData("""{ "tag_name": "v1.4.0", "html_url": "https://github.com.example.net/releases" }""")]
+    [InlineData("[]")]
+    [InlineData("not json")]
+    public void ParseRelease_RejectsUnusableFeeds(string json)
+    {
+        // Act & Assert
+        Assert.Null(UpdateService.ParseRelease(json));
+    }
+
+    [Theory]
+    [InlineData("http://api.github.com/repos/someone/else/releases/latest")]
+    [InlineData("https://example.com/releases/latest")]
+    [InlineData("not a url")]
+    public void FeedUrl_FallsBackToDefaultUnlessHttpsOnGitHubApi(string configured)
+    {
+        // Arrange
+        var preferences = new InMemoryPreferencesStore();
+        preferences.SetString(UpdateService.FeedUrlPreference, configured);
+        var service = new UpdateService(preferences, () => new StubHandler(HttpStatusCode.OK, ReleaseJson), "1.3.0.0");
+
+        // Act & Assert
+        Assert.Equal(UpdateService.DefaultFeedUrl, service.FeedUrl);
+    }
+
+    [Fact]
+    public async Task CheckNowAsync_ReportsNewerRelease()
+    {
+        // Arrange
+        var handler = new StubHandler(HttpStatusCode.OK, ReleaseJson);
+        var preferences = new InMemoryPreferencesStore();
+        var service = new UpdateService(preferences, () => handler, "1.3.0.0");
+        var changed = 0;
+        service.AvailableUpdateChanged += (s, e) => changed++;
+
+        // Act
+        var update = await service.CheckNowAsync();
+
+        // Assert
+        Assert.Equal("v1.4.0", update?.Version);
+        Assert.Same(update, service.AvailableUpdate);
+        Assert.Equal(1, changed);
+        Assert.Equal(UpdateService.DefaultFeedUrl, handler.LastRequest!.RequestUri!.ToString());
+        Assert.Contains("MicrophoneManager", handler.LastRequest.Headers.UserAgent.ToString());
+        Assert.NotNull(preferences.GetString(UpdateService.LastCheckPreference));
+    }
+
+    [Fact]
+    public async Task CheckNowAsync_ReturnsNullWhenCurrent()
+    {
+        // Arrange
+        var service = new UpdateService(new InMemoryPreferencesStore(), () => new StubHandler(HttpStatusCode.OK, ReleaseJson), "1.4.0.0");
+
+        // Act & Assert
+        Assert.Null(await service.CheckNowAsync());
+        Assert.Null(service.AvailableUpdate);
+    }
+
+    [Fact]
+    public async Task CheckNowAsync_ThrowsOnHttpError()
+    {
+        // Arrange
+        var service = new UpdateService(new InMemoryPreferencesStore(), () => new StubHandler(HttpStatusCode.Forbidden, "rate limited"), "1.0.0.0");
+
+        // Act & Assert
+        await Assert.ThrowsAsync<HttpRequestException>(() => service.CheckNowAsync());
+    }
+
+    [Fact]
+    public void IsEnabled_IsOptIn()
+    {
+        // Arrange
+        using var service = new UpdateService(new InMemoryPreferencesStore());
+
+        // Act & Assert
+        Assert.False(service.IsEnabled);
+    }
+}
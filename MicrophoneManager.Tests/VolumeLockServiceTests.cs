@@ -0,0 +1,106 @@
+using MicrophoneManager.Tests.Fakes;
+using MicrophoneManager.WinUI.Services;
+using Xunit;
+
+namespace MicrophoneManager.Tests;
+
+/// <summary>
+/// Tests for holding locked microphones at their target volume.
+/// </summary>
+public class VolumeLockServiceTests
+{
+    private static FakeAudioDeviceService CreateService()
+    {
+        return FakeAudioDeviceService.WithMicrophones(
+            "mic-1",
+            new FakeAudioDeviceService.FakeMicrophone("mic-1", "Desk Mic") { VolumeScalar = 0.6 },
+            new FakeAudioDeviceService.FakeMicrophone("mic-2", "Headset") { VolumeScalar = 0.4 });
+    }
+
+    [Fact]
+    public void ExternalChange_IsRevertedToTarget()
+    {
+        // Arrange
+        var fakeService = CreateService();
+        var service = new VolumeLockService(fakeService, new InMemoryPreferencesStore());
+        service.SetLock("mic-1", 0.6f);
+        VolumeLockService.VolumeRevertedEventArgs? reverted = null;
+        service.VolumeReverted += (s, e) => reverted = e;
+
+        // Act
+        fakeService.SetMicrophoneVolumeLevelScalar("mic-1", 1.0f);
+        fakeService.RaiseMicrophoneVolumeChanged("mic-1", 1.0f, false, changedExternally: true);
+
+        // Assert
+        Assert.Equal(0.6, fakeService.GetMicrophone("mic-1")!.VolumeLevel, 3);
+        Assert.NotNull(reverted);
+        Assert.Equal("Desk Mic", reverted!.DeviceName);
+        Assert.Equal(1.0f, reverted.AttemptedLevel);
+    }
+
+    [Fact]
+    public void OwnChange_MovesTarget()
+    {
+        // Arrange
+        var fakeService = CreateService();
+        var service = new VolumeLockService(fakeService, new InMemoryPreferencesStore());
+        service.SetLock("mic-1", 0.6f);
+
+        // Act
+        fakeService.SetMicrophoneVolumeLevelScalar("mic-1", 0.8f);
+        fakeService.RaiseMicrophoneVolumeChanged("mic-1", 0.8f, false);
+
+        // Assert
+        Assert.Equal(0.8f, service.GetTarget("mic-1"));
+        Assert.Equal(0.8, fakeService.GetMicrophone("mic-1")!.VolumeLevel, 3);
+    }
+
+    [Fact]
+    public void UnlockedDevice_IsLeftAlone()
+    {
+        // Arrange
+        var fakeService = CreateService();
+        var service = new VolumeLockService(fakeService, new InMemoryPreferencesStore());
+        service.SetLock("mic-1", 0.6f);
+
+        // Act
+        fakeService.SetMicrophoneVolumeLevelScalar("mic-2", 0.9f);
+        fakeService.RaiseMicrophoneVolumeChanged("mic-2", 0.9f, false, changedExternally: true);
+
+        // Assert
+        Assert.Equal(0.9, fakeService.GetMicrophone("mic-2")!.VolumeLevel, 3);
+    }
+
+    [Fact]
+    public void DevicesChanged_EnforcesEveryLock()
+    {
+        // Arrange
+        var fakeService = CreateService();
+        var preferences = new InMemoryPreferencesStore();
+        preferences.SetStringList(VolumeLockService.VolumeLocksPreference, new[] { "mic-2=0.25" });
+        using var service = new VolumeLockService(fakeService, preferences);
+
+        // Act
+        fakeService.RaiseDevicesChanged();
+
+        // Assert
+        Assert.Equal(0.25, fakeService.GetMicrophone("mic-2")!.VolumeLevel, 3);
+    }
+
+    [Fact]
+    public async Task Resume_EnforcesEveryLock()
+    {
+        // Arrange - the power service rebuilds a different audio service, so only Resumed reaches the lock
+        var fakeService = CreateService();
+        var preferences = new InMemoryPreferencesStore();
+        preferences.SetStringList(VolumeLockService.VolumeLocksPreference, new[] { "mic-2=0.25" });
+        var powerEvents = new PowerEventService(new FakeAudioDeviceService()) { ResumeSettleDelay = TimeSpan.Zero };
+        using var service = new VolumeLockService(fakeService, preferences, powerEvents);
+
+        // Act
+        await powerEvents.OnResumeAsync();
+
+        // Assert
+        Assert.Equal(0.25, fakeService.GetMicrophone("mic-2")!.VolumeLevel, 3);
+    }
+}
@@ -1,5 +1,6 @@
 using Microsoft.Extensions.DependencyInjection;
 using Microsoft.Extensions.Hosting;
+using Microsoft.Extensions.Logging;
 using Microsoft.UI.Dispatching;
 using Microsoft.UI.Xaml;
 using System;
@@ -39,12 +40,36 @@ public partial class App : Application
     /// </summary>
     public static DispatcherQueue MainDispatcherQueue { get; private set; } = null!;
 
-    // TODO: Remove these static references in favor of DI once full migration is complete
-    // Kept temporarily for compatibility with existing code patterns
-    public static object? TrayViewModel { get; set; }
-    public static object? AudioService { get; set; }
+    /// <summary>
+    /// The flyout's docked window, if open. Lives here because it outlasts the flyout that opened it.
+    /// </summary>
     public static Window? DockedWindow { get; set; }
 
+    /// <summary>
+    /// The change history window, if open.
+    /// </summary>
+    public static Window? HistoryWindow { get; set; }
+
+    /// <summary>
+    /// The application rules window, if open.
+    /// </summary>
+    public static Window? RulesWindow { get; set; }
+
+    /// <summary>
+    /// The level calibration window, if open.
+    /// </summary>
+    public static Window? CalibrationWindow { get; set; }
+
+    /// <summary>
+    /// The settings window, if open.
+    /// </summary>
+    public static Window? SettingsWindow { get; set; }
+
+    /// <summary>
+    /// The on-screen banner, once something has been shown in it. Hidden rather than closed between messages.
+    /// </summary>
+    public static Window? OsdWindow { get; set; }
+
     /// <summary>
     /// Initializes the singleton application object.
     /// </summary>
@@ -60,9 +85,14 @@ public partial class App : Application
             // Build dependency injection container
             Host = Microsoft.Extensions.Hosting.Host
                 .CreateDefaultBuilder()
+                .ConfigureLogging(ConfigureLogging)
                 .ConfigureServices(ConfigureServices)
                 .Build();
             LogError("DI container built");
+
+            // Crash reports include the tail of this log
+            MicrophoneManager.WinUI.Services.CrashReporter.AttachLog(
+                Host.Services.GetRequiredService<MicrophoneManager.WinUI.Services.RollingFileLoggerProvider>());
         }
         catch (Exception ex)
         {
@@ -71,27 +101,147 @@ public partial class App : Application
         }
     }
 
+    /// <summary>
+    /// Configure logging. Services log COM failures that would otherwise be swallowed, to the
+    /// debugger and to a rolling file whose level is the LogLevel preference.
+    /// </summary>
+    private static void ConfigureLogging(ILoggingBuilder logging)
+    {
+        // A tray app has no console; keep the debugger output and the log file.
+        logging.ClearProviders();
+        logging.AddDebug();
+
+        // Everything reaches the providers; the file provider applies the user's level itself so it can change at runtime
+        logging.SetMinimumLevel(LogLevel.Trace);
+#if DEBUG
+        logging.AddFilter<Microsoft.Extensions.Logging.Debug.DebugLoggerProvider>(null, LogLevel.Debug);
+#else
+        logging.AddFilter<Microsoft.Extensions.Logging.Debug.DebugLoggerProvider>(null, LogLevel.Information);
+#endif
+
+        logging.Services.AddSingleton<MicrophoneManager.WinUI.Services.RollingFileLoggerProvider>();
+        logging.Services.AddSingleton<ILoggerProvider>(sp => sp.GetRequiredService<MicrophoneManager.WinUI.Services.RollingFileLoggerProvider>());
+    }
+
     /// <summary>
     /// Configure dependency injection services
     /// </summary>
     private void ConfigureServices(IServiceCollection services)
     {
         // Register services
-        // ComThreadService provides STA thread for COM operations
-        services.AddSingleton<MicrophoneManager.WinUI.Services.ComThreadService>();
+        ConfigureAudioServices(services);
 
-        // PolicyConfigService requires ComThreadService
-        services.AddSingleton<MicrophoneManager.WinUI.Services.PolicyConfigService>();
+        // Preferences persist under HKCU\Software\MicrophoneManager, or in %APPDATA%\MicManager\config.toml once opted in
+        services.AddSingleton(sp => MicrophoneManager.WinUI.Services.PreferencesStoreFactory.Create(sp.GetService<ILoggerFactory>()));
+
+        // DevicePriorityService switches to the preferred microphone as devices come and go
+        services.AddSingleton<MicrophoneManager.WinUI.Services.DevicePriorityService>();
+
+        // ProfileService saves and applies named audio profiles
+        services.AddSingleton<MicrophoneManager.WinUI.Services.ProfileService>();
+
+        // SettingsTransferService exports and imports all preferences as one JSON file
+        services.AddSingleton<MicrophoneManager.WinUI.Services.SettingsTransferService>();
+
+        // DefaultDeviceLockService takes the default back when another app changes it
+        services.AddSingleton<MicrophoneManager.WinUI.Services.DefaultDeviceLockService>();
+
+        // DeviceAliasService keeps the names users give their microphones
+        services.AddSingleton<MicrophoneManager.WinUI.Services.DeviceAliasService>();
+
+        // HiddenDeviceService keeps the microphones users have hidden from the lists
+        services.AddSingleton<MicrophoneManager.WinUI.Services.HiddenDeviceService>();
+
+        // DeviceOrderService keeps pinned microphones and the user's list order
+        services.AddSingleton<MicrophoneManager.WinUI.Services.DeviceOrderService>();
+        // DeviceGroupService groups the endpoints of one physical device in the flyout
+        services.AddSingleton<MicrophoneManager.WinUI.Services.DeviceGroupService>();
+
+        // StartModeService decides what's shown at start and remembers where the docked window was left
+        services.AddSingleton<MicrophoneManager.WinUI.Services.StartModeService>();
 
-        // AudioDeviceService requires PolicyConfigService
-        services.AddSingleton<MicrophoneManager.WinUI.Services.IAudioDeviceService, MicrophoneManager.WinUI.Services.AudioDeviceService>();
+        // UndoService remembers recent changes made through the app so the last one can be reverted
+        services.AddSingleton<MicrophoneManager.WinUI.Services.UndoService>();
+
+        // VolumeLockService snaps locked microphones back to their target level
+        services.AddSingleton<MicrophoneManager.WinUI.Services.VolumeLockService>();
+
+        // QuietHoursService mutes the default microphone on a daily schedule
+        services.AddSingleton<MicrophoneManager.WinUI.Services.QuietHoursService>();
+
+        // ChangeHistoryService records default, volume and mute changes from this app and others
+        services.AddSingleton<MicrophoneManager.WinUI.Services.ChangeHistoryService>();
+
+        // AppRuleService switches the default microphone while chosen applications run
+        services.AddSingleton<MicrophoneManager.WinUI.Services.AppRuleService>();
+
+        // MutedSpeechService notices talking into the muted default microphone
+        services.AddSingleton<MicrophoneManager.WinUI.Services.MutedSpeechService>();
+
+        // SilentMicrophoneService warns when the default microphone records nothing but silence
+        services.AddSingleton<MicrophoneManager.WinUI.Services.SilentMicrophoneService>();
+
+        // ClippingService counts full-scale peaks on the default microphone
+        services.AddSingleton<MicrophoneManager.WinUI.Services.ClippingService>();
+
+        // MuteOsdService holds whether, where and how long the mute banner shows
+        services.AddSingleton<MicrophoneManager.WinUI.Services.MuteOsdService>();
+        // MeterSettingsService holds the flyout meter's mode and ballistics
+        services.AddSingleton<MicrophoneManager.WinUI.Services.MeterSettingsService>();
+        // LevelMeterService polls the flyout's meters on its own thread while they're on screen
+        services.AddSingleton<MicrophoneManager.WinUI.Services.LevelMeterService>();
+        // AppearanceService holds the windows' theme and the flyout's size
+        services.AddSingleton<MicrophoneManager.WinUI.Services.AppearanceService>();
+
+        // CalibrationService measures speaking level and recommends volume and boost
+        services.AddSingleton<MicrophoneManager.WinUI.Services.CalibrationService>();
+
+        // HookService runs the user's shell commands on mute, unmute and device removal
+        services.AddSingleton<MicrophoneManager.WinUI.Services.HookService>();
+
+        // ControlCommandDispatcher runs JSON commands from external tools; NamedPipeControlServer serves it on \\.\pipe\mic-manager
+        services.AddSingleton<MicrophoneManager.WinUI.Services.ControlCommandDispatcher>();
+        services.AddSingleton<MicrophoneManager.WinUI.Services.NamedPipeControlServer>();
+
+        // LocalApiServer is the opt-in HTTP/WebSocket endpoint for dashboards
+        services.AddSingleton<MicrophoneManager.WinUI.Services.LocalApiServer>();
+
+        // MqttPublisher mirrors the default microphone's mute state to an MQTT broker when configured
+        services.AddSingleton<MicrophoneManager.WinUI.Services.MqttPublisher>();
+
+        // UpdateService checks the GitHub releases feed for a newer version when the user opts in
+        services.AddSingleton<MicrophoneManager.WinUI.Services.UpdateService>();
+
+        // WindowMessageMonitor exposes the tray window's Win32 messages to the services below
+        services.AddSingleton<MicrophoneManager.WinUI.Services.WindowMessageMonitor>();
+
+        // HotkeyService registers global hotkeys on the tray window
+        services.AddSingleton<MicrophoneManager.WinUI.Services.HotkeyService>();
+
+        // TopologyProfileService applies the profile mapped to a display setup when docking or undocking
+        services.AddSingleton<MicrophoneManager.WinUI.Services.TopologyProfileService>();
+
+        // SessionLockService mutes microphones while the workstation is locked
+        services.AddSingleton<MicrophoneManager.WinUI.Services.SessionLockService>();
+
+        // PowerEventService rebuilds audio notifications after resume from sleep
+        services.AddSingleton<MicrophoneManager.WinUI.Services.PowerEventService>();
 
         // Register ViewModels
         services.AddSingleton<MicrophoneManager.WinUI.ViewModels.TrayViewModel>(sp =>
         {
             var audioService = sp.GetRequiredService<MicrophoneManager.WinUI.Services.IAudioDeviceService>();
+            var priorityService = sp.GetRequiredService<MicrophoneManager.WinUI.Services.DevicePriorityService>();
+            var profileService = sp.GetRequiredService<MicrophoneManager.WinUI.Services.ProfileService>();
+            var quietHoursService = sp.GetRequiredService<MicrophoneManager.WinUI.Services.QuietHoursService>();
+            var aliasService = sp.GetRequiredService<MicrophoneManager.WinUI.Services.DeviceAliasService>();
+            var hiddenDeviceService = sp.GetRequiredService<MicrophoneManager.WinUI.Services.HiddenDeviceService>();
+            var orderService = sp.GetRequiredService<MicrophoneManager.WinUI.Services.DeviceOrderService>();
+            var undoService = sp.GetRequiredService<MicrophoneManager.WinUI.Services.UndoService>();
+            var preferences = sp.GetRequiredService<MicrophoneManager.WinUI.Services.IPreferencesStore>();
             // Icon update callback will be set in MainWindow
-            return new MicrophoneManager.WinUI.ViewModels.TrayViewModel(audioService, _ => { });
+            return new MicrophoneManager.WinUI.ViewModels.TrayViewModel(
+                audioService, _ => { }, priorityService, profileService, quietHoursService, aliasService, hiddenDeviceService, orderService, undoService, preferences);
         });
 
         services.AddTransient<MicrophoneManager.WinUI.ViewModels.MicrophoneListViewModel>();
@@ -102,6 +252,43 @@ public partial class App : Application
         services.AddTransient<Views.MicrophoneFlyout>();
     }
 
+    /// <summary>
+    /// The audio stack on its own, shared by the tray and the command-line interface.
+    /// </summary>
+    /// <param name="monitorDevices">False for one-shot command-line runs, which shouldn't meter every microphone.</param>
+    internal static void ConfigureAudioServices(IServiceCollection services, bool monitorDevices = true)
+    {
+        // ComThreadService provides STA thread for COM operations
+        services.AddSingleton<MicrophoneManager.WinUI.Services.ComThreadService>();
+
+        // PolicyConfigService requires ComThreadService
+        services.AddSingleton<MicrophoneManager.WinUI.Services.PolicyConfigService>();
+
+        // DeviceTopologyService reads adapter-level controls (microphone boost)
+        services.AddSingleton<MicrophoneManager.WinUI.Services.DeviceTopologyService>();
+
+        // AudioEffectsService lists and toggles APO effects such as noise suppression (Windows 11)
+        services.AddSingleton<MicrophoneManager.WinUI.Services.AudioEffectsService>();
+
+        // MicTestService records and plays back a short microphone test
+        services.AddSingleton<MicrophoneManager.WinUI.Services.MicTestService>();
+
+        // RecordingService writes microphone captures to WAV files
+        services.AddSingleton<MicrophoneManager.WinUI.Services.RecordingService>();
+
+        // AudioDeviceService requires PolicyConfigService, DeviceTopologyService, AudioEffectsService, MicTestService and RecordingService,
+        // and reads its polling interval from the preferences store
+        if (monitorDevices)
+        {
+            services.AddSingleton<MicrophoneManager.WinUI.Services.IAudioDeviceService, MicrophoneManager.WinUI.Services.AudioDeviceService>();
+        }
+        else
+        {
+            services.AddSingleton<MicrophoneManager.WinUI.Services.IAudioDeviceService>(provider =>
+                ActivatorUtilities.CreateInstance<MicrophoneManager.WinUI.Services.AudioDeviceService>(provider, false));
+        }
+    }
+
     /// <summary>
     /// Invoked when the application is launched.
     /// </summary>
@@ -115,10 +302,6 @@ public partial class App : Application
             MainDispatcherQueue = DispatcherQueue.GetForCurrentThread();
             LogError("DispatcherQueue obtained");
 
-            // Initialize services
-            AudioService = Host.Services.GetRequiredService<MicrophoneManager.WinUI.Services.IAudioDeviceService>();
-            TrayViewModel = Host.Services.GetRequiredService<MicrophoneManager.WinUI.ViewModels.TrayViewModel>();
-
             // Create and activate main window (will be hidden, hosts tray icon)
             LogError("Creating MainWindow");
             m_window = Host.Services.GetRequiredService<MainWindow>();
@@ -1,10 +1,16 @@
+using Microsoft.Extensions.DependencyInjection;
+using Microsoft.Extensions.Logging;
 using Microsoft.UI.Windowing;
 using Microsoft.UI.Xaml;
+using Microsoft.UI.Xaml.Controls;
 using System;
 using System.ComponentModel;
 using System.Diagnostics;
+using System.Net.Http;
 using System.Windows.Input;
+using MicrophoneManager.WinUI.Models;
 using MicrophoneManager.WinUI.Services;
+using MicrophoneManager.WinUI.ViewModels;
 
 namespace MicrophoneManager.WinUI;
 
@@ -14,6 +20,80 @@ namespace MicrophoneManager.WinUI;
 public sealed partial class MainWindow : Window, INotifyPropertyChanged
 {
     private Views.MicrophoneWindow? _flyoutWindow;
+    private readonly WindowMessageMonitor _messageMonitor;
+    private readonly HotkeyService _hotkeyService;
+    private readonly SessionLockService _sessionLockService;
+    private readonly PowerEventService _powerEventService;
+    private readonly TopologyProfileService _topologyProfileService;
+    private readonly QuietHoursService _quietHoursService;
+    private readonly VolumeLockService _volumeLockService;
+    private readonly HiddenDeviceService _hiddenDeviceService;
+    private readonly DefaultDeviceLockService _defaultLockService;
+    private readonly NamedPipeControlServer _pipeServer;
+    private readonly LocalApiServer _localApiServer;
+    private readonly MqttPublisher _mqttPublisher;
+    private readonly HookService _hookService;
+    private readonly UpdateService _updateService;
+    private readonly ChangeHistoryService _historyService;
+    private readonly AppRuleService _ruleService;
+    private readonly MutedSpeechService _mutedSpeechService;
+    private readonly SilentMicrophoneService _silentMicrophoneService;
+    private readonly ClippingService _clippingService;
+    private readonly MuteOsdService _muteOsdService;
+    private readonly TrayViewModel _trayViewModel;
+    private readonly IAudioDeviceService _audioService;
+    private readonly IPreferencesStore _preferences;
+    private readonly ILogger<MainWindow> _logger;
+    private readonly StartModeService _startModeService;
+    private readonly MeterSettingsService _meterSettings;
+
+    // Offered in the tray until schedules can be edited freely
+    private static readonly QuietHoursWindow[] QuietHoursPresets =
+    {
+        new(new TimeOnly(22, 0), new TimeOnly(7, 0)),
+        new(new TimeOnly(18, 0), new TimeOnly(9, 0)),
+        new(new TimeOnly(12, 0), new TimeOnly(13, 0)),
+    };
+
+    internal static readonly (string Text, StartupOptions Options)[] StartupMethodChoices =
+    {
+        ("Sign-in (registry Run key)", new(StartupMethod.RunKey, TimeSpan.Zero)),
+        ("Scheduled task", new(StartupMethod.ScheduledTask, TimeSpan.Zero)),
+        ("Scheduled task, 30 seconds after sign-in", new(StartupMethod.ScheduledTask, TimeSpan.FromSeconds(30))),
+    };
+
+    internal static readonly (string Text, StartMode Mode)[] StartModeChoices =
+    {
+        ("Tray icon only", StartMode.Tray),
+        ("Microphone flyout", StartMode.Flyout),
+        ("Docked window", StartMode.Docked),
+    };
+
+    internal static readonly (string Text, MeterMode Mode)[] MeterModeChoices =
+    {
+        ("Peak", MeterMode.Peak),
+        ("RMS", MeterMode.Rms),
+        ("RMS with peak behind it", MeterMode.PeakAndRms),
+    };
+
+    internal static readonly (string Text, DeviceSortMode Mode)[] SortModeChoices =
+    {
+        ("My order", DeviceSortMode.Custom),
+        ("Name", DeviceSortMode.Name),
+        ("Default first", DeviceSortMode.DefaultFirst),
+        ("Last used", DeviceSortMode.LastUsed),
+        ("Level activity", DeviceSortMode.Activity),
+    };
+
+    internal static readonly (string Text, OsdPosition Position)[] OsdPositionChoices =
+    {
+        ("Top center", OsdPosition.TopCenter),
+        ("Top left", OsdPosition.TopLeft),
+        ("Top right", OsdPosition.TopRight),
+        ("Bottom center", OsdPosition.BottomCenter),
+        ("Bottom left", OsdPosition.BottomLeft),
+        ("Bottom right", OsdPosition.BottomRight),
+    };
     private bool _isDisposed;
 
     public event PropertyChangedEventHandler? PropertyChanged;
@@ -23,21 +103,145 @@ public sealed partial class MainWindow : Window, INotifyPropertyChanged
     public ICommand ToggleStartupCommand { get; }
     public ICommand ExitCommand { get; }
 
-    public string StartupMenuText => StartupService.IsStartupEnabled() ? "✓ Start with Windows" : "Start with Windows";
+    public string StartupMenuText => StartupService.KnownStartupEnabled == true ? "✓ Start with Windows" : "Start with Windows";
 
     public MainWindow()
     {
         // Create commands before InitializeComponent (needed for x:Bind)
         ShowFlyoutCommand = new RelayCommand(() => ShowFlyout());
         IconAttributionCommand = new RelayCommand(() => IconAttribution());
-        ToggleStartupCommand = new RelayCommand(() => { ToggleStartup(); OnPropertyChanged(nameof(StartupMenuText)); });
+        ToggleStartupCommand = new RelayCommand(() => ToggleStartup());
         ExitCommand = new RelayCommand(() => ExitApp());
 
         InitializeComponent();
 
+        _audioService = App.Host.Services.GetRequiredService<IAudioDeviceService>();
+        _trayViewModel = App.Host.Services.GetRequiredService<TrayViewModel>();
+        _preferences = App.Host.Services.GetRequiredService<IPreferencesStore>();
+        _logger = App.Host.Services.GetRequiredService<ILogger<MainWindow>>();
+
         // Don't show in taskbar/switchers
         AppWindow.IsShownInSwitchers = false;
 
+        // Output device submenu mirrors the tray view model's render device list
+        _trayViewModel.PropertyChanged += TrayViewModel_PropertyChanged;
+        RebuildMicrophoneMenu(_trayViewModel);
+        RebuildOutputDeviceMenu(_trayViewModel);
+        RebuildProfilesMenu(_trayViewModel);
+
+        UndoMenuItem.Command = _trayViewModel.UndoCommand;
+        UndoMenuItem.Text = _trayViewModel.UndoMenuText;
+        UndoMenuItem.IsEnabled = _trayViewModel.CanUndo;
+        MuteMenuItem.Command = _trayViewModel.ToggleMuteCommand;
+        MuteMenuItem.Text = _trayViewModel.MuteMenuText;
+        MuteAllMenuItem.Command = _trayViewModel.ToggleMuteAllCommand;
+        MuteAllMenuItem.Text = _trayViewModel.MuteAllMenuText;
+        SaveSnapshotMenuItem.Command = _trayViewModel.SaveSnapshotCommand;
+        RestoreSnapshotMenuItem.Command = _trayViewModel.RestoreSnapshotCommand;
+        DuckingMenuItem.Command = _trayViewModel.ToggleDuckingCommand;
+        DuckingMenuItem.IsChecked = _trayViewModel.IsDuckingEnabled;
+        AutoFailoverMenuItem.Command = _trayViewModel.ToggleAutoFailoverCommand;
+        AutoFailoverMenuItem.IsChecked = _trayViewModel.IsAutoFailoverEnabled;
+
+        // Both only exist as registry settings, which a portable copy doesn't write
+        if (PortableMode.IsEnabled)
+        {
+            StartupMenuItem.Visibility = Visibility.Collapsed;
+            StartupMethodMenu.Visibility = Visibility.Collapsed;
+            DuckingMenuItem.Visibility = Visibility.Collapsed;
+            TrayIcon.ToolTipText = "Microphone Manager (portable)";
+        }
+
+        // Hotkeys and session notifications arrive as messages on this (hidden) window
+        _messageMonitor = App.Host.Services.GetRequiredService<WindowMessageMonitor>();
+        _messageMonitor.Attach(WinRT.Interop.WindowNative.GetWindowHandle(this));
+
+        _hotkeyService = App.Host.Services.GetRequiredService<HotkeyService>();
+        _hotkeyService.HotkeyPressed += HotkeyService_HotkeyPressed;
+        _hotkeyService.BindingsChanged += (s, e) => RebuildHotkeysMenu();
+        _hotkeyService.Attach(_messageMonitor);
+        RebuildHotkeysMenu();
+
+        _sessionLockService = App.Host.Services.GetRequiredService<SessionLockService>();
+        _sessionLockService.Attach(_messageMonitor);
+        MuteOnLockMenuItem.IsChecked = _sessionLockService.IsMuteOnLockEnabled;
+
+        _powerEventService = App.Host.Services.GetRequiredService<PowerEventService>();
+        _powerEventService.Attach(_messageMonitor);
+
+        _topologyProfileService = App.Host.Services.GetRequiredService<TopologyProfileService>();
+        _topologyProfileService.ProfileApplied += TopologyProfileService_ProfileApplied;
+        _topologyProfileService.TopologyChanged += TopologyProfileService_TopologyChanged;
+        _topologyProfileService.MappingsChanged += TopologyProfileService_MappingsChanged;
+        _topologyProfileService.Attach(_messageMonitor);
+        RebuildDisplaySetupMenu();
+
+        _quietHoursService = App.Host.Services.GetRequiredService<QuietHoursService>();
+        RebuildQuietHoursMenu();
+        RebuildStartupMethodMenu();
+
+        _startModeService = App.Host.Services.GetRequiredService<StartModeService>();
+        RebuildStartModeMenu();
+
+        _meterSettings = App.Host.Services.GetRequiredService<MeterSettingsService>();
+        RebuildMeterModeMenu();
+
+        _volumeLockService = App.Host.Services.GetRequiredService<VolumeLockService>();
+        _volumeLockService.VolumeReverted += VolumeLockService_VolumeReverted;
+        VolumeLockNotifyMenuItem.IsChecked = _volumeLockService.NotifyOnRevert;
+
+        _hiddenDeviceService = App.Host.Services.GetRequiredService<HiddenDeviceService>();
+        ShowHiddenMenuItem.IsChecked = _hiddenDeviceService.ShowHidden;
+
+        _defaultLockService = App.Host.Services.GetRequiredService<DefaultDeviceLockService>();
+        _defaultLockService.DefaultReverted += DefaultLockService_DefaultReverted;
+        _defaultLockService.LockChanged += DefaultLockService_LockChanged;
+        DefaultLockMenuItem.IsChecked = _defaultLockService.IsLocked;
+
+        // Resolved here so the dispatcher captures the UI thread's context
+        _pipeServer = App.Host.Services.GetRequiredService<NamedPipeControlServer>();
+        _pipeServer.Start();
+
+        _localApiServer = App.Host.Services.GetRequiredService<LocalApiServer>();
+        _localApiServer.Start();
+        UpdateLocalApiMenu();
+
+        _mqttPublisher = App.Host.Services.GetRequiredService<MqttPublisher>();
+        _mqttPublisher.Start();
+        MqttMenuItem.IsChecked = _mqttPublisher.IsEnabled;
+
+        _hookService = App.Host.Services.GetRequiredService<HookService>();
+
+        // Created now so changes are recorded from the start, not from when the window first opens
+        _historyService = App.Host.Services.GetRequiredService<ChangeHistoryService>();
+
+        // Starts watching for the applications in the user's rules
+        _ruleService = App.Host.Services.GetRequiredService<AppRuleService>();
+
+        _mutedSpeechService = App.Host.Services.GetRequiredService<MutedSpeechService>();
+        _mutedSpeechService.SpeechWhileMuted += MutedSpeechService_SpeechWhileMuted;
+        MutedSpeechMenuItem.IsChecked = _mutedSpeechService.IsEnabled;
+
+        // Mute changes from hotkeys and the tray menu flash up on screen
+        _muteOsdService = App.Host.Services.GetRequiredService<MuteOsdService>();
+        _trayViewModel.MuteToggled += TrayViewModel_MuteToggled;
+        MuteOsdMenuItem.IsChecked = _muteOsdService.IsEnabled;
+
+        _silentMicrophoneService = App.Host.Services.GetRequiredService<SilentMicrophoneService>();
+        _silentMicrophoneService.MicrophoneSilent += SilentMicrophoneService_MicrophoneSilent;
+        SilentMicMenuItem.IsChecked = _silentMicrophoneService.IsEnabled;
+
+        // Created now so clips are counted from the start, not from when the flyout first opens
+        _clippingService = App.Host.Services.GetRequiredService<ClippingService>();
+        _clippingService.ClippingAlert += ClippingService_ClippingAlert;
+        ClipAlertMenuItem.IsChecked = _clippingService.IsAlertEnabled;
+
+        _updateService = App.Host.Services.GetRequiredService<UpdateService>();
+        _updateService.UpdateAvailable += UpdateService_UpdateAvailable;
+        _updateService.AvailableUpdateChanged += UpdateService_AvailableUpdateChanged;
+        _updateService.Start();
+        UpdateCheckMenuItem.IsChecked = _updateService.IsEnabled;
+
         // Subscribe to Activated event to hide the window after it's shown
         Activated += MainWindow_Activated;
         Closed += MainWindow_Closed;
@@ -48,6 +252,482 @@ public sealed partial class MainWindow : Window, INotifyPropertyChanged
         PropertyChanged?.Invoke(this, new PropertyChangedEventArgs(propertyName));
     }
 
+    private void TrayViewModel_PropertyChanged(object? sender, PropertyChangedEventArgs e)
+    {
+        if (sender is not TrayViewModel trayViewModel) return;
+
+        if (e.PropertyName == nameof(TrayViewModel.Microphones))
+        {
+            RebuildMicrophoneMenu(trayViewModel);
+        }
+        else if (e.PropertyName == nameof(TrayViewModel.OutputDevices))
+        {
+            RebuildOutputDeviceMenu(trayViewModel);
+        }
+        else if (e.PropertyName == nameof(TrayViewModel.Profiles))
+        {
+            RebuildProfilesMenu(trayViewModel);
+            RebuildDisplaySetupMenu();
+        }
+        else if (e.PropertyName == nameof(TrayViewModel.MuteMenuText))
+        {
+            MuteMenuItem.Text = trayViewModel.MuteMenuText;
+        }
+        else if (e.PropertyName == nameof(TrayViewModel.MuteAllMenuText))
+        {
+            MuteAllMenuItem.Text = trayViewModel.MuteAllMenuText;
+        }
+        else if (e.PropertyName == nameof(TrayViewModel.UndoMenuText))
+        {
+            UndoMenuItem.Text = trayViewModel.UndoMenuText;
+        }
+        else if (e.PropertyName == nameof(TrayViewModel.CanUndo))
+        {
+            UndoMenuItem.IsEnabled = trayViewModel.CanUndo;
+        }
+        else if (e.PropertyName == nameof(TrayViewModel.HasSnapshot))
+        {
+            RestoreSnapshotMenuItem.IsEnabled = trayViewModel.HasSnapshot;
+        }
+        else if (e.PropertyName == nameof(TrayViewModel.IsDuckingEnabled))
+        {
+            DuckingMenuItem.IsChecked = trayViewModel.IsDuckingEnabled;
+        }
+        else if (e.PropertyName == nameof(TrayViewModel.IsAutoFailoverEnabled))
+        {
+            AutoFailoverMenuItem.IsChecked = trayViewModel.IsAutoFailoverEnabled;
+        }
+    }
+
+    private void RebuildMicrophoneMenu(TrayViewModel trayViewModel)
+    {
+        MicrophoneMenu.Items.Clear();
+
+        foreach (var device in trayViewModel.Microphones)
+        {
+            MicrophoneMenu.Items.Add(new ToggleMenuFlyoutItem
+            {
+                Text = trayViewModel.DisplayNameFor(device),
+                IsChecked = device.IsDefault,
+                Command = trayViewModel.SetDefaultMicrophoneCommand,
+                CommandParameter = device.Id
+            });
+        }
+
+        MicrophoneMenu.IsEnabled = trayViewModel.Microphones.Count > 0;
+    }
+
+    private void RebuildOutputDeviceMenu(TrayViewModel trayViewModel)
+    {
+        OutputDeviceMenu.Items.Clear();
+
+        foreach (var device in trayViewModel.OutputDevices)
+        {
+            OutputDeviceMenu.Items.Add(new ToggleMenuFlyoutItem
+            {
+                Text = device.Name,
+                IsChecked = device.IsDefault,
+                Command = trayViewModel.SetDefaultOutputCommand,
+                CommandParameter = device.Id
+            });
+        }
+
+        if (trayViewModel.OutputDevices.Count > 0)
+        {
+            var defaultOutput = trayViewModel.OutputDevices.FirstOrDefault(d => d.IsDefault);
+            OutputDeviceMenu.Items.Add(new MenuFlyoutSeparator());
+            OutputDeviceMenu.Items.Add(new ToggleMenuFlyoutItem
+            {
+                Text = "Mute output",
+                IsChecked = defaultOutput?.IsMuted ?? false,
+                Command = trayViewModel.ToggleDefaultOutputMuteCommand
+            });
+        }
+
+        OutputDeviceMenu.IsEnabled = trayViewModel.OutputDevices.Count > 0;
+    }
+
+    private void RebuildProfilesMenu(TrayViewModel trayViewModel)
+    {
+        ProfilesMenu.Items.Clear();
+
+        foreach (var profile in trayViewModel.Profiles)
+        {
+            ProfilesMenu.Items.Add(new MenuFlyoutItem
+            {
+                Text = profile.Name,
+                Command = trayViewModel.ApplyProfileCommand,
+                CommandParameter = profile.Name
+            });
+        }
+
+        if (trayViewModel.Profiles.Count > 0)
+        {
+            ProfilesMenu.Items.Add(new MenuFlyoutSeparator());
+        }
+
+        ProfilesMenu.Items.Add(new MenuFlyoutItem
+        {
+            Text = "Save current as new profile",
+            Command = trayViewModel.SaveProfileCommand
+        });
+
+        var manageItem = new MenuFlyoutItem { Text = "Name and manage profiles…" };
+        manageItem.Click += ManageProfilesMenuItem_Click;
+        ProfilesMenu.Items.Add(manageItem);
+
+        if (trayViewModel.Profiles.Count > 0)
+        {
+            var deleteMenu = new MenuFlyoutSubItem { Text = "Delete profile" };
+            foreach (var profile in trayViewModel.Profiles)
+            {
+                deleteMenu.Items.Add(new MenuFlyoutItem
+                {
+                    Text = profile.Name,
+                    Command = trayViewModel.DeleteProfileCommand,
+                    CommandParameter = profile.Name
+                });
+            }
+            ProfilesMenu.Items.Add(deleteMenu);
+        }
+    }
+
+    private void RebuildDisplaySetupMenu()
+    {
+        DisplaySetupMenu.Items.Clear();
+
+        var current = _topologyProfileService.GetProfileForCurrent();
+        DisplaySetupMenu.Items.Add(new MenuFlyoutItem
+        {
+            Text = _topologyProfileService.CurrentTopology.Description,
+            IsEnabled = false
+        });
+
+        var none = new ToggleMenuFlyoutItem { Text = "None", IsChecked = current == null };
+        none.Click += (s, e) => _topologyProfileService.SetProfileForCurrent(null);
+        DisplaySetupMenu.Items.Add(none);
+
+        foreach (var profile in _trayViewModel.Profiles)
+        {
+            var item = new ToggleMenuFlyoutItem
+            {
+                Text = profile.Name,
+                IsChecked = string.Equals(profile.Name, current, StringComparison.OrdinalIgnoreCase)
+            };
+            item.Click += (s, e) => _topologyProfileService.SetProfileForCurrent(profile.Name);
+            DisplaySetupMenu.Items.Add(item);
+        }
+    }
+
+    private void RebuildHotkeysMenu()
+    {
+        HotkeysMenu.Items.Clear();
+
+        var bindings = _hotkeyService.GetBindings();
+        foreach (var (action, binding) in bindings)
+        {
+            var failed = _hotkeyService.FailedRegistrations.Contains(action);
+            var text = binding == null
+                ? action.GetDisplayName()
+                : $"{action.GetDisplayName()} ({binding}){(failed ? " - in use by another app" : "")}";
+
+            // Unchecking turns the hotkey off; checking brings back its default binding
+            var item = new ToggleMenuFlyoutItem { Text = text, IsChecked = binding != null && !failed };
+            item.Click += (s, e) => _hotkeyService.SetBinding(action, binding == null ? HotkeyService.DefaultBindings[action] : null);
+            HotkeysMenu.Items.Add(item);
+        }
+
+        HotkeysMenu.Items.Add(new MenuFlyoutSeparator());
+        var reset = new MenuFlyoutItem { Text = "Restore default hotkeys" };
+        reset.Click += (s, e) => _hotkeyService.ResetToDefaults();
+        HotkeysMenu.Items.Add(reset);
+    }
+
+    private void RebuildStartupMethodMenu()
+    {
+        StartupMethodMenu.Items.Clear();
+
+        var current = StartupService.GetOptions(_preferences);
+        foreach (var (text, options) in StartupMethodChoices)
+        {
+            var item = new ToggleMenuFlyoutItem { Text = text, IsChecked = options == current };
+            item.Click += (s, e) =>
+            {
+                _ = SetStartupOptionsAsync(options);
+            };
+            StartupMethodMenu.Items.Add(item);
+        }
+    }
+
+    private void RebuildStartModeMenu()
+    {
+        StartModeMenu.Items.Clear();
+
+        var current = _startModeService.Mode;
+        foreach (var (text, mode) in StartModeChoices)
+        {
+            var item = new ToggleMenuFlyoutItem { Text = text, IsChecked = mode == current };
+            item.Click += (s, e) =>
+            {
+                _startModeService.Mode = mode;
+                RebuildStartModeMenu();
+            };
+            StartModeMenu.Items.Add(item);
+        }
+    }
+
+    private void RebuildMeterModeMenu()
+    {
+        MeterModeMenu.Items.Clear();
+
+        var current = _meterSettings.Mode;
+        foreach (var (text, mode) in MeterModeChoices)
+        {
+            var item = new ToggleMenuFlyoutItem { Text = text, IsChecked = mode == current };
+            item.Click += (s, e) =>
+            {
+                _meterSettings.Mode = mode;
+                RebuildMeterModeMenu();
+            };
+            MeterModeMenu.Items.Add(item);
+        }
+    }
+
+    private void RebuildQuietHoursMenu()
+    {
+        QuietHoursMenu.Items.Clear();
+
+        var windows = _quietHoursService.GetWindows();
+        foreach (var window in windows.Union(QuietHoursPresets))
+        {
+            var item = new ToggleMenuFlyoutItem { Text = window.ToString(), IsChecked = windows.Contains(window) };
+            item.Click += (s, e) =>
+            {
+                var current = _quietHoursService.GetWindows().ToList();
+                if (!current.Remove(window)) current.Add(window);
+                _quietHoursService.SetWindows(current);
+                RebuildQuietHoursMenu();
+            };
+            QuietHoursMenu.Items.Add(item);
+        }
+    }
+
+    private void TopologyProfileService_ProfileApplied(object? sender, TopologyProfileMapping mapping)
+    {
+        DispatcherQueue.TryEnqueue(() =>
+        {
+            try
+            {
+                TrayIcon.ShowNotification("Display setup changed", $"Applied the \"{mapping.ProfileName}\" profile.");
+            }
+            catch (Exception ex)
+            {
+                _logger.LogWarning(ex, "Display setup notification failed");
+            }
+        });
+    }
+
+    private void TopologyProfileService_TopologyChanged(object? sender, DisplayTopology topology)
+    {
+        DispatcherQueue.TryEnqueue(RebuildDisplaySetupMenu);
+    }
+
+    private void TopologyProfileService_MappingsChanged(object? sender, EventArgs e)
+    {
+        RebuildDisplaySetupMenu();
+    }
+
+    private void VolumeLockNotifyMenuItem_Click(object sender, RoutedEventArgs e)
+    {
+        _volumeLockService.NotifyOnRevert = !_volumeLockService.NotifyOnRevert;
+        VolumeLockNotifyMenuItem.IsChecked = _volumeLockService.NotifyOnRevert;
+    }
+
+    private void ShowHiddenMenuItem_Click(object sender, RoutedEventArgs e)
+    {
+        _hiddenDeviceService.ShowHidden = !_hiddenDeviceService.ShowHidden;
+        ShowHiddenMenuItem.IsChecked = _hiddenDeviceService.ShowHidden;
+    }
+
+    private void VolumeLockService_VolumeReverted(object? sender, VolumeLockService.VolumeRevertedEventArgs e)
+    {
+        if (!_volumeLockService.NotifyOnRevert) return;
+
+        // Endpoint callbacks arrive on an audio thread
+        DispatcherQueue.TryEnqueue(() =>
+        {
+            try
+            {
+                TrayIcon.ShowNotification(
+                    "Microphone volume restored",
+                    $"{e.DeviceName} was changed to {e.AttemptedLevel:P0}; put back to {e.TargetLevel:P0}.");
+            }
+            catch (Exception ex)
+            {
+                _logger.LogWarning(ex, "Volume lock notification failed");
+            }
+        });
+    }
+
+    private void MutedSpeechMenuItem_Click(object sender, RoutedEventArgs e)
+    {
+        _mutedSpeechService.IsEnabled = !_mutedSpeechService.IsEnabled;
+        MutedSpeechMenuItem.IsChecked = _mutedSpeechService.IsEnabled;
+    }
+
+    private void MutedSpeechService_SpeechWhileMuted(object? sender, MutedSpeechService.MutedSpeechEventArgs e)
+    {
+        DispatcherQueue.TryEnqueue(() =>
+        {
+            try
+            {
+                Views.OsdWindow.ShowMessage(
+                    "\uE74F",
+                    $"You're muted ({e.DeviceName})",
+                    "Unmute",
+                    () => _audioService.SetMute(e.DeviceId, false),
+                    TimeSpan.FromSeconds(6));
+            }
+            catch (Exception ex)
+            {
+                _logger.LogWarning(ex, "Muted speech alert failed");
+            }
+        });
+    }
+
+    private void MuteOsdMenuItem_Click(object sender, RoutedEventArgs e)
+    {
+        _muteOsdService.IsEnabled = !_muteOsdService.IsEnabled;
+        MuteOsdMenuItem.IsChecked = _muteOsdService.IsEnabled;
+    }
+
+    private void TrayViewModel_MuteToggled(object? sender, TrayViewModel.MuteToggledEventArgs e)
+    {
+        if (!_muteOsdService.IsEnabled) return;
+
+        DispatcherQueue.TryEnqueue(() =>
+        {
+            try
+            {
+                Views.OsdWindow.ShowMessage(
+                    e.IsMuted ? "\uE74F" : "\uE720",
+                    MuteOsdService.GetMessage(e.IsMuted, e.AllMicrophones),
+                    duration: TimeSpan.FromMilliseconds(_muteOsdService.DurationMs),
+                    position: _muteOsdService.Position);
+            }
+            catch (Exception ex)
+            {
+                _logger.LogWarning(ex, "Mute OSD failed");
+            }
+        });
+    }
+
+    private void SilentMicMenuItem_Click(object sender, RoutedEventArgs e)
+    {
+        _silentMicrophoneService.IsEnabled = !_silentMicrophoneService.IsEnabled;
+        SilentMicMenuItem.IsChecked = _silentMicrophoneService.IsEnabled;
+    }
+
+    private void SilentMicrophoneService_MicrophoneSilent(object? sender, SilentMicrophoneService.SilentMicrophoneEventArgs e)
+    {
+        DispatcherQueue.TryEnqueue(() =>
+        {
+            try
+            {
+                TrayIcon.ShowNotification(
+                    "Your microphone appears silent",
+                    $"{e.RecordingApplication} is recording from {e.DeviceName}, but there's been no sound for {e.SilentFor.TotalSeconds:0} seconds. Check the cable and any mute switch on the device.");
+            }
+            catch (Exception ex)
+            {
+                _logger.LogWarning(ex, "Silent microphone notification failed");
+            }
+        });
+    }
+
+    private void ClipAlertMenuItem_Click(object sender, RoutedEventArgs e)
+    {
+        _clippingService.IsAlertEnabled = !_clippingService.IsAlertEnabled;
+        ClipAlertMenuItem.IsChecked = _clippingService.IsAlertEnabled;
+    }
+
+    private void ClippingService_ClippingAlert(object? sender, ClippingService.ClipEventArgs e)
+    {
+        if (!_clippingService.IsAlertEnabled) return;
+
+        DispatcherQueue.TryEnqueue(() =>
+        {
+            try
+            {
+                TrayIcon.ShowNotification(
+                    "Your mic is clipping",
+                    $"{e.DeviceName} keeps hitting 0 dBFS. Lower its volume or the gain on the device.");
+            }
+            catch (Exception ex)
+            {
+                _logger.LogWarning(ex, "Clipping notification failed");
+            }
+        });
+    }
+
+    private void DefaultLockMenuItem_Click(object sender, RoutedEventArgs e)
+    {
+        _defaultLockService.SetLocked(!_defaultLockService.IsLocked);
+        DefaultLockMenuItem.IsChecked = _defaultLockService.IsLocked;
+    }
+
+    private void DefaultLockService_LockChanged(object? sender, EventArgs e)
+    {
+        DispatcherQueue.TryEnqueue(() => DefaultLockMenuItem.IsChecked = _defaultLockService.IsLocked);
+    }
+
+    private void DefaultLockService_DefaultReverted(object? sender, DefaultDeviceLockService.DefaultRevertedEventArgs e)
+    {
+        if (!_defaultLockService.NotifyOnRevert) return;
+
+        var culprit = e.LikelyCulprit != null ? $" (probably by {e.LikelyCulprit})" : string.Empty;
+        var replacedBy = e.ReplacedByName ?? "another device";
+
+        DispatcherQueue.TryEnqueue(() =>
+        {
+            try
+            {
+                TrayIcon.ShowNotification(
+                    "Default microphone restored",
+                    $"The default was switched to {replacedBy}{culprit}; {e.LockedDeviceName} is the default again.");
+            }
+            catch (Exception ex)
+            {
+                _logger.LogWarning(ex, "Default lock notification failed");
+            }
+        });
+    }
+
+    private void MuteOnLockMenuItem_Click(object sender, RoutedEventArgs e)
+    {
+        _sessionLockService.IsMuteOnLockEnabled = !_sessionLockService.IsMuteOnLockEnabled;
+        MuteOnLockMenuItem.IsChecked = _sessionLockService.IsMuteOnLockEnabled;
+    }
+
+    private void HotkeyService_HotkeyPressed(object? sender, HotkeyAction action)
+    {
+        if (action == HotkeyAction.OpenFlyout)
+        {
+            ShowFlyout();
+            return;
+        }
+
+        ICommand command = action switch
+        {
+            HotkeyAction.ToggleMute => _trayViewModel.ToggleMuteCommand,
+            HotkeyAction.MuteAll => _trayViewModel.ToggleMuteAllCommand,
+            HotkeyAction.CycleDefaultMicrophone => _trayViewModel.CycleDefaultMicrophoneCommand,
+            HotkeyAction.VolumeUp => _trayViewModel.VolumeUpCommand,
+            _ => _trayViewModel.VolumeDownCommand
+        };
+        command.Execute(null);
+    }
+
     private void MainWindow_Activated(object sender, WindowActivatedEventArgs args)
     {
         // Only process on first activation
@@ -58,9 +738,33 @@ public sealed partial class MainWindow : Window, INotifyPropertyChanged
         DispatcherQueue.TryEnqueue(Microsoft.UI.Dispatching.DispatcherQueuePriority.Low, () =>
         {
             AppWindow.MoveAndResize(new Windows.Graphics.RectInt32(-32000, -32000, 1, 1));
+            ShowStartWindow();
         });
     }
 
+    /// <summary>
+    /// Opens whatever the user chose to see at start; with the default (tray only) nothing.
+    /// </summary>
+    private void ShowStartWindow()
+    {
+        try
+        {
+            switch (_startModeService.Mode)
+            {
+                case StartMode.Flyout:
+                    ShowFlyout();
+                    break;
+                case StartMode.Docked:
+                    Views.MicrophoneWindow.ShowDocked();
+                    break;
+            }
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "ShowStartWindow failed");
+        }
+    }
+
     private void ShowFlyout()
     {
         if (_flyoutWindow == null || !IsWindowVisible(_flyoutWindow))
@@ -98,6 +802,23 @@ public sealed partial class MainWindow : Window, INotifyPropertyChanged
         }
         catch { }
 
+        // Closing the docked window saves where it was for the next start
+        try
+        {
+            App.DockedWindow?.Close();
+        }
+        catch { }
+
+        try
+        {
+            App.HistoryWindow?.Close();
+            App.RulesWindow?.Close();
+            App.CalibrationWindow?.Close();
+            App.SettingsWindow?.Close();
+            App.OsdWindow?.Close();
+        }
+        catch { }
+
         DisposeServices();
 
         // Close this window
@@ -131,9 +852,331 @@ public sealed partial class MainWindow : Window, INotifyPropertyChanged
         }
     }
 
-    private void ToggleStartup()
+    private void ManageProfilesMenuItem_Click(object sender, RoutedEventArgs e)
+    {
+        try
+        {
+            Views.SettingsWindow.ShowSettings("Profiles");
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Open profile settings failed");
+        }
+    }
+
+    private void SettingsMenuItem_Click(object sender, RoutedEventArgs e)
+    {
+        try
+        {
+            Views.SettingsWindow.ShowSettings();
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Open settings failed");
+        }
+    }
+
+    /// <summary>
+    /// The settings window changes the same settings as the tray menu, so read them again each time it opens.
+    /// </summary>
+    private void TrayMenu_Opening(object? sender, object e)
+    {
+        _ = RefreshStartupMenuTextAsync();
+        RebuildStartupMethodMenu();
+        RebuildStartModeMenu();
+        RebuildMeterModeMenu();
+        _trayViewModel.RefreshMicrophones();
+        ShowHiddenMenuItem.IsChecked = _hiddenDeviceService.ShowHidden;
+        VolumeLockNotifyMenuItem.IsChecked = _volumeLockService.NotifyOnRevert;
+        MuteOnLockMenuItem.IsChecked = _sessionLockService.IsMuteOnLockEnabled;
+        MutedSpeechMenuItem.IsChecked = _mutedSpeechService.IsEnabled;
+        MuteOsdMenuItem.IsChecked = _muteOsdService.IsEnabled;
+        SilentMicMenuItem.IsChecked = _silentMicrophoneService.IsEnabled;
+        ClipAlertMenuItem.IsChecked = _clippingService.IsAlertEnabled;
+        UpdateCheckMenuItem.IsChecked = _updateService.IsEnabled;
+    }
+
+    private void RulesMenuItem_Click(object sender, RoutedEventArgs e)
+    {
+        try
+        {
+            Views.RulesWindow.ShowRules();
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Open application rules failed");
+        }
+    }
+
+    private void HistoryMenuItem_Click(object sender, RoutedEventArgs e)
+    {
+        try
+        {
+            Views.HistoryWindow.ShowHistory();
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Open history failed");
+        }
+    }
+
+    private void LocalApiMenuItem_Click(object sender, RoutedEventArgs e)
+    {
+        _localApiServer.IsEnabled = !_localApiServer.IsEnabled;
+        UpdateLocalApiMenu();
+
+        if (_localApiServer.IsEnabled && !_localApiServer.IsRunning)
+        {
+            TrayIcon.ShowNotification("Local API unavailable", $"Couldn't listen on {_localApiServer.Address}. Another program may be using the port.");
+        }
+    }
+
+    private void UpdateCheckMenuItem_Click(object sender, RoutedEventArgs e)
+    {
+        _updateService.IsEnabled = !_updateService.IsEnabled;
+        UpdateCheckMenuItem.IsChecked = _updateService.IsEnabled;
+    }
+
+    private async void CheckForUpdatesMenuItem_Click(object sender, RoutedEventArgs e)
+    {
+        try
+        {
+            var update = await _updateService.CheckNowAsync();
+            if (update == null)
+            {
+                TrayIcon.ShowNotification("No updates", $"Microphone Manager {BuildInfo.Current.Version} is the latest version.");
+            }
+            else
+            {
+                ShowUpdateNotification(update);
+            }
+        }
+        catch (HttpRequestException ex)
+        {
+            _logger.LogWarning(ex, "Update check failed");
+            TrayIcon.ShowNotification("Couldn't check for updates", ex.Message);
+        }
+    }
+
+    private void UpdateService_UpdateAvailable(object? sender, UpdateInfo update)
+    {
+        DispatcherQueue.TryEnqueue(() =>
+        {
+            try
+            {
+                ShowUpdateNotification(update);
+            }
+            catch (Exception ex)
+            {
+                _logger.LogWarning(ex, "Update notification failed");
+            }
+        });
+    }
+
+    private void ShowUpdateNotification(UpdateInfo update)
+    {
+        TrayIcon.ShowNotification(
+            "New version available",
+            $"Microphone Manager {update.Version} is available. Choose \"Download version {update.Version}\" in the tray menu to open the release page.");
+    }
+
+    private void UpdateService_AvailableUpdateChanged(object? sender, EventArgs e)
+    {
+        DispatcherQueue.TryEnqueue(() =>
+        {
+            var update = _updateService.AvailableUpdate;
+            DownloadUpdateMenuItem.Text = update != null ? $"Download version {update.Version}" : string.Empty;
+            DownloadUpdateMenuItem.Visibility = update != null ? Visibility.Visible : Visibility.Collapsed;
+        });
+    }
+
+    private void DownloadUpdateMenuItem_Click(object sender, RoutedEventArgs e)
+    {
+        if (_updateService.AvailableUpdate is not { } update) return;
+
+        try
+        {
+            Process.Start(new ProcessStartInfo(update.DownloadUrl) { UseShellExecute = true });
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Open release page failed");
+        }
+    }
+
+    private void CopyLocalApiMenuItem_Click(object sender, RoutedEventArgs e)
+    {
+        try
+        {
+            var package = new Windows.ApplicationModel.DataTransfer.DataPackage();
+            package.SetText($"{_localApiServer.Address}\nAuthorization: Bearer {_localApiServer.Token}");
+            Windows.ApplicationModel.DataTransfer.Clipboard.SetContent(package);
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Copy local API details failed");
+        }
+    }
+
+    private void RegenerateLocalApiTokenMenuItem_Click(object sender, RoutedEventArgs e)
+    {
+        _localApiServer.RegenerateToken();
+    }
+
+    private void MqttMenuItem_Click(object sender, RoutedEventArgs e)
+    {
+        if (!_mqttPublisher.IsEnabled && string.IsNullOrWhiteSpace(_mqttPublisher.Host))
+        {
+            MqttMenuItem.IsChecked = false;
+            TrayIcon.ShowNotification(
+                "MQTT broker not configured",
+                $"Set {MqttPublisher.HostPreference} under HKCU\\Software\\MicrophoneManager, then turn this on again.");
+            return;
+        }
+
+        _mqttPublisher.IsEnabled = !_mqttPublisher.IsEnabled;
+        MqttMenuItem.IsChecked = _mqttPublisher.IsEnabled;
+    }
+
+    private void UpdateLocalApiMenu()
     {
-        StartupService.ToggleStartup();
+        LocalApiMenuItem.IsChecked = _localApiServer.IsEnabled;
+        CopyLocalApiMenuItem.IsEnabled = _localApiServer.IsEnabled;
+    }
+
+    private void CopyDiagnosticsMenuItem_Click(object sender, RoutedEventArgs e)
+    {
+        try
+        {
+            var package = new Windows.ApplicationModel.DataTransfer.DataPackage();
+            package.SetText(_trayViewModel.BuildDiagnosticsJson());
+            Windows.ApplicationModel.DataTransfer.Clipboard.SetContent(package);
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Copy diagnostics failed");
+        }
+    }
+
+    private void OpenLogFolderMenuItem_Click(object sender, RoutedEventArgs e)
+    {
+        try
+        {
+            var directory = App.Host.Services.GetRequiredService<RollingFileLoggerProvider>().Directory;
+            System.IO.Directory.CreateDirectory(directory);
+            Process.Start(new ProcessStartInfo { FileName = directory, UseShellExecute = true });
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Open log folder failed");
+        }
+    }
+
+    private async void ExportSettingsMenuItem_Click(object sender, RoutedEventArgs e)
+    {
+        try
+        {
+            var picker = new Windows.Storage.Pickers.FileSavePicker
+            {
+                SuggestedStartLocation = Windows.Storage.Pickers.PickerLocationId.DocumentsLibrary,
+                SuggestedFileName = $"MicrophoneManager-settings-{DateTime.Now:yyyy-MM-dd}",
+            };
+            picker.FileTypeChoices.Add("Settings", new List<string> { ".json" });
+            WinRT.Interop.InitializeWithWindow.Initialize(picker, WinRT.Interop.WindowNative.GetWindowHandle(this));
+
+            var file = await picker.PickSaveFileAsync();
+            if (file == null) return;
+
+            var json = App.Host.Services.GetRequiredService<SettingsTransferService>().Export();
+            await File.WriteAllTextAsync(file.Path, json);
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Export settings failed");
+            TrayIcon.ShowNotification("Export failed", ex.Message);
+        }
+    }
+
+    private async void ImportSettingsMenuItem_Click(object sender, RoutedEventArgs e)
+    {
+        try
+        {
+            var picker = new Windows.Storage.Pickers.FileOpenPicker
+            {
+                SuggestedStartLocation = Windows.Storage.Pickers.PickerLocationId.DocumentsLibrary,
+            };
+            picker.FileTypeFilter.Add(".json");
+            WinRT.Interop.InitializeWithWindow.Initialize(picker, WinRT.Interop.WindowNative.GetWindowHandle(this));
+
+            var file = await picker.PickSingleFileAsync();
+            if (file == null) return;
+
+            var json = await File.ReadAllTextAsync(file.Path);
+            if (!App.Host.Services.GetRequiredService<SettingsTransferService>().TryImport(json, out var imported, out var skipped, out var error))
+            {
+                TrayIcon.ShowNotification("Import failed", error ?? "The settings file couldn't be read.");
+                return;
+            }
+
+            // Hotkeys are registered once at startup; most other settings are read as they're used
+            _hotkeyService.Reload();
+            RebuildQuietHoursMenu();
+            var message = $"Imported {imported} settings. Restart Microphone Manager to apply all of them.";
+            if (skipped.Count > 0)
+            {
+                message += $" Not imported, set these on this machine if you want them: {string.Join(", ", skipped)}.";
+            }
+
+            TrayIcon.ShowNotification("Settings imported", message);
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Import settings failed");
+            TrayIcon.ShowNotification("Import failed", ex.Message);
+        }
+    }
+
+    // Startup checks and changes may run schtasks.exe, which takes a moment; keep them off the UI thread
+    private async void ToggleStartup()
+    {
+        try
+        {
+            await Task.Run(() => StartupService.ToggleStartup(_preferences));
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Toggling startup failed");
+        }
+
+        OnPropertyChanged(nameof(StartupMenuText));
+    }
+
+    private async Task RefreshStartupMenuTextAsync()
+    {
+        try
+        {
+            await StartupService.IsStartupEnabledAsync(_preferences);
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Reading startup state failed");
+        }
+
+        OnPropertyChanged(nameof(StartupMenuText));
+    }
+
+    private async Task SetStartupOptionsAsync(StartupOptions options)
+    {
+        try
+        {
+            await Task.Run(() => StartupService.SetOptions(_preferences, options));
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Changing the startup method failed");
+        }
+
+        RebuildStartupMethodMenu();
     }
 
     private void MainWindow_Closed(object sender, WindowEventArgs args)
@@ -150,6 +1193,39 @@ public sealed partial class MainWindow : Window, INotifyPropertyChanged
     /// </summary>
     private void DisposeServices()
     {
+        // Release global hotkeys and session notifications while the window still exists
+        try
+        {
+            _hotkeyService.HotkeyPressed -= HotkeyService_HotkeyPressed;
+            _hotkeyService.Dispose();
+            _sessionLockService.Dispose();
+            _powerEventService.Dispose();
+            _topologyProfileService.ProfileApplied -= TopologyProfileService_ProfileApplied;
+            _topologyProfileService.TopologyChanged -= TopologyProfileService_TopologyChanged;
+            _topologyProfileService.MappingsChanged -= TopologyProfileService_MappingsChanged;
+            _topologyProfileService.Dispose();
+            _volumeLockService.VolumeReverted -= VolumeLockService_VolumeReverted;
+            _defaultLockService.DefaultReverted -= DefaultLockService_DefaultReverted;
+            _defaultLockService.LockChanged -= DefaultLockService_LockChanged;
+            _pipeServer.Dispose();
+            _localApiServer.Dispose();
+            _mqttPublisher.Dispose();
+            _updateService.UpdateAvailable -= UpdateService_UpdateAvailable;
+            _updateService.AvailableUpdateChanged -= UpdateService_AvailableUpdateChanged;
+            _updateService.Dispose();
+            _hookService.Dispose();
+            _historyService.Dispose();
+            _ruleService.Dispose();
+            _mutedSpeechService.SpeechWhileMuted -= MutedSpeechService_SpeechWhileMuted;
+            _mutedSpeechService.Dispose();
+            _silentMicrophoneService.MicrophoneSilent -= SilentMicrophoneService_MicrophoneSilent;
+            _silentMicrophoneService.Dispose();
+            _clippingService.ClippingAlert -= ClippingService_ClippingAlert;
+            _clippingService.Dispose();
+            _messageMonitor.Dispose();
+        }
+        catch { }
+
         // Dispose tray icon first (important to remove from system tray)
         try
         {
@@ -160,20 +1236,16 @@ public sealed partial class MainWindow : Window, INotifyPropertyChanged
         // Dispose TrayViewModel (unsubscribes service events)
         try
         {
-            if (App.TrayViewModel is IDisposable disposableViewModel)
-            {
-                disposableViewModel.Dispose();
-            }
+            _trayViewModel.PropertyChanged -= TrayViewModel_PropertyChanged;
+            _trayViewModel.MuteToggled -= TrayViewModel_MuteToggled;
+            _trayViewModel.Dispose();
         }
         catch { }
 
         // Dispose audio service (stops background threads and releases COM objects)
         try
         {
-            if (App.AudioService is IDisposable disposableService)
-            {
-                disposableService.Dispose();
-            }
+            _audioService.Dispose();
         }
         catch { }
 
@@ -0,0 +1,41 @@
+using System.Text.Json.Serialization;
+
+namespace MicrophoneManager.WinUI.Models;
+
+/// <summary>
+/// Which default an <see cref="AppRule"/> sets.
+/// </summary>
+[JsonConverter(typeof(JsonStringEnumConverter))]
+public enum AppRuleRole
+{
+    /// <summary>The default device, used by most apps.</summary>
+    Console,
+
+    /// <summary>The default communications device, used by Teams, Zoom and the like.</summary>
+    Communications,
+
+    /// <summary>Both defaults.</summary>
+    Both
+}
+
+/// <summary>
+/// "While obs64.exe runs, the XLR interface is the default microphone." <see cref="DeviceKey"/> is a
+/// <see cref="Services.DevicePriorityService.KeyFor"/> key so the rule survives replugging. With
+/// <see cref="RestoreOnExit"/>, the previous default comes back when the application exits.
+/// </summary>
+public sealed record AppRule(string ProcessName, string DeviceKey, AppRuleRole Role = AppRuleRole.Console, bool RestoreOnExit = true)
+{
+    /// <summary>
+    /// "OBS64.exe", "obs64" and " obs64.EXE " all name the same process.
+    /// </summary>
+    public static string NormalizeProcessName(string processName)
+    {
+        var name = processName.Trim();
+        return name.EndsWith(".exe", StringComparison.OrdinalIgnoreCase) ? name[..^4] : name;
+    }
+
+    public bool Matches(string processName)
+    {
+        return string.Equals(NormalizeProcessName(ProcessName), NormalizeProcessName(processName), StringComparison.OrdinalIgnoreCase);
+    }
+}
@@ -0,0 +1,7 @@
+namespace MicrophoneManager.WinUI.Models;
+
+/// <summary>
+/// An audio processing effect reported by IAudioEffectsManager (Windows 11 22H2+), e.g. noise suppression.
+/// <see cref="CanToggle"/> is false when the driver only reports the effect's state.
+/// </summary>
+public record AudioEffect(Guid Id, string Name, bool IsEnabled, bool CanToggle);
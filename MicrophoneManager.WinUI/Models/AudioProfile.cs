@@ -0,0 +1,10 @@
+namespace MicrophoneManager.WinUI.Models;
+
+/// <summary>
+/// Named, persisted audio state ("Streaming", "Meetings") that can be re-applied with one click.
+/// </summary>
+public class AudioProfile
+{
+    public required string Name { get; init; }
+    public required AudioSnapshot Snapshot { get; init; }
+}
@@ -0,0 +1,38 @@
+using System.Text.Json;
+
+namespace MicrophoneManager.WinUI.Models;
+
+/// <summary>
+/// Point-in-time copy of default devices and per-microphone volume/mute/boost, so a
+/// temporary change can be put back exactly as it was.
+/// </summary>
+public class AudioSnapshot
+{
+    private static readonly JsonSerializerOptions JsonOptions = new() { WriteIndented = true };
+
+    public DateTime TakenAtUtc { get; init; } = DateTime.UtcNow;
+
+    // Microphones are identified by their device key (stable ID when known), outputs by endpoint ID
+    public string? DefaultConsoleId { get; init; }
+    public string? DefaultCommunicationsId { get; init; }
+    public string? DefaultOutputId { get; init; }
+    public List<DeviceSnapshot> Devices { get; init; } = new();
+
+    public string ToJson() => JsonSerializer.Serialize(this, JsonOptions);
+
+    public static AudioSnapshot? FromJson(string json) => JsonSerializer.Deserialize<AudioSnapshot>(json, JsonOptions);
+}
+
+/// <summary>
+/// Volume, mute and boost for one capture endpoint within an <see cref="AudioSnapshot"/>.
+/// </summary>
+public class DeviceSnapshot
+{
+    /// <summary>
+    /// The microphone's device key; endpoint ID in snapshots saved by older versions.
+    /// </summary>
+    public required string DeviceId { get; init; }
+    public float VolumeLevel { get; init; }
+    public bool IsMuted { get; init; }
+    public float? BoostDb { get; init; }
+}
@@ -0,0 +1,53 @@
+using System.Reflection;
+using System.Runtime.InteropServices;
+
+namespace MicrophoneManager.WinUI.Models;
+
+/// <summary>
+/// Identifies the exact build that produced a diagnostics report or crash log.
+/// </summary>
+public class BuildInfo
+{
+    public string Version { get; init; } = "";
+
+    /// <summary>
+    /// Git commit the SDK stamps into the informational version ("1.0.0+&lt;sha&gt;"), when built from a checkout.
+    /// </summary>
+    public string? Commit { get; init; }
+
+    public string? BuildTimestampUtc { get; init; }
+    public string RuntimeVersion { get; init; } = "";
+    public string RuntimeIdentifier { get; init; } = "";
+    public string? NAudioVersion { get; init; }
+
+    public static BuildInfo Current { get; } = FromAssembly(typeof(BuildInfo).Assembly);
+
+    public static BuildInfo FromAssembly(Assembly assembly)
+    {
+        var informationalVersion = assembly.GetCustomAttribute<AssemblyInformationalVersionAttribute>()?.InformationalVersion;
+
+        return new BuildInfo
+        {
+            Version = assembly.GetName().Version?.ToString() ?? "",
+            Commit = ParseCommit(informationalVersion),
+            BuildTimestampUtc = assembly.GetCustomAttributes<AssemblyMetadataAttribute>()
+                .FirstOrDefault(a => a.Key == "BuildTimestamp")?.Value,
+            RuntimeVersion = RuntimeInformation.FrameworkDescription,
+            RuntimeIdentifier = RuntimeInformation.RuntimeIdentifier,
+            NAudioVersion = typeof(NAudio.CoreAudioApi.MMDevice).Assembly.GetName().Version?.ToString()
+        };
+    }
+
+    /// <summary>
+    /// Extracts the source revision from an informational version such as "1.2.0+3f2c9e1".
+    /// </summary>
+    public static string? ParseCommit(string? informationalVersion)
+    {
+        if (string.IsNullOrEmpty(informationalVersion)) return null;
+
+        var plus = informationalVersion.IndexOf('+');
+        if (plus < 0 || plus == informationalVersion.Length - 1) return null;
+
+        return informationalVersion[(plus + 1)..];
+    }
+}
@@ -0,0 +1,27 @@
+namespace MicrophoneManager.WinUI.Models;
+
+/// <summary>
+/// What a calibration run measured and the volume (and boost, where the device has one) that would
+/// bring the average speaking level to the target. Levels are in dBFS, volumes and boost in dB; the
+/// average and noise floor are RMS, the peak is the loudest sample peak.
+/// </summary>
+public sealed record CalibrationResult(
+    double AverageDb,
+    double PeakDb,
+    double NoiseFloorDb,
+    float CurrentVolumeDb,
+    float RecommendedVolumeDb,
+    float? CurrentBoostDb = null,
+    float? RecommendedBoostDb = null)
+{
+    /// <summary>
+    /// False when the run picked up nothing that sounds like speech, so there is nothing to recommend.
+    /// </summary>
+    public bool HeardSpeech { get; init; } = true;
+
+    public bool ChangesVolume => Math.Abs(RecommendedVolumeDb - CurrentVolumeDb) >= 0.5f;
+
+    public bool ChangesBoost => RecommendedBoostDb != null && RecommendedBoostDb != CurrentBoostDb;
+
+    public bool IsChangeRecommended => HeardSpeech && (ChangesVolume || ChangesBoost);
+}
@@ -0,0 +1,45 @@
+namespace MicrophoneManager.WinUI.Models;
+
+/// <summary>
+/// Snapshot of an audio session on a capture endpoint, i.e. an application
+/// that has opened the microphone.
+/// </summary>
+public class CaptureSession
+{
+    public required string SessionInstanceId { get; init; }
+    public required string DeviceId { get; init; }
+    public uint ProcessId { get; init; }
+
+    /// <summary>
+    /// Executable file name, e.g. "Teams.exe".
+    /// </summary>
+    public string ProcessName { get; init; } = "";
+
+    /// <summary>
+    /// Full path of the executable, or null if the process couldn't be queried.
+    /// </summary>
+    public string? ExecutablePath { get; init; }
+
+    public string DisplayName { get; init; } = "";
+
+    /// <summary>
+    /// True while the application is actively streaming from the device;
+    /// inactive sessions have the device open but are not capturing.
+    /// </summary>
+    public bool IsActive { get; init; }
+
+    /// <summary>
+    /// Per-application session mute (ISimpleAudioVolume), independent of the endpoint mute.
+    /// </summary>
+    public bool IsMuted { get; init; }
+
+    /// <summary>
+    /// Per-application session volume scalar (0.0 - 1.0).
+    /// </summary>
+    public float Volume { get; init; } = 1.0f;
+
+    /// <summary>
+    /// Best label for the UI: the session display name if the app set one, otherwise the process name.
+    /// </summary>
+    public string Label => !string.IsNullOrWhiteSpace(DisplayName) ? DisplayName : ProcessName;
+}
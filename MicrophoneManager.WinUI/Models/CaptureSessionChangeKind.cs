@@ -0,0 +1,19 @@
+namespace MicrophoneManager.WinUI.Models;
+
+/// <summary>
+/// What happened to a capture session, as reported by IAudioSessionNotification/IAudioSessionEvents.
+/// </summary>
+public enum CaptureSessionChangeKind
+{
+    /// <summary>An application opened the microphone.</summary>
+    Created,
+
+    /// <summary>The application started streaming (the session became active).</summary>
+    Activated,
+
+    /// <summary>The application stopped streaming but still has the device open.</summary>
+    Deactivated,
+
+    /// <summary>The session closed, or was disconnected because the device went away.</summary>
+    Expired
+}
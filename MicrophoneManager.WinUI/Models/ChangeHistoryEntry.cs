@@ -0,0 +1,70 @@
+using System.Text.Json.Serialization;
+
+namespace MicrophoneManager.WinUI.Models;
+
+/// <summary>
+/// What a <see cref="ChangeHistoryEntry"/> records.
+/// </summary>
+[JsonConverter(typeof(JsonStringEnumConverter))]
+public enum ChangeHistoryKind
+{
+    /// <summary>A microphone became the default device.</summary>
+    DefaultDevice,
+
+    /// <summary>A microphone became the default communications device.</summary>
+    DefaultCommunicationsDevice,
+
+    /// <summary>A microphone's volume changed.</summary>
+    Volume,
+
+    /// <summary>A microphone was muted or unmuted.</summary>
+    Mute
+}
+
+/// <summary>
+/// Who made a recorded change.
+/// </summary>
+[JsonConverter(typeof(JsonStringEnumConverter))]
+public enum ChangeHistorySource
+{
+    /// <summary>Microphone Manager: the flyout, tray, a hotkey, a profile or a control command.</summary>
+    App,
+
+    /// <summary>Windows or another application.</summary>
+    External
+}
+
+/// <summary>
+/// One default-device, volume or mute change. Values are display text ("45%", "Muted", a device
+/// name); <see cref="Culprit"/> is the foreground application when an external change happened.
+/// </summary>
+public sealed record ChangeHistoryEntry(
+    DateTime Timestamp,
+    ChangeHistoryKind Kind,
+    ChangeHistorySource Source,
+    string DeviceId,
+    string DeviceName,
+    string? OldValue,
+    string NewValue,
+    string? Culprit = null)
+{
+    /// <summary>
+    /// One-line summary for the history window, e.g. "Volume 40% → 65%".
+    /// </summary>
+    [JsonIgnore]
+    public string Description => Kind switch
+    {
+        ChangeHistoryKind.DefaultDevice => OldValue != null ? $"Default microphone (was {OldValue})" : "Default microphone",
+        ChangeHistoryKind.DefaultCommunicationsDevice => OldValue != null ? $"Communications microphone (was {OldValue})" : "Communications microphone",
+        ChangeHistoryKind.Volume => $"Volume {OldValue} → {NewValue}",
+        _ => NewValue,
+    };
+
+    /// <summary>
+    /// "This app", "External" or "External (Discord)".
+    /// </summary>
+    [JsonIgnore]
+    public string SourceText => Source == ChangeHistorySource.App
+        ? "This app"
+        : Culprit != null ? $"External ({Culprit})" : "External";
+}
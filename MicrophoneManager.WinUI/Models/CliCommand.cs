@@ -0,0 +1,127 @@
+namespace MicrophoneManager.WinUI.Models;
+
+public enum CliAction
+{
+    Help,
+    List,
+    Mute,
+    Unmute,
+    Toggle,
+    SetDefault,
+    Send,
+}
+
+/// <summary>
+/// A one-shot command given on the command line, e.g. <c>--mute</c> or <c>--set-default "Yeti"</c>.
+/// </summary>
+/// <param name="Device">Name or ID of the target microphone; null means the default microphone.</param>
+/// <param name="Json">Write machine-readable output (only <see cref="CliAction.List"/> produces any).</param>
+/// <param name="Payload">JSON request for <see cref="CliAction.Send"/>.</param>
+public sealed record CliCommand(CliAction Action, string? Device = null, bool Json = false, string? Payload = null)
+{
+    public const string Usage =
+        """
+        Usage: MicrophoneManager [command] [options]
+
+        Commands:
+          --list                 List microphones
+          --mute                 Mute the default microphone
+          --unmute               Unmute the default microphone
+          --toggle               Toggle mute on the default microphone
+          --set-default <name>   Make a microphone the default for all roles
+          --send <json>          Send a JSON request to the running tray app and print its reply,
+                                 e.g. --send "{\"command\":\"toggleMute\"}"
+          --help                 Show this help
+
+        Options:
+          --device <name>        Target a microphone other than the default (name, part of it, or ID)
+          --json                 Write --list output as JSON
+
+        Without a command the tray application starts.
+        """;
+
+    /// <summary>
+    /// Parses the process arguments. Returns false with an <paramref name="error"/> for malformed input;
+    /// an empty argument list isn't a command and also returns false, without an error.
+    /// </summary>
+    public static bool TryParse(IReadOnlyList<string> args, out CliCommand? command, out string? error)
+    {
+        command = null;
+        error = null;
+        if (args.Count == 0) return false;
+
+        CliAction? action = null;
+        string? device = null;
+        string? payload = null;
+        var json = false;
+
+        for (var i = 0; i < args.Count; i++)
+        {
+            var arg = args[i];
+            switch (arg.ToLowerInvariant())
+            {
+                case "--help" or "-h" or "/?":
+                    action = SetAction(action, CliAction.Help, ref error);
+                    break;
+                case "--list":
+                    action = SetAction(action, CliAction.List, ref error);
+                    break;
+                case "--mute":
+                    action = SetAction(action, CliAction.Mute, ref error);
+                    break;
+                case "--unmute":
+                    action = SetAction(action, CliAction.Unmute, ref error);
+                    break;
+                case "--toggle":
+                    action = SetAction(action, CliAction.Toggle, ref error);
+                    break;
+                case "--set-default":
+                    action = SetAction(action, CliAction.SetDefault, ref error);
+                    if (!TryReadValue(args, ref i, out device)) error ??= "--set-default needs a microphone name.";
+                    break;
+                case "--send":
+                    action = SetAction(action, CliAction.Send, ref error);
+                    if (!TryReadValue(args, ref i, out payload)) error ??= "--send needs a JSON request.";
+                    break;
+                case "--device":
+                    if (!TryReadValue(args, ref i, out device)) error ??= "--device needs a microphone name.";
+                    break;
+                case "--json":
+                    json = true;
+                    break;
+                default:
+                    error ??= $"Unknown argument '{arg}'.";
+                    break;
+            }
+        }
+
+        if (error == null && action == null)
+        {
+            error = "No command given.";
+        }
+
+        if (error != null) return false;
+
+        command = new CliCommand(action!.Value, device, json, payload);
+        return true;
+    }
+
+    private static CliAction SetAction(CliAction? current, CliAction next, ref string? error)
+    {
+        if (current != null && current != next)
+        {
+            error ??= "Only one command can be given at a time.";
+        }
+
+        return next;
+    }
+
+    private static bool TryReadValue(IReadOnlyList<string> args, ref int index, out string? value)
+    {
+        value = null;
+        if (index + 1 >= args.Count || args[index + 1].StartsWith("--", StringComparison.Ordinal)) return false;
+
+        value = args[++index];
+        return true;
+    }
+}
@@ -0,0 +1,131 @@
+using System.Text.Json;
+using System.Text.Json.Serialization;
+
+namespace MicrophoneManager.WinUI.Models;
+
+/// <summary>
+/// A JSON command from an external tool, e.g. <c>{"command":"toggleMute","device":"Yeti"}</c>.
+/// </summary>
+public sealed class ControlRequest
+{
+    public string Command { get; init; } = "";
+
+    /// <summary>
+    /// Name or ID of the target microphone; the default microphone when omitted.
+    /// </summary>
+    public string? Device { get; init; }
+
+    /// <summary>
+    /// Profile name for <c>applyProfile</c>.
+    /// </summary>
+    public string? Profile { get; init; }
+
+    /// <summary>
+    /// Application for <c>setRule</c> and <c>removeRule</c>, e.g. <c>obs64.exe</c>.
+    /// </summary>
+    public string? Process { get; init; }
+
+    /// <summary>
+    /// For <c>setRule</c>: <c>console</c> (the default), <c>communications</c> or <c>both</c>.
+    /// </summary>
+    public string? Role { get; init; }
+
+    /// <summary>
+    /// For <c>setRule</c>: put the previous default back when the application exits (the default).
+    /// </summary>
+    public bool? RestoreOnExit { get; init; }
+
+    /// <summary>
+    /// For <c>history</c>: only changes after this time.
+    /// </summary>
+    public DateTime? Since { get; init; }
+
+    /// <summary>
+    /// For <c>history</c>: at most this many changes, newest first.
+    /// </summary>
+    public int? Limit { get; init; }
+}
+
+/// <summary>
+/// Reply to a <see cref="ControlRequest"/>. <see cref="Data"/> carries a <see cref="ControlState"/>
+/// or a device list depending on the command.
+/// </summary>
+public sealed class ControlResponse
+{
+    public bool Ok { get; init; }
+    public string? Error { get; init; }
+
+    /// <summary>
+    /// Set on messages pushed for a <see cref="ControlEvent"/>, e.g. <c>clipping</c>; absent on replies and state pushes.
+    /// </summary>
+    public string? Event { get; init; }
+
+    public object? Data { get; init; }
+
+    public static ControlResponse Success(object? data = null) => new() { Ok = true, Data = data };
+
+    public static ControlResponse FromEvent(ControlEvent controlEvent) => new() { Ok = true, Event = controlEvent.Name, Data = controlEvent.Data };
+
+    public static ControlResponse Failure(string error) => new() { Ok = false, Error = error };
+}
+
+/// <summary>
+/// A microphone as reported to external tools. <see cref="DisplayName"/> is the user's alias when
+/// one is set and <see cref="Name"/> otherwise; <see cref="Hidden"/> is set for devices the user
+/// hid from the app's own lists, so other front ends can leave them out too.
+/// </summary>
+public sealed record ControlDevice(
+    string Id,
+    string Name,
+    string DisplayName,
+    bool IsDefault,
+    bool IsDefaultCommunications,
+    bool IsMuted,
+    double VolumePercent,
+    bool Hidden = false)
+{
+    public static ControlDevice From(MicrophoneDevice device) => From(device, null);
+
+    public static ControlDevice From(MicrophoneDevice device, string? displayName, bool hidden = false)
+    {
+        return new ControlDevice(
+            device.Id,
+            device.Name,
+            displayName ?? device.Name,
+            device.IsDefault,
+            device.IsDefaultCommunication,
+            device.IsMuted,
+            Math.Round(device.VolumeLevel * 100),
+            hidden);
+    }
+}
+
+/// <summary>
+/// Snapshot answered to <c>state</c> and pushed to subscribers on change.
+/// </summary>
+public sealed record ControlState(ControlDevice? DefaultDevice, IReadOnlyList<ControlDevice> Devices);
+
+/// <summary>
+/// Something that happened, as opposed to a change of state: pushed to subscribers once and not
+/// answered to <c>state</c>.
+/// </summary>
+public sealed record ControlEvent(string Name, object Data);
+
+/// <summary>
+/// Data of the <c>clipping</c> event: the default microphone's peaks reached full scale.
+/// <see cref="ClipCount"/> counts clips on the device since the app started or the count was reset.
+/// </summary>
+public sealed record ControlClipping(string DeviceId, string DeviceName, int ClipCount);
+
+public static class ControlJson
+{
+    /// <summary>
+    /// camelCase in both directions, since scripts and dashboards are usually JavaScript-flavoured.
+    /// </summary>
+    public static JsonSerializerOptions Options { get; } = new()
+    {
+        PropertyNamingPolicy = JsonNamingPolicy.CamelCase,
+        PropertyNameCaseInsensitive = true,
+        DefaultIgnoreCondition = JsonIgnoreCondition.WhenWritingNull,
+    };
+}
@@ -0,0 +1,17 @@
+namespace MicrophoneManager.WinUI.Models;
+
+/// <summary>
+/// Optional features available for an endpoint on this OS and driver, so the UI can hide
+/// controls up front instead of calling them and reporting a failure.
+/// </summary>
+[Flags]
+public enum DeviceCapabilities
+{
+    None = 0,
+    Boost = 1 << 0,
+    AutoGainControl = 1 << 1,
+    CaptureSessions = 1 << 2,
+    AudioEffects = 1 << 3,
+    Recording = 1 << 4,
+    RenderDevices = 1 << 5
+}
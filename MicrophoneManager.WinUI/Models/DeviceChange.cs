@@ -0,0 +1,7 @@
+namespace MicrophoneManager.WinUI.Models;
+
+/// <summary>
+/// One endpoint's entry in a coalesced device change set. When an endpoint fired several
+/// notifications in the same window, <see cref="Kind"/> is the last one.
+/// </summary>
+public record DeviceChange(string DeviceId, DeviceChangeKind Kind);
@@ -0,0 +1,19 @@
+namespace MicrophoneManager.WinUI.Models;
+
+/// <summary>
+/// What happened to an endpoint, as reported by IMMNotificationClient.
+/// </summary>
+public enum DeviceChangeKind
+{
+    /// <summary>A new endpoint was installed.</summary>
+    Added,
+
+    /// <summary>The endpoint was uninstalled.</summary>
+    Removed,
+
+    /// <summary>The endpoint was enabled, disabled, unplugged or plugged back in.</summary>
+    StateChanged,
+
+    /// <summary>A property-store value changed (name, format, ...).</summary>
+    PropertyChanged
+}
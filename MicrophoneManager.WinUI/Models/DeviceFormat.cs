@@ -0,0 +1,30 @@
+namespace MicrophoneManager.WinUI.Models;
+
+/// <summary>
+/// Shared-mode default format configured for an endpoint (the Advanced tab in mmsys.cpl),
+/// as opposed to the engine mix format.
+/// </summary>
+public record DeviceFormat(int SampleRate, int BitsPerSample, int Channels)
+{
+    /// <summary>
+    /// Rate and depth only, e.g. "48 kHz 24-bit", for pickers where the channel count is fixed.
+    /// </summary>
+    public string RateAndDepthLabel => $"{SampleRate / 1000.0:0.#} kHz {BitsPerSample}-bit";
+
+    /// <summary>
+    /// "sampleRate:bits" parameter for <c>SetDefaultFormatCommand</c>.
+    /// </summary>
+    public string CommandParameter => $"{SampleRate}:{BitsPerSample}";
+
+    public override string ToString()
+    {
+        var channelLabel = Channels switch
+        {
+            1 => "Mono",
+            2 => "Stereo",
+            _ => $"{Channels}-ch"
+        };
+
+        return $"{RateAndDepthLabel} {channelLabel}";
+    }
+}
@@ -0,0 +1,38 @@
+namespace MicrophoneManager.WinUI.Models;
+
+/// <summary>
+/// Extra property-store details for an endpoint. Endpoints that share a <see cref="ContainerId"/>
+/// belong to the same physical device (e.g. a headset's mic and speakers).
+/// </summary>
+public class DeviceProperties
+{
+    public required string DeviceId { get; init; }
+    public Guid? ContainerId { get; init; }
+    public string FormFactor { get; init; } = "Unknown";
+    public string? EnumeratorName { get; init; }
+    public string? AdapterName { get; init; }
+
+    /// <summary>
+    /// Endpoint description without the adapter, e.g. "Microphone" or "Line In".
+    /// </summary>
+    public string? EndpointDescription { get; init; }
+    public string? DriverDescription { get; init; }
+    public string? DriverVersion { get; init; }
+
+    /// <summary>
+    /// Physical jack behind the endpoint, or null for USB/Bluetooth devices and drivers that don't describe one.
+    /// </summary>
+    public JackInfo? Jack { get; init; }
+
+    /// <summary>
+    /// Identity that survives driver reinstalls, unlike <see cref="DeviceId"/>. See <see cref="Services.DeviceIdentity"/>.
+    /// </summary>
+    public string? StableId => Services.DeviceIdentity.CreateStableId(this);
+
+    /// <summary>
+    /// One-line summary for tooltips, e.g. "Headset · USB · Jabra Link 380" or
+    /// "Microphone · HDAUDIO · Realtek High Definition Audio · 3.5 mm, Front (#FF80C0)".
+    /// </summary>
+    public string Summary => string.Join(" · ", new[] { FormFactor, EnumeratorName, AdapterName, Jack?.Summary }
+        .Where(s => !string.IsNullOrEmpty(s)));
+}
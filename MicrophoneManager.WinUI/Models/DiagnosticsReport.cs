@@ -0,0 +1,78 @@
+using System.Text.Json;
+using System.Text.Json.Serialization;
+
+namespace MicrophoneManager.WinUI.Models;
+
+/// <summary>
+/// One-shot dump of the audio environment for attaching to support tickets.
+/// </summary>
+public class DiagnosticsReport
+{
+    private static readonly JsonSerializerOptions JsonOptions = new()
+    {
+        WriteIndented = true,
+        DefaultIgnoreCondition = JsonIgnoreCondition.WhenWritingNull
+    };
+
+    public DateTime GeneratedAtUtc { get; init; } = DateTime.UtcNow;
+    public string AppVersion { get; init; } = "";
+    public BuildInfo? Build { get; init; }
+    public string OsVersion { get; init; } = "";
+    public bool Is64BitProcess { get; init; }
+
+    /// <summary>
+    /// Apartment of the thread that built the report; COM calls fail with RPC_E_WRONG_THREAD from the wrong one.
+    /// </summary>
+    public string ComApartment { get; init; } = "";
+
+    /// <summary>
+    /// Capture endpoint count per <see cref="NAudio.CoreAudioApi.DeviceState"/> name.
+    /// </summary>
+    public Dictionary<string, int> CaptureEndpointCounts { get; init; } = new();
+
+    public string? DefaultConsoleCaptureId { get; init; }
+    public string? DefaultCommunicationsCaptureId { get; init; }
+    public string? DefaultMultimediaCaptureId { get; init; }
+    public string? DefaultOutputId { get; init; }
+    public List<DeviceDiagnostics> Devices { get; init; } = new();
+    public List<FailureDiagnostics> RecentFailures { get; init; } = new();
+
+    public string ToJson() => JsonSerializer.Serialize(this, JsonOptions);
+}
+
+/// <summary>
+/// Per-endpoint entry in a <see cref="DiagnosticsReport"/>.
+/// </summary>
+public class DeviceDiagnostics
+{
+    public required string DeviceId { get; init; }
+    public required string Name { get; init; }
+    public string State { get; init; } = "";
+    public bool IsDefault { get; init; }
+    public bool IsDefaultCommunication { get; init; }
+    public float VolumeLevel { get; init; }
+    public bool IsMuted { get; init; }
+    public string? Format { get; init; }
+    public string? DriverDescription { get; init; }
+    public string? DriverVersion { get; init; }
+    public string? EnumeratorName { get; init; }
+    public string? AdapterName { get; init; }
+    public string? Jack { get; init; }
+    public bool? JackPluggedIn { get; init; }
+    public string? Capabilities { get; init; }
+}
+
+/// <summary>
+/// A failed operation recorded in a <see cref="DiagnosticsReport"/>.
+/// </summary>
+public class FailureDiagnostics
+{
+    public required string Operation { get; init; }
+    public string? DeviceId { get; init; }
+    public string? DeviceName { get; init; }
+    public string HResult { get; init; } = "";
+    public string Code { get; init; } = "";
+    public string? ExceptionType { get; init; }
+    public string Message { get; init; } = "";
+    public DateTime OccurredAtUtc { get; init; }
+}
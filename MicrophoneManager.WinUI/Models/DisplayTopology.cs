@@ -0,0 +1,28 @@
+namespace MicrophoneManager.WinUI.Models;
+
+/// <summary>
+/// The monitors attached to the desktop, by hardware ID ("DEL40B6"), which is what tells "docked at
+/// the desk" from "laptop only". Two identical monitors appear twice.
+/// </summary>
+public sealed record DisplayTopology(IReadOnlyList<string> MonitorIds)
+{
+    /// <summary>
+    /// Order-independent key for the setup, e.g. "BOE0867,DEL40B6,DEL40B6".
+    /// </summary>
+    public string Signature => string.Join(",", MonitorIds.Order(StringComparer.OrdinalIgnoreCase));
+
+    /// <summary>
+    /// "1 display (BOE0867)" or "3 displays (BOE0867, DEL40B6, DEL40B6)".
+    /// </summary>
+    public string Description => MonitorIds.Count switch
+    {
+        0 => "No displays",
+        1 => $"1 display ({MonitorIds[0]})",
+        _ => $"{MonitorIds.Count} displays ({string.Join(", ", MonitorIds.Order(StringComparer.OrdinalIgnoreCase))})",
+    };
+}
+
+/// <summary>
+/// Profile applied when the displays change to <see cref="Signature"/>.
+/// </summary>
+public sealed record TopologyProfileMapping(string Signature, string Description, string ProfileName);
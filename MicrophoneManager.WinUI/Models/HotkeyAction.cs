@@ -0,0 +1,28 @@
+namespace MicrophoneManager.WinUI.Models;
+
+/// <summary>
+/// Actions that can be bound to a global hotkey.
+/// </summary>
+public enum HotkeyAction
+{
+    ToggleMute,
+    MuteAll,
+    CycleDefaultMicrophone,
+    OpenFlyout,
+    VolumeUp,
+    VolumeDown
+}
+
+public static class HotkeyActionExtensions
+{
+    public static string GetDisplayName(this HotkeyAction action) => action switch
+    {
+        HotkeyAction.ToggleMute => "Toggle mute",
+        HotkeyAction.MuteAll => "Mute all microphones",
+        HotkeyAction.CycleDefaultMicrophone => "Next microphone",
+        HotkeyAction.OpenFlyout => "Open microphone list",
+        HotkeyAction.VolumeUp => "Volume up",
+        HotkeyAction.VolumeDown => "Volume down",
+        _ => action.ToString()
+    };
+}
@@ -0,0 +1,125 @@
+namespace MicrophoneManager.WinUI.Models;
+
+/// <summary>
+/// Modifier flags, using the values RegisterHotKey expects.
+/// </summary>
+[Flags]
+public enum HotkeyModifiers : uint
+{
+    None = 0,
+    Alt = 0x1,
+    Control = 0x2,
+    Shift = 0x4,
+    Windows = 0x8
+}
+
+/// <summary>
+/// A key combination such as "Ctrl+Alt+M". Persisted in its text form.
+/// </summary>
+public record HotkeyBinding(HotkeyModifiers Modifiers, uint VirtualKey)
+{
+    private static readonly Dictionary<string, uint> NamedKeys = new(StringComparer.OrdinalIgnoreCase)
+    {
+        ["Space"] = 0x20,
+        ["PageUp"] = 0x21,
+        ["PageDown"] = 0x22,
+        ["End"] = 0x23,
+        ["Home"] = 0x24,
+        ["Left"] = 0x25,
+        ["Up"] = 0x26,
+        ["Right"] = 0x27,
+        ["Down"] = 0x28,
+        ["Insert"] = 0x2D,
+        ["Delete"] = 0x2E,
+        ["Pause"] = 0x13,
+    };
+
+    public override string ToString()
+    {
+        var modifiers = FormatModifiers(Modifiers);
+        var key = GetKeyName(VirtualKey);
+        return modifiers.Length == 0 ? key : $"{modifiers}+{key}";
+    }
+
+    /// <summary>
+    /// Modifiers in the order they're written in a binding, e.g. "Ctrl+Alt"; empty for none.
+    /// </summary>
+    public static string FormatModifiers(HotkeyModifiers modifiers)
+    {
+        var parts = new List<string>();
+        if (modifiers.HasFlag(HotkeyModifiers.Control)) parts.Add("Ctrl");
+        if (modifiers.HasFlag(HotkeyModifiers.Alt)) parts.Add("Alt");
+        if (modifiers.HasFlag(HotkeyModifiers.Shift)) parts.Add("Shift");
+        if (modifiers.HasFlag(HotkeyModifiers.Windows)) parts.Add("Win");
+        return string.Join("+", parts);
+    }
+
+    public static string GetKeyName(uint virtualKey)
+    {
+        if (virtualKey is >= 0x30 and <= 0x39 or >= 0x41 and <= 0x5A) return ((char)virtualKey).ToString();
+        if (virtualKey is >= 0x70 and <= 0x87) return $"F{virtualKey - 0x6F}";
+
+        var named = NamedKeys.FirstOrDefault(k => k.Value == virtualKey).Key;
+        return named ?? $"0x{virtualKey:X2}";
+    }
+
+    /// <summary>
+    /// Parses text produced by <see cref="ToString"/>. A binding needs exactly one non-modifier key.
+    /// </summary>
+    public static bool TryParse(string? text, out HotkeyBinding? binding)
+    {
+        binding = null;
+        if (string.IsNullOrWhiteSpace(text)) return false;
+
+        var modifiers = HotkeyModifiers.None;
+        uint? key = null;
+        foreach (var part in text.Split('+', StringSplitOptions.TrimEntries | StringSplitOptions.RemoveEmptyEntries))
+        {
+            switch (part.ToLowerInvariant())
+            {
+                case "ctrl":
+                case "control":
+                    modifiers |= HotkeyModifiers.Control;
+                    continue;
+                case "alt":
+                    modifiers |= HotkeyModifiers.Alt;
+                    continue;
+                case "shift":
+                    modifiers |= HotkeyModifiers.Shift;
+                    continue;
+                case "win":
+                case "windows":
+                    modifiers |= HotkeyModifiers.Windows;
+                    continue;
+            }
+
+            if (key != null) return false;
+            key = ParseKey(part);
+            if (key == null) return false;
+        }
+
+        if (key == null) return false;
+
+        binding = new HotkeyBinding(modifiers, key.Value);
+        return true;
+    }
+
+    private static uint? ParseKey(string name)
+    {
+        if (name.Length == 1 && char.IsAsciiLetterOrDigit(name[0])) return char.ToUpperInvariant(name[0]);
+        if (NamedKeys.TryGetValue(name, out var named)) return named;
+
+        if ((name[0] == 'F' || name[0] == 'f') && int.TryParse(name.AsSpan(1), out var function) && function is >= 1 and <= 24)
+        {
+            return (uint)(0x6F + function);
+        }
+
+        if (name.StartsWith("0x", StringComparison.OrdinalIgnoreCase) &&
+            uint.TryParse(name.AsSpan(2), System.Globalization.NumberStyles.HexNumber, null, out var raw))
+        {
+            return raw;
+        }
+
+        return null;
+    }
+}
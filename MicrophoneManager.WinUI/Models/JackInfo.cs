@@ -0,0 +1,17 @@
+namespace MicrophoneManager.WinUI.Models;
+
+/// <summary>
+/// Physical jack behind an endpoint, read from the adapter's KSJACK_DESCRIPTION.
+/// <see cref="IsPluggedIn"/> is null when the jack has no presence detection.
+/// </summary>
+public record JackInfo(uint ColorRgb, string Location, string ConnectionType, bool? IsPluggedIn)
+{
+    public string ColorHex => $"#{ColorRgb:X6}";
+
+    /// <summary>
+    /// e.g. "3.5 mm, Front panel (#FF80C0)".
+    /// </summary>
+    public string Summary => ColorRgb == 0
+        ? $"{ConnectionType}, {Location}"
+        : $"{ConnectionType}, {Location} ({ColorHex})";
+}
@@ -0,0 +1,7 @@
+namespace MicrophoneManager.WinUI.Models;
+
+/// <summary>
+/// "Listen to this device" state for a capture endpoint.
+/// </summary>
+/// <param name="TargetRenderId">Playback endpoint, or null for the default playback device.</param>
+public record ListenSettings(bool IsEnabled, string? TargetRenderId);
@@ -0,0 +1,13 @@
+namespace MicrophoneManager.WinUI.Models;
+
+/// <summary>
+/// Progress of a record-then-play-back microphone test.
+/// </summary>
+public enum MicTestStatus
+{
+    Idle,
+    Recording,
+    PlayingBack,
+    Completed,
+    Failed
+}
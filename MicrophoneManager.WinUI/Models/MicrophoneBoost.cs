@@ -0,0 +1,6 @@
+namespace MicrophoneManager.WinUI.Models;
+
+/// <summary>
+/// Hardware microphone boost read from the device topology (e.g. 0 / +10 / +20 / +30 dB).
+/// </summary>
+public record MicrophoneBoost(float LevelDb, float MinDb, float MaxDb, float StepDb);
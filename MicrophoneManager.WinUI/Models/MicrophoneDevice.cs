@@ -1,3 +1,5 @@
+using NAudio.CoreAudioApi;
+
 namespace MicrophoneManager.WinUI.Models;
 
 /// <summary>
@@ -8,13 +10,48 @@ public class MicrophoneDevice
 {
     public required string Id { get; init; }
     public required string Name { get; init; }
+
+    /// <summary>
+    /// Identity that survives driver reinstalls and re-enumeration; persist this rather than <see cref="Id"/>.
+    /// </summary>
+    public string? StableId { get; init; }
+
+    /// <summary>
+    /// The physical device the endpoint belongs to; endpoints of one interface or headset share it.
+    /// Null when the driver doesn't publish one.
+    /// </summary>
+    public Guid? ContainerId { get; init; }
     public string? IconPath { get; init; }
     public bool IsDefault { get; init; }
     public bool IsDefaultCommunication { get; init; }
     public bool IsMuted { get; init; }
     public float VolumeLevel { get; init; }
+
+    /// <summary>
+    /// Endpoint volume in dB, or null if it couldn't be read.
+    /// </summary>
+    public float? VolumeDb { get; init; }
+
+    public VolumeRange? VolumeRange { get; init; }
     public string FormatTag { get; init; } = "";
     public double InputLevelPercent { get; init; }
+    public DeviceState State { get; init; } = DeviceState.Active;
+
+    /// <summary>
+    /// Microphone boost, or null when the driver does not expose one.
+    /// </summary>
+    public MicrophoneBoost? Boost { get; init; }
+
+    /// <summary>
+    /// Whether the driver's automatic gain control is on, or null when it has none.
+    /// </summary>
+    public bool? AutoGainEnabled { get; init; }
+
+    /// <summary>
+    /// True when at least one application has an active capture session on the device.
+    /// </summary>
+    public bool IsInUse { get; init; }
 
     public bool IsSelected => IsDefault || IsDefaultCommunication;
+    public bool IsActive => State == DeviceState.Active;
 }
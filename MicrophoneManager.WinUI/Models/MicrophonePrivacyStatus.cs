@@ -0,0 +1,56 @@
+namespace MicrophoneManager.WinUI.Models;
+
+/// <summary>
+/// Windows "Microphone access" privacy switches. When any of these is off, capture apps get
+/// silence rather than an error, so meters just sit at zero.
+/// </summary>
+public class MicrophonePrivacyStatus
+{
+    /// <summary>
+    /// "Microphone access" for the whole device (set by an administrator or policy).
+    /// </summary>
+    public bool IsDeviceAccessAllowed { get; init; } = true;
+
+    /// <summary>
+    /// "Let apps access your microphone" for the current user.
+    /// </summary>
+    public bool IsUserAccessAllowed { get; init; } = true;
+
+    /// <summary>
+    /// "Let desktop apps access your microphone" for the current user.
+    /// </summary>
+    public bool IsDesktopAppAccessAllowed { get; init; } = true;
+
+    /// <summary>
+    /// Per-app consent keyed by package family name or executable path; true means allowed.
+    /// </summary>
+    public Dictionary<string, bool> AppAccess { get; init; } = new(StringComparer.OrdinalIgnoreCase);
+
+    public static MicrophonePrivacyStatus Allowed { get; } = new();
+
+    /// <summary>
+    /// True when desktop apps like this one (and OBS, Discord, ...) can capture at all.
+    /// </summary>
+    public bool IsDesktopCaptureAllowed => IsDeviceAccessAllowed && IsUserAccessAllowed && IsDesktopAppAccessAllowed;
+
+    /// <summary>
+    /// Warning for the UI, or null when desktop capture is allowed.
+    /// </summary>
+    public string? WarningText
+    {
+        get
+        {
+            if (!IsDeviceAccessAllowed || !IsUserAccessAllowed)
+            {
+                return "Microphone access is disabled in Windows privacy settings";
+            }
+
+            if (!IsDesktopAppAccessAllowed)
+            {
+                return "Desktop apps are blocked from the microphone in Windows privacy settings";
+            }
+
+            return null;
+        }
+    }
+}
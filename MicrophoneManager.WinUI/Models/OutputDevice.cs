@@ -0,0 +1,16 @@
+namespace MicrophoneManager.WinUI.Models;
+
+/// <summary>
+/// Snapshot of a render (playback) device returned from the audio service.
+/// Output devices share the MMDevice plumbing with microphones, including the endpoint peak meter.
+/// </summary>
+public class OutputDevice
+{
+    public required string Id { get; init; }
+    public required string Name { get; init; }
+    public bool IsDefault { get; init; }
+    public bool IsDefaultCommunication { get; init; }
+    public bool IsMuted { get; init; }
+    public float VolumeLevel { get; init; }
+    public double PeakLevelPercent { get; init; }
+}
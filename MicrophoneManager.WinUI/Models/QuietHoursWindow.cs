@@ -0,0 +1,46 @@
+using System.Globalization;
+
+namespace MicrophoneManager.WinUI.Models;
+
+/// <summary>
+/// Daily time range during which the default microphone is kept muted, e.g. "22:00-07:00".
+/// An end earlier than the start runs past midnight.
+/// </summary>
+public record QuietHoursWindow(TimeOnly Start, TimeOnly End)
+{
+    public bool Contains(DateTime localTime)
+    {
+        var time = TimeOnly.FromDateTime(localTime);
+        return Start <= End
+            ? time >= Start && time < End
+            : time >= Start || time < End;
+    }
+
+    /// <summary>
+    /// When the occurrence containing <paramref name="localTime"/> ends.
+    /// </summary>
+    public DateTime GetEnd(DateTime localTime)
+    {
+        var end = localTime.Date + End.ToTimeSpan();
+        return end > localTime ? end : end.AddDays(1);
+    }
+
+    public override string ToString() => $"{Start:HH\\:mm}-{End:HH\\:mm}";
+
+    public static bool TryParse(string? text, out QuietHoursWindow? window)
+    {
+        window = null;
+        var parts = text?.Split('-', StringSplitOptions.TrimEntries);
+        if (parts is not { Length: 2 }) return false;
+
+        if (!TimeOnly.TryParseExact(parts[0], "H:mm", CultureInfo.InvariantCulture, DateTimeStyles.None, out var start) ||
+            !TimeOnly.TryParseExact(parts[1], "H:mm", CultureInfo.InvariantCulture, DateTimeStyles.None, out var end) ||
+            start == end)
+        {
+            return false;
+        }
+
+        window = new QuietHoursWindow(start, end);
+        return true;
+    }
+}
@@ -0,0 +1,6 @@
+namespace MicrophoneManager.WinUI.Models;
+
+/// <summary>
+/// A finished WAV recording.
+/// </summary>
+public record RecordingResult(string DeviceId, string FilePath, TimeSpan Duration);
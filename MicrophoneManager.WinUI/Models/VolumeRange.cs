@@ -0,0 +1,14 @@
+namespace MicrophoneManager.WinUI.Models;
+
+/// <summary>
+/// Endpoint volume range in dB as reported by IAudioEndpointVolume::GetVolumeRange.
+/// The 0.0-1.0 scalar maps onto this range through the driver's audio taper.
+/// </summary>
+public record VolumeRange(float MinDb, float MaxDb, float StepDb)
+{
+    /// <summary>
+    /// The level a 0.0-1.0 scalar corresponds to, spread linearly across the range. Cheap enough
+    /// to call on every slider tick, unlike asking the driver.
+    /// </summary>
+    public float DbAt(double scalar) => (float)(MinDb + Math.Clamp(scalar, 0.0, 1.0) * (MaxDb - MinDb));
+}
@@ -1,6 +1,10 @@
 using System;
 using System.IO;
 using System.Runtime.InteropServices;
+using MicrophoneManager.WinUI.Models;
+using MicrophoneManager.WinUI.Services;
+using Microsoft.Extensions.DependencyInjection;
+using Microsoft.Extensions.Logging;
 using Microsoft.UI.Dispatching;
 using Microsoft.UI.Xaml;
 
@@ -9,7 +13,10 @@ namespace MicrophoneManager.WinUI;
 public static class Program
 {
     private static string LogPath => Path.Combine(AppContext.BaseDirectory, "startup_error.log");
-    private static string DebugLogPath => Path.Combine(Path.GetTempPath(), "MicrophoneManager_startup.log");
+    private static string DebugLogPath => Path.Combine(PortableMode.IsEnabled ? PortableMode.Directory : Path.GetTempPath(), "MicrophoneManager_startup.log");
+
+    // A running tray answers at once; this only bounds the wait when none is running
+    private static readonly TimeSpan TrayConnectTimeout = TimeSpan.FromMilliseconds(300);
 
     private static void Log(string message)
     {
@@ -29,10 +36,28 @@ public static class Program
     [STAThread]
     public static void Main(string[] args)
     {
+        args = PortableMode.Initialize(args);
+
+        // Started by the Stream Deck software as a plugin
+        if (StreamDeckLaunchArgs.TryParse(args, out var streamDeckLaunch))
+        {
+            Environment.ExitCode = RunStreamDeckPlugin(streamDeckLaunch!);
+            return;
+        }
+
+        // A command on the command line runs and exits without starting the tray
+        if (args.Length > 0)
+        {
+            Environment.ExitCode = RunCommandLine(args);
+            return;
+        }
+
         AppDomain.CurrentDomain.UnhandledException += (s, e) =>
         {
             Log($"UNHANDLED EXCEPTION: {e.ExceptionObject}");
         };
+        CrashReporter.Install();
+        ShowPendingCrashReport();
 
         try
         {
@@ -78,6 +103,127 @@ public static class Program
         }
     }
 
+    /// <summary>
+    /// Tells the user about a crash report the previous run left behind.
+    /// </summary>
+    private static void ShowPendingCrashReport()
+    {
+        var folder = CrashReporter.TakePendingReport(CrashReporter.DefaultDirectory);
+        if (folder == null) return;
+
+        const uint MB_YESNO = 0x04, MB_ICONWARNING = 0x30;
+        const int IDYES = 6;
+        var answer = MessageBox(
+            IntPtr.Zero,
+            $"Microphone Manager crashed the last time it ran. A report was saved to:\n\n{folder}\n\nPlease attach it if you report the problem. Open the folder now?",
+            "Microphone Manager",
+            MB_YESNO | MB_ICONWARNING);
+
+        if (answer == IDYES)
+        {
+            try
+            {
+                System.Diagnostics.Process.Start(new System.Diagnostics.ProcessStartInfo { FileName = folder, UseShellExecute = true });
+            }
+            catch (Exception ex)
+            {
+                Log($"Opening crash folder failed: {ex.Message}");
+            }
+        }
+    }
+
+    private static int RunCommandLine(string[] args)
+    {
+        // WinExe has no console of its own; write to the one the command was started from
+        if (AttachConsole(ATTACH_PARENT_PROCESS))
+        {
+            Console.SetOut(new StreamWriter(Console.OpenStandardOutput()) { AutoFlush = true });
+            Console.SetError(new StreamWriter(Console.OpenStandardError()) { AutoFlush = true });
+        }
+
+        if (!CliCommand.TryParse(args, out var command, out var error))
+        {
+            Console.Error.WriteLine(error);
+            Console.Error.WriteLine(CliCommand.Usage);
+            return CliCommandRunner.ExitUsage;
+        }
+
+        if (command!.Action == CliAction.Send)
+        {
+            return SendToRunningInstance(command.Payload!);
+        }
+
+        try
+        {
+            // The tray already has the devices open; going through it keeps this process from
+            // opening them again and lighting up the microphone-in-use indicator
+            if (NamedPipeControlServer.IsServerRunning(TrayConnectTimeout))
+            {
+                using var channel = new PipeControlChannel();
+                return new CliCommandRunner(channel, Console.Out, Console.Error).Run(command);
+            }
+
+            var services = new ServiceCollection().AddLogging();
+            App.ConfigureAudioServices(services, monitorDevices: false);
+            using var provider = services.BuildServiceProvider();
+
+            var audioService = provider.GetRequiredService<IAudioDeviceService>();
+            var aliasService = new DeviceAliasService(PreferencesStoreFactory.Create(provider.GetService<ILoggerFactory>()));
+            return new CliCommandRunner(audioService, Console.Out, Console.Error, aliasService).Run(command);
+        }
+        catch (Exception ex)
+        {
+            Log($"Command line failed: {ex}");
+            Console.Error.WriteLine(ex.Message);
+            return CliCommandRunner.ExitFailed;
+        }
+    }
+
+    private static int RunStreamDeckPlugin(StreamDeckLaunchArgs launch)
+    {
+        try
+        {
+            Log($"Starting as Stream Deck plugin on port {launch.Port}");
+
+            // Actions run in the tray; a second set of meters here would keep every microphone open
+            using var loggerFactory = LoggerFactory.Create(logging => logging.AddDebug());
+            using var channel = new PipeControlChannel(loggerFactory.CreateLogger<PipeControlChannel>());
+            return StreamDeckPlugin.RunAsync(launch, channel, loggerFactory.CreateLogger<StreamDeckPlugin>()).GetAwaiter().GetResult();
+        }
+        catch (Exception ex)
+        {
+            Log($"Stream Deck plugin failed: {ex}");
+            return CliCommandRunner.ExitFailed;
+        }
+    }
+
+    private static int SendToRunningInstance(string requestJson)
+    {
+        try
+        {
+            var reply = NamedPipeControlServer.SendAsync(requestJson, TimeSpan.FromSeconds(5)).GetAwaiter().GetResult();
+            Console.WriteLine(reply);
+
+            var response = System.Text.Json.JsonSerializer.Deserialize<ControlResponse>(reply, ControlJson.Options);
+            return response?.Ok == true ? CliCommandRunner.ExitSuccess : CliCommandRunner.ExitFailed;
+        }
+        catch (TimeoutException)
+        {
+            Console.Error.WriteLine("Microphone Manager isn't running.");
+            return CliCommandRunner.ExitFailed;
+        }
+        catch (Exception ex)
+        {
+            Console.Error.WriteLine(ex.Message);
+            return CliCommandRunner.ExitFailed;
+        }
+    }
+
+    private const int ATTACH_PARENT_PROCESS = -1;
+
+    [DllImport("kernel32.dll", SetLastError = true)]
+    private static extern bool AttachConsole(int dwProcessId);
+
     [DllImport("user32.dll", CharSet = CharSet.Unicode)]
     private static extern int MessageBox(IntPtr hWnd, string text, string caption, uint type);
 }
@@ -0,0 +1,311 @@
+using System.Diagnostics;
+using System.Text.Json;
+using Microsoft.Extensions.Logging;
+using Microsoft.Extensions.Logging.Abstractions;
+using MicrophoneManager.WinUI.Models;
+using NAudio.CoreAudioApi;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Switches the default microphone while particular applications run, following the user's
+/// <see cref="AppRule"/>s. Running processes are polled every few seconds. When several ruled
+/// applications run, the one started last wins; when the last one exits, the default from before
+/// the first one started is put back, unless the user has picked another microphone since.
+/// Microphones are only looked at when the set of running ruled applications, the rules or the
+/// devices change.
+/// </summary>
+public sealed class AppRuleService : IDisposable
+{
+    public const string RulesPreference = "AppRules";
+
+    private static readonly TimeSpan PollInterval = TimeSpan.FromSeconds(3);
+
+    private readonly IAudioDeviceService _audioService;
+    private readonly IPreferencesStore _preferences;
+    private readonly Func<IReadOnlyCollection<string>> _runningProcesses;
+    private readonly SynchronizationContext? _syncContext;
+    private readonly ILogger<AppRuleService> _logger;
+    private readonly List<string> _startedOrder = new();
+    private Timer? _timer;
+    private AppRule? _appliedRule;
+    private string? _appliedDeviceId;
+    private string? _restoreConsoleId;
+    private string? _restoreCommunicationsId;
+    private HashSet<string>? _lastRuledRunning;
+    private bool _reevaluate;
+    private bool _disposed;
+
+    /// <summary>
+    /// Raised after a rule is added, changed or removed.
+    /// </summary>
+    public event EventHandler? RulesChanged;
+
+    /// <summary>
+    /// Raised when a rule takes effect, or when the last one ends (with null).
+    /// </summary>
+    public event EventHandler<AppRule?>? ActiveRuleChanged;
+
+    public AppRuleService(IAudioDeviceService audioService, IPreferencesStore preferences, ILogger<AppRuleService>? logger = null)
+        : this(audioService, preferences, GetRunningProcessNames, logger)
+    {
+    }
+
+    public AppRuleService(IAudioDeviceService audioService, IPreferencesStore preferences, Func<IReadOnlyCollection<string>> runningProcesses, ILogger<AppRuleService>? logger = null)
+    {
+        _audioService = audioService ?? throw new ArgumentNullException(nameof(audioService));
+        _preferences = preferences ?? throw new ArgumentNullException(nameof(preferences));
+        _runningProcesses = runningProcesses ?? throw new ArgumentNullException(nameof(runningProcesses));
+        _syncContext = SynchronizationContext.Current;
+        _logger = logger ?? NullLogger<AppRuleService>.Instance;
+
+        // A ruled microphone plugged in while its application runs has to be picked up
+        _audioService.DevicesChanged += OnDevicesChanged;
+
+        // Processes are listed on the timer thread; only the switch itself runs on the UI thread
+        if (_syncContext != null)
+        {
+            _timer = new Timer(_ => Poll(), null, TimeSpan.Zero, PollInterval);
+        }
+    }
+
+    /// <summary>
+    /// The rule currently holding the default, or null.
+    /// </summary>
+    public AppRule? ActiveRule => _appliedRule;
+
+    public IReadOnlyList<AppRule> GetRules()
+    {
+        var json = _preferences.GetString(RulesPreference);
+        if (string.IsNullOrEmpty(json)) return Array.Empty<AppRule>();
+
+        try
+        {
+            return JsonSerializer.Deserialize<List<AppRule>>(json) ?? new List<AppRule>();
+        }
+        catch (JsonException ex)
+        {
+            _logger.LogWarning(ex, "Ignoring unreadable application rules");
+            return Array.Empty<AppRule>();
+        }
+    }
+
+    public AppRule? GetRule(string processName)
+    {
+        return GetRules().FirstOrDefault(r => r.Matches(processName));
+    }
+
+    /// <summary>
+    /// Adds a rule, replacing any rule for the same application.
+    /// </summary>
+    public void SaveRule(AppRule rule)
+    {
+        ArgumentNullException.ThrowIfNull(rule);
+        if (string.IsNullOrWhiteSpace(rule.ProcessName)) throw new ArgumentException("Application name is required.", nameof(rule));
+        if (string.IsNullOrWhiteSpace(rule.DeviceKey)) throw new ArgumentException("Microphone is required.", nameof(rule));
+
+        var rules = GetRules().ToList();
+        var index = rules.FindIndex(r => r.Matches(rule.ProcessName));
+        var saved = rule with { ProcessName = rule.ProcessName.Trim() };
+        if (index >= 0)
+        {
+            rules[index] = saved;
+        }
+        else
+        {
+            rules.Add(saved);
+        }
+
+        Save(rules);
+    }
+
+    public bool RemoveRule(string processName)
+    {
+        var rules = GetRules().ToList();
+        if (rules.RemoveAll(r => r.Matches(processName)) == 0) return false;
+
+        Save(rules);
+        return true;
+    }
+
+    /// <summary>
+    /// Applies or ends rules for the applications running now. Called periodically and after edits.
+    /// </summary>
+    public void Evaluate()
+    {
+        if (_disposed) return;
+
+        IReadOnlyCollection<string> running;
+        try
+        {
+            running = _runningProcesses();
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Listing processes failed");
+            return;
+        }
+
+        Evaluate(running);
+    }
+
+    private void Evaluate(IReadOnlyCollection<string> running)
+    {
+        if (_disposed) return;
+
+        var rules = GetRules();
+        var runningNames = running.Select(AppRule.NormalizeProcessName).ToHashSet(StringComparer.OrdinalIgnoreCase);
+
+        var ruledRunning = rules
+            .Select(r => AppRule.NormalizeProcessName(r.ProcessName))
+            .Where(runningNames.Contains)
+            .ToHashSet(StringComparer.OrdinalIgnoreCase);
+        if (!_reevaluate && _lastRuledRunning != null && _lastRuledRunning.SetEquals(ruledRunning)) return;
+
+        _lastRuledRunning = ruledRunning;
+        _reevaluate = false;
+
+        _startedOrder.RemoveAll(name => !runningNames.Contains(name) || !rules.Any(r => r.Matches(name)));
+        foreach (var rule in rules)
+        {
+            var name = AppRule.NormalizeProcessName(rule.ProcessName);
+            if (runningNames.Contains(name) && !_startedOrder.Contains(name, StringComparer.OrdinalIgnoreCase))
+            {
+                _startedOrder.Add(name);
+            }
+        }
+
+        // Latest started application whose microphone is connected
+        var microphones = _audioService.GetMicrophones();
+        (AppRule Rule, MicrophoneDevice Device)? target = null;
+        for (var i = _startedOrder.Count - 1; i >= 0 && target == null; i--)
+        {
+            var rule = rules.First(r => r.Matches(_startedOrder[i]));
+            var device = microphones.FirstOrDefault(m => string.Equals(DevicePriorityService.KeyFor(m), rule.DeviceKey, StringComparison.OrdinalIgnoreCase));
+            if (device != null) target = (rule, device);
+        }
+
+        if (target is var (targetRule, targetDevice))
+        {
+            if (targetRule == _appliedRule && targetDevice.Id == _appliedDeviceId) return;
+
+            if (_appliedRule == null)
+            {
+                _restoreConsoleId = _audioService.GetDefaultDeviceId(Role.Console);
+                _restoreCommunicationsId = _audioService.GetDefaultDeviceId(Role.Communications);
+            }
+
+            Apply(targetRule, targetDevice.Id);
+            _appliedRule = targetRule;
+            _appliedDeviceId = targetDevice.Id;
+            ActiveRuleChanged?.Invoke(this, targetRule);
+        }
+        else if (_appliedRule != null)
+        {
+            if (_appliedRule.RestoreOnExit)
+            {
+                Restore(_appliedRule.Role, _appliedDeviceId);
+            }
+
+            _appliedRule = null;
+            _appliedDeviceId = null;
+            _restoreConsoleId = null;
+            _restoreCommunicationsId = null;
+            ActiveRuleChanged?.Invoke(this, null);
+        }
+    }
+
+    private void Apply(AppRule rule, string deviceId)
+    {
+        if (rule.Role != AppRuleRole.Communications)
+        {
+            _audioService.SetMicrophoneForRole(deviceId, Role.Console);
+        }
+        if (rule.Role != AppRuleRole.Console)
+        {
+            _audioService.SetMicrophoneForRole(deviceId, Role.Communications);
+        }
+    }
+
+    private void Restore(AppRuleRole role, string? appliedDeviceId)
+    {
+        // A default the user chose while the application ran is theirs to keep
+        if (role != AppRuleRole.Communications && _restoreConsoleId != null
+            && _audioService.GetDefaultDeviceId(Role.Console) == appliedDeviceId)
+        {
+            _audioService.SetMicrophoneForRole(_restoreConsoleId, Role.Console);
+        }
+        if (role != AppRuleRole.Console && _restoreCommunicationsId != null
+            && _audioService.GetDefaultDeviceId(Role.Communications) == appliedDeviceId)
+        {
+            _audioService.SetMicrophoneForRole(_restoreCommunicationsId, Role.Communications);
+        }
+    }
+
+    private void Poll()
+    {
+        if (_disposed || _syncContext == null) return;
+
+        // Nothing to watch: don't list processes every few seconds for nothing
+        if (GetRules().Count == 0 && _appliedRule == null) return;
+
+        try
+        {
+            var running = _runningProcesses();
+            _syncContext.Post(_ => TryEvaluate(running), null);
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Listing processes failed");
+        }
+    }
+
+    private void TryEvaluate(IReadOnlyCollection<string> running)
+    {
+        try
+        {
+            Evaluate(running);
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Applying application rules failed");
+        }
+    }
+
+    private void OnDevicesChanged(object? sender, EventArgs e)
+    {
+        _reevaluate = true;
+    }
+
+    private void Save(List<AppRule> rules)
+    {
+        _preferences.SetString(RulesPreference, rules.Count > 0 ? JsonSerializer.Serialize(rules) : null);
+        RulesChanged?.Invoke(this, EventArgs.Empty);
+        _reevaluate = true;
+        Evaluate();
+    }
+
+    private static IReadOnlyCollection<string> GetRunningProcessNames()
+    {
+        var processes = Process.GetProcesses();
+        try
+        {
+            return processes.Select(p => p.ProcessName).ToHashSet(StringComparer.OrdinalIgnoreCase);
+        }
+        finally
+        {
+            foreach (var process in processes) process.Dispose();
+        }
+    }
+
+    public void Dispose()
+    {
+        if (_disposed) return;
+        _disposed = true;
+
+        try { _timer?.Dispose(); } catch { }
+        _timer = null;
+
+        try { _audioService.DevicesChanged -= OnDevicesChanged; } catch { }
+    }
+}
@@ -0,0 +1,91 @@
+using System.Globalization;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Light or dark windows.
+/// </summary>
+public enum AppTheme
+{
+    /// <summary>
+    /// Follow the Windows app mode.
+    /// </summary>
+    System,
+
+    Light,
+
+    Dark,
+}
+
+/// <summary>
+/// The theme of the app's windows, how large the microphone flyout is drawn on top of the
+/// Windows display scaling, and how much each microphone row shows.
+/// </summary>
+public sealed class AppearanceService
+{
+    public const string ThemePreference = "Theme";
+    public const string ScalePreference = "UiScalePercent";
+    public const string CompactPreference = "CompactList";
+
+    public const int DefaultScalePercent = 100;
+    public const int MinScalePercent = 100;
+    public const int MaxScalePercent = 200;
+
+    private readonly IPreferencesStore _preferences;
+
+    /// <summary>
+    /// Raised after the theme, scale or density changes through this service.
+    /// </summary>
+    public event EventHandler? Changed;
+
+    public AppearanceService(IPreferencesStore preferences)
+    {
+        _preferences = preferences ?? throw new ArgumentNullException(nameof(preferences));
+    }
+
+    public AppTheme Theme
+    {
+        get => Enum.TryParse<AppTheme>(_preferences.GetString(ThemePreference), ignoreCase: true, out var theme) && Enum.IsDefined(theme)
+            ? theme
+            : AppTheme.System;
+        set
+        {
+            _preferences.SetString(ThemePreference, value == AppTheme.System ? null : value.ToString());
+            Changed?.Invoke(this, EventArgs.Empty);
+        }
+    }
+
+    /// <summary>
+    /// Size of the flyout and docked window in percent.
+    /// </summary>
+    public int ScalePercent
+    {
+        get => int.TryParse(_preferences.GetString(ScalePreference), NumberStyles.Integer, CultureInfo.InvariantCulture, out var percent)
+            && percent is >= MinScalePercent and <= MaxScalePercent
+            ? percent
+            : DefaultScalePercent;
+        set
+        {
+            var percent = Math.Clamp(value, MinScalePercent, MaxScalePercent);
+            _preferences.SetString(ScalePreference, percent == DefaultScalePercent ? null : percent.ToString(CultureInfo.InvariantCulture));
+            Changed?.Invoke(this, EventArgs.Empty);
+        }
+    }
+
+    public double Scale => ScalePercent / 100.0;
+
+    /// <summary>
+    /// Whether microphone rows show just the name, mute button and a small meter, instead of the
+    /// sliders, format and role buttons as well.
+    /// </summary>
+    public bool IsCompact
+    {
+        get => _preferences.GetBool(CompactPreference, false);
+        set
+        {
+            if (value == IsCompact) return;
+            _preferences.SetBool(CompactPreference, value);
+            Changed?.Invoke(this, EventArgs.Empty);
+        }
+    }
+}
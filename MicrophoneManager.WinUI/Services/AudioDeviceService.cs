@@ -1,8 +1,12 @@
+using System.Collections.Concurrent;
+using System.Globalization;
+using System.Runtime.CompilerServices;
 using System.Runtime.InteropServices;
 using System.Threading;
 using NAudio.CoreAudioApi;
 using NAudio.CoreAudioApi.Interfaces;
 using NAudio.Wave;
+using Microsoft.Extensions.Logging;
 using MicrophoneManager.WinUI.Models;
 
 namespace MicrophoneManager.WinUI.Services;
@@ -11,17 +15,43 @@ public class AudioDeviceService : IDisposable, IAudioDeviceService
 {
     private static readonly Guid SubtypePcm = new("00000001-0000-0010-8000-00AA00389B71");
     private static readonly Guid SubtypeIeeeFloat = new("00000003-0000-0010-8000-00AA00389B71");
+
+    public const string ExternalPollPreference = "ExternalPollMs";
+    public const int DefaultExternalPollMs = 1000;
+    public const int MinExternalPollMs = 250;
+    public const int MaxExternalPollMs = 10_000;
+
+    /// <summary>
+    /// Event context passed with every volume and mute write this service makes.
+    /// </summary>
+    public static readonly Guid VolumeEventContext = new("6c1f3b2e-58d4-4f0a-9b7e-2d4c8a61e903");
+
     private readonly MMDeviceEnumerator _enumerator;
     private readonly DeviceNotificationClient _notificationClient;
     private readonly object _volumeNotificationLock = new();
     private readonly Dictionary<string, VolumeNotificationSubscription> _volumeNotificationSubscriptions = new();
+    private readonly Dictionary<string, VolumeNotificationSubscription> _outputVolumeNotificationSubscriptions = new();
+    private int _outputDevicesChangedPending;
+    private readonly object _sessionNotificationLock = new();
+    private readonly Dictionary<string, SessionNotificationSubscription> _sessionNotificationSubscriptions = new();
     private string? _currentDefaultCaptureDeviceId;
 
     private readonly SynchronizationContext? _syncContext;
     private readonly PolicyConfigService _policyConfigService;
+    private readonly DeviceTopologyService _topologyService;
+    private readonly AudioEffectsService _effectsService;
+    private readonly MicTestService _micTestService;
+    private readonly RecordingService _recordingService;
+    private readonly ILogger<AudioDeviceService> _logger;
+    private readonly IPreferencesStore? _preferences;
     private Timer? _externalStatePollTimer;
     private readonly Dictionary<string, (float VolumeScalar, bool IsMuted, string FormatTag)> _lastKnownStateById = new();
 
+    // Exclusive-mode probe results; an entry is dropped when another process's capture session on
+    // the device comes or goes, when the device changes, or once it is older than ExclusiveLockProbeInterval
+    private readonly ConcurrentDictionary<string, (bool Locked, long AtTicks)> _exclusiveLocks = new(StringComparer.OrdinalIgnoreCase);
+    private static readonly TimeSpan ExclusiveLockProbeInterval = TimeSpan.FromSeconds(30);
+
     private readonly object _capturesLock = new();
     private readonly Dictionary<string, MicrophoneCaptureState> _capturesByDeviceId = new();
     private volatile bool _disposed;
@@ -32,6 +62,13 @@ public class AudioDeviceService : IDisposable, IAudioDeviceService
         public required string DeviceId { get; init; }
         public DateTime LastEventRaisedAtUtc { get; set; } = DateTime.MinValue;
         public double AccumulatedPeak { get; set; } = 0.0;
+
+        // RMS window since the last level event
+        public double AccumulatedSquares;
+        public long AccumulatedSamples;
+
+        // RMS of the last completed window, read by the metering thread
+        public double LatestRmsDb = -96.0;
         public required string DeviceFormatSignature { get; init; }
     }
 
@@ -40,31 +77,110 @@ public class AudioDeviceService : IDisposable, IAudioDeviceService
     private const int DeviceChangeDebounceMs = 50;
     private readonly object _debounceTimerLock = new();
 
+    private Timer? _deviceEventCoalesceTimer;
+    private readonly Dictionary<string, DeviceChangeKind> _pendingDeviceChanges = new();
+    private bool _pendingTopologyChange;
+    private readonly object _deviceEventLock = new();
+
     // Device enumeration caching
     private List<MicrophoneDevice>? _cachedMicrophones = null;
     private DateTime _cacheTimestamp = DateTime.MinValue;
     private const int CacheValidityMs = 100;
     private readonly object _cacheLock = new();
 
+    // Resolved device cache: volume/mute calls from sliders arrive in tight loops, so keep the
+    // resolved MMDevice (and its lazily created AudioEndpointVolume) instead of calling GetDevice each time.
+    // Callers hold a DeviceLease while they use one. Evicting or clearing only drops it from the cache;
+    // it is disposed once the last lease on it is released, since the COM or meter thread may still be
+    // using it.
+    private readonly Dictionary<string, ResolvedDevice> _resolvedDevicesById = new();
+    private readonly object _resolvedDevicesLock = new();
+
     public event EventHandler? DevicesChanged;
     public event EventHandler? DefaultDeviceChanged;
+    public event EventHandler? OutputDevicesChanged;
     public event EventHandler<DefaultMicrophoneVolumeChangedEventArgs>? DefaultMicrophoneVolumeChanged;
     public event EventHandler<MicrophoneVolumeChangedEventArgs>? MicrophoneVolumeChanged;
     public event EventHandler<MicrophoneInputLevelChangedEventArgs>? MicrophoneInputLevelChanged;
     public event EventHandler<MicrophoneFormatChangedEventArgs>? MicrophoneFormatChanged;
+    public event EventHandler<AudioOperationFailedEventArgs>? OperationFailed;
+    public event EventHandler<CaptureSessionChangedEventArgs>? CaptureSessionChanged;
+
+    /// <summary>
+    /// The most recent failure reported through <see cref="OperationFailed"/>, for diagnostics.
+    /// </summary>
+    public AudioOperationFailedEventArgs? LastError { get; private set; }
+
+    /// <summary>
+    /// Endpoint notifications arriving within this window of the first one are reported as a single
+    /// <see cref="DevicesChanged"/>. A USB hub reset fires dozens of add/remove/state events in a burst.
+    /// </summary>
+    public TimeSpan DeviceEventCoalescingWindow { get; set; } = TimeSpan.FromMilliseconds(250);
 
-    public AudioDeviceService(PolicyConfigService policyConfigService)
+    /// <summary>
+    /// How often volume, mute and format changes made outside the app are looked for, for drivers
+    /// that don't send notifications. Setting it saves the preference and applies straight away.
+    /// </summary>
+    public int ExternalStatePollMs
+    {
+        get => int.TryParse(_preferences?.GetString(ExternalPollPreference), NumberStyles.Integer, CultureInfo.InvariantCulture, out var ms)
+            && ms is >= MinExternalPollMs and <= MaxExternalPollMs
+            ? ms
+            : DefaultExternalPollMs;
+        set
+        {
+            var ms = Math.Clamp(value, MinExternalPollMs, MaxExternalPollMs);
+            _preferences?.SetString(ExternalPollPreference, ms == DefaultExternalPollMs ? null : ms.ToString(CultureInfo.InvariantCulture));
+
+            try
+            {
+                _externalStatePollTimer?.Change(ms, ms);
+            }
+            catch (ObjectDisposedException)
+            {
+            }
+        }
+    }
+
+    /// <param name="monitorDevices">
+    /// Watch endpoints, volumes and sessions and meter every microphone, as the tray does. One-shot
+    /// command-line runs pass false: metering opens a capture stream on every microphone, which
+    /// lights up the Windows microphone-in-use indicator.
+    /// </param>
+    public AudioDeviceService(
+        PolicyConfigService policyConfigService,
+        DeviceTopologyService topologyService,
+        AudioEffectsService effectsService,
+        MicTestService micTestService,
+        RecordingService recordingService,
+        ILogger<AudioDeviceService> logger,
+        IPreferencesStore? preferences = null,
+        bool monitorDevices = true)
     {
         _policyConfigService = policyConfigService ?? throw new ArgumentNullException(nameof(policyConfigService));
+        _topologyService = topologyService ?? throw new ArgumentNullException(nameof(topologyService));
+        _effectsService = effectsService ?? throw new ArgumentNullException(nameof(effectsService));
+        _micTestService = micTestService ?? throw new ArgumentNullException(nameof(micTestService));
+        _recordingService = recordingService ?? throw new ArgumentNullException(nameof(recordingService));
+        _logger = logger ?? throw new ArgumentNullException(nameof(logger));
+        _preferences = preferences;
         _syncContext = SynchronizationContext.Current;
         _enumerator = new MMDeviceEnumerator();
         _notificationClient = new DeviceNotificationClient(this);
+        if (!monitorDevices) return;
+
         _enumerator.RegisterEndpointNotificationCallback(_notificationClient);
 
         // Track microphone volume/mute changes (e.g., changed by other apps) for ALL capture devices
         UpdateMicrophoneVolumeNotificationSubscriptions();
         _currentDefaultCaptureDeviceId = GetDefaultDeviceId(Role.Console);
 
+        // Same for render devices, so the tray's output menu follows volume keys and other apps
+        UpdateOutputVolumeNotificationSubscriptions();
+
+        // Track applications opening/closing the microphone as it happens rather than on the next poll
+        UpdateCaptureSessionNotificationSubscriptions();
+
         // Fallback: poll for external volume/mute changes (Sound settings, other apps)
         StartExternalStatePolling();
 
@@ -80,13 +196,14 @@ public class AudioDeviceService : IDisposable, IAudioDeviceService
         // Avoid starting twice
         if (_externalStatePollTimer != null) return;
 
-        // 1 second poll interval for detecting external volume/mute/format changes.
+        // Poll for external volume/mute/format changes, 1 second unless set otherwise.
         // Run on background thread to prevent UI blocking
+        var intervalMs = ExternalStatePollMs;
         _externalStatePollTimer = new Timer(
             _ => Task.Run(() => PollExternalStateChanges()),
             null,
-            dueTime: 1000,
-            period: 1000);
+            dueTime: intervalMs,
+            period: intervalMs);
     }
 
     private void PollExternalStateChanges()
@@ -98,8 +215,9 @@ public class AudioDeviceService : IDisposable, IAudioDeviceService
         {
             devices = _enumerator.EnumerateAudioEndPoints(DataFlow.Capture, DeviceState.Active).ToList();
         }
-        catch
+        catch (Exception ex)
         {
+            _logger.LogDebug(ex, "Capture endpoint enumeration failed during external state poll");
             return;
         }
 
@@ -113,10 +231,13 @@ public class AudioDeviceService : IDisposable, IAudioDeviceService
         var activeIds = new HashSet<string>(devices.Select(d => d.ID));
 
         // Drop removed devices from state map
-        var removedIds = _lastKnownStateById.Keys.Where(id => !activeIds.Contains(id)).ToList();
-        foreach (var id in removedIds)
+        lock (_volumeNotificationLock)
         {
-            _lastKnownStateById.Remove(id);
+            var removedIds = _lastKnownStateById.Keys.Where(id => !activeIds.Contains(id)).ToList();
+            foreach (var id in removedIds)
+            {
+                _lastKnownStateById.Remove(id);
+            }
         }
 
         foreach (var device in devices)
@@ -140,48 +261,30 @@ public class AudioDeviceService : IDisposable, IAudioDeviceService
 
             var hasVolumeChanged = false;
             var hasFormatChanged = false;
+            var isFirstReading = false;
 
-            if (_lastKnownStateById.TryGetValue(device.ID, out var prior))
-            {
-                hasVolumeChanged = Math.Abs(prior.VolumeScalar - volume) >= 0.0005f || prior.IsMuted != muted;
-                hasFormatChanged = prior.FormatTag != formatTag;
-            }
-            else
-            {
-                // First time seeing this device
-                hasVolumeChanged = true;
-                hasFormatChanged = true;
-            }
-
-            _lastKnownStateById[device.ID] = (volume, muted, formatTag);
-
-            if (hasVolumeChanged)
+            // The endpoint volume callback also writes this map, so compare-and-store under the lock
+            lock (_volumeNotificationLock)
             {
-                // Post events to UI thread
-                if (_syncContext != null)
+                if (_lastKnownStateById.TryGetValue(device.ID, out var prior))
                 {
-                    var volumeArgs = new MicrophoneVolumeChangedEventArgs(device.ID, volume, muted);
-                    _syncContext.Post(_ => MicrophoneVolumeChanged?.Invoke(this, volumeArgs), null);
-
-                    if (defaultId != null && device.ID == defaultId)
-                    {
-                        var defaultVolumeArgs = new DefaultMicrophoneVolumeChangedEventArgs(device.ID, volume, muted);
-                        _syncContext.Post(_ => DefaultMicrophoneVolumeChanged?.Invoke(this, defaultVolumeArgs), null);
-                    }
+                    hasVolumeChanged = Math.Abs(prior.VolumeScalar - volume) >= 0.0005f || prior.IsMuted != muted;
+                    hasFormatChanged = prior.FormatTag != formatTag;
                 }
                 else
                 {
-                    MicrophoneVolumeChanged?.Invoke(
-                        this,
-                        new MicrophoneVolumeChangedEventArgs(device.ID, volume, muted));
-
-                    if (defaultId != null && device.ID == defaultId)
-                    {
-                        DefaultMicrophoneVolumeChanged?.Invoke(
-                            this,
-                            new DefaultMicrophoneVolumeChangedEventArgs(device.ID, volume, muted));
-                    }
+                    // First time seeing this device
+                    hasVolumeChanged = true;
+                    hasFormatChanged = true;
+                    isFirstReading = true;
                 }
+
+                _lastKnownStateById[device.ID] = (volume, muted, formatTag);
+            }
+
+            if (hasVolumeChanged)
+            {
+                RaiseMicrophoneVolumeChanged(device.ID, volume, muted, defaultId, Guid.Empty, IsExternalVolumeChange(null, isFirstReading));
             }
 
             if (hasFormatChanged)
@@ -223,18 +326,102 @@ public class AudioDeviceService : IDisposable, IAudioDeviceService
     /// </summary>
     public void SetMicrophoneVolumeLevelScalar(string deviceId, float volumeLevelScalar)
     {
-        var device = GetDeviceById(deviceId);
+        using var scope = BeginDeviceScope(deviceId);
+        using var lease = AcquireDevice(deviceId);
+        var device = lease.Device;
         if (device?.AudioEndpointVolume == null) return;
 
         var clampedScalar = Math.Max(0.0f, Math.Min(1.0f, volumeLevelScalar));
 
         try
         {
-            device.AudioEndpointVolume.MasterVolumeLevelScalar = clampedScalar;
+            OwnEndpointVolume(device).MasterVolumeLevelScalar = clampedScalar;
+        }
+        catch (Exception ex)
+        {
+            // Device could disappear, access denied, etc. Report and keep going.
+            EvictResolvedDevice(deviceId);
+            RaiseOperationFailed("set volume", deviceId, ex);
+        }
+    }
+
+    /// <summary>
+    /// Gets the endpoint volume in dB, or null if the device can't be read.
+    /// </summary>
+    public float? GetVolumeDb(string deviceId)
+    {
+        using var lease = AcquireDevice(deviceId);
+        var device = lease.Device;
+        return device == null ? null : GetDeviceVolumeDb(device);
+    }
+
+    /// <summary>
+    /// Gets the endpoint's dB range and step, or null if the device can't be read.
+    /// </summary>
+    public VolumeRange? GetVolumeRange(string deviceId)
+    {
+        using var lease = AcquireDevice(deviceId);
+        var device = lease.Device;
+        return device == null ? null : GetDeviceVolumeRange(device);
+    }
+
+    /// <summary>
+    /// Sets the endpoint volume in dB, clamped to the device's range.
+    /// </summary>
+    public bool SetVolumeDb(string deviceId, float volumeDb)
+    {
+        using var scope = BeginDeviceScope(deviceId);
+        using var lease = AcquireDevice(deviceId);
+        var device = lease.Device;
+        if (device?.AudioEndpointVolume == null) return false;
+
+        try
+        {
+            var range = device.AudioEndpointVolume.VolumeRange;
+            OwnEndpointVolume(device).MasterVolumeLevel = Math.Clamp(volumeDb, range.MinDecibels, range.MaxDecibels);
+            return true;
+        }
+        catch (Exception ex)
+        {
+            EvictResolvedDevice(deviceId);
+            RaiseOperationFailed("set volume", deviceId, ex);
+            return false;
+        }
+    }
+
+    /// <summary>
+    /// Returns the device's endpoint volume with our event context set, so the notification
+    /// for the write can be told apart from changes made by other software.
+    /// </summary>
+    private static AudioEndpointVolume OwnEndpointVolume(MMDevice device)
+    {
+        var endpointVolume = device.AudioEndpointVolume;
+        endpointVolume.NotificationGuid = VolumeEventContext;
+        return endpointVolume;
+    }
+
+    private static float? GetDeviceVolumeDb(MMDevice device)
+    {
+        try
+        {
+            return device.AudioEndpointVolume?.MasterVolumeLevel;
+        }
+        catch
+        {
+            return null;
+        }
+    }
+
+    private static VolumeRange? GetDeviceVolumeRange(MMDevice device)
+    {
+        try
+        {
+            var range = device.AudioEndpointVolume?.VolumeRange;
+            return range == null ? null : new VolumeRange(range.MinDecibels, range.MaxDecibels, range.IncrementDecibels);
         }
         catch
         {
-            // Ignore failures (device could disappear, access denied, etc.)
+            return null;
         }
     }
 
@@ -262,18 +449,7 @@ public class AudioDeviceService : IDisposable, IAudioDeviceService
 
             foreach (var device in _enumerator.EnumerateAudioEndPoints(DataFlow.Capture, DeviceState.Active))
             {
-                var mic = new MicrophoneDevice
-                {
-                    Id = device.ID,
-                    Name = device.FriendlyName,
-                    IsDefault = device.ID == defaultId,
-                    IsDefaultCommunication = device.ID == defaultCommId,
-                    IsMuted = GetDeviceMuteState(device),
-                    VolumeLevel = GetDeviceVolume(device),
-                    FormatTag = GetDeviceFormat(device),
-                    InputLevelPercent = GetDeviceInputLevel(device)
-                };
-                devices.Add(mic);
+                devices.Add(ToMicrophoneDevice(device, defaultId, defaultCommId));
             }
 
             // Update cache
@@ -285,160 +461,1141 @@ public class AudioDeviceService : IDisposable, IAudioDeviceService
     }
 
     /// <summary>
-    /// Gets the device ID of the default capture device for the specified role.
+    /// Gets capture devices in any of the given states (e.g. Active | Disabled | Unplugged).
+    /// Not cached: inactive endpoints are only listed on request.
     /// </summary>
-    public string? GetDefaultDeviceId(Role role)
+    public List<MicrophoneDevice> GetMicrophonesWithStates(DeviceState states)
     {
+        using var scope = _logger.BeginScope("Enumerating capture endpoints ({States})", states);
+        var devices = new List<MicrophoneDevice>();
+        var defaultId = GetDefaultDeviceId(Role.Console);
+        var defaultCommId = GetDefaultDeviceId(Role.Communications);
+
         try
         {
-            var device = _enumerator.GetDefaultAudioEndpoint(DataFlow.Capture, role);
-            return device?.ID;
+            foreach (var device in _enumerator.EnumerateAudioEndPoints(DataFlow.Capture, states))
+            {
+                if (device.State == DeviceState.Active)
+                {
+                    devices.Add(ToMicrophoneDevice(device, defaultId, defaultCommId));
+                    continue;
+                }
+
+                devices.Add(ToInactiveMicrophoneDevice(device));
+            }
         }
-        catch
+        catch (Exception ex)
         {
-            return null;
+            _logger.LogWarning(ex, "Capture endpoint enumeration with states {States} failed", states);
         }
+
+        return devices;
     }
 
     /// <summary>
-    /// Gets the default microphone device.
+    /// Reads a single capture endpoint in any state, bypassing the enumeration cache.
     /// </summary>
-    public MicrophoneDevice? GetDefaultMicrophone()
+    /// <returns>The device, or null if it is gone or is a render endpoint.</returns>
+    public MicrophoneDevice? GetMicrophone(string deviceId)
     {
-        var defaultId = GetDefaultDeviceId(Role.Console);
-        if (defaultId == null) return null;
+        try
+        {
+            // Resolve fresh: the cached MMDevice may predate the state change being handled
+            using var device = _enumerator.GetDevice(deviceId);
+            if (device.DataFlow != DataFlow.Capture) return null;
 
-        var devices = GetMicrophones();
-        return devices.FirstOrDefault(d => d.Id == defaultId);
+            return device.State == DeviceState.Active
+                ? ToMicrophoneDevice(device, GetDefaultDeviceId(Role.Console), GetDefaultDeviceId(Role.Communications))
+                : ToInactiveMicrophoneDevice(device);
+        }
+        catch (Exception ex)
+        {
+            _logger.LogDebug(ex, "Could not read capture endpoint {DeviceId}", deviceId);
+            return null;
+        }
+    }
+
+    // Inactive endpoints cannot activate volume/client interfaces; report identity and state only.
+    private static MicrophoneDevice ToInactiveMicrophoneDevice(MMDevice device)
+    {
+        return new MicrophoneDevice
+        {
+            Id = device.ID,
+            Name = GetDeviceFriendlyName(device),
+            State = device.State,
+            VolumeLevel = 0f,
+            FormatTag = ""
+        };
+    }
+
+    private MicrophoneDevice ToMicrophoneDevice(MMDevice device, string? defaultId, string? defaultCommId)
+    {
+        var properties = GetDeviceProperties(device);
+        return new MicrophoneDevice
+        {
+            Id = device.ID,
+            Name = device.FriendlyName,
+            IsDefault = device.ID == defaultId,
+            IsDefaultCommunication = device.ID == defaultCommId,
+            IsMuted = GetDeviceMuteState(device),
+            VolumeLevel = GetDeviceVolume(device),
+            VolumeDb = GetDeviceVolumeDb(device),
+            VolumeRange = GetDeviceVolumeRange(device),
+            FormatTag = GetDeviceFormat(device),
+            InputLevelPercent = GetDeviceMeterLevel(device),
+            Boost = _topologyService.GetBoost(device.ID),
+            AutoGainEnabled = _topologyService.GetAutoGainControl(device.ID),
+            IsInUse = device.State == DeviceState.Active && IsDeviceInUse(device),
+            StableId = properties?.StableId,
+            ContainerId = properties?.ContainerId
+        };
     }
 
+    private static readonly PropertyKey PKeyDeviceContainerId = new(new Guid("8c7ed206-3f8a-4827-b3ab-ae9e1faefc6c"), 2);
+    private static readonly PropertyKey PKeyAudioEndpointFormFactor = new(new Guid("1da5d803-d492-4edd-8c23-e0c0ffee7f0e"), 0);
+    private static readonly PropertyKey PKeyDeviceEnumeratorName = new(new Guid("a45c254e-df1c-4efd-8020-67d146a850e0"), 24);
+    private static readonly PropertyKey PKeyDeviceDeviceDesc = new(new Guid("a45c254e-df1c-4efd-8020-67d146a850e0"), 2);
+    private static readonly PropertyKey PKeyDeviceInterfaceFriendlyName = new(new Guid("026e516e-b814-414b-83cd-856d6fef4822"), 2);
+    private static readonly PropertyKey PKeyDeviceDriverDesc = new(new Guid("a8b865dd-2e3d-4094-ad97-e593a70c75d6"), 4);
+    private static readonly PropertyKey PKeyDeviceDriverVersion = new(new Guid("a8b865dd-2e3d-4094-ad97-e593a70c75d6"), 3);
+
+    private static readonly PropertyKey PKeyAudioEngineDeviceFormat = new(new Guid("f19f064d-082c-4e27-bc73-6882a1bb8e4c"), 0);
+    private static readonly PropertyKey PKeyListenTarget = new(new Guid("24dbb0fc-9311-4b3d-9cf0-18ff155639d4"), 0);
+    private static readonly PropertyKey PKeyListenEnabled = new(new Guid("24dbb0fc-9311-4b3d-9cf0-18ff155639d4"), 1);
+
+    private static readonly string[] FormFactorNames =
+    {
+        "Remote network device", "Speakers", "Line level", "Headphones", "Microphone",
+        "Headset", "Handset", "Digital passthrough", "S/PDIF", "Display audio", "Unknown"
+    };
+
     /// <summary>
-    /// Sets the specified device as the default microphone for all roles.
+    /// Reads the Windows microphone privacy switches; capture from a blocked app returns silence.
     /// </summary>
-    /// <returns>True if both roles were set successfully, false otherwise.</returns>
-    public bool SetDefaultMicrophone(string deviceId)
+    public MicrophonePrivacyStatus GetPrivacyStatus()
     {
-        var consoleSuccess = SetMicrophoneForRole(deviceId, Role.Console);
-        var commSuccess = SetMicrophoneForRole(deviceId, Role.Communications);
-        return consoleSuccess && commSuccess;
+        return MicrophonePrivacyService.GetStatus();
     }
 
     /// <summary>
-    /// Sets the specified device as the default for the given role.
+    /// Reads container ID, form factor, bus enumerator and driver details from the endpoint's property store,
+    /// and the jack description from its topology. Keys the driver does not publish are left null.
     /// </summary>
-    /// <returns>True if successful, false if the operation failed.</returns>
-    public bool SetMicrophoneForRole(string deviceId, Role role)
+    public DeviceProperties? GetDeviceProperties(string deviceId)
+    {
+        using var lease = AcquireDevice(deviceId);
+        var device = lease.Device;
+        return device == null ? null : GetDeviceProperties(device, includeJack: true);
+    }
+
+    /// <param name="includeJack">Also walk the device topology for the jack description; otherwise it is
+    /// only read for built-in endpoints, whose stable ID needs it.</param>
+    private DeviceProperties? GetDeviceProperties(MMDevice device, bool includeJack = false)
     {
+        var deviceId = device.ID;
         try
         {
-            var roleToSet = role == Role.Console
-                ? PolicyConfigService.ERole.eConsole
-                : PolicyConfigService.ERole.eCommunications;
+            var store = device.Properties;
+            var formFactor = ReadProperty(store, PKeyAudioEndpointFormFactor) is uint ff && ff < FormFactorNames.Length
+                ? FormFactorNames[ff]
+                : "Unknown";
 
-            _policyConfigService.SetDefaultDevice(deviceId, roleToSet);
-            return true;
+            var containerId = ReadProperty(store, PKeyDeviceContainerId) as Guid?;
+            return new DeviceProperties
+            {
+                DeviceId = deviceId,
+                ContainerId = containerId,
+                FormFactor = formFactor,
+                EnumeratorName = ReadProperty(store, PKeyDeviceEnumeratorName) as string,
+                AdapterName = ReadProperty(store, PKeyDeviceInterfaceFriendlyName) as string,
+                EndpointDescription = ReadProperty(store, PKeyDeviceDeviceDesc) as string,
+                DriverDescription = ReadProperty(store, PKeyDeviceDriverDesc) as string,
+                DriverVersion = ReadProperty(store, PKeyDeviceDriverVersion) as string,
+                Jack = includeJack || DeviceIdentity.NeedsJack(containerId) ? _topologyService.GetJackInfo(deviceId) : null
+            };
         }
-        catch
+        catch (Exception ex)
         {
-            return false;
+            _logger.LogDebug(ex, "Could not read the property store for {DeviceId}", deviceId);
+            return null;
         }
     }
 
     /// <summary>
-    /// Sets the specified device as the default for the given role asynchronously.
+    /// Reads the configured shared-mode default format (PKEY_AudioEngine_DeviceFormat).
     /// </summary>
-    public async Task<bool> SetMicrophoneForRoleAsync(string deviceId, Role role, CancellationToken cancellationToken = default)
+    public DeviceFormat? GetDeviceDefaultFormat(string deviceId)
     {
+        using var lease = AcquireDevice(deviceId);
+        var device = lease.Device;
+        if (device == null) return null;
+
         try
         {
-            var roleToSet = role == Role.Console
-                ? PolicyConfigService.ERole.eConsole
-                : PolicyConfigService.ERole.eCommunications;
-
-            await _policyConfigService.SetDefaultDeviceAsync(deviceId, roleToSet, cancellationToken);
-            return true;
+            return DeviceFormatBlob.Read(ReadProperty(device.Properties, PKeyAudioEngineDeviceFormat) as byte[]);
         }
-        catch
+        catch (Exception ex)
         {
-            return false;
+            _logger.LogDebug(ex, "Could not read default format for {DeviceId}", deviceId);
+            return null;
         }
     }
 
+    private static readonly int[] ProbeSampleRates = { 44100, 48000, 88200, 96000, 176400, 192000 };
+    private static readonly int[] ProbeBitDepths = { 16, 24, 32 };
+
     /// <summary>
-    /// Sets the specified device as the default microphone for all roles asynchronously.
+    /// Lists the PCM formats the endpoint accepts as its default format, at its current channel count.
+    /// Probes IAudioClient::IsFormatSupported in exclusive mode, which is what the Advanced tab in
+    /// mmsys.cpl offers; shared mode would only ever accept the current mix format. Each probe is
+    /// the exact blob <see cref="SetDeviceDefaultFormatAsync"/> would write, so a listed format can
+    /// be applied. Runs off the calling thread, since a probe per format takes a while.
     /// </summary>
-    public async Task<bool> SetDefaultMicrophoneAsync(string deviceId, CancellationToken cancellationToken = default)
+    public async Task<List<DeviceFormat>> GetSupportedFormatsAsync(string deviceId, CancellationToken cancellationToken = default)
+    {
+        return await Task.Run(() =>
+        {
+            cancellationToken.ThrowIfCancellationRequested();
+            return GetSupportedFormats(deviceId);
+        }, cancellationToken);
+    }
+
+    private List<DeviceFormat> GetSupportedFormats(string deviceId)
     {
+        using var scope = BeginDeviceScope(deviceId);
+        var result = new List<DeviceFormat>();
+        using var lease = AcquireDevice(deviceId);
+        var device = lease.Device;
+        if (device == null) return result;
+
+        var channels = GetDeviceDefaultFormat(deviceId)?.Channels ?? 2;
+        AudioClient? audioClient = null;
         try
         {
-            await _policyConfigService.SetDefaultDeviceForAllRolesAsync(deviceId, cancellationToken);
-            return true;
+            audioClient = device.AudioClient;
+            foreach (var sampleRate in ProbeSampleRates)
+            {
+                foreach (var bits in ProbeBitDepths)
+                {
+                    var format = new DeviceFormat(sampleRate, bits, channels);
+                    if (audioClient.IsFormatSupported(AudioClientShareMode.Exclusive, ToWaveFormat(DeviceFormatBlob.BuildDeviceFormat(format))))
+                    {
+                        result.Add(format);
+                    }
+                }
+            }
         }
-        catch
+        catch (Exception ex)
         {
-            return false;
+            // AUDCLNT_E_EXCLUSIVE_MODE_NOT_ALLOWED when exclusive mode is turned off for the device
+            _logger.LogDebug(ex, "Format probe failed for {DeviceId}", deviceId);
+        }
+        finally
+        {
+            audioClient?.Dispose();
         }
+
+        return result;
     }
 
-    /// <summary>
-    /// Gets all microphones asynchronously without blocking the UI thread.
-    /// </summary>
-    public async Task<List<MicrophoneDevice>> GetMicrophonesAsync(CancellationToken cancellationToken = default)
+    // NAudio's own WaveFormatExtensible uses a 24-bit container, float for 32 bits and a different
+    // channel mask, none of which matches what SetDeviceFormat writes
+    private static WaveFormat ToWaveFormat(byte[] blob)
     {
-        return await Task.Run(() =>
+        var handle = GCHandle.Alloc(blob, GCHandleType.Pinned);
+        try
         {
-            cancellationToken.ThrowIfCancellationRequested();
-            return GetMicrophones();
-        }, cancellationToken);
+            return WaveFormat.MarshalFromPtr(handle.AddrOfPinnedObject());
+        }
+        finally
+        {
+            handle.Free();
+        }
     }
 
     /// <summary>
-    /// Gets the default device ID for the specified role asynchronously.
+    /// Sets the shared-mode default format (sample rate / bit depth / channels) of an endpoint.
     /// </summary>
-    public async Task<string?> GetDefaultDeviceIdAsync(Role role, CancellationToken cancellationToken = default)
+    public async Task<bool> SetDeviceDefaultFormatAsync(string deviceId, DeviceFormat format, CancellationToken cancellationToken = default)
     {
-        return await Task.Run(() =>
+        using var scope = BeginDeviceScope(deviceId);
+        try
         {
-            cancellationToken.ThrowIfCancellationRequested();
-            return GetDefaultDeviceId(role);
-        }, cancellationToken);
+            await _policyConfigService.SetDeviceFormatAsync(deviceId, format.SampleRate, format.BitsPerSample, format.Channels, cancellationToken);
+            EvictResolvedDevice(deviceId);
+            InvalidateMicrophoneCache();
+            return true;
+        }
+        catch (Exception ex)
+        {
+            RaiseOperationFailed("set device format", deviceId, ex);
+            return false;
+        }
     }
 
     /// <summary>
-    /// Toggles the mute state asynchronously.
+    /// Reads the "Listen to this device" settings for a capture endpoint.
     /// </summary>
-    public async Task<bool> ToggleMuteAsync(string deviceId, CancellationToken cancellationToken = default)
+    public ListenSettings? GetListenSettings(string deviceId)
     {
-        return await Task.Run(() =>
+        using var lease = AcquireDevice(deviceId);
+        var device = lease.Device;
+        if (device == null) return null;
+
+        try
         {
-            cancellationToken.ThrowIfCancellationRequested();
-            return ToggleMute(deviceId);
-        }, cancellationToken);
+            var store = device.Properties;
+            var enabled = ReadProperty(store, PKeyListenEnabled) is bool b && b;
+            var target = ReadProperty(store, PKeyListenTarget) as string;
+            return new ListenSettings(enabled, string.IsNullOrEmpty(target) ? null : target);
+        }
+        catch (Exception ex)
+        {
+            _logger.LogDebug(ex, "Could not read listen settings for {DeviceId}", deviceId);
+            return null;
+        }
     }
 
     /// <summary>
-    /// Toggles mute on the default microphone asynchronously.
+    /// Turns "Listen to this device" on or off, playing through <paramref name="targetRenderId"/>
+    /// (or the default playback device when null).
     /// </summary>
-    public async Task<bool> ToggleDefaultMicrophoneMuteAsync(CancellationToken cancellationToken = default)
+    public async Task<bool> SetListenAsync(string deviceId, bool enabled, string? targetRenderId = null, CancellationToken cancellationToken = default)
     {
-        return await Task.Run(() =>
+        using var scope = BeginDeviceScope(deviceId);
+        try
         {
-            cancellationToken.ThrowIfCancellationRequested();
-            return ToggleDefaultMicrophoneMute();
-        }, cancellationToken);
-    }
+            await _policyConfigService.SetListenAsync(deviceId, enabled, targetRenderId, cancellationToken);
+
+            // The property store is snapshotted per MMDevice; re-resolve to read the new values
+            EvictResolvedDevice(deviceId);
+            return true;
+        }
+        catch (Exception ex)
+        {
+            RaiseOperationFailed("change Listen to this device", deviceId, ex);
+            return false;
+        }
+    }
+
+    private static object? ReadProperty(PropertyStore store, PropertyKey key)
+    {
+        try
+        {
+            return store.Contains(key) ? store[key].Value : null;
+        }
+        catch
+        {
+            return null;
+        }
+    }
+
+    private static string GetDeviceFriendlyName(MMDevice device)
+    {
+        try
+        {
+            return device.FriendlyName;
+        }
+        catch
+        {
+            // NotPresent endpoints may have a partially populated property store
+            return device.ID;
+        }
+    }
+
+    /// <summary>
+    /// Gets the device ID of the default capture device for the specified role.
+    /// </summary>
+    public string? GetDefaultDeviceId(Role role) => GetDefaultEndpointId(DataFlow.Capture, role);
+
+    /// <summary>
+    /// Gets the device ID of the default render (playback) device for the specified role.
+    /// </summary>
+    public string? GetDefaultOutputDeviceId(Role role) => GetDefaultEndpointId(DataFlow.Render, role);
+
+    private string? GetDefaultEndpointId(DataFlow flow, Role role)
+    {
+        try
+        {
+            var device = _enumerator.GetDefaultAudioEndpoint(flow, role);
+            return device?.ID;
+        }
+        catch
+        {
+            return null;
+        }
+    }
+
+    /// <summary>
+    /// Gets all active render (playback) devices. Not cached: only read when the tray menu is rebuilt.
+    /// </summary>
+    public List<OutputDevice> GetOutputDevices()
+    {
+        var devices = new List<OutputDevice>();
+        var defaultId = GetDefaultOutputDeviceId(Role.Console);
+        var defaultCommId = GetDefaultOutputDeviceId(Role.Communications);
+
+        try
+        {
+            foreach (var device in _enumerator.EnumerateAudioEndPoints(DataFlow.Render, DeviceState.Active))
+            {
+                devices.Add(new OutputDevice
+                {
+                    Id = device.ID,
+                    Name = device.FriendlyName,
+                    IsDefault = device.ID == defaultId,
+                    IsDefaultCommunication = device.ID == defaultCommId,
+                    IsMuted = GetDeviceMuteState(device),
+                    VolumeLevel = GetDeviceVolume(device),
+                    PeakLevelPercent = GetDeviceMeterLevel(device)
+                });
+            }
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Render endpoint enumeration failed");
+        }
+
+        return devices;
+    }
+
+    /// <summary>
+    /// Sets the specified render device as the default for all roles asynchronously.
+    /// </summary>
+    public async Task<bool> SetDefaultOutputDeviceAsync(string deviceId, CancellationToken cancellationToken = default)
+    {
+        using var scope = BeginDeviceScope(deviceId);
+        try
+        {
+            await _policyConfigService.SetDefaultDeviceForAllRolesAsync(deviceId, cancellationToken);
+            return true;
+        }
+        catch (Exception ex)
+        {
+            RaiseOperationFailed("set default output device", deviceId, ex);
+            return false;
+        }
+    }
+
+    /// <summary>
+    /// Enables or disables an audio endpoint (capture or render). Disabled endpoints disappear
+    /// from active enumeration until re-enabled.
+    /// </summary>
+    public async Task<bool> SetDeviceEnabledAsync(string deviceId, bool enabled, CancellationToken cancellationToken = default)
+    {
+        using var scope = BeginDeviceScope(deviceId);
+        try
+        {
+            await _policyConfigService.SetEndpointVisibilityAsync(deviceId, enabled, cancellationToken);
+            EvictResolvedDevice(deviceId);
+            InvalidateMicrophoneCache();
+            return true;
+        }
+        catch (Exception ex)
+        {
+            RaiseOperationFailed(enabled ? "enable device" : "disable device", deviceId, ex);
+            return false;
+        }
+    }
+
+    /// <summary>
+    /// Sets the volume scalar (0.0 - 1.0) for a render device.
+    /// </summary>
+    public void SetOutputVolumeLevelScalar(string deviceId, float volumeLevelScalar)
+        => SetMicrophoneVolumeLevelScalar(deviceId, volumeLevelScalar);
+
+    /// <summary>
+    /// Toggles the mute state of a render device. Endpoint volume is flow-agnostic,
+    /// so this shares the capture implementation.
+    /// </summary>
+    public bool ToggleOutputMute(string deviceId) => ToggleMute(deviceId);
+
+    /// <summary>
+    /// Reads the render device's current peak as a meter percentage (0..100). The endpoint meter
+    /// reflects the mixed stream, so no loopback capture is needed.
+    /// </summary>
+    public double GetOutputPeakLevel(string deviceId)
+    {
+        using var lease = AcquireDevice(deviceId);
+        var device = lease.Device;
+        return device == null ? 0 : GetDeviceMeterLevel(device);
+    }
+
+    /// <summary>
+    /// Reads a capture device's current endpoint peak in dBFS. Unlike the meter captures, which go
+    /// silent when the endpoint is muted, the endpoint meter still sees what reaches the device, so
+    /// this can tell someone is talking into a muted microphone.
+    /// </summary>
+    public double GetInputPeakDb(string deviceId)
+    {
+        using var lease = AcquireDevice(deviceId);
+        var device = lease.Device;
+        return device == null ? ObsMeterMath.ClampMeterDb(double.NegativeInfinity) : GetDeviceMeterDb(device);
+    }
+
+    /// <summary>
+    /// Reads a capture device's current endpoint peak for each channel in dBFS, so a stereo device
+    /// with one dead side shows up. Empty when the device is gone or its meter can't be read.
+    /// </summary>
+    public double[] GetInputChannelPeaksDb(string deviceId)
+    {
+        using var lease = AcquireDevice(deviceId);
+        var device = lease.Device;
+        return device == null ? Array.Empty<double>() : GetDeviceChannelMeterDb(device);
+    }
+
+    /// <summary>
+    /// Reads the RMS of the most recent meter capture window for a capture device, in dBFS. Only the
+    /// captures compute RMS; the endpoint meter reports peaks alone.
+    /// </summary>
+    public double GetInputRmsDb(string deviceId)
+    {
+        MicrophoneCaptureState? state;
+        lock (_capturesLock)
+        {
+            _capturesByDeviceId.TryGetValue(deviceId, out state);
+        }
+
+        return state == null ? ObsMeterMath.ClampMeterDb(double.NegativeInfinity) : Volatile.Read(ref state.LatestRmsDb);
+    }
+
+    /// <summary>
+    /// Gets the default microphone device.
+    /// </summary>
+    public MicrophoneDevice? GetDefaultMicrophone()
+    {
+        var defaultId = GetDefaultDeviceId(Role.Console);
+        if (defaultId == null) return null;
+
+        var devices = GetMicrophones();
+        return devices.FirstOrDefault(d => d.Id == defaultId);
+    }
+
+    /// <summary>
+    /// How long after a request through this service a default change still counts as ours.
+    /// </summary>
+    public static readonly TimeSpan RequestedDefaultWindow = TimeSpan.FromSeconds(5);
+
+    private sealed record RequestedDefault(string Id, long AtTicks);
+
+    private volatile RequestedDefault? _requestedDefault;
+
+    /// <summary>
+    /// Console default requested through this service in the last <see cref="RequestedDefaultWindow"/>,
+    /// so a default change can be told apart from one made by another application. Set before the
+    /// request because the change notification can arrive before PolicyConfig returns, and
+    /// forgotten after the window so a later switch by someone else to the same device isn't ours.
+    /// </summary>
+    public string? LastRequestedDefaultId =>
+        _requestedDefault is { } requested && Environment.TickCount64 - requested.AtTicks <= (long)RequestedDefaultWindow.TotalMilliseconds
+            ? requested.Id
+            : null;
+
+    private void NoteRequestedDefault(string deviceId)
+    {
+        _requestedDefault = new RequestedDefault(deviceId, Environment.TickCount64);
+    }
+
+    /// <summary>
+    /// Sets the specified device as the default microphone for all roles.
+    /// Partial failures are rolled back by <see cref="PolicyConfigService"/>.
+    /// </summary>
+    /// <returns>True if both roles were set successfully, false otherwise.</returns>
+    public bool SetDefaultMicrophone(string deviceId)
+    {
+        using var scope = BeginDeviceScope(deviceId);
+        try
+        {
+            NoteRequestedDefault(deviceId);
+            _policyConfigService.SetDefaultDeviceForAllRoles(deviceId);
+            return true;
+        }
+        catch (Exception ex)
+        {
+            RaiseOperationFailed("set default device", deviceId, ex);
+            return false;
+        }
+    }
+
+    /// <summary>
+    /// Sets the specified device as the default for the given role.
+    /// </summary>
+    /// <returns>True if successful, false if the operation failed.</returns>
+    public bool SetMicrophoneForRole(string deviceId, Role role)
+    {
+        using var scope = BeginDeviceScope(deviceId);
+        try
+        {
+            var roleToSet = role == Role.Console
+                ? PolicyConfigService.ERole.eConsole
+                : PolicyConfigService.ERole.eCommunications;
+
+            if (role == Role.Console) NoteRequestedDefault(deviceId);
+            _policyConfigService.SetDefaultDevice(deviceId, roleToSet);
+            return true;
+        }
+        catch (Exception ex)
+        {
+            RaiseOperationFailed("set default device", deviceId, ex);
+            return false;
+        }
+    }
+
+    /// <summary>
+    /// Sets the specified device as the default for the given role asynchronously.
+    /// </summary>
+    public async Task<bool> SetMicrophoneForRoleAsync(string deviceId, Role role, CancellationToken cancellationToken = default)
+    {
+        using var scope = BeginDeviceScope(deviceId);
+        try
+        {
+            var roleToSet = role == Role.Console
+                ? PolicyConfigService.ERole.eConsole
+                : PolicyConfigService.ERole.eCommunications;
+
+            if (role == Role.Console) NoteRequestedDefault(deviceId);
+            await _policyConfigService.SetDefaultDeviceAsync(deviceId, roleToSet, cancellationToken);
+            return true;
+        }
+        catch (Exception ex)
+        {
+            RaiseOperationFailed("set default device", deviceId, ex);
+            return false;
+        }
+    }
+
+    /// <summary>
+    /// Sets the specified device as the default microphone for all roles asynchronously.
+    /// </summary>
+    public async Task<bool> SetDefaultMicrophoneAsync(string deviceId, CancellationToken cancellationToken = default)
+    {
+        try
+        {
+            NoteRequestedDefault(deviceId);
+            await _policyConfigService.SetDefaultDeviceForAllRolesAsync(deviceId, cancellationToken);
+            return true;
+        }
+        catch (Exception ex)
+        {
+            RaiseOperationFailed("set default device", deviceId, ex);
+            return false;
+        }
+    }
+
+    /// <summary>
+    /// Gets all microphones asynchronously without blocking the UI thread.
+    /// </summary>
+    public async Task<List<MicrophoneDevice>> GetMicrophonesAsync(CancellationToken cancellationToken = default)
+    {
+        return await Task.Run(() =>
+        {
+            cancellationToken.ThrowIfCancellationRequested();
+            return GetMicrophones();
+        }, cancellationToken);
+    }
+
+    /// <summary>
+    /// Gets the default device ID for the specified role asynchronously.
+    /// </summary>
+    public async Task<string?> GetDefaultDeviceIdAsync(Role role, CancellationToken cancellationToken = default)
+    {
+        return await Task.Run(() =>
+        {
+            cancellationToken.ThrowIfCancellationRequested();
+            return GetDefaultDeviceId(role);
+        }, cancellationToken);
+    }
+
+    /// <summary>
+    /// Toggles the mute state asynchronously.
+    /// </summary>
+    public async Task<bool> ToggleMuteAsync(string deviceId, CancellationToken cancellationToken = default)
+    {
+        return await Task.Run(() =>
+        {
+            cancellationToken.ThrowIfCancellationRequested();
+            return ToggleMute(deviceId);
+        }, cancellationToken);
+    }
+
+    /// <summary>
+    /// Toggles mute on the default microphone asynchronously.
+    /// </summary>
+    public async Task<bool> ToggleDefaultMicrophoneMuteAsync(CancellationToken cancellationToken = default)
+    {
+        return await Task.Run(() =>
+        {
+            cancellationToken.ThrowIfCancellationRequested();
+            return ToggleDefaultMicrophoneMute();
+        }, cancellationToken);
+    }
+
+    /// <summary>
+    /// Gets the audio sessions (applications) currently attached to a capture device.
+    /// Expired sessions, the system sounds session and our own sessions (the level meters) are excluded.
+    /// </summary>
+    public List<CaptureSession> GetCaptureSessions(string deviceId)
+    {
+        var result = new List<CaptureSession>();
+        using var lease = AcquireDevice(deviceId);
+        var device = lease.Device;
+        if (device == null) return result;
+
+        try
+        {
+            var sessionManager = device.AudioSessionManager;
+            sessionManager.RefreshSessions();
+            var sessions = sessionManager.Sessions;
+
+            for (var i = 0; i < sessions.Count; i++)
+            {
+                using var session = sessions[i];
+                if (session.State == NAudio.CoreAudioApi.Interfaces.AudioSessionState.AudioSessionStateExpired) continue;
+                if (session.IsSystemSoundsSession) continue;
+
+                var processId = session.GetProcessID;
+                if (IsOwnSession(processId)) continue;
+
+                var executablePath = ProcessInfoResolver.GetExecutablePath(processId);
+                var simpleVolume = session.SimpleAudioVolume;
+                result.Add(new CaptureSession
+                {
+                    SessionInstanceId = session.GetSessionInstanceIdentifier,
+                    DeviceId = deviceId,
+                    ProcessId = processId,
+                    ProcessName = ProcessInfoResolver.GetExecutableName(processId, executablePath),
+                    ExecutablePath = executablePath,
+                    DisplayName = session.DisplayName ?? "",
+                    IsActive = session.State == NAudio.CoreAudioApi.Interfaces.AudioSessionState.AudioSessionStateActive,
+                    IsMuted = simpleVolume?.Mute ?? false,
+                    Volume = simpleVolume?.Volume ?? 1.0f
+                });
+            }
+        }
+        catch (Exception ex)
+        {
+            _logger.LogDebug(ex, "Session enumeration failed for {DeviceId}", deviceId);
+        }
+
+        return result;
+    }
+
+    /// <summary>
+    /// True when any other application is actively capturing from the device (what Windows shows as
+    /// the "microphone in use" indicator). Our own level meter capture doesn't count.
+    /// </summary>
+    public bool IsDeviceInUse(string deviceId)
+    {
+        using var lease = AcquireDevice(deviceId);
+        var device = lease.Device;
+        return device != null && IsDeviceInUse(device);
+    }
+
+    private bool IsDeviceInUse(MMDevice device)
+    {
+        try
+        {
+            var sessionManager = device.AudioSessionManager;
+            sessionManager.RefreshSessions();
+            var sessions = sessionManager.Sessions;
+
+            for (var i = 0; i < sessions.Count; i++)
+            {
+                using var session = sessions[i];
+                if (session.IsSystemSoundsSession) continue;
+                if (IsOwnSession(session.GetProcessID)) continue;
+                if (session.State == NAudio.CoreAudioApi.Interfaces.AudioSessionState.AudioSessionStateActive) return true;
+            }
+        }
+        catch (Exception ex)
+        {
+            _logger.LogDebug(ex, "Session enumeration failed for {DeviceId}", device.ID);
+        }
+
+        return false;
+    }
+
+    /// <summary>
+    /// True when another application holds the device in exclusive mode (typically a DAW), which
+    /// leaves shared-mode clients like our meters with silence.
+    /// </summary>
+    /// <remarks>
+    /// Probes by initializing a shared-mode client, which fails with AUDCLNT_E_DEVICE_IN_USE
+    /// while an exclusive stream is open. The result is cached per device until a capture session
+    /// of another process comes or goes, the device changes, an operation on it fails with
+    /// AUDCLNT_E_DEVICE_IN_USE, or <see cref="ExclusiveLockProbeInterval"/> passes, so repeated
+    /// calls don't open a client of our own each time.
+    /// </remarks>
+    public bool IsDeviceExclusivelyLocked(string deviceId)
+    {
+        var now = Environment.TickCount64;
+        if (_exclusiveLocks.TryGetValue(deviceId, out var cached) &&
+            now - cached.AtTicks < (long)ExclusiveLockProbeInterval.TotalMilliseconds)
+        {
+            return cached.Locked;
+        }
+
+        var locked = ProbeExclusiveLock(deviceId);
+        _exclusiveLocks[deviceId] = (locked, now);
+        return locked;
+    }
+
+    private bool ProbeExclusiveLock(string deviceId)
+    {
+        using var lease = AcquireDevice(deviceId);
+        var device = lease.Device;
+        if (device == null || device.State != DeviceState.Active) return false;
+
+        AudioClient? audioClient = null;
+        try
+        {
+            audioClient = device.AudioClient;
+            audioClient.Initialize(AudioClientShareMode.Shared, AudioClientStreamFlags.None, 200_000, 0, audioClient.MixFormat, Guid.Empty);
+            return false;
+        }
+        catch (COMException ex) when (AudioErrorCodes.FromHResult(ex.HResult) == AudioErrorCode.DeviceInUseExclusive)
+        {
+            return true;
+        }
+        catch (Exception ex)
+        {
+            _logger.LogDebug(ex, "Exclusive-mode probe failed for {DeviceId}", deviceId);
+            return false;
+        }
+        finally
+        {
+            audioClient?.Dispose();
+        }
+    }
+
+    /// <summary>
+    /// Reads the hardware microphone boost for a device, or null if it has none.
+    /// </summary>
+    public MicrophoneBoost? GetMicrophoneBoost(string deviceId)
+    {
+        using var scope = BeginDeviceScope(deviceId);
+        return _topologyService.GetBoost(deviceId);
+    }
+
+    /// <summary>
+    /// Sets the hardware microphone boost (in dB) for a device.
+    /// </summary>
+    public bool SetMicrophoneBoost(string deviceId, float levelDb)
+    {
+        using var scope = BeginDeviceScope(deviceId);
+        try
+        {
+            var success = _topologyService.SetBoost(deviceId, levelDb);
+            if (success)
+            {
+                InvalidateMicrophoneCache();
+            }
+
+            return success;
+        }
+        catch (Exception ex)
+        {
+            RaiseOperationFailed("set microphone boost", deviceId, ex);
+            return false;
+        }
+    }
+
+    /// <summary>
+    /// Reads whether automatic gain control is on, or null if the driver has no AGC control.
+    /// </summary>
+    public bool? GetAutoGainControl(string deviceId)
+    {
+        using var scope = BeginDeviceScope(deviceId);
+        return _topologyService.GetAutoGainControl(deviceId);
+    }
+
+    /// <summary>
+    /// Turns the driver's automatic gain control on or off.
+    /// </summary>
+    public bool SetAutoGainControl(string deviceId, bool enabled)
+    {
+        using var scope = BeginDeviceScope(deviceId);
+        try
+        {
+            var success = _topologyService.SetAutoGainControl(deviceId, enabled);
+            if (success)
+            {
+                InvalidateMicrophoneCache();
+            }
+
+            return success;
+        }
+        catch (Exception ex)
+        {
+            RaiseOperationFailed("set automatic gain control", deviceId, ex);
+            return false;
+        }
+    }
+
+    /// <summary>
+    /// Probes which optional features work for the device. Walks the topology and activates several
+    /// COM interfaces, so call it once per device rather than per refresh.
+    /// </summary>
+    public DeviceCapabilities GetCapabilities(string deviceId)
+    {
+        var capabilities = DeviceCapabilities.None;
+        using var lease = AcquireDevice(deviceId);
+        var device = lease.Device;
+        if (device == null) return capabilities;
+
+        if (_topologyService.GetBoost(deviceId) != null) capabilities |= DeviceCapabilities.Boost;
+        if (_topologyService.GetAutoGainControl(deviceId) != null) capabilities |= DeviceCapabilities.AutoGainControl;
+        if (_effectsService.IsSupported(deviceId)) capabilities |= DeviceCapabilities.AudioEffects;
+
+        try
+        {
+            if (device.AudioSessionManager != null) capabilities |= DeviceCapabilities.CaptureSessions;
+        }
+        catch (Exception ex)
+        {
+            _logger.LogDebug(ex, "Session manager unavailable for {DeviceId}", deviceId);
+        }
+
+        try
+        {
+            if (device.State == DeviceState.Active) capabilities |= DeviceCapabilities.Recording;
+        }
+        catch (Exception ex)
+        {
+            _logger.LogDebug(ex, "Could not read state for {DeviceId}", deviceId);
+        }
+
+        if (GetOutputDevices().Count > 0) capabilities |= DeviceCapabilities.RenderDevices;
+
+        return capabilities;
+    }
+
+    /// <summary>
+    /// Lists the audio processing effects (noise suppression, echo cancellation, ...) the driver reports.
+    /// Empty before Windows 11 22H2.
+    /// </summary>
+    public List<AudioEffect> GetAudioEffects(string deviceId)
+    {
+        using var scope = BeginDeviceScope(deviceId);
+        try
+        {
+            return _effectsService.GetAudioEffects(deviceId);
+        }
+        catch (Exception ex)
+        {
+            RaiseOperationFailed("read audio effects", deviceId, ex);
+            return new List<AudioEffect>();
+        }
+    }
+
+    /// <summary>
+    /// Turns an audio processing effect on or off, where the driver allows it.
+    /// </summary>
+    public bool SetAudioEffectEnabled(string deviceId, Guid effectId, bool enabled)
+    {
+        using var scope = BeginDeviceScope(deviceId);
+        try
+        {
+            return _effectsService.SetAudioEffectEnabled(deviceId, effectId, enabled);
+        }
+        catch (Exception ex)
+        {
+            RaiseOperationFailed($"set {AudioEffectsService.GetEffectName(effectId).ToLowerInvariant()}", deviceId, ex);
+            return false;
+        }
+    }
+
+    /// <summary>
+    /// Records <paramref name="duration"/> from the device, then plays it back on the default output.
+    /// </summary>
+    public Task<MicTestStatus> RunMicTestAsync(string deviceId, TimeSpan duration, IProgress<MicTestStatus>? progress = null, CancellationToken cancellationToken = default)
+    {
+        return _micTestService.RunAsync(deviceId, duration, progress, cancellationToken);
+    }
+
+    /// <summary>
+    /// Plays the device's last test recording on the default output again.
+    /// </summary>
+    public Task<MicTestStatus> ReplayMicTestAsync(string deviceId, IProgress<MicTestStatus>? progress = null, CancellationToken cancellationToken = default)
+    {
+        return _micTestService.ReplayAsync(deviceId, progress, cancellationToken);
+    }
+
+    /// <summary>
+    /// Waveform of the device's last test recording, for drawing while it records and after.
+    /// </summary>
+    public double[] GetMicTestWaveform(string deviceId)
+    {
+        return _micTestService.GetWaveform(deviceId);
+    }
+
+    /// <summary>
+    /// Starts recording a microphone to a 16- or 24-bit PCM WAV file.
+    /// </summary>
+    public bool StartRecording(string deviceId, string filePath, int bitsPerSample = 16)
+    {
+        using var scope = BeginDeviceScope(deviceId);
+        try
+        {
+            _recordingService.StartRecording(deviceId, filePath, bitsPerSample);
+            return true;
+        }
+        catch (Exception ex)
+        {
+            RaiseOperationFailed("start recording", deviceId, ex);
+            return false;
+        }
+    }
+
+    /// <summary>
+    /// Stops recording a microphone and returns the saved file and duration.
+    /// </summary>
+    public async Task<RecordingResult?> StopRecordingAsync(string deviceId)
+    {
+        using var scope = BeginDeviceScope(deviceId);
+        try
+        {
+            return await _recordingService.StopRecordingAsync(deviceId);
+        }
+        catch (Exception ex)
+        {
+            RaiseOperationFailed("stop recording", deviceId, ex);
+            return null;
+        }
+    }
+
+    public bool IsRecording(string deviceId) => _recordingService.IsRecording(deviceId);
+
+    /// <summary>
+    /// Mutes or unmutes a single application's session on a capture device.
+    /// </summary>
+    /// <returns>True if the session was found and updated.</returns>
+    public bool SetCaptureSessionMute(string deviceId, string sessionInstanceId, bool muted)
+    {
+        return WithCaptureSession(deviceId, sessionInstanceId, "set session mute", session =>
+        {
+            session.SimpleAudioVolume.Mute = muted;
+        });
+    }
+
+    /// <summary>
+    /// Sets a single application's session volume (0.0 - 1.0) on a capture device.
+    /// </summary>
+    /// <returns>True if the session was found and updated.</returns>
+    public bool SetCaptureSessionVolume(string deviceId, string sessionInstanceId, float volume)
+    {
+        var clamped = Math.Max(0.0f, Math.Min(1.0f, volume));
+        return WithCaptureSession(deviceId, sessionInstanceId, "set session volume", session =>
+        {
+            session.SimpleAudioVolume.Volume = clamped;
+        });
+    }
+
+    private bool WithCaptureSession(string deviceId, string sessionInstanceId, string operation, Action<AudioSessionControl> action)
+    {
+        using var scope = _logger.BeginScope("{Operation} {DeviceId} session {SessionId}", operation, deviceId, sessionInstanceId);
+        using var lease = AcquireDevice(deviceId);
+        var device = lease.Device;
+        if (device == null) return false;
+
+        try
+        {
+            var sessionManager = device.AudioSessionManager;
+            sessionManager.RefreshSessions();
+            var sessions = sessionManager.Sessions;
+
+            for (var i = 0; i < sessions.Count; i++)
+            {
+                using var session = sessions[i];
+                if (session.GetSessionInstanceIdentifier != sessionInstanceId) continue;
+
+                action(session);
+                return true;
+            }
+        }
+        catch (Exception ex)
+        {
+            RaiseOperationFailed(operation, deviceId, ex);
+        }
+
+        return false;
+    }
 
     /// <summary>
     /// Toggles the mute state of the specified device.
     /// </summary>
     public bool ToggleMute(string deviceId)
     {
-        var device = GetDeviceById(deviceId);
+        using var scope = BeginDeviceScope(deviceId);
+        using var lease = AcquireDevice(deviceId);
+        var device = lease.Device;
+        if (device?.AudioEndpointVolume == null) return false;
+
+        try
+        {
+            var newMuteState = !device.AudioEndpointVolume.Mute;
+            OwnEndpointVolume(device).Mute = newMuteState;
+            return newMuteState;
+        }
+        catch
+        {
+            // Cached endpoint may have been invalidated; re-resolve on the next call
+            EvictResolvedDevice(deviceId);
+            throw;
+        }
+    }
+
+    /// <summary>
+    /// Sets the mute state of the specified device.
+    /// </summary>
+    /// <returns>True if the device was found and updated.</returns>
+    public bool SetMute(string deviceId, bool muted)
+    {
+        using var scope = BeginDeviceScope(deviceId);
+        using var lease = AcquireDevice(deviceId);
+        var device = lease.Device;
         if (device?.AudioEndpointVolume == null) return false;
 
-        var newMuteState = !device.AudioEndpointVolume.Mute;
-        device.AudioEndpointVolume.Mute = newMuteState;
-        return newMuteState;
+        try
+        {
+            OwnEndpointVolume(device).Mute = muted;
+            return true;
+        }
+        catch (Exception ex)
+        {
+            EvictResolvedDevice(deviceId);
+            RaiseOperationFailed(muted ? "mute microphone" : "unmute microphone", deviceId, ex);
+            return false;
+        }
+    }
+
+    /// <summary>
+    /// Mutes or unmutes every active capture endpoint (webcams, headsets, virtual cables).
+    /// </summary>
+    /// <returns>The number of endpoints that were updated.</returns>
+    public int SetAllMicrophonesMuted(bool muted)
+    {
+        var updated = 0;
+        foreach (var mic in GetMicrophones())
+        {
+            if (SetMute(mic.Id, muted))
+            {
+                updated++;
+            }
+        }
+
+        InvalidateMicrophoneCache();
+        _logger.LogInformation("{Action} {Count} microphones", muted ? "Muted" : "Unmuted", updated);
+        return updated;
+    }
+
+    /// <summary>
+    /// True when at least one microphone is active and every active microphone is muted.
+    /// </summary>
+    public bool AreAllMicrophonesMuted()
+    {
+        var mics = GetMicrophones();
+        return mics.Count > 0 && mics.All(m => IsMuted(m.Id));
     }
 
     /// <summary>
@@ -446,7 +1603,8 @@ public class AudioDeviceService : IDisposable, IAudioDeviceService
     /// </summary>
     public bool IsMuted(string deviceId)
     {
-        var device = GetDeviceById(deviceId);
+        using var lease = AcquireDevice(deviceId);
+        var device = lease.Device;
         return device?.AudioEndpointVolume?.Mute ?? false;
     }
 
@@ -460,25 +1618,126 @@ public class AudioDeviceService : IDisposable, IAudioDeviceService
         return ToggleMute(defaultId);
     }
 
-    /// <summary>
-    /// Gets the mute state of the default microphone.
-    /// </summary>
-    public bool IsDefaultMicrophoneMuted()
+    /// <summary>
+    /// Gets the mute state of the default microphone.
+    /// </summary>
+    public bool IsDefaultMicrophoneMuted()
+    {
+        var defaultId = GetDefaultDeviceId(Role.Console);
+        if (defaultId == null) return false;
+        return IsMuted(defaultId);
+    }
+
+    private DeviceLease AcquireDevice(string deviceId)
+    {
+        lock (_resolvedDevicesLock)
+        {
+            if (_resolvedDevicesById.TryGetValue(deviceId, out var cached))
+            {
+                cached.Users++;
+                return new DeviceLease(this, cached);
+            }
+        }
+
+        MMDevice device;
+        try
+        {
+            device = _enumerator.GetDevice(deviceId);
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "GetDevice failed for {DeviceId}", deviceId);
+            return default;
+        }
+
+        ResolvedDevice entry;
+        var resolvedElsewhere = false;
+        lock (_resolvedDevicesLock)
+        {
+            // Another thread may have resolved it meanwhile; share that one so neither is leaked
+            if (_resolvedDevicesById.TryGetValue(deviceId, out var existing))
+            {
+                entry = existing;
+                resolvedElsewhere = true;
+            }
+            else
+            {
+                entry = new ResolvedDevice(device);
+                _resolvedDevicesById[deviceId] = entry;
+            }
+
+            entry.Users++;
+        }
+
+        if (resolvedElsewhere)
+        {
+            DisposeResolvedDevice(device);
+        }
+
+        return new DeviceLease(this, entry);
+    }
+
+    private void ReleaseDevice(ResolvedDevice entry)
+    {
+        bool dispose;
+        lock (_resolvedDevicesLock)
+        {
+            entry.Users--;
+            dispose = entry.Evicted && entry.Users == 0;
+        }
+
+        if (dispose)
+        {
+            DisposeResolvedDevice(entry.Device);
+        }
+    }
+
+    private void EvictResolvedDevice(string deviceId)
+    {
+        ResolvedDevice? evicted;
+        bool dispose;
+        lock (_resolvedDevicesLock)
+        {
+            if (!_resolvedDevicesById.Remove(deviceId, out evicted)) return;
+            evicted.Evicted = true;
+            dispose = evicted.Users == 0;
+        }
+
+        if (dispose)
+        {
+            DisposeResolvedDevice(evicted.Device);
+        }
+    }
+
+    private void ClearResolvedDevices()
     {
-        var defaultId = GetDefaultDeviceId(Role.Console);
-        if (defaultId == null) return false;
-        return IsMuted(defaultId);
+        List<ResolvedDevice> unused;
+        lock (_resolvedDevicesLock)
+        {
+            foreach (var entry in _resolvedDevicesById.Values)
+            {
+                entry.Evicted = true;
+            }
+
+            // Devices still in use are disposed by whoever releases them last
+            unused = _resolvedDevicesById.Values.Where(entry => entry.Users == 0).ToList();
+            _resolvedDevicesById.Clear();
+        }
+
+        foreach (var entry in unused)
+        {
+            DisposeResolvedDevice(entry.Device);
+        }
     }
 
-    private MMDevice? GetDeviceById(string deviceId)
+    private static void DisposeResolvedDevice(MMDevice? device)
     {
         try
         {
-            return _enumerator.GetDevice(deviceId);
+            device?.Dispose();
         }
         catch
         {
-            return null;
         }
     }
 
@@ -532,55 +1791,145 @@ public class AudioDeviceService : IDisposable, IAudioDeviceService
         }
     }
 
-    private static double GetDeviceInputLevel(MMDevice device)
+    private static double GetDeviceMeterLevel(MMDevice device)
+    {
+        // Map through OBS-style LOG dB->deflection for a meter that behaves like OBS.
+        return ObsMeterMath.DbToPercent(GetDeviceMeterDb(device));
+    }
+
+    private static double GetDeviceMeterDb(MMDevice device)
     {
         try
         {
             var meter = device.AudioMeterInformation;
-            if (meter == null) return 0;
+            if (meter == null) return ObsMeterMath.ClampMeterDb(double.NegativeInfinity);
 
-            // AudioMeterInformation reports linear peak amplitude (0..1).
-            // Map through OBS-style LOG dB->deflection for a meter that behaves like OBS.
+            // AudioMeterInformation reports linear peak amplitude (0..1) for capture and render endpoints alike.
             var value = meter.MasterPeakValue;
             value = MathF.Max(0f, MathF.Min(1f, value));
 
-            var dbFs = ObsMeterMath.ClampMeterDb(ObsMeterMath.MulToDb(value));
-            var percent = ObsMeterMath.DbToPercent(dbFs);
-            
-            return percent;
+            return ObsMeterMath.ClampMeterDb(ObsMeterMath.MulToDb(value));
         }
         catch
         {
-            return 0;
+            return ObsMeterMath.ClampMeterDb(double.NegativeInfinity);
         }
     }
 
-    internal void OnDevicesChanged()
+    private static double[] GetDeviceChannelMeterDb(MMDevice device)
     {
-        // Invalidate cache when device list changes
-        InvalidateMicrophoneCache();
+        try
+        {
+            var peaks = device.AudioMeterInformation?.PeakValues;
+            if (peaks == null) return Array.Empty<double>();
+
+            var levels = new double[peaks.Count];
+            for (var i = 0; i < levels.Length; i++)
+            {
+                var value = MathF.Max(0f, MathF.Min(1f, peaks[i]));
+                levels[i] = ObsMeterMath.ClampMeterDb(ObsMeterMath.MulToDb(value));
+            }
+
+            return levels;
+        }
+        catch
+        {
+            return Array.Empty<double>();
+        }
+    }
+
+    /// <summary>
+    /// Records an endpoint notification and starts the coalescing window if one isn't already open.
+    /// The window is not extended by later events, so a continuous storm still flushes regularly.
+    /// </summary>
+    internal void QueueDeviceChange(string deviceId, DeviceChangeKind kind)
+    {
+        if (_disposed) return;
+
+        lock (_deviceEventLock)
+        {
+            _pendingDeviceChanges[deviceId] = kind;
+            _pendingTopologyChange |= kind != DeviceChangeKind.PropertyChanged;
+
+            if (_deviceEventCoalesceTimer != null) return;
+
+            _deviceEventCoalesceTimer = new Timer(
+                _ => FlushDeviceChanges(),
+                null,
+                dueTime: DeviceEventCoalescingWindow,
+                period: Timeout.InfiniteTimeSpan);
+        }
+    }
+
+    private void FlushDeviceChanges()
+    {
+        List<DeviceChange> changes;
+        bool topologyChanged;
+        lock (_deviceEventLock)
+        {
+            changes = _pendingDeviceChanges.Select(p => new DeviceChange(p.Key, p.Value)).ToList();
+            topologyChanged = _pendingTopologyChange;
+            _pendingDeviceChanges.Clear();
+            _pendingTopologyChange = false;
+
+            _deviceEventCoalesceTimer?.Dispose();
+            _deviceEventCoalesceTimer = null;
+        }
+
+        if (_disposed || changes.Count == 0) return;
+
+        _logger.LogDebug("Flushing {Count} coalesced device changes", changes.Count);
+
+        foreach (var change in changes)
+        {
+            _exclusiveLocks.TryRemove(change.DeviceId, out _);
+            _topologyService.Invalidate(change.DeviceId);
+        }
+
+        if (topologyChanged)
+        {
+            OnDeviceTopologyChanged(changes);
+        }
+        else
+        {
+            // Property-only changes don't add or remove endpoints; subscriptions stay valid
+            InvalidateMicrophoneCache();
+            RaiseDevicesChanged(changes);
+        }
+    }
+
+    /// <summary>
+    /// Raises <see cref="DevicesChanged"/> on the UI thread. When the affected endpoints are known the
+    /// args are a <see cref="DevicesChangedEventArgs"/>, so listeners can patch those entries instead
+    /// of re-enumerating.
+    /// </summary>
+    private void RaiseDevicesChanged(IReadOnlyList<DeviceChange>? changes)
+    {
+        var args = changes == null ? EventArgs.Empty : new DevicesChangedEventArgs(changes);
 
         // Post event to UI thread if available
         if (_syncContext != null)
         {
-            _syncContext.Post(_ => DevicesChanged?.Invoke(this, EventArgs.Empty), null);
+            _syncContext.Post(_ => DevicesChanged?.Invoke(this, args), null);
         }
         else
         {
-            DevicesChanged?.Invoke(this, EventArgs.Empty);
+            DevicesChanged?.Invoke(this, args);
         }
     }
 
-    internal void OnDeviceTopologyChanged()
+    private void OnDeviceTopologyChanged(IReadOnlyList<DeviceChange>? changes)
     {
-        // Invalidate cache when device topology changes
+        // Invalidate caches when device topology changes; resolved endpoints may now be stale
         InvalidateMicrophoneCache();
+        ClearResolvedDevices();
+        _topologyService.Invalidate();
 
         // Fire-and-forget: move expensive subscription updates to background thread
-        _ = OnDeviceTopologyChangedAsync();
+        _ = OnDeviceTopologyChangedAsync(changes);
     }
 
-    private async Task OnDeviceTopologyChangedAsync()
+    private async Task OnDeviceTopologyChangedAsync(IReadOnlyList<DeviceChange>? changes)
     {
         try
         {
@@ -588,24 +1937,86 @@ public class AudioDeviceService : IDisposable, IAudioDeviceService
             await Task.Run(() =>
             {
                 UpdateMicrophoneVolumeNotificationSubscriptions();
+                UpdateOutputVolumeNotificationSubscriptions();
+                UpdateCaptureSessionNotificationSubscriptions();
             }).ConfigureAwait(false);
 
             // Update meter subscriptions when devices added/removed
             await UpdateAllMicrophoneMeterSubscriptionsAsync().ConfigureAwait(false);
 
-            // Post event to UI thread
-            if (_syncContext != null)
-            {
-                _syncContext.Post(_ => DevicesChanged?.Invoke(this, EventArgs.Empty), null);
-            }
-            else
-            {
-                DevicesChanged?.Invoke(this, EventArgs.Empty);
-            }
+            RaiseDevicesChanged(changes);
         }
         catch (Exception ex)
         {
-            System.Diagnostics.Debug.WriteLine($"OnDeviceTopologyChangedAsync failed: {ex}");
+            _logger.LogError(ex, "Handling device topology change failed");
+        }
+    }
+
+    /// <summary>
+    /// Tags everything logged during a COM call on one endpoint (including NAudio and topology
+    /// failures logged further down) with the public method and device it came from.
+    /// </summary>
+    private IDisposable? BeginDeviceScope(string deviceId, [CallerMemberName] string operation = "")
+    {
+        return _logger.BeginScope("{Operation} {DeviceId}", operation, deviceId);
+    }
+
+    private void RaiseOperationFailed(string operation, string? deviceId, Exception exception)
+    {
+        if (exception is OperationCanceledException) return;
+
+        var origin = GetOriginatingException(exception);
+        _logger.LogWarning(exception, "{Operation} failed for {DeviceId} (0x{HResult:X8})", operation, deviceId, origin.HResult);
+
+        if (deviceId != null && AudioErrorCodes.FromHResult(origin.HResult) == AudioErrorCode.DeviceInUseExclusive)
+        {
+            _exclusiveLocks[deviceId] = (true, Environment.TickCount64);
+        }
+
+        var args = new AudioOperationFailedEventArgs(operation, deviceId, origin.HResult, origin.Message)
+        {
+            DeviceName = TryGetDeviceName(deviceId),
+            ExceptionType = origin.GetType().Name
+        };
+        LastError = args;
+
+        if (_syncContext != null)
+        {
+            _syncContext.Post(_ => OperationFailed?.Invoke(this, args), null);
+        }
+        else
+        {
+            OperationFailed?.Invoke(this, args);
+        }
+    }
+
+    /// <summary>
+    /// Unwraps task/reflection wrappers so the reported HRESULT is the one Core Audio returned.
+    /// </summary>
+    private static Exception GetOriginatingException(Exception exception)
+    {
+        var current = exception;
+        while (current is AggregateException or System.Reflection.TargetInvocationException && current.InnerException != null)
+        {
+            current = current.InnerException;
+        }
+
+        return current;
+    }
+
+    private string? TryGetDeviceName(string? deviceId)
+    {
+        if (deviceId == null) return null;
+
+        try
+        {
+            using var lease = AcquireDevice(deviceId);
+            return lease.Device?.FriendlyName;
+        }
+        catch
+        {
+            // The failure may be that the device is gone
+            return null;
         }
     }
 
@@ -618,6 +2029,18 @@ public class AudioDeviceService : IDisposable, IAudioDeviceService
         }
     }
 
+    internal void OnDefaultOutputDeviceChanged()
+    {
+        if (_syncContext != null)
+        {
+            _syncContext.Post(_ => OutputDevicesChanged?.Invoke(this, EventArgs.Empty), null);
+        }
+        else
+        {
+            OutputDevicesChanged?.Invoke(this, EventArgs.Empty);
+        }
+    }
+
     internal void OnDefaultDeviceChanged()
     {
         // Debounce: When setting both Console + Communications roles, Windows fires
@@ -666,7 +2089,7 @@ public class AudioDeviceService : IDisposable, IAudioDeviceService
         }
         catch (Exception ex)
         {
-            System.Diagnostics.Debug.WriteLine($"ProcessPendingDeviceChangesAsync failed: {ex}");
+            _logger.LogError(ex, "Handling default device change failed");
         }
     }
 
@@ -680,7 +2103,11 @@ public class AudioDeviceService : IDisposable, IAudioDeviceService
             {
                 activeDevices = _enumerator.EnumerateAudioEndPoints(DataFlow.Capture, DeviceState.Active).ToList();
             }
-            catch { return; }
+            catch (Exception ex)
+            {
+                _logger.LogWarning(ex, "Capture endpoint enumeration failed while updating meter subscriptions");
+                return;
+            }
 
             var activeIds = new HashSet<string>(activeDevices.Select(d => d.ID));
 
@@ -731,7 +2158,11 @@ public class AudioDeviceService : IDisposable, IAudioDeviceService
                             DeviceFormatSignature = formatSig
                         };
                     }
-                    catch { /* Device may not support capture */ }
+                    catch (Exception ex)
+                    {
+                        // Device may not support capture (exclusive mode, driver fault, etc.)
+                        _logger.LogInformation(ex, "Could not start meter capture for {DeviceId}", device.ID);
+                    }
                 }
             }
         }).ConfigureAwait(false);
@@ -772,8 +2203,8 @@ public class AudioDeviceService : IDisposable, IAudioDeviceService
         }
         if (state == null) return;
 
-        // Accumulate peak
-        var bufferPeak = CalculatePeakAmplitude(e.Buffer, e.BytesRecorded, capture.WaveFormat);
+        // Accumulate peak and the squares for RMS
+        var bufferPeak = CalculateLevels(e.Buffer, e.BytesRecorded, capture.WaveFormat, ref state.AccumulatedSquares, ref state.AccumulatedSamples);
         state.AccumulatedPeak = Math.Max(state.AccumulatedPeak, bufferPeak);
 
         // Throttle to ~120Hz per device
@@ -782,21 +2213,27 @@ public class AudioDeviceService : IDisposable, IAudioDeviceService
             return;
 
         var peak = state.AccumulatedPeak;
+        var rms = state.AccumulatedSamples > 0 ? Math.Sqrt(state.AccumulatedSquares / state.AccumulatedSamples) : 0.0;
         state.AccumulatedPeak = 0.0;
+        state.AccumulatedSquares = 0.0;
+        state.AccumulatedSamples = 0;
         state.LastEventRaisedAtUtc = nowUtc;
 
         // Convert to dBFS and percent
         var peakDb = ObsMeterMath.ClampMeterDb(ObsMeterMath.MulToDb(peak));
+        var rmsDb = ObsMeterMath.ClampMeterDb(ObsMeterMath.MulToDb(rms));
         var percent = ObsMeterMath.DbToPercent(peakDb);
+        Volatile.Write(ref state.LatestRmsDb, rmsDb);
 
-        var args = new MicrophoneInputLevelChangedEventArgs(state.DeviceId, percent, peakDb);
-        if (_syncContext != null)
-            _syncContext.Post(_ => MicrophoneInputLevelChanged?.Invoke(this, args), null);
-        else
-            MicrophoneInputLevelChanged?.Invoke(this, args);
+        // Raised right here on the capture thread: at ~120 Hz per microphone, posting each one would
+        // keep the UI thread busy for listeners that mostly drop them
+        MicrophoneInputLevelChanged?.Invoke(this, new MicrophoneInputLevelChangedEventArgs(state.DeviceId, percent, peakDb, rmsDb));
     }
 
-    private static double CalculatePeakAmplitude(byte[] buffer, int bytesRecorded, WaveFormat waveFormat)
+    /// <summary>
+    /// Returns the buffer's peak amplitude (0..1) and adds its squared samples to the RMS window.
+    /// </summary>
+    private static double CalculateLevels(byte[] buffer, int bytesRecorded, WaveFormat waveFormat, ref double sumOfSquares, ref long sampleCount)
     {
         if (bytesRecorded <= 0) return 0.0;
 
@@ -825,6 +2262,7 @@ public class AudioDeviceService : IDisposable, IAudioDeviceService
         var bits = waveFormat.BitsPerSample;
 
         double peak = 0.0;
+        double squares = 0.0;
 
         if (encoding == WaveFormatEncoding.IeeeFloat && bits == 32)
         {
@@ -832,9 +2270,12 @@ public class AudioDeviceService : IDisposable, IAudioDeviceService
             var floats = MemoryMarshal.Cast<byte, float>(span);
             for (var i = 0; i < floats.Length; i++)
             {
-                var v = Math.Abs(floats[i]);
+                double v = Math.Abs(floats[i]);
                 if (v > peak) peak = v;
+                squares += v * v;
             }
+            sumOfSquares += squares;
+            sampleCount += floats.Length;
             return Math.Min(1.0, peak);
         }
 
@@ -846,7 +2287,10 @@ public class AudioDeviceService : IDisposable, IAudioDeviceService
                 var sample = (short)(span[i] | (span[i + 1] << 8));
                 var v = Math.Abs(sample / 32768.0);
                 if (v > peak) peak = v;
+                squares += v * v;
             }
+            sumOfSquares += squares;
+            sampleCount += span.Length / 2;
             return Math.Min(1.0, peak);
         }
 
@@ -863,128 +2307,295 @@ public class AudioDeviceService : IDisposable, IAudioDeviceService
                 }
                 var v = Math.Abs(sample / 8388608.0);
                 if (v > peak) peak = v;
+                squares += v * v;
+            }
+            sumOfSquares += squares;
+            sampleCount += span.Length / 3;
+            return Math.Min(1.0, peak);
+        }
+
+        if (encoding == WaveFormatEncoding.Pcm && bits == 32)
+        {
+            var span = buffer.AsSpan(0, usableBytes);
+            for (var i = 0; i < span.Length; i += 4)
+            {
+                var sample = span[i] | (span[i + 1] << 8) | (span[i + 2] << 16) | (span[i + 3] << 24);
+                var v = Math.Abs(sample / 2147483648.0);
+                if (v > peak) peak = v;
+                squares += v * v;
+            }
+            sumOfSquares += squares;
+            sampleCount += span.Length / 4;
+            return Math.Min(1.0, peak);
+        }
+
+        // Fallback: treat as silence if we can't decode
+        _ = channels;
+        return 0.0;
+    }
+
+    private void UpdateMicrophoneVolumeNotificationSubscriptions()
+    {
+        UpdateVolumeNotificationSubscriptions(DataFlow.Capture, _volumeNotificationSubscriptions, OnMicrophoneVolumeNotification);
+    }
+
+    private void UpdateOutputVolumeNotificationSubscriptions()
+    {
+        UpdateVolumeNotificationSubscriptions(DataFlow.Render, _outputVolumeNotificationSubscriptions, OnOutputVolumeNotification);
+    }
+
+    private void UpdateVolumeNotificationSubscriptions(
+        DataFlow flow,
+        Dictionary<string, VolumeNotificationSubscription> subscriptions,
+        Action<string, AudioVolumeNotificationData> onNotification)
+    {
+        List<MMDevice> devices;
+        try
+        {
+            devices = _enumerator.EnumerateAudioEndPoints(flow, DeviceState.Active).ToList();
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "{Flow} endpoint enumeration failed while updating volume subscriptions", flow);
+            return;
+        }
+
+        var activeIds = new HashSet<string>(devices.Select(d => d.ID));
+
+        lock (_volumeNotificationLock)
+        {
+            // Remove subscriptions for devices that no longer exist/active
+            var toRemove = subscriptions.Keys.Where(id => !activeIds.Contains(id)).ToList();
+            foreach (var id in toRemove)
+            {
+                if (subscriptions.TryGetValue(id, out var sub))
+                {
+                    try
+                    {
+                        sub.EndpointVolume.OnVolumeNotification -= sub.Handler;
+                    }
+                    catch { }
+                }
+
+                subscriptions.Remove(id);
+            }
+
+            // Add subscriptions for new active devices
+            foreach (var device in devices)
+            {
+                if (subscriptions.ContainsKey(device.ID))
+                {
+                    continue;
+                }
+
+                var endpointVolume = device.AudioEndpointVolume;
+                if (endpointVolume == null)
+                {
+                    continue;
+                }
+
+                // Capture device ID as a string to avoid COM object lifetime issues in the callback
+                string deviceId = device.ID;
+                AudioEndpointVolumeNotificationDelegate handler = (data) => onNotification(deviceId, data);
+                try
+                {
+                    endpointVolume.OnVolumeNotification += handler;
+                    subscriptions[device.ID] = new VolumeNotificationSubscription(endpointVolume, handler);
+                }
+                catch (Exception ex)
+                {
+                    // Device could disappear or access denied
+                    _logger.LogDebug(ex, "Volume notification registration failed for {DeviceId}", device.ID);
+                }
+            }
+        }
+    }
+
+    /// <summary>
+    /// Render volume callbacks re-raise <see cref="OutputDevicesChanged"/>. A drag of the system volume
+    /// slider fires dozens of callbacks, so only one event is queued until the UI thread picks it up.
+    /// </summary>
+    private void OnOutputVolumeNotification(string deviceId, AudioVolumeNotificationData data)
+    {
+        if (_disposed) return;
+        if (Interlocked.Exchange(ref _outputDevicesChangedPending, 1) == 1) return;
+
+        if (_syncContext != null)
+        {
+            _syncContext.Post(_ =>
+            {
+                Interlocked.Exchange(ref _outputDevicesChangedPending, 0);
+                OutputDevicesChanged?.Invoke(this, EventArgs.Empty);
+            }, null);
+        }
+        else
+        {
+            Interlocked.Exchange(ref _outputDevicesChangedPending, 0);
+            OutputDevicesChanged?.Invoke(this, EventArgs.Empty);
+        }
+    }
+
+    private void OnMicrophoneVolumeNotification(string deviceId, AudioVolumeNotificationData data)
+    {
+        if (_disposed) return;
+
+        string? defaultId;
+        lock (_volumeNotificationLock)
+        {
+            defaultId = _currentDefaultCaptureDeviceId;
+
+            // Record what the callback reported so the fallback poll doesn't raise it a second time
+            if (_lastKnownStateById.TryGetValue(deviceId, out var prior))
+            {
+                _lastKnownStateById[deviceId] = (data.MasterVolume, data.Muted, prior.FormatTag);
+            }
+        }
+
+        RaiseMicrophoneVolumeChanged(deviceId, data.MasterVolume, data.Muted, defaultId, data.EventContext, IsExternalVolumeChange(data.EventContext));
+    }
+
+    /// <summary>
+    /// Whether a volume change was made by other software. The endpoint callback carries the writer's
+    /// event context (null for the poll). Our own writes are tagged with <see cref="VolumeEventContext"/>
+    /// and their callback updates the poll's baseline, so a difference only the poll sees came from
+    /// elsewhere; a device's first reading isn't a change at all.
+    /// </summary>
+    public static bool IsExternalVolumeChange(Guid? eventContext, bool isFirstReading = false)
+    {
+        return eventContext.HasValue ? eventContext.Value != VolumeEventContext : !isFirstReading;
+    }
+
+    /// <summary>
+    /// Whether a capture session belongs to this process, i.e. one of our level meters rather than
+    /// an application using the microphone.
+    /// </summary>
+    public static bool IsOwnSession(uint processId) => processId == Environment.ProcessId;
+
+    /// <summary>
+    /// Raises <see cref="MicrophoneVolumeChanged"/> (and <see cref="DefaultMicrophoneVolumeChanged"/> for the
+    /// default device) on the UI thread. Endpoint volume callbacks arrive on an MTA worker thread.
+    /// </summary>
+    private void RaiseMicrophoneVolumeChanged(string deviceId, float volume, bool muted, string? defaultId, Guid context, bool changedExternally)
+    {
+        var volumeArgs = new MicrophoneVolumeChangedEventArgs(deviceId, volume, muted, context, changedExternally);
+        var isDefault = defaultId != null && deviceId == defaultId;
+
+        if (_syncContext != null)
+        {
+            _syncContext.Post(_ => MicrophoneVolumeChanged?.Invoke(this, volumeArgs), null);
+
+            if (isDefault)
+            {
+                var defaultVolumeArgs = new DefaultMicrophoneVolumeChangedEventArgs(deviceId, volume, muted, context, changedExternally);
+                _syncContext.Post(_ => DefaultMicrophoneVolumeChanged?.Invoke(this, defaultVolumeArgs), null);
             }
-            return Math.Min(1.0, peak);
         }
-
-        if (encoding == WaveFormatEncoding.Pcm && bits == 32)
+        else
         {
-            var span = buffer.AsSpan(0, usableBytes);
-            for (var i = 0; i < span.Length; i += 4)
+            MicrophoneVolumeChanged?.Invoke(this, volumeArgs);
+
+            if (isDefault)
             {
-                var sample = span[i] | (span[i + 1] << 8) | (span[i + 2] << 16) | (span[i + 3] << 24);
-                var v = Math.Abs(sample / 2147483648.0);
-                if (v > peak) peak = v;
+                DefaultMicrophoneVolumeChanged?.Invoke(
+                    this,
+                    new DefaultMicrophoneVolumeChangedEventArgs(deviceId, volume, muted, context, changedExternally));
             }
-            return Math.Min(1.0, peak);
         }
-
-        // Fallback: treat as silence if we can't decode
-        _ = channels;
-        return 0.0;
     }
 
-    private void UpdateMicrophoneVolumeNotificationSubscriptions()
+    private void UpdateCaptureSessionNotificationSubscriptions()
     {
         List<MMDevice> devices;
         try
         {
             devices = _enumerator.EnumerateAudioEndPoints(DataFlow.Capture, DeviceState.Active).ToList();
         }
-        catch
+        catch (Exception ex)
         {
+            _logger.LogWarning(ex, "Capture endpoint enumeration failed while updating session subscriptions");
             return;
         }
 
         var activeIds = new HashSet<string>(devices.Select(d => d.ID));
 
-        lock (_volumeNotificationLock)
+        lock (_sessionNotificationLock)
         {
-            // Remove subscriptions for devices that no longer exist/active
-            var toRemove = _volumeNotificationSubscriptions.Keys.Where(id => !activeIds.Contains(id)).ToList();
+            var toRemove = _sessionNotificationSubscriptions.Keys.Where(id => !activeIds.Contains(id)).ToList();
             foreach (var id in toRemove)
             {
-                if (_volumeNotificationSubscriptions.TryGetValue(id, out var sub))
-                {
-                    try
-                    {
-                        sub.EndpointVolume.OnVolumeNotification -= sub.Handler;
-                    }
-                    catch { }
-                }
-
-                _volumeNotificationSubscriptions.Remove(id);
+                _sessionNotificationSubscriptions[id].Dispose();
+                _sessionNotificationSubscriptions.Remove(id);
             }
 
-            // Add subscriptions for new active devices
             foreach (var device in devices)
             {
-                if (_volumeNotificationSubscriptions.ContainsKey(device.ID))
-                {
-                    continue;
-                }
+                if (_sessionNotificationSubscriptions.ContainsKey(device.ID)) continue;
 
-                var endpointVolume = device.AudioEndpointVolume;
-                if (endpointVolume == null)
-                {
-                    continue;
-                }
-
-                // Capture device ID as a string to avoid COM object lifetime issues in the callback
-                string deviceId = device.ID;
-                AudioEndpointVolumeNotificationDelegate handler = (data) => OnMicrophoneVolumeNotification(deviceId, data);
                 try
                 {
-                    endpointVolume.OnVolumeNotification += handler;
-                    _volumeNotificationSubscriptions[device.ID] = new VolumeNotificationSubscription(endpointVolume, handler);
+                    _sessionNotificationSubscriptions[device.ID] = new SessionNotificationSubscription(this, device.ID, device.AudioSessionManager);
                 }
-                catch
+                catch (Exception ex)
                 {
-                    // Ignore - device could disappear or access denied
+                    _logger.LogDebug(ex, "Session notification registration failed for {DeviceId}", device.ID);
                 }
             }
         }
     }
 
-    private void OnMicrophoneVolumeNotification(string deviceId, AudioVolumeNotificationData data)
+    private void OnCaptureSessionChanged(string deviceId, string sessionInstanceId, uint processId, CaptureSessionChangeKind kind)
     {
-        MicrophoneVolumeChanged?.Invoke(
-            this,
-            new MicrophoneVolumeChangedEventArgs(deviceId, data.MasterVolume, data.Muted));
+        if (_disposed) return;
 
-        string? defaultId;
-        lock (_volumeNotificationLock)
+        _logger.LogDebug("Capture session {Kind} on {DeviceId} (pid {ProcessId})", kind, deviceId, processId);
+
+        // Our own sessions include the exclusive-mode probe; re-probing on those would loop
+        if (processId != (uint)Environment.ProcessId)
         {
-            defaultId = _currentDefaultCaptureDeviceId;
+            _exclusiveLocks.TryRemove(deviceId, out _);
         }
 
-        if (defaultId != null && deviceId == defaultId)
+        var args = new CaptureSessionChangedEventArgs(deviceId, sessionInstanceId, processId, kind);
+        if (_syncContext != null)
         {
-            DefaultMicrophoneVolumeChanged?.Invoke(
-                this,
-                new DefaultMicrophoneVolumeChangedEventArgs(deviceId, data.MasterVolume, data.Muted));
+            _syncContext.Post(_ => CaptureSessionChanged?.Invoke(this, args), null);
+        }
+        else
+        {
+            CaptureSessionChanged?.Invoke(this, args);
         }
     }
 
-    public void Dispose()
+    /// <summary>
+    /// Endpoint COM objects are often invalidated across sleep, leaving volume/session callbacks and
+    /// meter captures silently dead. Drops all of them, re-registers the endpoint notification client
+    /// and rebuilds from a fresh enumeration.
+    /// </summary>
+    public void RecoverAfterResume()
     {
         if (_disposed) return;
-        _disposed = true;
 
+        _logger.LogInformation("Rebuilding audio notifications after resume");
+
+        try { _enumerator.UnregisterEndpointNotificationCallback(_notificationClient); } catch { }
         try
         {
-            _externalStatePollTimer?.Dispose();
+            _enumerator.RegisterEndpointNotificationCallback(_notificationClient);
         }
-        catch { }
-        _externalStatePollTimer = null;
-
-        try
+        catch (Exception ex)
         {
-            _deviceChangeDebounceTimer?.Dispose();
+            _logger.LogWarning(ex, "Re-registering the endpoint notification client failed");
         }
-        catch { }
-        _deviceChangeDebounceTimer = null;
 
+        ReleaseMeterCaptures();
+        ReleaseNotificationSubscriptions();
+
+        // A full refresh: the list and tray re-read every device rather than patching entries
+        OnDeviceTopologyChanged(null);
+    }
+
+    private void ReleaseMeterCaptures()
+    {
         lock (_capturesLock)
         {
             foreach (var state in _capturesByDeviceId.Values)
@@ -993,7 +2604,10 @@ public class AudioDeviceService : IDisposable, IAudioDeviceService
             }
             _capturesByDeviceId.Clear();
         }
+    }
 
+    private void ReleaseNotificationSubscriptions()
+    {
         lock (_volumeNotificationLock)
         {
             foreach (var subscription in _volumeNotificationSubscriptions.Values)
@@ -1006,9 +2620,61 @@ public class AudioDeviceService : IDisposable, IAudioDeviceService
             }
 
             _volumeNotificationSubscriptions.Clear();
+
+            foreach (var subscription in _outputVolumeNotificationSubscriptions.Values)
+            {
+                try
+                {
+                    subscription.EndpointVolume.OnVolumeNotification -= subscription.Handler;
+                }
+                catch { }
+            }
+
+            _outputVolumeNotificationSubscriptions.Clear();
             _currentDefaultCaptureDeviceId = null;
         }
 
+        lock (_sessionNotificationLock)
+        {
+            foreach (var subscription in _sessionNotificationSubscriptions.Values)
+            {
+                subscription.Dispose();
+            }
+
+            _sessionNotificationSubscriptions.Clear();
+        }
+    }
+
+    public void Dispose()
+    {
+        if (_disposed) return;
+        _disposed = true;
+
+        try
+        {
+            _externalStatePollTimer?.Dispose();
+        }
+        catch { }
+        _externalStatePollTimer = null;
+
+        try
+        {
+            _deviceChangeDebounceTimer?.Dispose();
+        }
+        catch { }
+        _deviceChangeDebounceTimer = null;
+
+        lock (_deviceEventLock)
+        {
+            _deviceEventCoalesceTimer?.Dispose();
+            _deviceEventCoalesceTimer = null;
+            _pendingDeviceChanges.Clear();
+        }
+
+        ClearResolvedDevices();
+        ReleaseMeterCaptures();
+        ReleaseNotificationSubscriptions();
+
         try
         {
             _enumerator.UnregisterEndpointNotificationCallback(_notificationClient);
@@ -1020,39 +2686,74 @@ public class AudioDeviceService : IDisposable, IAudioDeviceService
 
     public sealed class DefaultMicrophoneVolumeChangedEventArgs : EventArgs
     {
-        public DefaultMicrophoneVolumeChangedEventArgs(string deviceId, float volumeLevelScalar, bool isMuted)
+        public DefaultMicrophoneVolumeChangedEventArgs(
+            string deviceId,
+            float volumeLevelScalar,
+            bool isMuted,
+            Guid eventContext = default,
+            bool changedExternally = false)
         {
             DeviceId = deviceId;
             VolumeLevelScalar = volumeLevelScalar;
             IsMuted = isMuted;
+            EventContext = eventContext;
+            ChangedExternally = changedExternally;
         }
 
         public string DeviceId { get; }
         public float VolumeLevelScalar { get; }
         public bool IsMuted { get; }
+
+        /// <summary>
+        /// Context GUID passed by the client that made the change; <see cref="VolumeEventContext"/> for our own writes.
+        /// </summary>
+        public Guid EventContext { get; }
+
+        /// <summary>
+        /// Another application changed the volume or mute state (as reported by the endpoint callback).
+        /// </summary>
+        public bool ChangedExternally { get; }
     }
 
     public sealed class MicrophoneVolumeChangedEventArgs : EventArgs
     {
-        public MicrophoneVolumeChangedEventArgs(string deviceId, float volumeLevelScalar, bool isMuted)
+        public MicrophoneVolumeChangedEventArgs(
+            string deviceId,
+            float volumeLevelScalar,
+            bool isMuted,
+            Guid eventContext = default,
+            bool changedExternally = false)
         {
             DeviceId = deviceId;
             VolumeLevelScalar = volumeLevelScalar;
             IsMuted = isMuted;
+            EventContext = eventContext;
+            ChangedExternally = changedExternally;
         }
 
         public string DeviceId { get; }
         public float VolumeLevelScalar { get; }
         public bool IsMuted { get; }
+
+        /// <summary>
+        /// Context GUID passed by the client that made the change; <see cref="VolumeEventContext"/> for our own writes.
+        /// </summary>
+        public Guid EventContext { get; }
+
+        /// <summary>
+        /// Another application changed the volume or mute state (as reported by the endpoint callback).
+        /// </summary>
+        public bool ChangedExternally { get; }
     }
 
     public sealed class MicrophoneInputLevelChangedEventArgs : EventArgs
     {
-        public MicrophoneInputLevelChangedEventArgs(string deviceId, double inputLevelPercent, double inputLevelDbFs)
+        public MicrophoneInputLevelChangedEventArgs(string deviceId, double inputLevelPercent, double inputLevelDbFs, double? rmsDbFs = null)
         {
             DeviceId = deviceId;
             InputLevelPercent = inputLevelPercent;
             InputLevelDbFs = inputLevelDbFs;
+            RmsDbFs = rmsDbFs ?? inputLevelDbFs;
         }
 
         public string DeviceId { get; }
@@ -1066,6 +2767,11 @@ public class AudioDeviceService : IDisposable, IAudioDeviceService
         /// Peak level in dBFS (clamped to [-60..0]).
         /// </summary>
         public double InputLevelDbFs { get; }
+
+        /// <summary>
+        /// RMS level in dBFS over the same window as the peak (a few ms), all channels together.
+        /// </summary>
+        public double RmsDbFs { get; }
     }
 
     public sealed class MicrophoneFormatChangedEventArgs : EventArgs
@@ -1080,6 +2786,88 @@ public class AudioDeviceService : IDisposable, IAudioDeviceService
         public string FormatTag { get; }
     }
 
+    /// <summary>
+    /// <see cref="DevicesChanged"/> args listing the endpoints a coalesced burst of notifications touched,
+    /// one entry per endpoint. Plain <see cref="EventArgs"/> still means "anything may have changed".
+    /// </summary>
+    public sealed class DevicesChangedEventArgs : EventArgs
+    {
+        public DevicesChangedEventArgs(IReadOnlyList<DeviceChange> changes)
+        {
+            Changes = changes;
+        }
+
+        public IReadOnlyList<DeviceChange> Changes { get; }
+    }
+
+    public sealed class CaptureSessionChangedEventArgs : EventArgs
+    {
+        public CaptureSessionChangedEventArgs(string deviceId, string sessionInstanceId, uint processId, CaptureSessionChangeKind kind)
+        {
+            DeviceId = deviceId;
+            SessionInstanceId = sessionInstanceId;
+            ProcessId = processId;
+            Kind = kind;
+        }
+
+        public string DeviceId { get; }
+        public string SessionInstanceId { get; }
+        public uint ProcessId { get; }
+        public CaptureSessionChangeKind Kind { get; }
+    }
+
+    /// <summary>
+    /// Raised at the point a device operation fails, carrying the HRESULT that would
+    /// otherwise be lost behind a bool return value or a swallowed exception.
+    /// </summary>
+    public sealed class AudioOperationFailedEventArgs : EventArgs
+    {
+        public AudioOperationFailedEventArgs(string operation, string? deviceId, int hResult, string message)
+        {
+            Operation = operation;
+            DeviceId = deviceId;
+            HResult = hResult;
+            Message = message;
+        }
+
+        /// <summary>
+        /// Short description of the failed operation, e.g. "set volume".
+        /// </summary>
+        public string Operation { get; }
+        public string? DeviceId { get; }
+        public int HResult { get; }
+        public string Message { get; }
+
+        /// <summary>
+        /// Friendly name of the device, when it could still be resolved.
+        /// </summary>
+        public string? DeviceName { get; init; }
+
+        /// <summary>
+        /// Type name of the originating exception, e.g. "COMException" or "TimeoutException".
+        /// </summary>
+        public string? ExceptionType { get; init; }
+
+        public DateTime OccurredAtUtc { get; init; } = DateTime.UtcNow;
+
+        public AudioErrorCode Code => AudioErrorCodes.FromHResult(HResult);
+
+        public string UserMessage => DeviceName == null
+            ? $"Failed to {Operation} (0x{HResult:X8})"
+            : $"Failed to {Operation} on {DeviceName} (0x{HResult:X8})";
+
+        /// <summary>
+        /// Multi-line description with every field, for logs and copied diagnostics.
+        /// </summary>
+        public string Details =>
+            $"Operation: {Operation}{Environment.NewLine}" +
+            $"Device: {DeviceName ?? "(unknown)"} [{DeviceId ?? "-"}]{Environment.NewLine}" +
+            $"HRESULT: 0x{HResult:X8} ({Code}){Environment.NewLine}" +
+            $"Exception: {ExceptionType ?? "-"}{Environment.NewLine}" +
+            $"Message: {Message}{Environment.NewLine}" +
+            $"Time (UTC): {OccurredAtUtc:O}";
+    }
+
     /// <summary>
     /// Internal notification client for device change events.
     /// </summary>
@@ -1094,17 +2882,17 @@ public class AudioDeviceService : IDisposable, IAudioDeviceService
 
         public void OnDeviceStateChanged(string deviceId, DeviceState newState)
         {
-            _service.OnDeviceTopologyChanged();
+            _service.QueueDeviceChange(deviceId, DeviceChangeKind.StateChanged);
         }
 
         public void OnDeviceAdded(string pwstrDeviceId)
         {
-            _service.OnDeviceTopologyChanged();
+            _service.QueueDeviceChange(pwstrDeviceId, DeviceChangeKind.Added);
         }
 
         public void OnDeviceRemoved(string deviceId)
         {
-            _service.OnDeviceTopologyChanged();
+            _service.QueueDeviceChange(deviceId, DeviceChangeKind.Removed);
         }
 
         public void OnDefaultDeviceChanged(DataFlow flow, Role role, string defaultDeviceId)
@@ -1113,12 +2901,168 @@ public class AudioDeviceService : IDisposable, IAudioDeviceService
             {
                 _service.OnDefaultDeviceChanged();
             }
+            else if (flow == DataFlow.Render)
+            {
+                _service.OnDefaultOutputDeviceChanged();
+            }
         }
 
         public void OnPropertyValueChanged(string pwstrDeviceId, PropertyKey key)
         {
-            _service.OnDevicesChanged();
+            _service.QueueDeviceChange(pwstrDeviceId, DeviceChangeKind.PropertyChanged);
+        }
+    }
+
+    /// <summary>
+    /// IAudioSessionNotification for one capture endpoint, plus IAudioSessionEvents on each of its
+    /// sessions so state changes (active/inactive/expired) are reported too. Sessions that expire or
+    /// disconnect are dropped, so a long-running app doesn't keep every session it has ever seen.
+    /// </summary>
+    private sealed class SessionNotificationSubscription : IDisposable
+    {
+        private readonly AudioDeviceService _service;
+        private readonly string _deviceId;
+        private readonly AudioSessionManager _sessionManager;
+        private readonly List<(AudioSessionControl Session, SessionEventsClient Client)> _sessions = new();
+        private readonly object _lock = new();
+        private bool _disposed;
+
+        public SessionNotificationSubscription(AudioDeviceService service, string deviceId, AudioSessionManager sessionManager)
+        {
+            _service = service;
+            _deviceId = deviceId;
+            _sessionManager = sessionManager;
+
+            // Session-created notifications only start after the sessions have been enumerated once
+            _sessionManager.RefreshSessions();
+            var existing = _sessionManager.Sessions;
+            for (var i = 0; i < existing.Count; i++)
+            {
+                Track(existing[i]);
+            }
+
+            _sessionManager.OnSessionCreated += OnSessionCreated;
+        }
+
+        private void OnSessionCreated(object sender, IAudioSessionControl newSession)
+        {
+            var client = Track(new AudioSessionControl(newSession));
+            if (client != null)
+            {
+                _service.OnCaptureSessionChanged(_deviceId, client.SessionInstanceId, client.ProcessId, CaptureSessionChangeKind.Created);
+            }
+        }
+
+        private SessionEventsClient? Track(AudioSessionControl session)
+        {
+            if (session.IsSystemSoundsSession)
+            {
+                session.Dispose();
+                return null;
+            }
+
+            var client = new SessionEventsClient(_service, _deviceId, session.GetSessionInstanceIdentifier, session.GetProcessID, OnSessionEnded);
+            lock (_lock)
+            {
+                if (_disposed)
+                {
+                    session.Dispose();
+                    return null;
+                }
+
+                session.RegisterEventClient(client);
+                _sessions.Add((session, client));
+            }
+
+            return client;
+        }
+
+        private void OnSessionEnded(SessionEventsClient client)
+        {
+            // Unregistering from inside the session's own callback can deadlock, so do it afterwards
+            _ = Task.Run(() =>
+            {
+                AudioSessionControl? session = null;
+                lock (_lock)
+                {
+                    var index = _sessions.FindIndex(s => s.Client == client);
+                    if (index < 0) return;
+
+                    session = _sessions[index].Session;
+                    _sessions.RemoveAt(index);
+                }
+
+                Release(session, client);
+            });
+        }
+
+        private static void Release(AudioSessionControl session, SessionEventsClient client)
+        {
+            try { session.UnRegisterEventClient(client); } catch { }
+            try { session.Dispose(); } catch { }
+        }
+
+        public void Dispose()
+        {
+            lock (_lock)
+            {
+                if (_disposed) return;
+                _disposed = true;
+
+                try { _sessionManager.OnSessionCreated -= OnSessionCreated; } catch { }
+
+                foreach (var (session, client) in _sessions)
+                {
+                    Release(session, client);
+                }
+
+                _sessions.Clear();
+            }
+        }
+    }
+
+    private sealed class SessionEventsClient : IAudioSessionEventsHandler
+    {
+        private readonly AudioDeviceService _service;
+        private readonly string _deviceId;
+        private readonly Action<SessionEventsClient> _ended;
+
+        public SessionEventsClient(AudioDeviceService service, string deviceId, string sessionInstanceId, uint processId, Action<SessionEventsClient> ended)
+        {
+            _service = service;
+            _deviceId = deviceId;
+            _ended = ended;
+            SessionInstanceId = sessionInstanceId;
+            ProcessId = processId;
+        }
+
+        public string SessionInstanceId { get; }
+        public uint ProcessId { get; }
+
+        public void OnStateChanged(AudioSessionState state)
+        {
+            var kind = state switch
+            {
+                AudioSessionState.AudioSessionStateActive => CaptureSessionChangeKind.Activated,
+                AudioSessionState.AudioSessionStateInactive => CaptureSessionChangeKind.Deactivated,
+                _ => CaptureSessionChangeKind.Expired
+            };
+
+            _service.OnCaptureSessionChanged(_deviceId, SessionInstanceId, ProcessId, kind);
+            if (kind == CaptureSessionChangeKind.Expired) _ended(this);
+        }
+
+        public void OnSessionDisconnected(AudioSessionDisconnectReason disconnectReason)
+        {
+            _service.OnCaptureSessionChanged(_deviceId, SessionInstanceId, ProcessId, CaptureSessionChangeKind.Expired);
+            _ended(this);
         }
+
+        public void OnVolumeChanged(float volume, bool isMuted) { }
+        public void OnDisplayNameChanged(string displayName) { }
+        public void OnIconPathChanged(string iconPath) { }
+        public void OnChannelVolumeChanged(uint channelCount, IntPtr newVolumes, uint channelIndex) { }
+        public void OnGroupingParamChanged(ref Guid groupingId) { }
     }
 
     private sealed class VolumeNotificationSubscription
@@ -1132,4 +3076,41 @@ public class AudioDeviceService : IDisposable, IAudioDeviceService
         public AudioEndpointVolume EndpointVolume { get; }
         public AudioEndpointVolumeNotificationDelegate Handler { get; }
     }
+
+    private sealed class ResolvedDevice
+    {
+        public ResolvedDevice(MMDevice device)
+        {
+            Device = device;
+        }
+
+        public MMDevice Device { get; }
+
+        // Both guarded by _resolvedDevicesLock
+        public int Users;
+        public bool Evicted;
+    }
+
+    /// <summary>
+    /// One use of a cached device; <see cref="Device"/> is null when it couldn't be resolved. Dispose it
+    /// when done so an evicted device can be released.
+    /// </summary>
+    private readonly struct DeviceLease : IDisposable
+    {
+        private readonly AudioDeviceService? _owner;
+        private readonly ResolvedDevice? _entry;
+
+        public DeviceLease(AudioDeviceService owner, ResolvedDevice entry)
+        {
+            _owner = owner;
+            _entry = entry;
+        }
+
+        public MMDevice? Device => _entry?.Device;
+
+        public void Dispose()
+        {
+            if (_entry != null) _owner?.ReleaseDevice(_entry);
+        }
+    }
 }
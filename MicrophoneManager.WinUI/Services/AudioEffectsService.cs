@@ -0,0 +1,248 @@
+using System.Runtime.InteropServices;
+using Microsoft.Extensions.Logging;
+using MicrophoneManager.WinUI.Models;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Lists and toggles the audio processing effects (noise suppression, echo cancellation, ...) that a
+/// driver's APO reports through IAudioEffectsManager. Windows only hands that out through
+/// IAudioClient::GetService on an initialized client, so every call opens a shared-mode client on the
+/// endpoint (never started, so nothing is captured). Only available on Windows 11 22H2 and later;
+/// older systems report no effects, and any other failure is thrown rather than read as "no effects".
+/// </summary>
+public class AudioEffectsService
+{
+    private readonly ILogger<AudioEffectsService> _logger;
+
+    private const uint ClsctxAll = 0x17;
+    private const int AudclntSharemodeShared = 0;
+    private const long BufferDuration100ns = 2_000_000;
+    private const int ENoInterface = unchecked((int)0x80004002);
+    private static readonly Guid IidAudioClient = new("1CB9AD4C-DBFA-4C32-B178-C2F568A703B2");
+    private static readonly Guid IidAudioEffectsManager = new("4460B3AE-4B44-4527-8676-7548A8ACD260");
+
+    // AUDIO_EFFECT_TYPE_* from ksmedia.h
+    private static readonly Dictionary<Guid, string> EffectNames = new()
+    {
+        [new Guid("6f64adbe-8211-11e2-8c70-2c27d7f001fa")] = "Echo cancellation",
+        [new Guid("6f64adbf-8211-11e2-8c70-2c27d7f001fa")] = "Noise suppression",
+        [new Guid("6f64adc0-8211-11e2-8c70-2c27d7f001fa")] = "Automatic gain control",
+        [new Guid("6f64adc1-8211-11e2-8c70-2c27d7f001fa")] = "Beamforming",
+        [new Guid("6f64adc2-8211-11e2-8c70-2c27d7f001fa")] = "Constant tone removal",
+        [new Guid("6f64adc3-8211-11e2-8c70-2c27d7f001fa")] = "Equalizer",
+        [new Guid("6f64adc4-8211-11e2-8c70-2c27d7f001fa")] = "Loudness equalizer",
+        [new Guid("6f64adc5-8211-11e2-8c70-2c27d7f001fa")] = "Bass boost",
+        [new Guid("6f64adc6-8211-11e2-8c70-2c27d7f001fa")] = "Virtual surround",
+        [new Guid("6f64adc7-8211-11e2-8c70-2c27d7f001fa")] = "Virtual headphones",
+        [new Guid("6f64adc8-8211-11e2-8c70-2c27d7f001fa")] = "Speaker fill",
+        [new Guid("6f64adc9-8211-11e2-8c70-2c27d7f001fa")] = "Room correction",
+        [new Guid("6f64adca-8211-11e2-8c70-2c27d7f001fa")] = "Bass management",
+        [new Guid("6f64adcb-8211-11e2-8c70-2c27d7f001fa")] = "Environmental effects",
+        [new Guid("6f64adcc-8211-11e2-8c70-2c27d7f001fa")] = "Speaker protection",
+        [new Guid("6f64adcd-8211-11e2-8c70-2c27d7f001fa")] = "Speaker compensation",
+        [new Guid("6f64adce-8211-11e2-8c70-2c27d7f001fa")] = "Dynamic range compression",
+        [new Guid("6f64adcf-8211-11e2-8c70-2c27d7f001fa")] = "Far-field beamforming",
+        [new Guid("6f64add0-8211-11e2-8c70-2c27d7f001fa")] = "Deep noise suppression",
+    };
+
+    #region COM interop
+
+    [ComImport]
+    [Guid("BCDE0395-E52F-467C-8E3D-C4579291692E")]
+    private class MMDeviceEnumeratorComObject { }
+
+    [ComImport]
+    [Guid("A95664D2-9614-4F35-A746-DE8DB63617E6")]
+    [InterfaceType(ComInterfaceType.InterfaceIsIUnknown)]
+    private interface IMMDeviceEnumerator
+    {
+        void NotImpl_EnumAudioEndpoints();
+        void NotImpl_GetDefaultAudioEndpoint();
+
+        [PreserveSig]
+        int GetDevice([MarshalAs(UnmanagedType.LPWStr)] string id, out IMMDevice device);
+    }
+
+    [ComImport]
+    [Guid("D666063F-1587-4E43-81F1-B948E807363F")]
+    [InterfaceType(ComInterfaceType.InterfaceIsIUnknown)]
+    private interface IMMDevice
+    {
+        [PreserveSig]
+        int Activate(ref Guid iid, uint clsCtx, IntPtr activationParams, [MarshalAs(UnmanagedType.IUnknown)] out object instance);
+    }
+
+    [ComImport]
+    [Guid("1CB9AD4C-DBFA-4C32-B178-C2F568A703B2")]
+    [InterfaceType(ComInterfaceType.InterfaceIsIUnknown)]
+    private interface IAudioClient
+    {
+        [PreserveSig]
+        int Initialize(int shareMode, uint streamFlags, long bufferDuration, long periodicity, IntPtr format, IntPtr audioSessionGuid);
+
+        void NotImpl_GetBufferSize();
+        void NotImpl_GetStreamLatency();
+        void NotImpl_GetCurrentPadding();
+        void NotImpl_IsFormatSupported();
+
+        // format is a CoTaskMemAlloc'd WAVEFORMATEX owned by the caller
+        [PreserveSig]
+        int GetMixFormat(out IntPtr format);
+
+        void NotImpl_GetDevicePeriod();
+        void NotImpl_Start();
+        void NotImpl_Stop();
+        void NotImpl_Reset();
+        void NotImpl_SetEventHandle();
+
+        [PreserveSig]
+        int GetService(ref Guid iid, [MarshalAs(UnmanagedType.IUnknown)] out object service);
+    }
+
+    [StructLayout(LayoutKind.Sequential)]
+    private struct AudioEffectNative
+    {
+        public Guid Id;
+        public int CanSetState;
+        public int State;
+    }
+
+    [ComImport]
+    [Guid("4460B3AE-4B44-4527-8676-7548A8ACD260")]
+    [InterfaceType(ComInterfaceType.InterfaceIsIUnknown)]
+    private interface IAudioEffectsManager
+    {
+        void NotImpl_RegisterAudioEffectsChangedNotificationCallback();
+        void NotImpl_UnregisterAudioEffectsChangedNotificationCallback();
+
+        // effects is a CoTaskMemAlloc'd AUDIO_EFFECT array owned by the caller
+        [PreserveSig]
+        int GetAudioEffects(out IntPtr effects, out uint numEffects);
+
+        [PreserveSig]
+        int SetAudioEffectState(Guid effectId, int state);
+    }
+
+    #endregion
+
+    public AudioEffectsService(ILogger<AudioEffectsService> logger)
+    {
+        _logger = logger ?? throw new ArgumentNullException(nameof(logger));
+    }
+
+    /// <summary>
+    /// Gets the effects the endpoint's audio processing objects report.
+    /// </summary>
+    /// <returns>The effects, or an empty list when the OS doesn't support effect reporting.</returns>
+    /// <exception cref="COMException">The endpoint or its audio client couldn't be opened.</exception>
+    public List<AudioEffect> GetAudioEffects(string deviceId)
+    {
+        return WithEffectsManager(deviceId, manager =>
+        {
+            Marshal.ThrowExceptionForHR(manager.GetAudioEffects(out var buffer, out var count));
+            try
+            {
+                var effects = new List<AudioEffect>((int)count);
+                var size = Marshal.SizeOf<AudioEffectNative>();
+                for (var i = 0; i < count; i++)
+                {
+                    var native = Marshal.PtrToStructure<AudioEffectNative>(buffer + i * size);
+                    effects.Add(new AudioEffect(native.Id, GetEffectName(native.Id), native.State != 0, native.CanSetState != 0));
+                }
+
+                return effects;
+            }
+            finally
+            {
+                Marshal.FreeCoTaskMem(buffer);
+            }
+        }) ?? new List<AudioEffect>();
+    }
+
+    /// <summary>
+    /// Whether the endpoint exposes IAudioEffectsManager at all (Windows 11 22H2+).
+    /// </summary>
+    public bool IsSupported(string deviceId)
+    {
+        try
+        {
+            return WithEffectsManager(deviceId, _ => (bool?)true) == true;
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Could not open the audio effects manager for {DeviceId}", deviceId);
+            return false;
+        }
+    }
+
+    /// <summary>
+    /// Turns an effect on or off. Fails for effects whose state the driver doesn't let apps set.
+    /// </summary>
+    /// <returns>True if the effects manager accepted the change.</returns>
+    public bool SetAudioEffectEnabled(string deviceId, Guid effectId, bool enabled)
+    {
+        var result = WithEffectsManager(deviceId, manager =>
+        {
+            Marshal.ThrowExceptionForHR(manager.SetAudioEffectState(effectId, enabled ? 1 : 0));
+            _logger.LogInformation("Set {Effect} for {DeviceId} to {Enabled}", GetEffectName(effectId), deviceId, enabled);
+            return (bool?)true;
+        });
+
+        return result == true;
+    }
+
+    public static string GetEffectName(Guid effectId)
+    {
+        return EffectNames.TryGetValue(effectId, out var name) ? name : "Vendor effect";
+    }
+
+    /// <summary>
+    /// Runs <paramref name="action"/> against the endpoint's effects manager. Returns default only
+    /// when the OS has no effects manager; every other failure throws.
+    /// </summary>
+    private static T? WithEffectsManager<T>(string deviceId, Func<IAudioEffectsManager, T?> action)
+    {
+        var comObjects = new List<object>();
+        try
+        {
+            var enumerator = (IMMDeviceEnumerator)new MMDeviceEnumeratorComObject();
+            comObjects.Add(enumerator);
+
+            Marshal.ThrowExceptionForHR(enumerator.GetDevice(deviceId, out var device));
+            comObjects.Add(device);
+
+            var clientIid = IidAudioClient;
+            Marshal.ThrowExceptionForHR(device.Activate(ref clientIid, ClsctxAll, IntPtr.Zero, out var clientObject));
+            comObjects.Add(clientObject);
+            var client = (IAudioClient)clientObject;
+
+            // GetService only works once the client is initialized; the mix format always is accepted
+            Marshal.ThrowExceptionForHR(client.GetMixFormat(out var mixFormat));
+            try
+            {
+                Marshal.ThrowExceptionForHR(client.Initialize(AudclntSharemodeShared, 0, BufferDuration100ns, 0, mixFormat, IntPtr.Zero));
+            }
+            finally
+            {
+                Marshal.FreeCoTaskMem(mixFormat);
+            }
+
+            // E_NOINTERFACE before Windows 11 22H2
+            var managerIid = IidAudioEffectsManager;
+            var hr = client.GetService(ref managerIid, out var instance);
+            if (hr == ENoInterface) return default;
+            Marshal.ThrowExceptionForHR(hr);
+            comObjects.Add(instance);
+
+            return action((IAudioEffectsManager)instance);
+        }
+        finally
+        {
+            foreach (var obj in comObjects)
+            {
+                Marshal.ReleaseComObject(obj);
+            }
+        }
+    }
+}
@@ -0,0 +1,58 @@
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Actionable categories for audio failures, mapped from the originating HRESULT so callers
+/// can decide whether to retry without matching on message text.
+/// </summary>
+public enum AudioErrorCode
+{
+    Unknown,
+    DeviceNotFound,
+    DeviceInvalidated,
+    DeviceInUseExclusive,
+
+    /// <summary>
+    /// The device or a policy forbids exclusive mode; this stays so until the setting is changed.
+    /// </summary>
+    ExclusiveModeNotAllowed,
+    AccessDenied,
+    ServiceNotRunning,
+    UnsupportedFormat,
+    InvalidArgument,
+    Timeout,
+    ComError
+}
+
+public static class AudioErrorCodes
+{
+    private const int E_NOTFOUND = unchecked((int)0x80070490);
+    private const int E_ACCESSDENIED = unchecked((int)0x80070005);
+    private const int E_INVALIDARG = unchecked((int)0x80070057);
+    private const int COR_E_TIMEOUT = unchecked((int)0x80131505);
+    private const int AUDCLNT_E_DEVICE_INVALIDATED = unchecked((int)0x88890004);
+    private const int AUDCLNT_E_UNSUPPORTED_FORMAT = unchecked((int)0x88890008);
+    private const int AUDCLNT_E_DEVICE_IN_USE = unchecked((int)0x8889000A);
+    private const int AUDCLNT_E_SERVICE_NOT_RUNNING = unchecked((int)0x88890010);
+    private const int AUDCLNT_E_EXCLUSIVE_MODE_NOT_ALLOWED = unchecked((int)0x8889000E);
+
+    public static AudioErrorCode FromHResult(int hResult) => hResult switch
+    {
+        0 => AudioErrorCode.Unknown,
+        E_NOTFOUND => AudioErrorCode.DeviceNotFound,
+        AUDCLNT_E_DEVICE_INVALIDATED => AudioErrorCode.DeviceInvalidated,
+        AUDCLNT_E_DEVICE_IN_USE => AudioErrorCode.DeviceInUseExclusive,
+        AUDCLNT_E_EXCLUSIVE_MODE_NOT_ALLOWED => AudioErrorCode.ExclusiveModeNotAllowed,
+        E_ACCESSDENIED => AudioErrorCode.AccessDenied,
+        AUDCLNT_E_SERVICE_NOT_RUNNING => AudioErrorCode.ServiceNotRunning,
+        AUDCLNT_E_UNSUPPORTED_FORMAT => AudioErrorCode.UnsupportedFormat,
+        E_INVALIDARG => AudioErrorCode.InvalidArgument,
+        COR_E_TIMEOUT => AudioErrorCode.Timeout,
+        _ => AudioErrorCode.ComError
+    };
+
+    /// <summary>
+    /// True for failures that usually clear up on their own (device churn, busy worker).
+    /// </summary>
+    public static bool IsTransient(AudioErrorCode code) =>
+        code is AudioErrorCode.DeviceInvalidated or AudioErrorCode.Timeout or AudioErrorCode.DeviceInUseExclusive;
+}
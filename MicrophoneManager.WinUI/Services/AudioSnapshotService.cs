@@ -0,0 +1,102 @@
+using MicrophoneManager.WinUI.Models;
+using NAudio.CoreAudioApi;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Takes and restores <see cref="AudioSnapshot"/>s using the audio service's primitives.
+/// Microphones are recorded by <see cref="DevicePriorityService.KeyFor"/>, so a saved profile still
+/// finds them after a driver reinstall gives them new endpoint IDs.
+/// </summary>
+public class AudioSnapshotService
+{
+    private readonly IAudioDeviceService _audioService;
+
+    public AudioSnapshotService(IAudioDeviceService audioService)
+    {
+        _audioService = audioService ?? throw new ArgumentNullException(nameof(audioService));
+    }
+
+    public AudioSnapshot TakeSnapshot()
+    {
+        var microphones = _audioService.GetMicrophones();
+        return new AudioSnapshot
+        {
+            DefaultConsoleId = KeyOf(microphones, _audioService.GetDefaultDeviceId(Role.Console)),
+            DefaultCommunicationsId = KeyOf(microphones, _audioService.GetDefaultDeviceId(Role.Communications)),
+            DefaultOutputId = _audioService.GetDefaultOutputDeviceId(Role.Console),
+            Devices = microphones
+                .Select(m => new DeviceSnapshot
+                {
+                    DeviceId = DevicePriorityService.KeyFor(m),
+                    VolumeLevel = m.VolumeLevel,
+                    IsMuted = m.IsMuted,
+                    BoostDb = m.Boost?.LevelDb
+                })
+                .ToList()
+        };
+    }
+
+    /// <summary>
+    /// Restores defaults and per-device state. Devices that are no longer present are skipped.
+    /// </summary>
+    /// <returns>True if every applicable setting was restored.</returns>
+    public async Task<bool> RestoreSnapshotAsync(AudioSnapshot snapshot, CancellationToken cancellationToken = default)
+    {
+        var microphones = _audioService.GetMicrophones();
+        var success = true;
+
+        var console = Resolve(microphones, snapshot.DefaultConsoleId);
+        if (console != null && _audioService.GetDefaultDeviceId(Role.Console) != console)
+        {
+            success &= await _audioService.SetMicrophoneForRoleAsync(console, Role.Console, cancellationToken);
+        }
+
+        var communications = Resolve(microphones, snapshot.DefaultCommunicationsId);
+        if (communications != null && _audioService.GetDefaultDeviceId(Role.Communications) != communications)
+        {
+            success &= await _audioService.SetMicrophoneForRoleAsync(communications, Role.Communications, cancellationToken);
+        }
+
+        if (snapshot.DefaultOutputId != null &&
+            _audioService.GetDefaultOutputDeviceId(Role.Console) != snapshot.DefaultOutputId &&
+            _audioService.GetOutputDevices().Any(o => o.Id == snapshot.DefaultOutputId))
+        {
+            success &= await _audioService.SetDefaultOutputDeviceAsync(snapshot.DefaultOutputId, cancellationToken);
+        }
+
+        foreach (var device in snapshot.Devices)
+        {
+            var id = Resolve(microphones, device.DeviceId);
+            if (id == null) continue;
+
+            _audioService.SetMicrophoneVolumeLevelScalar(id, device.VolumeLevel);
+            success &= _audioService.SetMute(id, device.IsMuted);
+
+            if (device.BoostDb is float boostDb)
+            {
+                success &= _audioService.SetMicrophoneBoost(id, boostDb);
+            }
+        }
+
+        return success;
+    }
+
+    private static string? KeyOf(IEnumerable<MicrophoneDevice> microphones, string? deviceId)
+    {
+        var device = microphones.FirstOrDefault(m => m.Id == deviceId);
+        return device != null ? DevicePriorityService.KeyFor(device) : deviceId;
+    }
+
+    /// <summary>
+    /// Endpoint ID of the connected microphone a snapshot refers to. Snapshots taken before they
+    /// were keyed by stable ID hold endpoint IDs, which still match.
+    /// </summary>
+    private static string? Resolve(IEnumerable<MicrophoneDevice> microphones, string? key)
+    {
+        if (key == null) return null;
+
+        return microphones.FirstOrDefault(m => string.Equals(DevicePriorityService.KeyFor(m), key, StringComparison.OrdinalIgnoreCase))?.Id
+            ?? microphones.FirstOrDefault(m => m.Id == key)?.Id;
+    }
+}
@@ -0,0 +1,154 @@
+using MicrophoneManager.WinUI.Models;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Measures a microphone while the user speaks normally and works out the volume, and if needed
+/// the boost, that brings the average speaking level to <see cref="TargetAverageDb"/>. Levels come
+/// from the meter capture the audio service already runs for every microphone, so nothing extra
+/// opens the device. The average and the noise floor come from each buffer's RMS, which is how loud it
+/// sounds; peaks only set the ceiling that keeps the loudest one a few dB clear of clipping.
+/// </summary>
+public sealed class CalibrationService
+{
+    public const double TargetAverageDb = -18;
+
+    public static readonly TimeSpan DefaultDuration = TimeSpan.FromSeconds(10);
+
+    // Loudest peak allowed after the change
+    private const double PeakCeilingDb = -3;
+
+    // Frames this far above the noise floor count as speech
+    private const double SpeechAboveFloorDb = 10;
+
+    // An average below this is background noise, not someone talking
+    private const double MinimumSpeechDb = -60;
+
+    private readonly IAudioDeviceService _audioService;
+
+    public CalibrationService(IAudioDeviceService audioService)
+    {
+        _audioService = audioService ?? throw new ArgumentNullException(nameof(audioService));
+    }
+
+    /// <summary>
+    /// Collects the device's levels for <paramref name="duration"/> and analyses them.
+    /// </summary>
+    /// <param name="progress">Receives each level as it arrives, for a live meter.</param>
+    /// <returns>The result, or null when the device is gone or muted.</returns>
+    public async Task<CalibrationResult?> MeasureAsync(string deviceId, TimeSpan duration, IProgress<double>? progress = null, CancellationToken cancellationToken = default)
+    {
+        if (_audioService.GetMicrophone(deviceId) == null || _audioService.IsMuted(deviceId)) return null;
+
+        var rms = new List<double>();
+        var peaks = new List<double>();
+        void OnLevel(object? sender, AudioDeviceService.MicrophoneInputLevelChangedEventArgs e)
+        {
+            if (e.DeviceId != deviceId) return;
+
+            lock (rms)
+            {
+                rms.Add(e.RmsDbFs);
+                peaks.Add(e.InputLevelDbFs);
+            }
+            progress?.Report(e.InputLevelDbFs);
+        }
+
+        _audioService.MicrophoneInputLevelChanged += OnLevel;
+        try
+        {
+            await Task.Delay(duration, cancellationToken);
+        }
+        finally
+        {
+            _audioService.MicrophoneInputLevelChanged -= OnLevel;
+        }
+
+        List<double> measuredRms;
+        List<double> measuredPeaks;
+        lock (rms)
+        {
+            measuredRms = rms.ToList();
+            measuredPeaks = peaks.ToList();
+        }
+
+        return Analyze(
+            measuredRms,
+            measuredPeaks,
+            _audioService.GetVolumeDb(deviceId) ?? 0f,
+            _audioService.GetVolumeRange(deviceId),
+            _audioService.GetMicrophoneBoost(deviceId));
+    }
+
+    /// <summary>
+    /// Works out the recommendation from per-buffer RMS and peak levels in dBFS.
+    /// </summary>
+    public static CalibrationResult Analyze(IReadOnlyList<double> rmsDb, IReadOnlyList<double> peaksDb, float currentVolumeDb, VolumeRange? range, MicrophoneBoost? boost)
+    {
+        if (rmsDb.Count == 0)
+        {
+            return new CalibrationResult(-96, -96, -96, currentVolumeDb, currentVolumeDb, boost?.LevelDb, boost?.LevelDb) { HeardSpeech = false };
+        }
+
+        var sorted = rmsDb.Order().ToList();
+        var noiseFloor = sorted[(int)(sorted.Count * 0.1)];
+        var peak = peaksDb.Count > 0 ? peaksDb.Max() : sorted[^1];
+
+        // Average power over the frames with speech in them, so pauses don't drag it down
+        var speech = sorted.Where(db => db >= noiseFloor + SpeechAboveFloorDb).ToList();
+        var average = speech.Count > 0
+            ? 10 * Math.Log10(speech.Average(db => Math.Pow(10, db / 10)))
+            : noiseFloor;
+
+        if (speech.Count == 0 || average < MinimumSpeechDb)
+        {
+            return new CalibrationResult(average, peak, noiseFloor, currentVolumeDb, currentVolumeDb, boost?.LevelDb, boost?.LevelDb) { HeardSpeech = false };
+        }
+
+        var gain = Math.Min(TargetAverageDb - average, PeakCeilingDb - peak);
+        var volume = currentVolumeDb + gain;
+        var boostLevel = boost?.LevelDb;
+
+        // Beyond what the volume can do: move the boost in whole steps, then trim with the volume
+        if (range != null && boost != null && boost.StepDb > 0)
+        {
+            var excess = volume > range.MaxDb ? volume - range.MaxDb : volume < range.MinDb ? volume - range.MinDb : 0;
+            if (excess != 0)
+            {
+                var steps = excess > 0 ? Math.Ceiling(excess / boost.StepDb) : Math.Floor(excess / boost.StepDb);
+                var newBoost = (float)Math.Clamp(boost.LevelDb + steps * boost.StepDb, boost.MinDb, boost.MaxDb);
+                volume -= newBoost - boost.LevelDb;
+                boostLevel = newBoost;
+            }
+        }
+
+        if (range != null)
+        {
+            volume = Math.Clamp(volume, range.MinDb, range.MaxDb);
+        }
+
+        return new CalibrationResult(
+            average,
+            peak,
+            noiseFloor,
+            currentVolumeDb,
+            (float)(Math.Round(volume * 2) / 2),
+            boost?.LevelDb,
+            boostLevel);
+    }
+
+    /// <summary>
+    /// Sets the recommended boost and volume.
+    /// </summary>
+    public bool Apply(string deviceId, CalibrationResult result)
+    {
+        if (!result.IsChangeRecommended) return true;
+
+        if (result.ChangesBoost && !_audioService.SetMicrophoneBoost(deviceId, result.RecommendedBoostDb!.Value))
+        {
+            return false;
+        }
+
+        return !result.ChangesVolume || _audioService.SetVolumeDb(deviceId, result.RecommendedVolumeDb);
+    }
+}
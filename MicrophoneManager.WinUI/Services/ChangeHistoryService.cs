@@ -0,0 +1,337 @@
+using System.Globalization;
+using System.Text;
+using System.Text.Json;
+using Microsoft.Extensions.Logging;
+using Microsoft.Extensions.Logging.Abstractions;
+using MicrophoneManager.WinUI.Models;
+using NAudio.CoreAudioApi;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Keeps the last <see cref="Capacity"/> default-device, volume and mute changes, whether they were
+/// made through this app or by something else, in <c>ChangeHistory.jsonl</c> beside the logs.
+/// Answers "what changed my mic?" after the fact; nothing is ever reverted from here.
+/// </summary>
+public sealed class ChangeHistoryService : IDisposable
+{
+    public const int Capacity = 500;
+    public const string FileName = "ChangeHistory.jsonl";
+
+    // A slider drag is one change, not one entry per notification
+    private static readonly TimeSpan CoalesceWindow = TimeSpan.FromSeconds(2);
+
+    // Writes are batched so a burst of changes costs one rewrite of the file
+    private static readonly TimeSpan SaveDelay = TimeSpan.FromSeconds(1);
+
+    private readonly IAudioDeviceService _audioService;
+    private readonly string? _filePath;
+    private readonly Func<DateTime> _clock;
+    private readonly Func<string?> _culpritResolver;
+    private readonly ILogger<ChangeHistoryService> _logger;
+    private readonly LinkedList<ChangeHistoryEntry> _entries = new();
+    private readonly Dictionary<string, (int VolumePercent, bool IsMuted)> _deviceStates = new(StringComparer.OrdinalIgnoreCase);
+    private readonly Dictionary<string, string> _deviceNames = new(StringComparer.OrdinalIgnoreCase);
+    private readonly object _lock = new();
+    private string? _consoleDefaultId;
+    private string? _communicationsDefaultId;
+    private Timer? _saveTimer;
+    private bool _disposed;
+
+    /// <summary>
+    /// Raised after an entry is added or updated, or the history is cleared.
+    /// </summary>
+    public event EventHandler? HistoryChanged;
+
+    public ChangeHistoryService(IAudioDeviceService audioService, ILogger<ChangeHistoryService>? logger = null)
+        : this(audioService, Path.Combine(RollingFileLoggerProvider.DefaultDirectory, FileName), () => DateTime.UtcNow, ProcessInfoResolver.GetForegroundProcessName, logger)
+    {
+    }
+
+    /// <param name="filePath">Where the history is kept; null keeps it in memory only.</param>
+    public ChangeHistoryService(IAudioDeviceService audioService, string? filePath, Func<DateTime> clock, Func<string?> culpritResolver, ILogger<ChangeHistoryService>? logger = null)
+    {
+        _audioService = audioService ?? throw new ArgumentNullException(nameof(audioService));
+        _filePath = filePath;
+        _clock = clock ?? throw new ArgumentNullException(nameof(clock));
+        _culpritResolver = culpritResolver ?? throw new ArgumentNullException(nameof(culpritResolver));
+        _logger = logger ?? NullLogger<ChangeHistoryService>.Instance;
+
+        Load();
+
+        // Changes are measured from here; what happened while the app wasn't running isn't known
+        _consoleDefaultId = _audioService.GetDefaultDeviceId(Role.Console);
+        _communicationsDefaultId = _audioService.GetDefaultDeviceId(Role.Communications);
+        foreach (var mic in _audioService.GetMicrophones())
+        {
+            _deviceNames[mic.Id] = mic.Name;
+            _deviceStates[mic.Id] = (ToPercent(mic.VolumeLevel), mic.IsMuted);
+        }
+
+        _audioService.DefaultDeviceChanged += OnDefaultDeviceChanged;
+        _audioService.MicrophoneVolumeChanged += OnMicrophoneVolumeChanged;
+    }
+
+    public string? FilePath => _filePath;
+
+    /// <summary>
+    /// Recorded changes, newest first.
+    /// </summary>
+    /// <param name="sinceUtc">Only changes after this time.</param>
+    /// <param name="limit">At most this many.</param>
+    public IReadOnlyList<ChangeHistoryEntry> GetEntries(DateTime? sinceUtc = null, int? limit = null)
+    {
+        lock (_lock)
+        {
+            IEnumerable<ChangeHistoryEntry> entries = _entries;
+            if (sinceUtc is { } since) entries = entries.TakeWhile(e => e.Timestamp > since);
+            if (limit is { } count) entries = entries.Take(Math.Max(0, count));
+            return entries.ToList();
+        }
+    }
+
+    public void Clear()
+    {
+        lock (_lock)
+        {
+            _entries.Clear();
+        }
+
+        ScheduleSave();
+        HistoryChanged?.Invoke(this, EventArgs.Empty);
+    }
+
+    /// <summary>
+    /// Writes any pending changes to the file now.
+    /// </summary>
+    public void Flush()
+    {
+        lock (_lock)
+        {
+            _saveTimer?.Dispose();
+            _saveTimer = null;
+        }
+
+        Save();
+    }
+
+    private void OnDefaultDeviceChanged(object? sender, EventArgs e)
+    {
+        if (_disposed) return;
+
+        try
+        {
+            var consoleId = _audioService.GetDefaultDeviceId(Role.Console);
+            var communicationsId = _audioService.GetDefaultDeviceId(Role.Communications);
+
+            RecordDefault(ChangeHistoryKind.DefaultDevice, ref _consoleDefaultId, consoleId);
+            RecordDefault(ChangeHistoryKind.DefaultCommunicationsDevice, ref _communicationsDefaultId, communicationsId);
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Recording default device change failed");
+        }
+    }
+
+    private void RecordDefault(ChangeHistoryKind kind, ref string? previousId, string? newId)
+    {
+        if (newId == null || string.Equals(previousId, newId, StringComparison.OrdinalIgnoreCase)) return;
+
+        var oldName = previousId != null ? NameFor(previousId) : null;
+        previousId = newId;
+
+        // Only console-role requests are tracked, but choosing a mic in the app sets both roles at once
+        var source = newId == _audioService.LastRequestedDefaultId ? ChangeHistorySource.App : ChangeHistorySource.External;
+        var name = NameFor(newId);
+        Add(new ChangeHistoryEntry(_clock(), kind, source, newId, name, oldName, name, CulpritFor(source)));
+    }
+
+    private void OnMicrophoneVolumeChanged(object? sender, AudioDeviceService.MicrophoneVolumeChangedEventArgs e)
+    {
+        if (_disposed) return;
+
+        try
+        {
+            var percent = ToPercent(e.VolumeLevelScalar);
+            var found = _deviceStates.TryGetValue(e.DeviceId, out var previous);
+            _deviceStates[e.DeviceId] = (percent, e.IsMuted);
+
+            // The first notification for a device that appeared later is its starting point
+            if (!found) return;
+
+            var source = e.ChangedExternally ? ChangeHistorySource.External : ChangeHistorySource.App;
+            var name = NameFor(e.DeviceId);
+
+            if (previous.IsMuted != e.IsMuted)
+            {
+                Add(new ChangeHistoryEntry(_clock(), ChangeHistoryKind.Mute, source, e.DeviceId, name,
+                    previous.IsMuted ? "Muted" : "Unmuted", e.IsMuted ? "Muted" : "Unmuted", CulpritFor(source)));
+            }
+
+            if (previous.VolumePercent != percent)
+            {
+                AddVolume(new ChangeHistoryEntry(_clock(), ChangeHistoryKind.Volume, source, e.DeviceId, name,
+                    FormatPercent(previous.VolumePercent), FormatPercent(percent), CulpritFor(source)));
+            }
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Recording volume change failed");
+        }
+    }
+
+    private void AddVolume(ChangeHistoryEntry entry)
+    {
+        lock (_lock)
+        {
+            if (_entries.First?.Value is { Kind: ChangeHistoryKind.Volume } latest
+                && latest.Source == entry.Source
+                && string.Equals(latest.DeviceId, entry.DeviceId, StringComparison.OrdinalIgnoreCase)
+                && entry.Timestamp - latest.Timestamp <= CoalesceWindow)
+            {
+                _entries.RemoveFirst();
+
+                // Dragged away and back again: nothing changed
+                if (latest.OldValue != entry.NewValue)
+                {
+                    _entries.AddFirst(entry with { OldValue = latest.OldValue, Culprit = latest.Culprit ?? entry.Culprit });
+                }
+            }
+            else
+            {
+                AddLocked(entry);
+            }
+        }
+
+        ScheduleSave();
+        HistoryChanged?.Invoke(this, EventArgs.Empty);
+    }
+
+    private void Add(ChangeHistoryEntry entry)
+    {
+        lock (_lock)
+        {
+            AddLocked(entry);
+        }
+
+        ScheduleSave();
+        HistoryChanged?.Invoke(this, EventArgs.Empty);
+    }
+
+    private void AddLocked(ChangeHistoryEntry entry)
+    {
+        _entries.AddFirst(entry);
+        while (_entries.Count > Capacity)
+        {
+            _entries.RemoveLast();
+        }
+    }
+
+    private string? CulpritFor(ChangeHistorySource source)
+    {
+        return source == ChangeHistorySource.External ? _culpritResolver() : null;
+    }
+
+    private string NameFor(string deviceId)
+    {
+        var name = _audioService.GetMicrophone(deviceId)?.Name;
+        if (name != null)
+        {
+            _deviceNames[deviceId] = name;
+            return name;
+        }
+
+        // Unplugged devices keep the name they had
+        return _deviceNames.TryGetValue(deviceId, out var known) ? known : deviceId;
+    }
+
+    private static int ToPercent(float volumeLevelScalar) => (int)Math.Round(volumeLevelScalar * 100);
+
+    private static string FormatPercent(int percent) => string.Create(CultureInfo.InvariantCulture, $"{percent}%");
+
+    private void Load()
+    {
+        if (_filePath == null || !File.Exists(_filePath)) return;
+
+        try
+        {
+            foreach (var line in File.ReadLines(_filePath))
+            {
+                if (string.IsNullOrWhiteSpace(line)) continue;
+
+                try
+                {
+                    if (JsonSerializer.Deserialize<ChangeHistoryEntry>(line, ControlJson.Options) is { } entry)
+                    {
+                        AddLocked(entry);
+                    }
+                }
+                catch (JsonException)
+                {
+                    // A line cut short by a crash; the rest are still good
+                }
+            }
+        }
+        catch (IOException ex)
+        {
+            _logger.LogWarning(ex, "Reading change history failed");
+        }
+    }
+
+    private void ScheduleSave()
+    {
+        if (_filePath == null) return;
+
+        lock (_lock)
+        {
+            if (_disposed) return;
+
+            _saveTimer?.Dispose();
+            _saveTimer = new Timer(_ => Save(), null, SaveDelay, Timeout.InfiniteTimeSpan);
+        }
+    }
+
+    private void Save()
+    {
+        if (_filePath == null) return;
+
+        try
+        {
+            var builder = new StringBuilder();
+            lock (_lock)
+            {
+                // Oldest first, so the file reads like a log
+                for (var node = _entries.Last; node != null; node = node.Previous)
+                {
+                    builder.AppendLine(JsonSerializer.Serialize(node.Value, ControlJson.Options));
+                }
+            }
+
+            Directory.CreateDirectory(Path.GetDirectoryName(_filePath)!);
+            File.WriteAllText(_filePath, builder.ToString());
+        }
+        catch (Exception ex) when (ex is IOException or UnauthorizedAccessException)
+        {
+            _logger.LogWarning(ex, "Saving change history failed");
+        }
+    }
+
+    public void Dispose()
+    {
+        if (_disposed) return;
+
+        try { _audioService.DefaultDeviceChanged -= OnDefaultDeviceChanged; } catch { }
+        try { _audioService.MicrophoneVolumeChanged -= OnMicrophoneVolumeChanged; } catch { }
+
+        bool pending;
+        lock (_lock)
+        {
+            _disposed = true;
+            pending = _saveTimer != null;
+            _saveTimer?.Dispose();
+            _saveTimer = null;
+        }
+
+        if (pending) Save();
+    }
+}
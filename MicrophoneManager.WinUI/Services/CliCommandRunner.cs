@@ -0,0 +1,144 @@
+using System.Text.Json;
+using MicrophoneManager.WinUI.Models;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Executes a <see cref="CliCommand"/> through an <see cref="IControlChannel"/> and reports through
+/// text writers. The command line uses the running tray's pipe when there is one and a local
+/// dispatcher otherwise, so scripts get the same behaviour either way.
+/// </summary>
+public sealed class CliCommandRunner
+{
+    public const int ExitSuccess = 0;
+    public const int ExitFailed = 1;
+    public const int ExitUsage = 2;
+    public const int ExitDeviceNotFound = 3;
+
+    private static readonly JsonSerializerOptions JsonOptions = new()
+    {
+        PropertyNamingPolicy = JsonNamingPolicy.CamelCase,
+        WriteIndented = true,
+    };
+
+    private readonly IControlChannel _channel;
+    private readonly TextWriter _output;
+    private readonly TextWriter _error;
+
+    public CliCommandRunner(IControlChannel channel, TextWriter output, TextWriter error)
+    {
+        _channel = channel ?? throw new ArgumentNullException(nameof(channel));
+        _output = output ?? throw new ArgumentNullException(nameof(output));
+        _error = error ?? throw new ArgumentNullException(nameof(error));
+    }
+
+    /// <summary>
+    /// Runs commands straight against an audio service, without a tray. With an
+    /// <paramref name="aliasService"/>, devices are listed and reported by their aliases as the tray does.
+    /// </summary>
+    public CliCommandRunner(IAudioDeviceService audioService, TextWriter output, TextWriter error, DeviceAliasService? aliasService = null)
+        : this(new ControlCommandDispatcher(audioService ?? throw new ArgumentNullException(nameof(audioService)), aliasService: aliasService), output, error)
+    {
+    }
+
+    /// <summary>
+    /// Runs the command and returns the process exit code.
+    /// </summary>
+    public int Run(CliCommand command) => RunAsync(command).GetAwaiter().GetResult();
+
+    public async Task<int> RunAsync(CliCommand command)
+    {
+        switch (command.Action)
+        {
+            case CliAction.Help:
+                _output.WriteLine(CliCommand.Usage);
+                return ExitSuccess;
+            case CliAction.List:
+                return await ListAsync(command.Json);
+        }
+
+        var listed = await _channel.DispatchAsync(new ControlRequest { Command = "list" });
+        if (listed.Data is not IReadOnlyList<ControlDevice> devices)
+        {
+            _error.WriteLine(listed.Error ?? "Couldn't list microphones.");
+            return ExitFailed;
+        }
+
+        var target = ResolveTarget(devices, command.Device);
+        if (target == null)
+        {
+            _error.WriteLine(command.Device != null
+                ? $"No microphone matches '{command.Device}'."
+                : "There is no default microphone.");
+            return ExitDeviceNotFound;
+        }
+
+        var requestCommand = command.Action switch
+        {
+            CliAction.Mute => "mute",
+            CliAction.Unmute => "unmute",
+            CliAction.Toggle => "toggleMute",
+            CliAction.SetDefault => "setDefault",
+            _ => null,
+        };
+        if (requestCommand == null)
+        {
+            _error.WriteLine($"Unsupported command {command.Action}.");
+            return ExitUsage;
+        }
+
+        // By ID, so the tray acts on exactly the device named here
+        var response = await _channel.DispatchAsync(new ControlRequest { Command = requestCommand, Device = target.Id });
+        if (!response.Ok)
+        {
+            _error.WriteLine(response.Error ?? $"Couldn't {command.Action} {target.DisplayName}.");
+            return ExitFailed;
+        }
+
+        var after = (response.Data as ControlState)?.Devices.FirstOrDefault(d => d.Id == target.Id) ?? target;
+        _output.WriteLine(command.Action switch
+        {
+            CliAction.SetDefault => $"{target.DisplayName} is now the default microphone.",
+            _ => $"{target.DisplayName} is {(after.IsMuted ? "muted" : "unmuted")}.",
+        });
+        return ExitSuccess;
+    }
+
+    /// <summary>
+    /// The default microphone, or the one <paramref name="pattern"/> names: an exact alias first,
+    /// then the same ID and name matching as everywhere else.
+    /// </summary>
+    private static ControlDevice? ResolveTarget(IReadOnlyList<ControlDevice> devices, string? pattern)
+    {
+        if (pattern == null) return devices.FirstOrDefault(d => d.IsDefault);
+
+        return devices.FirstOrDefault(d => string.Equals(d.DisplayName, pattern.Trim(), StringComparison.OrdinalIgnoreCase) && d.DisplayName != d.Name)
+            ?? DeviceLookup.FindMatch(devices, d => d.Id, d => d.Name, pattern);
+    }
+
+    private async Task<int> ListAsync(bool json)
+    {
+        var response = await _channel.DispatchAsync(new ControlRequest { Command = "list" });
+        if (response.Data is not IReadOnlyList<ControlDevice> devices)
+        {
+            _error.WriteLine(response.Error ?? "Couldn't list microphones.");
+            return ExitFailed;
+        }
+
+        if (json)
+        {
+            // Same shape the control pipe answers to "list"
+            _output.WriteLine(JsonSerializer.Serialize(devices, JsonOptions));
+            return ExitSuccess;
+        }
+
+        foreach (var device in devices)
+        {
+            var marker = device.IsDefault ? "*" : device.IsDefaultCommunications ? "c" : " ";
+            var muted = device.IsMuted ? " (muted)" : string.Empty;
+            _output.WriteLine($"{marker} {device.DisplayName} - {device.VolumePercent:0}%{muted}");
+        }
+
+        return ExitSuccess;
+    }
+}
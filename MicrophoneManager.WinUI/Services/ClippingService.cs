@@ -0,0 +1,199 @@
+using Microsoft.Extensions.Logging;
+using Microsoft.Extensions.Logging.Abstractions;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Counts how often the default microphone's peaks reach full scale. A loud burst clips on many
+/// consecutive buffers, so clips closer together than <see cref="ClipHold"/> count once. Several
+/// clips within a few seconds raise <see cref="ClippingAlert"/>, at most once every few minutes,
+/// so the tray can suggest lowering the gain and hooks can react. Counts are kept per device until reset or restart.
+/// </summary>
+public sealed class ClippingService : IDisposable
+{
+    public const string AlertPreference = "ClipAlert";
+
+    /// <summary>
+    /// Peaks at or above this count as clipping. Just under 0 dBFS, since a converter that clips
+    /// rarely reports the exact full-scale value.
+    /// </summary>
+    public const double ClipThresholdDb = -0.5;
+
+    public static readonly TimeSpan ClipHold = TimeSpan.FromMilliseconds(250);
+
+    private const int AlertClipCount = 3;
+    private static readonly TimeSpan AlertWindow = TimeSpan.FromSeconds(10);
+    private static readonly TimeSpan AlertCooldown = TimeSpan.FromMinutes(5);
+
+    private readonly IAudioDeviceService _audioService;
+    private readonly IPreferencesStore _preferences;
+    private readonly Func<DateTime> _clock;
+    private readonly Dictionary<string, int> _counts = new(StringComparer.OrdinalIgnoreCase);
+    private readonly Queue<DateTime> _recentClips = new();
+    private readonly SynchronizationContext? _syncContext;
+    private readonly object _clipLock = new();
+    private readonly ILogger<ClippingService> _logger;
+    private string? _defaultId;
+    private string _defaultName = string.Empty;
+    private DateTime _lastClip = DateTime.MinValue;
+    private DateTime? _lastAlert;
+    private bool _disposed;
+
+    /// <summary>
+    /// Raised for every counted clip, on the thread the service was created on (the UI thread in the app).
+    /// </summary>
+    public event EventHandler<ClipEventArgs>? Clipped;
+
+    /// <summary>
+    /// Raised when the default microphone clips repeatedly, at most once per cooldown. Whether to tell
+    /// the user is up to the subscriber; see <see cref="IsAlertEnabled"/>.
+    /// </summary>
+    public event EventHandler<ClipEventArgs>? ClippingAlert;
+
+    /// <summary>
+    /// Raised when a count is reset.
+    /// </summary>
+    public event EventHandler? CountsReset;
+
+    public ClippingService(IAudioDeviceService audioService, IPreferencesStore preferences, ILogger<ClippingService>? logger = null)
+        : this(audioService, preferences, () => DateTime.UtcNow, logger)
+    {
+    }
+
+    public ClippingService(IAudioDeviceService audioService, IPreferencesStore preferences, Func<DateTime> clock, ILogger<ClippingService>? logger = null)
+    {
+        _audioService = audioService ?? throw new ArgumentNullException(nameof(audioService));
+        _preferences = preferences ?? throw new ArgumentNullException(nameof(preferences));
+        _clock = clock ?? throw new ArgumentNullException(nameof(clock));
+        _logger = logger ?? NullLogger<ClippingService>.Instance;
+        _syncContext = SynchronizationContext.Current;
+
+        UpdateDefault();
+
+        _audioService.DefaultDeviceChanged += OnDefaultDeviceChanged;
+        _audioService.MicrophoneInputLevelChanged += OnMicrophoneInputLevelChanged;
+    }
+
+    /// <summary>
+    /// Whether the tray shows a notification on <see cref="ClippingAlert"/>.
+    /// </summary>
+    public bool IsAlertEnabled
+    {
+        get => _preferences.GetBool(AlertPreference, true);
+        set => _preferences.SetBool(AlertPreference, value);
+    }
+
+    public int GetClipCount(string deviceId)
+    {
+        return _counts.TryGetValue(deviceId, out var count) ? count : 0;
+    }
+
+    public void ResetCount(string deviceId)
+    {
+        if (!_counts.Remove(deviceId)) return;
+
+        if (string.Equals(deviceId, _defaultId, StringComparison.OrdinalIgnoreCase))
+        {
+            _recentClips.Clear();
+        }
+
+        CountsReset?.Invoke(this, EventArgs.Empty);
+    }
+
+    private void OnMicrophoneInputLevelChanged(object? sender, AudioDeviceService.MicrophoneInputLevelChangedEventArgs e)
+    {
+        // Level updates arrive on the capture thread for every microphone ~120 times a second; only
+        // the first clip of a burst on the default goes on to the UI thread
+        if (_disposed || e.InputLevelDbFs < ClipThresholdDb) return;
+        if (!string.Equals(e.DeviceId, Volatile.Read(ref _defaultId), StringComparison.OrdinalIgnoreCase)) return;
+
+        var now = _clock();
+        lock (_clipLock)
+        {
+            var burst = now - _lastClip < ClipHold;
+            _lastClip = now;
+            if (burst) return;
+        }
+
+        if (_syncContext != null)
+        {
+            _syncContext.Post(_ => CountClip(e.DeviceId, now), null);
+        }
+        else
+        {
+            CountClip(e.DeviceId, now);
+        }
+    }
+
+    private void CountClip(string deviceId, DateTime now)
+    {
+        if (_disposed) return;
+
+        var count = GetClipCount(deviceId) + 1;
+        _counts[deviceId] = count;
+
+        var args = new ClipEventArgs(deviceId, _defaultName, count);
+        Clipped?.Invoke(this, args);
+
+        _recentClips.Enqueue(now);
+        while (_recentClips.Count > 0 && now - _recentClips.Peek() > AlertWindow)
+        {
+            _recentClips.Dequeue();
+        }
+
+        if (_recentClips.Count < AlertClipCount) return;
+        if (_lastAlert is { } lastAlert && now - lastAlert < AlertCooldown) return;
+
+        _lastAlert = now;
+        _recentClips.Clear();
+        ClippingAlert?.Invoke(this, args);
+    }
+
+    private void OnDefaultDeviceChanged(object? sender, EventArgs e)
+    {
+        UpdateDefault();
+    }
+
+    private void UpdateDefault()
+    {
+        try
+        {
+            var device = _audioService.GetDefaultMicrophone();
+            Volatile.Write(ref _defaultId, device?.Id);
+            _defaultName = device?.Name ?? string.Empty;
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Reading the default microphone for clip counting failed");
+        }
+
+        _recentClips.Clear();
+    }
+
+    public void Dispose()
+    {
+        if (_disposed) return;
+        _disposed = true;
+
+        try { _audioService.DefaultDeviceChanged -= OnDefaultDeviceChanged; } catch { }
+        try { _audioService.MicrophoneInputLevelChanged -= OnMicrophoneInputLevelChanged; } catch { }
+    }
+
+    public sealed class ClipEventArgs : EventArgs
+    {
+        public ClipEventArgs(string deviceId, string deviceName, int clipCount)
+        {
+            DeviceId = deviceId;
+            DeviceName = deviceName;
+            ClipCount = clipCount;
+        }
+
+        public string DeviceId { get; }
+        public string DeviceName { get; }
+
+        /// <summary>
+        /// Clips counted on the device so far, including this one.
+        /// </summary>
+        public int ClipCount { get; }
+    }
+}
@@ -1,6 +1,10 @@
 using System;
 using System.Collections.Concurrent;
+using System.Diagnostics;
+using System.Runtime.CompilerServices;
 using System.Threading;
+using Microsoft.Extensions.Logging;
+using Microsoft.Extensions.Logging.Abstractions;
 
 namespace MicrophoneManager.WinUI.Services;
 
@@ -14,10 +18,20 @@ public class ComThreadService : IDisposable
     private readonly Thread _comThread;
     private readonly BlockingCollection<WorkItem> _workQueue;
     private readonly CancellationTokenSource _shutdownToken;
+    private readonly ILogger<ComThreadService> _logger;
     private volatile bool _disposed;
 
+    // Anything slower than this holds up every queued call behind it, so it's worth a warning
+    private static readonly TimeSpan SlowCallThreshold = TimeSpan.FromSeconds(1);
+
     public ComThreadService()
+        : this(NullLogger<ComThreadService>.Instance)
+    {
+    }
+
+    public ComThreadService(ILogger<ComThreadService> logger)
     {
+        _logger = logger ?? throw new ArgumentNullException(nameof(logger));
         _workQueue = new BlockingCollection<WorkItem>();
         _shutdownToken = new CancellationTokenSource();
 
@@ -30,60 +44,135 @@ public class ComThreadService : IDisposable
         _comThread.Start();
     }
 
+    /// <summary>
+    /// Default deadline for callers that use <see cref="InvokeWithDeadlineAsync{T}"/> without an explicit timeout.
+    /// COM calls during device churn (e.g. a Bluetooth headset reconnecting) can block for seconds.
+    /// </summary>
+    public TimeSpan DefaultTimeout { get; set; } = TimeSpan.FromSeconds(5);
+
     /// <summary>
     /// Invokes an action on the COM thread and returns a task that completes when the action finishes.
     /// </summary>
-    public Task InvokeAsync(Action action)
+    public Task InvokeAsync(Action action, [CallerMemberName] string operation = "")
     {
-        ObjectDisposedException.ThrowIf(_disposed, this);
-
-        var tcs = new TaskCompletionSource<bool>();
-        var workItem = new WorkItem(() =>
+        return InvokeAsync(() =>
         {
-            try
-            {
-                action();
-                tcs.SetResult(true);
-            }
-            catch (Exception ex)
-            {
-                tcs.SetException(ex);
-            }
-        });
+            action();
+            return true;
+        }, operation);
+    }
 
-        try
-        {
-            _workQueue.Add(workItem);
-        }
-        catch (InvalidOperationException)
+    /// <summary>
+    /// Invokes a function on the COM thread and returns a task with the result.
+    /// </summary>
+    public Task<T> InvokeAsync<T>(Func<T> func, [CallerMemberName] string operation = "")
+    {
+        return Enqueue(func, Timeout.InfiniteTimeSpan, CancellationToken.None, operation);
+    }
+
+    /// <summary>
+    /// Invokes an action on the COM thread, giving up after <paramref name="timeout"/>
+    /// (or <see cref="DefaultTimeout"/>) with a <see cref="TimeoutException"/>.
+    /// </summary>
+    public Task InvokeWithDeadlineAsync(Action action, TimeSpan? timeout = null, CancellationToken cancellationToken = default, [CallerMemberName] string operation = "")
+    {
+        return InvokeWithDeadlineAsync(() =>
         {
-            // WorkQueue has been marked as complete for adding (disposal in progress)
-            throw new ObjectDisposedException(nameof(ComThreadService));
-        }
+            action();
+            return true;
+        }, timeout, cancellationToken, operation);
+    }
 
-        return tcs.Task;
+    /// <summary>
+    /// Invokes a function on the COM thread, giving up after <paramref name="timeout"/>
+    /// (or <see cref="DefaultTimeout"/>) with a <see cref="TimeoutException"/>.
+    /// A call that has already started keeps running on the worker; only the caller stops waiting.
+    /// </summary>
+    public Task<T> InvokeWithDeadlineAsync<T>(Func<T> func, TimeSpan? timeout = null, CancellationToken cancellationToken = default, [CallerMemberName] string operation = "")
+    {
+        return Enqueue(func, timeout ?? DefaultTimeout, cancellationToken, operation);
     }
 
     /// <summary>
-    /// Invokes a function on the COM thread and returns a task with the result.
+    /// Cancels every queued work item that has not started yet. Their tasks complete as canceled.
     /// </summary>
-    public Task<T> InvokeAsync<T>(Func<T> func)
+    public int CancelPending()
+    {
+        var canceled = 0;
+        while (_workQueue.TryTake(out var workItem))
+        {
+            if (workItem.TryAbandon())
+            {
+                canceled++;
+            }
+        }
+
+        return canceled;
+    }
+
+    private Task<T> Enqueue<T>(Func<T> func, TimeSpan timeout, CancellationToken cancellationToken, string operation)
     {
         ObjectDisposedException.ThrowIf(_disposed, this);
+        cancellationToken.ThrowIfCancellationRequested();
 
         var tcs = new TaskCompletionSource<T>();
-        var workItem = new WorkItem(() =>
+        var workItem = new WorkItem(
+            () =>
+            {
+                // Every COM call gets a scope, so whatever it logs is attributed to the caller
+                using var scope = _logger.BeginScope("COM {Operation}", operation);
+                var stopwatch = Stopwatch.StartNew();
+                try
+                {
+                    tcs.TrySetResult(func());
+                }
+                catch (Exception ex)
+                {
+                    _logger.LogDebug(ex, "COM {Operation} threw after {ElapsedMs} ms", operation, stopwatch.ElapsedMilliseconds);
+                    tcs.TrySetException(ex);
+                }
+
+                if (stopwatch.Elapsed >= SlowCallThreshold)
+                {
+                    _logger.LogWarning("COM {Operation} took {ElapsedMs} ms", operation, stopwatch.ElapsedMilliseconds);
+                }
+                else
+                {
+                    _logger.LogTrace("COM {Operation} took {ElapsedMs} ms", operation, stopwatch.ElapsedMilliseconds);
+                }
+            },
+            () => tcs.TrySetCanceled());
+
+        if (timeout != Timeout.InfiniteTimeSpan || cancellationToken.CanBeCanceled)
         {
-            try
+            var deadline = CancellationTokenSource.CreateLinkedTokenSource(cancellationToken);
+            if (timeout != Timeout.InfiniteTimeSpan)
             {
-                var result = func();
-                tcs.SetResult(result);
+                deadline.CancelAfter(timeout);
             }
-            catch (Exception ex)
+
+            var registration = deadline.Token.Register(() =>
             {
-                tcs.SetException(ex);
-            }
-        });
+                if (cancellationToken.IsCancellationRequested)
+                {
+                    tcs.TrySetCanceled(cancellationToken);
+                }
+                else
+                {
+                    _logger.LogWarning("COM {Operation} timed out after {TimeoutMs} ms", operation, timeout.TotalMilliseconds);
+                    tcs.TrySetException(new TimeoutException($"COM operation {operation} did not complete within {timeout.TotalMilliseconds:0} ms."));
+                }
+
+                // Skip the work if it has not reached the front of the queue yet
+                workItem.TryAbandon();
+            });
+
+            tcs.Task.ContinueWith(_ =>
+            {
+                registration.Dispose();
+                deadline.Dispose();
+            }, TaskScheduler.Default);
+        }
 
         try
         {
@@ -122,6 +211,9 @@ public class ComThreadService : IDisposable
         _shutdownToken.Cancel();
         _workQueue.CompleteAdding();
 
+        // Complete anything still queued so awaiting callers don't hang
+        CancelPending();
+
         // Wait for the thread to finish (max 1 second)
         if (!_comThread.Join(1000))
         {
@@ -135,15 +227,31 @@ public class ComThreadService : IDisposable
     private class WorkItem
     {
         private readonly Action _action;
+        private readonly Action _onAbandoned;
+
+        // 0 = queued, 1 = started, 2 = abandoned
+        private int _state;
 
-        public WorkItem(Action action)
+        public WorkItem(Action action, Action onAbandoned)
         {
             _action = action;
+            _onAbandoned = onAbandoned;
         }
 
         public void Execute()
         {
+            if (Interlocked.CompareExchange(ref _state, 1, 0) != 0) return;
             _action();
         }
+
+        /// <summary>
+        /// Prevents a queued item from running. Returns false if it already started.
+        /// </summary>
+        public bool TryAbandon()
+        {
+            if (Interlocked.CompareExchange(ref _state, 2, 0) != 0) return false;
+            _onAbandoned();
+            return true;
+        }
     }
 }
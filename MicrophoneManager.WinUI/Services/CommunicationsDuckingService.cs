@@ -0,0 +1,140 @@
+using Microsoft.Win32;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Values of the Sound Control Panel's Communications tab ("When Windows detects communications activity").
+/// </summary>
+public enum CommunicationsDuckingPreference
+{
+    MuteOtherSounds = 0,
+    ReduceBy80Percent = 1,
+    ReduceBy50Percent = 2,
+    DoNothing = 3
+}
+
+/// <summary>
+/// Where Windows keeps the raw ducking preference.
+/// </summary>
+public interface ICommunicationsDuckingStore
+{
+    /// <summary>
+    /// The stored value, or null if it has never been set or can't be read.
+    /// </summary>
+    int? Read();
+
+    void Write(int value);
+}
+
+/// <summary>
+/// Reads and writes the per-user communications ducking preference, i.e. whether Windows lowers
+/// other audio when a call starts.
+/// </summary>
+public sealed class CommunicationsDuckingService
+{
+    /// <summary>
+    /// The level that was in effect before ducking was turned off, so turning it back on restores it.
+    /// </summary>
+    public const string PreviousLevelPreference = "DuckingLevelBeforeOff";
+
+    // Windows treats a missing value as the out-of-box default
+    public const CommunicationsDuckingPreference DefaultPreference = CommunicationsDuckingPreference.ReduceBy80Percent;
+
+    private readonly IPreferencesStore? _preferences;
+    private readonly ICommunicationsDuckingStore _store;
+
+    public CommunicationsDuckingService(IPreferencesStore? preferences = null, ICommunicationsDuckingStore? store = null)
+    {
+        _preferences = preferences;
+        _store = store ?? new RegistryCommunicationsDuckingStore();
+    }
+
+    /// <summary>
+    /// Gets the current preference, or the Windows default if it has never been changed.
+    /// </summary>
+    public CommunicationsDuckingPreference GetPreference()
+    {
+        return _store.Read() is int value && Enum.IsDefined(typeof(CommunicationsDuckingPreference), value)
+            ? (CommunicationsDuckingPreference)value
+            : DefaultPreference;
+    }
+
+    /// <summary>
+    /// Sets the preference. Takes effect for communication streams opened afterwards.
+    /// </summary>
+    public void SetPreference(CommunicationsDuckingPreference preference)
+    {
+        _store.Write((int)preference);
+    }
+
+    /// <summary>
+    /// Gets whether Windows lowers other sounds during calls.
+    /// </summary>
+    public bool IsDuckingEnabled() => GetPreference() != CommunicationsDuckingPreference.DoNothing;
+
+    /// <summary>
+    /// Switches to "Do nothing", or back to the level in use before that; returns the ducking state
+    /// read back afterwards, which is unchanged if the write was refused.
+    /// </summary>
+    public bool ToggleDucking()
+    {
+        var current = GetPreference();
+        if (current == CommunicationsDuckingPreference.DoNothing)
+        {
+            SetPreference(GetPreviousPreference());
+        }
+        else
+        {
+            _preferences?.SetString(PreviousLevelPreference, current.ToString());
+            SetPreference(CommunicationsDuckingPreference.DoNothing);
+        }
+
+        return IsDuckingEnabled();
+    }
+
+    private CommunicationsDuckingPreference GetPreviousPreference()
+    {
+        return Enum.TryParse<CommunicationsDuckingPreference>(_preferences?.GetString(PreviousLevelPreference), out var previous)
+            && Enum.IsDefined(previous)
+            && previous != CommunicationsDuckingPreference.DoNothing
+                ? previous
+                : DefaultPreference;
+    }
+}
+
+/// <summary>
+/// The ducking preference as Windows stores it, under HKCU\Software\Microsoft\Multimedia\Audio.
+/// </summary>
+public sealed class RegistryCommunicationsDuckingStore : ICommunicationsDuckingStore
+{
+    private const string RegistryKeyPath = @"Software\Microsoft\Multimedia\Audio";
+    private const string ValueName = "UserDuckingPreference";
+
+    public int? Read()
+    {
+        try
+        {
+            using var key = Registry.CurrentUser.OpenSubKey(RegistryKeyPath, false);
+            return key?.GetValue(ValueName) as int?;
+        }
+        catch
+        {
+            return null;
+        }
+    }
+
+    public void Write(int value)
+    {
+        try
+        {
+            if (PortableMode.IsEnabled) return;
+
+            using var key = Registry.CurrentUser.CreateSubKey(RegistryKeyPath, true);
+            key?.SetValue(ValueName, value, RegistryValueKind.DWord);
+        }
+        catch (Exception)
+        {
+            // Silently fail - registry access may be restricted
+        }
+    }
+}
@@ -0,0 +1,316 @@
+using System.Text.Json;
+using Microsoft.Extensions.Logging;
+using Microsoft.Extensions.Logging.Abstractions;
+using MicrophoneManager.WinUI.Models;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Executes <see cref="ControlRequest"/>s from external tools. Shared by every control transport so
+/// they all accept the same commands: <c>list</c>, <c>state</c>, <c>mute</c>, <c>unmute</c>,
+/// <c>toggleMute</c>, <c>setDefault</c>, <c>listProfiles</c>, <c>applyProfile</c>, <c>history</c>,
+/// <c>listRules</c>, <c>setRule</c> and <c>removeRule</c>.
+/// </summary>
+public sealed class ControlCommandDispatcher : IControlChannel, IDisposable
+{
+    // Dragging a volume slider fires dozens of notifications a second; subscribers only need the settled state
+    private static readonly TimeSpan StateChangedDebounce = TimeSpan.FromMilliseconds(100);
+
+    private readonly IAudioDeviceService _audioService;
+    private readonly ProfileService? _profileService;
+    private readonly DeviceAliasService? _aliasService;
+    private readonly HiddenDeviceService? _hiddenDeviceService;
+    private readonly ChangeHistoryService? _historyService;
+    private readonly AppRuleService? _ruleService;
+    private readonly ClippingService? _clippingService;
+    private readonly SynchronizationContext? _syncContext;
+    private readonly ILogger<ControlCommandDispatcher> _logger;
+    private readonly object _debounceLock = new();
+    private Timer? _debounceTimer;
+    private bool _disposed;
+
+    /// <summary>
+    /// Raised with the new state after devices, the default or a mute/volume level change.
+    /// Subscribers push this to connected clients.
+    /// </summary>
+    public event EventHandler<ControlState>? StateChanged;
+
+    /// <summary>
+    /// Raised for momentary events that aren't part of the state, such as the default microphone
+    /// clipping. Subscribers push these to connected clients as they happen.
+    /// </summary>
+    public event EventHandler<ControlEvent>? EventRaised;
+
+    public ControlCommandDispatcher(
+        IAudioDeviceService audioService,
+        ProfileService? profileService = null,
+        DeviceAliasService? aliasService = null,
+        HiddenDeviceService? hiddenDeviceService = null,
+        ChangeHistoryService? historyService = null,
+        AppRuleService? ruleService = null,
+        ClippingService? clippingService = null,
+        ILogger<ControlCommandDispatcher>? logger = null)
+    {
+        _audioService = audioService ?? throw new ArgumentNullException(nameof(audioService));
+        _profileService = profileService;
+        _aliasService = aliasService;
+        _hiddenDeviceService = hiddenDeviceService;
+        _historyService = historyService;
+        _ruleService = ruleService;
+        _clippingService = clippingService;
+        _syncContext = SynchronizationContext.Current;
+        _logger = logger ?? NullLogger<ControlCommandDispatcher>.Instance;
+
+        _audioService.DevicesChanged += OnAudioStateChanged;
+        _audioService.DefaultDeviceChanged += OnAudioStateChanged;
+        _audioService.MicrophoneVolumeChanged += OnAudioStateChanged;
+        if (_aliasService != null)
+        {
+            _aliasService.AliasesChanged += OnAudioStateChanged;
+        }
+        if (_hiddenDeviceService != null)
+        {
+            _hiddenDeviceService.HiddenDevicesChanged += OnAudioStateChanged;
+        }
+        if (_clippingService != null)
+        {
+            _clippingService.Clipped += OnClipped;
+        }
+    }
+
+    /// <summary>
+    /// Current default microphone and device list.
+    /// </summary>
+    public ControlState GetState()
+    {
+        var devices = _audioService.GetMicrophones()
+            .Select(d => ControlDevice.From(d, _aliasService?.DisplayNameFor(d), _hiddenDeviceService?.IsHidden(d) ?? false))
+            .ToList();
+        return new ControlState(devices.FirstOrDefault(d => d.IsDefault), devices);
+    }
+
+    /// <summary>
+    /// <see cref="GetState"/> on the dispatcher's thread, for callers on other threads.
+    /// </summary>
+    public async Task<ControlState> GetStateAsync()
+    {
+        var response = await DispatchAsync(new ControlRequest { Command = "state" });
+        return response.Data as ControlState ?? throw new InvalidOperationException(response.Error);
+    }
+
+    /// <summary>
+    /// Parses a JSON request, runs it and returns the JSON response. Malformed input gets an error response.
+    /// </summary>
+    public async Task<string> DispatchJsonAsync(string requestJson)
+    {
+        ControlResponse response;
+        try
+        {
+            var request = JsonSerializer.Deserialize<ControlRequest>(requestJson, ControlJson.Options);
+            response = request == null
+                ? ControlResponse.Failure("Empty request.")
+                : await DispatchAsync(request);
+        }
+        catch (JsonException ex)
+        {
+            response = ControlResponse.Failure($"Invalid JSON: {ex.Message}");
+        }
+
+        return JsonSerializer.Serialize(response, ControlJson.Options);
+    }
+
+    /// <summary>
+    /// Runs a request on the UI thread the dispatcher was created on, like every other caller of the audio service.
+    /// </summary>
+    public Task<ControlResponse> DispatchAsync(ControlRequest request)
+    {
+        if (_syncContext == null || SynchronizationContext.Current == _syncContext)
+        {
+            return ExecuteAsync(request);
+        }
+
+        var completion = new TaskCompletionSource<ControlResponse>(TaskCreationOptions.RunContinuationsAsynchronously);
+        _syncContext.Post(async _ =>
+        {
+            try
+            {
+                completion.SetResult(await ExecuteAsync(request));
+            }
+            catch (Exception ex)
+            {
+                completion.SetException(ex);
+            }
+        }, null);
+        return completion.Task;
+    }
+
+    private async Task<ControlResponse> ExecuteAsync(ControlRequest request)
+    {
+        try
+        {
+            switch (request.Command.ToLowerInvariant())
+            {
+                case "list":
+                    return ControlResponse.Success(GetState().Devices);
+                case "state":
+                    return ControlResponse.Success(GetState());
+                case "mute":
+                    return RunOnDevice(request, id => _audioService.SetMute(id, true));
+                case "unmute":
+                    return RunOnDevice(request, id => _audioService.SetMute(id, false));
+                case "togglemute":
+                case "toggle":
+                    return RunOnDevice(request, _audioService.ToggleMute);
+                case "setdefault":
+                    if (string.IsNullOrWhiteSpace(request.Device)) return ControlResponse.Failure("setDefault needs a device.");
+                    return RunOnDevice(request, _audioService.SetDefaultMicrophone);
+                case "listprofiles":
+                    if (_profileService == null) return ControlResponse.Failure("Profiles aren't available.");
+                    return ControlResponse.Success(_profileService.GetProfiles().Select(p => p.Name).ToList());
+                case "applyprofile":
+                    if (_profileService == null) return ControlResponse.Failure("Profiles aren't available.");
+                    if (string.IsNullOrWhiteSpace(request.Profile)) return ControlResponse.Failure("applyProfile needs a profile.");
+                    if (_profileService.GetProfile(request.Profile) == null) return ControlResponse.Failure($"No profile named '{request.Profile}'.");
+                    return await _profileService.ApplyProfileAsync(request.Profile)
+                        ? ControlResponse.Success(GetState())
+                        : ControlResponse.Failure(_audioService.LastError?.Message ?? "The profile was only partly applied.");
+                case "history":
+                    if (_historyService == null) return ControlResponse.Failure("Change history isn't available.");
+                    return ControlResponse.Success(_historyService.GetEntries(request.Since?.ToUniversalTime(), request.Limit));
+                case "listrules":
+                    if (_ruleService == null) return ControlResponse.Failure("Application rules aren't available.");
+                    return ControlResponse.Success(_ruleService.GetRules());
+                case "setrule":
+                    return SetRule(request);
+                case "removerule":
+                    if (_ruleService == null) return ControlResponse.Failure("Application rules aren't available.");
+                    if (string.IsNullOrWhiteSpace(request.Process)) return ControlResponse.Failure("removeRule needs a process.");
+                    return _ruleService.RemoveRule(request.Process)
+                        ? ControlResponse.Success(_ruleService.GetRules())
+                        : ControlResponse.Failure($"No rule for '{request.Process}'.");
+                default:
+                    return ControlResponse.Failure($"Unknown command '{request.Command}'.");
+            }
+        }
+        catch (Exception ex)
+        {
+            return ControlResponse.Failure(ex.Message);
+        }
+    }
+
+    private void OnAudioStateChanged(object? sender, EventArgs e)
+    {
+        if (_disposed || StateChanged == null) return;
+
+        lock (_debounceLock)
+        {
+            _debounceTimer?.Dispose();
+            _debounceTimer = new Timer(_ => RaiseStateChanged(), null, StateChangedDebounce, Timeout.InfiniteTimeSpan);
+        }
+    }
+
+    /// <summary>
+    /// Publishes the current state now. Tests call this instead of waiting out the debounce.
+    /// </summary>
+    public void RaiseStateChanged()
+    {
+        if (_disposed) return;
+
+        if (_syncContext != null && SynchronizationContext.Current != _syncContext)
+        {
+            _syncContext.Post(_ => RaiseStateChanged(), null);
+            return;
+        }
+
+        try
+        {
+            StateChanged?.Invoke(this, GetState());
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Publishing control state failed");
+        }
+    }
+
+    private void OnClipped(object? sender, ClippingService.ClipEventArgs e)
+    {
+        if (_disposed) return;
+
+        try
+        {
+            EventRaised?.Invoke(this, new ControlEvent("clipping", new ControlClipping(e.DeviceId, e.DeviceName, e.ClipCount)));
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Publishing clipping event failed");
+        }
+    }
+
+    private ControlResponse SetRule(ControlRequest request)
+    {
+        if (_ruleService == null) return ControlResponse.Failure("Application rules aren't available.");
+        if (string.IsNullOrWhiteSpace(request.Process)) return ControlResponse.Failure("setRule needs a process.");
+        if (string.IsNullOrWhiteSpace(request.Device)) return ControlResponse.Failure("setRule needs a device.");
+
+        var role = AppRuleRole.Console;
+        if (request.Role != null && (!Enum.TryParse(request.Role, ignoreCase: true, out role) || !Enum.IsDefined(role)))
+        {
+            return ControlResponse.Failure($"Unknown role '{request.Role}'. Use console, communications or both.");
+        }
+
+        var target = _aliasService?.FindByAlias(_audioService.GetMicrophones(), request.Device) ?? _audioService.FindMicrophone(request.Device);
+        if (target == null) return ControlResponse.Failure($"No microphone matches '{request.Device}'.");
+
+        _ruleService.SaveRule(new AppRule(request.Process, DevicePriorityService.KeyFor(target), role, request.RestoreOnExit ?? true));
+        return ControlResponse.Success(_ruleService.GetRules());
+    }
+
+    private ControlResponse RunOnDevice(ControlRequest request, Func<string, bool> operation)
+    {
+        // An alias is matched exactly before falling back to the driver's name
+        var target = request.Device != null
+            ? _aliasService?.FindByAlias(_audioService.GetMicrophones(), request.Device) ?? _audioService.FindMicrophone(request.Device)
+            : _audioService.GetDefaultMicrophone();
+        if (target == null)
+        {
+            return ControlResponse.Failure(request.Device != null
+                ? $"No microphone matches '{request.Device}'."
+                : "There is no default microphone.");
+        }
+
+        if (!operation(target.Id))
+        {
+            return ControlResponse.Failure(_audioService.LastError?.Message ?? "The operation failed.");
+        }
+
+        return ControlResponse.Success(GetState());
+    }
+
+    public void Dispose()
+    {
+        if (_disposed) return;
+        _disposed = true;
+
+        try { _audioService.DevicesChanged -= OnAudioStateChanged; } catch { }
+        try { _audioService.DefaultDeviceChanged -= OnAudioStateChanged; } catch { }
+        try { _audioService.MicrophoneVolumeChanged -= OnAudioStateChanged; } catch { }
+        if (_aliasService != null)
+        {
+            try { _aliasService.AliasesChanged -= OnAudioStateChanged; } catch { }
+        }
+        if (_hiddenDeviceService != null)
+        {
+            try { _hiddenDeviceService.HiddenDevicesChanged -= OnAudioStateChanged; } catch { }
+        }
+        if (_clippingService != null)
+        {
+            try { _clippingService.Clipped -= OnClipped; } catch { }
+        }
+
+        lock (_debounceLock)
+        {
+            _debounceTimer?.Dispose();
+            _debounceTimer = null;
+        }
+    }
+}
@@ -0,0 +1,157 @@
+using System.Diagnostics;
+using System.Runtime.InteropServices;
+using System.Text;
+using MicrophoneManager.WinUI.Models;
+using Microsoft.Win32.SafeHandles;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Last-chance handling for unhandled managed exceptions: writes a report folder with a minidump,
+/// the exception, build details and the tail of the in-memory log, then leaves a marker so the
+/// next start can tell the user where it is. Native crashes that never reach the runtime's
+/// handler are left to Windows Error Reporting.
+/// </summary>
+public static class CrashReporter
+{
+    private const string PendingMarkerName = "pending.txt";
+
+    private static RollingFileLoggerProvider? _log;
+    private static int _reporting;
+
+    /// <summary>
+    /// %LOCALAPPDATA%\MicrophoneManager\Crashes, or Crashes beside the exe in portable mode.
+    /// </summary>
+    public static string DefaultDirectory => PortableMode.IsEnabled
+        ? Path.Combine(PortableMode.Directory, "Crashes")
+        : Path.Combine(Environment.GetFolderPath(Environment.SpecialFolder.LocalApplicationData), "MicrophoneManager", "Crashes");
+
+    /// <summary>
+    /// Hooks the process-wide unhandled exception event. Call once, as early as possible.
+    /// </summary>
+    public static void Install()
+    {
+        AppDomain.CurrentDomain.UnhandledException += (s, e) =>
+        {
+            if (e.ExceptionObject is Exception ex && e.IsTerminating)
+            {
+                WriteReport(ex, DefaultDirectory);
+            }
+        };
+    }
+
+    /// <summary>
+    /// Supplies the log whose recent entries go into reports; flushed before a report is written.
+    /// </summary>
+    public static void AttachLog(RollingFileLoggerProvider log)
+    {
+        _log = log;
+    }
+
+    /// <summary>
+    /// Writes a crash report and marks it for the next start. A crash on another thread while a
+    /// report is being written is skipped.
+    /// </summary>
+    /// <returns>The report folder, or null if nothing could be written.</returns>
+    public static string? WriteReport(Exception exception, string crashDirectory, bool includeDump = true)
+    {
+        if (Interlocked.Exchange(ref _reporting, 1) == 1) return null;
+
+        try
+        {
+            var folder = Path.Combine(crashDirectory, $"crash-{DateTime.Now:yyyyMMdd-HHmmss}");
+            Directory.CreateDirectory(folder);
+
+            var log = _log;
+            try { log?.Flush(TimeSpan.FromSeconds(1)); } catch { }
+
+            var report = new StringBuilder()
+                .AppendLine($"Microphone Manager {BuildInfo.Current.Version} ({BuildInfo.Current.Commit ?? "unknown commit"})")
+                .AppendLine($"Crashed at {DateTime.Now:yyyy-MM-dd HH:mm:ss zzz} on {Environment.OSVersion}, {RuntimeInformation.FrameworkDescription}")
+                .AppendLine()
+                .AppendLine(exception.ToString())
+                .AppendLine()
+                .AppendLine("Recent log entries:");
+            foreach (var entry in log?.GetRecentEntries() ?? Array.Empty<string>())
+            {
+                report.Append(entry);
+            }
+
+            File.WriteAllText(Path.Combine(folder, "crash.txt"), report.ToString());
+
+            if (includeDump)
+            {
+                TryWriteMinidump(Path.Combine(folder, "crash.dmp"));
+            }
+
+            File.WriteAllText(Path.Combine(crashDirectory, PendingMarkerName), folder);
+            return folder;
+        }
+        catch (Exception ex)
+        {
+            Debug.WriteLine($"Writing crash report failed: {ex}");
+            return null;
+        }
+        finally
+        {
+            Interlocked.Exchange(ref _reporting, 0);
+        }
+    }
+
+    /// <summary>
+    /// The folder of a report written since the last start, if any. Clears the marker so it's
+    /// only shown once.
+    /// </summary>
+    public static string? TakePendingReport(string crashDirectory)
+    {
+        var marker = Path.Combine(crashDirectory, PendingMarkerName);
+        try
+        {
+            if (!File.Exists(marker)) return null;
+
+            var folder = File.ReadAllText(marker).Trim();
+            File.Delete(marker);
+            return Directory.Exists(folder) ? folder : null;
+        }
+        catch (Exception ex)
+        {
+            Debug.WriteLine($"Reading crash marker failed: {ex.Message}");
+            return null;
+        }
+    }
+
+    private static void TryWriteMinidump(string path)
+    {
+        try
+        {
+            using var process = Process.GetCurrentProcess();
+            using var file = new FileStream(path, FileMode.Create, FileAccess.ReadWrite, FileShare.None);
+
+            // Threads, stacks and handles without the full heap: enough to read the crash, small enough to attach
+            const uint dumpType = MiniDumpWithDataSegs | MiniDumpWithHandleData | MiniDumpWithThreadInfo | MiniDumpWithUnloadedModules;
+            if (!MiniDumpWriteDump(process.Handle, (uint)process.Id, file.SafeFileHandle, dumpType, IntPtr.Zero, IntPtr.Zero, IntPtr.Zero))
+            {
+                Debug.WriteLine($"MiniDumpWriteDump failed: {Marshal.GetLastWin32Error()}");
+            }
+        }
+        catch (Exception ex)
+        {
+            Debug.WriteLine($"Writing minidump failed: {ex.Message}");
+        }
+    }
+
+    private const uint MiniDumpWithDataSegs = 0x00000001;
+    private const uint MiniDumpWithHandleData = 0x00000004;
+    private const uint MiniDumpWithUnloadedModules = 0x00000020;
+    private const uint MiniDumpWithThreadInfo = 0x00001000;
+
+    [DllImport("dbghelp.dll", SetLastError = true)]
+    private static extern bool MiniDumpWriteDump(
+        IntPtr hProcess,
+        uint processId,
+        SafeFileHandle hFile,
+        uint dumpType,
+        IntPtr exceptionParam,
+        IntPtr userStreamParam,
+        IntPtr callbackParam);
+}
@@ -0,0 +1,209 @@
+using Microsoft.Extensions.Logging;
+using Microsoft.Extensions.Logging.Abstractions;
+using MicrophoneManager.WinUI.Models;
+using NAudio.CoreAudioApi;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Keeps a chosen microphone as the Windows default. When another application (a game, a driver
+/// installer) moves the default elsewhere, it is switched straight back. Defaults chosen through
+/// this app move the lock instead of being reverted.
+/// </summary>
+public sealed class DefaultDeviceLockService : IDisposable
+{
+    public const string LockedDefaultPreference = "LockedDefaultMicrophone";
+    public const string NotifyOnRevertPreference = "DefaultLockNotifications";
+
+    private readonly IAudioDeviceService _audioService;
+    private readonly IPreferencesStore _preferences;
+    private readonly Func<string?> _culpritResolver;
+    private readonly PowerEventService? _powerEvents;
+    private readonly DeviceAliasService? _aliasService;
+    private readonly ILogger<DefaultDeviceLockService> _logger;
+    private bool _disposed;
+
+    /// <summary>
+    /// Raised after another application's default change was reverted.
+    /// </summary>
+    public event EventHandler<DefaultRevertedEventArgs>? DefaultReverted;
+
+    /// <summary>
+    /// Raised when the lock is turned on, off or moved to another device.
+    /// </summary>
+    public event EventHandler? LockChanged;
+
+    public DefaultDeviceLockService(
+        IAudioDeviceService audioService,
+        IPreferencesStore preferences,
+        PowerEventService? powerEvents = null,
+        DeviceAliasService? aliasService = null,
+        ILogger<DefaultDeviceLockService>? logger = null)
+        : this(audioService, preferences, ProcessInfoResolver.GetForegroundProcessName, powerEvents, aliasService, logger)
+    {
+    }
+
+    public DefaultDeviceLockService(
+        IAudioDeviceService audioService,
+        IPreferencesStore preferences,
+        Func<string?> culpritResolver,
+        PowerEventService? powerEvents = null,
+        DeviceAliasService? aliasService = null,
+        ILogger<DefaultDeviceLockService>? logger = null)
+    {
+        _audioService = audioService ?? throw new ArgumentNullException(nameof(audioService));
+        _preferences = preferences ?? throw new ArgumentNullException(nameof(preferences));
+        _culpritResolver = culpritResolver ?? throw new ArgumentNullException(nameof(culpritResolver));
+        _aliasService = aliasService;
+        _logger = logger ?? NullLogger<DefaultDeviceLockService>.Instance;
+
+        _audioService.DefaultDeviceChanged += OnDefaultDeviceChanged;
+
+        // The locked mic coming back (replugged, or after a resume) takes the default back too
+        _audioService.DevicesChanged += OnDevicesChanged;
+
+        // A resume doesn't always raise either event, yet Windows may have picked another default while asleep
+        _powerEvents = powerEvents;
+        if (_powerEvents != null)
+        {
+            _powerEvents.Resumed += OnResumed;
+        }
+    }
+
+    /// <summary>
+    /// Preference key (<see cref="DevicePriorityService.KeyFor"/>) of the locked microphone, or null.
+    /// </summary>
+    public string? LockedDeviceKey => _preferences.GetString(LockedDefaultPreference);
+
+    public bool IsLocked => LockedDeviceKey != null;
+
+    /// <summary>
+    /// Whether to show a notification each time the default is put back.
+    /// </summary>
+    public bool NotifyOnRevert
+    {
+        get => _preferences.GetBool(NotifyOnRevertPreference, true);
+        set => _preferences.SetBool(NotifyOnRevertPreference, value);
+    }
+
+    /// <summary>
+    /// Locks the current default microphone, or releases the lock.
+    /// </summary>
+    public void SetLocked(bool locked)
+    {
+        string? key = null;
+        if (locked)
+        {
+            var defaultMic = _audioService.GetDefaultMicrophone();
+            if (defaultMic == null) return;
+            key = DevicePriorityService.KeyFor(defaultMic);
+        }
+
+        SetLockedKey(key);
+    }
+
+    /// <summary>
+    /// Puts the locked microphone back as default if it's connected and something else took over.
+    /// </summary>
+    public void Enforce() => Enforce(reportRevert: true);
+
+    private void Enforce(bool reportRevert)
+    {
+        if (_disposed || LockedDeviceKey is not { } key) return;
+
+        var currentId = _audioService.GetDefaultDeviceId(Role.Console);
+        var current = currentId != null ? _audioService.GetMicrophone(currentId) : null;
+        if (current != null && string.Equals(DevicePriorityService.KeyFor(current), key, StringComparison.OrdinalIgnoreCase)) return;
+
+        var locked = _audioService.GetMicrophones()
+            .FirstOrDefault(m => string.Equals(DevicePriorityService.KeyFor(m), key, StringComparison.OrdinalIgnoreCase));
+        if (locked == null) return;
+
+        // Chosen from this app (flyout, hotkey, profile, failover): that's the new lock, not a theft
+        if (current != null && current.Id == _audioService.LastRequestedDefaultId)
+        {
+            SetLockedKey(DevicePriorityService.KeyFor(current));
+            return;
+        }
+
+        if (!_audioService.SetDefaultMicrophone(locked.Id) || !reportRevert) return;
+
+        DefaultReverted?.Invoke(this, new DefaultRevertedEventArgs(NameOf(locked), current != null ? NameOf(current) : null, _culpritResolver()));
+    }
+
+    private string NameOf(MicrophoneDevice device) => _aliasService?.DisplayNameFor(device) ?? device.Name;
+
+    private void SetLockedKey(string? key)
+    {
+        if (string.Equals(LockedDeviceKey, key, StringComparison.OrdinalIgnoreCase)) return;
+
+        _preferences.SetString(LockedDefaultPreference, key);
+        LockChanged?.Invoke(this, EventArgs.Empty);
+    }
+
+    private void OnDefaultDeviceChanged(object? sender, EventArgs e)
+    {
+        TryEnforce(reportRevert: true);
+    }
+
+    private void OnDevicesChanged(object? sender, EventArgs e)
+    {
+        // Property-only changes (names, icons) can't change which device is default
+        if (e is AudioDeviceService.DevicesChangedEventArgs changeSet &&
+            changeSet.Changes.All(c => c.Kind == DeviceChangeKind.PropertyChanged))
+        {
+            return;
+        }
+
+        // Windows picked a stand-in while the locked mic was away; taking it back isn't worth a toast
+        TryEnforce(reportRevert: false);
+    }
+
+    private void OnResumed(object? sender, EventArgs e)
+    {
+        TryEnforce(reportRevert: false);
+    }
+
+    private void TryEnforce(bool reportRevert)
+    {
+        try
+        {
+            Enforce(reportRevert);
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Default device lock enforcement failed");
+        }
+    }
+
+    public void Dispose()
+    {
+        if (_disposed) return;
+        _disposed = true;
+
+        try { _audioService.DefaultDeviceChanged -= OnDefaultDeviceChanged; } catch { }
+        try { _audioService.DevicesChanged -= OnDevicesChanged; } catch { }
+        if (_powerEvents != null)
+        {
+            try { _powerEvents.Resumed -= OnResumed; } catch { }
+        }
+    }
+
+    public sealed class DefaultRevertedEventArgs : EventArgs
+    {
+        public DefaultRevertedEventArgs(string lockedDeviceName, string? replacedByName, string? likelyCulprit)
+        {
+            LockedDeviceName = lockedDeviceName;
+            ReplacedByName = replacedByName;
+            LikelyCulprit = likelyCulprit;
+        }
+
+        public string LockedDeviceName { get; }
+        public string? ReplacedByName { get; }
+
+        /// <summary>
+        /// Process that was in the foreground when the default changed, if any.
+        /// </summary>
+        public string? LikelyCulprit { get; }
+    }
+}
@@ -0,0 +1,92 @@
+using MicrophoneManager.WinUI.Models;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// User-chosen display names ("Desk Mic" for "Microphone (3- USB Audio Device)"), stored by
+/// <see cref="DevicePriorityService.KeyFor"/> so they survive re-enumeration. The driver's name is
+/// still used to find devices; aliases only change what's shown.
+/// </summary>
+public sealed class DeviceAliasService
+{
+    public const string AliasesPreference = "DeviceAliases";
+
+    private readonly IPreferencesStore _preferences;
+
+    /// <summary>
+    /// Raised when a device is renamed or its alias cleared.
+    /// </summary>
+    public event EventHandler? AliasesChanged;
+
+    public DeviceAliasService(IPreferencesStore preferences)
+    {
+        _preferences = preferences ?? throw new ArgumentNullException(nameof(preferences));
+    }
+
+    /// <summary>
+    /// Alias for a device key, or null when it has none.
+    /// </summary>
+    public string? GetAlias(string key)
+    {
+        return GetAliases().TryGetValue(key, out var alias) ? alias : null;
+    }
+
+    /// <summary>
+    /// The alias if the device has one, otherwise the name Windows reports.
+    /// </summary>
+    public string DisplayNameFor(MicrophoneDevice device)
+    {
+        return GetAlias(DevicePriorityService.KeyFor(device)) ?? device.Name;
+    }
+
+    /// <summary>
+    /// The device whose alias is exactly <paramref name="alias"/> (ignoring case), if any.
+    /// </summary>
+    public MicrophoneDevice? FindByAlias(IEnumerable<MicrophoneDevice> devices, string alias)
+    {
+        var aliases = GetAliases();
+        if (aliases.Count == 0) return null;
+
+        var trimmed = alias.Trim();
+        return devices.FirstOrDefault(d =>
+            aliases.TryGetValue(DevicePriorityService.KeyFor(d), out var candidate)
+            && string.Equals(candidate, trimmed, StringComparison.OrdinalIgnoreCase));
+    }
+
+    /// <summary>
+    /// Renames a device. A blank alias, or one equal to <paramref name="deviceName"/>, clears it.
+    /// </summary>
+    public void SetAlias(string key, string? alias, string? deviceName = null)
+    {
+        var aliases = GetAliases();
+        var trimmed = alias?.Trim();
+
+        if (string.IsNullOrEmpty(trimmed) || string.Equals(trimmed, deviceName, StringComparison.Ordinal))
+        {
+            if (!aliases.Remove(key)) return;
+        }
+        else
+        {
+            if (aliases.TryGetValue(key, out var existing) && existing == trimmed) return;
+            aliases[key] = trimmed;
+        }
+
+        _preferences.SetStringList(AliasesPreference, aliases.Select(a => $"{a.Key}={a.Value}").ToList());
+        AliasesChanged?.Invoke(this, EventArgs.Empty);
+    }
+
+    private Dictionary<string, string> GetAliases()
+    {
+        var aliases = new Dictionary<string, string>(StringComparer.OrdinalIgnoreCase);
+        foreach (var entry in _preferences.GetStringList(AliasesPreference))
+        {
+            // Keys never contain '=', aliases might
+            var separator = entry.IndexOf('=');
+            if (separator <= 0 || separator == entry.Length - 1) continue;
+
+            aliases[entry[..separator]] = entry[(separator + 1)..];
+        }
+
+        return aliases;
+    }
+}
@@ -0,0 +1,93 @@
+using MicrophoneManager.WinUI.Models;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Builds and reads the WAVEFORMATEXTENSIBLE blobs behind an endpoint's default format, the same
+/// way mmsys.cpl writes them: the device format in PCM (24-bit in a 32-bit container) and the
+/// engine mix format in 32-bit float at the same rate and channel count.
+/// </summary>
+public static class DeviceFormatBlob
+{
+    public const int Size = 40;
+
+    private const ushort WaveFormatExtensibleTag = 0xFFFE;
+    private static readonly Guid PcmSubFormat = new("00000001-0000-0010-8000-00aa00389b71");
+    private static readonly Guid IeeeFloatSubFormat = new("00000003-0000-0010-8000-00aa00389b71");
+
+    /// <summary>
+    /// The PCM device format for <paramref name="format"/>.
+    /// </summary>
+    public static byte[] BuildDeviceFormat(DeviceFormat format)
+    {
+        var containerBits = format.BitsPerSample == 24 ? 32 : format.BitsPerSample;
+        return Build(format.SampleRate, containerBits, format.BitsPerSample, format.Channels, PcmSubFormat);
+    }
+
+    /// <summary>
+    /// The float mix format the engine runs at for <paramref name="format"/>.
+    /// </summary>
+    public static byte[] BuildMixFormat(DeviceFormat format)
+    {
+        return Build(format.SampleRate, 32, 32, format.Channels, IeeeFloatSubFormat);
+    }
+
+    /// <summary>
+    /// The KSAUDIO_SPEAKER_* layout Windows uses for a channel count, or 0 when there is none.
+    /// </summary>
+    public static uint GetChannelMask(int channels)
+    {
+        return channels switch
+        {
+            1 => 0x4,   // KSAUDIO_SPEAKER_MONO
+            2 => 0x3,   // KSAUDIO_SPEAKER_STEREO
+            3 => 0x7,   // FL | FR | FC
+            4 => 0x33,  // KSAUDIO_SPEAKER_QUAD
+            5 => 0x37,  // KSAUDIO_SPEAKER_QUAD | FC
+            6 => 0x3F,  // KSAUDIO_SPEAKER_5POINT1
+            7 => 0x13F, // KSAUDIO_SPEAKER_5POINT1 | BC
+            8 => 0x63F, // KSAUDIO_SPEAKER_7POINT1_SURROUND
+            _ => 0
+        };
+    }
+
+    /// <summary>
+    /// Reads a WAVEFORMATEX or WAVEFORMATEXTENSIBLE blob, taking the valid bits rather than the
+    /// container size for extensible formats.
+    /// </summary>
+    public static DeviceFormat? Read(byte[]? blob)
+    {
+        // tag(2) channels(2) rate(4) avgBytes(4) blockAlign(2) bits(2) cbSize(2) validBits(2) ...
+        if (blob == null || blob.Length < 16) return null;
+
+        var channels = BitConverter.ToUInt16(blob, 2);
+        var sampleRate = BitConverter.ToInt32(blob, 4);
+        int bits = BitConverter.ToUInt16(blob, 14);
+        if (BitConverter.ToUInt16(blob, 0) == WaveFormatExtensibleTag && blob.Length >= 20)
+        {
+            var validBits = BitConverter.ToUInt16(blob, 18);
+            if (validBits > 0) bits = validBits;
+        }
+
+        return new DeviceFormat(sampleRate, bits, channels);
+    }
+
+    private static byte[] Build(int sampleRate, int containerBits, int validBits, int channels, Guid subFormat)
+    {
+        var blockAlign = (ushort)(channels * containerBits / 8);
+
+        using var stream = new MemoryStream(Size);
+        using var writer = new BinaryWriter(stream);
+        writer.Write(WaveFormatExtensibleTag);
+        writer.Write((ushort)channels);
+        writer.Write(sampleRate);
+        writer.Write(sampleRate * blockAlign);
+        writer.Write(blockAlign);
+        writer.Write((ushort)containerBits);
+        writer.Write((ushort)22); // cbSize
+        writer.Write((ushort)validBits);
+        writer.Write(GetChannelMask(channels));
+        writer.Write(subFormat.ToByteArray());
+        return stream.ToArray();
+    }
+}
@@ -0,0 +1,79 @@
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Groups the endpoints of one physical device (same container ID) under a single header in the
+/// flyout, e.g. "Elgato Wave: Microphone, Monitor Mix". Keeps whether grouping is on and which
+/// groups the user has collapsed. Groups are stored by <see cref="KeyFor"/>.
+/// </summary>
+public sealed class DeviceGroupService
+{
+    public const string GroupByDevicePreference = "GroupByDevice";
+    public const string CollapsedGroupsPreference = "CollapsedDeviceGroups";
+
+    private readonly IPreferencesStore _preferences;
+
+    /// <summary>
+    /// Raised when grouping is turned on or off, or a group is collapsed or expanded.
+    /// </summary>
+    public event EventHandler? Changed;
+
+    public DeviceGroupService(IPreferencesStore preferences)
+    {
+        _preferences = preferences ?? throw new ArgumentNullException(nameof(preferences));
+    }
+
+    public bool IsEnabled
+    {
+        get => _preferences.GetBool(GroupByDevicePreference, true);
+        set
+        {
+            if (value == IsEnabled) return;
+            _preferences.SetBool(GroupByDevicePreference, value);
+            Changed?.Invoke(this, EventArgs.Empty);
+        }
+    }
+
+    public bool IsCollapsed(string key)
+    {
+        return _preferences.GetStringList(CollapsedGroupsPreference).Contains(key, StringComparer.OrdinalIgnoreCase);
+    }
+
+    public void SetCollapsed(string key, bool collapsed)
+    {
+        var keys = _preferences.GetStringList(CollapsedGroupsPreference).ToList();
+        var index = keys.FindIndex(k => string.Equals(k, key, StringComparison.OrdinalIgnoreCase));
+        if (collapsed == index >= 0) return;
+
+        if (collapsed)
+        {
+            keys.Add(key);
+        }
+        else
+        {
+            keys.RemoveAt(index);
+        }
+
+        _preferences.SetStringList(CollapsedGroupsPreference, keys);
+        Changed?.Invoke(this, EventArgs.Empty);
+    }
+
+    /// <summary>
+    /// The group an endpoint belongs to, or null when its driver doesn't report a container. Some
+    /// drivers report an all-zero ID for every endpoint, which would lump unrelated devices together.
+    /// </summary>
+    public static string? KeyFor(Guid? containerId)
+    {
+        if (containerId is not { } id || id == Guid.Empty) return null;
+        return id.ToString("B");
+    }
+
+    /// <summary>
+    /// Header for a group, e.g. "Elgato Wave: Microphone, Monitor Mix". Falls back to listing the
+    /// endpoints alone when the adapter has no name.
+    /// </summary>
+    public static string GetTitle(string? adapterName, IEnumerable<string> endpointNames)
+    {
+        var endpoints = string.Join(", ", endpointNames);
+        return string.IsNullOrWhiteSpace(adapterName) ? endpoints : $"{adapterName}: {endpoints}";
+    }
+}
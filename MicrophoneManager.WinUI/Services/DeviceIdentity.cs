@@ -0,0 +1,61 @@
+using MicrophoneManager.WinUI.Models;
+using NAudio.CoreAudioApi;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Stable endpoint identity. MMDevice IDs are regenerated when a driver is reinstalled, so anything
+/// persisted by ID is silently orphaned; the physical device's container ID plus the endpoint's place
+/// on its adapter does not change. A USB device without a serial number gets a new container ID on
+/// another port, so it is still a new device after a port move.
+/// </summary>
+public static class DeviceIdentity
+{
+    /// <summary>
+    /// Container shared by everything built into the PC (onboard audio, internal microphones).
+    /// </summary>
+    public static readonly Guid MachineContainerId = new("00000000-0000-0000-ffff-ffffffffffff");
+
+    /// <summary>
+    /// Whether endpoints need their jack to tell them apart: built-in ones share the machine container,
+    /// and a front and rear "Microphone" on the same codec differ only by jack.
+    /// </summary>
+    public static bool NeedsJack(Guid? containerId) => containerId == null || containerId == MachineContainerId;
+
+    /// <summary>
+    /// Builds the stable key, e.g. "{container}|usb|jabra link 380|microphone", with the jack's type,
+    /// location and colour appended for built-in endpoints. Null when the endpoint exposes neither a
+    /// container ID nor an adapter name.
+    /// </summary>
+    public static string? CreateStableId(DeviceProperties properties)
+    {
+        if (properties.ContainerId == null && string.IsNullOrEmpty(properties.AdapterName)) return null;
+
+        var parts = new List<string>
+        {
+            properties.ContainerId?.ToString("B") ?? "",
+            properties.EnumeratorName ?? "",
+            properties.AdapterName ?? "",
+            properties.EndpointDescription ?? ""
+        };
+
+        if (NeedsJack(properties.ContainerId) && properties.Jack is { } jack)
+        {
+            parts.Add($"{jack.ConnectionType}, {jack.Location}, {jack.ColorHex}");
+        }
+
+        return string.Join("|", parts).ToLowerInvariant();
+    }
+
+    /// <summary>
+    /// Finds the current endpoint ID for a stable ID, including disabled and unplugged endpoints.
+    /// </summary>
+    public static string? ResolveStableId(this IAudioDeviceService audioService, string stableId)
+    {
+        if (string.IsNullOrEmpty(stableId)) return null;
+
+        return audioService.GetMicrophonesWithStates(DeviceState.All)
+            .FirstOrDefault(m => string.Equals(m.StableId, stableId, StringComparison.OrdinalIgnoreCase))
+            ?.Id;
+    }
+}
@@ -0,0 +1,77 @@
+using System.Text.RegularExpressions;
+using MicrophoneManager.WinUI.Models;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Resolves a microphone from what a user or script knows ("Blue Yeti") rather than its
+/// endpoint ID ("{0.0.1.00000000}.{guid}").
+/// </summary>
+public static class DeviceLookup
+{
+    private static readonly TimeSpan RegexTimeout = TimeSpan.FromMilliseconds(250);
+
+    /// <summary>
+    /// Finds the best match, in order: exact ID, exact name, name prefix, name substring
+    /// (all case-insensitive), or a regex on the name when <paramref name="useRegex"/> is set.
+    /// </summary>
+    public static MicrophoneDevice? FindMicrophone(this IAudioDeviceService audioService, string pattern, bool useRegex = false)
+    {
+        return FindMicrophone(audioService, pattern, useRegex, out _);
+    }
+
+    /// <param name="error">Why a regex couldn't be used (invalid, or too slow to match); null otherwise.</param>
+    public static MicrophoneDevice? FindMicrophone(this IAudioDeviceService audioService, string pattern, bool useRegex, out string? error)
+    {
+        return FindMatch(audioService.GetMicrophones(), d => d.Id, d => d.Name, pattern, useRegex, out error);
+    }
+
+    public static MicrophoneDevice? FindMatch(IReadOnlyList<MicrophoneDevice> devices, string pattern, bool useRegex = false)
+    {
+        return FindMatch(devices, d => d.Id, d => d.Name, pattern, useRegex);
+    }
+
+    /// <summary>
+    /// The same matching over any device shape, e.g. the <see cref="ControlDevice"/>s a tray reports.
+    /// </summary>
+    public static T? FindMatch<T>(IReadOnlyList<T> devices, Func<T, string> idOf, Func<T, string> nameOf, string pattern, bool useRegex = false)
+        where T : class
+    {
+        return FindMatch(devices, idOf, nameOf, pattern, useRegex, out _);
+    }
+
+    /// <param name="error">Why a regex couldn't be used (invalid, or too slow to match); null otherwise.</param>
+    public static T? FindMatch<T>(IReadOnlyList<T> devices, Func<T, string> idOf, Func<T, string> nameOf, string pattern, bool useRegex, out string? error)
+        where T : class
+    {
+        error = null;
+        if (string.IsNullOrWhiteSpace(pattern)) return null;
+
+        var byId = devices.FirstOrDefault(d => string.Equals(idOf(d), pattern, StringComparison.OrdinalIgnoreCase));
+        if (byId != null) return byId;
+
+        if (useRegex)
+        {
+            try
+            {
+                var regex = new Regex(pattern, RegexOptions.IgnoreCase | RegexOptions.CultureInvariant, RegexTimeout);
+                return devices.FirstOrDefault(d => regex.IsMatch(nameOf(d)));
+            }
+            catch (RegexMatchTimeoutException)
+            {
+                error = $"The pattern took too long to match (over {RegexTimeout.TotalMilliseconds:0} ms).";
+                return null;
+            }
+            catch (ArgumentException ex)
+            {
+                error = $"Invalid pattern: {ex.Message}";
+                return null;
+            }
+        }
+
+        var trimmed = pattern.Trim();
+        return devices.FirstOrDefault(d => string.Equals(nameOf(d), trimmed, StringComparison.OrdinalIgnoreCase))
+            ?? devices.FirstOrDefault(d => nameOf(d).StartsWith(trimmed, StringComparison.OrdinalIgnoreCase))
+            ?? devices.FirstOrDefault(d => nameOf(d).Contains(trimmed, StringComparison.OrdinalIgnoreCase));
+    }
+}
@@ -0,0 +1,249 @@
+using System.Globalization;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// What the microphone list is sorted by, below the pinned devices.
+/// </summary>
+public enum DeviceSortMode
+{
+    /// <summary>
+    /// The order the user arranged with "Move up" and "Move down".
+    /// </summary>
+    Custom,
+
+    Name,
+
+    /// <summary>
+    /// The default microphone, then the communications default, then the user's order.
+    /// </summary>
+    DefaultFirst,
+
+    /// <summary>
+    /// Most recently the default or recording first; see <see cref="DeviceOrderService.RecordUsed"/>.
+    /// </summary>
+    LastUsed,
+
+    /// <summary>
+    /// Loudest over the last half minute first; see <see cref="DeviceOrderService.RecordLevel"/>.
+    /// </summary>
+    Activity,
+}
+
+/// <summary>
+/// The order microphones are listed in the flyout and tray menu: pinned devices first, then the
+/// chosen <see cref="SortMode"/>, then anything never placed in the order Windows enumerates it.
+/// Only affects display; failover order is <see cref="DevicePriorityService"/>'s. Stored by
+/// <see cref="DevicePriorityService.KeyFor"/>.
+/// </summary>
+public sealed class DeviceOrderService
+{
+    public const string DeviceOrderPreference = "DeviceOrder";
+    public const string PinnedDevicesPreference = "PinnedDevices";
+    public const string SortModePreference = "DeviceSortMode";
+    public const string LastUsedPreference = "DeviceLastUsed";
+
+    // Saving on every refresh would rewrite the preferences every few seconds while recording
+    private static readonly TimeSpan LastUsedResolution = TimeSpan.FromMinutes(1);
+
+    // How quickly a device's activity fades once it goes quiet
+    private static readonly TimeSpan ActivityHalfLife = TimeSpan.FromSeconds(30);
+
+    private readonly IPreferencesStore _preferences;
+    private readonly Func<DateTime> _clock;
+    private readonly Dictionary<string, (double Level, DateTime At)> _activity = new(StringComparer.OrdinalIgnoreCase);
+
+    /// <summary>
+    /// Raised when a device is pinned, unpinned or moved, or the sort mode changes.
+    /// </summary>
+    public event EventHandler? OrderChanged;
+
+    public DeviceOrderService(IPreferencesStore preferences)
+        : this(preferences, () => DateTime.UtcNow)
+    {
+    }
+
+    public DeviceOrderService(IPreferencesStore preferences, Func<DateTime> clock)
+    {
+        _preferences = preferences ?? throw new ArgumentNullException(nameof(preferences));
+        _clock = clock ?? throw new ArgumentNullException(nameof(clock));
+    }
+
+    public DeviceSortMode SortMode
+    {
+        get => Enum.TryParse<DeviceSortMode>(_preferences.GetString(SortModePreference), ignoreCase: true, out var mode) && Enum.IsDefined(mode)
+            ? mode
+            : DeviceSortMode.Custom;
+        set
+        {
+            if (value == SortMode) return;
+            _preferences.SetString(SortModePreference, value == DeviceSortMode.Custom ? null : value.ToString());
+            OrderChanged?.Invoke(this, EventArgs.Empty);
+        }
+    }
+
+    /// <summary>
+    /// Whether "Move up" and "Move down" change what's shown, i.e. the list is in the user's order.
+    /// </summary>
+    public bool IsCustomOrder => SortMode == DeviceSortMode.Custom;
+
+    /// <summary>
+    /// Notes that a device is in use now (the default, or recording), for <see cref="DeviceSortMode.LastUsed"/>.
+    /// Doesn't raise <see cref="OrderChanged"/>; the list picks it up the next time it's sorted.
+    /// </summary>
+    public void RecordUsed(string key)
+    {
+        var lastUsed = GetLastUsed();
+        var now = _clock();
+        if (lastUsed.TryGetValue(key, out var previous) && now - previous < LastUsedResolution) return;
+
+        lastUsed[key] = now;
+        _preferences.SetStringList(LastUsedPreference, lastUsed
+            .Select(u => $"{u.Key}={u.Value.ToString("O", CultureInfo.InvariantCulture)}")
+            .ToList());
+    }
+
+    /// <summary>
+    /// Feeds a meter reading (0-100) into the device's activity for <see cref="DeviceSortMode.Activity"/>.
+    /// Only kept in memory; activity from before a restart says little about what's active now.
+    /// </summary>
+    public void RecordLevel(string key, double levelPercent)
+    {
+        var now = _clock();
+        var current = GetActivity(key, now);
+        if (levelPercent >= current)
+        {
+            _activity[key] = (levelPercent, now);
+        }
+    }
+
+    public bool IsPinned(string key)
+    {
+        return _preferences.GetStringList(PinnedDevicesPreference).Contains(key, StringComparer.OrdinalIgnoreCase);
+    }
+
+    public void SetPinned(string key, bool pinned)
+    {
+        var keys = _preferences.GetStringList(PinnedDevicesPreference).ToList();
+        var index = keys.FindIndex(k => string.Equals(k, key, StringComparison.OrdinalIgnoreCase));
+        if (pinned == index >= 0) return;
+
+        if (pinned)
+        {
+            keys.Add(key);
+        }
+        else
+        {
+            keys.RemoveAt(index);
+        }
+
+        _preferences.SetStringList(PinnedDevicesPreference, keys);
+        OrderChanged?.Invoke(this, EventArgs.Empty);
+    }
+
+    /// <summary>
+    /// Returns <paramref name="items"/> in the user's order, whatever the <see cref="SortMode"/>:
+    /// the order stepped through by "cycle microphone", which has to stay put as the default moves.
+    /// Items the user hasn't placed keep their relative order after those they have.
+    /// </summary>
+    public IReadOnlyList<T> Sort<T>(IEnumerable<T> items, Func<T, string> keyOf)
+    {
+        return SortPinnedThen(items, keyOf, sorted => sorted);
+    }
+
+    /// <summary>
+    /// Returns <paramref name="items"/> in the order they're listed: pinned first, then by the
+    /// <see cref="SortMode"/>, then in the user's order.
+    /// </summary>
+    /// <param name="defaultRankOf">0 for the default microphone, 1 for the communications default, 2 otherwise.</param>
+    public IReadOnlyList<T> SortForDisplay<T>(IEnumerable<T> items, Func<T, string> keyOf, Func<T, string> nameOf, Func<T, int> defaultRankOf)
+    {
+        var now = _clock();
+        switch (SortMode)
+        {
+            case DeviceSortMode.Name:
+                return SortPinnedThen(items, keyOf, sorted => sorted.ThenBy(nameOf, StringComparer.CurrentCultureIgnoreCase));
+            case DeviceSortMode.DefaultFirst:
+                return SortPinnedThen(items, keyOf, sorted => sorted.ThenBy(defaultRankOf));
+            case DeviceSortMode.LastUsed:
+                var lastUsed = GetLastUsed();
+                return SortPinnedThen(items, keyOf, sorted => sorted.ThenByDescending(item => lastUsed.TryGetValue(keyOf(item), out var at) ? at : DateTime.MinValue));
+            case DeviceSortMode.Activity:
+                return SortPinnedThen(items, keyOf, sorted => sorted.ThenByDescending(item => GetActivity(keyOf(item), now)));
+            default:
+                return Sort(items, keyOf);
+        }
+    }
+
+    private IReadOnlyList<T> SortPinnedThen<T>(IEnumerable<T> items, Func<T, string> keyOf, Func<IOrderedEnumerable<T>, IOrderedEnumerable<T>> thenBy)
+    {
+        var pinned = new HashSet<string>(_preferences.GetStringList(PinnedDevicesPreference), StringComparer.OrdinalIgnoreCase);
+        var order = _preferences.GetStringList(DeviceOrderPreference);
+
+        // OrderBy is stable, so unplaced items stay in enumeration order
+        return thenBy(items.OrderBy(item => pinned.Contains(keyOf(item)) ? 0 : 1))
+            .ThenBy(item =>
+            {
+                var index = IndexOf(order, keyOf(item));
+                return index < 0 ? int.MaxValue : index;
+            })
+            .ToList();
+    }
+
+    /// <summary>
+    /// Moves a device <paramref name="offset"/> places within <paramref name="currentOrder"/> (the
+    /// keys as currently listed) and saves the result. Pinned devices stay above unpinned ones.
+    /// </summary>
+    public void Move(string key, int offset, IReadOnlyList<string> currentOrder)
+    {
+        var order = currentOrder.ToList();
+        var index = IndexOf(order, key);
+        var target = index + offset;
+        if (index < 0 || target < 0 || target >= order.Count) return;
+        if (IsPinned(order[target]) != IsPinned(key)) return;
+
+        (order[index], order[target]) = (order[target], order[index]);
+
+        // Devices that aren't connected right now keep their place after the ones listed
+        order.AddRange(_preferences.GetStringList(DeviceOrderPreference).Where(k => IndexOf(order, k) < 0));
+
+        _preferences.SetStringList(DeviceOrderPreference, order);
+        OrderChanged?.Invoke(this, EventArgs.Empty);
+    }
+
+    private double GetActivity(string key, DateTime now)
+    {
+        if (!_activity.TryGetValue(key, out var activity)) return 0;
+
+        var halfLives = Math.Max(0, (now - activity.At).TotalSeconds) / ActivityHalfLife.TotalSeconds;
+        return activity.Level * Math.Pow(0.5, halfLives);
+    }
+
+    private Dictionary<string, DateTime> GetLastUsed()
+    {
+        var lastUsed = new Dictionary<string, DateTime>(StringComparer.OrdinalIgnoreCase);
+        foreach (var entry in _preferences.GetStringList(LastUsedPreference))
+        {
+            // Keys never contain '='
+            var separator = entry.IndexOf('=');
+            if (separator <= 0) continue;
+
+            if (DateTime.TryParse(entry[(separator + 1)..], CultureInfo.InvariantCulture, DateTimeStyles.RoundtripKind, out var at))
+            {
+                lastUsed[entry[..separator]] = at;
+            }
+        }
+
+        return lastUsed;
+    }
+
+    private static int IndexOf(IReadOnlyList<string> keys, string key)
+    {
+        for (var i = 0; i < keys.Count; i++)
+        {
+            if (string.Equals(keys[i], key, StringComparison.OrdinalIgnoreCase)) return i;
+        }
+
+        return -1;
+    }
+}
@@ -0,0 +1,227 @@
+using Microsoft.Extensions.Logging;
+using Microsoft.Extensions.Logging.Abstractions;
+using MicrophoneManager.WinUI.Models;
+using NAudio.CoreAudioApi;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Keeps the user's preferred microphone order and, when auto-failover is on, makes the
+/// highest-priority connected microphone the default when the default microphone goes away or a
+/// microphone ranked above it is plugged in. Other device changes leave a manual pick alone.
+/// </summary>
+public class DevicePriorityService : IDisposable
+{
+    public const string PriorityListPreference = "PreferredMicrophones";
+    public const string AutoFailoverPreference = "AutoFailover";
+
+    private readonly IAudioDeviceService _audioService;
+    private readonly IPreferencesStore _preferences;
+    private readonly ILogger<DevicePriorityService> _logger;
+    private HashSet<string> _activeIds;
+    private string? _lastDefaultId;
+    private bool _disposed;
+
+    /// <summary>
+    /// Raised after the priority list is edited.
+    /// </summary>
+    public event EventHandler? PriorityListChanged;
+
+    public DevicePriorityService(IAudioDeviceService audioService, IPreferencesStore preferences, ILogger<DevicePriorityService>? logger = null)
+    {
+        _audioService = audioService ?? throw new ArgumentNullException(nameof(audioService));
+        _preferences = preferences ?? throw new ArgumentNullException(nameof(preferences));
+        _logger = logger ?? NullLogger<DevicePriorityService>.Instance;
+
+        _activeIds = GetActiveIds();
+        _lastDefaultId = _audioService.GetDefaultDeviceId(Role.Console);
+
+        // Default changes are only recorded, never acted on, so a manual pick isn't undone
+        _audioService.DevicesChanged += OnDevicesChanged;
+        _audioService.DefaultDeviceChanged += OnDefaultDeviceChanged;
+    }
+
+    /// <summary>
+    /// Key a device is stored under: its stable ID when it has one, otherwise its endpoint ID.
+    /// </summary>
+    public static string KeyFor(MicrophoneDevice device) => device.StableId ?? device.Id;
+
+    public bool IsAutoFailoverEnabled
+    {
+        get => _preferences.GetBool(AutoFailoverPreference, false);
+        set => _preferences.SetBool(AutoFailoverPreference, value);
+    }
+
+    /// <summary>
+    /// Preferred device keys, highest priority first.
+    /// </summary>
+    public IReadOnlyList<string> GetPriorityList() => _preferences.GetStringList(PriorityListPreference);
+
+    /// <summary>
+    /// 1-based position of a device in the priority list, or null when it isn't listed.
+    /// </summary>
+    public int? GetRank(string key)
+    {
+        var index = IndexOf(GetPriorityList(), key);
+        return index < 0 ? null : index + 1;
+    }
+
+    /// <summary>
+    /// Appends a device to the end of the priority list.
+    /// </summary>
+    public void Prefer(string key)
+    {
+        var list = GetPriorityList().ToList();
+        if (IndexOf(list, key) >= 0) return;
+
+        list.Add(key);
+        Save(list);
+    }
+
+    public void Remove(string key)
+    {
+        var list = GetPriorityList().ToList();
+        var index = IndexOf(list, key);
+        if (index < 0) return;
+
+        list.RemoveAt(index);
+        Save(list);
+    }
+
+    /// <summary>
+    /// Moves a listed device by <paramref name="offset"/> places (negative is higher priority).
+    /// </summary>
+    public void Move(string key, int offset)
+    {
+        var list = GetPriorityList().ToList();
+        var index = IndexOf(list, key);
+        if (index < 0) return;
+
+        var target = Math.Clamp(index + offset, 0, list.Count - 1);
+        if (target == index) return;
+
+        list.RemoveAt(index);
+        list.Insert(target, key);
+        Save(list);
+    }
+
+    /// <summary>
+    /// Endpoint ID of the highest-priority active microphone, or null when none is connected.
+    /// </summary>
+    public string? GetPreferredDeviceId()
+    {
+        var list = GetPriorityList();
+        if (list.Count == 0) return null;
+
+        return _audioService.GetMicrophones()
+            .Select(m => (Device: m, Index: IndexOf(list, KeyFor(m))))
+            .Where(m => m.Index >= 0)
+            .OrderBy(m => m.Index)
+            .Select(m => m.Device.Id)
+            .FirstOrDefault();
+    }
+
+    /// <summary>
+    /// Makes the preferred microphone the default if auto-failover is on and it isn't already.
+    /// </summary>
+    /// <returns>True if the default was changed.</returns>
+    public async Task<bool> ApplyPreferredDefaultAsync(CancellationToken cancellationToken = default)
+    {
+        if (_disposed || !IsAutoFailoverEnabled) return false;
+
+        var preferredId = GetPreferredDeviceId();
+        if (preferredId == null || preferredId == _audioService.GetDefaultDeviceId(Role.Console)) return false;
+
+        return await _audioService.SetDefaultMicrophoneAsync(preferredId, cancellationToken);
+    }
+
+    private void OnDefaultDeviceChanged(object? sender, EventArgs e)
+    {
+        // When Windows moves the default off a microphone that just went away, keep remembering
+        // that one so OnDevicesChanged still sees the default as lost
+        if (_lastDefaultId != null && !GetActiveIds().Contains(_lastDefaultId)) return;
+
+        _lastDefaultId = _audioService.GetDefaultDeviceId(Role.Console);
+    }
+
+    private async void OnDevicesChanged(object? sender, EventArgs e)
+    {
+        // Property-only changes (names, icons) can't change which device should win
+        if (e is AudioDeviceService.DevicesChangedEventArgs changeSet &&
+            changeSet.Changes.All(c => c.Kind == DeviceChangeKind.PropertyChanged))
+        {
+            return;
+        }
+
+        try
+        {
+            if (ShouldFailOver())
+            {
+                await ApplyPreferredDefaultAsync();
+            }
+
+            _lastDefaultId = _audioService.GetDefaultDeviceId(Role.Console);
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Preferred microphone failover failed");
+        }
+    }
+
+    /// <summary>
+    /// True when the default microphone was removed or disabled, or a microphone ranked above it
+    /// has just become available. Updates the set of active microphones either way.
+    /// </summary>
+    private bool ShouldFailOver()
+    {
+        var microphones = _audioService.GetMicrophones();
+        var activeIds = microphones.Select(m => m.Id).ToHashSet(StringComparer.OrdinalIgnoreCase);
+        var previousIds = _activeIds;
+        _activeIds = activeIds;
+
+        var defaultId = _audioService.GetDefaultDeviceId(Role.Console);
+        var defaultLost = defaultId == null || !activeIds.Contains(defaultId) ||
+            (_lastDefaultId != null && previousIds.Contains(_lastDefaultId) && !activeIds.Contains(_lastDefaultId));
+        if (defaultLost) return true;
+
+        var list = GetPriorityList();
+        var defaultDevice = microphones.FirstOrDefault(m => string.Equals(m.Id, defaultId, StringComparison.OrdinalIgnoreCase));
+        var defaultIndex = defaultDevice == null ? -1 : IndexOf(list, KeyFor(defaultDevice));
+        if (defaultIndex < 0) defaultIndex = int.MaxValue;
+
+        return microphones
+            .Where(m => !previousIds.Contains(m.Id))
+            .Select(m => IndexOf(list, KeyFor(m)))
+            .Any(index => index >= 0 && index < defaultIndex);
+    }
+
+    private HashSet<string> GetActiveIds()
+    {
+        return _audioService.GetMicrophones().Select(m => m.Id).ToHashSet(StringComparer.OrdinalIgnoreCase);
+    }
+
+    private void Save(List<string> list)
+    {
+        _preferences.SetStringList(PriorityListPreference, list);
+        PriorityListChanged?.Invoke(this, EventArgs.Empty);
+    }
+
+    private static int IndexOf(IReadOnlyList<string> list, string key)
+    {
+        for (var i = 0; i < list.Count; i++)
+        {
+            if (string.Equals(list[i], key, StringComparison.OrdinalIgnoreCase)) return i;
+        }
+
+        return -1;
+    }
+
+    public void Dispose()
+    {
+        if (_disposed) return;
+        _disposed = true;
+
+        try { _audioService.DevicesChanged -= OnDevicesChanged; } catch { }
+        try { _audioService.DefaultDeviceChanged -= OnDefaultDeviceChanged; } catch { }
+    }
+}
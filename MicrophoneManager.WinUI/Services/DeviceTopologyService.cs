@@ -0,0 +1,494 @@
+using System.Collections.Concurrent;
+using System.Runtime.InteropServices;
+using Microsoft.Extensions.Logging;
+using MicrophoneManager.WinUI.Models;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Reads and writes controls that live in the adapter's device topology rather than on
+/// IAudioEndpointVolume (e.g. the "+10/+20 dB" microphone boost and the AGC checkbox shown in the
+/// Sound Control Panel), plus the jack descriptions on the adapter's bridge pin.
+/// </summary>
+/// <remarks>
+/// Boost and AGC reads walk the whole topology over COM, so they're cached per endpoint until
+/// <see cref="Invalidate"/> is called for it or it's written through this service.
+/// </remarks>
+public class DeviceTopologyService
+{
+    private readonly ILogger<DeviceTopologyService> _logger;
+    private readonly ConcurrentDictionary<string, MicrophoneBoost?> _boostCache = new(StringComparer.OrdinalIgnoreCase);
+    private readonly ConcurrentDictionary<string, bool?> _autoGainCache = new(StringComparer.OrdinalIgnoreCase);
+
+    private const uint ClsctxAll = 0x17;
+    private static readonly Guid IidDeviceTopology = new("2A07407E-6497-4A18-9787-32F79BD0D98F");
+    private static readonly Guid IidAudioVolumeLevel = new("7FB7B48F-531D-44A2-BCB3-5AD5A134B3DC");
+    private static readonly Guid IidAudioAutoGainControl = new("85401FD4-6DE4-4B9D-9869-2D6753A82F3C");
+    private static readonly Guid IidKsJackDescription = new("4509F757-2D46-4637-8E62-CE7DB944F57B");
+    private static readonly Guid IidKsJackDescription2 = new("478F3A9B-E0C9-4827-9228-6F5505FFE76A");
+
+    private const uint JackDesc2PresenceDetectCapability = 0x1;
+
+    // EPcxGeoLocation, starting at eGeoLocRear = 1
+    private static readonly string[] GeoLocationNames =
+    {
+        "Unknown", "Rear", "Front", "Left", "Right", "Top", "Bottom", "Rear panel", "Riser",
+        "Inside lid", "Drive bay", "HDMI", "Outside lid", "ATAPI"
+    };
+
+    // EPcxConnectionType
+    private static readonly string[] ConnectionTypeNames =
+    {
+        "Unknown", "3.5 mm", "6.35 mm", "ATAPI internal", "RCA", "Optical", "Digital", "Analog",
+        "DIN", "XLR", "RJ-11", "Combination"
+    };
+
+    // Parts are only walked this far upstream; real adapter graphs are a handful of nodes deep.
+    private const int MaxTopologyDepth = 16;
+
+    #region COM interop
+
+    [ComImport]
+    [Guid("BCDE0395-E52F-467C-8E3D-C4579291692E")]
+    private class MMDeviceEnumeratorComObject { }
+
+    [ComImport]
+    [Guid("A95664D2-9614-4F35-A746-DE8DB63617E6")]
+    [InterfaceType(ComInterfaceType.InterfaceIsIUnknown)]
+    private interface IMMDeviceEnumerator
+    {
+        void NotImpl_EnumAudioEndpoints();
+        void NotImpl_GetDefaultAudioEndpoint();
+
+        [PreserveSig]
+        int GetDevice([MarshalAs(UnmanagedType.LPWStr)] string id, out IMMDevice device);
+    }
+
+    [ComImport]
+    [Guid("D666063F-1587-4E43-81F1-B948E807363F")]
+    [InterfaceType(ComInterfaceType.InterfaceIsIUnknown)]
+    private interface IMMDevice
+    {
+        [PreserveSig]
+        int Activate(ref Guid iid, uint clsCtx, IntPtr activationParams, [MarshalAs(UnmanagedType.IUnknown)] out object instance);
+    }
+
+    [ComImport]
+    [Guid("2A07407E-6497-4A18-9787-32F79BD0D98F")]
+    [InterfaceType(ComInterfaceType.InterfaceIsIUnknown)]
+    private interface IDeviceTopology
+    {
+        [PreserveSig]
+        int GetConnectorCount(out uint count);
+
+        [PreserveSig]
+        int GetConnector(uint index, out IConnector connector);
+    }
+
+    [ComImport]
+    [Guid("9C2C4058-23F5-41DE-877A-DF3AF236A09E")]
+    [InterfaceType(ComInterfaceType.InterfaceIsIUnknown)]
+    private interface IConnector
+    {
+        void NotImpl_GetType();
+        void NotImpl_GetDataFlow();
+        void NotImpl_ConnectTo();
+        void NotImpl_Disconnect();
+        void NotImpl_IsConnected();
+
+        [PreserveSig]
+        int GetConnectedTo(out IConnector connectedTo);
+    }
+
+    [ComImport]
+    [Guid("AE2DE0E4-5BCA-4F2D-AA46-5D13F8FDB3A9")]
+    [InterfaceType(ComInterfaceType.InterfaceIsIUnknown)]
+    private interface IPart
+    {
+        [PreserveSig]
+        int GetName([MarshalAs(UnmanagedType.LPWStr)] out string name);
+
+        void NotImpl_GetLocalId();
+        void NotImpl_GetGlobalId();
+        void NotImpl_GetPartType();
+        void NotImpl_GetSubType();
+        void NotImpl_GetControlInterfaceCount();
+        void NotImpl_GetControlInterface();
+
+        [PreserveSig]
+        int EnumPartsIncoming(out IPartsList parts);
+
+        void NotImpl_EnumPartsOutgoing();
+        void NotImpl_GetTopologyObject();
+
+        [PreserveSig]
+        int Activate(uint clsCtx, ref Guid iid, [MarshalAs(UnmanagedType.IUnknown)] out object instance);
+    }
+
+    [ComImport]
+    [Guid("6DAA848C-5EB0-45CC-AEA5-998A2CDA1FFB")]
+    [InterfaceType(ComInterfaceType.InterfaceIsIUnknown)]
+    private interface IPartsList
+    {
+        [PreserveSig]
+        int GetCount(out uint count);
+
+        [PreserveSig]
+        int GetPart(uint index, out IPart part);
+    }
+
+    // IAudioVolumeLevel adds no methods to IPerChannelDbLevel
+    [ComImport]
+    [Guid("7FB7B48F-531D-44A2-BCB3-5AD5A134B3DC")]
+    [InterfaceType(ComInterfaceType.InterfaceIsIUnknown)]
+    private interface IAudioVolumeLevel
+    {
+        [PreserveSig]
+        int GetChannelCount(out uint channels);
+
+        [PreserveSig]
+        int GetLevelRange(uint channel, out float minLevelDb, out float maxLevelDb, out float stepping);
+
+        [PreserveSig]
+        int GetLevel(uint channel, out float levelDb);
+
+        [PreserveSig]
+        int SetLevel(uint channel, float levelDb, ref Guid eventContext);
+
+        [PreserveSig]
+        int SetLevelUniform(float levelDb, ref Guid eventContext);
+    }
+
+    [ComImport]
+    [Guid("85401FD4-6DE4-4B9D-9869-2D6753A82F3C")]
+    [InterfaceType(ComInterfaceType.InterfaceIsIUnknown)]
+    private interface IAudioAutoGainControl
+    {
+        [PreserveSig]
+        int GetEnabled([MarshalAs(UnmanagedType.Bool)] out bool enabled);
+
+        [PreserveSig]
+        int SetEnabled([MarshalAs(UnmanagedType.Bool)] bool enabled, ref Guid eventContext);
+    }
+
+    [StructLayout(LayoutKind.Sequential)]
+    private struct KsJackDescription
+    {
+        public uint ChannelMapping;
+        public uint Color;
+        public int ConnectionType;
+        public int GeoLocation;
+        public int GenLocation;
+        public int PortConnection;
+        public int IsConnected;
+    }
+
+    [StructLayout(LayoutKind.Sequential)]
+    private struct KsJackDescription2
+    {
+        public uint DeviceStateInfo;
+        public uint JackCapabilities;
+    }
+
+    [ComImport]
+    [Guid("4509F757-2D46-4637-8E62-CE7DB944F57B")]
+    [InterfaceType(ComInterfaceType.InterfaceIsIUnknown)]
+    private interface IKsJackDescription
+    {
+        [PreserveSig]
+        int GetJackCount(out uint jacks);
+
+        [PreserveSig]
+        int GetJackDescription(uint jack, out KsJackDescription description);
+    }
+
+    [ComImport]
+    [Guid("478F3A9B-E0C9-4827-9228-6F5505FFE76A")]
+    [InterfaceType(ComInterfaceType.InterfaceIsIUnknown)]
+    private interface IKsJackDescription2
+    {
+        [PreserveSig]
+        int GetJackCount(out uint jacks);
+
+        [PreserveSig]
+        int GetJackDescription2(uint jack, out KsJackDescription2 description);
+    }
+
+    #endregion
+
+    public DeviceTopologyService(ILogger<DeviceTopologyService> logger)
+    {
+        _logger = logger ?? throw new ArgumentNullException(nameof(logger));
+    }
+
+    /// <summary>
+    /// Drops the cached boost and AGC state of one endpoint, or of all of them when
+    /// <paramref name="deviceId"/> is null, e.g. after a device or topology change.
+    /// </summary>
+    public void Invalidate(string? deviceId = null)
+    {
+        if (deviceId == null)
+        {
+            _boostCache.Clear();
+            _autoGainCache.Clear();
+            return;
+        }
+
+        _boostCache.TryRemove(deviceId, out _);
+        _autoGainCache.TryRemove(deviceId, out _);
+    }
+
+    /// <summary>
+    /// Reads the microphone boost for a capture endpoint.
+    /// </summary>
+    /// <returns>The boost level and range, or null if the driver exposes no boost control.</returns>
+    public MicrophoneBoost? GetBoost(string deviceId)
+    {
+        return _boostCache.GetOrAdd(deviceId, ReadBoost);
+    }
+
+    private MicrophoneBoost? ReadBoost(string deviceId)
+    {
+        try
+        {
+            return WithBoostControl(deviceId, level =>
+            {
+                Marshal.ThrowExceptionForHR(level.GetLevelRange(0, out var min, out var max, out var step));
+                Marshal.ThrowExceptionForHR(level.GetLevel(0, out var current));
+                return new MicrophoneBoost(current, min, max, step);
+            });
+        }
+        catch (Exception ex)
+        {
+            _logger.LogDebug(ex, "Could not read microphone boost for {DeviceId}", deviceId);
+            return null;
+        }
+    }
+
+    /// <summary>
+    /// Sets the microphone boost (in dB) uniformly across channels, snapped to the driver's step.
+    /// </summary>
+    /// <returns>True if a boost control was found and updated.</returns>
+    public bool SetBoost(string deviceId, float levelDb)
+    {
+        _boostCache.TryRemove(deviceId, out _);
+        var result = WithBoostControl(deviceId, level =>
+        {
+            Marshal.ThrowExceptionForHR(level.GetLevelRange(0, out var min, out var max, out var step));
+            var target = Math.Clamp(levelDb, min, max);
+            if (step > 0)
+            {
+                target = min + MathF.Round((target - min) / step) * step;
+            }
+
+            var context = Guid.Empty;
+            Marshal.ThrowExceptionForHR(level.SetLevelUniform(target, ref context));
+            _logger.LogInformation("Set microphone boost for {DeviceId} to {LevelDb} dB", deviceId, target);
+            return (bool?)true;
+        });
+
+        return result == true;
+    }
+
+    /// <summary>
+    /// Reads whether the driver's automatic gain control is enabled.
+    /// </summary>
+    /// <returns>The AGC state, or null if the driver exposes no AGC control.</returns>
+    public bool? GetAutoGainControl(string deviceId)
+    {
+        return _autoGainCache.GetOrAdd(deviceId, ReadAutoGainControl);
+    }
+
+    private bool? ReadAutoGainControl(string deviceId)
+    {
+        try
+        {
+            return WithTopologyControl<IAudioAutoGainControl, bool?>(deviceId, IidAudioAutoGainControl, null, agc =>
+            {
+                Marshal.ThrowExceptionForHR(agc.GetEnabled(out var enabled));
+                return enabled;
+            });
+        }
+        catch (Exception ex)
+        {
+            _logger.LogDebug(ex, "Could not read automatic gain control for {DeviceId}", deviceId);
+            return null;
+        }
+    }
+
+    /// <summary>
+    /// Turns the driver's automatic gain control on or off.
+    /// </summary>
+    /// <returns>True if an AGC control was found and updated.</returns>
+    public bool SetAutoGainControl(string deviceId, bool enabled)
+    {
+        _autoGainCache.TryRemove(deviceId, out _);
+        var result = WithTopologyControl<IAudioAutoGainControl, bool?>(deviceId, IidAudioAutoGainControl, null, agc =>
+        {
+            var context = Guid.Empty;
+            Marshal.ThrowExceptionForHR(agc.SetEnabled(enabled, ref context));
+            _logger.LogInformation("Set automatic gain control for {DeviceId} to {Enabled}", deviceId, enabled);
+            return true;
+        });
+
+        return result == true;
+    }
+
+    /// <summary>
+    /// Reads the color, panel location and plug state of the first jack behind an endpoint.
+    /// </summary>
+    /// <returns>The jack, or null for endpoints without one (USB and Bluetooth devices usually have none).</returns>
+    public JackInfo? GetJackInfo(string deviceId)
+    {
+        try
+        {
+            return WithBridgePin(deviceId, (pin, comObjects) =>
+            {
+                var iid = IidKsJackDescription;
+                if (pin.Activate(ClsctxAll, ref iid, out var jackObject) < 0) return null;
+                comObjects.Add(jackObject);
+
+                if (jackObject is not IKsJackDescription jacks) return null;
+                if (jacks.GetJackCount(out var count) < 0 || count == 0) return null;
+                Marshal.ThrowExceptionForHR(jacks.GetJackDescription(0, out var description));
+
+                // Without presence detection IsConnected is always TRUE, so only trust it when the driver says so
+                bool? pluggedIn = null;
+                var iid2 = IidKsJackDescription2;
+                if (pin.Activate(ClsctxAll, ref iid2, out var jack2Object) >= 0)
+                {
+                    comObjects.Add(jack2Object);
+                    if (jack2Object is IKsJackDescription2 jacks2
+                        && jacks2.GetJackDescription2(0, out var description2) >= 0
+                        && (description2.JackCapabilities & JackDesc2PresenceDetectCapability) != 0)
+                    {
+                        pluggedIn = description.IsConnected != 0;
+                    }
+                }
+
+                // COLORREF is 0x00BBGGRR
+                var color = description.Color;
+                var rgb = ((color & 0xFF) << 16) | (color & 0xFF00) | ((color >> 16) & 0xFF);
+
+                var location = description.GeoLocation > 0 && description.GeoLocation < GeoLocationNames.Length
+                    ? GeoLocationNames[description.GeoLocation]
+                    : "Unknown";
+                var connectionType = description.ConnectionType >= 0 && description.ConnectionType < ConnectionTypeNames.Length
+                    ? ConnectionTypeNames[description.ConnectionType]
+                    : "Unknown";
+
+                return new JackInfo(rgb, location, connectionType, pluggedIn);
+            });
+        }
+        catch (Exception ex)
+        {
+            _logger.LogDebug(ex, "Could not read jack description for {DeviceId}", deviceId);
+            return null;
+        }
+    }
+
+    private T? WithBoostControl<T>(string deviceId, Func<IAudioVolumeLevel, T?> action)
+    {
+        return WithTopologyControl(
+            deviceId,
+            IidAudioVolumeLevel,
+            name => name?.Contains("Boost", StringComparison.OrdinalIgnoreCase) == true,
+            action);
+    }
+
+    /// <summary>
+    /// Walks upstream from the endpoint's connector into the adapter topology and runs
+    /// <paramref name="action"/> against the first part that exposes <typeparamref name="TControl"/>
+    /// (and, when <paramref name="nameFilter"/> is given, whose name matches it).
+    /// </summary>
+    private T? WithTopologyControl<TControl, T>(string deviceId, Guid controlIid, Func<string?, bool>? nameFilter, Func<TControl, T?> action)
+        where TControl : class
+    {
+        return WithBridgePin(deviceId, (start, comObjects) =>
+        {
+            var control = FindControl<TControl>(start, controlIid, nameFilter, comObjects, 0);
+            return control == null ? default : action(control);
+        });
+    }
+
+    /// <summary>
+    /// Runs <paramref name="action"/> against the adapter-side connector the endpoint is wired to,
+    /// releasing every COM object the walk (and the action) added to the list afterwards.
+    /// </summary>
+    private static T? WithBridgePin<T>(string deviceId, Func<IPart, List<object>, T?> action)
+    {
+        var comObjects = new List<object>();
+        try
+        {
+            var topology = ActivateTopology(deviceId, comObjects);
+            if (topology == null) return default;
+
+            if (topology.GetConnectorCount(out var connectorCount) < 0 || connectorCount == 0) return default;
+            if (topology.GetConnector(0, out var endpointConnector) < 0) return default;
+            comObjects.Add(endpointConnector);
+
+            if (endpointConnector.GetConnectedTo(out var adapterConnector) < 0) return default;
+            comObjects.Add(adapterConnector);
+
+            var start = adapterConnector as IPart;
+            if (start == null) return default;
+
+            return action(start, comObjects);
+        }
+        finally
+        {
+            foreach (var obj in comObjects)
+            {
+                Marshal.ReleaseComObject(obj);
+            }
+        }
+    }
+
+    private static IDeviceTopology? ActivateTopology(string deviceId, List<object> comObjects)
+    {
+        var enumerator = (IMMDeviceEnumerator)new MMDeviceEnumeratorComObject();
+        comObjects.Add(enumerator);
+
+        if (enumerator.GetDevice(deviceId, out var device) < 0) return null;
+        comObjects.Add(device);
+
+        var iid = IidDeviceTopology;
+        if (device.Activate(ref iid, ClsctxAll, IntPtr.Zero, out var topology) < 0) return null;
+        comObjects.Add(topology);
+
+        return topology as IDeviceTopology;
+    }
+
+    private static TControl? FindControl<TControl>(IPart part, Guid controlIid, Func<string?, bool>? nameFilter, List<object> comObjects, int depth)
+        where TControl : class
+    {
+        if (depth > MaxTopologyDepth) return null;
+
+        var nameMatches = nameFilter == null || (part.GetName(out var name) >= 0 && nameFilter(name));
+        if (nameMatches)
+        {
+            var iid = controlIid;
+            if (part.Activate(ClsctxAll, ref iid, out var control) >= 0)
+            {
+                comObjects.Add(control);
+                if (control is TControl typed) return typed;
+            }
+        }
+
+        // Input pins (the jack) have no incoming parts and return E_NOTFOUND
+        if (part.EnumPartsIncoming(out var incoming) < 0 || incoming == null) return null;
+        comObjects.Add(incoming);
+
+        if (incoming.GetCount(out var count) < 0) return null;
+        for (uint i = 0; i < count; i++)
+        {
+            if (incoming.GetPart(i, out var upstream) < 0) continue;
+            comObjects.Add(upstream);
+
+            var found = FindControl<TControl>(upstream, controlIid, nameFilter, comObjects, depth + 1);
+            if (found != null) return found;
+        }
+
+        return null;
+    }
+}
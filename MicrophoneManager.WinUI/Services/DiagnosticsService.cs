@@ -0,0 +1,126 @@
+using System.Reflection;
+using System.Runtime.InteropServices;
+using MicrophoneManager.WinUI.Models;
+using NAudio.CoreAudioApi;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Builds <see cref="DiagnosticsReport"/>s and remembers recent operation failures to include in them.
+/// </summary>
+public class DiagnosticsService : IDisposable
+{
+    private const int MaxRecentFailures = 20;
+
+    private readonly IAudioDeviceService _audioService;
+    private readonly object _failuresLock = new();
+    private readonly Queue<AudioDeviceService.AudioOperationFailedEventArgs> _recentFailures = new();
+    private bool _disposed;
+
+    public DiagnosticsService(IAudioDeviceService audioService)
+    {
+        _audioService = audioService ?? throw new ArgumentNullException(nameof(audioService));
+        _audioService.OperationFailed += OnOperationFailed;
+
+        // Failures raised before we subscribed are still worth reporting. The handler may already be
+        // running on another thread, and may even have queued this same failure.
+        if (_audioService.LastError is { } lastError)
+        {
+            lock (_failuresLock)
+            {
+                if (!_recentFailures.Contains(lastError))
+                {
+                    _recentFailures.Enqueue(lastError);
+                }
+            }
+        }
+    }
+
+    private void OnOperationFailed(object? sender, AudioDeviceService.AudioOperationFailedEventArgs e)
+    {
+        lock (_failuresLock)
+        {
+            _recentFailures.Enqueue(e);
+            while (_recentFailures.Count > MaxRecentFailures)
+            {
+                _recentFailures.Dequeue();
+            }
+        }
+    }
+
+    public DiagnosticsReport BuildReport()
+    {
+        var allMicrophones = _audioService.GetMicrophonesWithStates(DeviceState.All);
+
+        List<FailureDiagnostics> failures;
+        lock (_failuresLock)
+        {
+            failures = _recentFailures.Select(ToFailureDiagnostics).ToList();
+        }
+
+        return new DiagnosticsReport
+        {
+            AppVersion = Assembly.GetExecutingAssembly().GetName().Version?.ToString() ?? "",
+            Build = BuildInfo.Current,
+            OsVersion = $"{RuntimeInformation.OSDescription} ({Environment.OSVersion.Version})",
+            Is64BitProcess = Environment.Is64BitProcess,
+            ComApartment = Thread.CurrentThread.GetApartmentState().ToString(),
+            CaptureEndpointCounts = allMicrophones
+                .GroupBy(m => m.State.ToString())
+                .ToDictionary(g => g.Key, g => g.Count()),
+            DefaultConsoleCaptureId = _audioService.GetDefaultDeviceId(Role.Console),
+            DefaultCommunicationsCaptureId = _audioService.GetDefaultDeviceId(Role.Communications),
+            DefaultMultimediaCaptureId = _audioService.GetDefaultDeviceId(Role.Multimedia),
+            DefaultOutputId = _audioService.GetDefaultOutputDeviceId(Role.Console),
+            Devices = allMicrophones.Select(ToDeviceDiagnostics).ToList(),
+            RecentFailures = failures
+        };
+    }
+
+    private DeviceDiagnostics ToDeviceDiagnostics(MicrophoneDevice device)
+    {
+        var properties = _audioService.GetDeviceProperties(device.Id);
+
+        return new DeviceDiagnostics
+        {
+            DeviceId = device.Id,
+            Name = device.Name,
+            State = device.State.ToString(),
+            IsDefault = device.IsDefault,
+            IsDefaultCommunication = device.IsDefaultCommunication,
+            VolumeLevel = device.VolumeLevel,
+            IsMuted = device.IsMuted,
+            Format = string.IsNullOrEmpty(device.FormatTag) ? null : device.FormatTag,
+            DriverDescription = properties?.DriverDescription,
+            DriverVersion = properties?.DriverVersion,
+            EnumeratorName = properties?.EnumeratorName,
+            AdapterName = properties?.AdapterName,
+            Jack = properties?.Jack?.Summary,
+            JackPluggedIn = properties?.Jack?.IsPluggedIn,
+            Capabilities = device.State == DeviceState.Active ? _audioService.GetCapabilities(device.Id).ToString() : null
+        };
+    }
+
+    private static FailureDiagnostics ToFailureDiagnostics(AudioDeviceService.AudioOperationFailedEventArgs e)
+    {
+        return new FailureDiagnostics
+        {
+            Operation = e.Operation,
+            DeviceId = e.DeviceId,
+            DeviceName = e.DeviceName,
+            HResult = $"0x{e.HResult:X8}",
+            Code = e.Code.ToString(),
+            ExceptionType = e.ExceptionType,
+            Message = e.Message,
+            OccurredAtUtc = e.OccurredAtUtc
+        };
+    }
+
+    public void Dispose()
+    {
+        if (_disposed) return;
+        _disposed = true;
+
+        try { _audioService.OperationFailed -= OnOperationFailed; } catch { }
+    }
+}
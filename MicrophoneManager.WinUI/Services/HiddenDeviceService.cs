@@ -0,0 +1,71 @@
+using MicrophoneManager.WinUI.Models;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Microphones the user has hidden from the flyout and tray, such as virtual cables and endpoints
+/// they never use. Hidden devices still exist and can still be the default; they're just not
+/// listed unless <see cref="ShowHidden"/> is on. Stored by <see cref="DevicePriorityService.KeyFor"/>.
+/// </summary>
+public sealed class HiddenDeviceService
+{
+    public const string HiddenDevicesPreference = "HiddenDevices";
+    public const string ShowHiddenPreference = "ShowHiddenDevices";
+
+    private readonly IPreferencesStore _preferences;
+
+    /// <summary>
+    /// Raised when a device is hidden or unhidden, or <see cref="ShowHidden"/> changes.
+    /// </summary>
+    public event EventHandler? HiddenDevicesChanged;
+
+    public HiddenDeviceService(IPreferencesStore preferences)
+    {
+        _preferences = preferences ?? throw new ArgumentNullException(nameof(preferences));
+    }
+
+    /// <summary>
+    /// Whether hidden devices are listed anyway (greyed out), so they can be unhidden.
+    /// </summary>
+    public bool ShowHidden
+    {
+        get => _preferences.GetBool(ShowHiddenPreference, false);
+        set
+        {
+            if (value == ShowHidden) return;
+            _preferences.SetBool(ShowHiddenPreference, value);
+            HiddenDevicesChanged?.Invoke(this, EventArgs.Empty);
+        }
+    }
+
+    public bool IsHidden(string key)
+    {
+        return _preferences.GetStringList(HiddenDevicesPreference).Contains(key, StringComparer.OrdinalIgnoreCase);
+    }
+
+    public bool IsHidden(MicrophoneDevice device) => IsHidden(DevicePriorityService.KeyFor(device));
+
+    /// <summary>
+    /// Whether a device should be listed right now.
+    /// </summary>
+    public bool ShouldShow(MicrophoneDevice device) => ShowHidden || !IsHidden(device);
+
+    public void SetHidden(string key, bool hidden)
+    {
+        var keys = _preferences.GetStringList(HiddenDevicesPreference).ToList();
+        var index = keys.FindIndex(k => string.Equals(k, key, StringComparison.OrdinalIgnoreCase));
+        if (hidden == index >= 0) return;
+
+        if (hidden)
+        {
+            keys.Add(key);
+        }
+        else
+        {
+            keys.RemoveAt(index);
+        }
+
+        _preferences.SetStringList(HiddenDevicesPreference, keys);
+        HiddenDevicesChanged?.Invoke(this, EventArgs.Empty);
+    }
+}
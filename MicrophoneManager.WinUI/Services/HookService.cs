@@ -0,0 +1,221 @@
+using System.Diagnostics;
+using System.Globalization;
+using Microsoft.Extensions.Logging;
+using Microsoft.Extensions.Logging.Abstractions;
+using MicrophoneManager.WinUI.Models;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Runs user-configured shell commands when a microphone is muted, unmuted or removed, or when the
+/// default microphone keeps clipping. Commands run through <c>cmd.exe /c</c> without a window, are
+/// not waited on, and are killed if they outlive the timeout. <c>{event}</c> in a command is replaced with the event name. The device's name and
+/// ID are only passed as <c>MICMANAGER_DEVICE_NAME</c> and <c>MICMANAGER_DEVICE_ID</c> (with the
+/// event as <c>MICMANAGER_EVENT</c>): devices name themselves, so their names never become part of
+/// the command line cmd parses.
+/// </summary>
+public sealed class HookService : IDisposable
+{
+    public const string OnMutePreference = "HookOnMute";
+    public const string OnUnmutePreference = "HookOnUnmute";
+    public const string OnDeviceRemovedPreference = "HookOnDeviceRemoved";
+    public const string OnClippingPreference = "HookOnClipping";
+    public const string TimeoutPreference = "HookTimeoutSeconds";
+
+    public const int DefaultTimeoutSeconds = 30;
+
+    private readonly IAudioDeviceService _audioService;
+    private readonly IPreferencesStore _preferences;
+    private readonly Func<ProcessStartInfo, TimeSpan, Task> _runProcess;
+    private readonly ClippingService? _clippingService;
+    private readonly ILogger<HookService> _logger;
+    private readonly object _stateLock = new();
+
+    // Last known mute state and name of every connected microphone, so we only fire on transitions
+    // and still know a removed device's name after it's gone
+    private Dictionary<string, (string Name, bool IsMuted)> _known = new(StringComparer.OrdinalIgnoreCase);
+    private bool _disposed;
+
+    /// <param name="runProcess">Starts a hook; defaults to a detached process killed after the timeout.</param>
+    public HookService(
+        IAudioDeviceService audioService,
+        IPreferencesStore preferences,
+        Func<ProcessStartInfo, TimeSpan, Task>? runProcess = null,
+        ClippingService? clippingService = null,
+        ILogger<HookService>? logger = null)
+    {
+        _audioService = audioService ?? throw new ArgumentNullException(nameof(audioService));
+        _preferences = preferences ?? throw new ArgumentNullException(nameof(preferences));
+        _runProcess = runProcess ?? RunDetachedAsync;
+        _clippingService = clippingService;
+        _logger = logger ?? NullLogger<HookService>.Instance;
+
+        Snapshot();
+
+        _audioService.MicrophoneVolumeChanged += OnMicrophoneVolumeChanged;
+        _audioService.DevicesChanged += OnDevicesChanged;
+        if (_clippingService != null)
+        {
+            _clippingService.ClippingAlert += OnClippingAlert;
+        }
+    }
+
+    /// <summary>
+    /// How long a hook may run before it's killed.
+    /// </summary>
+    public TimeSpan Timeout =>
+        TimeSpan.FromSeconds(int.TryParse(_preferences.GetString(TimeoutPreference), NumberStyles.Integer, CultureInfo.InvariantCulture, out var seconds) && seconds > 0
+            ? seconds
+            : DefaultTimeoutSeconds);
+
+    /// <summary>
+    /// Substitutes <c>{event}</c> in a hook command. Only our own event names go into the command
+    /// text; anything a device reports about itself stays in the environment.
+    /// </summary>
+    public static string ExpandPlaceholders(string command, string eventName)
+    {
+        return command.Replace("{event}", eventName, StringComparison.OrdinalIgnoreCase);
+    }
+
+    private void OnMicrophoneVolumeChanged(object? sender, AudioDeviceService.MicrophoneVolumeChangedEventArgs e)
+    {
+        if (_disposed) return;
+
+        string name;
+        lock (_stateLock)
+        {
+            if (!_known.TryGetValue(e.DeviceId, out var known))
+            {
+                // First reading of a new device: nothing flipped, so just remember where it starts
+                _known[e.DeviceId] = (_audioService.GetMicrophone(e.DeviceId)?.Name ?? e.DeviceId, e.IsMuted);
+                return;
+            }
+
+            // Volume moves raise this too; only a flip of the mute state is a hook event
+            if (known.IsMuted == e.IsMuted) return;
+            name = known.Name;
+            _known[e.DeviceId] = (name, e.IsMuted);
+        }
+
+        Fire(e.IsMuted ? OnMutePreference : OnUnmutePreference, e.IsMuted ? "mute" : "unmute", e.DeviceId, name);
+    }
+
+    private void OnDevicesChanged(object? sender, EventArgs e)
+    {
+        if (_disposed) return;
+
+        if (e is AudioDeviceService.DevicesChangedEventArgs changeSet && changeSet.Changes.All(c => c.Kind == DeviceChangeKind.PropertyChanged))
+        {
+            return;
+        }
+
+        Dictionary<string, (string Name, bool IsMuted)> previous;
+        lock (_stateLock)
+        {
+            previous = _known;
+        }
+
+        var current = Snapshot();
+
+        // Unplugging reports StateChanged rather than Removed, so compare against what was connected
+        foreach (var (id, device) in previous)
+        {
+            if (!current.ContainsKey(id))
+            {
+                Fire(OnDeviceRemovedPreference, "removed", id, device.Name);
+            }
+        }
+    }
+
+    private Dictionary<string, (string Name, bool IsMuted)> Snapshot()
+    {
+        var snapshot = new Dictionary<string, (string Name, bool IsMuted)>(StringComparer.OrdinalIgnoreCase);
+        try
+        {
+            foreach (var microphone in _audioService.GetMicrophones())
+            {
+                snapshot[microphone.Id] = (microphone.Name, microphone.IsMuted);
+            }
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Hook device snapshot failed");
+        }
+
+        lock (_stateLock)
+        {
+            _known = snapshot;
+        }
+
+        return snapshot;
+    }
+
+    private void OnClippingAlert(object? sender, ClippingService.ClipEventArgs e)
+    {
+        if (_disposed) return;
+
+        Fire(OnClippingPreference, "clipping", e.DeviceId, e.DeviceName);
+    }
+
+    private void Fire(string preference, string eventName, string deviceId, string deviceName)
+    {
+        var command = _preferences.GetString(preference);
+        if (string.IsNullOrWhiteSpace(command)) return;
+
+        var startInfo = new ProcessStartInfo
+        {
+            FileName = Environment.GetEnvironmentVariable("ComSpec") ?? "cmd.exe",
+            Arguments = $"/d /c {ExpandPlaceholders(command, eventName)}",
+            UseShellExecute = false,
+            CreateNoWindow = true,
+            WorkingDirectory = Environment.GetFolderPath(Environment.SpecialFolder.UserProfile),
+        };
+        startInfo.Environment["MICMANAGER_EVENT"] = eventName;
+        startInfo.Environment["MICMANAGER_DEVICE_ID"] = deviceId;
+        startInfo.Environment["MICMANAGER_DEVICE_NAME"] = deviceName;
+
+        _ = RunSafelyAsync(startInfo, eventName);
+    }
+
+    private async Task RunSafelyAsync(ProcessStartInfo startInfo, string eventName)
+    {
+        try
+        {
+            await _runProcess(startInfo, Timeout);
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "'{Event}' hook failed", eventName);
+        }
+    }
+
+    private async Task RunDetachedAsync(ProcessStartInfo startInfo, TimeSpan timeout)
+    {
+        using var process = Process.Start(startInfo);
+        if (process == null) return;
+
+        using var cancellation = new CancellationTokenSource(timeout);
+        try
+        {
+            await process.WaitForExitAsync(cancellation.Token);
+        }
+        catch (OperationCanceledException)
+        {
+            _logger.LogWarning("Hook '{Command}' ran longer than {Seconds:0}s; killing it", startInfo.Arguments, timeout.TotalSeconds);
+            try { process.Kill(entireProcessTree: true); } catch { }
+        }
+    }
+
+    public void Dispose()
+    {
+        if (_disposed) return;
+        _disposed = true;
+
+        try { _audioService.MicrophoneVolumeChanged -= OnMicrophoneVolumeChanged; } catch { }
+        try { _audioService.DevicesChanged -= OnDevicesChanged; } catch { }
+        if (_clippingService != null)
+        {
+            try { _clippingService.ClippingAlert -= OnClippingAlert; } catch { }
+        }
+    }
+}
@@ -0,0 +1,103 @@
+using MicrophoneManager.WinUI.Models;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Turns the keys pressed while a hotkey box has focus into a <see cref="HotkeyBinding"/>. Modifiers
+/// are collected while they're held; the first other key completes the binding. Escape cancels.
+/// Keys are Win32 virtual-key codes, which is also what WinUI's VirtualKey values are.
+/// </summary>
+public sealed class HotkeyCapture
+{
+    private const uint VK_ESCAPE = 0x1B;
+
+    private HotkeyModifiers _held;
+
+    /// <summary>
+    /// The modifiers held now, or those of the completed binding.
+    /// </summary>
+    public HotkeyModifiers Modifiers => Binding?.Modifiers ?? _held;
+
+    /// <summary>
+    /// The captured binding, once a non-modifier key was pressed.
+    /// </summary>
+    public HotkeyBinding? Binding { get; private set; }
+
+    public bool IsCancelled { get; private set; }
+
+    public bool IsComplete => Binding != null || IsCancelled;
+
+    /// <summary>
+    /// What to show in the box while capturing: a prompt, the modifiers held so far ("Ctrl+Alt+…")
+    /// or the finished binding.
+    /// </summary>
+    public string Preview
+    {
+        get
+        {
+            if (Binding != null) return Binding.ToString();
+            if (_held == HotkeyModifiers.None) return "Press a key combination";
+            return HotkeyBinding.FormatModifiers(_held) + "+…";
+        }
+    }
+
+    /// <summary>
+    /// Records a key press. Returns true once the capture is complete or cancelled.
+    /// </summary>
+    public bool KeyDown(uint virtualKey)
+    {
+        if (IsComplete) return true;
+
+        if (GetModifier(virtualKey) is { } modifier)
+        {
+            _held |= modifier;
+            return false;
+        }
+
+        // Escape on its own backs out; with modifiers it's a binding like any other
+        if (virtualKey == VK_ESCAPE && _held == HotkeyModifiers.None)
+        {
+            IsCancelled = true;
+            return true;
+        }
+
+        Binding = new HotkeyBinding(_held, virtualKey);
+        return true;
+    }
+
+    public void KeyUp(uint virtualKey)
+    {
+        if (IsComplete) return;
+
+        if (GetModifier(virtualKey) is { } modifier)
+        {
+            _held &= ~modifier;
+        }
+    }
+
+    /// <summary>
+    /// Why <paramref name="binding"/> can't be used as a global hotkey, or null if it can. A letter,
+    /// digit or navigation key needs Ctrl, Alt or Win, or it would stop typing everywhere else.
+    /// </summary>
+    public static string? Validate(HotkeyBinding binding)
+    {
+        if (GetModifier(binding.VirtualKey) != null) return "A hotkey needs a key besides the modifiers.";
+
+        const HotkeyModifiers commandModifiers = HotkeyModifiers.Control | HotkeyModifiers.Alt | HotkeyModifiers.Windows;
+        if ((binding.Modifiers & commandModifiers) != 0) return null;
+
+        // Function keys and Pause are free to use alone
+        if (binding.VirtualKey is >= 0x70 and <= 0x87 or 0x13) return null;
+
+        return "Add Ctrl, Alt or Win so the key still works in other applications.";
+    }
+
+    private static HotkeyModifiers? GetModifier(uint virtualKey) => virtualKey switch
+    {
+        0x10 or 0xA0 or 0xA1 => HotkeyModifiers.Shift,
+        0x11 or 0xA2 or 0xA3 => HotkeyModifiers.Control,
+        0x12 or 0xA4 or 0xA5 => HotkeyModifiers.Alt,
+        0x5B or 0x5C => HotkeyModifiers.Windows,
+        _ => null,
+    };
+}
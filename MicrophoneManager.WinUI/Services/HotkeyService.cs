@@ -0,0 +1,266 @@
+using System.Runtime.InteropServices;
+using Microsoft.Extensions.Logging;
+using Microsoft.Extensions.Logging.Abstractions;
+using MicrophoneManager.WinUI.Models;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Registers the user's global hotkeys with RegisterHotKey and raises <see cref="HotkeyPressed"/>
+/// when one fires. WM_HOTKEY arrives through the tray window's <see cref="WindowMessageMonitor"/>,
+/// so no extra message loop is needed.
+/// </summary>
+public sealed class HotkeyService : IDisposable
+{
+    public const string HotkeysPreference = "Hotkeys";
+
+    private const int WM_HOTKEY = 0x0312;
+    private const uint MOD_NOREPEAT = 0x4000;
+
+    // Outside the action ids, for trying a combination before it's saved
+    private const int ProbeHotkeyId = 0xBFFF;
+
+    // Ctrl+Alt keeps the defaults clear of most application shortcuts
+    public static IReadOnlyDictionary<HotkeyAction, HotkeyBinding> DefaultBindings { get; } = new Dictionary<HotkeyAction, HotkeyBinding>
+    {
+        [HotkeyAction.ToggleMute] = new(HotkeyModifiers.Control | HotkeyModifiers.Alt, 'M'),
+        [HotkeyAction.MuteAll] = new(HotkeyModifiers.Control | HotkeyModifiers.Alt | HotkeyModifiers.Shift, 'M'),
+        [HotkeyAction.CycleDefaultMicrophone] = new(HotkeyModifiers.Control | HotkeyModifiers.Alt, 'N'),
+        [HotkeyAction.OpenFlyout] = new(HotkeyModifiers.Control | HotkeyModifiers.Alt, 'O'),
+        [HotkeyAction.VolumeUp] = new(HotkeyModifiers.Control | HotkeyModifiers.Alt, 0x26),
+        [HotkeyAction.VolumeDown] = new(HotkeyModifiers.Control | HotkeyModifiers.Alt, 0x28),
+    };
+
+    private readonly IPreferencesStore _preferences;
+    private readonly ILogger<HotkeyService> _logger;
+    private readonly HashSet<HotkeyAction> _registered = new();
+    private readonly HashSet<HotkeyAction> _failed = new();
+    private WindowMessageMonitor? _monitor;
+    private IntPtr _hwnd;
+    private bool _suspended;
+    private bool _disposed;
+
+    /// <summary>
+    /// Raised on the window's thread when a registered hotkey is pressed.
+    /// </summary>
+    public event EventHandler<HotkeyAction>? HotkeyPressed;
+
+    /// <summary>
+    /// Raised after bindings are changed or re-registered.
+    /// </summary>
+    public event EventHandler? BindingsChanged;
+
+    public HotkeyService(IPreferencesStore preferences, ILogger<HotkeyService>? logger = null)
+    {
+        _preferences = preferences ?? throw new ArgumentNullException(nameof(preferences));
+        _logger = logger ?? NullLogger<HotkeyService>.Instance;
+    }
+
+    /// <summary>
+    /// Current binding for every action; null when the user turned it off.
+    /// </summary>
+    public IReadOnlyDictionary<HotkeyAction, HotkeyBinding?> GetBindings()
+    {
+        return ParseBindings(_preferences.GetStringList(HotkeysPreference));
+    }
+
+    /// <summary>
+    /// Actions whose hotkey couldn't be registered, usually because another application owns it.
+    /// </summary>
+    public IReadOnlyCollection<HotkeyAction> FailedRegistrations => _failed;
+
+    /// <summary>
+    /// Rebinds an action (null turns it off), saves the change and re-registers.
+    /// </summary>
+    public void SetBinding(HotkeyAction action, HotkeyBinding? binding)
+    {
+        var bindings = GetBindings().ToDictionary(b => b.Key, b => b.Value);
+        bindings[action] = binding;
+        _preferences.SetStringList(HotkeysPreference, FormatBindings(bindings));
+        RegisterAll();
+    }
+
+    public void ResetToDefaults()
+    {
+        _preferences.SetStringList(HotkeysPreference, Array.Empty<string>());
+        RegisterAll();
+    }
+
+    /// <summary>
+    /// Why <paramref name="binding"/> can't be given to <paramref name="action"/>, or null if it can:
+    /// another action already has it, or another application registered it. The second check needs
+    /// the window, so it's skipped before <see cref="Attach"/>.
+    /// </summary>
+    public string? GetConflict(HotkeyAction action, HotkeyBinding binding)
+    {
+        foreach (var (other, otherBinding) in GetBindings())
+        {
+            if (other != action && binding == otherBinding)
+            {
+                return $"Already used for {other.GetDisplayName()}.";
+            }
+        }
+
+        if (_hwnd == IntPtr.Zero) return null;
+
+        // Our own registrations would make every combination we use look taken
+        var wasSuspended = _suspended;
+        if (!wasSuspended) UnregisterAll();
+
+        try
+        {
+            if (!RegisterHotKey(_hwnd, ProbeHotkeyId, (uint)binding.Modifiers | MOD_NOREPEAT, binding.VirtualKey))
+            {
+                return "In use by another application.";
+            }
+
+            UnregisterHotKey(_hwnd, ProbeHotkeyId);
+            return null;
+        }
+        finally
+        {
+            if (!wasSuspended) RegisterAll();
+        }
+    }
+
+    /// <summary>
+    /// Releases every hotkey so the keys reach the focused window, while a new combination is being
+    /// pressed. <see cref="ResumeRegistrations"/> or any binding change registers them again.
+    /// </summary>
+    public void SuspendRegistrations()
+    {
+        if (_suspended || _hwnd == IntPtr.Zero) return;
+
+        _suspended = true;
+        UnregisterAll();
+    }
+
+    public void ResumeRegistrations()
+    {
+        if (!_suspended) return;
+        RegisterAll();
+    }
+
+    /// <summary>
+    /// Re-registers from the saved bindings, e.g. after settings were imported.
+    /// </summary>
+    public void Reload()
+    {
+        RegisterAll();
+    }
+
+    /// <summary>
+    /// Registers the saved bindings on the monitored window and starts listening for them.
+    /// </summary>
+    public void Attach(WindowMessageMonitor monitor)
+    {
+        if (_disposed || monitor.Handle == IntPtr.Zero || _monitor != null) return;
+
+        _monitor = monitor;
+        _hwnd = monitor.Handle;
+        _monitor.MessageReceived += OnMessageReceived;
+        RegisterAll();
+    }
+
+    /// <summary>
+    /// Reads "Action=Ctrl+Alt+M" entries. Actions without an entry keep their default; an empty
+    /// binding means the action is turned off.
+    /// </summary>
+    public static Dictionary<HotkeyAction, HotkeyBinding?> ParseBindings(IReadOnlyList<string> entries)
+    {
+        var bindings = Enum.GetValues<HotkeyAction>()
+            .ToDictionary(a => a, a => DefaultBindings.TryGetValue(a, out var binding) ? binding : null);
+
+        foreach (var entry in entries)
+        {
+            var separator = entry.IndexOf('=');
+            if (separator <= 0 || !Enum.TryParse<HotkeyAction>(entry[..separator], ignoreCase: true, out var action)) continue;
+
+            var text = entry[(separator + 1)..];
+            if (string.IsNullOrWhiteSpace(text))
+            {
+                bindings[action] = null;
+            }
+            else if (HotkeyBinding.TryParse(text, out var binding))
+            {
+                bindings[action] = binding;
+            }
+        }
+
+        return bindings;
+    }
+
+    public static List<string> FormatBindings(IReadOnlyDictionary<HotkeyAction, HotkeyBinding?> bindings)
+    {
+        return bindings.OrderBy(b => b.Key).Select(b => $"{b.Key}={b.Value}").ToList();
+    }
+
+    private void RegisterAll()
+    {
+        _suspended = false;
+
+        if (_hwnd != IntPtr.Zero)
+        {
+            UnregisterAll();
+
+            foreach (var (action, binding) in GetBindings())
+            {
+                if (binding == null) continue;
+
+                if (RegisterHotKey(_hwnd, HotkeyId(action), (uint)binding.Modifiers | MOD_NOREPEAT, binding.VirtualKey))
+                {
+                    _registered.Add(action);
+                }
+                else
+                {
+                    _failed.Add(action);
+                    _logger.LogWarning("RegisterHotKey failed for {Action} ({Binding}): {Error}", action, binding, Marshal.GetLastWin32Error());
+                }
+            }
+        }
+
+        BindingsChanged?.Invoke(this, EventArgs.Empty);
+    }
+
+    private void UnregisterAll()
+    {
+        foreach (var action in _registered)
+        {
+            UnregisterHotKey(_hwnd, HotkeyId(action));
+        }
+
+        _registered.Clear();
+        _failed.Clear();
+    }
+
+    private static int HotkeyId(HotkeyAction action) => (int)action + 1;
+
+    private void OnMessageReceived(object? sender, WindowMessageMonitor.WindowMessageEventArgs e)
+    {
+        if (e.Message != WM_HOTKEY) return;
+
+        var action = (HotkeyAction)((int)e.WParam - 1);
+        if (!_registered.Contains(action)) return;
+
+        e.Handled = true;
+        HotkeyPressed?.Invoke(this, action);
+    }
+
+    public void Dispose()
+    {
+        if (_disposed) return;
+        _disposed = true;
+
+        if (_hwnd == IntPtr.Zero) return;
+
+        try { UnregisterAll(); } catch { }
+        try { if (_monitor != null) _monitor.MessageReceived -= OnMessageReceived; } catch { }
+        _hwnd = IntPtr.Zero;
+    }
+
+    [DllImport("user32.dll", SetLastError = true)]
+    private static extern bool RegisterHotKey(IntPtr hWnd, int id, uint fsModifiers, uint vk);
+
+    [DllImport("user32.dll", SetLastError = true)]
+    private static extern bool UnregisterHotKey(IntPtr hWnd, int id);
+}
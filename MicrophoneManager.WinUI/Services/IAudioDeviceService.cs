@@ -7,22 +7,90 @@ public interface IAudioDeviceService : IDisposable
 {
     event EventHandler? DevicesChanged;
     event EventHandler? DefaultDeviceChanged;
+    event EventHandler? OutputDevicesChanged;
     event EventHandler<AudioDeviceService.DefaultMicrophoneVolumeChangedEventArgs>? DefaultMicrophoneVolumeChanged;
     event EventHandler<AudioDeviceService.MicrophoneVolumeChangedEventArgs>? MicrophoneVolumeChanged;
+
+    /// <summary>
+    /// Raised on the capture thread about 120 times a second per metered microphone. Handlers
+    /// should filter cheaply and marshal anything they keep to the UI thread themselves.
+    /// </summary>
     event EventHandler<AudioDeviceService.MicrophoneInputLevelChangedEventArgs>? MicrophoneInputLevelChanged;
+
     event EventHandler<AudioDeviceService.MicrophoneFormatChangedEventArgs>? MicrophoneFormatChanged;
+    event EventHandler<AudioDeviceService.AudioOperationFailedEventArgs>? OperationFailed;
+    event EventHandler<AudioDeviceService.CaptureSessionChangedEventArgs>? CaptureSessionChanged;
+    AudioDeviceService.AudioOperationFailedEventArgs? LastError { get; }
+    string? LastRequestedDefaultId { get; }
 
     List<MicrophoneDevice> GetMicrophones();
+    List<MicrophoneDevice> GetMicrophonesWithStates(DeviceState states);
+    MicrophoneDevice? GetMicrophone(string deviceId);
     string? GetDefaultDeviceId(Role role);
     MicrophoneDevice? GetDefaultMicrophone();
     bool SetDefaultMicrophone(string deviceId);
     bool SetMicrophoneForRole(string deviceId, Role role);
     void SetDefaultMicrophoneVolumePercent(double volumePercent);
     void SetMicrophoneVolumeLevelScalar(string deviceId, float volumeLevelScalar);
+    float? GetVolumeDb(string deviceId);
+    VolumeRange? GetVolumeRange(string deviceId);
+    bool SetVolumeDb(string deviceId, float volumeDb);
     bool ToggleMute(string deviceId);
     bool IsMuted(string deviceId);
+    bool SetMute(string deviceId, bool muted);
+    int SetAllMicrophonesMuted(bool muted);
+    bool AreAllMicrophonesMuted();
     bool ToggleDefaultMicrophoneMute();
     bool IsDefaultMicrophoneMuted();
+    DeviceProperties? GetDeviceProperties(string deviceId);
+    MicrophonePrivacyStatus GetPrivacyStatus();
+    ListenSettings? GetListenSettings(string deviceId);
+    DeviceFormat? GetDeviceDefaultFormat(string deviceId);
+    Task<List<DeviceFormat>> GetSupportedFormatsAsync(string deviceId, CancellationToken cancellationToken = default);
+    Task<bool> SetDeviceDefaultFormatAsync(string deviceId, DeviceFormat format, CancellationToken cancellationToken = default);
+    Task<bool> SetListenAsync(string deviceId, bool enabled, string? targetRenderId = null, CancellationToken cancellationToken = default);
+    MicrophoneBoost? GetMicrophoneBoost(string deviceId);
+    bool SetMicrophoneBoost(string deviceId, float levelDb);
+    bool? GetAutoGainControl(string deviceId);
+    bool SetAutoGainControl(string deviceId, bool enabled);
+    DeviceCapabilities GetCapabilities(string deviceId);
+    List<AudioEffect> GetAudioEffects(string deviceId);
+    bool SetAudioEffectEnabled(string deviceId, Guid effectId, bool enabled);
+    Task<MicTestStatus> RunMicTestAsync(string deviceId, TimeSpan duration, IProgress<MicTestStatus>? progress = null, CancellationToken cancellationToken = default);
+
+    // Plays the device's last mic test recording again; Failed when it has none
+    Task<MicTestStatus> ReplayMicTestAsync(string deviceId, IProgress<MicTestStatus>? progress = null, CancellationToken cancellationToken = default);
+
+    // Peaks (0..1) of the device's last mic test recording per 50 ms, growing while it records
+    double[] GetMicTestWaveform(string deviceId);
+    bool StartRecording(string deviceId, string filePath, int bitsPerSample = 16);
+    Task<RecordingResult?> StopRecordingAsync(string deviceId);
+    bool IsRecording(string deviceId);
+    List<CaptureSession> GetCaptureSessions(string deviceId);
+    bool IsDeviceInUse(string deviceId);
+    bool IsDeviceExclusivelyLocked(string deviceId);
+
+    // Endpoint peak in dBFS (-96..0), read on demand; keeps reporting while the endpoint is muted
+    double GetInputPeakDb(string deviceId);
+
+    // RMS in dBFS of the meter capture's latest window; -96 while muted or not captured
+    double GetInputRmsDb(string deviceId);
+
+    // Endpoint peak of each channel in dBFS, in channel order; empty when the meter can't be read
+    double[] GetInputChannelPeaksDb(string deviceId);
+    bool SetCaptureSessionMute(string deviceId, string sessionInstanceId, bool muted);
+    bool SetCaptureSessionVolume(string deviceId, string sessionInstanceId, float volume);
+
+    // Rebuilds callbacks and meter captures after resume from sleep, then raises DevicesChanged
+    void RecoverAfterResume();
+
+    // Render (playback) devices
+    List<OutputDevice> GetOutputDevices();
+    string? GetDefaultOutputDeviceId(Role role);
+    void SetOutputVolumeLevelScalar(string deviceId, float volumeLevelScalar);
+    bool ToggleOutputMute(string deviceId);
+    double GetOutputPeakLevel(string deviceId);
+    Task<bool> SetDefaultOutputDeviceAsync(string deviceId, CancellationToken cancellationToken = default);
 
     // Async methods to prevent UI thread blocking
     Task<List<MicrophoneDevice>> GetMicrophonesAsync(CancellationToken cancellationToken = default);
@@ -31,4 +99,5 @@ public interface IAudioDeviceService : IDisposable
     Task<bool> SetMicrophoneForRoleAsync(string deviceId, Role role, CancellationToken cancellationToken = default);
     Task<bool> ToggleMuteAsync(string deviceId, CancellationToken cancellationToken = default);
     Task<bool> ToggleDefaultMicrophoneMuteAsync(CancellationToken cancellationToken = default);
+    Task<bool> SetDeviceEnabledAsync(string deviceId, bool enabled, CancellationToken cancellationToken = default);
 }
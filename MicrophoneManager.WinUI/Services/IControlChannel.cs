@@ -0,0 +1,24 @@
+using MicrophoneManager.WinUI.Models;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Somewhere <see cref="ControlRequest"/>s can be run: the tray's own
+/// <see cref="ControlCommandDispatcher"/>, or the running tray reached over its control pipe from
+/// another process.
+/// </summary>
+public interface IControlChannel
+{
+    /// <summary>
+    /// Raised with the new state after devices, the default or a mute/volume level change.
+    /// </summary>
+    event EventHandler<ControlState>? StateChanged;
+
+    Task<ControlState> GetStateAsync();
+
+    /// <summary>
+    /// Runs a request. <see cref="ControlResponse.Data"/> is a <see cref="ControlState"/> for commands
+    /// that answer with the state, and a list of <see cref="ControlDevice"/> for <c>list</c>.
+    /// </summary>
+    Task<ControlResponse> DispatchAsync(ControlRequest request);
+}
@@ -0,0 +1,25 @@
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Named user preferences. Values that were never written read back as the supplied default.
+/// </summary>
+public interface IPreferencesStore
+{
+    string? GetString(string name);
+
+    /// <summary>
+    /// Writes a value; null removes it.
+    /// </summary>
+    void SetString(string name, string? value);
+
+    IReadOnlyList<string> GetStringList(string name);
+    void SetStringList(string name, IReadOnlyList<string> values);
+
+    bool GetBool(string name, bool defaultValue);
+    void SetBool(string name, bool value);
+
+    /// <summary>
+    /// Every stored value: a <see cref="string"/>, <see cref="bool"/> or <see cref="string"/> array.
+    /// </summary>
+    IReadOnlyDictionary<string, object> GetAll();
+}
@@ -0,0 +1,94 @@
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// The last 30 seconds of a microphone's peak level, for the sparkline under its meter. The
+/// metering thread adds every reading; each 100 ms slot keeps the loudest, so a slot that stays
+/// down at the floor is a dropout. A fixed ring buffer, so it never allocates once created.
+/// </summary>
+public sealed class LevelHistory
+{
+    public const int Capacity = 300;
+
+    public static readonly TimeSpan SlotLength = TimeSpan.FromMilliseconds(100);
+
+    private const double FloorDb = -96.0;
+
+    private readonly double[] _slots = new double[Capacity];
+    private readonly object _lock = new();
+    private int _next;
+    private int _count;
+    private long _currentSlot = -1;
+    private int _version;
+
+    /// <summary>
+    /// Goes up every time a new slot starts, so readers can skip copying an unchanged history.
+    /// </summary>
+    public int Version => Volatile.Read(ref _version);
+
+    public int Count
+    {
+        get
+        {
+            lock (_lock)
+            {
+                return _count;
+            }
+        }
+    }
+
+    /// <summary>
+    /// Adds a peak reading in dBFS taken at <paramref name="atUtc"/>.
+    /// </summary>
+    public void Add(double peakDb, DateTime atUtc)
+    {
+        peakDb = ObsMeterMath.ClampMeterDb(peakDb);
+        var slot = atUtc.Ticks / SlotLength.Ticks;
+
+        lock (_lock)
+        {
+            // A reading from the same slot, or from before it if the clock went back, joins it
+            if (_count > 0 && slot <= _currentSlot)
+            {
+                var last = (_next - 1 + Capacity) % Capacity;
+                _slots[last] = Math.Max(_slots[last], peakDb);
+                return;
+            }
+
+            _slots[_next] = peakDb;
+            _next = (_next + 1) % Capacity;
+            _count = Math.Min(_count + 1, Capacity);
+            _currentSlot = slot;
+            Interlocked.Increment(ref _version);
+        }
+    }
+
+    /// <summary>
+    /// The slots from oldest to newest.
+    /// </summary>
+    public double[] ToArray()
+    {
+        lock (_lock)
+        {
+            var levels = new double[_count];
+            var start = (_next - _count + Capacity) % Capacity;
+            for (var i = 0; i < _count; i++)
+            {
+                levels[i] = _slots[(start + i) % Capacity];
+            }
+
+            return levels;
+        }
+    }
+
+    public void Clear()
+    {
+        lock (_lock)
+        {
+            Array.Fill(_slots, FloorDb);
+            _next = 0;
+            _count = 0;
+            _currentSlot = -1;
+            Interlocked.Increment(ref _version);
+        }
+    }
+}
@@ -0,0 +1,230 @@
+using System.Collections.Concurrent;
+using System.Globalization;
+using Microsoft.Extensions.Logging;
+using Microsoft.Extensions.Logging.Abstractions;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Reads every microphone's level on a dedicated background thread, so the flyout's meters don't
+/// cost the UI thread anything but drawing. While a flyout is showing meters the thread polls the
+/// endpoint meters (IAudioMeterInformation) at <see cref="Rate"/>; with none showing it
+/// sleeps until one opens. Each pass publishes an immutable <see cref="MeterSnapshot"/> that the
+/// UI picks up on its own tick without taking a lock, and adds to each microphone's <see cref="LevelHistory"/>.
+/// The thread never enumerates devices; the UI hands it the microphones it lists through <see cref="SetDevices"/>.
+/// </summary>
+public sealed class LevelMeterService : IDisposable
+{
+    public const string RatePreference = "MeterRateHz";
+
+    /// <summary>
+    /// Polls per second while meters are on screen, unless set otherwise.
+    /// </summary>
+    public const int VisibleRate = 40;
+
+    public const int MinRate = 20;
+    public const int MaxRate = 60;
+
+    private readonly IAudioDeviceService _audioService;
+    private readonly IPreferencesStore? _preferences;
+    private readonly ManualResetEventSlim _wake = new(false);
+    private readonly CancellationTokenSource _shutdown = new();
+    private readonly Thread? _thread;
+    private MeterSnapshot _snapshot = MeterSnapshot.Empty;
+    private readonly ConcurrentDictionary<string, LevelHistory> _histories = new(StringComparer.OrdinalIgnoreCase);
+    private readonly ILogger<LevelMeterService> _logger;
+    private string[] _deviceIds = Array.Empty<string>();
+    private int _viewers;
+    private int _rate;
+    private bool _disposed;
+
+    public LevelMeterService(IAudioDeviceService audioService, IPreferencesStore? preferences = null, ILogger<LevelMeterService>? logger = null)
+    {
+        _audioService = audioService ?? throw new ArgumentNullException(nameof(audioService));
+        _preferences = preferences;
+        _logger = logger ?? NullLogger<LevelMeterService>.Instance;
+        _rate = int.TryParse(_preferences?.GetString(RatePreference), NumberStyles.Integer, CultureInfo.InvariantCulture, out var rate)
+            && rate is >= MinRate and <= MaxRate
+            ? rate
+            : VisibleRate;
+
+        // No background polling without a UI context (unit tests call Poll)
+        if (SynchronizationContext.Current != null)
+        {
+            _thread = new Thread(Run)
+            {
+                Name = "Level meter",
+                IsBackground = true,
+                Priority = ThreadPriority.BelowNormal,
+            };
+            _thread.Start();
+        }
+    }
+
+    /// <summary>
+    /// The levels from the latest poll. Safe to read from any thread.
+    /// </summary>
+    public MeterSnapshot Snapshot => Volatile.Read(ref _snapshot);
+
+    /// <summary>
+    /// The microphone's recent peaks, or null before it has been polled. Safe to read from any thread.
+    /// </summary>
+    public LevelHistory? GetHistory(string deviceId)
+    {
+        return _histories.TryGetValue(deviceId, out var history) ? history : null;
+    }
+
+    /// <summary>
+    /// Sets the microphones to poll, as listed by the UI. Safe to call from any thread.
+    /// </summary>
+    public void SetDevices(IReadOnlyCollection<string> deviceIds)
+    {
+        var ids = deviceIds.ToArray();
+        Volatile.Write(ref _deviceIds, ids);
+        foreach (var id in _histories.Keys.Except(ids, StringComparer.OrdinalIgnoreCase).ToList())
+        {
+            _histories.TryRemove(id, out _);
+        }
+    }
+
+    /// <summary>
+    /// Polls per second while meters are on screen. Read from preferences once; takes effect from the next poll.
+    /// </summary>
+    public int Rate
+    {
+        get => Volatile.Read(ref _rate);
+        set
+        {
+            var rate = Math.Clamp(value, MinRate, MaxRate);
+            Volatile.Write(ref _rate, rate);
+            _preferences?.SetString(RatePreference, rate == VisibleRate ? null : rate.ToString(CultureInfo.InvariantCulture));
+        }
+    }
+
+    /// <summary>
+    /// Whether anything is showing meters, and so whether the thread is polling.
+    /// </summary>
+    public bool IsPolling => Volatile.Read(ref _viewers) > 0;
+
+    /// <summary>
+    /// Starts polling, or keeps it going, for a view that shows meters. Pair with <see cref="RemoveViewer"/>.
+    /// </summary>
+    public void AddViewer()
+    {
+        if (Interlocked.Increment(ref _viewers) == 1)
+        {
+            _wake.Set();
+        }
+    }
+
+    /// <summary>
+    /// Stops polling once the last view showing meters is hidden.
+    /// </summary>
+    public void RemoveViewer()
+    {
+        if (Interlocked.Decrement(ref _viewers) <= 0)
+        {
+            Interlocked.Exchange(ref _viewers, 0);
+            _wake.Reset();
+
+            // Don't show stale levels when the meters come back, nor a history with a hole in it
+            Volatile.Write(ref _snapshot, MeterSnapshot.Empty);
+            foreach (var history in _histories.Values)
+            {
+                history.Clear();
+            }
+        }
+    }
+
+    /// <summary>
+    /// Reads each listed microphone's level once and publishes the snapshot. Called by the thread.
+    /// </summary>
+    public void Poll()
+    {
+        if (_disposed) return;
+
+        var deviceIds = Volatile.Read(ref _deviceIds);
+        var nowUtc = DateTime.UtcNow;
+        var readings = new Dictionary<string, MeterReading>(deviceIds.Length, StringComparer.OrdinalIgnoreCase);
+        foreach (var id in deviceIds)
+        {
+            // A mono microphone's one channel is the same as its overall peak
+            var channels = _audioService.GetInputChannelPeaksDb(id);
+            readings[id] = new MeterReading(
+                _audioService.GetInputPeakDb(id),
+                _audioService.GetInputRmsDb(id),
+                channels.Length > 1 ? channels : null);
+            _histories.GetOrAdd(id, _ => new LevelHistory()).Add(readings[id].PeakDb, nowUtc);
+        }
+
+        Volatile.Write(ref _snapshot, new MeterSnapshot(readings, nowUtc));
+    }
+
+    private void Run()
+    {
+        var token = _shutdown.Token;
+        try
+        {
+            while (!token.IsCancellationRequested)
+            {
+                _wake.Wait(token);
+
+                try
+                {
+                    Poll();
+                }
+                catch (Exception ex)
+                {
+                    _logger.LogWarning(ex, "Level metering failed");
+                }
+
+                token.WaitHandle.WaitOne(TimeSpan.FromSeconds(1.0 / Rate));
+            }
+        }
+        catch (Exception ex) when (ex is OperationCanceledException or ObjectDisposedException)
+        {
+            // Shutting down
+        }
+    }
+
+    public void Dispose()
+    {
+        if (_disposed) return;
+        _disposed = true;
+
+        try { _shutdown.Cancel(); } catch { }
+        try { _thread?.Join(TimeSpan.FromSeconds(1)); } catch { }
+        _shutdown.Dispose();
+        _wake.Dispose();
+    }
+}
+
+/// <summary>
+/// A microphone's level at one poll, in dBFS. The peak comes from the endpoint meter, which keeps
+/// reporting while the microphone is muted; the RMS from the meter capture, which goes silent.
+/// Multi-channel microphones also carry the endpoint peak of each channel.
+/// </summary>
+public readonly record struct MeterReading(double PeakDb, double RmsDb, IReadOnlyList<double>? ChannelPeaksDb = null);
+
+/// <summary>
+/// Levels of all active microphones from one poll. Never changed after it's published.
+/// </summary>
+public sealed class MeterSnapshot
+{
+    public static readonly MeterSnapshot Empty = new(new Dictionary<string, MeterReading>(), DateTime.MinValue);
+
+    private readonly IReadOnlyDictionary<string, MeterReading> _readings;
+
+    public MeterSnapshot(IReadOnlyDictionary<string, MeterReading> readings, DateTime takenAtUtc)
+    {
+        _readings = readings;
+        TakenAtUtc = takenAtUtc;
+    }
+
+    public DateTime TakenAtUtc { get; }
+
+    public bool TryGetReading(string deviceId, out MeterReading reading)
+    {
+        return _readings.TryGetValue(deviceId, out reading);
+    }
+}
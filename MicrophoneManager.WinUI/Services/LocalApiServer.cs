@@ -0,0 +1,386 @@
+using System.Collections.Concurrent;
+using System.Net;
+using System.Net.WebSockets;
+using System.Security.Cryptography;
+using System.Text;
+using System.Text.Json;
+using Microsoft.Extensions.Logging;
+using Microsoft.Extensions.Logging.Abstractions;
+using MicrophoneManager.WinUI.Models;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Opt-in HTTP + WebSocket endpoint on localhost for dashboards and home-automation tools.
+/// Every request needs the API token, either as <c>Authorization: Bearer &lt;token&gt;</c> or, for
+/// browsers opening a WebSocket, as <c>?token=&lt;token&gt;</c>.
+/// <list type="bullet">
+/// <item><c>GET /api/state</c> returns the <see cref="ControlState"/>.</item>
+/// <item><c>POST /api/command</c> takes a <see cref="ControlRequest"/> and returns a <see cref="ControlResponse"/>.</item>
+/// <item><c>GET /api/events</c> upgrades to a WebSocket that receives the state on every change,
+/// each <see cref="ControlEvent"/> as it happens, and accepts the same requests as <c>/api/command</c>.</item>
+/// </list>
+/// </summary>
+public sealed class LocalApiServer : IDisposable
+{
+    public const string EnabledPreference = "LocalApiEnabled";
+    public const string PortPreference = "LocalApiPort";
+    public const string TokenPreference = "LocalApiToken";
+    public const int DefaultPort = 47813;
+
+    private const int MaxRequestBytes = 64 * 1024;
+
+    // A client that stops reading would otherwise hold up every broadcast queued behind it
+    private static readonly TimeSpan SendTimeout = TimeSpan.FromSeconds(5);
+
+    private readonly ControlCommandDispatcher _dispatcher;
+    private readonly IPreferencesStore _preferences;
+    private readonly ILogger<LocalApiServer> _logger;
+    private readonly ConcurrentDictionary<WebSocket, SemaphoreSlim> _sockets = new();
+    private HttpListener? _listener;
+    private CancellationTokenSource? _shutdown;
+    private bool _disposed;
+
+    public LocalApiServer(ControlCommandDispatcher dispatcher, IPreferencesStore preferences, ILogger<LocalApiServer>? logger = null)
+    {
+        _dispatcher = dispatcher ?? throw new ArgumentNullException(nameof(dispatcher));
+        _preferences = preferences ?? throw new ArgumentNullException(nameof(preferences));
+        _logger = logger ?? NullLogger<LocalApiServer>.Instance;
+    }
+
+    public bool IsEnabled
+    {
+        get => _preferences.GetBool(EnabledPreference, false);
+        set
+        {
+            _preferences.SetBool(EnabledPreference, value);
+            if (value) Start(); else Stop();
+        }
+    }
+
+    public bool IsRunning => _listener?.IsListening == true;
+
+    public int Port =>
+        int.TryParse(_preferences.GetString(PortPreference), out var port) && port is > 0 and <= 65535 ? port : DefaultPort;
+
+    /// <summary>
+    /// Base address clients connect to.
+    /// </summary>
+    public string Address => $"http://localhost:{Port}/";
+
+    /// <summary>
+    /// The token clients must present; created on first use.
+    /// </summary>
+    public string Token
+    {
+        get
+        {
+            var token = _preferences.GetString(TokenPreference);
+            if (string.IsNullOrEmpty(token))
+            {
+                token = Convert.ToHexString(RandomNumberGenerator.GetBytes(24)).ToLowerInvariant();
+                _preferences.SetString(TokenPreference, token);
+            }
+
+            return token;
+        }
+    }
+
+    /// <summary>
+    /// Replaces the token, disconnecting nobody already connected but refusing the old token from now on.
+    /// </summary>
+    public string RegenerateToken()
+    {
+        _preferences.SetString(TokenPreference, null);
+        return Token;
+    }
+
+    /// <summary>
+    /// Starts listening if the preference is on. Safe to call repeatedly.
+    /// </summary>
+    public void Start()
+    {
+        if (_disposed || IsRunning || !IsEnabled) return;
+
+        // "localhost" binds loopback only and, unlike a "+" or "*" prefix, needs no URL ACL
+        var listener = new HttpListener();
+        listener.Prefixes.Add(Address);
+
+        try
+        {
+            listener.Start();
+        }
+        catch (HttpListenerException ex)
+        {
+            _logger.LogWarning(ex, "Local API couldn't listen on {Address}", Address);
+            listener.Close();
+            return;
+        }
+
+        _listener = listener;
+        _shutdown = new CancellationTokenSource();
+        _dispatcher.StateChanged += OnStateChanged;
+        _dispatcher.EventRaised += OnEventRaised;
+        _ = Task.Run(() => AcceptLoopAsync(listener, _shutdown.Token));
+    }
+
+    public void Stop()
+    {
+        if (_listener == null) return;
+
+        _dispatcher.StateChanged -= OnStateChanged;
+        _dispatcher.EventRaised -= OnEventRaised;
+        try { _shutdown?.Cancel(); } catch { }
+
+        foreach (var socket in _sockets.Keys)
+        {
+            try { socket.Abort(); } catch { }
+        }
+
+        _sockets.Clear();
+
+        try { _listener.Close(); } catch { }
+        _listener = null;
+        _shutdown = null;
+    }
+
+    /// <summary>
+    /// Constant-time comparison so the token can't be guessed a character at a time.
+    /// </summary>
+    public bool IsAuthorized(string? authorizationHeader, string? queryToken)
+    {
+        var presented = authorizationHeader != null && authorizationHeader.StartsWith("Bearer ", StringComparison.OrdinalIgnoreCase)
+            ? authorizationHeader["Bearer ".Length..].Trim()
+            : queryToken;
+        if (string.IsNullOrEmpty(presented)) return false;
+
+        return CryptographicOperations.FixedTimeEquals(Encoding.UTF8.GetBytes(presented), Encoding.UTF8.GetBytes(Token));
+    }
+
+    private async Task AcceptLoopAsync(HttpListener listener, CancellationToken cancellationToken)
+    {
+        while (!cancellationToken.IsCancellationRequested && listener.IsListening)
+        {
+            HttpListenerContext context;
+            try
+            {
+                context = await listener.GetContextAsync();
+            }
+            catch (Exception) when (cancellationToken.IsCancellationRequested || !listener.IsListening)
+            {
+                return;
+            }
+            catch (Exception ex)
+            {
+                _logger.LogWarning(ex, "Local API accept failed");
+                continue;
+            }
+
+            _ = Task.Run(() => HandleAsync(context, cancellationToken));
+        }
+    }
+
+    private async Task HandleAsync(HttpListenerContext context, CancellationToken cancellationToken)
+    {
+        var request = context.Request;
+        var response = context.Response;
+
+        try
+        {
+            if (!IsAuthorized(request.Headers["Authorization"], request.QueryString["token"]))
+            {
+                await WriteJsonAsync(response, HttpStatusCode.Unauthorized, ControlResponse.Failure("Missing or wrong token."));
+                return;
+            }
+
+            switch (request.HttpMethod, request.Url?.AbsolutePath.TrimEnd('/'))
+            {
+                case ("GET", "/api/state"):
+                    await WriteJsonAsync(response, HttpStatusCode.OK, ControlResponse.Success(_dispatcher.GetState()));
+                    break;
+
+                case ("POST", "/api/command"):
+                    var body = request.ContentLength64 <= MaxRequestBytes
+                        ? await ReadBodyAsync(request.InputStream, cancellationToken)
+                        : null;
+                    if (body == null)
+                    {
+                        await WriteJsonAsync(response, HttpStatusCode.RequestEntityTooLarge, ControlResponse.Failure("Request too large."));
+                        break;
+                    }
+
+                    var result = await _dispatcher.DispatchJsonAsync(body);
+                    await WriteRawJsonAsync(response, HttpStatusCode.OK, result);
+                    break;
+
+                case ("GET", "/api/events") when request.IsWebSocketRequest:
+                    var webSocketContext = await context.AcceptWebSocketAsync(subProtocol: null);
+                    await ServeWebSocketAsync(webSocketContext.WebSocket, cancellationToken);
+                    break;
+
+                default:
+                    await WriteJsonAsync(response, HttpStatusCode.NotFound, ControlResponse.Failure("Not found."));
+                    break;
+            }
+        }
+        catch (Exception ex) when (ex is not OperationCanceledException)
+        {
+            _logger.LogWarning(ex, "Local API request failed");
+            try { response.Abort(); } catch { }
+        }
+    }
+
+    private async Task ServeWebSocketAsync(WebSocket socket, CancellationToken cancellationToken)
+    {
+        var sendLock = new SemaphoreSlim(1, 1);
+        _sockets[socket] = sendLock;
+
+        try
+        {
+            // New subscribers get the current state straight away rather than waiting for a change
+            await SendAsync(socket, sendLock, JsonSerializer.Serialize(ControlResponse.Success(_dispatcher.GetState()), ControlJson.Options), cancellationToken);
+
+            var buffer = new byte[MaxRequestBytes];
+            while (socket.State == WebSocketState.Open && !cancellationToken.IsCancellationRequested)
+            {
+                var message = await ReceiveTextAsync(socket, buffer, cancellationToken);
+                if (message == null) break;
+
+                var reply = await _dispatcher.DispatchJsonAsync(message);
+                await SendAsync(socket, sendLock, reply, cancellationToken);
+            }
+
+            if (socket.State == WebSocketState.CloseReceived)
+            {
+                await socket.CloseOutputAsync(WebSocketCloseStatus.NormalClosure, null, CancellationToken.None);
+            }
+        }
+        finally
+        {
+            _sockets.TryRemove(socket, out _);
+            socket.Dispose();
+
+            // Sends already queued see the closed socket and return; wait them out before disposing
+            if (await sendLock.WaitAsync(SendTimeout * 2))
+            {
+                sendLock.Dispose();
+            }
+        }
+    }
+
+    /// <summary>
+    /// Reads a request body of at most <see cref="MaxRequestBytes"/>; null when it's longer. Chunked
+    /// requests don't declare a length, so the limit is enforced while reading.
+    /// </summary>
+    public static async Task<string?> ReadBodyAsync(Stream stream, CancellationToken cancellationToken)
+    {
+        var buffer = new byte[MaxRequestBytes + 1];
+        var count = 0;
+        int read;
+        while (count < buffer.Length && (read = await stream.ReadAsync(buffer.AsMemory(count), cancellationToken)) > 0)
+        {
+            count += read;
+        }
+
+        return count > MaxRequestBytes ? null : Encoding.UTF8.GetString(buffer, 0, count);
+    }
+
+    private static async Task<string?> ReceiveTextAsync(WebSocket socket, byte[] buffer, CancellationToken cancellationToken)
+    {
+        var count = 0;
+        while (true)
+        {
+            if (count == buffer.Length)
+            {
+                await socket.CloseAsync(WebSocketCloseStatus.MessageTooBig, null, cancellationToken);
+                return null;
+            }
+
+            var result = await socket.ReceiveAsync(new ArraySegment<byte>(buffer, count, buffer.Length - count), cancellationToken);
+            if (result.MessageType == WebSocketMessageType.Close) return null;
+
+            count += result.Count;
+            if (result.EndOfMessage) return Encoding.UTF8.GetString(buffer, 0, count);
+        }
+    }
+
+    private void OnStateChanged(object? sender, ControlState state)
+    {
+        Broadcast(ControlResponse.Success(state));
+    }
+
+    private void OnEventRaised(object? sender, ControlEvent controlEvent)
+    {
+        Broadcast(ControlResponse.FromEvent(controlEvent));
+    }
+
+    private void Broadcast(ControlResponse message)
+    {
+        if (_sockets.IsEmpty) return;
+
+        var json = JsonSerializer.Serialize(message, ControlJson.Options);
+        foreach (var (socket, sendLock) in _sockets)
+        {
+            _ = SendAsync(socket, sendLock, json, CancellationToken.None);
+        }
+    }
+
+    private static async Task SendAsync(WebSocket socket, SemaphoreSlim sendLock, string json, CancellationToken cancellationToken)
+    {
+        using var timeout = CancellationTokenSource.CreateLinkedTokenSource(cancellationToken);
+        timeout.CancelAfter(SendTimeout);
+
+        // WebSocket allows one send at a time; replies and pushed state can overlap
+        try
+        {
+            await sendLock.WaitAsync(timeout.Token);
+        }
+        catch (Exception ex) when (ex is OperationCanceledException or ObjectDisposedException)
+        {
+            // Stuck behind a send that's timing out, or the socket has already gone
+            return;
+        }
+
+        try
+        {
+            if (socket.State != WebSocketState.Open) return;
+            await socket.SendAsync(Encoding.UTF8.GetBytes(json), WebSocketMessageType.Text, endOfMessage: true, timeout.Token);
+        }
+        catch (OperationCanceledException) when (!cancellationToken.IsCancellationRequested)
+        {
+            // The client stopped reading; drop it rather than let it hold up everyone else
+            socket.Abort();
+        }
+        catch (Exception ex) when (ex is WebSocketException or ObjectDisposedException)
+        {
+            // The receive loop notices the broken socket and cleans up
+        }
+        finally
+        {
+            sendLock.Release();
+        }
+    }
+
+    private static Task WriteJsonAsync(HttpListenerResponse response, HttpStatusCode status, ControlResponse body)
+    {
+        return WriteRawJsonAsync(response, status, JsonSerializer.Serialize(body, ControlJson.Options));
+    }
+
+    private static async Task WriteRawJsonAsync(HttpListenerResponse response, HttpStatusCode status, string json)
+    {
+        var bytes = Encoding.UTF8.GetBytes(json);
+        response.StatusCode = (int)status;
+        response.ContentType = "application/json; charset=utf-8";
+        response.ContentLength64 = bytes.Length;
+        await response.OutputStream.WriteAsync(bytes);
+        response.Close();
+    }
+
+    public void Dispose()
+    {
+        if (_disposed) return;
+        _disposed = true;
+
+        Stop();
+    }
+}
@@ -0,0 +1,51 @@
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Smooths a level in dBFS the way a hardware meter's needle moves: it rises towards louder input
+/// with the attack time constant and falls towards quieter input with the decay time constant.
+/// An attack of 0 ms follows increases instantly, which is what OBS does.
+/// </summary>
+public sealed class MeterBallistics
+{
+    private const double FloorDb = -96.0;
+
+    public MeterBallistics(double attackMs, double decayMs)
+    {
+        AttackMs = Math.Max(0, attackMs);
+        DecayMs = Math.Max(0, decayMs);
+    }
+
+    public double AttackMs { get; }
+    public double DecayMs { get; }
+
+    /// <summary>
+    /// The smoothed level in dBFS.
+    /// </summary>
+    public double LevelDb { get; private set; } = FloorDb;
+
+    /// <summary>
+    /// Moves the level towards <paramref name="inputDb"/> for <paramref name="elapsedMs"/> and returns it.
+    /// </summary>
+    public double Update(double inputDb, double elapsedMs)
+    {
+        inputDb = ObsMeterMath.ClampMeterDb(inputDb);
+
+        var timeConstantMs = inputDb >= LevelDb ? AttackMs : DecayMs;
+        if (timeConstantMs <= 0)
+        {
+            LevelDb = inputDb;
+        }
+        else if (elapsedMs > 0)
+        {
+            var alpha = 1.0 - Math.Exp(-elapsedMs / timeConstantMs);
+            LevelDb += (inputDb - LevelDb) * alpha;
+        }
+
+        return LevelDb;
+    }
+
+    public void Reset()
+    {
+        LevelDb = FloorDb;
+    }
+}
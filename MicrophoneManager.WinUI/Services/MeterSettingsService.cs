@@ -0,0 +1,110 @@
+using System.Globalization;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// What the level meters in the flyout show.
+/// </summary>
+public enum MeterMode
+{
+    /// <summary>
+    /// Sample peaks; shows every transient, but jumps around with speech.
+    /// </summary>
+    Peak,
+
+    /// <summary>
+    /// Short-window RMS; follows loudness, so it is steadier while setting the gain.
+    /// </summary>
+    Rms,
+
+    /// <summary>
+    /// RMS as the solid bar with the peak drawn faintly behind it, like a mixing desk.
+    /// </summary>
+    PeakAndRms,
+}
+
+/// <summary>
+/// How the flyout's level meters are drawn: which level they show and how quickly the bar rises
+/// and falls. Attack and decay are time constants; 0 ms attack follows every increase at once.
+/// </summary>
+public sealed class MeterSettingsService
+{
+    public const string ModePreference = "MeterMode";
+    public const string AttackPreference = "MeterAttackMs";
+    public const string DecayPreference = "MeterDecayMs";
+    public const string HistoryPreference = "MeterHistory";
+
+    public const int DefaultAttackMs = 0;
+    public const int DefaultDecayMs = 300;
+
+    private const int MaxTimeConstantMs = 5000;
+
+    private readonly IPreferencesStore _preferences;
+
+    /// <summary>
+    /// Raised after any of the settings changes through this service.
+    /// </summary>
+    public event EventHandler? Changed;
+
+    public MeterSettingsService(IPreferencesStore preferences)
+    {
+        _preferences = preferences ?? throw new ArgumentNullException(nameof(preferences));
+    }
+
+    public MeterMode Mode
+    {
+        get => Enum.TryParse<MeterMode>(_preferences.GetString(ModePreference), ignoreCase: true, out var mode) && Enum.IsDefined(mode)
+            ? mode
+            : MeterMode.Peak;
+        set
+        {
+            _preferences.SetString(ModePreference, value == MeterMode.Peak ? null : value.ToString());
+            Changed?.Invoke(this, EventArgs.Empty);
+        }
+    }
+
+    /// <summary>
+    /// Time constant for the bar rising, in milliseconds.
+    /// </summary>
+    public int AttackMs
+    {
+        get => ReadTimeConstant(AttackPreference, DefaultAttackMs);
+        set => WriteTimeConstant(AttackPreference, value);
+    }
+
+    /// <summary>
+    /// Time constant for the bar falling, in milliseconds.
+    /// </summary>
+    public int DecayMs
+    {
+        get => ReadTimeConstant(DecayPreference, DefaultDecayMs);
+        set => WriteTimeConstant(DecayPreference, value);
+    }
+
+    /// <summary>
+    /// Whether each microphone shows the last 30 seconds of its level under the meter.
+    /// </summary>
+    public bool ShowHistory
+    {
+        get => _preferences.GetBool(HistoryPreference, false);
+        set
+        {
+            if (value == ShowHistory) return;
+            _preferences.SetBool(HistoryPreference, value);
+            Changed?.Invoke(this, EventArgs.Empty);
+        }
+    }
+
+    private int ReadTimeConstant(string preference, int defaultMs)
+    {
+        return int.TryParse(_preferences.GetString(preference), NumberStyles.Integer, CultureInfo.InvariantCulture, out var ms) && ms is >= 0 and <= MaxTimeConstantMs
+            ? ms
+            : defaultMs;
+    }
+
+    private void WriteTimeConstant(string preference, int ms)
+    {
+        _preferences.SetString(preference, Math.Clamp(ms, 0, MaxTimeConstantMs).ToString(CultureInfo.InvariantCulture));
+        Changed?.Invoke(this, EventArgs.Empty);
+    }
+}
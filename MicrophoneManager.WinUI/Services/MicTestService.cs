@@ -0,0 +1,192 @@
+using Microsoft.Extensions.Logging;
+using MicrophoneManager.WinUI.Models;
+using NAudio.CoreAudioApi;
+using NAudio.Wave;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Records a few seconds from a microphone and plays the buffer back on the default
+/// render device, so users can hear whether a mic actually works. The last recording is kept
+/// so it can be played again, along with its <see cref="MicTestWaveform"/>.
+/// </summary>
+public class MicTestService
+{
+    private readonly ILogger<MicTestService> _logger;
+    private readonly SemaphoreSlim _testGate = new(1, 1);
+    private readonly object _recordingLock = new();
+    private string? _recordingDeviceId;
+    private MicTestWaveform? _waveform;
+    private byte[]? _recording;
+    private WaveFormat? _recordingFormat;
+
+    public MicTestService(ILogger<MicTestService> logger)
+    {
+        _logger = logger ?? throw new ArgumentNullException(nameof(logger));
+    }
+
+    /// <summary>
+    /// Runs one test. Only one test runs at a time; a second call while busy returns
+    /// <see cref="MicTestStatus.Failed"/> immediately.
+    /// </summary>
+    public async Task<MicTestStatus> RunAsync(string deviceId, TimeSpan duration, IProgress<MicTestStatus>? progress = null, CancellationToken cancellationToken = default)
+    {
+        if (!await _testGate.WaitAsync(0, cancellationToken))
+        {
+            return MicTestStatus.Failed;
+        }
+
+        try
+        {
+            progress?.Report(MicTestStatus.Recording);
+            var (buffer, format) = await RecordAsync(deviceId, duration, cancellationToken);
+            lock (_recordingLock)
+            {
+                _recording = buffer;
+                _recordingFormat = format;
+            }
+
+            progress?.Report(MicTestStatus.PlayingBack);
+            await PlayBackAsync(buffer, format, cancellationToken);
+
+            progress?.Report(MicTestStatus.Completed);
+            return MicTestStatus.Completed;
+        }
+        catch (OperationCanceledException)
+        {
+            progress?.Report(MicTestStatus.Idle);
+            return MicTestStatus.Idle;
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Microphone test failed for {DeviceId}", deviceId);
+            progress?.Report(MicTestStatus.Failed);
+            return MicTestStatus.Failed;
+        }
+        finally
+        {
+            _testGate.Release();
+        }
+    }
+
+    /// <summary>
+    /// Plays the last test recording from <paramref name="deviceId"/> again. Returns
+    /// <see cref="MicTestStatus.Failed"/> when there is none or another test is running.
+    /// </summary>
+    public async Task<MicTestStatus> ReplayAsync(string deviceId, IProgress<MicTestStatus>? progress = null, CancellationToken cancellationToken = default)
+    {
+        byte[]? buffer;
+        WaveFormat? format;
+        lock (_recordingLock)
+        {
+            var isSameDevice = string.Equals(_recordingDeviceId, deviceId, StringComparison.OrdinalIgnoreCase);
+            buffer = isSameDevice ? _recording : null;
+            format = isSameDevice ? _recordingFormat : null;
+        }
+
+        if (buffer == null || format == null || !await _testGate.WaitAsync(0, cancellationToken))
+        {
+            return MicTestStatus.Failed;
+        }
+
+        try
+        {
+            progress?.Report(MicTestStatus.PlayingBack);
+            await PlayBackAsync(buffer, format, cancellationToken);
+
+            progress?.Report(MicTestStatus.Completed);
+            return MicTestStatus.Completed;
+        }
+        catch (OperationCanceledException)
+        {
+            progress?.Report(MicTestStatus.Idle);
+            return MicTestStatus.Idle;
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Replaying the microphone test failed for {DeviceId}", deviceId);
+            progress?.Report(MicTestStatus.Failed);
+            return MicTestStatus.Failed;
+        }
+        finally
+        {
+            _testGate.Release();
+        }
+    }
+
+    /// <summary>
+    /// The waveform of the last test recording from <paramref name="deviceId"/>, growing while it
+    /// records; empty when that device hasn't been tested.
+    /// </summary>
+    public double[] GetWaveform(string deviceId)
+    {
+        lock (_recordingLock)
+        {
+            return _waveform != null && string.Equals(_recordingDeviceId, deviceId, StringComparison.OrdinalIgnoreCase)
+                ? _waveform.ToArray()
+                : Array.Empty<double>();
+        }
+    }
+
+    private async Task<(byte[] Buffer, WaveFormat Format)> RecordAsync(string deviceId, TimeSpan duration, CancellationToken cancellationToken)
+    {
+        using var enumerator = new MMDeviceEnumerator();
+        var device = enumerator.GetDevice(deviceId);
+
+        using var capture = new WasapiCapture(device, true, 20);
+        using var buffer = new MemoryStream();
+        var stopped = new TaskCompletionSource<Exception?>(TaskCreationOptions.RunContinuationsAsynchronously);
+
+        // A new test replaces the last recording, whichever device it was from
+        var waveform = new MicTestWaveform(capture.WaveFormat);
+        lock (_recordingLock)
+        {
+            _recordingDeviceId = deviceId;
+            _waveform = waveform;
+            _recording = null;
+            _recordingFormat = null;
+        }
+
+        capture.DataAvailable += (s, e) =>
+        {
+            buffer.Write(e.Buffer, 0, e.BytesRecorded);
+            waveform.Add(e.Buffer, e.BytesRecorded);
+        };
+        capture.RecordingStopped += (s, e) => stopped.TrySetResult(e.Exception);
+
+        capture.StartRecording();
+        try
+        {
+            await Task.Delay(duration, cancellationToken);
+        }
+        finally
+        {
+            capture.StopRecording();
+            var error = await stopped.Task;
+            if (error != null && !cancellationToken.IsCancellationRequested)
+            {
+                throw error;
+            }
+        }
+
+        return (buffer.ToArray(), capture.WaveFormat);
+    }
+
+    private static async Task PlayBackAsync(byte[] buffer, WaveFormat format, CancellationToken cancellationToken)
+    {
+        using var source = new RawSourceWaveStream(new MemoryStream(buffer), format);
+        using var output = new WasapiOut(AudioClientShareMode.Shared, 100);
+        var stopped = new TaskCompletionSource<Exception?>(TaskCreationOptions.RunContinuationsAsynchronously);
+
+        output.PlaybackStopped += (s, e) => stopped.TrySetResult(e.Exception);
+        output.Init(source);
+        output.Play();
+
+        using (cancellationToken.Register(() => output.Stop()))
+        {
+            var error = await stopped.Task;
+            cancellationToken.ThrowIfCancellationRequested();
+            if (error != null) throw error;
+        }
+    }
+}
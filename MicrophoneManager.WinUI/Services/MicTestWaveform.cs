@@ -0,0 +1,89 @@
+using NAudio.Wave;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// The outline of a microphone test recording for the flyout: the loudest sample of every
+/// <see cref="SlotLength"/>, as linear amplitude 0..1 across all channels. Built from the capture
+/// callbacks while the test records and read by the UI, so it locks around both.
+/// </summary>
+public sealed class MicTestWaveform
+{
+    public static readonly TimeSpan SlotLength = TimeSpan.FromMilliseconds(50);
+
+    private static readonly Guid IeeeFloatSubFormat = new("00000003-0000-0010-8000-00aa00389b71");
+
+    private readonly WaveFormat _format;
+    private readonly bool _isFloat;
+    private readonly int _framesPerSlot;
+    private readonly List<double> _slots = new();
+    private readonly object _lock = new();
+    private double _slotPeak;
+    private int _slotFrames;
+
+    public MicTestWaveform(WaveFormat format)
+    {
+        _format = format ?? throw new ArgumentNullException(nameof(format));
+        _framesPerSlot = Math.Max(1, (int)(format.SampleRate * SlotLength.TotalSeconds));
+        _isFloat = format.Encoding == WaveFormatEncoding.IeeeFloat ||
+                   (format is WaveFormatExtensible ext && ext.SubFormat == IeeeFloatSubFormat);
+    }
+
+    /// <summary>
+    /// Adds captured bytes in the recording's format. Partial frames at the end are ignored.
+    /// </summary>
+    public void Add(byte[] buffer, int count)
+    {
+        var bytesPerSample = _format.BitsPerSample / 8;
+        var channels = Math.Max(1, _format.Channels);
+        if (bytesPerSample == 0) return;
+
+        var frameBytes = bytesPerSample * channels;
+        lock (_lock)
+        {
+            for (var frame = 0; frame + frameBytes <= count; frame += frameBytes)
+            {
+                for (var channel = 0; channel < channels; channel++)
+                {
+                    var sample = Math.Abs(ReadSample(buffer, frame + (channel * bytesPerSample)));
+                    _slotPeak = Math.Max(_slotPeak, Math.Min(1.0, sample));
+                }
+
+                if (++_slotFrames == _framesPerSlot)
+                {
+                    _slots.Add(_slotPeak);
+                    _slotPeak = 0;
+                    _slotFrames = 0;
+                }
+            }
+        }
+    }
+
+    /// <summary>
+    /// The slots recorded so far, oldest first.
+    /// </summary>
+    public double[] ToArray()
+    {
+        lock (_lock)
+        {
+            return _slots.ToArray();
+        }
+    }
+
+    private double ReadSample(byte[] buffer, int offset)
+    {
+        if (_isFloat)
+        {
+            return _format.BitsPerSample == 64 ? BitConverter.ToDouble(buffer, offset) : BitConverter.ToSingle(buffer, offset);
+        }
+
+        return _format.BitsPerSample switch
+        {
+            8 => (buffer[offset] - 128) / 128.0,
+            16 => BitConverter.ToInt16(buffer, offset) / 32768.0,
+            24 => ((buffer[offset] << 8) | (buffer[offset + 1] << 16) | (buffer[offset + 2] << 24)) / 2147483648.0,
+            32 => BitConverter.ToInt32(buffer, offset) / 2147483648.0,
+            _ => 0.0,
+        };
+    }
+}
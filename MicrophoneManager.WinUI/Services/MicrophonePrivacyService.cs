@@ -0,0 +1,65 @@
+using Microsoft.Win32;
+using MicrophoneManager.WinUI.Models;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Reads the Windows microphone privacy settings from the capability access manager's ConsentStore.
+/// </summary>
+public static class MicrophonePrivacyService
+{
+    private const string ConsentStorePath = @"Software\Microsoft\Windows\CurrentVersion\CapabilityAccessManager\ConsentStore\microphone";
+    private const string NonPackagedSubKey = "NonPackaged";
+
+    public static MicrophonePrivacyStatus GetStatus()
+    {
+        try
+        {
+            using var machineKey = Registry.LocalMachine.OpenSubKey(ConsentStorePath, false);
+            using var userKey = Registry.CurrentUser.OpenSubKey(ConsentStorePath, false);
+            using var nonPackagedKey = userKey?.OpenSubKey(NonPackagedSubKey, false);
+
+            var appAccess = new Dictionary<string, bool>(StringComparer.OrdinalIgnoreCase);
+            ReadAppConsents(userKey, appAccess, nameTransform: null);
+
+            // Desktop app keys are executable paths with '\' replaced by '#'
+            ReadAppConsents(nonPackagedKey, appAccess, nameTransform: name => name.Replace('#', '\\'));
+
+            return new MicrophonePrivacyStatus
+            {
+                IsDeviceAccessAllowed = IsAllowed(machineKey),
+                IsUserAccessAllowed = IsAllowed(userKey),
+                IsDesktopAppAccessAllowed = IsAllowed(nonPackagedKey),
+                AppAccess = appAccess
+            };
+        }
+        catch
+        {
+            // Unreadable settings shouldn't produce a false "access disabled" warning
+            return MicrophonePrivacyStatus.Allowed;
+        }
+    }
+
+    /// <summary>
+    /// ConsentStore keys hold "Allow" or "Deny" in their Value; a missing key or value means allowed.
+    /// </summary>
+    internal static bool IsAllowed(RegistryKey? key)
+    {
+        return !string.Equals(key?.GetValue("Value") as string, "Deny", StringComparison.OrdinalIgnoreCase);
+    }
+
+    private static void ReadAppConsents(RegistryKey? parent, Dictionary<string, bool> appAccess, Func<string, string>? nameTransform)
+    {
+        if (parent == null) return;
+
+        foreach (var name in parent.GetSubKeyNames())
+        {
+            if (string.Equals(name, NonPackagedSubKey, StringComparison.OrdinalIgnoreCase)) continue;
+
+            using var appKey = parent.OpenSubKey(name, false);
+            if (appKey == null) continue;
+
+            appAccess[nameTransform?.Invoke(name) ?? name] = IsAllowed(appKey);
+        }
+    }
+}
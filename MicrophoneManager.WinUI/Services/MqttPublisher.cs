@@ -0,0 +1,314 @@
+using Microsoft.Extensions.Logging;
+using Microsoft.Extensions.Logging.Abstractions;
+using MicrophoneManager.WinUI.Models;
+using MQTTnet;
+using MQTTnet.Client;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Optional MQTT client for home automation: publishes the default microphone's mute state and name
+/// (retained, so an "ON AIR" light picks them up on connect) and listens on a command topic.
+/// All settings live in the preferences store; the connection is kept alive with backoff.
+/// The connection uses TLS whenever a username is set, unless <see cref="TlsPreference"/> turns it
+/// off, so credentials never cross the network in the clear by default.
+/// </summary>
+public sealed class MqttPublisher : IDisposable
+{
+    public const string EnabledPreference = "MqttEnabled";
+    public const string HostPreference = "MqttHost";
+    public const string PortPreference = "MqttPort";
+    public const string UsernamePreference = "MqttUsername";
+    public const string PasswordPreference = "MqttPassword";
+    public const string TlsPreference = "MqttTls";
+    public const string MuteTopicPreference = "MqttMuteTopic";
+    public const string NameTopicPreference = "MqttNameTopic";
+    public const string CommandTopicPreference = "MqttCommandTopic";
+    public const string AvailabilityTopicPreference = "MqttAvailabilityTopic";
+
+    public const int DefaultPort = 1883;
+    public const int DefaultTlsPort = 8883;
+    public const string DefaultMuteTopic = "micmanager/default/muted";
+    public const string DefaultNameTopic = "micmanager/default/name";
+    public const string DefaultCommandTopic = "micmanager/command";
+    public const string DefaultAvailabilityTopic = "micmanager/availability";
+
+    private static readonly TimeSpan MinReconnectDelay = TimeSpan.FromSeconds(2);
+    private static readonly TimeSpan MaxReconnectDelay = TimeSpan.FromMinutes(1);
+
+    private readonly ControlCommandDispatcher _dispatcher;
+    private readonly IPreferencesStore _preferences;
+    private readonly ILogger<MqttPublisher> _logger;
+    private readonly SemaphoreSlim _connectionLock = new(1, 1);
+    private IMqttClient? _client;
+    private CancellationTokenSource? _shutdown;
+    private (bool? Muted, string? Name) _lastPublished;
+    private bool _disposed;
+
+    /// <summary>
+    /// Raised when the connection is established or lost.
+    /// </summary>
+    public event EventHandler? ConnectionChanged;
+
+    public MqttPublisher(ControlCommandDispatcher dispatcher, IPreferencesStore preferences, ILogger<MqttPublisher>? logger = null)
+    {
+        _dispatcher = dispatcher ?? throw new ArgumentNullException(nameof(dispatcher));
+        _preferences = preferences ?? throw new ArgumentNullException(nameof(preferences));
+        _logger = logger ?? NullLogger<MqttPublisher>.Instance;
+    }
+
+    public bool IsEnabled
+    {
+        get => _preferences.GetBool(EnabledPreference, false);
+        set
+        {
+            _preferences.SetBool(EnabledPreference, value);
+            if (value) Start(); else _ = StopAsync();
+        }
+    }
+
+    public bool IsConnected => _client?.IsConnected == true;
+
+    public string? Host => _preferences.GetString(HostPreference);
+
+    public int Port =>
+        int.TryParse(_preferences.GetString(PortPreference), out var port) && port is > 0 and <= 65535
+            ? port
+            : UseTls ? DefaultTlsPort : DefaultPort;
+
+    public string? Username => _preferences.GetString(UsernamePreference);
+
+    /// <summary>
+    /// Whether to connect over TLS; on by default once credentials are set.
+    /// </summary>
+    public bool UseTls => _preferences.GetBool(TlsPreference, !string.IsNullOrEmpty(Username));
+
+    public string MuteTopic => _preferences.GetString(MuteTopicPreference) ?? DefaultMuteTopic;
+    public string NameTopic => _preferences.GetString(NameTopicPreference) ?? DefaultNameTopic;
+    public string CommandTopic => _preferences.GetString(CommandTopicPreference) ?? DefaultCommandTopic;
+    public string AvailabilityTopic => _preferences.GetString(AvailabilityTopicPreference) ?? DefaultAvailabilityTopic;
+
+    /// <summary>
+    /// Connects in the background if the preference is on and a broker is configured.
+    /// </summary>
+    public void Start()
+    {
+        if (_disposed || _shutdown != null || !IsEnabled || string.IsNullOrWhiteSpace(Host)) return;
+
+        // The password is usually typed into the registry by hand; don't leave it readable there
+        PreferenceSecrets.ProtectStored(_preferences);
+
+        _shutdown = new CancellationTokenSource();
+        _dispatcher.StateChanged += OnStateChanged;
+        _ = Task.Run(() => ConnectWithBackoffAsync(_shutdown.Token));
+    }
+
+    public async Task StopAsync()
+    {
+        var shutdown = _shutdown;
+        if (shutdown == null) return;
+
+        _shutdown = null;
+        _dispatcher.StateChanged -= OnStateChanged;
+        shutdown.Cancel();
+
+        await _connectionLock.WaitAsync();
+        try
+        {
+            if (_client?.IsConnected == true)
+            {
+                await PublishAsync(AvailabilityTopic, "offline", CancellationToken.None);
+                await _client.DisconnectAsync();
+            }
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "MQTT disconnect failed");
+        }
+        finally
+        {
+            _client?.Dispose();
+            _client = null;
+            _lastPublished = default;
+            _connectionLock.Release();
+        }
+
+        ConnectionChanged?.Invoke(this, EventArgs.Empty);
+    }
+
+    /// <summary>
+    /// Maps a command-topic payload to a request: <c>toggle</c>, <c>mute</c>, <c>unmute</c>, <c>ON</c>
+    /// (muted) / <c>OFF</c>, or a full JSON <see cref="ControlRequest"/>.
+    /// </summary>
+    public static ControlRequest? ParseCommand(string payload)
+    {
+        var text = payload.Trim();
+        if (text.StartsWith('{'))
+        {
+            try
+            {
+                return System.Text.Json.JsonSerializer.Deserialize<ControlRequest>(text, ControlJson.Options);
+            }
+            catch (System.Text.Json.JsonException)
+            {
+                return null;
+            }
+        }
+
+        return text.ToLowerInvariant() switch
+        {
+            "toggle" or "togglemute" => new ControlRequest { Command = "toggleMute" },
+            "mute" or "on" => new ControlRequest { Command = "mute" },
+            "unmute" or "off" => new ControlRequest { Command = "unmute" },
+            _ => null,
+        };
+    }
+
+    private async Task ConnectWithBackoffAsync(CancellationToken cancellationToken)
+    {
+        var delay = MinReconnectDelay;
+        while (!cancellationToken.IsCancellationRequested)
+        {
+            if (await TryConnectAsync(cancellationToken))
+            {
+                return;
+            }
+
+            try { await Task.Delay(delay, cancellationToken); } catch (OperationCanceledException) { return; }
+            delay = TimeSpan.FromTicks(Math.Min(delay.Ticks * 2, MaxReconnectDelay.Ticks));
+        }
+    }
+
+    private async Task<bool> TryConnectAsync(CancellationToken cancellationToken)
+    {
+        await _connectionLock.WaitAsync(cancellationToken);
+        try
+        {
+            _client?.Dispose();
+            _client = new MqttFactory().CreateMqttClient();
+            _client.ApplicationMessageReceivedAsync += OnMessageReceivedAsync;
+            _client.DisconnectedAsync += OnDisconnectedAsync;
+
+            var options = new MqttClientOptionsBuilder()
+                .WithTcpServer(Host!, Port)
+                .WithClientId($"micmanager-{Environment.MachineName}")
+                .WithWillTopic(AvailabilityTopic)
+                .WithWillPayload("offline")
+                .WithWillRetain()
+                .WithCleanSession();
+
+            if (UseTls)
+            {
+                options = options.WithTlsOptions(tls => tls.UseTls());
+            }
+
+            var username = Username;
+            if (!string.IsNullOrEmpty(username))
+            {
+                options = options.WithCredentials(username, _preferences.GetString(PasswordPreference));
+            }
+
+            await _client.ConnectAsync(options.Build(), cancellationToken);
+            await _client.SubscribeAsync(CommandTopic, cancellationToken: cancellationToken);
+            await PublishAsync(AvailabilityTopic, "online", cancellationToken);
+
+            _lastPublished = default;
+            await PublishStateAsync(_dispatcher.GetState(), cancellationToken);
+        }
+        catch (Exception ex) when (ex is not OperationCanceledException)
+        {
+            _logger.LogWarning(ex, "MQTT connect to {Host}:{Port} failed", Host, Port);
+            return false;
+        }
+        finally
+        {
+            _connectionLock.Release();
+        }
+
+        ConnectionChanged?.Invoke(this, EventArgs.Empty);
+        return true;
+    }
+
+    private Task OnDisconnectedAsync(MqttClientDisconnectedEventArgs e)
+    {
+        // Failed attempts also land here; the backoff loop already retries those
+        if (!e.ClientWasConnected) return Task.CompletedTask;
+
+        ConnectionChanged?.Invoke(this, EventArgs.Empty);
+
+        // A clean stop cancels first; anything else is a dropped connection worth retrying
+        if (_shutdown is { IsCancellationRequested: false } shutdown)
+        {
+            _ = Task.Run(() => ConnectWithBackoffAsync(shutdown.Token));
+        }
+
+        return Task.CompletedTask;
+    }
+
+    private async Task OnMessageReceivedAsync(MqttApplicationMessageReceivedEventArgs e)
+    {
+        var payload = e.ApplicationMessage.ConvertPayloadToString() ?? "";
+        var request = ParseCommand(payload);
+        if (request == null)
+        {
+            _logger.LogWarning("Ignoring MQTT command '{Payload}'", payload);
+            return;
+        }
+
+        await _dispatcher.DispatchAsync(request);
+    }
+
+    private async void OnStateChanged(object? sender, ControlState state)
+    {
+        try
+        {
+            await PublishStateAsync(state, CancellationToken.None);
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "MQTT publish failed");
+        }
+    }
+
+    private async Task PublishStateAsync(ControlState state, CancellationToken cancellationToken)
+    {
+        if (_client?.IsConnected != true) return;
+
+        var muted = state.DefaultDevice?.IsMuted;
+        var name = state.DefaultDevice?.DisplayName;
+
+        // Volume moves also raise StateChanged; only mute and name changes are news here.
+        // Each value is remembered only once it's out, so a failed publish is retried next time.
+        if (muted != _lastPublished.Muted)
+        {
+            await PublishAsync(MuteTopic, muted switch { true => "ON", false => "OFF", null => "" }, cancellationToken);
+            _lastPublished.Muted = muted;
+        }
+
+        if (name != _lastPublished.Name)
+        {
+            await PublishAsync(NameTopic, name ?? "", cancellationToken);
+            _lastPublished.Name = name;
+        }
+    }
+
+    private Task PublishAsync(string topic, string payload, CancellationToken cancellationToken)
+    {
+        var message = new MqttApplicationMessageBuilder()
+            .WithTopic(topic)
+            .WithPayload(payload)
+            .WithRetainFlag()
+            .Build();
+
+        return _client!.PublishAsync(message, cancellationToken);
+    }
+
+    public void Dispose()
+    {
+        if (_disposed) return;
+        _disposed = true;
+
+        // Off the UI thread so the awaits inside don't wait for the thread we're blocking
+        try { Task.Run(StopAsync).Wait(TimeSpan.FromSeconds(2)); } catch { }
+    }
+}
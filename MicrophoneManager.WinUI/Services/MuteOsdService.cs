@@ -0,0 +1,97 @@
+using System.Globalization;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Where on the active display the on-screen banner appears.
+/// </summary>
+public enum OsdPosition
+{
+    TopCenter,
+
+    TopLeft,
+
+    TopRight,
+
+    BottomCenter,
+
+    BottomLeft,
+
+    BottomRight,
+}
+
+/// <summary>
+/// Settings for the brief "Muted" / "Live" banner shown when mute is toggled from a hotkey or
+/// the tray menu, like the Windows volume OSD: whether it shows, where and for how long.
+/// </summary>
+public sealed class MuteOsdService
+{
+    public const string EnabledPreference = "MuteOsd";
+    public const string PositionPreference = "MuteOsdPosition";
+    public const string DurationPreference = "MuteOsdMs";
+
+    public const int DefaultDurationMs = 1500;
+    public const int MinDurationMs = 500;
+    public const int MaxDurationMs = 10000;
+
+    private readonly IPreferencesStore _preferences;
+
+    /// <summary>
+    /// Raised after any of the settings changes through this service.
+    /// </summary>
+    public event EventHandler? Changed;
+
+    public MuteOsdService(IPreferencesStore preferences)
+    {
+        _preferences = preferences ?? throw new ArgumentNullException(nameof(preferences));
+    }
+
+    public bool IsEnabled
+    {
+        get => _preferences.GetBool(EnabledPreference, true);
+        set
+        {
+            if (value == IsEnabled) return;
+            _preferences.SetBool(EnabledPreference, value);
+            Changed?.Invoke(this, EventArgs.Empty);
+        }
+    }
+
+    public OsdPosition Position
+    {
+        get => Enum.TryParse<OsdPosition>(_preferences.GetString(PositionPreference), ignoreCase: true, out var position) && Enum.IsDefined(position)
+            ? position
+            : OsdPosition.TopCenter;
+        set
+        {
+            _preferences.SetString(PositionPreference, value == OsdPosition.TopCenter ? null : value.ToString());
+            Changed?.Invoke(this, EventArgs.Empty);
+        }
+    }
+
+    /// <summary>
+    /// How long the banner stays up, in milliseconds.
+    /// </summary>
+    public int DurationMs
+    {
+        get => int.TryParse(_preferences.GetString(DurationPreference), NumberStyles.Integer, CultureInfo.InvariantCulture, out var ms)
+            && ms is >= MinDurationMs and <= MaxDurationMs
+            ? ms
+            : DefaultDurationMs;
+        set
+        {
+            var ms = Math.Clamp(value, MinDurationMs, MaxDurationMs);
+            _preferences.SetString(DurationPreference, ms == DefaultDurationMs ? null : ms.ToString(CultureInfo.InvariantCulture));
+            Changed?.Invoke(this, EventArgs.Empty);
+        }
+    }
+
+    /// <summary>
+    /// The banner's text, e.g. "Muted" or "All microphones live".
+    /// </summary>
+    public static string GetMessage(bool isMuted, bool allMicrophones)
+    {
+        var state = isMuted ? "muted" : "live";
+        return allMicrophones ? $"All microphones {state}" : isMuted ? "Muted" : "Live";
+    }
+}
@@ -0,0 +1,243 @@
+using System.Globalization;
+using Microsoft.Extensions.Logging;
+using Microsoft.Extensions.Logging.Abstractions;
+using NAudio.CoreAudioApi;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Notices someone talking into the muted default microphone so the tray can say "You're muted".
+/// While the default is muted, its endpoint peak is sampled a few times a second; input above the
+/// threshold for long enough counts as speech. Short dips between words don't end it, a cough or a
+/// door does not last long enough to start it. After an alert, the cooldown keeps it from nagging
+/// until the microphone has been unmuted.
+/// Sampling runs on the timer thread, so the three or more COM calls per sample stay off the UI thread;
+/// only the alert itself is posted back.
+/// </summary>
+/// <remarks>
+/// This relies on the endpoint meter (IAudioMeterInformation) still reporting input while the endpoint
+/// is muted. Windows doesn't document either way whether it does; it has only been seen to on the
+/// USB and onboard microphones this was written against, not verified across drivers. A driver that
+/// applies the mute before the meter reads silence, and then the alert simply never fires.
+/// </remarks>
+public sealed class MutedSpeechService : IDisposable
+{
+    public const string EnabledPreference = "MutedSpeechAlert";
+    public const string ThresholdPreference = "MutedSpeechThresholdDb";
+    public const string SustainPreference = "MutedSpeechSustainMs";
+    public const string CooldownPreference = "MutedSpeechCooldownSeconds";
+
+    public const int DefaultThresholdDb = -30;
+    public const int DefaultSustainMs = 800;
+    public const int DefaultCooldownSeconds = 60;
+
+    private static readonly TimeSpan SampleInterval = TimeSpan.FromMilliseconds(100);
+
+    // Longer than the pause between words, shorter than the pause between sentences
+    private static readonly TimeSpan GapTolerance = TimeSpan.FromMilliseconds(300);
+
+    private readonly IAudioDeviceService _audioService;
+    private readonly IPreferencesStore _preferences;
+    private readonly Func<DateTime> _clock;
+    private readonly SynchronizationContext? _syncContext;
+    private readonly ILogger<MutedSpeechService> _logger;
+    private readonly object _sampleLock = new();
+    private Timer? _timer;
+    private string? _mutedDeviceId;
+    private DateTime? _speechStart;
+    private DateTime? _lastAbove;
+    private DateTime? _lastAlert;
+    private int _samplePending;
+    private volatile bool _disposed;
+
+    /// <summary>
+    /// Raised on the UI thread (the context the service was created on) when speech is detected on the
+    /// muted default; on the thread that called <see cref="Sample"/> when there is no such context.
+    /// </summary>
+    public event EventHandler<MutedSpeechEventArgs>? SpeechWhileMuted;
+
+    public MutedSpeechService(IAudioDeviceService audioService, IPreferencesStore preferences, ILogger<MutedSpeechService>? logger = null)
+        : this(audioService, preferences, () => DateTime.UtcNow, logger)
+    {
+    }
+
+    public MutedSpeechService(IAudioDeviceService audioService, IPreferencesStore preferences, Func<DateTime> clock, ILogger<MutedSpeechService>? logger = null)
+    {
+        _audioService = audioService ?? throw new ArgumentNullException(nameof(audioService));
+        _preferences = preferences ?? throw new ArgumentNullException(nameof(preferences));
+        _clock = clock ?? throw new ArgumentNullException(nameof(clock));
+        _logger = logger ?? NullLogger<MutedSpeechService>.Instance;
+        _syncContext = SynchronizationContext.Current;
+
+        // No background sampling without a UI context (unit tests call Sample)
+        if (_syncContext != null)
+        {
+            _timer = new Timer(_ => SampleOnTimer(), null, SampleInterval, SampleInterval);
+        }
+    }
+
+    public bool IsEnabled
+    {
+        get => _preferences.GetBool(EnabledPreference, true);
+        set
+        {
+            _preferences.SetBool(EnabledPreference, value);
+            Reset();
+        }
+    }
+
+    /// <summary>
+    /// Peak level, in dBFS, that counts as someone talking.
+    /// </summary>
+    public int ThresholdDb
+    {
+        get => int.TryParse(_preferences.GetString(ThresholdPreference), NumberStyles.Integer, CultureInfo.InvariantCulture, out var db) && db is < 0 and >= -96
+            ? db
+            : DefaultThresholdDb;
+        set => _preferences.SetString(ThresholdPreference, Math.Clamp(value, -96, -1).ToString(CultureInfo.InvariantCulture));
+    }
+
+    /// <summary>
+    /// How long the input has to stay above the threshold.
+    /// </summary>
+    public TimeSpan Sustain => TimeSpan.FromMilliseconds(
+        int.TryParse(_preferences.GetString(SustainPreference), NumberStyles.Integer, CultureInfo.InvariantCulture, out var ms) && ms > 0
+            ? ms
+            : DefaultSustainMs);
+
+    /// <summary>
+    /// Minimum time between two alerts while the microphone stays muted.
+    /// </summary>
+    public TimeSpan Cooldown => TimeSpan.FromSeconds(
+        int.TryParse(_preferences.GetString(CooldownPreference), NumberStyles.Integer, CultureInfo.InvariantCulture, out var seconds) && seconds >= 0
+            ? seconds
+            : DefaultCooldownSeconds);
+
+    /// <summary>
+    /// Reads the default microphone's level once and raises <see cref="SpeechWhileMuted"/> if it has
+    /// been talked into long enough. Called by the timer.
+    /// </summary>
+    public void Sample()
+    {
+        if (_disposed) return;
+
+        MutedSpeechEventArgs? alert;
+        lock (_sampleLock)
+        {
+            alert = SampleLocked();
+        }
+
+        if (alert == null) return;
+
+        if (_syncContext != null)
+        {
+            _syncContext.Post(_ =>
+            {
+                if (!_disposed) SpeechWhileMuted?.Invoke(this, alert);
+            }, null);
+        }
+        else
+        {
+            SpeechWhileMuted?.Invoke(this, alert);
+        }
+    }
+
+    private MutedSpeechEventArgs? SampleLocked()
+    {
+        var defaultId = IsEnabled ? _audioService.GetDefaultDeviceId(Role.Console) : null;
+        if (defaultId == null || !_audioService.IsMuted(defaultId))
+        {
+            // Unmuting (or switching microphones) ends the cooldown: the next mute starts fresh
+            ResetLocked();
+            return null;
+        }
+
+        if (!string.Equals(defaultId, _mutedDeviceId, StringComparison.OrdinalIgnoreCase))
+        {
+            ResetLocked();
+            _mutedDeviceId = defaultId;
+        }
+
+        var now = _clock();
+        var peakDb = _audioService.GetInputPeakDb(defaultId);
+
+        if (peakDb >= ThresholdDb)
+        {
+            _speechStart ??= now;
+            _lastAbove = now;
+        }
+        else if (_lastAbove is { } lastAbove && now - lastAbove > GapTolerance)
+        {
+            _speechStart = null;
+            _lastAbove = null;
+        }
+
+        if (_speechStart is not { } start || _lastAbove is not { } end || end - start < Sustain) return null;
+        if (_lastAlert is { } lastAlert && now - lastAlert < Cooldown) return null;
+
+        _speechStart = null;
+        _lastAbove = null;
+        _lastAlert = now;
+
+        var name = _audioService.GetMicrophones().FirstOrDefault(m => m.Id == defaultId)?.Name ?? "Microphone";
+        return new MutedSpeechEventArgs(defaultId, name, peakDb);
+    }
+
+    private void SampleOnTimer()
+    {
+        // A slow COM call shouldn't let timer callbacks pile up behind it
+        if (Interlocked.Exchange(ref _samplePending, 1) == 1) return;
+
+        try
+        {
+            Sample();
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Muted speech sampling failed");
+        }
+        finally
+        {
+            Interlocked.Exchange(ref _samplePending, 0);
+        }
+    }
+
+    private void Reset()
+    {
+        lock (_sampleLock)
+        {
+            ResetLocked();
+        }
+    }
+
+    private void ResetLocked()
+    {
+        _mutedDeviceId = null;
+        _speechStart = null;
+        _lastAbove = null;
+        _lastAlert = null;
+    }
+
+    public void Dispose()
+    {
+        if (_disposed) return;
+        _disposed = true;
+
+        try { _timer?.Dispose(); } catch { }
+        _timer = null;
+    }
+
+    public sealed class MutedSpeechEventArgs : EventArgs
+    {
+        public MutedSpeechEventArgs(string deviceId, string deviceName, double peakDb)
+        {
+            DeviceId = deviceId;
+            DeviceName = deviceName;
+            PeakDb = peakDb;
+        }
+
+        public string DeviceId { get; }
+        public string DeviceName { get; }
+        public double PeakDb { get; }
+    }
+}
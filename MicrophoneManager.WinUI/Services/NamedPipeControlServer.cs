@@ -0,0 +1,199 @@
+using System.IO.Pipes;
+using System.Text;
+using System.Text.Json;
+using Microsoft.Extensions.Logging;
+using Microsoft.Extensions.Logging.Abstractions;
+using MicrophoneManager.WinUI.Models;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Serves <see cref="ControlCommandDispatcher"/> on <c>\\.\pipe\mic-manager</c> so AutoHotkey,
+/// PowerShell and similar tools can drive the running tray app. Each line written to the pipe is one
+/// JSON request and gets one JSON line back. After <c>{"command":"subscribe"}</c> the connection
+/// also gets a state line whenever the state changes, which is how the command line and the Stream
+/// Deck plugin follow the tray without opening the devices themselves. Only the current user can connect.
+/// </summary>
+public sealed class NamedPipeControlServer : IDisposable
+{
+    public const string PipeName = "mic-manager";
+
+    private const int MaxConnections = 4;
+
+    private readonly ControlCommandDispatcher _dispatcher;
+    private readonly CancellationTokenSource _shutdown = new();
+    private readonly ILogger<NamedPipeControlServer> _logger;
+    private bool _started;
+    private bool _disposed;
+
+    public NamedPipeControlServer(ControlCommandDispatcher dispatcher, ILogger<NamedPipeControlServer>? logger = null)
+    {
+        _dispatcher = dispatcher ?? throw new ArgumentNullException(nameof(dispatcher));
+        _logger = logger ?? NullLogger<NamedPipeControlServer>.Instance;
+    }
+
+    /// <summary>
+    /// Starts accepting connections in the background.
+    /// </summary>
+    public void Start()
+    {
+        if (_disposed || _started) return;
+        _started = true;
+
+        for (var i = 0; i < MaxConnections; i++)
+        {
+            _ = Task.Run(() => AcceptLoopAsync(_shutdown.Token));
+        }
+    }
+
+    /// <summary>
+    /// Whether a running instance is listening, waiting at most <paramref name="timeout"/> for a free connection.
+    /// </summary>
+    public static bool IsServerRunning(TimeSpan timeout)
+    {
+        try
+        {
+            using var client = new NamedPipeClientStream(".", PipeName, PipeDirection.InOut, PipeOptions.CurrentUserOnly);
+            client.Connect((int)timeout.TotalMilliseconds);
+            return true;
+        }
+        catch (Exception ex) when (ex is TimeoutException or IOException or UnauthorizedAccessException)
+        {
+            return false;
+        }
+    }
+
+    /// <summary>
+    /// Sends one request to a running instance and returns its reply. Used by <c>--send</c>.
+    /// </summary>
+    public static async Task<string> SendAsync(string requestJson, TimeSpan timeout, CancellationToken cancellationToken = default)
+    {
+        using var client = new NamedPipeClientStream(".", PipeName, PipeDirection.InOut, PipeOptions.Asynchronous | PipeOptions.CurrentUserOnly);
+        await client.ConnectAsync((int)timeout.TotalMilliseconds, cancellationToken);
+
+        using var reader = new StreamReader(client, Encoding.UTF8, leaveOpen: true);
+        using var writer = new StreamWriter(client, new UTF8Encoding(false), leaveOpen: true) { AutoFlush = true };
+
+        // Requests are line-delimited, so a pretty-printed request has to be flattened
+        await writer.WriteLineAsync(requestJson.ReplaceLineEndings(" "));
+
+        using var timeoutSource = CancellationTokenSource.CreateLinkedTokenSource(cancellationToken);
+        timeoutSource.CancelAfter(timeout);
+        return await reader.ReadLineAsync(timeoutSource.Token) ?? throw new IOException("The server closed the connection.");
+    }
+
+    private async Task AcceptLoopAsync(CancellationToken cancellationToken)
+    {
+        while (!cancellationToken.IsCancellationRequested)
+        {
+            try
+            {
+                using var server = new NamedPipeServerStream(
+                    PipeName,
+                    PipeDirection.InOut,
+                    MaxConnections,
+                    PipeTransmissionMode.Byte,
+                    PipeOptions.Asynchronous | PipeOptions.CurrentUserOnly);
+
+                await server.WaitForConnectionAsync(cancellationToken);
+                await ServeConnectionAsync(server, cancellationToken);
+            }
+            catch (OperationCanceledException)
+            {
+                return;
+            }
+            catch (Exception ex)
+            {
+                // A broken client shouldn't take the listener down; back off briefly in case the pipe itself is failing
+                _logger.LogWarning(ex, "Control pipe error");
+                try { await Task.Delay(TimeSpan.FromSeconds(1), cancellationToken); } catch (OperationCanceledException) { return; }
+            }
+        }
+    }
+
+    private async Task ServeConnectionAsync(NamedPipeServerStream server, CancellationToken cancellationToken)
+    {
+        using var reader = new StreamReader(server, Encoding.UTF8, leaveOpen: true);
+        using var writer = new StreamWriter(server, new UTF8Encoding(false), leaveOpen: true) { AutoFlush = true };
+
+        // Replies and state pushes share the connection
+        using var writeLock = new SemaphoreSlim(1, 1);
+        EventHandler<ControlState>? push = null;
+
+        async Task WriteLineAsync(string line)
+        {
+            await writeLock.WaitAsync(cancellationToken);
+            try
+            {
+                await writer.WriteLineAsync(line.AsMemory(), cancellationToken);
+            }
+            finally
+            {
+                writeLock.Release();
+            }
+        }
+
+        try
+        {
+            while (server.IsConnected && !cancellationToken.IsCancellationRequested)
+            {
+                var line = await reader.ReadLineAsync(cancellationToken);
+                if (line == null) break;
+                if (string.IsNullOrWhiteSpace(line)) continue;
+
+                if (push == null && IsSubscribe(line))
+                {
+                    push = async (s, state) =>
+                    {
+                        try
+                        {
+                            await WriteLineAsync(JsonSerializer.Serialize(ControlResponse.Success(state), ControlJson.Options));
+                        }
+                        catch (Exception ex)
+                        {
+                            // The read loop notices the client going away and unsubscribes
+                            _logger.LogWarning(ex, "Control pipe push failed");
+                        }
+                    };
+                    _dispatcher.StateChanged += push;
+
+                    // The subscriber starts from the current state rather than waiting for a change
+                    var state = await _dispatcher.GetStateAsync();
+                    await WriteLineAsync(JsonSerializer.Serialize(ControlResponse.Success(state), ControlJson.Options));
+                    continue;
+                }
+
+                await WriteLineAsync(await _dispatcher.DispatchJsonAsync(line));
+            }
+        }
+        finally
+        {
+            if (push != null)
+            {
+                _dispatcher.StateChanged -= push;
+            }
+        }
+    }
+
+    private static bool IsSubscribe(string line)
+    {
+        try
+        {
+            var request = JsonSerializer.Deserialize<ControlRequest>(line, ControlJson.Options);
+            return string.Equals(request?.Command, "subscribe", StringComparison.OrdinalIgnoreCase);
+        }
+        catch (JsonException)
+        {
+            return false;
+        }
+    }
+
+    public void Dispose()
+    {
+        if (_disposed) return;
+        _disposed = true;
+
+        // Not disposed: the accept loops still observe the token while they unwind
+        try { _shutdown.Cancel(); } catch { }
+    }
+}
@@ -62,4 +62,20 @@ internal static class ObsMeterMath
 
     public static double PercentToDb(double percent)
         => ClampMeterDb(DeflectionToDb(Math.Clamp(percent, 0.0, 100.0) / 100.0));
+
+    /// <summary>
+    /// Quietest level the flyout's meters draw; their bars start here rather than at -96 dBFS.
+    /// </summary>
+    public const double VisibleMinDb = -60.0;
+
+    /// <summary>
+    /// Rescales OBS deflection (0..100) so that <see cref="VisibleMinDb"/> is the left end of a bar.
+    /// </summary>
+    public static double ToVisiblePercent(double percent)
+    {
+        var minPercent = DbToPercent(VisibleMinDb);
+        percent = Math.Clamp(percent, 0.0, 100.0);
+        if (percent <= minPercent) return 0.0;
+        return Math.Clamp((percent - minPercent) / (100.0 - minPercent) * 100.0, 0.0, 100.0);
+    }
 }
@@ -0,0 +1,155 @@
+using System.IO.Pipes;
+using System.Text;
+using System.Text.Json;
+using Microsoft.Extensions.Logging;
+using Microsoft.Extensions.Logging.Abstractions;
+using MicrophoneManager.WinUI.Models;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Runs control requests in the tray app over <see cref="NamedPipeControlServer"/>, so the command
+/// line and the Stream Deck plugin use the tray's devices instead of opening their own. State
+/// changes are followed over a second, subscribed connection, opened once something listens to
+/// <see cref="StateChanged"/> and reopened if the tray restarts.
+/// </summary>
+public sealed class PipeControlChannel : IControlChannel, IDisposable
+{
+    private static readonly TimeSpan RequestTimeout = TimeSpan.FromSeconds(5);
+    private static readonly TimeSpan ReconnectDelay = TimeSpan.FromSeconds(2);
+
+    // Commands answered with the whole state, as opposed to a device list or something else
+    private static readonly HashSet<string> StateCommands = new(StringComparer.OrdinalIgnoreCase)
+    {
+        "state", "mute", "unmute", "toggleMute", "toggle", "setDefault", "applyProfile",
+    };
+
+    private readonly CancellationTokenSource _shutdown = new();
+    private readonly object _subscribeLock = new();
+    private readonly ILogger<PipeControlChannel> _logger;
+    private EventHandler<ControlState>? _stateChanged;
+    private bool _following;
+    private bool _disposed;
+
+    public PipeControlChannel(ILogger<PipeControlChannel>? logger = null)
+    {
+        _logger = logger ?? NullLogger<PipeControlChannel>.Instance;
+    }
+
+    public event EventHandler<ControlState>? StateChanged
+    {
+        add
+        {
+            lock (_subscribeLock)
+            {
+                _stateChanged += value;
+                if (_following || _disposed) return;
+                _following = true;
+            }
+
+            _ = Task.Run(() => FollowStateAsync(_shutdown.Token));
+        }
+        remove
+        {
+            lock (_subscribeLock)
+            {
+                _stateChanged -= value;
+            }
+        }
+    }
+
+    public async Task<ControlState> GetStateAsync()
+    {
+        var response = await DispatchAsync(new ControlRequest { Command = "state" });
+        return response.Data as ControlState ?? throw new IOException(response.Error ?? "Microphone Manager sent no state.");
+    }
+
+    /// <summary>
+    /// Sends the request to the tray. A tray that isn't running is reported as a failed response
+    /// rather than thrown.
+    /// </summary>
+    public async Task<ControlResponse> DispatchAsync(ControlRequest request)
+    {
+        string reply;
+        try
+        {
+            reply = await NamedPipeControlServer.SendAsync(JsonSerializer.Serialize(request, ControlJson.Options), RequestTimeout, _shutdown.Token);
+        }
+        catch (TimeoutException)
+        {
+            return ControlResponse.Failure("Microphone Manager isn't running.");
+        }
+        catch (IOException ex)
+        {
+            return ControlResponse.Failure(ex.Message);
+        }
+
+        return Parse(reply, request.Command);
+    }
+
+    /// <summary>
+    /// Reads a reply, turning <see cref="ControlResponse.Data"/> back into the type the command answers with.
+    /// </summary>
+    public static ControlResponse Parse(string reply, string command)
+    {
+        using var document = JsonDocument.Parse(reply);
+        var root = document.RootElement;
+        var ok = root.TryGetProperty("ok", out var okElement) && okElement.ValueKind == JsonValueKind.True;
+        var error = root.TryGetProperty("error", out var errorElement) ? errorElement.GetString() : null;
+        if (!ok) return ControlResponse.Failure(error ?? "The request failed.");
+        if (!root.TryGetProperty("data", out var data)) return ControlResponse.Success();
+
+        object? value = command.Equals("list", StringComparison.OrdinalIgnoreCase)
+            ? data.Deserialize<List<ControlDevice>>(ControlJson.Options)
+            : StateCommands.Contains(command)
+                ? data.Deserialize<ControlState>(ControlJson.Options)
+                : data.Clone();
+        return ControlResponse.Success(value);
+    }
+
+    private async Task FollowStateAsync(CancellationToken cancellationToken)
+    {
+        while (!cancellationToken.IsCancellationRequested)
+        {
+            try
+            {
+                using var client = new NamedPipeClientStream(".", NamedPipeControlServer.PipeName, PipeDirection.InOut, PipeOptions.Asynchronous | PipeOptions.CurrentUserOnly);
+                await client.ConnectAsync(cancellationToken);
+
+                using var reader = new StreamReader(client, Encoding.UTF8, leaveOpen: true);
+                using var writer = new StreamWriter(client, new UTF8Encoding(false), leaveOpen: true) { AutoFlush = true };
+                await writer.WriteLineAsync("""{"command":"subscribe"}""");
+
+                while (await reader.ReadLineAsync(cancellationToken) is { } line)
+                {
+                    if (Parse(line, "state").Data is ControlState state)
+                    {
+                        _stateChanged?.Invoke(this, state);
+                    }
+                }
+            }
+            catch (OperationCanceledException)
+            {
+                return;
+            }
+            catch (Exception ex) when (ex is IOException or JsonException)
+            {
+                _logger.LogWarning(ex, "Following the tray's state failed");
+            }
+
+            // The tray went away; wait for it to come back
+            try { await Task.Delay(ReconnectDelay, cancellationToken); } catch (OperationCanceledException) { return; }
+        }
+    }
+
+    public void Dispose()
+    {
+        lock (_subscribeLock)
+        {
+            if (_disposed) return;
+            _disposed = true;
+        }
+
+        try { _shutdown.Cancel(); } catch { }
+    }
+}
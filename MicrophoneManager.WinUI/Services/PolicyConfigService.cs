@@ -1,4 +1,7 @@
 using System.Runtime.InteropServices;
+using Microsoft.Extensions.Logging;
+using MicrophoneManager.WinUI.Models;
+using NAudio.CoreAudioApi;
 
 namespace MicrophoneManager.WinUI.Services;
 
@@ -10,6 +13,7 @@ namespace MicrophoneManager.WinUI.Services;
 public class PolicyConfigService : IDisposable
 {
     private readonly ComThreadService _comThread;
+    private readonly ILogger<PolicyConfigService> _logger;
     private bool _disposed;
 
     // Device roles
@@ -29,25 +33,61 @@ public class PolicyConfigService : IDisposable
         void Reserved1();
         void Reserved2();
         void Reserved3();
-        void Reserved4();
+
+        [PreserveSig]
+        int SetDeviceFormat([MarshalAs(UnmanagedType.LPWStr)] string deviceId, IntPtr endpointFormat, IntPtr mixFormat);
+
         void Reserved5();
         void Reserved6();
         void Reserved7();
         void Reserved8();
-        void Reserved9();
-        void Reserved10();
+
+        [PreserveSig]
+        int GetPropertyValue([MarshalAs(UnmanagedType.LPWStr)] string deviceId, [MarshalAs(UnmanagedType.Bool)] bool fxStore, ref PropertyKeyStruct key, out PropVariant value);
+
+        [PreserveSig]
+        int SetPropertyValue([MarshalAs(UnmanagedType.LPWStr)] string deviceId, [MarshalAs(UnmanagedType.Bool)] bool fxStore, ref PropertyKeyStruct key, ref PropVariant value);
 
         [PreserveSig]
         int SetDefaultEndpoint([MarshalAs(UnmanagedType.LPWStr)] string deviceId, ERole role);
+
+        [PreserveSig]
+        int SetEndpointVisibility([MarshalAs(UnmanagedType.LPWStr)] string deviceId, [MarshalAs(UnmanagedType.Bool)] bool visible);
     }
 
     [ComImport]
     [Guid("870AF99C-171D-4F9E-AF0D-E63DF40C2BC9")]
     private class PolicyConfigClient { }
 
-    public PolicyConfigService(ComThreadService comThread)
+    [StructLayout(LayoutKind.Sequential)]
+    private struct PropertyKeyStruct
+    {
+        public Guid FormatId;
+        public int PropertyId;
+    }
+
+    // Only the VT_BOOL and VT_LPWSTR arms are used
+    [StructLayout(LayoutKind.Explicit, Size = 24)]
+    private struct PropVariant
+    {
+        [FieldOffset(0)] public ushort VarType;
+        [FieldOffset(8)] public short BoolValue;
+        [FieldOffset(8)] public IntPtr PointerValue;
+    }
+
+    private const ushort VT_EMPTY = 0;
+    private const ushort VT_BOOL = 11;
+    private const ushort VT_LPWSTR = 31;
+
+    // "Listen to this device" (mmsys.cpl Listen tab) endpoint properties
+    private static readonly Guid ListenPropertySet = new("24DBB0FC-9311-4B3D-9CF0-18FF155639D4");
+    private const int ListenTargetPropertyId = 0;
+    private const int ListenEnabledPropertyId = 1;
+
+    public PolicyConfigService(ComThreadService comThread, ILogger<PolicyConfigService> logger)
     {
         _comThread = comThread ?? throw new ArgumentNullException(nameof(comThread));
+        _logger = logger ?? throw new ArgumentNullException(nameof(logger));
     }
 
     /// <summary>
@@ -62,16 +102,17 @@ public class PolicyConfigService : IDisposable
 
         cancellationToken.ThrowIfCancellationRequested();
 
-        await _comThread.InvokeAsync(() =>
+        await _comThread.InvokeWithDeadlineAsync(() =>
         {
             cancellationToken.ThrowIfCancellationRequested();
             SetDefaultDeviceInternal(deviceId, role);
-        });
+        }, cancellationToken: cancellationToken);
     }
 
     /// <summary>
     /// Sets the specified device as the default for both Console and Communications roles asynchronously.
-    /// Uses a single COM object for both calls to reduce overhead.
+    /// Uses a single COM object for both calls to reduce overhead. If the second role fails, the
+    /// Console role is rolled back so the device is never left half-applied.
     /// </summary>
     public async Task SetDefaultDeviceForAllRolesAsync(string deviceId, CancellationToken cancellationToken = default)
     {
@@ -82,11 +123,70 @@ public class PolicyConfigService : IDisposable
 
         cancellationToken.ThrowIfCancellationRequested();
 
-        await _comThread.InvokeAsync(() =>
+        await _comThread.InvokeWithDeadlineAsync(() =>
         {
             cancellationToken.ThrowIfCancellationRequested();
             SetDefaultDeviceForAllRolesInternal(deviceId);
-        });
+        }, cancellationToken: cancellationToken);
+    }
+
+    /// <summary>
+    /// Enables or disables an audio endpoint, the same as "Disable" in the Sound Control Panel.
+    /// </summary>
+    public async Task SetEndpointVisibilityAsync(string deviceId, bool visible, CancellationToken cancellationToken = default)
+    {
+        if (_disposed)
+        {
+            throw new ObjectDisposedException(nameof(PolicyConfigService));
+        }
+
+        cancellationToken.ThrowIfCancellationRequested();
+
+        await _comThread.InvokeWithDeadlineAsync(() =>
+        {
+            cancellationToken.ThrowIfCancellationRequested();
+            SetEndpointVisibilityInternal(deviceId, visible);
+        }, cancellationToken: cancellationToken);
+    }
+
+    /// <summary>
+    /// Writes the shared-mode default format for an endpoint. The audio engine restarts the
+    /// endpoint's streams with the new format.
+    /// </summary>
+    public async Task SetDeviceFormatAsync(string deviceId, int sampleRate, int bitsPerSample, int channels, CancellationToken cancellationToken = default)
+    {
+        if (_disposed)
+        {
+            throw new ObjectDisposedException(nameof(PolicyConfigService));
+        }
+
+        cancellationToken.ThrowIfCancellationRequested();
+
+        await _comThread.InvokeWithDeadlineAsync(() =>
+        {
+            cancellationToken.ThrowIfCancellationRequested();
+            SetDeviceFormatInternal(deviceId, sampleRate, bitsPerSample, channels);
+        }, cancellationToken: cancellationToken);
+    }
+
+    /// <summary>
+    /// Turns "Listen to this device" on or off for a capture endpoint.
+    /// </summary>
+    /// <param name="targetRenderId">Render endpoint to play through, or null for the default playback device.</param>
+    public async Task SetListenAsync(string deviceId, bool enabled, string? targetRenderId, CancellationToken cancellationToken = default)
+    {
+        if (_disposed)
+        {
+            throw new ObjectDisposedException(nameof(PolicyConfigService));
+        }
+
+        cancellationToken.ThrowIfCancellationRequested();
+
+        await _comThread.InvokeWithDeadlineAsync(() =>
+        {
+            cancellationToken.ThrowIfCancellationRequested();
+            SetListenInternal(deviceId, enabled, targetRenderId);
+        }, cancellationToken: cancellationToken);
     }
 
     /// <summary>
@@ -115,12 +215,13 @@ public class PolicyConfigService : IDisposable
         SetDefaultDeviceForAllRolesInternal(deviceId);
     }
 
-    private static void SetDefaultDeviceInternal(string deviceId, ERole role)
+    private void SetDefaultDeviceInternal(string deviceId, ERole role)
     {
         var policyConfig = (IPolicyConfig)new PolicyConfigClient();
         try
         {
             int hr = policyConfig.SetDefaultEndpoint(deviceId, role);
+            LogResult(deviceId, role, hr);
             Marshal.ThrowExceptionForHR(hr);
         }
         finally
@@ -129,24 +230,175 @@ public class PolicyConfigService : IDisposable
         }
     }
 
-    private static void SetDefaultDeviceForAllRolesInternal(string deviceId)
+    private void SetDefaultDeviceForAllRolesInternal(string deviceId)
     {
+        var previousConsoleId = TryGetDefaultEndpointId(deviceId, Role.Console);
+
         // Use single COM object for both calls to reduce overhead
         var policyConfig = (IPolicyConfig)new PolicyConfigClient();
         try
         {
             int hr1 = policyConfig.SetDefaultEndpoint(deviceId, ERole.eConsole);
+            LogResult(deviceId, ERole.eConsole, hr1);
             Marshal.ThrowExceptionForHR(hr1);
 
             int hr2 = policyConfig.SetDefaultEndpoint(deviceId, ERole.eCommunications);
-            Marshal.ThrowExceptionForHR(hr2);
+            LogResult(deviceId, ERole.eCommunications, hr2);
+            if (hr2 < 0)
+            {
+                string consoleOutcome;
+                if (string.Equals(previousConsoleId, deviceId, StringComparison.OrdinalIgnoreCase))
+                {
+                    // It already was the Console default, so nothing changed that needs undoing
+                    consoleOutcome = "is unchanged.";
+                }
+                else if (previousConsoleId != null)
+                {
+                    var hrRollback = policyConfig.SetDefaultEndpoint(previousConsoleId, ERole.eConsole);
+                    LogResult(previousConsoleId, ERole.eConsole, hrRollback);
+                    consoleOutcome = hrRollback >= 0 ? "was rolled back." : "could not be rolled back.";
+                }
+                else
+                {
+                    consoleOutcome = "could not be rolled back.";
+                }
+
+                throw new COMException(
+                    $"Setting the Communications default failed (0x{hr2:X8}); the Console default {consoleOutcome}",
+                    hr2);
+            }
+        }
+        finally
+        {
+            Marshal.ReleaseComObject(policyConfig);
+        }
+    }
+
+    private void SetEndpointVisibilityInternal(string deviceId, bool visible)
+    {
+        var policyConfig = (IPolicyConfig)new PolicyConfigClient();
+        try
+        {
+            int hr = policyConfig.SetEndpointVisibility(deviceId, visible);
+            if (hr < 0)
+            {
+                _logger.LogWarning("IPolicyConfig.SetEndpointVisibility({Visible}) failed for {DeviceId} (0x{HResult:X8})", visible, deviceId, hr);
+            }
+            else
+            {
+                _logger.LogInformation("IPolicyConfig.SetEndpointVisibility({Visible}) set {DeviceId}", visible, deviceId);
+            }
+
+            Marshal.ThrowExceptionForHR(hr);
+        }
+        finally
+        {
+            Marshal.ReleaseComObject(policyConfig);
+        }
+    }
+
+    private void SetDeviceFormatInternal(string deviceId, int sampleRate, int bitsPerSample, int channels)
+    {
+        var requested = new DeviceFormat(sampleRate, bitsPerSample, channels);
+        var deviceHandle = GCHandle.Alloc(DeviceFormatBlob.BuildDeviceFormat(requested), GCHandleType.Pinned);
+        var mixHandle = GCHandle.Alloc(DeviceFormatBlob.BuildMixFormat(requested), GCHandleType.Pinned);
+        var policyConfig = (IPolicyConfig)new PolicyConfigClient();
+        try
+        {
+            // The engine mixes in float whatever the device format is, as mmsys.cpl sets it
+            int hr = policyConfig.SetDeviceFormat(deviceId, deviceHandle.AddrOfPinnedObject(), mixHandle.AddrOfPinnedObject());
+            if (hr < 0)
+            {
+                _logger.LogWarning("IPolicyConfig.SetDeviceFormat({SampleRate}/{Bits}/{Channels}) failed for {DeviceId} (0x{HResult:X8})",
+                    sampleRate, bitsPerSample, channels, deviceId, hr);
+            }
+            else
+            {
+                _logger.LogInformation("Set default format of {DeviceId} to {SampleRate} Hz {Bits}-bit {Channels} ch",
+                    deviceId, sampleRate, bitsPerSample, channels);
+            }
+
+            Marshal.ThrowExceptionForHR(hr);
         }
         finally
         {
+            deviceHandle.Free();
+            mixHandle.Free();
             Marshal.ReleaseComObject(policyConfig);
         }
     }
 
+    private void SetListenInternal(string deviceId, bool enabled, string? targetRenderId)
+    {
+        var policyConfig = (IPolicyConfig)new PolicyConfigClient();
+        var target = new PropVariant { VarType = VT_EMPTY };
+        try
+        {
+            // Target first, so enabling never plays through a stale device
+            if (!string.IsNullOrEmpty(targetRenderId))
+            {
+                target.VarType = VT_LPWSTR;
+                target.PointerValue = Marshal.StringToCoTaskMemUni(targetRenderId);
+            }
+
+            var targetKey = new PropertyKeyStruct { FormatId = ListenPropertySet, PropertyId = ListenTargetPropertyId };
+            int hr = policyConfig.SetPropertyValue(deviceId, false, ref targetKey, ref target);
+            Marshal.ThrowExceptionForHR(hr);
+
+            var enabledKey = new PropertyKeyStruct { FormatId = ListenPropertySet, PropertyId = ListenEnabledPropertyId };
+            var enabledValue = new PropVariant { VarType = VT_BOOL, BoolValue = (short)(enabled ? -1 : 0) };
+            hr = policyConfig.SetPropertyValue(deviceId, false, ref enabledKey, ref enabledValue);
+            if (hr < 0)
+            {
+                _logger.LogWarning("IPolicyConfig.SetPropertyValue(Listen={Enabled}) failed for {DeviceId} (0x{HResult:X8})", enabled, deviceId, hr);
+            }
+            else
+            {
+                _logger.LogInformation("Listen to this device {State} for {DeviceId}", enabled ? "enabled" : "disabled", deviceId);
+            }
+
+            Marshal.ThrowExceptionForHR(hr);
+        }
+        finally
+        {
+            if (target.VarType == VT_LPWSTR)
+            {
+                Marshal.FreeCoTaskMem(target.PointerValue);
+            }
+
+            Marshal.ReleaseComObject(policyConfig);
+        }
+    }
+
+    private string? TryGetDefaultEndpointId(string deviceId, Role role)
+    {
+        try
+        {
+            using var enumerator = new MMDeviceEnumerator();
+            using var device = enumerator.GetDevice(deviceId);
+            using var current = enumerator.GetDefaultAudioEndpoint(device.DataFlow, role);
+            return current?.ID;
+        }
+        catch (Exception ex)
+        {
+            // No previous default (or device vanished) - nothing to roll back to
+            _logger.LogDebug(ex, "Could not read the current {Role} default before switching to {DeviceId}", role, deviceId);
+            return null;
+        }
+    }
+
+    private void LogResult(string deviceId, ERole role, int hr)
+    {
+        if (hr < 0)
+        {
+            _logger.LogWarning("IPolicyConfig.SetDefaultEndpoint({Role}) failed for {DeviceId} (0x{HResult:X8})", role, deviceId, hr);
+        }
+        else
+        {
+            _logger.LogDebug("IPolicyConfig.SetDefaultEndpoint({Role}) set {DeviceId}", role, deviceId);
+        }
+    }
+
     public void Dispose()
     {
         if (_disposed) return;
@@ -0,0 +1,34 @@
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Portable mode keeps everything beside the executable and writes nothing to the registry:
+/// preferences (profiles included) go to <c>config.toml</c> and logs next to the exe, and the
+/// features that only exist as registry settings (start with Windows, communications ducking) are
+/// turned off. Enabled by a <c>portable.ini</c> beside the exe or the <c>--portable</c> switch.
+/// </summary>
+public static class PortableMode
+{
+    public const string MarkerFileName = "portable.ini";
+    public const string CommandLineSwitch = "--portable";
+
+    public static bool IsEnabled { get; private set; }
+
+    /// <summary>
+    /// Folder holding the executable. Not <see cref="AppContext.BaseDirectory"/>, which is the
+    /// extraction folder for single-file builds.
+    /// </summary>
+    public static string Directory =>
+        Path.GetDirectoryName(Environment.ProcessPath) ?? AppContext.BaseDirectory;
+
+    public static string ConfigPath => Path.Combine(Directory, "config.toml");
+
+    /// <summary>
+    /// Decides whether we're portable and returns the arguments without the switch.
+    /// </summary>
+    public static string[] Initialize(string[] args)
+    {
+        var remaining = args.Where(a => !string.Equals(a, CommandLineSwitch, StringComparison.OrdinalIgnoreCase)).ToArray();
+        IsEnabled = remaining.Length != args.Length || File.Exists(Path.Combine(Directory, MarkerFileName));
+        return remaining;
+    }
+}
@@ -0,0 +1,82 @@
+using Microsoft.Extensions.Logging;
+using Microsoft.Extensions.Logging.Abstractions;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Watches WM_POWERBROADCAST and, after a resume from sleep, has the audio service rebuild its
+/// endpoint callbacks so the tray and flyout don't keep showing pre-sleep state.
+/// </summary>
+public sealed class PowerEventService : IDisposable
+{
+    private const int WM_POWERBROADCAST = 0x0218;
+    private const int PBT_APMRESUMEAUTOMATIC = 0x12;
+
+    private readonly IAudioDeviceService _audioService;
+    private readonly ILogger<PowerEventService> _logger;
+    private WindowMessageMonitor? _monitor;
+    private bool _disposed;
+
+    /// <summary>
+    /// Raised after the audio service has been re-initialized following a resume.
+    /// </summary>
+    public event EventHandler? Resumed;
+
+    public PowerEventService(IAudioDeviceService audioService, ILogger<PowerEventService>? logger = null)
+    {
+        _audioService = audioService ?? throw new ArgumentNullException(nameof(audioService));
+        _logger = logger ?? NullLogger<PowerEventService>.Instance;
+    }
+
+    /// <summary>
+    /// How long to wait after resume before rebuilding; USB audio devices re-enumerate during this time.
+    /// </summary>
+    public TimeSpan ResumeSettleDelay { get; set; } = TimeSpan.FromSeconds(2);
+
+    public void Attach(WindowMessageMonitor monitor)
+    {
+        if (_disposed || monitor.Handle == IntPtr.Zero || _monitor != null) return;
+
+        _monitor = monitor;
+        _monitor.MessageReceived += OnMessageReceived;
+    }
+
+    public async Task OnResumeAsync()
+    {
+        if (ResumeSettleDelay > TimeSpan.Zero)
+        {
+            await Task.Delay(ResumeSettleDelay);
+        }
+
+        if (_disposed) return;
+
+        _audioService.RecoverAfterResume();
+        Resumed?.Invoke(this, EventArgs.Empty);
+    }
+
+    private async void OnMessageReceived(object? sender, WindowMessageMonitor.WindowMessageEventArgs e)
+    {
+        // PBT_APMRESUMEAUTOMATIC is sent on every resume; PBT_APMRESUMESUSPEND only when a user is present
+        if (e.Message != WM_POWERBROADCAST || (int)e.WParam != PBT_APMRESUMEAUTOMATIC) return;
+
+        try
+        {
+            await OnResumeAsync();
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Resume handling failed");
+        }
+    }
+
+    public void Dispose()
+    {
+        if (_disposed) return;
+        _disposed = true;
+
+        if (_monitor == null) return;
+
+        try { _monitor.MessageReceived -= OnMessageReceived; } catch { }
+        _monitor = null;
+    }
+}
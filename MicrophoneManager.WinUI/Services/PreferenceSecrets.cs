@@ -0,0 +1,67 @@
+using System.Security.Cryptography;
+using System.Text;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Preferences that hold credentials, and the DPAPI wrapping that keeps them unreadable at rest.
+/// Protected values are tied to the Windows user, so a synced or copied config file only carries
+/// ciphertext; under another account they read back as unset and have to be entered again.
+/// </summary>
+public static class PreferenceSecrets
+{
+    private const string Prefix = "dpapi:";
+
+    private static readonly byte[] Entropy = Encoding.UTF8.GetBytes("MicrophoneManager.Preferences");
+
+    public static readonly IReadOnlySet<string> Names = new HashSet<string>(StringComparer.Ordinal)
+    {
+        LocalApiServer.TokenPreference,
+        MqttPublisher.PasswordPreference,
+    };
+
+    public static bool IsSecret(string name) => Names.Contains(name);
+
+    public static bool IsProtected(string value) => value.StartsWith(Prefix, StringComparison.Ordinal);
+
+    public static string Protect(string value)
+    {
+        var data = ProtectedData.Protect(Encoding.UTF8.GetBytes(value), Entropy, DataProtectionScope.CurrentUser);
+        return Prefix + Convert.ToBase64String(data);
+    }
+
+    /// <summary>
+    /// The plain value, or null when it was protected for another user or machine. A value that
+    /// isn't protected (written by hand, or by an older build) comes back as it is.
+    /// </summary>
+    public static string? Unprotect(string? stored)
+    {
+        if (stored == null || !IsProtected(stored)) return stored;
+
+        try
+        {
+            var data = ProtectedData.Unprotect(Convert.FromBase64String(stored[Prefix.Length..]), Entropy, DataProtectionScope.CurrentUser);
+            return Encoding.UTF8.GetString(data);
+        }
+        catch (Exception ex) when (ex is CryptographicException or FormatException)
+        {
+            return null;
+        }
+    }
+
+    /// <summary>
+    /// Rewrites secrets stored in plain text (typed in by hand, or saved by an older build) so the
+    /// store protects them.
+    /// </summary>
+    public static void ProtectStored(IPreferencesStore preferences)
+    {
+        var values = preferences.GetAll();
+        foreach (var name in Names)
+        {
+            if (values.TryGetValue(name, out var value) && value is string text && !IsProtected(text))
+            {
+                preferences.SetString(name, text);
+            }
+        }
+    }
+}
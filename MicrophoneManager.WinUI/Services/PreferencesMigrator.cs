@@ -0,0 +1,119 @@
+using System.Globalization;
+using Microsoft.Extensions.Logging;
+using Microsoft.Extensions.Logging.Abstractions;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Brings stored preferences up to <see cref="CurrentVersion"/> at startup. Each step rewrites
+/// values whose shape changed and records its version as soon as it's done, so an interrupted run
+/// picks up where it stopped and a step never runs twice. Add a step here whenever a preference's
+/// format changes instead of teaching the reader to cope with both.
+/// </summary>
+public sealed class PreferencesMigrator
+{
+    public const string SchemaVersionPreference = "SchemaVersion";
+
+    /// <summary>
+    /// One upgrade: run on stores older than <paramref name="Version"/>.
+    /// </summary>
+    public sealed record Migration(int Version, string Description, Action<IPreferencesStore> Apply);
+
+    // On/off settings, for stores where someone wrote them by hand as text
+    private static readonly string[] BoolPreferences =
+    {
+        DevicePriorityService.AutoFailoverPreference,
+        SessionLockService.MuteOnLockPreference,
+        VolumeLockService.NotifyOnRevertPreference,
+        DefaultDeviceLockService.NotifyOnRevertPreference,
+        LocalApiServer.EnabledPreference,
+        MqttPublisher.EnabledPreference,
+        PreferencesStoreFactory.UseConfigFilePreference,
+    };
+
+    public static IReadOnlyList<Migration> Migrations { get; } = new[]
+    {
+        new Migration(1, "Store on/off settings written as text as booleans", ConvertTextBooleans),
+        new Migration(2, "Protect stored passwords and tokens", PreferenceSecrets.ProtectStored),
+    };
+
+    public static int CurrentVersion => Migrations[^1].Version;
+
+    private readonly IReadOnlyList<Migration> _migrations;
+    private readonly ILogger<PreferencesMigrator> _logger;
+
+    public PreferencesMigrator(ILogger<PreferencesMigrator>? logger = null)
+        : this(Migrations, logger)
+    {
+    }
+
+    /// <param name="migrations">Steps in ascending version order.</param>
+    public PreferencesMigrator(IReadOnlyList<Migration> migrations, ILogger<PreferencesMigrator>? logger = null)
+    {
+        _migrations = migrations ?? throw new ArgumentNullException(nameof(migrations));
+        _logger = logger ?? NullLogger<PreferencesMigrator>.Instance;
+    }
+
+    /// <summary>
+    /// Version the store was last migrated to; 0 for stores written before versioning.
+    /// </summary>
+    public static int GetVersion(IPreferencesStore preferences)
+    {
+        return int.TryParse(preferences.GetString(SchemaVersionPreference), NumberStyles.Integer, CultureInfo.InvariantCulture, out var version) && version > 0
+            ? version
+            : 0;
+    }
+
+    public static void SetVersion(IPreferencesStore preferences, int version)
+    {
+        preferences.SetString(SchemaVersionPreference, version.ToString(CultureInfo.InvariantCulture));
+    }
+
+    /// <summary>
+    /// Runs every step newer than the stored version. A store from a newer build is left alone,
+    /// since we can't know what its values mean.
+    /// </summary>
+    /// <returns>The descriptions of the steps that ran.</returns>
+    public IReadOnlyList<string> Migrate(IPreferencesStore preferences)
+    {
+        var ran = new List<string>();
+        var version = GetVersion(preferences);
+        var latest = _migrations.Count > 0 ? _migrations[^1].Version : 0;
+
+        if (version > latest)
+        {
+            _logger.LogWarning("Preferences are schema {Version}, newer than this build's {Latest}; not migrating", version, latest);
+            return ran;
+        }
+
+        foreach (var migration in _migrations.Where(m => m.Version > version))
+        {
+            migration.Apply(preferences);
+            SetVersion(preferences, migration.Version);
+            ran.Add(migration.Description);
+        }
+
+        return ran;
+    }
+
+    private static void ConvertTextBooleans(IPreferencesStore preferences)
+    {
+        var values = preferences.GetAll();
+        foreach (var name in BoolPreferences)
+        {
+            if (!values.TryGetValue(name, out var value) || value is not string text) continue;
+
+            switch (text.Trim().ToLowerInvariant())
+            {
+                case "1" or "true" or "yes" or "on":
+                    preferences.SetString(name, null);
+                    preferences.SetBool(name, true);
+                    break;
+                case "0" or "false" or "no" or "off" or "":
+                    preferences.SetString(name, null);
+                    preferences.SetBool(name, false);
+                    break;
+            }
+        }
+    }
+}
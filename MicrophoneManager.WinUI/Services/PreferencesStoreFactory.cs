@@ -0,0 +1,53 @@
+using Microsoft.Extensions.Logging;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Picks where preferences live. In <see cref="PortableMode"/> that's always the TOML file beside the
+/// exe. Otherwise the file in %APPDATA% is used when it exists or when the registry flag
+/// <see cref="UseConfigFilePreference"/> is set, and the registry when neither is true. Switching to the file copies
+/// the current registry values into it the first time, so nothing is lost.
+/// </summary>
+public static class PreferencesStoreFactory
+{
+    public const string UseConfigFilePreference = "UseConfigFile";
+
+    /// <summary>
+    /// Opens the store in use and brings it up to the current schema.
+    /// </summary>
+    public static IPreferencesStore Create(ILoggerFactory? loggerFactory = null)
+    {
+        var store = PortableMode.IsEnabled
+            ? new TomlPreferencesStore(PortableMode.ConfigPath, loggerFactory?.CreateLogger<TomlPreferencesStore>())
+            : Create(new RegistryPreferencesStore(), TomlPreferencesStore.DefaultPath, loggerFactory);
+        new PreferencesMigrator(loggerFactory?.CreateLogger<PreferencesMigrator>()).Migrate(store);
+        return store;
+    }
+
+    public static IPreferencesStore Create(RegistryPreferencesStore registry, string configPath, ILoggerFactory? loggerFactory = null)
+    {
+        var useFile = File.Exists(configPath) || registry.GetBool(UseConfigFilePreference, false);
+        if (!useFile) return registry;
+
+        var file = new TomlPreferencesStore(configPath, loggerFactory?.CreateLogger<TomlPreferencesStore>());
+        MigrateIfEmpty(registry.GetAll(), file);
+        return file;
+    }
+
+    /// <summary>
+    /// Copies registry values into a config file that has none yet. An empty file is how users opt
+    /// in, so it counts as not yet migrated.
+    /// </summary>
+    public static bool MigrateIfEmpty(IReadOnlyDictionary<string, object> registryValues, TomlPreferencesStore file)
+    {
+        if (file.GetAll().Count > 0) return false;
+
+        var values = registryValues
+            .Where(v => v.Key != UseConfigFilePreference)
+            .ToDictionary(v => v.Key, v => v.Value, StringComparer.Ordinal);
+        if (values.Count == 0) return false;
+
+        file.Import(values);
+        return true;
+    }
+}
@@ -0,0 +1,133 @@
+using System.Collections.Concurrent;
+using System.Drawing;
+using System.Drawing.Imaging;
+using System.Runtime.InteropServices;
+using System.Text;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Resolves capture-session PIDs to executable names, paths and icons so sessions read
+/// "Teams.exe, obs64.exe" instead of raw process IDs.
+/// </summary>
+public static class ProcessInfoResolver
+{
+    private const uint ProcessQueryLimitedInformation = 0x1000;
+
+    // Icons are keyed by path; the same executable is usually behind many sessions and refreshes
+    private static readonly ConcurrentDictionary<string, byte[]?> IconPngByPath = new(StringComparer.OrdinalIgnoreCase);
+
+    [DllImport("kernel32.dll", SetLastError = true)]
+    private static extern IntPtr OpenProcess(uint desiredAccess, [MarshalAs(UnmanagedType.Bool)] bool inheritHandle, uint processId);
+
+    [DllImport("kernel32.dll", SetLastError = true, CharSet = CharSet.Unicode)]
+    [return: MarshalAs(UnmanagedType.Bool)]
+    private static extern bool QueryFullProcessImageName(IntPtr process, uint flags, StringBuilder exeName, ref uint size);
+
+    [DllImport("kernel32.dll", SetLastError = true)]
+    [return: MarshalAs(UnmanagedType.Bool)]
+    private static extern bool CloseHandle(IntPtr handle);
+
+    [DllImport("user32.dll")]
+    private static extern IntPtr GetForegroundWindow();
+
+    [DllImport("user32.dll")]
+    private static extern uint GetWindowThreadProcessId(IntPtr hWnd, out uint lpdwProcessId);
+
+    /// <summary>
+    /// Full path of the process's executable, or null if it exited or can't be queried.
+    /// </summary>
+    /// <remarks>
+    /// Uses PROCESS_QUERY_LIMITED_INFORMATION, which also works for elevated and protected
+    /// processes where <see cref="System.Diagnostics.Process.MainModule"/> throws.
+    /// </remarks>
+    public static string? GetExecutablePath(uint processId)
+    {
+        if (processId == 0) return null;
+
+        var handle = OpenProcess(ProcessQueryLimitedInformation, false, processId);
+        if (handle == IntPtr.Zero) return null;
+
+        try
+        {
+            var size = 1024u;
+            var buffer = new StringBuilder((int)size);
+            return QueryFullProcessImageName(handle, 0, buffer, ref size) ? buffer.ToString() : null;
+        }
+        finally
+        {
+            CloseHandle(handle);
+        }
+    }
+
+    /// <summary>
+    /// Executable file name (e.g. "Teams.exe"), falling back to the process name, or "" when unknown.
+    /// </summary>
+    public static string GetExecutableName(uint processId, string? executablePath)
+    {
+        if (!string.IsNullOrEmpty(executablePath)) return Path.GetFileName(executablePath);
+        if (processId == 0) return "";
+
+        try
+        {
+            using var process = System.Diagnostics.Process.GetProcessById((int)processId);
+            return process.ProcessName + ".exe";
+        }
+        catch
+        {
+            // Process exited or is inaccessible
+            return "";
+        }
+    }
+
+    /// <summary>
+    /// Name of the foreground application's process, or null when it's this app or unknown.
+    /// </summary>
+    /// <remarks>
+    /// Windows doesn't say who changed a default or a volume; the foreground application is the best guess.
+    /// </remarks>
+    public static string? GetForegroundProcessName()
+    {
+        try
+        {
+            var hwnd = GetForegroundWindow();
+            if (hwnd == IntPtr.Zero) return null;
+
+            GetWindowThreadProcessId(hwnd, out var processId);
+            if (processId == 0 || processId == Environment.ProcessId) return null;
+
+            using var process = System.Diagnostics.Process.GetProcessById((int)processId);
+            return process.ProcessName;
+        }
+        catch
+        {
+            return null;
+        }
+    }
+
+    /// <summary>
+    /// The executable's associated icon encoded as PNG, or null if it has none.
+    /// </summary>
+    public static byte[]? GetIconPng(string executablePath)
+    {
+        if (string.IsNullOrEmpty(executablePath)) return null;
+
+        return IconPngByPath.GetOrAdd(executablePath, path =>
+        {
+            try
+            {
+                using var icon = Icon.ExtractAssociatedIcon(path);
+                if (icon == null) return null;
+
+                using var bitmap = icon.ToBitmap();
+                using var stream = new MemoryStream();
+                bitmap.Save(stream, ImageFormat.Png);
+                return stream.ToArray();
+            }
+            catch
+            {
+                return null;
+            }
+        });
+    }
+}
@@ -0,0 +1,156 @@
+using System.Text.Json;
+using Microsoft.Extensions.Logging;
+using Microsoft.Extensions.Logging.Abstractions;
+using MicrophoneManager.WinUI.Models;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Saves, lists and applies <see cref="AudioProfile"/>s. Profiles are stored together as one JSON
+/// preference; applying one restores its snapshot through <see cref="AudioSnapshotService"/>.
+/// </summary>
+public class ProfileService
+{
+    public const string ProfilesPreference = "Profiles";
+
+    private readonly IPreferencesStore _preferences;
+    private readonly AudioSnapshotService _snapshotService;
+    private readonly ILogger<ProfileService> _logger;
+
+    /// <summary>
+    /// Raised after a profile is saved, renamed or deleted.
+    /// </summary>
+    public event EventHandler? ProfilesChanged;
+
+    /// <summary>
+    /// Raised after a profile is renamed, so settings that refer to it by name can follow.
+    /// </summary>
+    public event EventHandler<RenamedEventArgs>? ProfileRenamed;
+
+    public ProfileService(IAudioDeviceService audioService, IPreferencesStore preferences, ILogger<ProfileService>? logger = null)
+    {
+        _preferences = preferences ?? throw new ArgumentNullException(nameof(preferences));
+        _snapshotService = new AudioSnapshotService(audioService);
+        _logger = logger ?? NullLogger<ProfileService>.Instance;
+    }
+
+    public IReadOnlyList<AudioProfile> GetProfiles()
+    {
+        var json = _preferences.GetString(ProfilesPreference);
+        if (string.IsNullOrEmpty(json)) return Array.Empty<AudioProfile>();
+
+        try
+        {
+            return JsonSerializer.Deserialize<List<AudioProfile>>(json) ?? new List<AudioProfile>();
+        }
+        catch (JsonException ex)
+        {
+            _logger.LogWarning(ex, "Ignoring unreadable profiles");
+            return Array.Empty<AudioProfile>();
+        }
+    }
+
+    public AudioProfile? GetProfile(string name)
+    {
+        return GetProfiles().FirstOrDefault(p => string.Equals(p.Name, name, StringComparison.OrdinalIgnoreCase));
+    }
+
+    /// <summary>
+    /// Captures the current audio state under <paramref name="name"/>, replacing any profile with that name.
+    /// </summary>
+    public AudioProfile SaveProfile(string name)
+    {
+        if (string.IsNullOrWhiteSpace(name)) throw new ArgumentException("Profile name is required.", nameof(name));
+
+        var profile = new AudioProfile { Name = name.Trim(), Snapshot = _snapshotService.TakeSnapshot() };
+        var profiles = GetProfiles().ToList();
+        var index = profiles.FindIndex(p => string.Equals(p.Name, profile.Name, StringComparison.OrdinalIgnoreCase));
+        if (index >= 0)
+        {
+            profiles[index] = profile;
+        }
+        else
+        {
+            profiles.Add(profile);
+        }
+
+        Save(profiles);
+        return profile;
+    }
+
+    /// <summary>
+    /// Gives a profile a new name, keeping its settings.
+    /// </summary>
+    /// <returns>False if there is no such profile or another profile already has the new name.</returns>
+    public bool RenameProfile(string name, string newName)
+    {
+        if (string.IsNullOrWhiteSpace(newName)) throw new ArgumentException("Profile name is required.", nameof(newName));
+
+        newName = newName.Trim();
+        var profiles = GetProfiles().ToList();
+        var index = profiles.FindIndex(p => string.Equals(p.Name, name, StringComparison.OrdinalIgnoreCase));
+        if (index < 0) return false;
+
+        // Changing only the case of a name is fine; taking another profile's name is not
+        var existing = profiles.FindIndex(p => string.Equals(p.Name, newName, StringComparison.OrdinalIgnoreCase));
+        if (existing >= 0 && existing != index) return false;
+
+        var oldName = profiles[index].Name;
+        if (oldName == newName) return true;
+
+        profiles[index] = new AudioProfile { Name = newName, Snapshot = profiles[index].Snapshot };
+        Save(profiles);
+        ProfileRenamed?.Invoke(this, new RenamedEventArgs(oldName, newName));
+        return true;
+    }
+
+    /// <summary>
+    /// First unused "Profile N" name, for saving without prompting.
+    /// </summary>
+    public string GetNewProfileName()
+    {
+        var names = GetProfiles().Select(p => p.Name).ToHashSet(StringComparer.OrdinalIgnoreCase);
+        var number = 1;
+        while (names.Contains($"Profile {number}")) number++;
+        return $"Profile {number}";
+    }
+
+    public bool DeleteProfile(string name)
+    {
+        var profiles = GetProfiles().ToList();
+        if (profiles.RemoveAll(p => string.Equals(p.Name, name, StringComparison.OrdinalIgnoreCase)) == 0) return false;
+
+        Save(profiles);
+        return true;
+    }
+
+    /// <summary>
+    /// Applies a saved profile. Devices in it that aren't connected are skipped.
+    /// </summary>
+    /// <returns>False if the profile doesn't exist or any setting couldn't be applied.</returns>
+    public async Task<bool> ApplyProfileAsync(string name, CancellationToken cancellationToken = default)
+    {
+        var profile = GetProfile(name);
+        if (profile == null) return false;
+
+        return await _snapshotService.RestoreSnapshotAsync(profile.Snapshot, cancellationToken);
+    }
+
+    private void Save(List<AudioProfile> profiles)
+    {
+        _preferences.SetString(ProfilesPreference, JsonSerializer.Serialize(profiles));
+        ProfilesChanged?.Invoke(this, EventArgs.Empty);
+    }
+
+    public sealed class RenamedEventArgs : EventArgs
+    {
+        public RenamedEventArgs(string oldName, string newName)
+        {
+            OldName = oldName;
+            NewName = newName;
+        }
+
+        public string OldName { get; }
+        public string NewName { get; }
+    }
+}
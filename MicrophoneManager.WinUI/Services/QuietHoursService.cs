@@ -0,0 +1,143 @@
+using MicrophoneManager.WinUI.Models;
+using NAudio.CoreAudioApi;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Mutes the default microphone during the configured <see cref="QuietHoursWindow"/>s and unmutes it
+/// when the window ends. Unmuting by hand during quiet hours overrides the schedule until the
+/// window is over, and the end of that window then leaves the mute state alone.
+/// </summary>
+public sealed class QuietHoursService : IDisposable
+{
+    public const string QuietHoursPreference = "QuietHours";
+
+    private static readonly TimeSpan EvaluateInterval = TimeSpan.FromSeconds(30);
+
+    private readonly IAudioDeviceService _audioService;
+    private readonly IPreferencesStore _preferences;
+    private readonly Func<DateTime> _clock;
+    private readonly SynchronizationContext? _syncContext;
+    private Timer? _timer;
+    private string? _mutedDeviceId;
+    private bool _disposed;
+
+    /// <summary>
+    /// Raised when quiet hours start, end or are overridden.
+    /// </summary>
+    public event EventHandler? StateChanged;
+
+    public QuietHoursService(IAudioDeviceService audioService, IPreferencesStore preferences)
+        : this(audioService, preferences, () => DateTime.Now)
+    {
+    }
+
+    public QuietHoursService(IAudioDeviceService audioService, IPreferencesStore preferences, Func<DateTime> clock)
+    {
+        _audioService = audioService ?? throw new ArgumentNullException(nameof(audioService));
+        _preferences = preferences ?? throw new ArgumentNullException(nameof(preferences));
+        _clock = clock ?? throw new ArgumentNullException(nameof(clock));
+        _syncContext = SynchronizationContext.Current;
+
+        _audioService.DefaultMicrophoneVolumeChanged += OnDefaultMicrophoneVolumeChanged;
+
+        // Like the audio service's polling, no background timer without a UI context (unit tests)
+        if (_syncContext != null)
+        {
+            _timer = new Timer(_ => _syncContext.Post(_ => Evaluate(), null), null, TimeSpan.Zero, EvaluateInterval);
+        }
+    }
+
+    /// <summary>
+    /// End of the quiet hours in effect, or null outside quiet hours.
+    /// </summary>
+    public DateTime? QuietUntil { get; private set; }
+
+    /// <summary>
+    /// True when the user unmuted during the current quiet hours.
+    /// </summary>
+    public bool IsOverridden { get; private set; }
+
+    /// <summary>
+    /// Whether the schedule is currently holding the microphone muted.
+    /// </summary>
+    public bool IsActive => QuietUntil != null && !IsOverridden;
+
+    public IReadOnlyList<QuietHoursWindow> GetWindows()
+    {
+        return _preferences.GetStringList(QuietHoursPreference)
+            .Select(text => QuietHoursWindow.TryParse(text, out var window) ? window : null)
+            .OfType<QuietHoursWindow>()
+            .ToList();
+    }
+
+    public void SetWindows(IReadOnlyList<QuietHoursWindow> windows)
+    {
+        _preferences.SetStringList(QuietHoursPreference, windows.Select(w => w.ToString()).ToList());
+        Evaluate();
+    }
+
+    /// <summary>
+    /// Starts or ends quiet hours according to the clock. Called periodically and after edits.
+    /// </summary>
+    public void Evaluate()
+    {
+        if (_disposed) return;
+
+        var now = _clock();
+        var active = GetWindows().FirstOrDefault(w => w.Contains(now));
+
+        if (QuietUntil is { } until && (now >= until || active == null))
+        {
+            if (!IsOverridden && _mutedDeviceId != null)
+            {
+                _audioService.SetMute(_mutedDeviceId, false);
+            }
+
+            _mutedDeviceId = null;
+            QuietUntil = null;
+            IsOverridden = false;
+            StateChanged?.Invoke(this, EventArgs.Empty);
+        }
+
+        // Checked after ending so back-to-back windows hand over in one pass
+        if (QuietUntil == null && active != null)
+        {
+            QuietUntil = active.GetEnd(now);
+            MuteDefault();
+            StateChanged?.Invoke(this, EventArgs.Empty);
+        }
+    }
+
+    private void MuteDefault()
+    {
+        var defaultId = _audioService.GetDefaultDeviceId(Role.Console);
+        if (defaultId == null || _audioService.IsMuted(defaultId)) return;
+
+        // Only a mic we muted gets unmuted when the window ends
+        if (_audioService.SetMute(defaultId, true))
+        {
+            _mutedDeviceId = defaultId;
+        }
+    }
+
+    private void OnDefaultMicrophoneVolumeChanged(object? sender, AudioDeviceService.DefaultMicrophoneVolumeChangedEventArgs e)
+    {
+        if (!IsActive || e.IsMuted) return;
+
+        // Unmuted during quiet hours: the user wants the mic, so stop managing it for this window
+        IsOverridden = true;
+        _mutedDeviceId = null;
+        StateChanged?.Invoke(this, EventArgs.Empty);
+    }
+
+    public void Dispose()
+    {
+        if (_disposed) return;
+        _disposed = true;
+
+        try { _timer?.Dispose(); } catch { }
+        _timer = null;
+        try { _audioService.DefaultMicrophoneVolumeChanged -= OnDefaultMicrophoneVolumeChanged; } catch { }
+    }
+}
@@ -0,0 +1,224 @@
+using Microsoft.Extensions.Logging;
+using MicrophoneManager.WinUI.Models;
+using NAudio.CoreAudioApi;
+using NAudio.Wave;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Records a microphone to a PCM WAV file using WASAPI shared-mode capture.
+/// The device mix format (normally 32-bit float, sometimes 16-, 24- or 32-bit PCM) is converted
+/// to 16- or 24-bit PCM at the mix sample rate and channel count.
+/// </summary>
+public class RecordingService : IDisposable
+{
+    private readonly ILogger<RecordingService> _logger;
+    private readonly object _recordingsLock = new();
+    private readonly Dictionary<string, ActiveRecording> _recordingsByDeviceId = new();
+    private bool _disposed;
+
+    // KSDATAFORMAT_SUBTYPE_IEEE_FLOAT
+    private static readonly Guid IeeeFloatSubFormat = new("00000003-0000-0010-8000-00aa00389b71");
+
+    private sealed class ActiveRecording
+    {
+        public required WasapiCapture Capture { get; init; }
+        public required WaveFileWriter Writer { get; init; }
+        public required string FilePath { get; init; }
+        public required bool InputIsFloat { get; init; }
+        public required int BytesPerInputSample { get; init; }
+        public required int BytesPerOutputSample { get; init; }
+        public TaskCompletionSource Stopped { get; } = new(TaskCreationOptions.RunContinuationsAsynchronously);
+    }
+
+    public RecordingService(ILogger<RecordingService> logger)
+    {
+        _logger = logger ?? throw new ArgumentNullException(nameof(logger));
+    }
+
+    /// <summary>
+    /// Default folder for recordings: Music\Microphone Manager.
+    /// </summary>
+    public static string DefaultRecordingsFolder =>
+        Path.Combine(Environment.GetFolderPath(Environment.SpecialFolder.MyMusic), "Microphone Manager");
+
+    public bool IsRecording(string deviceId)
+    {
+        lock (_recordingsLock)
+        {
+            return _recordingsByDeviceId.ContainsKey(deviceId);
+        }
+    }
+
+    /// <summary>
+    /// Starts recording <paramref name="deviceId"/> to <paramref name="filePath"/>.
+    /// </summary>
+    /// <param name="bitsPerSample">16 or 24.</param>
+    public void StartRecording(string deviceId, string filePath, int bitsPerSample = 16)
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+        if (bitsPerSample is not (16 or 24))
+        {
+            throw new ArgumentOutOfRangeException(nameof(bitsPerSample), "Only 16- and 24-bit recordings are supported.");
+        }
+
+        lock (_recordingsLock)
+        {
+            if (_recordingsByDeviceId.ContainsKey(deviceId))
+            {
+                throw new InvalidOperationException("This microphone is already being recorded.");
+            }
+
+            Directory.CreateDirectory(Path.GetDirectoryName(filePath)!);
+
+            using var enumerator = new MMDeviceEnumerator();
+            var device = enumerator.GetDevice(deviceId);
+            var capture = new WasapiCapture(device, true, 20);
+            WaveFileWriter? writer = null;
+            try
+            {
+                var mixFormat = capture.WaveFormat;
+                var isFloat = mixFormat.Encoding == WaveFormatEncoding.IeeeFloat ||
+                              (mixFormat is WaveFormatExtensible ext && ext.SubFormat == IeeeFloatSubFormat);
+                var bytesPerInputSample = mixFormat.BlockAlign / mixFormat.Channels;
+                if (isFloat ? bytesPerInputSample != 4 : bytesPerInputSample is not (2 or 3 or 4))
+                {
+                    throw new NotSupportedException($"Recording from a {mixFormat} capture format is not supported.");
+                }
+
+                writer = new WaveFileWriter(filePath, new WaveFormat(mixFormat.SampleRate, bitsPerSample, mixFormat.Channels));
+                var recording = new ActiveRecording
+                {
+                    Capture = capture,
+                    Writer = writer,
+                    FilePath = filePath,
+                    InputIsFloat = isFloat,
+                    BytesPerInputSample = bytesPerInputSample,
+                    BytesPerOutputSample = bitsPerSample / 8
+                };
+
+                capture.DataAvailable += (s, e) => WriteSamples(recording, e.Buffer, e.BytesRecorded);
+                capture.RecordingStopped += (s, e) =>
+                {
+                    if (e.Exception != null)
+                    {
+                        _logger.LogWarning(e.Exception, "Recording of {DeviceId} stopped with an error", deviceId);
+                    }
+
+                    recording.Stopped.TrySetResult();
+                };
+
+                _recordingsByDeviceId[deviceId] = recording;
+                capture.StartRecording();
+            }
+            catch
+            {
+                // A bad path, a locked file or a capture that won't start must not leave the device open
+                _recordingsByDeviceId.Remove(deviceId);
+                capture.Dispose();
+                writer?.Dispose();
+                throw;
+            }
+
+            _logger.LogInformation("Recording {DeviceId} to {FilePath}", deviceId, filePath);
+        }
+    }
+
+    /// <summary>
+    /// Stops a recording and finalizes the WAV header.
+    /// </summary>
+    /// <returns>The file and its duration, or null if the device was not being recorded.</returns>
+    public async Task<RecordingResult?> StopRecordingAsync(string deviceId)
+    {
+        ActiveRecording? recording;
+        lock (_recordingsLock)
+        {
+            if (!_recordingsByDeviceId.Remove(deviceId, out recording))
+            {
+                return null;
+            }
+        }
+
+        recording.Capture.StopRecording();
+        await recording.Stopped.Task;
+
+        var duration = recording.Writer.TotalTime;
+        recording.Writer.Dispose();
+        recording.Capture.Dispose();
+
+        _logger.LogInformation("Saved {Duration} recording of {DeviceId} to {FilePath}", duration, deviceId, recording.FilePath);
+        return new RecordingResult(deviceId, recording.FilePath, duration);
+    }
+
+    private static void WriteSamples(ActiveRecording recording, byte[] buffer, int bytesRecorded)
+    {
+        if (!recording.InputIsFloat && recording.BytesPerInputSample == recording.BytesPerOutputSample)
+        {
+            recording.Writer.Write(buffer, 0, bytesRecorded);
+            return;
+        }
+
+        var sampleCount = bytesRecorded / recording.BytesPerInputSample;
+        var output = new byte[sampleCount * recording.BytesPerOutputSample];
+        var o = 0;
+        for (var i = 0; i < sampleCount; i++)
+        {
+            var sample = Math.Clamp(ReadSample(recording, buffer, i * recording.BytesPerInputSample), -1.0f, 1.0f);
+            if (recording.BytesPerOutputSample == 2)
+            {
+                var value = (short)(sample * short.MaxValue);
+                output[o++] = (byte)value;
+                output[o++] = (byte)(value >> 8);
+            }
+            else
+            {
+                var value = (int)(sample * 8388607.0f);
+                output[o++] = (byte)value;
+                output[o++] = (byte)(value >> 8);
+                output[o++] = (byte)(value >> 16);
+            }
+        }
+
+        recording.Writer.Write(output, 0, output.Length);
+    }
+
+    private static float ReadSample(ActiveRecording recording, byte[] buffer, int offset)
+    {
+        if (recording.InputIsFloat) return BitConverter.ToSingle(buffer, offset);
+
+        return recording.BytesPerInputSample switch
+        {
+            2 => BitConverter.ToInt16(buffer, offset) / 32768.0f,
+            3 => (buffer[offset] | (buffer[offset + 1] << 8) | ((sbyte)buffer[offset + 2] << 16)) / 8388608.0f,
+            _ => BitConverter.ToInt32(buffer, offset) / 2147483648.0f
+        };
+    }
+
+    public void Dispose()
+    {
+        if (_disposed) return;
+        _disposed = true;
+
+        List<KeyValuePair<string, ActiveRecording>> recordings;
+        lock (_recordingsLock)
+        {
+            recordings = _recordingsByDeviceId.ToList();
+            _recordingsByDeviceId.Clear();
+        }
+
+        // Finalize any open files so their WAV headers are valid. RecordingStopped is posted to the
+        // UI context, so don't wait on it here; disposing the capture stops its thread first.
+        foreach (var (deviceId, recording) in recordings)
+        {
+            try
+            {
+                recording.Capture.Dispose();
+                recording.Writer.Dispose();
+            }
+            catch (Exception ex)
+            {
+                _logger.LogWarning(ex, "Could not finalize recording of {DeviceId} on shutdown", deviceId);
+            }
+        }
+    }
+}
@@ -0,0 +1,107 @@
+using Microsoft.Win32;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Stores preferences as values under HKCU\Software\MicrophoneManager. <see cref="PreferenceSecrets"/>
+/// are stored DPAPI-protected.
+/// </summary>
+public sealed class RegistryPreferencesStore : IPreferencesStore
+{
+    private const string RegistryKeyPath = @"Software\MicrophoneManager";
+
+    public string? GetString(string name)
+    {
+        var value = Read(name) as string;
+        return PreferenceSecrets.IsSecret(name) ? PreferenceSecrets.Unprotect(value) : value;
+    }
+
+    public void SetString(string name, string? value)
+    {
+        Write(key =>
+        {
+            if (value == null)
+            {
+                key.DeleteValue(name, false);
+            }
+            else
+            {
+                key.SetValue(name, PreferenceSecrets.IsSecret(name) ? PreferenceSecrets.Protect(value) : value, RegistryValueKind.String);
+            }
+        });
+    }
+
+    public IReadOnlyList<string> GetStringList(string name)
+    {
+        return Read(name) as string[] ?? Array.Empty<string>();
+    }
+
+    public void SetStringList(string name, IReadOnlyList<string> values)
+    {
+        Write(key => key.SetValue(name, values.ToArray(), RegistryValueKind.MultiString));
+    }
+
+    public bool GetBool(string name, bool defaultValue)
+    {
+        return Read(name) is int value ? value != 0 : defaultValue;
+    }
+
+    public void SetBool(string name, bool value)
+    {
+        Write(key => key.SetValue(name, value ? 1 : 0, RegistryValueKind.DWord));
+    }
+
+    /// <summary>
+    /// Every stored value, with DWORDs read back as booleans.
+    /// </summary>
+    public IReadOnlyDictionary<string, object> GetAll()
+    {
+        var values = new Dictionary<string, object>(StringComparer.Ordinal);
+        try
+        {
+            using var key = Registry.CurrentUser.OpenSubKey(RegistryKeyPath, false);
+            if (key == null) return values;
+
+            foreach (var name in key.GetValueNames())
+            {
+                switch (key.GetValue(name))
+                {
+                    case string text: values[name] = text; break;
+                    case string[] list: values[name] = list; break;
+                    case int flag: values[name] = flag != 0; break;
+                }
+            }
+        }
+        catch
+        {
+        }
+
+        return values;
+    }
+
+    private static object? Read(string name)
+    {
+        try
+        {
+            using var key = Registry.CurrentUser.OpenSubKey(RegistryKeyPath, false);
+            return key?.GetValue(name);
+        }
+        catch
+        {
+            return null;
+        }
+    }
+
+    private static void Write(Action<RegistryKey> write)
+    {
+        try
+        {
+            using var key = Registry.CurrentUser.CreateSubKey(RegistryKeyPath, true);
+            write(key);
+        }
+        catch (Exception)
+        {
+            // Silently fail - registry access may be restricted
+        }
+    }
+}
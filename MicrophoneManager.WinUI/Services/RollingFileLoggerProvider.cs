@@ -0,0 +1,260 @@
+using System.Collections.Concurrent;
+using System.Text;
+using Microsoft.Extensions.Logging;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Writes log entries to <c>MicrophoneManager.log</c> in <see cref="DefaultDirectory"/>, starting a
+/// new file when it reaches <see cref="MaxFileBytes"/> and keeping the last <see cref="MaxArchives"/>
+/// as <c>MicrophoneManager.1.log</c> (newest) onwards. Entries are queued and written on a
+/// background thread so logging never blocks the COM or UI thread. The level comes from the
+/// <see cref="LogLevelPreference"/> preference. The last <see cref="RecentCapacity"/> entries are
+/// also kept in memory for crash reports.
+/// </summary>
+public sealed class RollingFileLoggerProvider : ILoggerProvider, ISupportExternalScope
+{
+    public const string LogLevelPreference = "LogLevel";
+    public const string FileName = "MicrophoneManager.log";
+    public const long MaxFileBytes = 2 * 1024 * 1024;
+    public const int MaxArchives = 4;
+    public const int RecentCapacity = 500;
+
+    private readonly IPreferencesStore? _preferences;
+    private readonly BlockingCollection<string> _queue = new(boundedCapacity: 10_000);
+    private readonly Thread _writer;
+    private readonly Queue<string> _recent = new();
+    private IExternalScopeProvider? _scopeProvider;
+    private LogLevel _minimumLevel;
+    private int _pending;
+    private bool _disposed;
+
+    public RollingFileLoggerProvider(IPreferencesStore preferences)
+        : this(DefaultDirectory, preferences)
+    {
+    }
+
+    public RollingFileLoggerProvider(string directory, IPreferencesStore? preferences = null)
+    {
+        Directory = directory ?? throw new ArgumentNullException(nameof(directory));
+        _preferences = preferences;
+        _minimumLevel = ReadLevel(preferences);
+
+        _writer = new Thread(WriteLoop) { Name = "Log Writer", IsBackground = true };
+        _writer.Start();
+    }
+
+    /// <summary>
+    /// %LOCALAPPDATA%\MicrophoneManager\Logs, or Logs beside the exe in portable mode.
+    /// </summary>
+    public static string DefaultDirectory => PortableMode.IsEnabled
+        ? Path.Combine(PortableMode.Directory, "Logs")
+        : Path.Combine(Environment.GetFolderPath(Environment.SpecialFolder.LocalApplicationData), "MicrophoneManager", "Logs");
+
+    public string Directory { get; }
+
+    public string CurrentFilePath => Path.Combine(Directory, FileName);
+
+    /// <summary>
+    /// Lowest level written. Setting it saves the preference and applies straight away.
+    /// </summary>
+    public LogLevel MinimumLevel
+    {
+        get => _minimumLevel;
+        set
+        {
+            _minimumLevel = value;
+            _preferences?.SetString(LogLevelPreference, value.ToString());
+        }
+    }
+
+    public ILogger CreateLogger(string categoryName)
+    {
+        return new FileLogger(this, categoryName);
+    }
+
+    public void SetScopeProvider(IExternalScopeProvider scopeProvider)
+    {
+        _scopeProvider = scopeProvider;
+    }
+
+    /// <summary>
+    /// The most recent entries, oldest first.
+    /// </summary>
+    public IReadOnlyList<string> GetRecentEntries()
+    {
+        lock (_recent)
+        {
+            return _recent.ToArray();
+        }
+    }
+
+    /// <summary>
+    /// Blocks until everything queued so far is on disk, or the timeout passes.
+    /// </summary>
+    public void Flush(TimeSpan timeout)
+    {
+        var deadline = DateTime.UtcNow + timeout;
+        while (Volatile.Read(ref _pending) > 0 && DateTime.UtcNow < deadline)
+        {
+            Thread.Sleep(10);
+        }
+    }
+
+    private static LogLevel ReadLevel(IPreferencesStore? preferences)
+    {
+        if (Enum.TryParse<LogLevel>(preferences?.GetString(LogLevelPreference), ignoreCase: true, out var level))
+        {
+            return level;
+        }
+
+#if DEBUG
+        return LogLevel.Debug;
+#else
+        return LogLevel.Information;
+#endif
+    }
+
+    private void Enqueue(string line)
+    {
+        if (_disposed) return;
+
+        lock (_recent)
+        {
+            if (_recent.Count == RecentCapacity) _recent.Dequeue();
+            _recent.Enqueue(line);
+        }
+
+        // A full queue means the disk can't keep up; dropping beats stalling the caller
+        Interlocked.Increment(ref _pending);
+        if (!_queue.TryAdd(line)) Interlocked.Decrement(ref _pending);
+    }
+
+    private void WriteLoop()
+    {
+        StreamWriter? writer = null;
+        try
+        {
+            foreach (var line in _queue.GetConsumingEnumerable())
+            {
+                try
+                {
+                    writer ??= Open();
+                    writer.Write(line);
+
+                    if (_queue.Count == 0) writer.Flush();
+
+                    if (writer.BaseStream.Length >= MaxFileBytes)
+                    {
+                        writer.Dispose();
+                        writer = null;
+                        Roll();
+                    }
+                }
+                catch (Exception ex) when (ex is IOException or UnauthorizedAccessException)
+                {
+                    System.Diagnostics.Debug.WriteLine($"Log write failed: {ex.Message}");
+                    writer?.Dispose();
+                    writer = null;
+                }
+                finally
+                {
+                    Interlocked.Decrement(ref _pending);
+                }
+            }
+        }
+        finally
+        {
+            writer?.Dispose();
+        }
+    }
+
+    private StreamWriter Open()
+    {
+        System.IO.Directory.CreateDirectory(Directory);
+        var stream = new FileStream(CurrentFilePath, FileMode.Append, FileAccess.Write, FileShare.ReadWrite | FileShare.Delete);
+        return new StreamWriter(stream, new UTF8Encoding(false));
+    }
+
+    /// <summary>
+    /// Shifts MicrophoneManager.log to .1.log, .1 to .2 and so on, dropping the oldest.
+    /// </summary>
+    private void Roll()
+    {
+        string Archive(int index) => Path.Combine(Directory, $"{Path.GetFileNameWithoutExtension(FileName)}.{index}.log");
+
+        File.Delete(Archive(MaxArchives));
+        for (var index = MaxArchives - 1; index >= 1; index--)
+        {
+            if (File.Exists(Archive(index))) File.Move(Archive(index), Archive(index + 1));
+        }
+
+        File.Move(CurrentFilePath, Archive(1));
+    }
+
+    public void Dispose()
+    {
+        if (_disposed) return;
+        _disposed = true;
+
+        _queue.CompleteAdding();
+        _writer.Join(TimeSpan.FromSeconds(2));
+    }
+
+    private sealed class FileLogger : ILogger
+    {
+        private readonly RollingFileLoggerProvider _provider;
+        private readonly string _category;
+
+        public FileLogger(RollingFileLoggerProvider provider, string category)
+        {
+            _provider = provider;
+
+            // "MicrophoneManager.WinUI.Services.AudioDeviceService" reads as just the class name
+            _category = category[(category.LastIndexOf('.') + 1)..];
+        }
+
+        public IDisposable? BeginScope<TState>(TState state) where TState : notnull
+        {
+            return _provider._scopeProvider?.Push(state);
+        }
+
+        public bool IsEnabled(LogLevel logLevel)
+        {
+            return logLevel != LogLevel.None && logLevel >= _provider._minimumLevel;
+        }
+
+        public void Log<TState>(LogLevel logLevel, EventId eventId, TState state, Exception? exception, Func<TState, Exception?, string> formatter)
+        {
+            if (!IsEnabled(logLevel)) return;
+
+            var builder = new StringBuilder();
+            builder.Append(DateTime.Now.ToString("yyyy-MM-dd HH:mm:ss.fff"))
+                .Append(" [").Append(Abbreviate(logLevel)).Append("] ")
+                .Append('[').Append(Environment.CurrentManagedThreadId).Append("] ")
+                .Append(_category);
+
+            _provider._scopeProvider?.ForEachScope((scope, b) => b.Append(" => ").Append(scope), builder);
+
+            builder.Append(": ").Append(formatter(state, exception));
+            if (exception != null)
+            {
+                builder.AppendLine().Append(exception);
+            }
+
+            builder.AppendLine();
+            _provider.Enqueue(builder.ToString());
+        }
+
+        private static string Abbreviate(LogLevel level) => level switch
+        {
+            LogLevel.Trace => "TRC",
+            LogLevel.Debug => "DBG",
+            LogLevel.Information => "INF",
+            LogLevel.Warning => "WRN",
+            LogLevel.Error => "ERR",
+            LogLevel.Critical => "CRT",
+            _ => "???",
+        };
+    }
+}
@@ -0,0 +1,130 @@
+using System.Runtime.InteropServices;
+using Microsoft.Extensions.Logging;
+using Microsoft.Extensions.Logging.Abstractions;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Optionally mutes every microphone when the workstation locks and puts each one back to its
+/// previous mute state on unlock. Lock notifications come from WTSRegisterSessionNotification.
+/// </summary>
+public sealed class SessionLockService : IDisposable
+{
+    public const string MuteOnLockPreference = "MuteOnLock";
+
+    private const int WM_WTSSESSION_CHANGE = 0x02B1;
+    private const int WTS_SESSION_LOCK = 0x7;
+    private const int WTS_SESSION_UNLOCK = 0x8;
+    private const int NOTIFY_FOR_THIS_SESSION = 0;
+
+    private readonly IAudioDeviceService _audioService;
+    private readonly IPreferencesStore _preferences;
+    private readonly ILogger<SessionLockService> _logger;
+    private WindowMessageMonitor? _monitor;
+    private Dictionary<string, bool>? _muteStatesBeforeLock;
+    private bool _disposed;
+
+    public SessionLockService(IAudioDeviceService audioService, IPreferencesStore preferences, ILogger<SessionLockService>? logger = null)
+    {
+        _audioService = audioService ?? throw new ArgumentNullException(nameof(audioService));
+        _preferences = preferences ?? throw new ArgumentNullException(nameof(preferences));
+        _logger = logger ?? NullLogger<SessionLockService>.Instance;
+    }
+
+    public bool IsMuteOnLockEnabled
+    {
+        get => _preferences.GetBool(MuteOnLockPreference, false);
+        set => _preferences.SetBool(MuteOnLockPreference, value);
+    }
+
+    /// <summary>
+    /// True between a lock that muted the microphones and the matching unlock.
+    /// </summary>
+    public bool IsMutedForLock => _muteStatesBeforeLock != null;
+
+    /// <summary>
+    /// Starts receiving session notifications for the monitored window.
+    /// </summary>
+    public void Attach(WindowMessageMonitor monitor)
+    {
+        if (_disposed || monitor.Handle == IntPtr.Zero || _monitor != null) return;
+
+        if (!WTSRegisterSessionNotification(monitor.Handle, NOTIFY_FOR_THIS_SESSION))
+        {
+            _logger.LogWarning("WTSRegisterSessionNotification failed: {Error}", Marshal.GetLastWin32Error());
+            return;
+        }
+
+        _monitor = monitor;
+        _monitor.MessageReceived += OnMessageReceived;
+    }
+
+    /// <summary>
+    /// Remembers each microphone's mute state and mutes them all, if the preference is on.
+    /// </summary>
+    public void OnSessionLocked()
+    {
+        if (!IsMuteOnLockEnabled || _muteStatesBeforeLock != null) return;
+
+        _muteStatesBeforeLock = _audioService.GetMicrophones().ToDictionary(m => m.Id, m => m.IsMuted);
+        _audioService.SetAllMicrophonesMuted(true);
+    }
+
+    /// <summary>
+    /// Restores the mute states saved at lock. Microphones unplugged in the meantime are skipped.
+    /// </summary>
+    public void OnSessionUnlocked()
+    {
+        var saved = _muteStatesBeforeLock;
+        _muteStatesBeforeLock = null;
+        if (saved == null) return;
+
+        foreach (var microphone in _audioService.GetMicrophones())
+        {
+            if (saved.TryGetValue(microphone.Id, out var wasMuted) && !wasMuted)
+            {
+                _audioService.SetMute(microphone.Id, false);
+            }
+        }
+    }
+
+    private void OnMessageReceived(object? sender, WindowMessageMonitor.WindowMessageEventArgs e)
+    {
+        if (e.Message != WM_WTSSESSION_CHANGE) return;
+
+        try
+        {
+            switch ((int)e.WParam)
+            {
+                case WTS_SESSION_LOCK:
+                    OnSessionLocked();
+                    break;
+                case WTS_SESSION_UNLOCK:
+                    OnSessionUnlocked();
+                    break;
+            }
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Session lock handling failed");
+        }
+    }
+
+    public void Dispose()
+    {
+        if (_disposed) return;
+        _disposed = true;
+
+        if (_monitor == null) return;
+
+        try { _monitor.MessageReceived -= OnMessageReceived; } catch { }
+        try { WTSUnRegisterSessionNotification(_monitor.Handle); } catch { }
+        _monitor = null;
+    }
+
+    [DllImport("wtsapi32.dll", SetLastError = true)]
+    private static extern bool WTSRegisterSessionNotification(IntPtr hWnd, int dwFlags);
+
+    [DllImport("wtsapi32.dll", SetLastError = true)]
+    private static extern bool WTSUnRegisterSessionNotification(IntPtr hWnd);
+}
@@ -0,0 +1,289 @@
+using System.Text.Json;
+using System.Text.Json.Nodes;
+using MicrophoneManager.WinUI.Models;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Exports every preference to a single JSON document and imports one back. Profiles, hotkeys, the
+/// priority list and the per-device aliases, hidden flags and list order get their own sections so
+/// the file is readable; everything else is under <c>preferences</c>. Secrets (the local API token, the MQTT password) are
+/// never exported. Hook commands and the update feed and proxy are exported but never imported, since
+/// a file from someone else could otherwise run commands or point update checks at their server.
+/// </summary>
+public sealed class SettingsTransferService
+{
+    public const string FormatName = "MicrophoneManagerSettings";
+    public const int CurrentVersion = 1;
+
+    // Section name in the file -> preference it holds
+    private static readonly IReadOnlyDictionary<string, string> Sections = new Dictionary<string, string>
+    {
+        ["profiles"] = ProfileService.ProfilesPreference,
+        ["hotkeys"] = HotkeyService.HotkeysPreference,
+        ["priority"] = DevicePriorityService.PriorityListPreference,
+        ["aliases"] = DeviceAliasService.AliasesPreference,
+        ["hidden"] = HiddenDeviceService.HiddenDevicesPreference,
+        ["pinned"] = DeviceOrderService.PinnedDevicesPreference,
+        ["order"] = DeviceOrderService.DeviceOrderPreference,
+    };
+
+    // Sections whose preference is itself a JSON string; they're embedded as JSON rather than escaped
+    private static readonly HashSet<string> JsonSections = new(StringComparer.Ordinal) { "profiles" };
+
+    // Secrets, and bookkeeping that only means something to the install that wrote it. Per-device
+    // settings keyed by endpoint ID still go across; on another machine they just match nothing.
+    private static readonly HashSet<string> Excluded = new(StringComparer.Ordinal)
+    {
+        LocalApiServer.TokenPreference,
+        MqttPublisher.PasswordPreference,
+        PreferencesStoreFactory.UseConfigFilePreference,
+        PreferencesMigrator.SchemaVersionPreference,
+        UpdateService.LastCheckPreference,
+        UpdateService.NotifiedVersionPreference,
+        StartModeService.DockedPositionPreference,
+        TopologyProfileService.LastTopologyPreference,
+        CommunicationsDuckingService.PreviousLevelPreference,
+    };
+
+    // Run commands, choose where the app connects to or open it to control; only ever set by hand on this machine
+    private static readonly HashSet<string> NotImported = new(StringComparer.Ordinal)
+    {
+        HookService.OnMutePreference,
+        HookService.OnUnmutePreference,
+        HookService.OnDeviceRemovedPreference,
+        HookService.OnClippingPreference,
+        UpdateService.FeedUrlPreference,
+        UpdateService.ProxyPreference,
+        MqttPublisher.EnabledPreference,
+        MqttPublisher.HostPreference,
+        MqttPublisher.PortPreference,
+        MqttPublisher.TlsPreference,
+        MqttPublisher.UsernamePreference,
+        MqttPublisher.MuteTopicPreference,
+        MqttPublisher.NameTopicPreference,
+        MqttPublisher.CommandTopicPreference,
+        MqttPublisher.AvailabilityTopicPreference,
+        LocalApiServer.EnabledPreference,
+        LocalApiServer.PortPreference,
+    };
+
+    private readonly IPreferencesStore _preferences;
+
+    public SettingsTransferService(IPreferencesStore preferences)
+    {
+        _preferences = preferences ?? throw new ArgumentNullException(nameof(preferences));
+    }
+
+    public string Export()
+    {
+        var values = _preferences.GetAll();
+        var document = new JsonObject
+        {
+            ["format"] = FormatName,
+            ["version"] = CurrentVersion,
+            ["exportedUtc"] = DateTime.UtcNow.ToString("O"),
+            ["appVersion"] = BuildInfo.Current.Version,
+            ["schemaVersion"] = PreferencesMigrator.GetVersion(_preferences),
+        };
+
+        foreach (var (section, name) in Sections)
+        {
+            if (!values.TryGetValue(name, out var value)) continue;
+
+            document[section] = JsonSections.Contains(section) && value is string embedded
+                ? ParseOrNull(embedded) ?? ToNode(value)
+                : ToNode(value);
+        }
+
+        var preferences = new JsonObject();
+        foreach (var (name, value) in values.OrderBy(v => v.Key, StringComparer.Ordinal))
+        {
+            if (Excluded.Contains(name) || Sections.Values.Contains(name)) continue;
+            preferences[name] = ToNode(value);
+        }
+
+        document["preferences"] = preferences;
+        return document.ToJsonString(new JsonSerializerOptions { WriteIndented = true });
+    }
+
+    /// <summary>
+    /// Checks the whole document before writing anything, so a bad file changes nothing.
+    /// Settings not in the file are left as they are; the imported ones are then migrated from the
+    /// schema they were exported with.
+    /// </summary>
+    public bool TryImport(string json, out int imported, out string? error) =>
+        TryImport(json, out imported, out _, out error);
+
+    /// <inheritdoc cref="TryImport(string, out int, out string?)"/>
+    /// <param name="skipped">Settings in the file that are never imported, such as hook commands.</param>
+    public bool TryImport(string json, out int imported, out IReadOnlyList<string> skipped, out string? error)
+    {
+        imported = 0;
+        skipped = Array.Empty<string>();
+        if (!TryRead(json, out var values, out var schemaVersion, out error)) return false;
+
+        if (schemaVersion > PreferencesMigrator.CurrentVersion)
+        {
+            error = "The settings file is from a newer version of Microphone Manager. Update the app to import it.";
+            return false;
+        }
+
+        skipped = values.Keys.Where(NotImported.Contains).OrderBy(name => name, StringComparer.Ordinal).ToList();
+        foreach (var name in skipped)
+        {
+            values.Remove(name);
+        }
+
+        foreach (var (name, value) in values)
+        {
+            switch (value)
+            {
+                case string text: _preferences.SetString(name, text); break;
+                case bool flag: _preferences.SetBool(name, flag); break;
+                case string[] list: _preferences.SetStringList(name, list); break;
+            }
+        }
+
+        PreferencesMigrator.SetVersion(_preferences, schemaVersion);
+        new PreferencesMigrator().Migrate(_preferences);
+
+        imported = values.Count;
+        return true;
+    }
+
+    /// <summary>
+    /// Parses and validates an exported document into preference name/value pairs and the
+    /// preferences schema they were written with (0 when the file doesn't say).
+    /// </summary>
+    public static bool TryRead(string json, out Dictionary<string, object> values, out int schemaVersion, out string? error)
+    {
+        values = new Dictionary<string, object>(StringComparer.Ordinal);
+        schemaVersion = 0;
+
+        JsonNode? root;
+        try
+        {
+            root = JsonNode.Parse(json);
+        }
+        catch (JsonException ex)
+        {
+            error = $"The file isn't valid JSON: {ex.Message}";
+            return false;
+        }
+
+        if (root is not JsonObject document || document["format"]?.GetValueKind() != JsonValueKind.String
+            || document["format"]!.GetValue<string>() != FormatName)
+        {
+            error = "The file isn't a Microphone Manager settings export.";
+            return false;
+        }
+
+        if (document["version"] is not JsonValue versionNode || !versionNode.TryGetValue<int>(out var version) || version < 1)
+        {
+            error = "The settings file has no valid version.";
+            return false;
+        }
+
+        if (version > CurrentVersion)
+        {
+            error = $"The settings file is from a newer version of Microphone Manager (format {version}). Update the app to import it.";
+            return false;
+        }
+
+        if (document["schemaVersion"] is JsonValue schemaNode && schemaNode.TryGetValue<int>(out var schema) && schema > 0)
+        {
+            schemaVersion = schema;
+        }
+
+        foreach (var (section, name) in Sections)
+        {
+            if (document[section] is not { } node) continue;
+            if (JsonSections.Contains(section) && node.GetValueKind() is JsonValueKind.Array or JsonValueKind.Object)
+            {
+                values[name] = node.ToJsonString();
+                continue;
+            }
+
+            if (!TryConvert(node, out var value))
+            {
+                error = $"'{section}' has a value Microphone Manager doesn't understand.";
+                return false;
+            }
+
+            values[name] = value!;
+        }
+
+        if (document["preferences"] is JsonObject preferences)
+        {
+            foreach (var (name, node) in preferences)
+            {
+                if (Excluded.Contains(name)) continue;
+                if (node == null || !TryConvert(node, out var value))
+                {
+                    error = $"Setting '{name}' has a value Microphone Manager doesn't understand.";
+                    return false;
+                }
+
+                values[name] = value!;
+            }
+        }
+        else if (document["preferences"] != null)
+        {
+            error = "'preferences' must be an object.";
+            return false;
+        }
+
+        error = null;
+        return true;
+    }
+
+    private static JsonNode? ParseOrNull(string json)
+    {
+        try
+        {
+            return JsonNode.Parse(json);
+        }
+        catch (JsonException)
+        {
+            return null;
+        }
+    }
+
+    private static JsonNode? ToNode(object value)
+    {
+        return value switch
+        {
+            bool flag => JsonValue.Create(flag),
+            string[] list => new JsonArray(list.Select(item => (JsonNode?)JsonValue.Create(item)).ToArray()),
+            _ => JsonValue.Create(value.ToString()),
+        };
+    }
+
+    private static bool TryConvert(JsonNode node, out object? value)
+    {
+        value = null;
+        switch (node.GetValueKind())
+        {
+            case JsonValueKind.String:
+                value = node.GetValue<string>();
+                return true;
+            case JsonValueKind.True:
+            case JsonValueKind.False:
+                value = node.GetValue<bool>();
+                return true;
+            case JsonValueKind.Array:
+                var items = new List<string>();
+                foreach (var item in node.AsArray())
+                {
+                    if (item?.GetValueKind() != JsonValueKind.String) return false;
+                    items.Add(item.GetValue<string>());
+                }
+
+                value = items.ToArray();
+                return true;
+            default:
+                return false;
+        }
+    }
+}
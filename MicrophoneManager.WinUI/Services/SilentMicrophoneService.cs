@@ -0,0 +1,174 @@
+using System.Globalization;
+using Microsoft.Extensions.Logging;
+using Microsoft.Extensions.Logging.Abstractions;
+using NAudio.CoreAudioApi;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Warns when the default microphone delivers nothing while an application is recording from it.
+/// A hardware mute switch, an unplugged XLR cable or a headset with a flat battery all look like a
+/// working device to Windows; the only sign is a signal that stays at the noise floor of an empty
+/// input. Nothing is checked while the microphone is muted or no other application is capturing,
+/// and each silent stretch is reported once.
+/// </summary>
+public sealed class SilentMicrophoneService : IDisposable
+{
+    public const string EnabledPreference = "SilentMicAlert";
+    public const string SilencePreference = "SilentMicSeconds";
+
+    public const int DefaultSilenceSeconds = 30;
+
+    // Even a quiet room puts a connected microphone well above this; a dead input sits below it
+    public const double SilenceFloorDb = -75;
+
+    private static readonly TimeSpan SampleInterval = TimeSpan.FromSeconds(1);
+
+    private readonly IAudioDeviceService _audioService;
+    private readonly IPreferencesStore _preferences;
+    private readonly Func<DateTime> _clock;
+    private readonly SynchronizationContext? _syncContext;
+    private readonly ILogger<SilentMicrophoneService> _logger;
+    private Timer? _timer;
+    private string? _deviceId;
+    private DateTime? _silentSince;
+    private bool _reported;
+    private bool _disposed;
+
+    /// <summary>
+    /// Raised on the sampling thread (the UI thread in the app) once a silent stretch reaches the configured length.
+    /// </summary>
+    public event EventHandler<SilentMicrophoneEventArgs>? MicrophoneSilent;
+
+    public SilentMicrophoneService(IAudioDeviceService audioService, IPreferencesStore preferences, ILogger<SilentMicrophoneService>? logger = null)
+        : this(audioService, preferences, () => DateTime.UtcNow, logger)
+    {
+    }
+
+    public SilentMicrophoneService(IAudioDeviceService audioService, IPreferencesStore preferences, Func<DateTime> clock, ILogger<SilentMicrophoneService>? logger = null)
+    {
+        _audioService = audioService ?? throw new ArgumentNullException(nameof(audioService));
+        _preferences = preferences ?? throw new ArgumentNullException(nameof(preferences));
+        _clock = clock ?? throw new ArgumentNullException(nameof(clock));
+        _logger = logger ?? NullLogger<SilentMicrophoneService>.Instance;
+        _syncContext = SynchronizationContext.Current;
+
+        // No background sampling without a UI context (unit tests call Sample)
+        if (_syncContext != null)
+        {
+            _timer = new Timer(_ => _syncContext.Post(_ => SampleSafely(), null), null, SampleInterval, SampleInterval);
+        }
+    }
+
+    public bool IsEnabled
+    {
+        get => _preferences.GetBool(EnabledPreference, true);
+        set
+        {
+            _preferences.SetBool(EnabledPreference, value);
+            Reset(null);
+        }
+    }
+
+    /// <summary>
+    /// How long the signal has to stay at the floor before the warning.
+    /// </summary>
+    public TimeSpan SilencePeriod => TimeSpan.FromSeconds(
+        int.TryParse(_preferences.GetString(SilencePreference), NumberStyles.Integer, CultureInfo.InvariantCulture, out var seconds) && seconds > 0
+            ? seconds
+            : DefaultSilenceSeconds);
+
+    /// <summary>
+    /// Checks the default microphone once. Called by the timer.
+    /// </summary>
+    public void Sample()
+    {
+        if (_disposed) return;
+
+        var defaultId = IsEnabled ? _audioService.GetDefaultDeviceId(Role.Console) : null;
+        if (defaultId == null || _audioService.IsMuted(defaultId))
+        {
+            Reset(null);
+            return;
+        }
+
+        // Our own meter captures don't count as someone using the microphone
+        var recorder = _audioService.GetCaptureSessions(defaultId)
+            .FirstOrDefault(s => s.IsActive && s.ProcessId != Environment.ProcessId);
+        if (recorder == null)
+        {
+            Reset(null);
+            return;
+        }
+
+        if (!string.Equals(defaultId, _deviceId, StringComparison.OrdinalIgnoreCase))
+        {
+            Reset(defaultId);
+        }
+
+        var now = _clock();
+        if (_audioService.GetInputPeakDb(defaultId) > SilenceFloorDb)
+        {
+            // Signal is back; a later silent stretch is reported again
+            _silentSince = null;
+            _reported = false;
+            return;
+        }
+
+        _silentSince ??= now;
+        if (_reported || now - _silentSince.Value < SilencePeriod) return;
+
+        _reported = true;
+        var name = _audioService.GetMicrophones().FirstOrDefault(m => m.Id == defaultId)?.Name ?? "Microphone";
+        MicrophoneSilent?.Invoke(this, new SilentMicrophoneEventArgs(defaultId, name, recorder.Label, now - _silentSince.Value));
+    }
+
+    private void SampleSafely()
+    {
+        try
+        {
+            Sample();
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Silent microphone check failed");
+        }
+    }
+
+    private void Reset(string? deviceId)
+    {
+        _deviceId = deviceId;
+        _silentSince = null;
+        _reported = false;
+    }
+
+    public void Dispose()
+    {
+        if (_disposed) return;
+        _disposed = true;
+
+        try { _timer?.Dispose(); } catch { }
+        _timer = null;
+    }
+
+    public sealed class SilentMicrophoneEventArgs : EventArgs
+    {
+        public SilentMicrophoneEventArgs(string deviceId, string deviceName, string recordingApplication, TimeSpan silentFor)
+        {
+            DeviceId = deviceId;
+            DeviceName = deviceName;
+            RecordingApplication = recordingApplication;
+            SilentFor = silentFor;
+        }
+
+        public string DeviceId { get; }
+        public string DeviceName { get; }
+
+        /// <summary>
+        /// The application capturing from the device, e.g. "Teams.exe".
+        /// </summary>
+        public string RecordingApplication { get; }
+
+        public TimeSpan SilentFor { get; }
+    }
+}
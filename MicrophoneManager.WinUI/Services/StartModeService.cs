@@ -0,0 +1,68 @@
+using System.Globalization;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// What's shown when the app starts.
+/// </summary>
+public enum StartMode
+{
+    /// <summary>
+    /// Just the tray icon.
+    /// </summary>
+    Tray,
+
+    /// <summary>
+    /// The tray flyout, open as if the icon had been clicked.
+    /// </summary>
+    Flyout,
+
+    /// <summary>
+    /// The docked window, where it was last closed.
+    /// </summary>
+    Docked,
+}
+
+/// <summary>
+/// The user's start mode and where the docked window was last left, so starting docked puts it back there.
+/// </summary>
+public sealed class StartModeService
+{
+    public const string StartModePreference = "StartMode";
+    public const string DockedPositionPreference = "DockedWindowPosition";
+
+    private readonly IPreferencesStore _preferences;
+
+    public StartModeService(IPreferencesStore preferences)
+    {
+        _preferences = preferences ?? throw new ArgumentNullException(nameof(preferences));
+    }
+
+    public StartMode Mode
+    {
+        get => Enum.TryParse<StartMode>(_preferences.GetString(StartModePreference), ignoreCase: true, out var mode) && Enum.IsDefined(mode)
+            ? mode
+            : StartMode.Tray;
+        set => _preferences.SetString(StartModePreference, value == StartMode.Tray ? null : value.ToString());
+    }
+
+    /// <summary>
+    /// Top-left corner of the docked window in screen pixels when it was last closed, or null if it never was.
+    /// </summary>
+    public (int X, int Y)? DockedPosition
+    {
+        get
+        {
+            var parts = _preferences.GetString(DockedPositionPreference)?.Split(',');
+            if (parts is not { Length: 2 }) return null;
+
+            return int.TryParse(parts[0], NumberStyles.Integer, CultureInfo.InvariantCulture, out var x)
+                && int.TryParse(parts[1], NumberStyles.Integer, CultureInfo.InvariantCulture, out var y)
+                ? (x, y)
+                : null;
+        }
+        set => _preferences.SetString(
+            DockedPositionPreference,
+            value is var (x, y) ? string.Create(CultureInfo.InvariantCulture, $"{x},{y}") : null);
+    }
+}
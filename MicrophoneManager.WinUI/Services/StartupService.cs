@@ -1,19 +1,220 @@
+using System.Diagnostics;
+using System.Security;
 using Microsoft.Win32;
 
 namespace MicrophoneManager.WinUI.Services;
 
 /// <summary>
-/// Manages application auto-start on Windows startup via Registry.
+/// How the app is launched at sign-in.
+/// </summary>
+public enum StartupMethod
+{
+    /// <summary>
+    /// HKCU\...\Run entry. Starts as early as possible, which can be before the audio service is ready.
+    /// </summary>
+    RunKey,
+
+    /// <summary>
+    /// Per-user scheduled task with a logon trigger and an optional delay. Works where policy blocks Run entries.
+    /// </summary>
+    ScheduledTask,
+}
+
+/// <summary>
+/// The chosen startup method and, for a scheduled task, how long after sign-in it starts.
+/// </summary>
+public readonly record struct StartupOptions(StartupMethod Method, TimeSpan Delay)
+{
+    public static StartupOptions Default => new(StartupMethod.RunKey, TimeSpan.Zero);
+}
+
+/// <summary>
+/// Manages application auto-start on Windows startup, via the Registry Run key or a scheduled task.
 /// </summary>
 public static class StartupService
 {
+    public const string StartupMethodPreference = "StartupMethod";
+    public const string StartupDelayPreference = "StartupDelaySeconds";
+
     private const string AppName = "MicrophoneManager";
     private const string RegistryKeyPath = @"Software\Microsoft\Windows\CurrentVersion\Run";
+    private const string TaskName = @"\MicrophoneManager";
+
+    private static readonly object StateLock = new();
+
+    // What the last check or change found; null until checked, or after a change that failed part-way
+    private static bool? _enabled;
+
+    /// <summary>
+    /// Gets whether the application starts with Windows by the user's chosen method. Only that method
+    /// is checked, and the answer is kept until startup is changed through this class. Checking a
+    /// scheduled task runs schtasks.exe, so the UI goes through <see cref="IsStartupEnabledAsync"/>.
+    /// </summary>
+    public static bool IsStartupEnabled(IPreferencesStore preferences)
+    {
+        lock (StateLock)
+        {
+            _enabled ??= GetOptions(preferences).Method == StartupMethod.ScheduledTask ? IsTaskRegistered() : IsRunKeySet();
+            return _enabled.Value;
+        }
+    }
+
+    /// <summary>
+    /// <see cref="IsStartupEnabled"/> on a worker thread.
+    /// </summary>
+    public static Task<bool> IsStartupEnabledAsync(IPreferencesStore preferences)
+    {
+        return Task.Run(() => IsStartupEnabled(preferences));
+    }
+
+    /// <summary>
+    /// The last known state, without checking; null before the first check. For menu text that is
+    /// read on the UI thread.
+    /// </summary>
+    public static bool? KnownStartupEnabled
+    {
+        get
+        {
+            lock (StateLock) return _enabled;
+        }
+    }
+
+    /// <summary>
+    /// The startup method the user picked; <see cref="StartupOptions.Default"/> if they never did.
+    /// </summary>
+    public static StartupOptions GetOptions(IPreferencesStore preferences)
+    {
+        var method = Enum.TryParse<StartupMethod>(preferences.GetString(StartupMethodPreference), out var parsed)
+            ? parsed
+            : StartupMethod.RunKey;
+        var delay = int.TryParse(preferences.GetString(StartupDelayPreference), out var seconds) && seconds > 0
+            ? TimeSpan.FromSeconds(seconds)
+            : TimeSpan.Zero;
+        return new StartupOptions(method, method == StartupMethod.ScheduledTask ? delay : TimeSpan.Zero);
+    }
+
+    /// <summary>
+    /// Saves the startup method and, if startup is on, switches the existing registration over to it.
+    /// </summary>
+    public static void SetOptions(IPreferencesStore preferences, StartupOptions options)
+    {
+        // Checked under the old method, which is the one registered
+        var enabled = IsStartupEnabled(preferences);
+
+        preferences.SetString(StartupMethodPreference, options.Method.ToString());
+        preferences.SetString(StartupDelayPreference, options.Delay > TimeSpan.Zero ? ((int)options.Delay.TotalSeconds).ToString() : null);
+
+        if (enabled)
+        {
+            SetStartupEnabled(true, options);
+        }
+    }
+
+    /// <summary>
+    /// Enables or disables auto-start on Windows startup using the Run key.
+    /// </summary>
+    public static void SetStartupEnabled(bool enabled)
+    {
+        SetStartupEnabled(enabled, StartupOptions.Default);
+    }
+
+    /// <summary>
+    /// Enables auto-start with <paramref name="options"/>, removing the other method so the app
+    /// isn't launched twice, or disables both.
+    /// </summary>
+    public static void SetStartupEnabled(bool enabled, StartupOptions options)
+    {
+        // A portable copy mustn't leave a Run entry or task behind when the stick is pulled
+        if (PortableMode.IsEnabled) return;
+
+        if (!enabled)
+        {
+            var removed = SetRunKey(false) & SetTask(false, options.Delay);
+            Remember(removed ? false : null);
+            return;
+        }
+
+        // Register the new method first, so a failure leaves the old one in place
+        bool registered;
+        if (options.Method == StartupMethod.ScheduledTask)
+        {
+            registered = SetTask(true, options.Delay);
+            if (registered) SetRunKey(false);
+        }
+        else
+        {
+            registered = SetRunKey(true);
+            if (registered) SetTask(false, options.Delay);
+        }
+
+        Remember(registered ? true : null);
+    }
+
+    /// <summary>
+    /// Toggles the auto-start setting using the user's chosen method.
+    /// </summary>
+    /// <returns>Whether startup is on afterwards; registering can fail (policy, Task Scheduler off).</returns>
+    public static bool ToggleStartup(IPreferencesStore preferences)
+    {
+        SetStartupEnabled(!IsStartupEnabled(preferences), GetOptions(preferences));
+        return IsStartupEnabled(preferences);
+    }
 
     /// <summary>
-    /// Gets whether the application is set to start with Windows.
+    /// Task Scheduler XML for a task that starts <paramref name="exePath"/> when <paramref name="userId"/>
+    /// signs in, without elevation and regardless of battery state.
     /// </summary>
-    public static bool IsStartupEnabled()
+    public static string BuildTaskXml(string exePath, string userId, TimeSpan delay)
+    {
+        var delayElement = delay > TimeSpan.Zero
+            ? $"\n      <Delay>PT{(int)delay.TotalSeconds}S</Delay>"
+            : string.Empty;
+
+        return $"""
+            <?xml version="1.0" encoding="UTF-16"?>
+            <Task version="1.2" xmlns="http://schemas.microsoft.com/windows/2004/02/mit/task">
+              <RegistrationInfo>
+                <Description>Starts Microphone Manager when you sign in.</Description>
+              </RegistrationInfo>
+              <Triggers>
+                <LogonTrigger>
+                  <Enabled>true</Enabled>
+                  <UserId>{SecurityElement.Escape(userId)}</UserId>{delayElement}
+                </LogonTrigger>
+              </Triggers>
+              <Principals>
+                <Principal id="Author">
+                  <UserId>{SecurityElement.Escape(userId)}</UserId>
+                  <LogonType>InteractiveToken</LogonType>
+                  <RunLevel>LeastPrivilege</RunLevel>
+                </Principal>
+              </Principals>
+              <Settings>
+                <MultipleInstancesPolicy>IgnoreNew</MultipleInstancesPolicy>
+                <DisallowStartIfOnBatteries>false</DisallowStartIfOnBatteries>
+                <StopIfGoingOnBatteries>false</StopIfGoingOnBatteries>
+                <ExecutionTimeLimit>PT0S</ExecutionTimeLimit>
+                <Priority>7</Priority>
+              </Settings>
+              <Actions Context="Author">
+                <Exec>
+                  <Command>"{SecurityElement.Escape(exePath)}"</Command>
+                </Exec>
+              </Actions>
+            </Task>
+            """;
+    }
+
+    // Null makes the next IsStartupEnabled look again
+    private static void Remember(bool? enabled)
+    {
+        lock (StateLock)
+        {
+            _enabled = enabled;
+        }
+    }
+
+    private static bool IsRunKeySet()
     {
         try
         {
@@ -27,43 +228,97 @@ public static class StartupService
         }
     }
 
-    /// <summary>
-    /// Enables or disables auto-start on Windows startup.
-    /// </summary>
-    public static void SetStartupEnabled(bool enabled)
+    private static bool SetRunKey(bool enabled)
     {
         try
         {
             using var key = Registry.CurrentUser.OpenSubKey(RegistryKeyPath, true);
-            if (key == null) return;
+            if (key == null) return false;
 
             if (enabled)
             {
                 // Get the path to the current executable
                 var exePath = Environment.ProcessPath;
-                if (!string.IsNullOrEmpty(exePath))
-                {
-                    key.SetValue(AppName, $"\"{exePath}\"");
-                }
+                if (string.IsNullOrEmpty(exePath)) return false;
+
+                key.SetValue(AppName, $"\"{exePath}\"");
             }
             else
             {
                 key.DeleteValue(AppName, false);
             }
+
+            return true;
         }
         catch (Exception)
         {
             // Silently fail - registry access may be restricted
+            return false;
         }
     }
 
-    /// <summary>
-    /// Toggles the auto-start setting.
-    /// </summary>
-    public static bool ToggleStartup()
+    private static bool IsTaskRegistered()
     {
-        var currentState = IsStartupEnabled();
-        SetStartupEnabled(!currentState);
-        return !currentState;
+        return RunSchtasks($"/Query /TN \"{TaskName}\"");
+    }
+
+    private static bool SetTask(bool enabled, TimeSpan delay)
+    {
+        if (!enabled)
+        {
+            return !IsTaskRegistered() || RunSchtasks($"/Delete /TN \"{TaskName}\" /F");
+        }
+
+        var exePath = Environment.ProcessPath;
+        if (string.IsNullOrEmpty(exePath)) return false;
+
+        var xmlPath = Path.Combine(Path.GetTempPath(), $"MicrophoneManager-startup-{Guid.NewGuid():N}.xml");
+        try
+        {
+            // schtasks only reads task XML from a file, and expects UTF-16 as declared
+            File.WriteAllText(xmlPath, BuildTaskXml(exePath, $@"{Environment.UserDomainName}\{Environment.UserName}", delay), System.Text.Encoding.Unicode);
+            return RunSchtasks($"/Create /TN \"{TaskName}\" /XML \"{xmlPath}\" /F");
+        }
+        catch (Exception)
+        {
+            return false;
+        }
+        finally
+        {
+            try { File.Delete(xmlPath); } catch { }
+        }
+    }
+
+    private static bool RunSchtasks(string arguments)
+    {
+        try
+        {
+            using var process = Process.Start(new ProcessStartInfo
+            {
+                FileName = "schtasks.exe",
+                Arguments = arguments,
+                UseShellExecute = false,
+                CreateNoWindow = true,
+                RedirectStandardOutput = true,
+                RedirectStandardError = true,
+            });
+            if (process == null) return false;
+
+            // Drain both pipes at once so a chatty error can't block the process on a full one
+            _ = process.StandardOutput.ReadToEndAsync();
+            _ = process.StandardError.ReadToEndAsync();
+            if (!process.WaitForExit(10_000))
+            {
+                try { process.Kill(); } catch { }
+                return false;
+            }
+
+            return process.ExitCode == 0;
+        }
+        catch (Exception)
+        {
+            // Task Scheduler may be disabled by policy
+            return false;
+        }
     }
 }
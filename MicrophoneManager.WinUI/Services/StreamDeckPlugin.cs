@@ -0,0 +1,333 @@
+using System.Collections.Concurrent;
+using System.Net.WebSockets;
+using System.Text;
+using System.Text.Json;
+using System.Text.Json.Nodes;
+using Microsoft.Extensions.Logging;
+using Microsoft.Extensions.Logging.Abstractions;
+using MicrophoneManager.WinUI.Models;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Speaks the Elgato Stream Deck plugin protocol. The Stream Deck software starts this exe with
+/// <c>-port</c>, <c>-pluginUUID</c>, <c>-registerEvent</c> and <c>-info</c> (see
+/// <c>streamdeck/com.micmanager.sdPlugin/manifest.json</c>); we connect back over WebSocket,
+/// register, and then:
+/// <list type="bullet">
+/// <item><see cref="ToggleMuteAction"/> toggles mute on the button's device (settings <c>device</c>,
+/// default microphone when empty) and shows state 1 while it's muted.</item>
+/// <item><see cref="SetDefaultAction"/> makes the button's device the default and shows state 1
+/// while it is.</item>
+/// </list>
+/// Every visible button is refreshed whenever the audio state changes. The actions run in the tray
+/// app over its control pipe, so the plugin process never opens the microphones itself; while the
+/// tray isn't running, key presses show an alert.
+/// </summary>
+public sealed class StreamDeckPlugin : IDisposable
+{
+    public const string ToggleMuteAction = "com.micmanager.togglemute";
+    public const string SetDefaultAction = "com.micmanager.setdefault";
+
+    private static readonly TimeSpan ReconnectDelay = TimeSpan.FromSeconds(2);
+
+    // Stream Deck restarts plugins it loses; give up rather than linger if it has gone away
+    private const int MaxReconnectAttempts = 15;
+
+    private readonly IControlChannel _channel;
+    private readonly Func<string, Task> _send;
+    private readonly ILogger<StreamDeckPlugin> _logger;
+    private readonly ConcurrentDictionary<string, ButtonContext> _buttons = new();
+    private bool _disposed;
+
+    /// <param name="send">Writes one message to the Stream Deck connection.</param>
+    public StreamDeckPlugin(IControlChannel channel, Func<string, Task> send, ILogger<StreamDeckPlugin>? logger = null)
+    {
+        _channel = channel ?? throw new ArgumentNullException(nameof(channel));
+        _send = send ?? throw new ArgumentNullException(nameof(send));
+        _logger = logger ?? NullLogger<StreamDeckPlugin>.Instance;
+        _channel.StateChanged += OnStateChanged;
+    }
+
+    /// <summary>
+    /// Buttons currently on screen, by Stream Deck context.
+    /// </summary>
+    public IReadOnlyDictionary<string, ButtonContext> Buttons => _buttons;
+
+    /// <summary>
+    /// Handles one event from Stream Deck.
+    /// </summary>
+    public async Task HandleMessageAsync(string json)
+    {
+        JsonNode? message;
+        try
+        {
+            message = JsonNode.Parse(json);
+        }
+        catch (JsonException)
+        {
+            return;
+        }
+
+        var eventName = message?["event"]?.GetValue<string>();
+        var context = message?["context"]?.GetValue<string>();
+        var action = message?["action"]?.GetValue<string>();
+        if (eventName == null || context == null) return;
+
+        var device = message?["payload"]?["settings"]?["device"]?.GetValue<string>();
+        if (string.IsNullOrWhiteSpace(device)) device = null;
+
+        switch (eventName)
+        {
+            case "willAppear":
+            case "didReceiveSettings":
+                var button = new ButtonContext(context, action ?? "", device);
+                _buttons[context] = button;
+                await UpdateButtonAsync(button, await GetStateOrEmptyAsync());
+                break;
+
+            case "willDisappear":
+                _buttons.TryRemove(context, out _);
+                break;
+
+            case "keyDown":
+                await OnKeyDownAsync(context, action ?? "", device);
+                break;
+        }
+    }
+
+    /// <summary>
+    /// Pushes the current state to every visible button.
+    /// </summary>
+    public async Task RefreshAllAsync(ControlState state)
+    {
+        foreach (var button in _buttons.Values)
+        {
+            await UpdateButtonAsync(button, state);
+        }
+    }
+
+    /// <summary>
+    /// The button's state for Stream Deck: 1 is the "active" image (muted, or is the default).
+    /// </summary>
+    public static int GetButtonState(ButtonContext button, ControlState state)
+    {
+        var device = FindDevice(button.Device, state);
+        return button.Action switch
+        {
+            ToggleMuteAction => device?.IsMuted == true ? 1 : 0,
+            SetDefaultAction => device?.IsDefault == true ? 1 : 0,
+            _ => 0,
+        };
+    }
+
+    private async Task OnKeyDownAsync(string context, string action, string? device)
+    {
+        var request = action switch
+        {
+            ToggleMuteAction => new ControlRequest { Command = "toggleMute", Device = device },
+            SetDefaultAction when device != null => new ControlRequest { Command = "setDefault", Device = device },
+            _ => null,
+        };
+
+        if (request == null)
+        {
+            await SendEventAsync("showAlert", context);
+            return;
+        }
+
+        var response = await _channel.DispatchAsync(request);
+        if (!response.Ok)
+        {
+            await SendEventAsync("showAlert", context);
+            return;
+        }
+
+        // Don't wait for the debounced change notification; the key press should feel immediate
+        if (response.Data is ControlState state)
+        {
+            await RefreshAllAsync(state);
+        }
+    }
+
+    private async Task<ControlState> GetStateOrEmptyAsync()
+    {
+        try
+        {
+            return await _channel.GetStateAsync();
+        }
+        catch (Exception ex) when (ex is IOException or InvalidOperationException)
+        {
+            // Tray not running yet; the button catches up on the first state push
+            return new ControlState(null, Array.Empty<ControlDevice>());
+        }
+    }
+
+    private Task UpdateButtonAsync(ButtonContext button, ControlState state)
+    {
+        var message = new JsonObject
+        {
+            ["event"] = "setState",
+            ["context"] = button.Context,
+            ["payload"] = new JsonObject { ["state"] = GetButtonState(button, state) },
+        };
+        return _send(message.ToJsonString());
+    }
+
+    private Task SendEventAsync(string eventName, string context)
+    {
+        return _send(new JsonObject { ["event"] = eventName, ["context"] = context }.ToJsonString());
+    }
+
+    private static ControlDevice? FindDevice(string? device, ControlState state)
+    {
+        if (device == null) return state.DefaultDevice;
+
+        var byId = state.Devices.FirstOrDefault(d => string.Equals(d.Id, device, StringComparison.OrdinalIgnoreCase));
+        return byId
+            ?? state.Devices.FirstOrDefault(d => string.Equals(d.DisplayName, device, StringComparison.OrdinalIgnoreCase))
+            ?? state.Devices.FirstOrDefault(d => string.Equals(d.Name, device, StringComparison.OrdinalIgnoreCase))
+            ?? state.Devices.FirstOrDefault(d => d.Name.Contains(device, StringComparison.OrdinalIgnoreCase));
+    }
+
+    private async void OnStateChanged(object? sender, ControlState state)
+    {
+        try
+        {
+            await RefreshAllAsync(state);
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Stream Deck refresh failed");
+        }
+    }
+
+    /// <summary>
+    /// Connects to Stream Deck and serves until it closes the connection or can't be reached again.
+    /// </summary>
+    public static async Task<int> RunAsync(StreamDeckLaunchArgs launch, IControlChannel channel, ILogger<StreamDeckPlugin>? logger = null, CancellationToken cancellationToken = default)
+    {
+        ClientWebSocket? socket = null;
+        var sendLock = new SemaphoreSlim(1, 1);
+
+        async Task Send(string json)
+        {
+            var current = socket;
+            if (current?.State != WebSocketState.Open) return;
+
+            await sendLock.WaitAsync(cancellationToken);
+            try
+            {
+                await current.SendAsync(Encoding.UTF8.GetBytes(json), WebSocketMessageType.Text, true, cancellationToken);
+            }
+            catch (WebSocketException)
+            {
+                // The receive loop sees the drop and reconnects
+            }
+            finally
+            {
+                sendLock.Release();
+            }
+        }
+
+        using var plugin = new StreamDeckPlugin(channel, Send, logger);
+        var failures = 0;
+
+        while (!cancellationToken.IsCancellationRequested && failures < MaxReconnectAttempts)
+        {
+            socket?.Dispose();
+            socket = new ClientWebSocket();
+
+            try
+            {
+                await socket.ConnectAsync(new Uri($"ws://127.0.0.1:{launch.Port}"), cancellationToken);
+                await Send(new JsonObject { ["event"] = launch.RegisterEvent, ["uuid"] = launch.PluginUuid }.ToJsonString());
+                failures = 0;
+
+                // Buttons re-announce themselves with willAppear after registering
+                plugin._buttons.Clear();
+
+                var buffer = new byte[16 * 1024];
+                while (socket.State == WebSocketState.Open)
+                {
+                    var message = await ReceiveTextAsync(socket, buffer, cancellationToken);
+                    if (message == null) break;
+
+                    await plugin.HandleMessageAsync(message);
+                }
+
+                if (socket.CloseStatus == WebSocketCloseStatus.NormalClosure) break;
+            }
+            catch (OperationCanceledException)
+            {
+                break;
+            }
+            catch (Exception ex) when (ex is WebSocketException or IOException)
+            {
+                plugin._logger.LogWarning(ex, "Stream Deck connection failed");
+            }
+
+            failures++;
+            try { await Task.Delay(ReconnectDelay, cancellationToken); } catch (OperationCanceledException) { break; }
+        }
+
+        socket?.Dispose();
+        return failures >= MaxReconnectAttempts ? 1 : 0;
+    }
+
+    private static async Task<string?> ReceiveTextAsync(WebSocket socket, byte[] buffer, CancellationToken cancellationToken)
+    {
+        using var message = new MemoryStream();
+        while (true)
+        {
+            var result = await socket.ReceiveAsync(buffer, cancellationToken);
+            if (result.MessageType == WebSocketMessageType.Close) return null;
+
+            message.Write(buffer, 0, result.Count);
+            if (result.EndOfMessage) return Encoding.UTF8.GetString(message.GetBuffer(), 0, (int)message.Length);
+        }
+    }
+
+    public void Dispose()
+    {
+        if (_disposed) return;
+        _disposed = true;
+
+        try { _channel.StateChanged -= OnStateChanged; } catch { }
+    }
+
+    /// <summary>
+    /// A button placed on the Stream Deck.
+    /// </summary>
+    /// <param name="Device">Device name or ID from the button's settings; null for the default microphone.</param>
+    public sealed record ButtonContext(string Context, string Action, string? Device);
+}
+
+/// <summary>
+/// Arguments the Stream Deck software passes when it starts a plugin.
+/// </summary>
+public sealed record StreamDeckLaunchArgs(int Port, string PluginUuid, string RegisterEvent)
+{
+    public static bool TryParse(IReadOnlyList<string> args, out StreamDeckLaunchArgs? launch)
+    {
+        launch = null;
+        string? port = null, uuid = null, registerEvent = null;
+
+        for (var i = 0; i + 1 < args.Count; i += 2)
+        {
+            switch (args[i])
+            {
+                case "-port": port = args[i + 1]; break;
+                case "-pluginUUID": uuid = args[i + 1]; break;
+                case "-registerEvent": registerEvent = args[i + 1]; break;
+                case "-info": break;
+                default: return false;
+            }
+        }
+
+        if (!int.TryParse(port, out var portNumber) || uuid == null || registerEvent == null) return false;
+
+        launch = new StreamDeckLaunchArgs(portNumber, uuid, registerEvent);
+        return true;
+    }
+}
@@ -0,0 +1,421 @@
+using System.Globalization;
+using System.Text;
+using Microsoft.Extensions.Logging;
+using Microsoft.Extensions.Logging.Abstractions;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Stores preferences in a TOML file (by default <c>%APPDATA%\MicManager\config.toml</c>) so they
+/// can be diffed, backed up and synced. Only the subset we write is understood: top-level
+/// <c>key = value</c> pairs whose values are strings, booleans, numbers (kept as their text, the way
+/// every other store holds them) or arrays of strings, which may run over several lines. Tables and
+/// anything else are logged and ignored. The file is re-read when it changes on disk, so hand edits
+/// apply without a restart. <see cref="PreferenceSecrets"/> are only ever written DPAPI-protected,
+/// so a synced or shared file doesn't carry the MQTT password or the local API token.
+/// </summary>
+public sealed class TomlPreferencesStore : IPreferencesStore
+{
+    private readonly object _lock = new();
+    private readonly ILogger<TomlPreferencesStore> _logger;
+    private Dictionary<string, object> _values = new(StringComparer.Ordinal);
+    private DateTime _loadedWriteTime;
+
+    public TomlPreferencesStore(string path, ILogger<TomlPreferencesStore>? logger = null)
+    {
+        Path = path ?? throw new ArgumentNullException(nameof(path));
+        _logger = logger ?? NullLogger<TomlPreferencesStore>.Instance;
+    }
+
+    public static string DefaultPath =>
+        System.IO.Path.Combine(Environment.GetFolderPath(Environment.SpecialFolder.ApplicationData), "MicManager", "config.toml");
+
+    public string Path { get; }
+
+    public string? GetString(string name)
+    {
+        var value = Read(name) as string;
+        return PreferenceSecrets.IsSecret(name) ? PreferenceSecrets.Unprotect(value) : value;
+    }
+
+    public void SetString(string name, string? value)
+    {
+        Write(values =>
+        {
+            if (value == null)
+            {
+                values.Remove(name);
+            }
+            else
+            {
+                values[name] = PreferenceSecrets.IsSecret(name) ? PreferenceSecrets.Protect(value) : value;
+            }
+        });
+    }
+
+    public IReadOnlyList<string> GetStringList(string name)
+    {
+        return Read(name) as string[] ?? Array.Empty<string>();
+    }
+
+    public void SetStringList(string name, IReadOnlyList<string> values)
+    {
+        Write(v => v[name] = values.ToArray());
+    }
+
+    public bool GetBool(string name, bool defaultValue)
+    {
+        return Read(name) is bool value ? value : defaultValue;
+    }
+
+    public void SetBool(string name, bool value)
+    {
+        Write(v => v[name] = value);
+    }
+
+    public IReadOnlyDictionary<string, object> GetAll()
+    {
+        lock (_lock)
+        {
+            ReloadIfChanged();
+            return new Dictionary<string, object>(_values, StringComparer.Ordinal);
+        }
+    }
+
+    /// <summary>
+    /// Copies values into the file in one write, leaving existing keys alone.
+    /// </summary>
+    public void Import(IReadOnlyDictionary<string, object> values)
+    {
+        Write(current =>
+        {
+            foreach (var (name, value) in values)
+            {
+                if (value is string secret && PreferenceSecrets.IsSecret(name) && !PreferenceSecrets.IsProtected(secret))
+                {
+                    current.TryAdd(name, PreferenceSecrets.Protect(secret));
+                }
+                else if (value is string or bool or string[])
+                {
+                    current.TryAdd(name, value);
+                }
+            }
+        });
+    }
+
+    private object? Read(string name)
+    {
+        lock (_lock)
+        {
+            ReloadIfChanged();
+            return _values.TryGetValue(name, out var value) ? value : null;
+        }
+    }
+
+    private void Write(Action<Dictionary<string, object>> update)
+    {
+        lock (_lock)
+        {
+            ReloadIfChanged();
+            update(_values);
+            Save();
+        }
+    }
+
+    // Callers hold _lock
+    private void Save()
+    {
+        try
+        {
+            Directory.CreateDirectory(System.IO.Path.GetDirectoryName(Path)!);
+
+            // Write then swap so a crash or a sync client never sees half a file
+            var temp = Path + ".tmp";
+            File.WriteAllText(temp, Serialize(_values), new UTF8Encoding(false));
+            File.Move(temp, Path, overwrite: true);
+            _loadedWriteTime = File.GetLastWriteTimeUtc(Path);
+        }
+        catch (Exception ex)
+        {
+            // Same as the registry store: a read-only profile shouldn't take the app down
+            _logger.LogWarning(ex, "Writing {Path} failed", Path);
+        }
+    }
+
+    private void ReloadIfChanged()
+    {
+        DateTime writeTime;
+        try
+        {
+            writeTime = File.Exists(Path) ? File.GetLastWriteTimeUtc(Path) : default;
+        }
+        catch
+        {
+            return;
+        }
+
+        if (writeTime == _loadedWriteTime) return;
+        _loadedWriteTime = writeTime;
+
+        var problems = new List<string>();
+        try
+        {
+            _values = writeTime == default ? new(StringComparer.Ordinal) : Parse(File.ReadAllText(Path), problems);
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Reading {Path} failed", Path);
+            return;
+        }
+
+        foreach (var problem in problems)
+        {
+            _logger.LogWarning("Ignoring part of {Path}, which won't be kept when settings are next saved: {Problem}", Path, problem);
+        }
+
+        // A password typed into the file by hand is protected straight away rather than left readable
+        var plainSecrets = _values
+            .Where(v => PreferenceSecrets.IsSecret(v.Key) && v.Value is string text && !PreferenceSecrets.IsProtected(text))
+            .ToList();
+        if (plainSecrets.Count == 0) return;
+
+        foreach (var (name, value) in plainSecrets)
+        {
+            _values[name] = PreferenceSecrets.Protect((string)value);
+        }
+        Save();
+    }
+
+    /// <summary>
+    /// Reads the TOML subset described on the class. Anything else is skipped rather than failing
+    /// the whole file, and described in <paramref name="problems"/> with its line number: tables
+    /// (with every key under them, which would otherwise land at the top level), inline tables,
+    /// dates, arrays of anything but strings and lines that don't parse.
+    /// </summary>
+    public static Dictionary<string, object> Parse(string text, ICollection<string>? problems = null)
+    {
+        var values = new Dictionary<string, object>(StringComparer.Ordinal);
+        var lines = text.Split('\n');
+        var inTable = false;
+
+        for (var i = 0; i < lines.Length; i++)
+        {
+            var lineNumber = i + 1;
+            var line = lines[i].Trim();
+            if (line.Length == 0 || line[0] == '#') continue;
+
+            if (line[0] == '[')
+            {
+                inTable = true;
+                problems?.Add($"line {lineNumber}: tables aren't supported ({line} and the keys under it)");
+                continue;
+            }
+
+            if (inTable) continue;
+
+            var equals = line.IndexOf('=');
+            if (equals <= 0)
+            {
+                problems?.Add($"line {lineNumber}: expected key = value");
+                continue;
+            }
+
+            var key = UnquoteKey(line[..equals].Trim());
+            var valueText = line[(equals + 1)..].TrimStart();
+            var position = 0;
+            object? value = null;
+            var parsed = key.Length > 0 && TryParseValue(valueText, ref position, out value);
+
+            // An array can run over several lines; take more until it closes
+            var last = i;
+            while (!parsed && key.Length > 0 && valueText.StartsWith('[') && last + 1 < lines.Length)
+            {
+                valueText += "\n" + lines[++last].Trim();
+                position = 0;
+                parsed = TryParseValue(valueText, ref position, out value);
+            }
+
+            if (!parsed)
+            {
+                problems?.Add($"line {lineNumber}: can't read the value of {(key.Length > 0 ? key : "an unnamed key")}");
+                continue;
+            }
+
+            values[key] = value!;
+            i = last;
+        }
+
+        return values;
+    }
+
+    public static string Serialize(IReadOnlyDictionary<string, object> values)
+    {
+        var builder = new StringBuilder();
+        builder.Append("# Microphone Manager settings. Edits are picked up while the app runs.\n\n");
+
+        foreach (var (name, value) in values.OrderBy(v => v.Key, StringComparer.Ordinal))
+        {
+            builder.Append(QuoteKey(name)).Append(" = ");
+            switch (value)
+            {
+                case bool flag:
+                    builder.Append(flag ? "true" : "false");
+                    break;
+                case string[] list:
+                    builder.Append('[').Append(string.Join(", ", list.Select(Quote))).Append(']');
+                    break;
+                default:
+                    builder.Append(Quote(Convert.ToString(value, CultureInfo.InvariantCulture) ?? ""));
+                    break;
+            }
+
+            builder.Append('\n');
+        }
+
+        return builder.ToString();
+    }
+
+    private static bool TryParseValue(string text, ref int position, out object? value)
+    {
+        value = null;
+        if (position >= text.Length) return false;
+
+        switch (text[position])
+        {
+            case '"':
+            case '\'':
+                if (!TryParseString(text, ref position, out var s)) return false;
+                value = s;
+                return true;
+
+            case '[':
+                position++;
+                var items = new List<string>();
+                while (true)
+                {
+                    SkipWhitespaceAndComments(text, ref position);
+                    if (position >= text.Length) return false;
+                    if (text[position] == ']') { position++; break; }
+
+                    if (!TryParseString(text, ref position, out var item)) return false;
+                    items.Add(item!);
+
+                    SkipWhitespaceAndComments(text, ref position);
+                    if (position < text.Length && text[position] == ',') position++;
+                }
+
+                value = items.ToArray();
+                return true;
+
+            default:
+                var end = text.IndexOfAny(new[] { ' ', '\t', '#' }, position);
+                var word = end < 0 ? text[position..] : text[position..end];
+                var number = word.Replace("_", "");
+                if (word == "true") value = true;
+                else if (word == "false") value = false;
+                else if (long.TryParse(number, NumberStyles.AllowLeadingSign, CultureInfo.InvariantCulture, out var integer)) value = integer.ToString(CultureInfo.InvariantCulture);
+                else if (double.TryParse(number, NumberStyles.Float, CultureInfo.InvariantCulture, out var real)) value = real.ToString(CultureInfo.InvariantCulture);
+                else return false;
+                position += word.Length;
+                return true;
+        }
+    }
+
+    private static bool TryParseString(string text, ref int position, out string? value)
+    {
+        value = null;
+        var quote = text[position];
+        if (quote != '"' && quote != '\'') return false;
+
+        var builder = new StringBuilder();
+        for (var i = position + 1; i < text.Length; i++)
+        {
+            var c = text[i];
+            if (c == quote)
+            {
+                position = i + 1;
+                value = builder.ToString();
+                return true;
+            }
+
+            // Literal ('...') strings have no escapes
+            if (c != '\\' || quote == '\'')
+            {
+                builder.Append(c);
+                continue;
+            }
+
+            if (++i >= text.Length) return false;
+            switch (text[i])
+            {
+                case '"': builder.Append('"'); break;
+                case '\\': builder.Append('\\'); break;
+                case 'n': builder.Append('\n'); break;
+                case 'r': builder.Append('\r'); break;
+                case 't': builder.Append('\t'); break;
+                case 'u' when i + 4 < text.Length && int.TryParse(text.AsSpan(i + 1, 4), NumberStyles.HexNumber, CultureInfo.InvariantCulture, out var code):
+                    builder.Append((char)code);
+                    i += 4;
+                    break;
+                default:
+                    return false;
+            }
+        }
+
+        return false;
+    }
+
+    // Inside a multi-line array a comment runs to the end of its line
+    private static void SkipWhitespaceAndComments(string text, ref int position)
+    {
+        while (position < text.Length)
+        {
+            if (char.IsWhiteSpace(text[position]))
+            {
+                position++;
+            }
+            else if (text[position] == '#')
+            {
+                var newline = text.IndexOf('\n', position);
+                position = newline < 0 ? text.Length : newline + 1;
+            }
+            else
+            {
+                return;
+            }
+        }
+    }
+
+    private static string Quote(string value)
+    {
+        var builder = new StringBuilder("\"");
+        foreach (var c in value)
+        {
+            switch (c)
+            {
+                case '"': builder.Append("\\\""); break;
+                case '\\': builder.Append("\\\\"); break;
+                case '\n': builder.Append("\\n"); break;
+                case '\r': builder.Append("\\r"); break;
+                case '\t': builder.Append("\\t"); break;
+                case < ' ': builder.Append($"\\u{(int)c:X4}"); break;
+                default: builder.Append(c); break;
+            }
+        }
+
+        return builder.Append('"').ToString();
+    }
+
+    private static string QuoteKey(string key)
+    {
+        return key.Length > 0 && key.All(c => char.IsAsciiLetterOrDigit(c) || c is '_' or '-') ? key : Quote(key);
+    }
+
+    private static string UnquoteKey(string key)
+    {
+        var position = 0;
+        return key.Length > 0 && key[0] is '"' or '\'' && TryParseString(key, ref position, out var unquoted)
+            ? unquoted!
+            : key;
+    }
+}
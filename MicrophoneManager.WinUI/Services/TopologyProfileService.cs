@@ -0,0 +1,288 @@
+using System.Runtime.InteropServices;
+using System.Text.Json;
+using Microsoft.Extensions.Logging;
+using Microsoft.Extensions.Logging.Abstractions;
+using MicrophoneManager.WinUI.Models;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Applies a profile when the display setup changes: docking at the desk can bring up "Desk" and
+/// undocking "Laptop only". Watches WM_DISPLAYCHANGE and WM_DEVICECHANGE on the tray window and,
+/// once things settle, compares the attached monitors with the setups the user mapped. The last
+/// setup seen is remembered, so starting the app in a different setup than it was closed in counts
+/// as a change too.
+/// </summary>
+public sealed class TopologyProfileService : IDisposable
+{
+    public const string MappingsPreference = "DisplayTopologyProfiles";
+    public const string LastTopologyPreference = "LastDisplayTopology";
+
+    private const int WM_DISPLAYCHANGE = 0x007E;
+    private const int WM_DEVICECHANGE = 0x0219;
+    private const int DBT_DEVNODES_CHANGED = 0x0007;
+    private const int DBT_DEVICEARRIVAL = 0x8000;
+    private const int DBT_DEVICEREMOVECOMPLETE = 0x8004;
+
+    private readonly ProfileService _profileService;
+    private readonly IPreferencesStore _preferences;
+    private readonly Func<DisplayTopology> _readTopology;
+    private readonly SynchronizationContext? _syncContext;
+    private readonly object _settleLock = new();
+    private readonly ILogger<TopologyProfileService> _logger;
+    private WindowMessageMonitor? _monitor;
+    private Timer? _settleTimer;
+    private bool _disposed;
+
+    /// <summary>
+    /// Raised after a mapped profile was applied for a new setup.
+    /// </summary>
+    public event EventHandler<TopologyProfileMapping>? ProfileApplied;
+
+    /// <summary>
+    /// Raised when the display setup differs from the last one seen, before any profile is applied.
+    /// </summary>
+    public event EventHandler<DisplayTopology>? TopologyChanged;
+
+    /// <summary>
+    /// Raised when a mapping is added or removed, or follows a renamed profile.
+    /// </summary>
+    public event EventHandler? MappingsChanged;
+
+    public TopologyProfileService(ProfileService profileService, IPreferencesStore preferences, ILogger<TopologyProfileService>? logger = null)
+        : this(profileService, preferences, ReadCurrentTopology, logger)
+    {
+    }
+
+    public TopologyProfileService(ProfileService profileService, IPreferencesStore preferences, Func<DisplayTopology> readTopology, ILogger<TopologyProfileService>? logger = null)
+    {
+        _profileService = profileService ?? throw new ArgumentNullException(nameof(profileService));
+        _preferences = preferences ?? throw new ArgumentNullException(nameof(preferences));
+        _readTopology = readTopology ?? throw new ArgumentNullException(nameof(readTopology));
+        _logger = logger ?? NullLogger<TopologyProfileService>.Instance;
+        _syncContext = SynchronizationContext.Current;
+
+        _profileService.ProfileRenamed += OnProfileRenamed;
+    }
+
+    /// <summary>
+    /// A dock brings its monitors up one at a time, with device notifications in between.
+    /// </summary>
+    public TimeSpan SettleDelay { get; set; } = TimeSpan.FromSeconds(3);
+
+    public DisplayTopology CurrentTopology => _readTopology();
+
+    public void Attach(WindowMessageMonitor monitor)
+    {
+        if (_disposed || monitor.Handle == IntPtr.Zero || _monitor != null) return;
+
+        _monitor = monitor;
+        _monitor.MessageReceived += OnMessageReceived;
+
+        _ = CheckAsync();
+    }
+
+    public IReadOnlyList<TopologyProfileMapping> GetMappings()
+    {
+        var json = _preferences.GetString(MappingsPreference);
+        if (string.IsNullOrEmpty(json)) return Array.Empty<TopologyProfileMapping>();
+
+        try
+        {
+            return JsonSerializer.Deserialize<List<TopologyProfileMapping>>(json) ?? new List<TopologyProfileMapping>();
+        }
+        catch (JsonException ex)
+        {
+            _logger.LogWarning(ex, "Ignoring unreadable display setup profiles");
+            return Array.Empty<TopologyProfileMapping>();
+        }
+    }
+
+    /// <summary>
+    /// Profile mapped to the current setup, or null.
+    /// </summary>
+    public string? GetProfileForCurrent()
+    {
+        var signature = CurrentTopology.Signature;
+        return GetMappings().FirstOrDefault(m => m.Signature == signature)?.ProfileName;
+    }
+
+    /// <summary>
+    /// Maps the current setup to <paramref name="profileName"/>, or removes its mapping when null.
+    /// Nothing is applied now; the profile is used the next time the setup changes to this one.
+    /// </summary>
+    public void SetProfileForCurrent(string? profileName)
+    {
+        var topology = CurrentTopology;
+        var mappings = GetMappings().Where(m => m.Signature != topology.Signature).ToList();
+        if (!string.IsNullOrWhiteSpace(profileName))
+        {
+            mappings.Add(new TopologyProfileMapping(topology.Signature, topology.Description, profileName.Trim()));
+        }
+
+        _preferences.SetString(MappingsPreference, mappings.Count > 0 ? JsonSerializer.Serialize(mappings) : null);
+
+        // The setup the user is mapping is the one they're in; it's not a change to act on later
+        _preferences.SetString(LastTopologyPreference, topology.Signature);
+        MappingsChanged?.Invoke(this, EventArgs.Empty);
+    }
+
+    private void OnProfileRenamed(object? sender, ProfileService.RenamedEventArgs e)
+    {
+        var mappings = GetMappings().ToList();
+        if (!mappings.Any(m => string.Equals(m.ProfileName, e.OldName, StringComparison.OrdinalIgnoreCase))) return;
+
+        mappings = mappings
+            .Select(m => string.Equals(m.ProfileName, e.OldName, StringComparison.OrdinalIgnoreCase) ? m with { ProfileName = e.NewName } : m)
+            .ToList();
+        _preferences.SetString(MappingsPreference, JsonSerializer.Serialize(mappings));
+        MappingsChanged?.Invoke(this, EventArgs.Empty);
+    }
+
+    /// <summary>
+    /// Reads the displays and applies the mapped profile if the setup differs from the last one seen.
+    /// </summary>
+    /// <returns>The mapping that was applied, or null.</returns>
+    public async Task<TopologyProfileMapping?> CheckAsync()
+    {
+        if (_disposed) return null;
+
+        DisplayTopology topology;
+        try
+        {
+            topology = _readTopology();
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Reading the display setup failed");
+            return null;
+        }
+
+        // Every monitor briefly gone (a display driver restart, the lid closing first) isn't a setup
+        if (topology.MonitorIds.Count == 0) return null;
+
+        var signature = topology.Signature;
+        if (signature == _preferences.GetString(LastTopologyPreference)) return null;
+        _preferences.SetString(LastTopologyPreference, signature);
+        TopologyChanged?.Invoke(this, topology);
+
+        var mapping = GetMappings().FirstOrDefault(m => m.Signature == signature);
+        if (mapping == null || _profileService.GetProfile(mapping.ProfileName) == null) return null;
+
+        try
+        {
+            await _profileService.ApplyProfileAsync(mapping.ProfileName);
+            ProfileApplied?.Invoke(this, mapping);
+            return mapping;
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Applying display setup profile {Profile} failed", mapping.ProfileName);
+            return null;
+        }
+    }
+
+    private void OnMessageReceived(object? sender, WindowMessageMonitor.WindowMessageEventArgs e)
+    {
+        var relevant = e.Message == WM_DISPLAYCHANGE
+            || (e.Message == WM_DEVICECHANGE && (int)e.WParam is DBT_DEVNODES_CHANGED or DBT_DEVICEARRIVAL or DBT_DEVICEREMOVECOMPLETE);
+        if (!relevant) return;
+
+        lock (_settleLock)
+        {
+            _settleTimer?.Dispose();
+            _settleTimer = new Timer(_ => OnSettled(), null, SettleDelay, Timeout.InfiniteTimeSpan);
+        }
+    }
+
+    private void OnSettled()
+    {
+        if (_disposed) return;
+
+        if (_syncContext != null)
+        {
+            _syncContext.Post(_ => _ = CheckAsync(), null);
+        }
+        else
+        {
+            _ = CheckAsync();
+        }
+    }
+
+    /// <summary>
+    /// Hardware IDs of the monitors attached to the desktop, from EnumDisplayDevices.
+    /// </summary>
+    private static DisplayTopology ReadCurrentTopology()
+    {
+        var monitors = new List<string>();
+        var adapter = new DISPLAY_DEVICE { cb = Marshal.SizeOf<DISPLAY_DEVICE>() };
+        for (uint a = 0; EnumDisplayDevices(null, a, ref adapter, 0); a++)
+        {
+            if ((adapter.StateFlags & DISPLAY_DEVICE_ATTACHED_TO_DESKTOP) != 0)
+            {
+                var monitor = new DISPLAY_DEVICE { cb = Marshal.SizeOf<DISPLAY_DEVICE>() };
+                for (uint m = 0; EnumDisplayDevices(adapter.DeviceName, m, ref monitor, 0); m++)
+                {
+                    // DeviceID is "MONITOR\DEL40B6\{4d36e96e-...}\0001"
+                    var parts = monitor.DeviceID.Split('\\');
+                    if ((monitor.StateFlags & DISPLAY_DEVICE_ACTIVE) != 0 && parts.Length > 1)
+                    {
+                        monitors.Add(parts[1]);
+                    }
+
+                    monitor = new DISPLAY_DEVICE { cb = Marshal.SizeOf<DISPLAY_DEVICE>() };
+                }
+            }
+
+            adapter = new DISPLAY_DEVICE { cb = Marshal.SizeOf<DISPLAY_DEVICE>() };
+        }
+
+        return new DisplayTopology(monitors);
+    }
+
+    public void Dispose()
+    {
+        if (_disposed) return;
+        _disposed = true;
+
+        lock (_settleLock)
+        {
+            _settleTimer?.Dispose();
+            _settleTimer = null;
+        }
+
+        try { _profileService.ProfileRenamed -= OnProfileRenamed; } catch { }
+
+        if (_monitor == null) return;
+
+        try { _monitor.MessageReceived -= OnMessageReceived; } catch { }
+        _monitor = null;
+    }
+
+    private const int DISPLAY_DEVICE_ATTACHED_TO_DESKTOP = 0x1;
+    private const int DISPLAY_DEVICE_ACTIVE = 0x1;
+
+    [StructLayout(LayoutKind.Sequential, CharSet = CharSet.Unicode)]
+    private struct DISPLAY_DEVICE
+    {
+        public int cb;
+
+        [MarshalAs(UnmanagedType.ByValTStr, SizeConst = 32)]
+        public string DeviceName;
+
+        [MarshalAs(UnmanagedType.ByValTStr, SizeConst = 128)]
+        public string DeviceString;
+
+        public int StateFlags;
+
+        [MarshalAs(UnmanagedType.ByValTStr, SizeConst = 128)]
+        public string DeviceID;
+
+        [MarshalAs(UnmanagedType.ByValTStr, SizeConst = 128)]
+        public string DeviceKey;
+    }
+
+    [DllImport("user32.dll", CharSet = CharSet.Unicode)]
+    [return: MarshalAs(UnmanagedType.Bool)]
+    private static extern bool EnumDisplayDevices(string? lpDevice, uint iDevNum, ref DISPLAY_DEVICE lpDisplayDevice, uint dwFlags);
+}
@@ -0,0 +1,145 @@
+using MicrophoneManager.WinUI.Models;
+using NAudio.CoreAudioApi;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// A short history of changes made through the app, each kept as the part of the audio state it
+/// touched, so the latest one can be put back. Callers record just before they change something;
+/// changes made by other apps aren't recorded and are only reverted if they touch the same setting.
+/// </summary>
+public sealed class UndoService
+{
+    public const int Capacity = 10;
+
+    // A slider drag or a run of hotkey presses is one change to undo, not dozens
+    private static readonly TimeSpan CoalesceWindow = TimeSpan.FromSeconds(2);
+
+    private readonly IAudioDeviceService _audioService;
+    private readonly AudioSnapshotService _snapshotService;
+    private readonly Func<DateTime> _clock;
+    private readonly LinkedList<Entry> _history = new();
+    private readonly object _lock = new();
+
+    /// <summary>
+    /// Raised when a change is recorded or undone.
+    /// </summary>
+    public event EventHandler? HistoryChanged;
+
+    public UndoService(IAudioDeviceService audioService)
+        : this(audioService, () => DateTime.UtcNow)
+    {
+    }
+
+    public UndoService(IAudioDeviceService audioService, Func<DateTime> clock)
+    {
+        _audioService = audioService ?? throw new ArgumentNullException(nameof(audioService));
+        _snapshotService = new AudioSnapshotService(audioService);
+        _clock = clock ?? throw new ArgumentNullException(nameof(clock));
+    }
+
+    public bool CanUndo
+    {
+        get
+        {
+            lock (_lock) return _history.Count > 0;
+        }
+    }
+
+    /// <summary>
+    /// What undo would revert, e.g. "default microphone change"; null when there's nothing to undo.
+    /// </summary>
+    public string? NextDescription
+    {
+        get
+        {
+            lock (_lock) return _history.First?.Value.Description;
+        }
+    }
+
+    /// <summary>
+    /// Records the default microphones for both roles before they're changed.
+    /// </summary>
+    public void RecordDefaults(string description = "default microphone change")
+    {
+        Push(description, null, new AudioSnapshot
+        {
+            DefaultConsoleId = _audioService.GetDefaultDeviceId(Role.Console),
+            DefaultCommunicationsId = _audioService.GetDefaultDeviceId(Role.Communications),
+        });
+    }
+
+    /// <summary>
+    /// Records the default output device before it's changed.
+    /// </summary>
+    public void RecordOutputDefault(string description = "output device change")
+    {
+        Push(description, null, new AudioSnapshot { DefaultOutputId = _audioService.GetDefaultOutputDeviceId(Role.Console) });
+    }
+
+    /// <summary>
+    /// Records one microphone's volume and mute before they're changed. Repeated changes to the same
+    /// device in quick succession are kept as one.
+    /// </summary>
+    public void RecordDevice(string deviceId, string description = "volume change")
+    {
+        var microphone = _audioService.GetMicrophone(deviceId);
+        if (microphone == null) return;
+
+        Push(description, $"device:{deviceId}:{description}", new AudioSnapshot
+        {
+            Devices = { new DeviceSnapshot { DeviceId = deviceId, VolumeLevel = microphone.VolumeLevel, IsMuted = microphone.IsMuted } },
+        });
+    }
+
+    /// <summary>
+    /// Records everything before a change that touches several devices, like applying a profile.
+    /// </summary>
+    public void RecordAll(string description)
+    {
+        Push(description, null, _snapshotService.TakeSnapshot());
+    }
+
+    /// <summary>
+    /// Puts back the most recent recorded change.
+    /// </summary>
+    /// <returns>False when there was nothing to undo or it was only partly restored.</returns>
+    public async Task<bool> UndoAsync(CancellationToken cancellationToken = default)
+    {
+        Entry entry;
+        lock (_lock)
+        {
+            if (_history.First == null) return false;
+            entry = _history.First.Value;
+            _history.RemoveFirst();
+        }
+
+        HistoryChanged?.Invoke(this, EventArgs.Empty);
+        return await _snapshotService.RestoreSnapshotAsync(entry.State, cancellationToken);
+    }
+
+    private void Push(string description, string? coalesceKey, AudioSnapshot state)
+    {
+        var now = _clock();
+        lock (_lock)
+        {
+            // Keep the state from before the first change in the run
+            if (coalesceKey != null && _history.First?.Value is { } latest
+                && latest.CoalesceKey == coalesceKey && now - latest.RecordedAtUtc < CoalesceWindow)
+            {
+                latest.RecordedAtUtc = now;
+                return;
+            }
+
+            _history.AddFirst(new Entry(description, coalesceKey, state) { RecordedAtUtc = now });
+            if (_history.Count > Capacity) _history.RemoveLast();
+        }
+
+        HistoryChanged?.Invoke(this, EventArgs.Empty);
+    }
+
+    private sealed record Entry(string Description, string? CoalesceKey, AudioSnapshot State)
+    {
+        public DateTime RecordedAtUtc { get; set; }
+    }
+}
@@ -0,0 +1,296 @@
+using System.Net;
+using System.Net.Http.Headers;
+using System.Text.Json;
+using Microsoft.Extensions.Logging;
+using Microsoft.Extensions.Logging.Abstractions;
+using MicrophoneManager.WinUI.Models;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// A newer release than the running build.
+/// </summary>
+public sealed record UpdateInfo(string Version, string DownloadUrl, string? Name);
+
+/// <summary>
+/// Opt-in check of the GitHub releases feed for a newer version. Nothing is downloaded or installed;
+/// the user is told about the release and given its page. Checks at most once a day in the background,
+/// or on demand. <see cref="ProxyPreference"/> can point at a proxy, or be <c>none</c> to bypass the
+/// system one.
+/// </summary>
+public sealed class UpdateService : IDisposable
+{
+    public const string EnabledPreference = "UpdateCheckEnabled";
+    public const string ProxyPreference = "UpdateProxy";
+    public const string FeedUrlPreference = "UpdateFeedUrl";
+    public const string LastCheckPreference = "UpdateLastCheckUtc";
+    public const string NotifiedVersionPreference = "UpdateNotifiedVersion";
+
+    public const string DefaultFeedUrl = "https://api.github.com/repos/tomrussell-ia/windows-mic-manager/releases/latest";
+
+    // A changed preference or a tampered feed must not make the tray show and open an arbitrary page
+    private const string FeedHost = "api.github.com";
+    private const string ReleasePageHost = "github.com";
+
+    private static readonly TimeSpan CheckInterval = TimeSpan.FromDays(1);
+    private static readonly TimeSpan PollInterval = TimeSpan.FromHours(1);
+    private static readonly TimeSpan StartupDelay = TimeSpan.FromMinutes(1);
+    private static readonly TimeSpan RequestTimeout = TimeSpan.FromSeconds(15);
+
+    private readonly IPreferencesStore _preferences;
+    private readonly Func<HttpMessageHandler> _createHandler;
+    private readonly string _currentVersion;
+    private readonly ILogger<UpdateService> _logger;
+    private CancellationTokenSource? _shutdown;
+    private bool _disposed;
+
+    /// <summary>
+    /// Raised by the background check the first time it finds a given newer version.
+    /// </summary>
+    public event EventHandler<UpdateInfo>? UpdateAvailable;
+
+    /// <summary>
+    /// Raised when any check changes <see cref="AvailableUpdate"/>.
+    /// </summary>
+    public event EventHandler? AvailableUpdateChanged;
+
+    public UpdateService(IPreferencesStore preferences, ILogger<UpdateService>? logger = null)
+        : this(preferences, null, BuildInfo.Current.Version, logger)
+    {
+    }
+
+    public UpdateService(IPreferencesStore preferences, Func<HttpMessageHandler>? createHandler, string currentVersion, ILogger<UpdateService>? logger = null)
+    {
+        _preferences = preferences ?? throw new ArgumentNullException(nameof(preferences));
+        _createHandler = createHandler ?? CreateHandler;
+        _currentVersion = currentVersion;
+        _logger = logger ?? NullLogger<UpdateService>.Instance;
+    }
+
+    public bool IsEnabled
+    {
+        get => _preferences.GetBool(EnabledPreference, false);
+        set
+        {
+            _preferences.SetBool(EnabledPreference, value);
+            if (value) Start(); else Stop();
+        }
+    }
+
+    /// <summary>
+    /// The newer release found by the last check, or null if none was (or it failed).
+    /// </summary>
+    public UpdateInfo? AvailableUpdate { get; private set; }
+
+    /// <summary>
+    /// The releases API URL from preferences, or the default when it isn't an https URL on api.github.com.
+    /// </summary>
+    public string FeedUrl => TryGetHttpsUrl(_preferences.GetString(FeedUrlPreference), FeedHost, out var feed)
+        ? feed.ToString()
+        : DefaultFeedUrl;
+
+    /// <summary>
+    /// Starts background checks if the preference is on.
+    /// </summary>
+    public void Start()
+    {
+        if (_disposed || _shutdown != null || !IsEnabled) return;
+
+        _shutdown = new CancellationTokenSource();
+        _ = Task.Run(() => PollAsync(_shutdown.Token));
+    }
+
+    public void Stop()
+    {
+        var shutdown = _shutdown;
+        if (shutdown == null) return;
+
+        _shutdown = null;
+        shutdown.Cancel();
+    }
+
+    /// <summary>
+    /// Fetches the latest release and compares it with the running build.
+    /// </summary>
+    /// <returns>The newer release, or null when this build is current.</returns>
+    /// <exception cref="HttpRequestException">The feed couldn't be reached or read.</exception>
+    public async Task<UpdateInfo?> CheckNowAsync(CancellationToken cancellationToken = default)
+    {
+        using var client = new HttpClient(_createHandler(), disposeHandler: true) { Timeout = RequestTimeout };
+        client.DefaultRequestHeaders.UserAgent.Add(new ProductInfoHeaderValue("MicrophoneManager", _currentVersion));
+        client.DefaultRequestHeaders.Accept.Add(new MediaTypeWithQualityHeaderValue("application/vnd.github+json"));
+
+        string json;
+        try
+        {
+            json = await client.GetStringAsync(FeedUrl, cancellationToken);
+        }
+        catch (TaskCanceledException ex) when (!cancellationToken.IsCancellationRequested)
+        {
+            throw new HttpRequestException("The update check timed out.", ex);
+        }
+
+        _preferences.SetString(LastCheckPreference, DateTime.UtcNow.ToString("O"));
+
+        var release = ParseRelease(json) ?? throw new HttpRequestException("The release feed wasn't understood.");
+        var update = IsNewer(release.Version, _currentVersion) ? release : null;
+
+        if (update != AvailableUpdate)
+        {
+            AvailableUpdate = update;
+            AvailableUpdateChanged?.Invoke(this, EventArgs.Empty);
+        }
+
+        return update;
+    }
+
+    /// <summary>
+    /// Reads a GitHub release (<c>tag_name</c>, <c>html_url</c>, <c>name</c>) from the releases API.
+    /// </summary>
+    public static UpdateInfo? ParseRelease(string json)
+    {
+        try
+        {
+            using var document = JsonDocument.Parse(json);
+            var root = document.RootElement;
+            if (root.ValueKind != JsonValueKind.Object) return null;
+
+            var tag = root.TryGetProperty("tag_name", out var tagElement) ? tagElement.GetString() : null;
+            var url = root.TryGetProperty("html_url", out var urlElement) ? urlElement.GetString() : null;
+            var name = root.TryGetProperty("name", out var nameElement) && nameElement.ValueKind == JsonValueKind.String
+                ? nameElement.GetString()
+                : null;
+
+            // Only a GitHub release page is ever opened for the user
+            if (string.IsNullOrWhiteSpace(tag) || !TryGetHttpsUrl(url, ReleasePageHost, out var uri))
+            {
+                return null;
+            }
+
+            return new UpdateInfo(tag.Trim(), uri.ToString(), string.IsNullOrWhiteSpace(name) ? null : name);
+        }
+        catch (JsonException)
+        {
+            return null;
+        }
+    }
+
+    private static bool TryGetHttpsUrl(string? url, string host, out Uri uri)
+    {
+        return Uri.TryCreate(url, UriKind.Absolute, out uri!)
+            && uri.Scheme == Uri.UriSchemeHttps
+            && string.Equals(uri.Host, host, StringComparison.OrdinalIgnoreCase)
+            && uri.IsDefaultPort
+            && string.IsNullOrEmpty(uri.UserInfo);
+    }
+
+    /// <summary>
+    /// Whether release tag <paramref name="latest"/> ("v1.4.0") is a later version than <paramref name="current"/>.
+    /// Tags that aren't versions never count as newer.
+    /// </summary>
+    public static bool IsNewer(string latest, string current)
+    {
+        var latestVersion = ParseVersion(latest);
+        var currentVersion = ParseVersion(current);
+        return latestVersion != null && currentVersion != null && latestVersion > currentVersion;
+    }
+
+    /// <summary>
+    /// "v1.4", "1.4.0-beta.2" and "1.4.0+abc" all read as 1.4.0.0; missing parts count as zero.
+    /// </summary>
+    public static Version? ParseVersion(string? text)
+    {
+        if (string.IsNullOrWhiteSpace(text)) return null;
+
+        var trimmed = text.Trim().TrimStart('v', 'V');
+        var end = trimmed.IndexOfAny(new[] { '-', '+', ' ' });
+        if (end >= 0) trimmed = trimmed[..end];
+
+        var parts = trimmed.Split('.');
+        if (parts.Length > 4) return null;
+
+        var numbers = new int[4];
+        for (var i = 0; i < parts.Length; i++)
+        {
+            if (!int.TryParse(parts[i], System.Globalization.NumberStyles.None, System.Globalization.CultureInfo.InvariantCulture, out numbers[i]))
+            {
+                return null;
+            }
+        }
+
+        return new Version(numbers[0], numbers[1], numbers[2], numbers[3]);
+    }
+
+    private async Task PollAsync(CancellationToken cancellationToken)
+    {
+        try
+        {
+            // Let startup settle before going to the network
+            await Task.Delay(StartupDelay, cancellationToken);
+
+            while (!cancellationToken.IsCancellationRequested)
+            {
+                if (IsCheckDue())
+                {
+                    await CheckInBackgroundAsync(cancellationToken);
+                }
+
+                // Polled hourly so a check still happens roughly daily across sleep and resume
+                await Task.Delay(PollInterval, cancellationToken);
+            }
+        }
+        catch (OperationCanceledException)
+        {
+        }
+    }
+
+    private bool IsCheckDue()
+    {
+        return !DateTime.TryParse(_preferences.GetString(LastCheckPreference), null, System.Globalization.DateTimeStyles.RoundtripKind, out var lastCheck)
+            || DateTime.UtcNow - lastCheck >= CheckInterval;
+    }
+
+    private async Task CheckInBackgroundAsync(CancellationToken cancellationToken)
+    {
+        try
+        {
+            var update = await CheckNowAsync(cancellationToken);
+
+            // One notification per release, not one a day
+            if (update != null && update.Version != _preferences.GetString(NotifiedVersionPreference))
+            {
+                _preferences.SetString(NotifiedVersionPreference, update.Version);
+                UpdateAvailable?.Invoke(this, update);
+            }
+        }
+        catch (HttpRequestException ex)
+        {
+            _logger.LogWarning(ex, "Update check failed");
+        }
+    }
+
+    private HttpMessageHandler CreateHandler()
+    {
+        var handler = new SocketsHttpHandler { DefaultProxyCredentials = CredentialCache.DefaultCredentials };
+
+        // Unset: the system proxy. "none": direct. Anything else: that proxy.
+        var proxy = _preferences.GetString(ProxyPreference)?.Trim();
+        if (string.Equals(proxy, "none", StringComparison.OrdinalIgnoreCase))
+        {
+            handler.UseProxy = false;
+        }
+        else if (!string.IsNullOrEmpty(proxy) && Uri.TryCreate(proxy, UriKind.Absolute, out var proxyUri))
+        {
+            handler.Proxy = new WebProxy(proxyUri) { UseDefaultCredentials = true };
+        }
+
+        return handler;
+    }
+
+    public void Dispose()
+    {
+        if (_disposed) return;
+        _disposed = true;
+        Stop();
+    }
+}
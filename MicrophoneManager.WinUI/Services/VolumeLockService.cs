@@ -0,0 +1,219 @@
+using System.Globalization;
+using Microsoft.Extensions.Logging;
+using Microsoft.Extensions.Logging.Abstractions;
+using MicrophoneManager.WinUI.Models;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Holds locked microphones at a target volume: any change made outside this app is snapped back
+/// as soon as it's reported. Changes made from our own UI move the target instead, so the lock
+/// always keeps the level the user last chose here.
+/// </summary>
+public sealed class VolumeLockService : IDisposable
+{
+    public const string VolumeLocksPreference = "VolumeLocks";
+    public const string NotifyOnRevertPreference = "VolumeLockNotifications";
+
+    // Endpoint volume round-trips through dB steps; ignore differences smaller than half a percent
+    private const float Tolerance = 0.005f;
+
+    private readonly IAudioDeviceService _audioService;
+    private readonly IPreferencesStore _preferences;
+    private readonly PowerEventService? _powerEvents;
+    private readonly DeviceAliasService? _aliasService;
+    private readonly ILogger<VolumeLockService> _logger;
+    private bool _disposed;
+
+    /// <summary>
+    /// Raised after an external change was reverted.
+    /// </summary>
+    public event EventHandler<VolumeRevertedEventArgs>? VolumeReverted;
+
+    /// <summary>
+    /// Raised when a device is locked, unlocked or its target changes.
+    /// </summary>
+    public event EventHandler? LocksChanged;
+
+    public VolumeLockService(
+        IAudioDeviceService audioService,
+        IPreferencesStore preferences,
+        PowerEventService? powerEvents = null,
+        DeviceAliasService? aliasService = null,
+        ILogger<VolumeLockService>? logger = null)
+    {
+        _audioService = audioService ?? throw new ArgumentNullException(nameof(audioService));
+        _preferences = preferences ?? throw new ArgumentNullException(nameof(preferences));
+        _aliasService = aliasService;
+        _logger = logger ?? NullLogger<VolumeLockService>.Instance;
+
+        _audioService.MicrophoneVolumeChanged += OnMicrophoneVolumeChanged;
+
+        // Newly connected devices are brought back to target
+        _audioService.DevicesChanged += OnDevicesChanged;
+
+        // Drivers can come back from sleep at a different level without raising a volume event
+        _powerEvents = powerEvents;
+        if (_powerEvents != null)
+        {
+            _powerEvents.Resumed += OnResumed;
+        }
+    }
+
+    /// <summary>
+    /// Whether to show a notification each time a change is reverted.
+    /// </summary>
+    public bool NotifyOnRevert
+    {
+        get => _preferences.GetBool(NotifyOnRevertPreference, true);
+        set => _preferences.SetBool(NotifyOnRevertPreference, value);
+    }
+
+    /// <summary>
+    /// Target level (0-1) for a locked device, or null when it isn't locked.
+    /// </summary>
+    public float? GetTarget(string key)
+    {
+        return GetLocks().TryGetValue(key, out var target) ? target : null;
+    }
+
+    /// <summary>
+    /// Locks a device at <paramref name="target"/> (0-1); null unlocks it.
+    /// </summary>
+    public void SetLock(string key, float? target)
+    {
+        var locks = GetLocks();
+        if (target is float level)
+        {
+            locks[key] = Math.Clamp(level, 0f, 1f);
+        }
+        else if (!locks.Remove(key))
+        {
+            return;
+        }
+
+        _preferences.SetStringList(
+            VolumeLocksPreference,
+            locks.Select(l => $"{l.Key}={l.Value.ToString("0.###", CultureInfo.InvariantCulture)}").ToList());
+        LocksChanged?.Invoke(this, EventArgs.Empty);
+
+        if (target != null) EnforceAll();
+    }
+
+    /// <summary>
+    /// Puts every connected locked device back to its target.
+    /// </summary>
+    public void EnforceAll()
+    {
+        if (_disposed) return;
+
+        var locks = GetLocks();
+        if (locks.Count == 0) return;
+
+        foreach (var microphone in _audioService.GetMicrophones())
+        {
+            if (locks.TryGetValue(DevicePriorityService.KeyFor(microphone), out var target))
+            {
+                Enforce(microphone.Id, NameOf(microphone), microphone.VolumeLevel, target);
+            }
+        }
+    }
+
+    private Dictionary<string, float> GetLocks()
+    {
+        var locks = new Dictionary<string, float>(StringComparer.OrdinalIgnoreCase);
+        foreach (var entry in _preferences.GetStringList(VolumeLocksPreference))
+        {
+            var separator = entry.LastIndexOf('=');
+            if (separator <= 0) continue;
+
+            if (float.TryParse(entry[(separator + 1)..], NumberStyles.Float, CultureInfo.InvariantCulture, out var target))
+            {
+                locks[entry[..separator]] = target;
+            }
+        }
+
+        return locks;
+    }
+
+    private void OnMicrophoneVolumeChanged(object? sender, AudioDeviceService.MicrophoneVolumeChangedEventArgs e)
+    {
+        if (_disposed) return;
+
+        var locks = GetLocks();
+        if (locks.Count == 0) return;
+
+        var microphone = _audioService.GetMicrophone(e.DeviceId);
+        if (microphone == null) return;
+
+        var key = DevicePriorityService.KeyFor(microphone);
+        if (!locks.TryGetValue(key, out var target)) return;
+
+        if (e.EventContext == AudioDeviceService.VolumeEventContext)
+        {
+            // Our own slider: the user is choosing a new level to hold
+            if (Math.Abs(e.VolumeLevelScalar - target) > Tolerance)
+            {
+                SetLock(key, e.VolumeLevelScalar);
+            }
+            return;
+        }
+
+        Enforce(e.DeviceId, NameOf(microphone), e.VolumeLevelScalar, target);
+    }
+
+    private void OnDevicesChanged(object? sender, EventArgs e) => TryEnforceAll();
+
+    private void OnResumed(object? sender, EventArgs e) => TryEnforceAll();
+
+    private void TryEnforceAll()
+    {
+        try
+        {
+            EnforceAll();
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Volume lock enforcement failed");
+        }
+    }
+
+    private string NameOf(MicrophoneDevice device) => _aliasService?.DisplayNameFor(device) ?? device.Name;
+
+    private void Enforce(string deviceId, string deviceName, float currentLevel, float target)
+    {
+        if (Math.Abs(currentLevel - target) <= Tolerance) return;
+
+        _audioService.SetMicrophoneVolumeLevelScalar(deviceId, target);
+        VolumeReverted?.Invoke(this, new VolumeRevertedEventArgs(deviceId, deviceName, currentLevel, target));
+    }
+
+    public void Dispose()
+    {
+        if (_disposed) return;
+        _disposed = true;
+
+        try { _audioService.MicrophoneVolumeChanged -= OnMicrophoneVolumeChanged; } catch { }
+        try { _audioService.DevicesChanged -= OnDevicesChanged; } catch { }
+        if (_powerEvents != null)
+        {
+            try { _powerEvents.Resumed -= OnResumed; } catch { }
+        }
+    }
+
+    public sealed class VolumeRevertedEventArgs : EventArgs
+    {
+        public VolumeRevertedEventArgs(string deviceId, string deviceName, float attemptedLevel, float targetLevel)
+        {
+            DeviceId = deviceId;
+            DeviceName = deviceName;
+            AttemptedLevel = attemptedLevel;
+            TargetLevel = targetLevel;
+        }
+
+        public string DeviceId { get; }
+        public string DeviceName { get; }
+        public float AttemptedLevel { get; }
+        public float TargetLevel { get; }
+    }
+}
@@ -0,0 +1,91 @@
+using System.Runtime.InteropServices;
+
+namespace MicrophoneManager.WinUI.Services;
+
+/// <summary>
+/// Subclasses the hidden tray window so services can see Win32 messages (hotkeys, session and
+/// power notifications) that WinUI doesn't surface.
+/// </summary>
+public sealed class WindowMessageMonitor : IDisposable
+{
+    private const nuint SubclassId = 0x4D4D4D4D; // "MMMM"
+
+    private SubclassProc? _subclassProc;
+    private bool _disposed;
+
+    /// <summary>
+    /// Window being monitored, or zero before <see cref="Attach"/>.
+    /// </summary>
+    public IntPtr Handle { get; private set; }
+
+    /// <summary>
+    /// Raised on the window's thread for every message; set <see cref="WindowMessageEventArgs.Handled"/>
+    /// to stop default processing.
+    /// </summary>
+    public event EventHandler<WindowMessageEventArgs>? MessageReceived;
+
+    public void Attach(IntPtr hwnd)
+    {
+        if (_disposed || hwnd == IntPtr.Zero || Handle != IntPtr.Zero) return;
+
+        _subclassProc = WindowSubclassProc;
+        if (!SetWindowSubclass(hwnd, _subclassProc, SubclassId, 0))
+        {
+            _subclassProc = null;
+            return;
+        }
+
+        Handle = hwnd;
+    }
+
+    private IntPtr WindowSubclassProc(IntPtr hwnd, uint msg, IntPtr wParam, IntPtr lParam, nuint idSubclass, nuint refData)
+    {
+        var handler = MessageReceived;
+        if (handler != null)
+        {
+            var args = new WindowMessageEventArgs(msg, wParam, lParam);
+            handler(this, args);
+            if (args.Handled) return args.Result;
+        }
+
+        return DefSubclassProc(hwnd, msg, wParam, lParam);
+    }
+
+    public void Dispose()
+    {
+        if (_disposed) return;
+        _disposed = true;
+
+        if (Handle == IntPtr.Zero || _subclassProc == null) return;
+
+        try { RemoveWindowSubclass(Handle, _subclassProc, SubclassId); } catch { }
+        Handle = IntPtr.Zero;
+    }
+
+    public class WindowMessageEventArgs : EventArgs
+    {
+        public WindowMessageEventArgs(uint message, IntPtr wParam, IntPtr lParam)
+        {
+            Message = message;
+            WParam = wParam;
+            LParam = lParam;
+        }
+
+        public uint Message { get; }
+        public IntPtr WParam { get; }
+        public IntPtr LParam { get; }
+        public bool Handled { get; set; }
+        public IntPtr Result { get; set; }
+    }
+
+    private delegate IntPtr SubclassProc(IntPtr hwnd, uint msg, IntPtr wParam, IntPtr lParam, nuint idSubclass, nuint refData);
+
+    [DllImport("comctl32.dll", SetLastError = true)]
+    private static extern bool SetWindowSubclass(IntPtr hWnd, SubclassProc pfnSubclass, nuint uIdSubclass, nuint dwRefData);
+
+    [DllImport("comctl32.dll", SetLastError = true)]
+    private static extern bool RemoveWindowSubclass(IntPtr hWnd, SubclassProc pfnSubclass, nuint uIdSubclass);
+
+    [DllImport("comctl32.dll")]
+    private static extern IntPtr DefSubclassProc(IntPtr hWnd, uint uMsg, IntPtr wParam, IntPtr lParam);
+}
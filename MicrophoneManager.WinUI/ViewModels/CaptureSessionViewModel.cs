@@ -0,0 +1,151 @@
+using CommunityToolkit.Mvvm.ComponentModel;
+using CommunityToolkit.Mvvm.Input;
+using MicrophoneManager.WinUI.Models;
+using MicrophoneManager.WinUI.Services;
+using Microsoft.UI.Dispatching;
+using Microsoft.UI.Xaml.Media;
+using Microsoft.UI.Xaml.Media.Imaging;
+using Windows.Storage.Streams;
+
+namespace MicrophoneManager.WinUI.ViewModels;
+
+/// <summary>
+/// One application's capture session on a microphone, with its own mute and volume.
+/// </summary>
+public partial class CaptureSessionViewModel : ObservableObject
+{
+    private readonly IAudioDeviceService _audioService;
+    private readonly DispatcherQueue? _dispatcherQueue;
+    private bool _suppressVolumeWrite;
+    private int _iconLoadVersion;
+    private InMemoryRandomAccessStream? _iconStream;
+
+    public CaptureSessionViewModel(CaptureSession session, IAudioDeviceService audioService)
+    {
+        _audioService = audioService;
+        _dispatcherQueue = DispatcherQueue.GetForCurrentThread();
+        SessionInstanceId = session.SessionInstanceId;
+        DeviceId = session.DeviceId;
+        UpdateFrom(session);
+    }
+
+    public string SessionInstanceId { get; }
+
+    public string DeviceId { get; }
+
+    [ObservableProperty]
+    private string _label = string.Empty;
+
+    [ObservableProperty]
+    private string? _executablePath;
+
+    /// <summary>
+    /// The executable's icon, or null while it loads or when it has none.
+    /// </summary>
+    [ObservableProperty]
+    private ImageSource? _icon;
+
+    [ObservableProperty]
+    private bool _isActive;
+
+    [ObservableProperty]
+    private bool _isMuted;
+
+    [ObservableProperty]
+    private double _volumePercent;
+
+    public void UpdateFrom(CaptureSession session)
+    {
+        Label = session.Label;
+        ExecutablePath = session.ExecutablePath;
+        IsActive = session.IsActive;
+        IsMuted = session.IsMuted;
+
+        _suppressVolumeWrite = true;
+        try
+        {
+            VolumePercent = Math.Round(session.Volume * 100.0, 2);
+        }
+        finally
+        {
+            _suppressVolumeWrite = false;
+        }
+    }
+
+    [RelayCommand]
+    private void ToggleMute()
+    {
+        var target = !IsMuted;
+        if (_audioService.SetCaptureSessionMute(DeviceId, SessionInstanceId, target))
+        {
+            IsMuted = target;
+        }
+    }
+
+    partial void OnExecutablePathChanged(string? value)
+    {
+        _ = LoadIconAsync(value);
+    }
+
+    private async Task LoadIconAsync(string? path)
+    {
+        var version = ++_iconLoadVersion;
+        Icon = null;
+        ReleaseIconStream();
+
+        // Images can only be created on a UI thread; unit tests don't have one
+        if (_dispatcherQueue == null || string.IsNullOrEmpty(path)) return;
+
+        try
+        {
+            var png = await Task.Run(() => ProcessInfoResolver.GetIconPng(path));
+            if (png == null || version != _iconLoadVersion) return;
+
+            var stream = new InMemoryRandomAccessStream();
+            using (var writer = new DataWriter(stream.GetOutputStreamAt(0)))
+            {
+                writer.WriteBytes(png);
+                await writer.StoreAsync();
+            }
+
+            if (version != _iconLoadVersion)
+            {
+                stream.Dispose();
+                return;
+            }
+
+            // The stream has to outlive SetSourceAsync: decoding can finish later, so it is
+            // released once the image has opened or failed, or when a newer icon replaces it
+            var image = new BitmapImage();
+            _iconStream = stream;
+            image.ImageOpened += (_, _) => ReleaseIconStream(stream);
+            image.ImageFailed += (_, _) => ReleaseIconStream(stream);
+            await image.SetSourceAsync(stream);
+
+            if (version == _iconLoadVersion)
+            {
+                Icon = image;
+            }
+        }
+        catch (Exception ex)
+        {
+            System.Diagnostics.Debug.WriteLine($"Failed to load icon for {path}: {ex.Message}");
+        }
+    }
+
+    private void ReleaseIconStream(InMemoryRandomAccessStream? stream = null)
+    {
+        var current = _iconStream;
+        if (current == null || (stream != null && !ReferenceEquals(stream, current))) return;
+
+        _iconStream = null;
+        current.Dispose();
+    }
+
+    partial void OnVolumePercentChanged(double value)
+    {
+        if (_suppressVolumeWrite) return;
+        var clamped = Math.Max(0.0, Math.Min(100.0, value));
+        _audioService.SetCaptureSessionVolume(DeviceId, SessionInstanceId, (float)(clamped / 100.0));
+    }
+}
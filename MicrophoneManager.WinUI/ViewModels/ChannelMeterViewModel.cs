@@ -0,0 +1,50 @@
+using CommunityToolkit.Mvvm.ComponentModel;
+using MicrophoneManager.WinUI.Services;
+
+namespace MicrophoneManager.WinUI.ViewModels;
+
+/// <summary>
+/// The meter of one channel of a multi-channel microphone, so a stereo device whose left or
+/// right side is dead is easy to spot.
+/// </summary>
+public partial class ChannelMeterViewModel : ObservableObject
+{
+    private MeterBallistics _ballistics;
+
+    public ChannelMeterViewModel(string label, double attackMs, double decayMs)
+    {
+        Label = label;
+        _ballistics = new MeterBallistics(attackMs, decayMs);
+    }
+
+    /// <summary>
+    /// "L" or "R" for stereo, otherwise the channel's number.
+    /// </summary>
+    public string Label { get; }
+
+    [ObservableProperty]
+    [NotifyPropertyChangedFor(nameof(VisiblePercent))]
+    private double _levelDbFs = -96.0;
+
+    /// <summary>
+    /// How far along the bar the level reaches, on the same -60..0 dB scale as the main meter.
+    /// </summary>
+    public double VisiblePercent => ObsMeterMath.ToVisiblePercent(ObsMeterMath.DbToPercent(LevelDbFs));
+
+    public void Configure(double attackMs, double decayMs)
+    {
+        if (_ballistics.AttackMs == attackMs && _ballistics.DecayMs == decayMs) return;
+        _ballistics = new MeterBallistics(attackMs, decayMs);
+    }
+
+    public void Update(double peakDbFs, double elapsedMs)
+    {
+        LevelDbFs = _ballistics.Update(peakDbFs, elapsedMs);
+    }
+
+    public static string GetLabel(int channel, int channelCount)
+    {
+        if (channelCount == 2) return channel == 0 ? "L" : "R";
+        return (channel + 1).ToString(System.Globalization.CultureInfo.InvariantCulture);
+    }
+}
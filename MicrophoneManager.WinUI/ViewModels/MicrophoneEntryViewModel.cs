@@ -1,3 +1,4 @@
+using System.Collections.ObjectModel;
 using CommunityToolkit.Mvvm.ComponentModel;
 using CommunityToolkit.Mvvm.Input;
 using MicrophoneManager.WinUI.Models;
@@ -9,34 +10,121 @@ public partial class MicrophoneEntryViewModel : ObservableObject
 {
     private readonly IAudioDeviceService _audioService;
     private readonly Action<string>? _onError;
+    private readonly UndoService? _undoService;
     private bool _suppressVolumeWrite;
+    private VolumeRange? _volumeRange;
+    private bool _suppressBoostWrite;
+    private bool _suppressAutoGainWrite;
     private DateTime _peakHoldUntilUtc;
     private DateTime _lastPeakTickUtc;
     private DateTime _lastMeterUpdateUtc;
+    private DateTime _lastChannelUpdateUtc;
+    private int _historyVersion = -1;
 
     private double _peakDbFs = -96.0;
-    private double _smoothedDbFs = -96.0;
+    private MeterBallistics _peakBallistics = new(MeterSettingsService.DefaultAttackMs, MeterSettingsService.DefaultDecayMs);
+    private MeterBallistics _rmsBallistics = new(MeterSettingsService.DefaultAttackMs, MeterSettingsService.DefaultDecayMs);
+
+    private static readonly TimeSpan MicTestDuration = TimeSpan.FromSeconds(5);
+    private const int MicTestTickMilliseconds = 100;
+    private DateTime _micTestRecordingStartedUtc;
 
     private const int PeakHoldMilliseconds = 5000;
     private const double PeakDecayDbPerSecond = 20.0;
 
-    // OBS-style ballistics: instant attack, exponential release (~300ms time constant).
-    private const double MeterReleaseTimeMs = 300.0;
-
-    public MicrophoneEntryViewModel(MicrophoneDevice device, IAudioDeviceService audioService, Action<string>? onError = null)
+    public MicrophoneEntryViewModel(MicrophoneDevice device, IAudioDeviceService audioService, Action<string>? onError = null, UndoService? undoService = null)
     {
         _audioService = audioService;
         _onError = onError;
+        _undoService = undoService;
         _lastPeakTickUtc = DateTime.UtcNow;
         _lastMeterUpdateUtc = DateTime.UtcNow;
-        UpdateFrom(device);
+        _lastChannelUpdateUtc = _lastMeterUpdateUtc;
+        var properties = audioService.GetDeviceProperties(device.Id);
+        UpdateFrom(device, properties);
+        GroupKey = DeviceGroupService.KeyFor(properties?.ContainerId ?? device.ContainerId);
+        AdapterName = properties?.AdapterName;
+        EndpointName = string.IsNullOrWhiteSpace(properties?.EndpointDescription) ? device.Name : properties.EndpointDescription;
+        IsListening = audioService.GetListenSettings(device.Id)?.IsEnabled ?? false;
     }
 
     public string Id { get; private set; } = string.Empty;
 
+    /// <summary>
+    /// Key the device is stored under in the preferred-microphone list.
+    /// </summary>
+    public string PreferenceKey { get; private set; } = string.Empty;
+
+    /// <summary>
+    /// 1-based position in the preferred-microphone list, or null when the device isn't listed.
+    /// </summary>
+    [ObservableProperty]
+    [NotifyPropertyChangedFor(nameof(HasPriorityRank))]
+    [NotifyPropertyChangedFor(nameof(PriorityText))]
+    private int? _priorityRank;
+
+    public bool HasPriorityRank => PriorityRank != null;
+
+    /// <summary>
+    /// Whether the volume is held at its current level against changes from other apps.
+    /// </summary>
     [ObservableProperty]
+    private bool _isVolumeLocked;
+
+    public string PriorityText => PriorityRank is { } rank ? $"#{rank}" : string.Empty;
+
+    /// <summary>
+    /// Times the peaks reached full scale while this was the default microphone; see <see cref="ClippingService"/>.
+    /// </summary>
+    [ObservableProperty]
+    [NotifyPropertyChangedFor(nameof(HasClips))]
+    [NotifyPropertyChangedFor(nameof(ClipCountText))]
+    private int _clipCount;
+
+    public bool HasClips => ClipCount > 0;
+
+    public string ClipCountText => ClipCount == 1 ? "Clipped once" : $"Clipped {ClipCount} times";
+
+    /// <summary>
+    /// Name the driver reports; see <see cref="DisplayName"/> for what's shown.
+    /// </summary>
+    [ObservableProperty]
+    [NotifyPropertyChangedFor(nameof(DisplayName))]
+    [NotifyPropertyChangedFor(nameof(NameToolTip))]
     private string _name = string.Empty;
 
+    /// <summary>
+    /// The user's name for the device, or null when they haven't renamed it.
+    /// </summary>
+    [ObservableProperty]
+    [NotifyPropertyChangedFor(nameof(DisplayName))]
+    [NotifyPropertyChangedFor(nameof(NameToolTip))]
+    private string? _alias;
+
+    public string DisplayName => Alias ?? Name;
+
+    /// <summary>
+    /// The driver's name when the device has an alias, then the form factor / bus / adapter summary.
+    /// </summary>
+    public string NameToolTip => Alias == null
+        ? DetailsText
+        : string.IsNullOrEmpty(DetailsText) ? Name : $"{Name}\n{DetailsText}";
+
+    /// <summary>
+    /// Whether the name is being edited in place.
+    /// </summary>
+    [ObservableProperty]
+    [NotifyPropertyChangedFor(nameof(IsNotRenaming))]
+    private bool _isRenaming;
+
+    public bool IsNotRenaming => !IsRenaming;
+
+    /// <summary>
+    /// Text in the rename box while <see cref="IsRenaming"/>.
+    /// </summary>
+    [ObservableProperty]
+    private string _renameText = string.Empty;
+
     [ObservableProperty]
     private bool _isDefault;
 
@@ -49,31 +137,449 @@ public partial class MicrophoneEntryViewModel : ObservableObject
     [ObservableProperty]
     private double _volumePercent;
 
+    /// <summary>
+    /// Endpoint volume in dB, e.g. "-12.5 dB"; empty when the driver doesn't report one.
+    /// </summary>
+    [ObservableProperty]
+    private string _volumeDbText = string.Empty;
+
     [ObservableProperty]
     private string _formatTag = string.Empty;
 
+    /// <summary>
+    /// Form factor / bus / adapter summary.
+    /// </summary>
+    [ObservableProperty]
+    [NotifyPropertyChangedFor(nameof(NameToolTip))]
+    private string _detailsText = string.Empty;
+
     [ObservableProperty]
     private double _inputLevelPercent;
 
     [ObservableProperty]
+    [NotifyPropertyChangedFor(nameof(MeterLevelDbFs))]
     private double _inputLevelDbFs;
 
     [ObservableProperty]
     private double _peakLevelPercent;
 
+    /// <summary>
+    /// Smoothed RMS level, for the RMS meter modes; see <see cref="MeterMode"/>.
+    /// </summary>
+    [ObservableProperty]
+    private double _rmsLevelPercent;
+
+    [ObservableProperty]
+    [NotifyPropertyChangedFor(nameof(MeterLevelDbFs))]
+    private double _rmsLevelDbFs = -96.0;
+
+    [ObservableProperty]
+    [NotifyPropertyChangedFor(nameof(MeterLevelDbFs))]
+    private MeterMode _meterMode;
+
+    /// <summary>
+    /// The level the meter's solid bar and readout show: the peak, or the RMS in the RMS modes.
+    /// </summary>
+    public double MeterLevelDbFs => MeterMode == MeterMode.Peak ? InputLevelDbFs : RmsLevelDbFs;
+
+    /// <summary>
+    /// One meter per channel for a multi-channel microphone; empty for a mono one.
+    /// </summary>
+    public ObservableCollection<ChannelMeterViewModel> ChannelMeters { get; } = new();
+
+    public bool HasChannelMeters => ChannelMeters.Count > 0;
+
+    /// <summary>
+    /// The per-channel bars replace the single bar in the detailed row; compact rows keep the single bar.
+    /// </summary>
+    public bool IsChannelMetersShown => HasChannelMeters && IsDetailed;
+
+    public bool IsMonoMeterShown => !IsChannelMetersShown;
+
+    /// <summary>
+    /// The dB scale lines up with the single bar only; the channel bars are narrower for their labels.
+    /// </summary>
+    public bool IsMeterAxisShown => IsDetailed && !HasChannelMeters;
+
+    /// <summary>
+    /// The level history setting is on; see <see cref="MeterSettingsService.ShowHistory"/>.
+    /// </summary>
+    [ObservableProperty]
+    [NotifyPropertyChangedFor(nameof(IsHistoryShown))]
+    private bool _isHistoryEnabled;
+
+    public bool IsHistoryShown => IsHistoryEnabled && IsDetailed;
+
+    /// <summary>
+    /// The last 30 seconds of peaks in dBFS, oldest first, for the sparkline under the meter.
+    /// </summary>
+    [ObservableProperty]
+    private double[] _historyDbFs = Array.Empty<double>();
+
+    [ObservableProperty]
+    [NotifyPropertyChangedFor(nameof(IsActive))]
+    [NotifyPropertyChangedFor(nameof(IsInactive))]
+    [NotifyPropertyChangedFor(nameof(StateText))]
+    [NotifyPropertyChangedFor(nameof(CardOpacity))]
+    [NotifyPropertyChangedFor(nameof(EnableToggleText))]
+    private NAudio.CoreAudioApi.DeviceState _state = NAudio.CoreAudioApi.DeviceState.Active;
+
+    public bool IsActive => State == NAudio.CoreAudioApi.DeviceState.Active;
+
+    public bool IsInactive => !IsActive;
+
+    public string StateText => State switch
+    {
+        NAudio.CoreAudioApi.DeviceState.Disabled => "Disabled",
+        NAudio.CoreAudioApi.DeviceState.Unplugged => "Unplugged",
+        NAudio.CoreAudioApi.DeviceState.NotPresent => "Not present",
+        _ => string.Empty
+    };
+
+    public double CardOpacity => IsActive && !IsHidden ? 1.0 : 0.5;
+
+    /// <summary>
+    /// Hidden from the list by the user; only listed while hidden devices are shown.
+    /// </summary>
+    [ObservableProperty]
+    [NotifyPropertyChangedFor(nameof(CardOpacity))]
+    [NotifyPropertyChangedFor(nameof(HideToggleText))]
+    private bool _isHidden;
+
+    public string HideToggleText => IsHidden ? "Unhide" : "Hide from list";
+
+    /// <summary>
+    /// Pinned to the top of the list.
+    /// </summary>
+    [ObservableProperty]
+    [NotifyPropertyChangedFor(nameof(PinToggleText))]
+    private bool _isPinned;
+
+    public string PinToggleText => IsPinned ? "Unpin" : "Pin to top";
+
+    /// <summary>
+    /// The physical device this endpoint belongs to, or null when the driver doesn't say; see
+    /// <see cref="DeviceGroupService.KeyFor"/>.
+    /// </summary>
+    public string? GroupKey { get; }
+
+    /// <summary>
+    /// The physical device's name, e.g. "Elgato Wave", for the group header.
+    /// </summary>
+    public string? AdapterName { get; }
+
+    /// <summary>
+    /// The endpoint's name within its device, e.g. "Monitor Mix", for the group header.
+    /// </summary>
+    public string EndpointName { get; }
+
+    /// <summary>
+    /// Shows just the name, mute button and a small meter; see <see cref="AppearanceService.IsCompact"/>.
+    /// </summary>
+    [ObservableProperty]
+    [NotifyPropertyChangedFor(nameof(IsDetailed))]
+    [NotifyPropertyChangedFor(nameof(IsBoostShown))]
+    [NotifyPropertyChangedFor(nameof(IsAutoGainShown))]
+    [NotifyPropertyChangedFor(nameof(IsChannelMetersShown))]
+    [NotifyPropertyChangedFor(nameof(IsMonoMeterShown))]
+    [NotifyPropertyChangedFor(nameof(IsMeterAxisShown))]
+    [NotifyPropertyChangedFor(nameof(IsHistoryShown))]
+    private bool _isCompact;
+
+    public bool IsDetailed => !IsCompact;
+
+    /// <summary>
+    /// Header shown above this entry when it's the first of a group, or null.
+    /// </summary>
+    [ObservableProperty]
+    [NotifyPropertyChangedFor(nameof(IsGroupStart))]
+    private string? _groupTitle;
+
+    public bool IsGroupStart => GroupTitle != null;
+
+    /// <summary>
+    /// This entry's group header is collapsed; only meaningful when <see cref="IsGroupStart"/>.
+    /// </summary>
+    [ObservableProperty]
+    [NotifyPropertyChangedFor(nameof(GroupChevronGlyph))]
+    private bool _isGroupCollapsed;
+
+    // ChevronRight / ChevronDown
+    public string GroupChevronGlyph => IsGroupCollapsed ? "\uE76C" : "\uE70D";
+
+    /// <summary>
+    /// The entry belongs to a collapsed group, so only the header is shown.
+    /// </summary>
+    [ObservableProperty]
+    [NotifyPropertyChangedFor(nameof(IsCardShown))]
+    private bool _isInCollapsedGroup;
+
+    public bool IsCardShown => !IsInCollapsedGroup;
+
+    /// <summary>
+    /// Every endpoint in this entry's group is muted; drives the group mute button.
+    /// </summary>
+    [ObservableProperty]
+    [NotifyPropertyChangedFor(nameof(GroupMuteToolTip))]
+    private bool _isGroupMuted;
+
+    public string GroupMuteToolTip => IsGroupMuted ? "Unmute all endpoints of this device" : "Mute all endpoints of this device";
+
+    public string EnableToggleText => State == NAudio.CoreAudioApi.DeviceState.Disabled ? "Enable device" : "Disable device";
+
+    [ObservableProperty]
+    [NotifyPropertyChangedFor(nameof(MicTestText))]
+    [NotifyPropertyChangedFor(nameof(IsMicTestVisible))]
+    [NotifyPropertyChangedFor(nameof(IsMicTestRunning))]
+    [NotifyPropertyChangedFor(nameof(IsMicTestPanelShown))]
+    [NotifyPropertyChangedFor(nameof(CanReplayMicTest))]
+    private MicTestStatus _micTestStatus = MicTestStatus.Idle;
+
+    /// <summary>
+    /// Whole seconds of the test recording still to go, for the countdown.
+    /// </summary>
+    [ObservableProperty]
+    [NotifyPropertyChangedFor(nameof(MicTestText))]
+    private int _micTestSecondsLeft;
+
+    /// <summary>
+    /// Peaks (0..1) of the test recording per <see cref="MicrophoneManager.WinUI.Services.MicTestWaveform.SlotLength"/>, filling in as it records.
+    /// </summary>
+    [ObservableProperty]
+    [NotifyPropertyChangedFor(nameof(IsMicTestPanelShown))]
+    private double[] _micTestWaveform = Array.Empty<double>();
+
+    /// <summary>
+    /// How many waveform slots a whole test recording has, so the waveform fills in from the left.
+    /// </summary>
+    public static int MicTestWaveformSlots => (int)Math.Ceiling(MicTestDuration / MicrophoneManager.WinUI.Services.MicTestWaveform.SlotLength);
+
+    public bool IsMicTestRunning => MicTestStatus is MicTestStatus.Recording or MicTestStatus.PlayingBack;
+
+    public bool IsMicTestPanelShown => MicTestStatus != MicTestStatus.Idle && MicTestWaveform.Length > 0;
+
+    public bool CanReplayMicTest => MicTestStatus == MicTestStatus.Completed;
+
+    public string MicTestText => MicTestStatus switch
+    {
+        MicTestStatus.Recording => $"Recording... speak now ({MicTestSecondsLeft} s)",
+        MicTestStatus.PlayingBack => "Playing back...",
+        MicTestStatus.Completed => "Test complete",
+        MicTestStatus.Failed => "Mic test failed",
+        _ => string.Empty
+    };
+
+    public bool IsMicTestVisible => MicTestStatus != MicTestStatus.Idle;
+
+    [ObservableProperty]
+    [NotifyPropertyChangedFor(nameof(RecordingToggleText))]
+    private bool _isRecording;
+
+    [ObservableProperty]
+    [NotifyPropertyChangedFor(nameof(HasRecordingText))]
+    private string _recordingText = string.Empty;
+
+    public bool HasRecordingText => !string.IsNullOrEmpty(RecordingText);
+
+    public string RecordingToggleText => IsRecording ? "Stop recording" : "Record to file";
+
+    [ObservableProperty]
+    [NotifyPropertyChangedFor(nameof(ListenToggleText))]
+    private bool _isListening;
+
+    public string ListenToggleText => IsListening ? "Stop listening to this device" : "Listen to this device";
+
+    [ObservableProperty]
+    [NotifyPropertyChangedFor(nameof(IsBoostShown))]
+    private bool _hasBoost;
+
+    public bool IsBoostShown => HasBoost && IsDetailed;
+
+    [ObservableProperty]
+    [NotifyPropertyChangedFor(nameof(BoostText))]
+    private double _boostDb;
+
+    [ObservableProperty]
+    private double _boostMinDb;
+
+    [ObservableProperty]
+    private double _boostMaxDb;
+
+    [ObservableProperty]
+    private double _boostStepDb = 1.0;
+
+    public string BoostText => $"Boost +{BoostDb:0.#} dB";
+
+    [ObservableProperty]
+    [NotifyPropertyChangedFor(nameof(IsAutoGainShown))]
+    private bool _hasAutoGain;
+
+    public bool IsAutoGainShown => HasAutoGain && IsDetailed;
+
+    [ObservableProperty]
+    private bool _isAutoGainEnabled;
+
+    [ObservableProperty]
+    [NotifyPropertyChangedFor(nameof(HasActiveSessions))]
+    [NotifyPropertyChangedFor(nameof(ExclusiveLockText))]
+    private string _activeSessionsText = string.Empty;
+
+    public bool HasActiveSessions => !string.IsNullOrEmpty(ActiveSessionsText);
+
+    /// <summary>
+    /// Another application holds the device in exclusive mode, so the meter reads silence.
+    /// </summary>
+    [ObservableProperty]
+    [NotifyPropertyChangedFor(nameof(ExclusiveLockText))]
+    private bool _isExclusivelyLocked;
+
+    public string ExclusiveLockText => !IsExclusivelyLocked
+        ? string.Empty
+        : string.IsNullOrEmpty(_activeSessionNames)
+            ? "Locked in exclusive mode by another application"
+            : $"Locked in exclusive mode ({_activeSessionNames})";
+
+    private string _activeSessionNames = string.Empty;
+
+    /// <summary>
+    /// An application is capturing from this device; drives the "in use" dot next to the name.
+    /// </summary>
+    [ObservableProperty]
+    private bool _isInUse;
+
+    public ObservableCollection<CaptureSessionViewModel> Sessions { get; } = new();
+
+    public bool HasSessions => Sessions.Count > 0;
+
     public void UpdateFrom(MicrophoneDevice device)
+    {
+        UpdateFrom(device, _audioService.GetDeviceProperties(device.Id));
+    }
+
+    private void UpdateFrom(MicrophoneDevice device, DeviceProperties? properties)
     {
         Id = device.Id;
+        PreferenceKey = DevicePriorityService.KeyFor(device);
         Name = device.Name;
+        State = device.State;
         IsDefault = device.IsDefault;
         IsDefaultCommunication = device.IsDefaultCommunication;
         IsMuted = device.IsMuted;
+        _volumeRange = device.VolumeRange;
         ApplyVolumeFromSystem(Math.Round(device.VolumeLevel * 100.0, 2));
         FormatTag = device.FormatTag;
+        ApplyBoostFromSystem(device.Boost);
+        ApplyAutoGainFromSystem(device.AutoGainEnabled);
+        IsInUse = device.IsInUse;
         UpdateMeter(device.InputLevelPercent);
+
+        // The form factor, bus or adapter can change under the same ID, e.g. after a driver update
+        DetailsText = properties?.Summary ?? string.Empty;
     }
 
-    public void UpdateMeter(double inputPercent)
+    private void ApplyBoostFromSystem(MicrophoneBoost? boost)
+    {
+        HasBoost = boost != null;
+        if (boost == null) return;
+
+        _suppressBoostWrite = true;
+        try
+        {
+            BoostMinDb = boost.MinDb;
+            BoostMaxDb = boost.MaxDb;
+            BoostStepDb = boost.StepDb > 0 ? boost.StepDb : 1.0;
+            BoostDb = boost.LevelDb;
+        }
+        finally
+        {
+            _suppressBoostWrite = false;
+        }
+    }
+
+    private void ApplyAutoGainFromSystem(bool? autoGainEnabled)
+    {
+        HasAutoGain = autoGainEnabled.HasValue;
+        if (autoGainEnabled is not bool enabled) return;
+
+        _suppressAutoGainWrite = true;
+        try
+        {
+            IsAutoGainEnabled = enabled;
+        }
+        finally
+        {
+            _suppressAutoGainWrite = false;
+        }
+    }
+
+    public void UpdateSessions(IReadOnlyList<CaptureSession> sessions)
+    {
+        var names = sessions
+            .Where(s => s.IsActive)
+            .Select(s => s.Label)
+            .Where(n => !string.IsNullOrEmpty(n))
+            .Distinct(StringComparer.OrdinalIgnoreCase)
+            .ToList();
+
+        _activeSessionNames = string.Join(", ", names);
+        ActiveSessionsText = names.Count == 0 ? string.Empty : $"In use by {_activeSessionNames}";
+        IsInUse = sessions.Any(s => s.IsActive);
+
+        SyncSessions(sessions);
+    }
+
+    private void SyncSessions(IReadOnlyList<CaptureSession> sessions)
+    {
+        var hadSessions = HasSessions;
+        var incomingIds = new HashSet<string>(sessions.Select(s => s.SessionInstanceId));
+
+        for (var i = Sessions.Count - 1; i >= 0; i--)
+        {
+            if (!incomingIds.Contains(Sessions[i].SessionInstanceId))
+            {
+                Sessions.RemoveAt(i);
+            }
+        }
+
+        foreach (var session in sessions)
+        {
+            var existing = Sessions.FirstOrDefault(s => s.SessionInstanceId == session.SessionInstanceId);
+            if (existing != null)
+            {
+                existing.UpdateFrom(session);
+            }
+            else
+            {
+                Sessions.Add(new CaptureSessionViewModel(session, _audioService));
+            }
+        }
+
+        if (hadSessions != HasSessions)
+        {
+            OnPropertyChanged(nameof(HasSessions));
+        }
+    }
+
+    /// <summary>
+    /// Switches what the meter shows and how fast it moves; see <see cref="MeterSettingsService"/>.
+    /// </summary>
+    public void ConfigureMeter(MeterMode mode, double attackMs, double decayMs)
+    {
+        MeterMode = mode;
+        foreach (var channel in ChannelMeters)
+        {
+            channel.Configure(attackMs, decayMs);
+        }
+
+        if (_peakBallistics.AttackMs == attackMs && _peakBallistics.DecayMs == decayMs) return;
+
+        _peakBallistics = new MeterBallistics(attackMs, decayMs);
+        _rmsBallistics = new MeterBallistics(attackMs, decayMs);
+    }
+
+    /// <param name="inputPercent">Peak level as OBS meter deflection (0..100).</param>
+    /// <param name="rmsDbFs">RMS level in dBFS; the peak stands in when the source has no RMS.</param>
+    public void UpdateMeter(double inputPercent, double? rmsDbFs = null)
     {
         var clamped = Math.Max(0, Math.Min(100.0, inputPercent));
         var nowUtc = DateTime.UtcNow;
@@ -83,20 +589,14 @@ public partial class MicrophoneEntryViewModel : ObservableObject
         // Interpret UI percent as OBS deflection (0..100) and run peak-hold/decay in dB.
         var inputDbFs = MicrophoneManager.WinUI.Services.ObsMeterMath.PercentToDb(clamped);
 
-        // OBS-style ballistics: instant attack, exponential release.
-        if (inputDbFs >= _smoothedDbFs)
-        {
-            _smoothedDbFs = inputDbFs;
-        }
-        else if (dtMs > 0)
-        {
-            var alpha = 1.0 - Math.Exp(-dtMs / MeterReleaseTimeMs);
-            _smoothedDbFs += (inputDbFs - _smoothedDbFs) * alpha;
-        }
+        var smoothedDbFs = _peakBallistics.Update(inputDbFs, dtMs);
+        InputLevelPercent = MicrophoneManager.WinUI.Services.ObsMeterMath.DbToPercent(smoothedDbFs);
+        InputLevelDbFs = smoothedDbFs;
 
-        var smoothedPercent = MicrophoneManager.WinUI.Services.ObsMeterMath.DbToPercent(_smoothedDbFs);
-        InputLevelPercent = smoothedPercent;
-        InputLevelDbFs = MicrophoneManager.WinUI.Services.ObsMeterMath.ClampMeterDb(_smoothedDbFs);
+        // RMS never exceeds the peak of the same samples
+        var smoothedRmsDbFs = _rmsBallistics.Update(Math.Min(rmsDbFs ?? inputDbFs, inputDbFs), dtMs);
+        RmsLevelPercent = MicrophoneManager.WinUI.Services.ObsMeterMath.DbToPercent(smoothedRmsDbFs);
+        RmsLevelDbFs = smoothedRmsDbFs;
 
         // Peak hold tracks the RAW input (not smoothed) so transients register.
         if (inputDbFs >= _peakDbFs)
@@ -109,6 +609,56 @@ public partial class MicrophoneEntryViewModel : ObservableObject
         TickPeak(nowUtc);
     }
 
+    /// <summary>
+    /// Moves each channel's meter towards its latest peak. A different number of channels than
+    /// before rebuilds the meters; null or a single channel leaves just the main meter.
+    /// </summary>
+    /// <param name="channelPeaksDbFs">Endpoint peak of each channel in dBFS.</param>
+    public void UpdateChannelMeters(IReadOnlyList<double>? channelPeaksDbFs)
+    {
+        var nowUtc = DateTime.UtcNow;
+        var dtMs = (nowUtc - _lastChannelUpdateUtc).TotalMilliseconds;
+        _lastChannelUpdateUtc = nowUtc;
+
+        var count = channelPeaksDbFs is { Count: > 1 } ? channelPeaksDbFs.Count : 0;
+        if (count != ChannelMeters.Count)
+        {
+            var hadChannelMeters = HasChannelMeters;
+            ChannelMeters.Clear();
+            for (var i = 0; i < count; i++)
+            {
+                ChannelMeters.Add(new ChannelMeterViewModel(ChannelMeterViewModel.GetLabel(i, count), _peakBallistics.AttackMs, _peakBallistics.DecayMs));
+            }
+
+            if (hadChannelMeters != HasChannelMeters)
+            {
+                OnPropertyChanged(nameof(HasChannelMeters));
+                OnPropertyChanged(nameof(IsChannelMetersShown));
+                OnPropertyChanged(nameof(IsMonoMeterShown));
+                OnPropertyChanged(nameof(IsMeterAxisShown));
+            }
+        }
+
+        for (var i = 0; i < count; i++)
+        {
+            ChannelMeters[i].Update(channelPeaksDbFs![i], dtMs);
+        }
+    }
+
+    /// <summary>
+    /// Takes a copy of the history when it has moved on and the sparkline is showing.
+    /// </summary>
+    public void UpdateHistory(LevelHistory? history)
+    {
+        if (history == null || !IsHistoryShown) return;
+
+        var version = history.Version;
+        if (version == _historyVersion) return;
+
+        _historyVersion = version;
+        HistoryDbFs = history.ToArray();
+    }
+
     public void TickPeak(DateTime nowUtc)
     {
         var dt = nowUtc - _lastPeakTickUtc;
@@ -142,6 +692,8 @@ public partial class MicrophoneEntryViewModel : ObservableObject
         {
             _suppressVolumeWrite = false;
         }
+
+        RefreshVolumeDb();
     }
 
     [ObservableProperty]
@@ -155,6 +707,7 @@ public partial class MicrophoneEntryViewModel : ObservableObject
         try
         {
             IsChangingDevice = true;
+            _undoService?.RecordDefaults();
             var success = await _audioService.SetMicrophoneForRoleAsync(Id, NAudio.CoreAudioApi.Role.Console, CancellationToken.None);
             if (!success)
             {
@@ -180,6 +733,7 @@ public partial class MicrophoneEntryViewModel : ObservableObject
         try
         {
             IsChangingDevice = true;
+            _undoService?.RecordDefaults();
             var success = await _audioService.SetMicrophoneForRoleAsync(Id, NAudio.CoreAudioApi.Role.Communications, CancellationToken.None);
             if (!success)
             {
@@ -205,6 +759,7 @@ public partial class MicrophoneEntryViewModel : ObservableObject
         try
         {
             IsChangingDevice = true;
+            _undoService?.RecordDefaults();
             var success = await _audioService.SetDefaultMicrophoneAsync(Id, CancellationToken.None);
             if (!success)
             {
@@ -230,6 +785,7 @@ public partial class MicrophoneEntryViewModel : ObservableObject
         try
         {
             IsChangingDevice = true;
+            _undoService?.RecordDevice(Id, "mute change");
             IsMuted = await _audioService.ToggleMuteAsync(Id, CancellationToken.None);
         }
         catch (Exception ex)
@@ -243,10 +799,264 @@ public partial class MicrophoneEntryViewModel : ObservableObject
         }
     }
 
+    [RelayCommand]
+    private async Task TestMicrophoneAsync()
+    {
+        if (IsMicTestRunning) return;
+
+        MicTestWaveform = Array.Empty<double>();
+
+        // The service reports from its own awaits, which resume on the caller's (UI) context.
+        var progress = new StatusReporter(status => MicTestStatus = status);
+        var result = await FollowMicTestAsync(_audioService.RunMicTestAsync(Id, MicTestDuration, progress, CancellationToken.None));
+        MicTestStatus = result;
+        if (result == MicTestStatus.Failed)
+        {
+            _onError?.Invoke("Mic test failed");
+        }
+    }
+
+    /// <summary>
+    /// Plays the last test recording again, keeping its waveform on screen.
+    /// </summary>
+    [RelayCommand]
+    private async Task ReplayMicTestAsync()
+    {
+        if (IsMicTestRunning) return;
+
+        var progress = new StatusReporter(status => MicTestStatus = status);
+        var result = await _audioService.ReplayMicTestAsync(Id, progress, CancellationToken.None);
+        MicTestStatus = result;
+        if (result == MicTestStatus.Failed)
+        {
+            _onError?.Invoke("Couldn't play the test recording again");
+        }
+    }
+
+    /// <summary>
+    /// Hides the test's status and waveform once the user is done with them.
+    /// </summary>
+    [RelayCommand]
+    private void CloseMicTest()
+    {
+        if (IsMicTestRunning) return;
+
+        MicTestStatus = MicTestStatus.Idle;
+        MicTestWaveform = Array.Empty<double>();
+    }
+
+    /// <summary>
+    /// Counts down and picks up the waveform while the test records, until it finishes.
+    /// </summary>
+    private async Task<MicTestStatus> FollowMicTestAsync(Task<MicTestStatus> test)
+    {
+        while (!test.IsCompleted)
+        {
+            if (MicTestStatus == MicTestStatus.Recording)
+            {
+                var left = MicTestDuration - (DateTime.UtcNow - _micTestRecordingStartedUtc);
+                MicTestSecondsLeft = Math.Max(0, (int)Math.Ceiling(left.TotalSeconds));
+                MicTestWaveform = _audioService.GetMicTestWaveform(Id);
+            }
+
+            await Task.WhenAny(test, Task.Delay(MicTestTickMilliseconds));
+        }
+
+        MicTestWaveform = _audioService.GetMicTestWaveform(Id);
+        return await test;
+    }
+
+    partial void OnMicTestStatusChanged(MicTestStatus value)
+    {
+        if (value != MicTestStatus.Recording) return;
+
+        _micTestRecordingStartedUtc = DateTime.UtcNow;
+        MicTestSecondsLeft = (int)Math.Ceiling(MicTestDuration.TotalSeconds);
+    }
+
+    [RelayCommand]
+    private async Task ToggleRecordingAsync()
+    {
+        if (IsRecording)
+        {
+            var result = await _audioService.StopRecordingAsync(Id);
+            IsRecording = false;
+            RecordingText = result == null
+                ? string.Empty
+                : $"Saved {Path.GetFileName(result.FilePath)} ({result.Duration:m\\:ss})";
+            return;
+        }
+
+        var fileName = $"{SanitizeFileName(Name)} {DateTime.Now:yyyyMMdd-HHmmss}.wav";
+        var filePath = Path.Combine(RecordingService.DefaultRecordingsFolder, fileName);
+        if (_audioService.StartRecording(Id, filePath))
+        {
+            IsRecording = true;
+            RecordingText = "Recording...";
+        }
+        else
+        {
+            _onError?.Invoke("Failed to start recording");
+        }
+    }
+
+    private static string SanitizeFileName(string name)
+    {
+        var invalid = Path.GetInvalidFileNameChars();
+        return new string(name.Select(c => invalid.Contains(c) ? '_' : c).ToArray());
+    }
+
+    /// <summary>
+    /// Formats the device accepts as its default, for the "Default format" menu; empty if it couldn't be probed.
+    /// </summary>
+    public async Task<IReadOnlyList<DeviceFormat>> GetSupportedFormatsAsync()
+    {
+        return await _audioService.GetSupportedFormatsAsync(Id);
+    }
+
+    /// <summary>
+    /// Current default format, used to check the matching menu item.
+    /// </summary>
+    public DeviceFormat? GetDefaultFormat()
+    {
+        return _audioService.GetDeviceDefaultFormat(Id);
+    }
+
+    private DeviceCapabilities? _capabilities;
+
+    /// <summary>
+    /// Whether the device supports an optional feature. Probed on first use and cached, since the
+    /// probe walks the device topology.
+    /// </summary>
+    public bool Supports(DeviceCapabilities capability)
+    {
+        _capabilities ??= _audioService.GetCapabilities(Id);
+        return _capabilities.Value.HasFlag(capability);
+    }
+
+    /// <summary>
+    /// Audio processing effects the driver reports, for the "Audio effects" menu; empty before Windows 11 22H2.
+    /// </summary>
+    public IReadOnlyList<AudioEffect> GetAudioEffects()
+    {
+        return _audioService.GetAudioEffects(Id);
+    }
+
+    [RelayCommand]
+    private void ToggleAudioEffect(AudioEffect? effect)
+    {
+        if (effect == null || !effect.CanToggle) return;
+
+        if (!_audioService.SetAudioEffectEnabled(Id, effect.Id, !effect.IsEnabled))
+        {
+            _onError?.Invoke($"Failed to change {effect.Name.ToLowerInvariant()}");
+        }
+    }
+
+    /// <summary>
+    /// Sets the device default format. Parameter is "sampleRate:bits" (e.g. "48000:24");
+    /// the current channel count is kept.
+    /// </summary>
+    [RelayCommand]
+    private async Task SetDefaultFormatAsync(string? parameter)
+    {
+        var parts = parameter?.Split(':');
+        if (parts?.Length != 2 || !int.TryParse(parts[0], out var sampleRate) || !int.TryParse(parts[1], out var bits)) return;
+
+        var channels = _audioService.GetDeviceDefaultFormat(Id)?.Channels ?? 2;
+        var format = new DeviceFormat(sampleRate, bits, channels);
+        if (await _audioService.SetDeviceDefaultFormatAsync(Id, format, CancellationToken.None))
+        {
+            FormatTag = format.ToString();
+        }
+        else
+        {
+            _onError?.Invoke("Failed to set device format");
+        }
+    }
+
+    [RelayCommand]
+    private async Task ToggleListenAsync()
+    {
+        var enable = !IsListening;
+        if (await _audioService.SetListenAsync(Id, enable, null, CancellationToken.None))
+        {
+            IsListening = enable;
+        }
+        else
+        {
+            _onError?.Invoke("Failed to change Listen to this device");
+        }
+    }
+
+    [RelayCommand]
+    private async Task ToggleEnabledAsync()
+    {
+        if (IsChangingDevice) return;
+
+        var enable = State == NAudio.CoreAudioApi.DeviceState.Disabled;
+        try
+        {
+            IsChangingDevice = true;
+            var success = await _audioService.SetDeviceEnabledAsync(Id, enable, CancellationToken.None);
+            if (!success)
+            {
+                _onError?.Invoke(enable ? "Failed to enable device" : "Failed to disable device");
+            }
+        }
+        catch (Exception ex)
+        {
+            System.Diagnostics.Debug.WriteLine($"ToggleEnabledAsync failed: {ex}");
+            _onError?.Invoke(enable ? "Failed to enable device" : "Failed to disable device");
+        }
+        finally
+        {
+            IsChangingDevice = false;
+        }
+    }
+
+    private sealed class StatusReporter : IProgress<MicTestStatus>
+    {
+        private readonly Action<MicTestStatus> _report;
+
+        public StatusReporter(Action<MicTestStatus> report) => _report = report;
+
+        public void Report(MicTestStatus value) => _report(value);
+    }
+
+    partial void OnBoostDbChanged(double value)
+    {
+        if (_suppressBoostWrite) return;
+        if (!_audioService.SetMicrophoneBoost(Id, (float)value))
+        {
+            _onError?.Invoke("Failed to set microphone boost");
+        }
+    }
+
+    partial void OnIsAutoGainEnabledChanged(bool value)
+    {
+        if (_suppressAutoGainWrite) return;
+        if (!_audioService.SetAutoGainControl(Id, value))
+        {
+            _onError?.Invoke("Failed to change automatic gain control");
+        }
+    }
+
     partial void OnVolumePercentChanged(double value)
     {
-        if (_suppressVolumeWrite) return;
-        var clamped = Math.Max(0.0, Math.Min(100.0, value));
-        _audioService.SetMicrophoneVolumeLevelScalar(Id, (float)(clamped / 100.0));
+        if (!_suppressVolumeWrite)
+        {
+            var clamped = Math.Max(0.0, Math.Min(100.0, value));
+            _undoService?.RecordDevice(Id);
+            _audioService.SetMicrophoneVolumeLevelScalar(Id, (float)(clamped / 100.0));
+        }
+
+        RefreshVolumeDb();
+    }
+
+    private void RefreshVolumeDb()
+    {
+        // Runs on every slider tick, so use the range cached at enumeration instead of a COM round trip
+        VolumeDbText = _volumeRange is { } range ? $"{range.DbAt(VolumePercent / 100.0):0.#} dB" : string.Empty;
     }
 }
@@ -10,11 +10,26 @@ namespace MicrophoneManager.WinUI.ViewModels;
 public partial class MicrophoneListViewModel : ObservableObject, IDisposable
 {
     private readonly IAudioDeviceService _audioService;
+    private readonly DevicePriorityService? _priorityService;
+    private readonly VolumeLockService? _volumeLockService;
+    private readonly DeviceAliasService? _aliasService;
+    private readonly HiddenDeviceService? _hiddenDeviceService;
+    private readonly DeviceOrderService? _orderService;
+    private readonly UndoService? _undoService;
+    private readonly ClippingService? _clippingService;
+    private readonly MeterSettingsService? _meterSettings;
+    private readonly LevelMeterService? _levelMeter;
+
+    // Read on capture threads to drop level events nothing here shows
+    private volatile string? _defaultDeviceId;
+    private readonly DeviceGroupService? _groupService;
+    private readonly AppearanceService? _appearance;
     private readonly DispatcherQueue? _dispatcherQueue;
     private bool _suppressVolumeWrite;
     private bool _suppressInputMeterReset;
 
     private DispatcherQueueTimer? _peakHoldTimer;
+    private DispatcherQueueTimer? _sessionRefreshTimer;
     private DateTime _peakHoldUntilUtc;
     private DateTime _lastPeakTickUtc;
 
@@ -29,10 +44,26 @@ public partial class MicrophoneListViewModel : ObservableObject, IDisposable
     private readonly EventHandler<AudioDeviceService.MicrophoneVolumeChangedEventArgs> _microphoneVolumeChangedHandler;
     private readonly EventHandler<AudioDeviceService.MicrophoneInputLevelChangedEventArgs> _microphoneInputLevelChangedHandler;
     private readonly EventHandler<AudioDeviceService.MicrophoneFormatChangedEventArgs> _formatChangedHandler;
+    private readonly EventHandler<AudioDeviceService.AudioOperationFailedEventArgs> _operationFailedHandler;
+    private readonly EventHandler<AudioDeviceService.CaptureSessionChangedEventArgs> _captureSessionChangedHandler;
+    private readonly EventHandler _priorityListChangedHandler;
+    private readonly EventHandler _volumeLocksChangedHandler;
+    private readonly EventHandler _aliasesChangedHandler;
+    private readonly EventHandler _hiddenDevicesChangedHandler;
+    private readonly EventHandler _orderChangedHandler;
+    private readonly EventHandler _undoHistoryChangedHandler;
+    private readonly EventHandler<ClippingService.ClipEventArgs> _clippedHandler;
+    private readonly EventHandler _clipCountsResetHandler;
+    private readonly EventHandler _meterSettingsChangedHandler;
+    private readonly EventHandler _groupsChangedHandler;
+    private readonly EventHandler _appearanceChangedHandler;
 
     private const int PeakHoldMilliseconds = 5000;
     private const double PeakDecayDbPerSecond = 20.0;
 
+    // Sessions have no cheap change notification, so poll while the UI is visible.
+    private const int SessionRefreshMilliseconds = 2000;
+
     [ObservableProperty]
     private ObservableCollection<MicrophoneEntryViewModel> _microphones = new();
 
@@ -60,6 +91,27 @@ public partial class MicrophoneListViewModel : ObservableObject, IDisposable
     [ObservableProperty]
     private string? _errorMessage;
 
+    /// <summary>
+    /// Persistent warning when Windows privacy settings block microphone access; unlike
+    /// <see cref="ErrorMessage"/> it stays until the setting changes.
+    /// </summary>
+    [ObservableProperty]
+    [NotifyPropertyChangedFor(nameof(HasPrivacyWarning))]
+    private string? _privacyWarning;
+
+    public bool HasPrivacyWarning => !string.IsNullOrEmpty(PrivacyWarning);
+
+    /// <summary>
+    /// When true, disabled and unplugged microphones are listed (greyed out) alongside active ones.
+    /// </summary>
+    [ObservableProperty]
+    private bool _showInactiveDevices;
+
+    private const NAudio.CoreAudioApi.DeviceState InactiveDeviceStates =
+        NAudio.CoreAudioApi.DeviceState.Active |
+        NAudio.CoreAudioApi.DeviceState.Disabled |
+        NAudio.CoreAudioApi.DeviceState.Unplugged;
+
     private DispatcherQueueTimer? _errorDismissTimer;
     private const int ErrorDismissMilliseconds = 5000;
 
@@ -106,16 +158,53 @@ public partial class MicrophoneListViewModel : ObservableObject, IDisposable
         action();
     }
 
-    public MicrophoneListViewModel(IAudioDeviceService audioService)
+    public MicrophoneListViewModel(
+        IAudioDeviceService audioService,
+        DevicePriorityService? priorityService = null,
+        VolumeLockService? volumeLockService = null,
+        DeviceAliasService? aliasService = null,
+        HiddenDeviceService? hiddenDeviceService = null,
+        DeviceOrderService? orderService = null,
+        UndoService? undoService = null,
+        ClippingService? clippingService = null,
+        MeterSettingsService? meterSettings = null,
+        LevelMeterService? levelMeter = null,
+        DeviceGroupService? groupService = null,
+        AppearanceService? appearance = null)
     {
         _audioService = audioService;
+        _priorityService = priorityService;
+        _volumeLockService = volumeLockService;
+        _aliasService = aliasService;
+        _hiddenDeviceService = hiddenDeviceService;
+        _orderService = orderService;
+        _undoService = undoService;
+        _clippingService = clippingService;
+        _meterSettings = meterSettings;
+        _levelMeter = levelMeter;
+        _groupService = groupService;
+        _appearance = appearance;
         _dispatcherQueue = DispatcherQueue.GetForCurrentThread();
 
         _peakHoldUntilUtc = DateTime.MinValue;
         _lastPeakTickUtc = DateTime.UtcNow;
 
-        _devicesChangedHandler = (s, e) => InvokeOnUiThread(RefreshDevices);
-        _defaultDeviceChangedHandler = (s, e) => InvokeOnUiThread(RefreshDevices);
+        // Known change sets patch the affected entries; anything else still re-enumerates
+        _devicesChangedHandler = (s, e) => InvokeOnUiThread(() =>
+        {
+            if (e is AudioDeviceService.DevicesChangedEventArgs changeSet)
+            {
+                foreach (var change in changeSet.Changes)
+                {
+                    ApplyDeviceChange(change.DeviceId, change.Kind);
+                }
+            }
+            else
+            {
+                RefreshDevices();
+            }
+        });
+        _defaultDeviceChangedHandler = (s, e) => InvokeOnUiThread(ApplyDefaultDeviceChange);
         _defaultVolumeChangedHandler = (s, e) =>
             InvokeOnUiThread(() =>
             {
@@ -130,17 +219,12 @@ public partial class MicrophoneListViewModel : ObservableObject, IDisposable
                     CurrentMicLevelPercent = volumePercent;
                     IsMuted = e.IsMuted;
 
-                    // TODO: Update TrayViewModel if needed via App static reference
-                    // if (App.TrayViewModel != null)
-                    // {
-                    //     App.TrayViewModel.IsMuted = IsMuted;
-                    // }
-
                     var defaultVm = Microphones.FirstOrDefault(m => m.Id == defaultId);
                     defaultVm?.ApplyVolumeFromSystem(volumePercent);
                     if (defaultVm != null)
                     {
                         defaultVm.IsMuted = e.IsMuted;
+                        UpdateGroupMuted(defaultVm.GroupKey);
                     }
                 }
                 finally
@@ -161,22 +245,31 @@ public partial class MicrophoneListViewModel : ObservableObject, IDisposable
                 var volumePercent = Math.Round(e.VolumeLevelScalar * 100.0, 2);
                 vm.ApplyVolumeFromSystem(volumePercent);
                 vm.IsMuted = e.IsMuted;
+                UpdateGroupMuted(vm.GroupKey);
             });
 
         _microphoneInputLevelChangedHandler = (s, e) =>
+        {
+            // Arrives on the capture thread; with the meter thread feeding the entries only the
+            // default's level is shown from here
+            if (_levelMeter != null && e.DeviceId != _defaultDeviceId) return;
+
             InvokeOnUiThread(() =>
             {
-                // Route to per-device VM
+                // Route to per-device VM, unless the meter thread feeds the entries on the UI tick
                 var vm = Microphones.FirstOrDefault(m => m.Id == e.DeviceId);
                 if (vm == null) return;
 
-                var shouldMute = vm.IsMuted;
-                var finalLevel = shouldMute ? 0 : e.InputLevelPercent;
-                vm.UpdateMeter(finalLevel);
+                if (_levelMeter == null)
+                {
+                    var shouldMute = vm.IsMuted;
+                    var finalLevel = shouldMute ? 0 : e.InputLevelPercent;
+                    vm.UpdateMeter(finalLevel, shouldMute ? null : e.RmsDbFs);
+                    _orderService?.RecordLevel(vm.PreferenceKey, finalLevel);
+                }
 
                 // Also update list-level meters if this is the default
-                var defaultId = _audioService.GetDefaultDeviceId(NAudio.CoreAudioApi.Role.Console);
-                if (defaultId != null && e.DeviceId == defaultId)
+                if (e.DeviceId == _defaultDeviceId)
                 {
                     if (IsMuted)
                     {
@@ -202,6 +295,7 @@ public partial class MicrophoneListViewModel : ObservableObject, IDisposable
                     }
                 }
             });
+        };
 
         _formatChangedHandler = (s, e) =>
             InvokeOnUiThread(() =>
@@ -213,6 +307,35 @@ public partial class MicrophoneListViewModel : ObservableObject, IDisposable
                 }
             });
 
+        _operationFailedHandler = (s, e) => InvokeOnUiThread(() => ShowError(e.UserMessage));
+
+        // Session notifications update "in use by" immediately; the refresh timer only runs while the flyout is open
+        _captureSessionChangedHandler = (s, e) =>
+            InvokeOnUiThread(() =>
+            {
+                var vm = Microphones.FirstOrDefault(m => m.Id == e.DeviceId);
+                if (vm != null && vm.IsActive)
+                {
+                    vm.UpdateSessions(_audioService.GetCaptureSessions(vm.Id));
+                }
+            });
+
+        _priorityListChangedHandler = (s, e) => InvokeOnUiThread(UpdatePriorityRanks);
+        _volumeLocksChangedHandler = (s, e) => InvokeOnUiThread(UpdateVolumeLocks);
+        _aliasesChangedHandler = (s, e) => InvokeOnUiThread(UpdateAliases);
+        _hiddenDevicesChangedHandler = (s, e) => InvokeOnUiThread(RefreshDevices);
+        _orderChangedHandler = (s, e) => InvokeOnUiThread(ApplyOrder);
+        _undoHistoryChangedHandler = (s, e) => InvokeOnUiThread(UpdateCanUndo);
+        _clippedHandler = (s, e) => InvokeOnUiThread(() =>
+        {
+            var vm = Microphones.FirstOrDefault(m => m.Id == e.DeviceId);
+            if (vm != null) vm.ClipCount = e.ClipCount;
+        });
+        _clipCountsResetHandler = (s, e) => InvokeOnUiThread(UpdateClipCounts);
+        _meterSettingsChangedHandler = (s, e) => InvokeOnUiThread(UpdateMeterSettings);
+        _groupsChangedHandler = (s, e) => InvokeOnUiThread(UpdateGroups);
+        _appearanceChangedHandler = (s, e) => InvokeOnUiThread(UpdateDensity);
+
         // Subscribe to changes
         _audioService.DevicesChanged += _devicesChangedHandler;
         _audioService.DefaultDeviceChanged += _defaultDeviceChangedHandler;
@@ -220,6 +343,50 @@ public partial class MicrophoneListViewModel : ObservableObject, IDisposable
         _audioService.MicrophoneVolumeChanged += _microphoneVolumeChangedHandler;
         _audioService.MicrophoneInputLevelChanged += _microphoneInputLevelChangedHandler;
         _audioService.MicrophoneFormatChanged += _formatChangedHandler;
+        _audioService.OperationFailed += _operationFailedHandler;
+        _audioService.CaptureSessionChanged += _captureSessionChangedHandler;
+        if (_priorityService != null)
+        {
+            _priorityService.PriorityListChanged += _priorityListChangedHandler;
+        }
+        if (_volumeLockService != null)
+        {
+            _volumeLockService.LocksChanged += _volumeLocksChangedHandler;
+        }
+        if (_aliasService != null)
+        {
+            _aliasService.AliasesChanged += _aliasesChangedHandler;
+        }
+        if (_hiddenDeviceService != null)
+        {
+            _hiddenDeviceService.HiddenDevicesChanged += _hiddenDevicesChangedHandler;
+        }
+        if (_orderService != null)
+        {
+            _orderService.OrderChanged += _orderChangedHandler;
+        }
+        if (_undoService != null)
+        {
+            _undoService.HistoryChanged += _undoHistoryChangedHandler;
+            UpdateCanUndo();
+        }
+        if (_clippingService != null)
+        {
+            _clippingService.Clipped += _clippedHandler;
+            _clippingService.CountsReset += _clipCountsResetHandler;
+        }
+        if (_meterSettings != null)
+        {
+            _meterSettings.Changed += _meterSettingsChangedHandler;
+        }
+        if (_groupService != null)
+        {
+            _groupService.Changed += _groupsChangedHandler;
+        }
+        if (_appearance != null)
+        {
+            _appearance.Changed += _appearanceChangedHandler;
+        }
 
         // Initial load
         RefreshDevices();
@@ -236,6 +403,21 @@ public partial class MicrophoneListViewModel : ObservableObject, IDisposable
 
         _meteringEnabled = enabled;
 
+        if (enabled)
+        {
+            _levelMeter?.AddViewer();
+
+            // Usage and activity have moved on since the list was last shown
+            if (_orderService?.SortMode is DeviceSortMode.LastUsed or DeviceSortMode.Activity)
+            {
+                ApplyOrder();
+            }
+        }
+        else
+        {
+            _levelMeter?.RemoveViewer();
+        }
+
         if (_dispatcherQueue == null)
         {
             return;
@@ -248,19 +430,35 @@ public partial class MicrophoneListViewModel : ObservableObject, IDisposable
             {
                 _peakHoldTimer = _dispatcherQueue.CreateTimer();
                 _peakHoldTimer.Interval = TimeSpan.FromMilliseconds(33);
-                _peakHoldTimer.Tick += (s, e) => TickPeakHold();
+                _peakHoldTimer.Tick += (s, e) =>
+                {
+                    RefreshMeters();
+                    TickPeakHold();
+                };
             }
             _peakHoldTimer.Start();
+
+            if (_sessionRefreshTimer == null)
+            {
+                _sessionRefreshTimer = _dispatcherQueue.CreateTimer();
+                _sessionRefreshTimer.Interval = TimeSpan.FromMilliseconds(SessionRefreshMilliseconds);
+                _sessionRefreshTimer.Tick += (s, e) => RefreshSessions();
+            }
+            _sessionRefreshTimer.Start();
         }
         else
         {
             try { _peakHoldTimer?.Stop(); } catch { }
+            try { _sessionRefreshTimer?.Stop(); } catch { }
         }
     }
 
     public void RefreshDevices()
     {
-        var devices = _audioService.GetMicrophones();
+        var devices = (ShowInactiveDevices
+            ? _audioService.GetMicrophonesWithStates(InactiveDeviceStates)
+            : _audioService.GetMicrophones())
+            .Where(IsListed);
 
         var existingById = Microphones.ToDictionary(m => m.Id, m => m);
         var seenIds = new HashSet<string>();
@@ -273,7 +471,7 @@ public partial class MicrophoneListViewModel : ObservableObject, IDisposable
             }
             else
             {
-                Microphones.Add(new MicrophoneEntryViewModel(device, _audioService, ShowError));
+                Microphones.Add(new MicrophoneEntryViewModel(device, _audioService, ShowError, _undoService));
             }
 
             seenIds.Add(device.Id);
@@ -285,7 +483,8 @@ public partial class MicrophoneListViewModel : ObservableObject, IDisposable
             Microphones.Remove(remove);
         }
 
-        SelectedMicrophone = Microphones.FirstOrDefault(m => m.IsDefault);
+        SelectDefaultMicrophone();
+        UpdateMeteredDevices();
         IsMuted = _audioService.IsDefaultMicrophoneMuted();
 
         _suppressVolumeWrite = true;
@@ -309,15 +508,555 @@ public partial class MicrophoneListViewModel : ObservableObject, IDisposable
             _peakHoldUntilUtc = DateTime.MinValue;
         }
 
+        RefreshSessions();
+        UpdatePriorityRanks();
+        UpdateVolumeLocks();
+        UpdateClipCounts();
+        UpdateMeterSettings();
+        UpdateDensity();
+        UpdateAliases();
+        UpdateHiddenFlags();
+        ApplyOrder();
+        PrivacyWarning = _audioService.GetPrivacyStatus().WarningText;
+
+        OnPropertyChanged(nameof(HasMicrophones));
+        OnPropertyChanged(nameof(HasNoMicrophones));
+    }
+
+    /// <summary>
+    /// Adds, updates or removes the one entry a device notification refers to, instead of
+    /// re-reading every endpoint (a USB reconnect fires several notifications per device).
+    /// </summary>
+    public void ApplyDeviceChange(string deviceId, DeviceChangeKind kind)
+    {
+        if (_disposed) return;
+
+        var existing = Microphones.FirstOrDefault(m => m.Id == deviceId);
+        var device = kind == DeviceChangeKind.Removed ? null : _audioService.GetMicrophone(deviceId);
+        var visibleStates = ShowInactiveDevices ? InactiveDeviceStates : NAudio.CoreAudioApi.DeviceState.Active;
+
+        if (device == null || (device.State & visibleStates) == 0 || !IsListed(device))
+        {
+            // Render endpoints and hidden states land here too
+            if (existing == null) return;
+            Microphones.Remove(existing);
+            UpdateGroups();
+        }
+        else if (existing != null)
+        {
+            existing.UpdateFrom(device);
+            UpdateGroupMuted(existing.GroupKey);
+        }
+        else
+        {
+            var vm = new MicrophoneEntryViewModel(device, _audioService, ShowError, _undoService);
+            vm.PriorityRank = _priorityService?.GetRank(vm.PreferenceKey);
+            vm.IsVolumeLocked = _volumeLockService?.GetTarget(vm.PreferenceKey) != null;
+            vm.Alias = _aliasService?.GetAlias(vm.PreferenceKey);
+            vm.IsHidden = _hiddenDeviceService?.IsHidden(vm.PreferenceKey) ?? false;
+            vm.ClipCount = _clippingService?.GetClipCount(vm.Id) ?? 0;
+            if (_meterSettings != null)
+            {
+                vm.ConfigureMeter(_meterSettings.Mode, _meterSettings.AttackMs, _meterSettings.DecayMs);
+            }
+            vm.IsHistoryEnabled = IsHistoryEnabled;
+            vm.IsCompact = IsCompact;
+            Microphones.Add(vm);
+            ApplyOrder();
+            if (vm.IsActive)
+            {
+                vm.UpdateSessions(_audioService.GetCaptureSessions(vm.Id));
+            }
+        }
+
+        SelectDefaultMicrophone();
+        UpdateMeteredDevices();
         OnPropertyChanged(nameof(HasMicrophones));
         OnPropertyChanged(nameof(HasNoMicrophones));
     }
 
+    private void SelectDefaultMicrophone()
+    {
+        SelectedMicrophone = Microphones.FirstOrDefault(m => m.IsDefault);
+        _defaultDeviceId = SelectedMicrophone?.Id;
+    }
+
+    /// <summary>
+    /// Hands the listed active microphones to the meter thread, so it never enumerates devices itself.
+    /// </summary>
+    private void UpdateMeteredDevices()
+    {
+        _levelMeter?.SetDevices(Microphones.Where(m => m.IsActive).Select(m => m.Id).ToList());
+    }
+
+    /// <summary>
+    /// Moves the default and communications flags without re-enumerating. Falls back to a full
+    /// refresh when the new default isn't listed yet.
+    /// </summary>
+    public void ApplyDefaultDeviceChange()
+    {
+        if (_disposed) return;
+
+        var defaultId = _audioService.GetDefaultDeviceId(NAudio.CoreAudioApi.Role.Console);
+        var defaultCommId = _audioService.GetDefaultDeviceId(NAudio.CoreAudioApi.Role.Communications);
+        if (defaultId != null && Microphones.All(m => m.Id != defaultId))
+        {
+            RefreshDevices();
+            return;
+        }
+
+        foreach (var vm in Microphones)
+        {
+            vm.IsDefault = vm.Id == defaultId;
+            vm.IsDefaultCommunication = vm.Id == defaultCommId;
+            if (vm.IsDefault) _orderService?.RecordUsed(vm.PreferenceKey);
+        }
+
+        if (_orderService?.SortMode == DeviceSortMode.DefaultFirst)
+        {
+            ApplyOrder();
+        }
+
+        SelectDefaultMicrophone();
+        IsMuted = _audioService.IsDefaultMicrophoneMuted();
+
+        _suppressVolumeWrite = true;
+        try
+        {
+            CurrentMicLevelPercent = SelectedMicrophone?.VolumePercent ?? 100.0;
+        }
+        finally
+        {
+            _suppressVolumeWrite = false;
+        }
+    }
+
+    /// <summary>
+    /// Updates each entry's "in use by" label from the capture sessions on its device, and
+    /// whether another application holds it in exclusive mode.
+    /// </summary>
+    public void RefreshSessions()
+    {
+        if (_disposed) return;
+
+        foreach (var vm in Microphones)
+        {
+            if (!vm.IsActive) continue;
+            vm.UpdateSessions(_audioService.GetCaptureSessions(vm.Id));
+            vm.IsExclusivelyLocked = _audioService.IsDeviceExclusivelyLocked(vm.Id);
+            if (vm.IsDefault || vm.IsInUse) _orderService?.RecordUsed(vm.PreferenceKey);
+        }
+    }
+
+    /// <summary>
+    /// Whether the preferred-microphone list can be edited from this view.
+    /// </summary>
+    public bool CanEditPriority => _priorityService != null;
+
+    private void UpdatePriorityRanks()
+    {
+        if (_disposed || _priorityService == null) return;
+
+        foreach (var vm in Microphones)
+        {
+            vm.PriorityRank = _priorityService.GetRank(vm.PreferenceKey);
+        }
+    }
+
+    /// <summary>
+    /// Whether volume locks can be set from this view.
+    /// </summary>
+    public bool CanLockVolume => _volumeLockService != null;
+
+    private void UpdateVolumeLocks()
+    {
+        if (_disposed || _volumeLockService == null) return;
+
+        foreach (var vm in Microphones)
+        {
+            vm.IsVolumeLocked = _volumeLockService.GetTarget(vm.PreferenceKey) != null;
+        }
+    }
+
+    private void UpdateClipCounts()
+    {
+        if (_disposed || _clippingService == null) return;
+
+        foreach (var vm in Microphones)
+        {
+            vm.ClipCount = _clippingService.GetClipCount(vm.Id);
+        }
+    }
+
+    private void UpdateMeterSettings()
+    {
+        if (_disposed || _meterSettings == null) return;
+
+        var mode = _meterSettings.Mode;
+        var attackMs = _meterSettings.AttackMs;
+        var decayMs = _meterSettings.DecayMs;
+        IsHistoryEnabled = _meterSettings.ShowHistory;
+        foreach (var vm in Microphones)
+        {
+            vm.ConfigureMeter(mode, attackMs, decayMs);
+            vm.IsHistoryEnabled = IsHistoryEnabled;
+        }
+    }
+
+    /// <summary>
+    /// Whether rows show the level history under the meter.
+    /// </summary>
+    [ObservableProperty]
+    private bool _isHistoryEnabled;
+
+    /// <summary>
+    /// Whether rows show just the name, mute button and a small meter.
+    /// </summary>
+    [ObservableProperty]
+    private bool _isCompact;
+
+    private void UpdateDensity()
+    {
+        if (_disposed) return;
+
+        if (_appearance != null)
+        {
+            IsCompact = _appearance.IsCompact;
+        }
+
+        foreach (var vm in Microphones)
+        {
+            vm.IsCompact = IsCompact;
+        }
+    }
+
+    /// <summary>
+    /// Switches between compact and detailed rows (Ctrl+D in the flyout) and remembers the choice.
+    /// </summary>
+    [RelayCommand]
+    private void ToggleCompact()
+    {
+        if (_appearance != null)
+        {
+            _appearance.IsCompact = !IsCompact;
+            return;
+        }
+
+        IsCompact = !IsCompact;
+        UpdateDensity();
+    }
+
+    /// <summary>
+    /// Starts the entry's clip count over, e.g. after lowering the gain.
+    /// </summary>
+    [RelayCommand]
+    private void ResetClipCount(MicrophoneEntryViewModel? entry)
+    {
+        if (entry == null || _clippingService == null) return;
+
+        _clippingService.ResetCount(entry.Id);
+    }
+
+    /// <summary>
+    /// Locks the entry's volume at its current level, or releases the lock.
+    /// </summary>
+    [RelayCommand]
+    private void ToggleVolumeLock(MicrophoneEntryViewModel? entry)
+    {
+        if (entry == null || _volumeLockService == null) return;
+
+        _volumeLockService.SetLock(entry.PreferenceKey, entry.IsVolumeLocked ? null : (float)(entry.VolumePercent / 100.0));
+    }
+
+    /// <summary>
+    /// Whether devices can be renamed from this view.
+    /// </summary>
+    public bool CanRename => _aliasService != null;
+
+    private void UpdateAliases()
+    {
+        if (_disposed || _aliasService == null) return;
+
+        foreach (var vm in Microphones)
+        {
+            vm.Alias = _aliasService.GetAlias(vm.PreferenceKey);
+        }
+
+        if (_orderService?.SortMode == DeviceSortMode.Name)
+        {
+            ApplyOrder();
+        }
+    }
+
+    /// <summary>
+    /// Switches the entry's name to an edit box, starting from the name currently shown.
+    /// </summary>
+    [RelayCommand]
+    private void BeginRename(MicrophoneEntryViewModel? entry)
+    {
+        if (entry == null || _aliasService == null) return;
+
+        entry.RenameText = entry.DisplayName;
+        entry.IsRenaming = true;
+    }
+
+    /// <summary>
+    /// Saves the edited name as the entry's alias; clearing it (or typing the driver's name) removes the alias.
+    /// </summary>
+    [RelayCommand]
+    private void CommitRename(MicrophoneEntryViewModel? entry)
+    {
+        if (entry == null || !entry.IsRenaming) return;
+
+        entry.IsRenaming = false;
+        _aliasService?.SetAlias(entry.PreferenceKey, entry.RenameText, entry.Name);
+    }
+
+    [RelayCommand]
+    private void CancelRename(MicrophoneEntryViewModel? entry)
+    {
+        if (entry == null) return;
+        entry.IsRenaming = false;
+    }
+
+    /// <summary>
+    /// Whether devices can be hidden from this view.
+    /// </summary>
+    public bool CanHide => _hiddenDeviceService != null;
+
+    private bool IsListed(MicrophoneDevice device) => _hiddenDeviceService?.ShouldShow(device) ?? true;
+
+    private void UpdateHiddenFlags()
+    {
+        if (_disposed || _hiddenDeviceService == null) return;
+
+        foreach (var vm in Microphones)
+        {
+            vm.IsHidden = _hiddenDeviceService.IsHidden(vm.PreferenceKey);
+        }
+    }
+
+    /// <summary>
+    /// Hides the entry from the list, or unhides it when hidden devices are being shown.
+    /// </summary>
+    [RelayCommand]
+    private void ToggleHidden(MicrophoneEntryViewModel? entry)
+    {
+        if (entry == null) return;
+        _hiddenDeviceService?.SetHidden(entry.PreferenceKey, !entry.IsHidden);
+    }
+
+    /// <summary>
+    /// Whether entries can be pinned and reordered from this view.
+    /// </summary>
+    public bool CanReorder => _orderService != null;
+
+    /// <summary>
+    /// Whether "Move up" and "Move down" apply; in the other sort modes the order is worked out.
+    /// </summary>
+    public bool CanMoveEntries => _orderService?.IsCustomOrder ?? false;
+
+    public DeviceSortMode SortMode => _orderService?.SortMode ?? DeviceSortMode.Custom;
+
+    [RelayCommand]
+    private void SetSortMode(DeviceSortMode mode)
+    {
+        if (_orderService == null) return;
+        _orderService.SortMode = mode;
+    }
+
+    /// <summary>
+    /// Puts the entries in the user's order and refreshes their pinned flags, then their groups.
+    /// </summary>
+    private void ApplyOrder()
+    {
+        if (_disposed) return;
+
+        if (_orderService != null)
+        {
+            var sorted = _orderService.SortForDisplay(Microphones, m => m.PreferenceKey, m => m.DisplayName, m => m.IsDefault ? 0 : m.IsDefaultCommunication ? 1 : 2);
+            for (var i = 0; i < sorted.Count; i++)
+            {
+                sorted[i].IsPinned = _orderService.IsPinned(sorted[i].PreferenceKey);
+
+                var current = Microphones.IndexOf(sorted[i]);
+                if (current != i) Microphones.Move(current, i);
+            }
+        }
+
+        UpdateGroups();
+    }
+
+    /// <summary>
+    /// Whether endpoints of the same physical device are listed under a shared header.
+    /// </summary>
+    public bool IsGroupingEnabled => _groupService?.IsEnabled ?? false;
+
+    /// <summary>
+    /// Moves the endpoints of each physical device next to the first one listed and gives that
+    /// one the group's header. Devices with a single endpoint aren't grouped.
+    /// </summary>
+    private void UpdateGroups()
+    {
+        if (_disposed) return;
+
+        var groups = IsGroupingEnabled
+            ? Microphones
+                .Where(m => m.GroupKey != null)
+                .GroupBy(m => m.GroupKey!, StringComparer.OrdinalIgnoreCase)
+                .Where(g => g.Count() > 1)
+                .ToDictionary(g => g.Key, g => g.ToList(), StringComparer.OrdinalIgnoreCase)
+            : new Dictionary<string, List<MicrophoneEntryViewModel>>();
+
+        // Pull later members up under the first; the first member keeps its place in the user's order
+        var index = 0;
+        var placed = new HashSet<string>(StringComparer.OrdinalIgnoreCase);
+        while (index < Microphones.Count)
+        {
+            var vm = Microphones[index];
+            if (vm.GroupKey == null || !groups.TryGetValue(vm.GroupKey, out var members) || !placed.Add(vm.GroupKey))
+            {
+                index++;
+                continue;
+            }
+
+            foreach (var member in members)
+            {
+                var current = Microphones.IndexOf(member);
+                if (current != index) Microphones.Move(current, index);
+                index++;
+            }
+        }
+
+        foreach (var vm in Microphones)
+        {
+            var members = vm.GroupKey != null && groups.TryGetValue(vm.GroupKey, out var list) ? list : null;
+            var collapsed = members != null && _groupService!.IsCollapsed(vm.GroupKey!);
+
+            vm.GroupTitle = members != null && ReferenceEquals(members[0], vm)
+                ? DeviceGroupService.GetTitle(vm.AdapterName, members.Select(m => m.EndpointName))
+                : null;
+            vm.IsGroupCollapsed = collapsed;
+            vm.IsInCollapsedGroup = collapsed;
+            vm.IsGroupMuted = members != null && members.All(m => m.IsMuted);
+        }
+    }
+
+    private void UpdateGroupMuted(string? groupKey)
+    {
+        if (_disposed || groupKey == null) return;
+
+        var members = Microphones.Where(m => string.Equals(m.GroupKey, groupKey, StringComparison.OrdinalIgnoreCase)).ToList();
+        var muted = members.Count > 1 && members.All(m => m.IsMuted);
+        foreach (var member in members)
+        {
+            member.IsGroupMuted = muted;
+        }
+    }
+
+    [RelayCommand]
+    private void ToggleGroupCollapsed(MicrophoneEntryViewModel? entry)
+    {
+        if (entry?.GroupKey == null) return;
+        _groupService?.SetCollapsed(entry.GroupKey, !entry.IsGroupCollapsed);
+    }
+
+    /// <summary>
+    /// Mutes every endpoint of the entry's device, or unmutes them all when they're all muted.
+    /// </summary>
+    [RelayCommand]
+    private void ToggleGroupMute(MicrophoneEntryViewModel? entry)
+    {
+        if (entry?.GroupKey == null) return;
+
+        var members = Microphones.Where(m => string.Equals(m.GroupKey, entry.GroupKey, StringComparison.OrdinalIgnoreCase)).ToList();
+        var mute = !members.All(m => m.IsMuted);
+        _undoService?.RecordAll(mute ? "mute device" : "unmute device");
+
+        var failed = false;
+        foreach (var member in members.Where(m => m.IsMuted != mute))
+        {
+            if (_audioService.SetMute(member.Id, mute))
+            {
+                member.IsMuted = mute;
+            }
+            else
+            {
+                failed = true;
+            }
+        }
+
+        UpdateGroupMuted(entry.GroupKey);
+        if (failed)
+        {
+            ShowError(mute ? "Couldn't mute every endpoint of this device" : "Couldn't unmute every endpoint of this device");
+        }
+    }
+
+    [RelayCommand]
+    private void TogglePinned(MicrophoneEntryViewModel? entry)
+    {
+        if (entry == null) return;
+        _orderService?.SetPinned(entry.PreferenceKey, !entry.IsPinned);
+    }
+
+    [RelayCommand]
+    private void MoveUp(MicrophoneEntryViewModel? entry)
+    {
+        if (entry == null) return;
+        _orderService?.Move(entry.PreferenceKey, -1, Microphones.Select(m => m.PreferenceKey).ToList());
+    }
+
+    [RelayCommand]
+    private void MoveDown(MicrophoneEntryViewModel? entry)
+    {
+        if (entry == null) return;
+        _orderService?.Move(entry.PreferenceKey, 1, Microphones.Select(m => m.PreferenceKey).ToList());
+    }
+
+    [RelayCommand]
+    private void PreferMicrophone(MicrophoneEntryViewModel? entry)
+    {
+        if (entry == null) return;
+        _priorityService?.Prefer(entry.PreferenceKey);
+    }
+
+    [RelayCommand]
+    private void RaisePriority(MicrophoneEntryViewModel? entry)
+    {
+        if (entry == null) return;
+        _priorityService?.Move(entry.PreferenceKey, -1);
+    }
+
+    [RelayCommand]
+    private void LowerPriority(MicrophoneEntryViewModel? entry)
+    {
+        if (entry == null) return;
+        _priorityService?.Move(entry.PreferenceKey, 1);
+    }
+
+    [RelayCommand]
+    private void RemovePreference(MicrophoneEntryViewModel? entry)
+    {
+        if (entry == null) return;
+        _priorityService?.Remove(entry.PreferenceKey);
+    }
+
+    partial void OnShowInactiveDevicesChanged(bool value)
+    {
+        RefreshDevices();
+    }
+
     partial void OnCurrentMicLevelPercentChanged(double value)
     {
         if (_suppressVolumeWrite) return;
 
         // Slider drives the current default microphone volume.
+        if (_audioService.GetDefaultDeviceId(NAudio.CoreAudioApi.Role.Console) is string defaultId)
+        {
+            _undoService?.RecordDevice(defaultId);
+        }
+
         _audioService.SetDefaultMicrophoneVolumePercent(value);
     }
 
@@ -326,6 +1065,11 @@ public partial class MicrophoneListViewModel : ObservableObject, IDisposable
     {
         try
         {
+            if (_audioService.GetDefaultDeviceId(NAudio.CoreAudioApi.Role.Console) is string defaultId)
+            {
+                _undoService?.RecordDevice(defaultId, "mute change");
+            }
+
             IsMuted = await _audioService.ToggleDefaultMicrophoneMuteAsync(CancellationToken.None);
 
             if (IsMuted)
@@ -337,12 +1081,6 @@ public partial class MicrophoneListViewModel : ObservableObject, IDisposable
                 _peakHoldUntilUtc = DateTime.MinValue;
                 _peakMicDbFs = -96;
             }
-
-            // TODO: Update the TrayViewModel as well via App static reference
-            // if (App.TrayViewModel != null)
-            // {
-            //     App.TrayViewModel.IsMuted = IsMuted;
-            // }
         }
         catch (Exception ex)
         {
@@ -351,6 +1089,37 @@ public partial class MicrophoneListViewModel : ObservableObject, IDisposable
         }
     }
 
+    /// <summary>
+    /// Whether there's a change made through the app to undo (Ctrl+Z in the flyout).
+    /// </summary>
+    [ObservableProperty]
+    private bool _canUndo;
+
+    private void UpdateCanUndo()
+    {
+        if (_disposed) return;
+        CanUndo = _undoService?.CanUndo ?? false;
+    }
+
+    [RelayCommand]
+    private async Task UndoAsync()
+    {
+        if (_undoService == null || !_undoService.CanUndo) return;
+
+        try
+        {
+            if (!await _undoService.UndoAsync(CancellationToken.None))
+            {
+                ShowError("Couldn't undo all of the last change");
+            }
+        }
+        catch (Exception ex)
+        {
+            System.Diagnostics.Debug.WriteLine($"UndoAsync failed: {ex}");
+            ShowError("Failed to undo");
+        }
+    }
+
     private void UpdatePeakHold(double currentPercent, double currentDbFs)
     {
         var clampedPercent = Math.Max(0.0, Math.Min(100.0, currentPercent));
@@ -364,6 +1133,36 @@ public partial class MicrophoneListViewModel : ObservableObject, IDisposable
         }
     }
 
+    /// <summary>
+    /// Moves the entries' meters to the meter thread's latest levels. Called on the UI tick while metering is enabled.
+    /// </summary>
+    public void RefreshMeters()
+    {
+        if (_disposed || _levelMeter == null) return;
+
+        var snapshot = _levelMeter.Snapshot;
+        foreach (var vm in Microphones)
+        {
+            if (!snapshot.TryGetReading(vm.Id, out var reading)) continue;
+
+            // The endpoint meter keeps going while muted; the meter shows what others hear
+            if (vm.IsMuted)
+            {
+                vm.UpdateMeter(0);
+                vm.UpdateChannelMeters(reading.ChannelPeaksDb?.Select(_ => ObsMeterMath.ClampMeterDb(double.NegativeInfinity)).ToArray());
+            }
+            else
+            {
+                var percent = ObsMeterMath.DbToPercent(reading.PeakDb);
+                vm.UpdateMeter(percent, reading.RmsDb);
+                vm.UpdateChannelMeters(reading.ChannelPeaksDb);
+                _orderService?.RecordLevel(vm.PreferenceKey, percent);
+            }
+
+            vm.UpdateHistory(_levelMeter.GetHistory(vm.Id));
+        }
+    }
+
     private void TickPeakHold()
     {
         var nowUtc = DateTime.UtcNow;
@@ -400,5 +1199,48 @@ public partial class MicrophoneListViewModel : ObservableObject, IDisposable
         try { _audioService.MicrophoneVolumeChanged -= _microphoneVolumeChangedHandler; } catch { }
         try { _audioService.MicrophoneInputLevelChanged -= _microphoneInputLevelChangedHandler; } catch { }
         try { _audioService.MicrophoneFormatChanged -= _formatChangedHandler; } catch { }
+        try { _audioService.OperationFailed -= _operationFailedHandler; } catch { }
+        try { _audioService.CaptureSessionChanged -= _captureSessionChangedHandler; } catch { }
+        if (_priorityService != null)
+        {
+            try { _priorityService.PriorityListChanged -= _priorityListChangedHandler; } catch { }
+        }
+        if (_volumeLockService != null)
+        {
+            try { _volumeLockService.LocksChanged -= _volumeLocksChangedHandler; } catch { }
+        }
+        if (_aliasService != null)
+        {
+            try { _aliasService.AliasesChanged -= _aliasesChangedHandler; } catch { }
+        }
+        if (_hiddenDeviceService != null)
+        {
+            try { _hiddenDeviceService.HiddenDevicesChanged -= _hiddenDevicesChangedHandler; } catch { }
+        }
+        if (_orderService != null)
+        {
+            try { _orderService.OrderChanged -= _orderChangedHandler; } catch { }
+        }
+        if (_undoService != null)
+        {
+            try { _undoService.HistoryChanged -= _undoHistoryChangedHandler; } catch { }
+        }
+        if (_clippingService != null)
+        {
+            try { _clippingService.Clipped -= _clippedHandler; } catch { }
+            try { _clippingService.CountsReset -= _clipCountsResetHandler; } catch { }
+        }
+        if (_meterSettings != null)
+        {
+            try { _meterSettings.Changed -= _meterSettingsChangedHandler; } catch { }
+        }
+        if (_groupService != null)
+        {
+            try { _groupService.Changed -= _groupsChangedHandler; } catch { }
+        }
+        if (_appearance != null)
+        {
+            try { _appearance.Changed -= _appearanceChangedHandler; } catch { }
+        }
     }
 }
@@ -1,6 +1,7 @@
 using Microsoft.UI.Dispatching;
 using CommunityToolkit.Mvvm.ComponentModel;
 using CommunityToolkit.Mvvm.Input;
+using MicrophoneManager.WinUI.Models;
 using MicrophoneManager.WinUI.Services;
 
 namespace MicrophoneManager.WinUI.ViewModels;
@@ -8,41 +9,187 @@ namespace MicrophoneManager.WinUI.ViewModels;
 public partial class TrayViewModel : ObservableObject, IDisposable
 {
     private readonly IAudioDeviceService _audioService;
+    private readonly AudioSnapshotService _snapshotService;
+    private readonly DiagnosticsService _diagnosticsService;
+    private readonly CommunicationsDuckingService _duckingService;
+    private readonly DevicePriorityService? _priorityService;
+    private readonly ProfileService? _profileService;
+    private readonly QuietHoursService? _quietHoursService;
+    private readonly DeviceAliasService? _aliasService;
+    private readonly HiddenDeviceService? _hiddenDeviceService;
+    private readonly DeviceOrderService? _orderService;
+    private readonly UndoService? _undoService;
+    private readonly IPreferencesStore? _preferences;
     private readonly Action<bool> _updateIconCallback;
     private readonly DispatcherQueue? _dispatcherQueue;
     private readonly EventHandler<AudioDeviceService.DefaultMicrophoneVolumeChangedEventArgs> _defaultVolumeChangedHandler;
+    private readonly EventHandler<AudioDeviceService.MicrophoneVolumeChangedEventArgs> _microphoneVolumeChangedHandler;
+
+    // Mute state per microphone for "Mute all", kept current from volume events between refreshes
+    private readonly Dictionary<string, bool> _mutedById = new(StringComparer.OrdinalIgnoreCase);
     private bool _disposed;
 
+    // Volume change per "Volume up" / "Volume down" hotkey press
+    private const double VolumeStepPercent = 5.0;
+
     [ObservableProperty]
     private string _tooltipText = "Microphone Manager";
 
     [ObservableProperty]
+    [NotifyPropertyChangedFor(nameof(MuteMenuText))]
     private bool _isMuted;
 
+    public string MuteMenuText => IsMuted ? "Unmute microphone" : "Mute microphone";
+
+    /// <summary>
+    /// Raised after the tray menu or a hotkey mutes or unmutes, for the on-screen banner.
+    /// </summary>
+    public event EventHandler<MuteToggledEventArgs>? MuteToggled;
+
     [ObservableProperty]
     private bool _isStartupEnabled;
 
+    /// <summary>
+    /// Whether Windows lowers other sounds when it detects communications activity.
+    /// </summary>
+    [ObservableProperty]
+    private bool _isDuckingEnabled;
+
+    /// <summary>
+    /// Whether the highest-priority connected microphone automatically becomes the default.
+    /// </summary>
+    [ObservableProperty]
+    private bool _isAutoFailoverEnabled;
+
+    [ObservableProperty]
+    [NotifyPropertyChangedFor(nameof(MuteAllMenuText))]
+    private bool _areAllMuted;
+
+    /// <summary>
+    /// Last saved audio state, restored by <see cref="RestoreSnapshotCommand"/>.
+    /// </summary>
+    [ObservableProperty]
+    [NotifyPropertyChangedFor(nameof(HasSnapshot))]
+    private AudioSnapshot? _savedSnapshot;
+
+    public bool HasSnapshot => SavedSnapshot != null;
+
+    public string MuteAllMenuText => AreAllMuted ? "Unmute all microphones" : "Mute all microphones";
+
+    [ObservableProperty]
+    private List<OutputDevice> _outputDevices = new();
+
+    /// <summary>
+    /// Microphones for the tray menu: in the flyout's sort order, without hidden ones.
+    /// </summary>
+    [ObservableProperty]
+    private IReadOnlyList<MicrophoneDevice> _microphones = Array.Empty<MicrophoneDevice>();
+
+    /// <summary>
+    /// Saved profiles, listed in the tray menu for one-click switching.
+    /// </summary>
+    [ObservableProperty]
+    private IReadOnlyList<AudioProfile> _profiles = Array.Empty<AudioProfile>();
+
+    /// <summary>
+    /// Whether there's a change made through the app to undo.
+    /// </summary>
+    [ObservableProperty]
+    [NotifyPropertyChangedFor(nameof(UndoMenuText))]
+    private bool _canUndo;
+
+    public string UndoMenuText => _undoService?.NextDescription is string description ? $"Undo {description}" : "Undo";
+
     public string StartupMenuText => IsStartupEnabled ? "✓ Start with Windows" : "Start with Windows";
 
-    public TrayViewModel(IAudioDeviceService audioService, Action<bool> updateIconCallback)
+    public TrayViewModel(
+        IAudioDeviceService audioService,
+        Action<bool> updateIconCallback,
+        DevicePriorityService? priorityService = null,
+        ProfileService? profileService = null,
+        QuietHoursService? quietHoursService = null,
+        DeviceAliasService? aliasService = null,
+        HiddenDeviceService? hiddenDeviceService = null,
+        DeviceOrderService? orderService = null,
+        UndoService? undoService = null,
+        IPreferencesStore? preferences = null)
     {
         _audioService = audioService;
+        _snapshotService = new AudioSnapshotService(audioService);
+        _diagnosticsService = new DiagnosticsService(audioService);
+        _duckingService = new CommunicationsDuckingService(preferences);
+        _priorityService = priorityService;
+        _profileService = profileService;
+        _quietHoursService = quietHoursService;
+        _aliasService = aliasService;
+        _hiddenDeviceService = hiddenDeviceService;
+        _orderService = orderService;
+        _undoService = undoService;
+        _preferences = preferences;
         _updateIconCallback = updateIconCallback;
         _dispatcherQueue = DispatcherQueue.GetForCurrentThread();
 
         // Subscribe to device changes
         _audioService.DefaultDeviceChanged += OnDefaultDeviceChanged;
         _audioService.DevicesChanged += OnDevicesChanged;
+        _audioService.OutputDevicesChanged += OnOutputDevicesChanged;
 
         // Subscribe to default mic volume/mute changes (including external changes)
-        _defaultVolumeChangedHandler = (s, e) => InvokeOnUiThread(UpdateState);
+        _defaultVolumeChangedHandler = (s, e) => InvokeOnUiThread(UpdateDefaultState);
         _audioService.DefaultMicrophoneVolumeChanged += _defaultVolumeChangedHandler;
 
+        // Any mic muted elsewhere changes "Mute all". Volume events arrive for every slider tick,
+        // so they update the cached state instead of enumerating the microphones again.
+        _microphoneVolumeChangedHandler = (s, e) => InvokeOnUiThread(() =>
+        {
+            if (!_mutedById.ContainsKey(e.DeviceId)) return;
+
+            _mutedById[e.DeviceId] = e.IsMuted;
+            UpdateAreAllMuted();
+        });
+        _audioService.MicrophoneVolumeChanged += _microphoneVolumeChangedHandler;
+
+        if (_profileService != null)
+        {
+            _profileService.ProfilesChanged += OnProfilesChanged;
+        }
+
+        if (_quietHoursService != null)
+        {
+            _quietHoursService.StateChanged += OnQuietHoursStateChanged;
+        }
+
+        if (_aliasService != null)
+        {
+            _aliasService.AliasesChanged += OnAliasesChanged;
+        }
+
+        if (_hiddenDeviceService != null)
+        {
+            _hiddenDeviceService.HiddenDevicesChanged += OnMicrophoneListChanged;
+        }
+
+        if (_orderService != null)
+        {
+            _orderService.OrderChanged += OnMicrophoneListChanged;
+        }
+
+        if (_undoService != null)
+        {
+            _undoService.HistoryChanged += OnUndoHistoryChanged;
+        }
+
         // Initial state
         UpdateState();
+        UpdateMicrophones();
+        UpdateOutputDevices();
+        UpdateProfiles();
+        UpdateUndo();
 
-        // Check startup state
-        IsStartupEnabled = StartupService.IsStartupEnabled();
+        // Checking a scheduled task starts schtasks.exe, so the answer arrives later
+        _ = RefreshStartupStateAsync();
+        IsDuckingEnabled = _duckingService.IsDuckingEnabled();
+        IsAutoFailoverEnabled = _priorityService?.IsAutoFailoverEnabled ?? false;
     }
 
     private void InvokeOnUiThread(Action action)
@@ -58,14 +205,26 @@ public partial class TrayViewModel : ObservableObject, IDisposable
     }
 
     private void UpdateState()
+    {
+        RefreshMuteStates();
+        UpdateDefaultState();
+    }
+
+    private void UpdateDefaultState()
     {
         var defaultMic = _audioService.GetDefaultMicrophone();
         if (defaultMic != null)
         {
             IsMuted = _audioService.IsDefaultMicrophoneMuted();
+            var name = _aliasService?.DisplayNameFor(defaultMic) ?? defaultMic.Name;
             TooltipText = IsMuted
-                ? $"{defaultMic.Name} (Muted)"
-                : defaultMic.Name;
+                ? $"{name} (Muted)"
+                : name;
+
+            if (_quietHoursService is { IsActive: true, QuietUntil: { } quietUntil })
+            {
+                TooltipText += $" - quiet hours until {quietUntil:t}";
+            }
         }
         else
         {
@@ -76,14 +235,155 @@ public partial class TrayViewModel : ObservableObject, IDisposable
         _updateIconCallback?.Invoke(IsMuted);
     }
 
+    private void RefreshMuteStates()
+    {
+        _mutedById.Clear();
+        foreach (var mic in _audioService.GetMicrophones())
+        {
+            _mutedById[mic.Id] = mic.IsMuted;
+        }
+
+        UpdateAreAllMuted();
+    }
+
+    private void UpdateAreAllMuted()
+    {
+        AreAllMuted = _mutedById.Count > 0 && _mutedById.Values.All(muted => muted);
+    }
+
     private void OnDefaultDeviceChanged(object? sender, EventArgs e)
+    {
+        InvokeOnUiThread(() =>
+        {
+            UpdateState();
+            UpdateMicrophones();
+        });
+    }
+
+    private void OnQuietHoursStateChanged(object? sender, EventArgs e)
     {
         InvokeOnUiThread(UpdateState);
     }
 
+    private void OnAliasesChanged(object? sender, EventArgs e)
+    {
+        InvokeOnUiThread(() =>
+        {
+            UpdateState();
+            UpdateMicrophones();
+        });
+    }
+
+    private void OnMicrophoneListChanged(object? sender, EventArgs e)
+    {
+        InvokeOnUiThread(UpdateMicrophones);
+    }
+
+    private void UpdateMicrophones()
+    {
+        var listed = _audioService.GetMicrophones().Where(m => _hiddenDeviceService?.ShouldShow(m) ?? true).ToList();
+        foreach (var device in listed.Where(m => m.IsDefault || m.IsInUse))
+        {
+            _orderService?.RecordUsed(DevicePriorityService.KeyFor(device));
+        }
+
+        Microphones = _orderService?.SortForDisplay(listed, DevicePriorityService.KeyFor, DisplayNameFor, m => m.IsDefault ? 0 : m.IsDefaultCommunication ? 1 : 2)
+            ?? listed;
+    }
+
+    /// <summary>
+    /// Re-reads and re-sorts the microphones, e.g. when the tray menu opens and the last-used or
+    /// activity order may have changed since the last device notification.
+    /// </summary>
+    public void RefreshMicrophones()
+    {
+        if (_disposed) return;
+        UpdateMicrophones();
+    }
+
+    /// <summary>
+    /// The user's alias for a device, or the name Windows reports.
+    /// </summary>
+    public string DisplayNameFor(MicrophoneDevice device)
+    {
+        return _aliasService?.DisplayNameFor(device) ?? device.Name;
+    }
+
+    private void OnUndoHistoryChanged(object? sender, EventArgs e)
+    {
+        InvokeOnUiThread(UpdateUndo);
+    }
+
+    private void UpdateUndo()
+    {
+        CanUndo = _undoService?.CanUndo ?? false;
+
+        // The description can change without CanUndo changing
+        OnPropertyChanged(nameof(UndoMenuText));
+    }
+
     private void OnDevicesChanged(object? sender, EventArgs e)
     {
-        InvokeOnUiThread(UpdateState);
+        InvokeOnUiThread(() =>
+        {
+            UpdateState();
+            UpdateMicrophones();
+            UpdateOutputDevices();
+        });
+    }
+
+    private void OnOutputDevicesChanged(object? sender, EventArgs e)
+    {
+        InvokeOnUiThread(UpdateOutputDevices);
+    }
+
+    private void UpdateOutputDevices()
+    {
+        OutputDevices = _audioService.GetOutputDevices();
+    }
+
+    private void OnProfilesChanged(object? sender, EventArgs e)
+    {
+        InvokeOnUiThread(UpdateProfiles);
+    }
+
+    private void UpdateProfiles()
+    {
+        Profiles = _profileService?.GetProfiles() ?? Array.Empty<AudioProfile>();
+    }
+
+    [RelayCommand]
+    private async Task SetDefaultOutputAsync(string? deviceId)
+    {
+        if (string.IsNullOrEmpty(deviceId)) return;
+
+        try
+        {
+            _undoService?.RecordOutputDefault();
+            await _audioService.SetDefaultOutputDeviceAsync(deviceId, CancellationToken.None);
+            UpdateOutputDevices();
+        }
+        catch (Exception ex)
+        {
+            System.Diagnostics.Debug.WriteLine($"SetDefaultOutputAsync failed: {ex}");
+        }
+    }
+
+    [RelayCommand]
+    private void ToggleDefaultOutputMute()
+    {
+        var defaultId = _audioService.GetDefaultOutputDeviceId(NAudio.CoreAudioApi.Role.Console);
+        if (defaultId == null) return;
+
+        try
+        {
+            _audioService.ToggleOutputMute(defaultId);
+            UpdateOutputDevices();
+        }
+        catch (Exception ex)
+        {
+            System.Diagnostics.Debug.WriteLine($"ToggleDefaultOutputMute failed: {ex}");
+        }
     }
 
     [RelayCommand]
@@ -91,8 +391,14 @@ public partial class TrayViewModel : ObservableObject, IDisposable
     {
         try
         {
+            if (_audioService.GetDefaultDeviceId(NAudio.CoreAudioApi.Role.Console) is string defaultId)
+            {
+                _undoService?.RecordDevice(defaultId, "mute change");
+            }
+
             IsMuted = await _audioService.ToggleDefaultMicrophoneMuteAsync(CancellationToken.None);
             UpdateState();
+            MuteToggled?.Invoke(this, new MuteToggledEventArgs(IsMuted, allMicrophones: false));
         }
         catch (Exception ex)
         {
@@ -100,13 +406,226 @@ public partial class TrayViewModel : ObservableObject, IDisposable
         }
     }
 
+    /// <summary>
+    /// Makes the next active microphone (in list order) the default for both roles.
+    /// </summary>
+    [RelayCommand]
+    private async Task CycleDefaultMicrophoneAsync()
+    {
+        // Hidden microphones are skipped, unless one of them is the current default
+        var listed = _audioService.GetMicrophones()
+            .Where(m => m.IsDefault || (_hiddenDeviceService?.ShouldShow(m) ?? true));
+        var microphones = (_orderService?.Sort(listed, DevicePriorityService.KeyFor) ?? listed.ToList()).ToList();
+        if (microphones.Count < 2) return;
+
+        var currentIndex = microphones.FindIndex(m => m.IsDefault);
+        var next = microphones[(currentIndex + 1) % microphones.Count];
+
+        try
+        {
+            _undoService?.RecordDefaults();
+            await _audioService.SetDefaultMicrophoneAsync(next.Id, CancellationToken.None);
+            UpdateState();
+        }
+        catch (Exception ex)
+        {
+            System.Diagnostics.Debug.WriteLine($"CycleDefaultMicrophoneAsync failed: {ex}");
+        }
+    }
+
+    [RelayCommand]
+    private async Task SetDefaultMicrophoneAsync(string? deviceId)
+    {
+        if (string.IsNullOrEmpty(deviceId)) return;
+
+        try
+        {
+            _undoService?.RecordDefaults();
+            await _audioService.SetDefaultMicrophoneAsync(deviceId, CancellationToken.None);
+            UpdateState();
+            UpdateMicrophones();
+        }
+        catch (Exception ex)
+        {
+            System.Diagnostics.Debug.WriteLine($"SetDefaultMicrophoneAsync failed: {ex}");
+        }
+    }
+
+    [RelayCommand]
+    private void VolumeUp() => StepDefaultVolume(VolumeStepPercent);
+
+    [RelayCommand]
+    private void VolumeDown() => StepDefaultVolume(-VolumeStepPercent);
+
+    private void StepDefaultVolume(double deltaPercent)
+    {
+        var defaultMic = _audioService.GetDefaultMicrophone();
+        if (defaultMic == null) return;
+
+        var volumePercent = Math.Clamp(Math.Round(defaultMic.VolumeLevel * 100.0) + deltaPercent, 0.0, 100.0);
+        _undoService?.RecordDevice(defaultMic.Id);
+        _audioService.SetDefaultMicrophoneVolumePercent(volumePercent);
+    }
+
+    [RelayCommand]
+    private void ToggleMuteAll()
+    {
+        try
+        {
+            _undoService?.RecordAll(AreAllMuted ? "unmute all" : "mute all");
+            _audioService.SetAllMicrophonesMuted(!AreAllMuted);
+            UpdateState();
+            MuteToggled?.Invoke(this, new MuteToggledEventArgs(AreAllMuted, allMicrophones: true));
+        }
+        catch (Exception ex)
+        {
+            System.Diagnostics.Debug.WriteLine($"ToggleMuteAll failed: {ex}");
+        }
+    }
+
+    [RelayCommand]
+    private void SaveSnapshot()
+    {
+        SavedSnapshot = _snapshotService.TakeSnapshot();
+    }
+
+    [RelayCommand]
+    private async Task RestoreSnapshotAsync()
+    {
+        if (SavedSnapshot == null) return;
+
+        try
+        {
+            _undoService?.RecordAll("snapshot restore");
+            await _snapshotService.RestoreSnapshotAsync(SavedSnapshot, CancellationToken.None);
+            UpdateState();
+            UpdateOutputDevices();
+        }
+        catch (Exception ex)
+        {
+            System.Diagnostics.Debug.WriteLine($"RestoreSnapshotAsync failed: {ex}");
+        }
+    }
+
+    [RelayCommand]
+    private async Task ApplyProfileAsync(string? name)
+    {
+        if (_profileService == null || string.IsNullOrEmpty(name)) return;
+
+        try
+        {
+            _undoService?.RecordAll("profile change");
+            await _profileService.ApplyProfileAsync(name, CancellationToken.None);
+            UpdateState();
+            UpdateOutputDevices();
+        }
+        catch (Exception ex)
+        {
+            System.Diagnostics.Debug.WriteLine($"ApplyProfileAsync failed: {ex}");
+        }
+    }
+
+    /// <summary>
+    /// Reverts the most recent change made through the app.
+    /// </summary>
+    [RelayCommand]
+    private async Task UndoAsync()
+    {
+        if (_undoService == null) return;
+
+        try
+        {
+            await _undoService.UndoAsync(CancellationToken.None);
+            UpdateState();
+            UpdateMicrophones();
+            UpdateOutputDevices();
+        }
+        catch (Exception ex)
+        {
+            System.Diagnostics.Debug.WriteLine($"UndoAsync failed: {ex}");
+        }
+    }
+
+    /// <summary>
+    /// Saves the current state as a profile; without a name it gets the next "Profile N".
+    /// </summary>
+    [RelayCommand]
+    private void SaveProfile(string? name)
+    {
+        if (_profileService == null) return;
+
+        _profileService.SaveProfile(string.IsNullOrWhiteSpace(name) ? _profileService.GetNewProfileName() : name);
+    }
+
+    [RelayCommand]
+    private void DeleteProfile(string? name)
+    {
+        if (_profileService == null || string.IsNullOrEmpty(name)) return;
+
+        _profileService.DeleteProfile(name);
+    }
+
+    /// <summary>
+    /// JSON diagnostics report for "Copy diagnostics"; the view puts it on the clipboard.
+    /// </summary>
+    public string BuildDiagnosticsJson()
+    {
+        return _diagnosticsService.BuildReport().ToJson();
+    }
+
+    private async Task RefreshStartupStateAsync()
+    {
+        if (_preferences == null) return;
+
+        try
+        {
+            IsStartupEnabled = await StartupService.IsStartupEnabledAsync(_preferences);
+            OnPropertyChanged(nameof(StartupMenuText));
+        }
+        catch (Exception ex)
+        {
+            System.Diagnostics.Debug.WriteLine($"Reading startup state failed: {ex}");
+        }
+    }
+
     [RelayCommand]
-    private void ToggleStartup()
+    private async Task ToggleStartupAsync()
     {
-        IsStartupEnabled = StartupService.ToggleStartup();
+        if (_preferences == null) return;
+
+        var preferences = _preferences;
+        IsStartupEnabled = await Task.Run(() => StartupService.ToggleStartup(preferences));
         OnPropertyChanged(nameof(StartupMenuText));
     }
 
+    [RelayCommand]
+    private void ToggleDucking()
+    {
+        IsDuckingEnabled = _duckingService.ToggleDucking();
+    }
+
+    [RelayCommand]
+    private async Task ToggleAutoFailoverAsync()
+    {
+        if (_priorityService == null) return;
+
+        _priorityService.IsAutoFailoverEnabled = !_priorityService.IsAutoFailoverEnabled;
+        IsAutoFailoverEnabled = _priorityService.IsAutoFailoverEnabled;
+
+        try
+        {
+            // Switch right away rather than waiting for the next device change
+            if (await _priorityService.ApplyPreferredDefaultAsync(CancellationToken.None))
+            {
+                UpdateState();
+            }
+        }
+        catch (Exception ex)
+        {
+            System.Diagnostics.Debug.WriteLine($"ToggleAutoFailoverAsync failed: {ex}");
+        }
+    }
+
     [RelayCommand]
     private void Exit()
     {
@@ -121,6 +640,49 @@ public partial class TrayViewModel : ObservableObject, IDisposable
 
         try { _audioService.DefaultDeviceChanged -= OnDefaultDeviceChanged; } catch { }
         try { _audioService.DevicesChanged -= OnDevicesChanged; } catch { }
+        try { _audioService.OutputDevicesChanged -= OnOutputDevicesChanged; } catch { }
         try { _audioService.DefaultMicrophoneVolumeChanged -= _defaultVolumeChangedHandler; } catch { }
+        try { _audioService.MicrophoneVolumeChanged -= _microphoneVolumeChangedHandler; } catch { }
+        if (_profileService != null)
+        {
+            try { _profileService.ProfilesChanged -= OnProfilesChanged; } catch { }
+        }
+        if (_quietHoursService != null)
+        {
+            try { _quietHoursService.StateChanged -= OnQuietHoursStateChanged; } catch { }
+        }
+        if (_aliasService != null)
+        {
+            try { _aliasService.AliasesChanged -= OnAliasesChanged; } catch { }
+        }
+        if (_hiddenDeviceService != null)
+        {
+            try { _hiddenDeviceService.HiddenDevicesChanged -= OnMicrophoneListChanged; } catch { }
+        }
+        if (_orderService != null)
+        {
+            try { _orderService.OrderChanged -= OnMicrophoneListChanged; } catch { }
+        }
+        if (_undoService != null)
+        {
+            try { _undoService.HistoryChanged -= OnUndoHistoryChanged; } catch { }
+        }
+        try { _diagnosticsService.Dispose(); } catch { }
+    }
+
+    public sealed class MuteToggledEventArgs : EventArgs
+    {
+        public MuteToggledEventArgs(bool isMuted, bool allMicrophones)
+        {
+            IsMuted = isMuted;
+            AllMicrophones = allMicrophones;
+        }
+
+        public bool IsMuted { get; }
+
+        /// <summary>
+        /// "Mute all microphones" rather than the default microphone alone.
+        /// </summary>
+        public bool AllMicrophones { get; }
     }
 }
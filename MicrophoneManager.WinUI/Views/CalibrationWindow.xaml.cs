@@ -0,0 +1,185 @@
+using System.Globalization;
+using Microsoft.Extensions.DependencyInjection;
+using Microsoft.Extensions.Logging;
+using Microsoft.UI.Dispatching;
+using Microsoft.UI.Xaml;
+using MicrophoneManager.WinUI.Models;
+using MicrophoneManager.WinUI.Services;
+
+namespace MicrophoneManager.WinUI.Views;
+
+/// <summary>
+/// Guides the user through a <see cref="CalibrationService"/> run for one microphone and offers to
+/// apply the recommended volume and boost.
+/// </summary>
+public sealed partial class CalibrationWindow : Window
+{
+    private const int ClientWidth = 520;
+    private const int ClientHeight = 400;
+
+    private readonly CalibrationService _calibrationService;
+    private readonly ILogger<CalibrationWindow> _logger;
+    private readonly string _deviceId;
+    private readonly DispatcherQueueTimer _timeTimer;
+    private CancellationTokenSource? _measuring;
+    private CalibrationResult? _result;
+    private DateTime _startedUtc;
+
+    public CalibrationWindow(string deviceId, string deviceName)
+    {
+        _calibrationService = App.Host.Services.GetRequiredService<CalibrationService>();
+        _logger = App.Host.Services.GetRequiredService<ILogger<CalibrationWindow>>();
+        _deviceId = deviceId;
+
+        InitializeComponent();
+        WindowTheme.Track(this);
+
+        AppWindow.ResizeClient(new Windows.Graphics.SizeInt32(ClientWidth, ClientHeight));
+        DeviceText.Text = deviceName;
+
+        _timeTimer = DispatcherQueue.CreateTimer();
+        _timeTimer.Interval = TimeSpan.FromMilliseconds(100);
+        _timeTimer.Tick += (s, e) =>
+            TimeBar.Value = Math.Min(100, (DateTime.UtcNow - _startedUtc) / CalibrationService.DefaultDuration * 100);
+
+        Closed += CalibrationWindow_Closed;
+    }
+
+    public string DeviceId => _deviceId;
+
+    /// <summary>
+    /// Opens the calibration window for a microphone, replacing one open for another microphone.
+    /// </summary>
+    public static CalibrationWindow ShowCalibration(string deviceId, string deviceName)
+    {
+        if (App.CalibrationWindow is CalibrationWindow existing)
+        {
+            if (existing.DeviceId == deviceId)
+            {
+                existing.Activate();
+                return existing;
+            }
+
+            existing.Close();
+        }
+
+        var window = new CalibrationWindow(deviceId, deviceName);
+        App.CalibrationWindow = window;
+        window.Activate();
+        return window;
+    }
+
+    private async void StartButton_Click(object sender, RoutedEventArgs e)
+    {
+        _result = null;
+        ApplyButton.IsEnabled = false;
+        StartButton.IsEnabled = false;
+        ResultText.Text = "Listening… keep talking.";
+
+        _measuring = new CancellationTokenSource();
+        _startedUtc = DateTime.UtcNow;
+        _timeTimer.Start();
+
+        try
+        {
+            var progress = new Progress<double>(db => LevelBar.Value = ObsMeterMath.DbToPercent(db));
+            _result = await _calibrationService.MeasureAsync(_deviceId, CalibrationService.DefaultDuration, progress, _measuring.Token);
+            ShowResult(_result);
+        }
+        catch (OperationCanceledException)
+        {
+            return;
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Calibration failed");
+            ResultText.Text = $"Calibration failed: {ex.Message}";
+        }
+        finally
+        {
+            _timeTimer.Stop();
+            _measuring = null;
+        }
+
+        LevelBar.Value = 0;
+        StartButton.Content = "Start again";
+        StartButton.IsEnabled = true;
+        ApplyButton.IsEnabled = _result?.IsChangeRecommended == true;
+    }
+
+    private void ShowResult(CalibrationResult? result)
+    {
+        if (result == null)
+        {
+            ResultText.Text = "The microphone is muted or no longer connected. Unmute or reconnect it and try again.";
+            return;
+        }
+
+        if (!result.HeardSpeech)
+        {
+            ResultText.Text = "No speech was picked up. Check that this is the microphone you're talking into and try again.";
+            return;
+        }
+
+        var lines = new List<string>
+        {
+            string.Format(CultureInfo.CurrentCulture,
+                "While you spoke the average was {0:0.0} dBFS and the loudest peak {1:0.0} dBFS; the background sat at {2:0.0} dBFS.",
+                result.AverageDb, result.PeakDb, result.NoiseFloorDb),
+        };
+
+        if (!result.IsChangeRecommended)
+        {
+            lines.Add($"That's about right for the {CalibrationService.TargetAverageDb} dBFS target. No change needed.");
+        }
+        else
+        {
+            var change = string.Format(CultureInfo.CurrentCulture,
+                "Recommended: volume {0:0.0} dB (now {1:0.0} dB)", result.RecommendedVolumeDb, result.CurrentVolumeDb);
+            if (result.ChangesBoost)
+            {
+                change += string.Format(CultureInfo.CurrentCulture,
+                    " and boost +{0:0} dB (now +{1:0} dB)", result.RecommendedBoostDb, result.CurrentBoostDb);
+            }
+
+            lines.Add(change + ".");
+        }
+
+        if (result.NoiseFloorDb > -50)
+        {
+            lines.Add("The background is loud. A quieter room or sitting closer to the microphone will help more than more gain.");
+        }
+
+        ResultText.Text = string.Join(Environment.NewLine + Environment.NewLine, lines);
+    }
+
+    private void ApplyButton_Click(object sender, RoutedEventArgs e)
+    {
+        if (_result == null) return;
+
+        if (_calibrationService.Apply(_deviceId, _result))
+        {
+            ApplyButton.IsEnabled = false;
+            ResultText.Text += Environment.NewLine + Environment.NewLine + "Applied.";
+        }
+        else
+        {
+            ResultText.Text += Environment.NewLine + Environment.NewLine + "The new level couldn't be set.";
+        }
+    }
+
+    private void CloseButton_Click(object sender, RoutedEventArgs e)
+    {
+        Close();
+    }
+
+    private void CalibrationWindow_Closed(object sender, WindowEventArgs args)
+    {
+        try { _measuring?.Cancel(); } catch { }
+        _timeTimer.Stop();
+        if (ReferenceEquals(App.CalibrationWindow, this))
+        {
+            App.CalibrationWindow = null;
+        }
+    }
+}
@@ -0,0 +1,103 @@
+using System.Collections.ObjectModel;
+using System.Diagnostics;
+using Microsoft.Extensions.DependencyInjection;
+using Microsoft.Extensions.Logging;
+using Microsoft.UI.Xaml;
+using MicrophoneManager.WinUI.Models;
+using MicrophoneManager.WinUI.Services;
+
+namespace MicrophoneManager.WinUI.Views;
+
+/// <summary>
+/// Lists the changes kept by <see cref="ChangeHistoryService"/> and follows new ones while open.
+/// </summary>
+public sealed partial class HistoryWindow : Window
+{
+    private const int ClientWidth = 720;
+    private const int ClientHeight = 480;
+
+    private readonly ChangeHistoryService _historyService;
+    private readonly ILogger<HistoryWindow> _logger;
+    private readonly ObservableCollection<ChangeHistoryEntry> _entries = new();
+
+    public HistoryWindow()
+    {
+        _historyService = App.Host.Services.GetRequiredService<ChangeHistoryService>();
+        _logger = App.Host.Services.GetRequiredService<ILogger<HistoryWindow>>();
+
+        InitializeComponent();
+        WindowTheme.Track(this);
+
+        AppWindow.ResizeClient(new Windows.Graphics.SizeInt32(ClientWidth, ClientHeight));
+        EntriesList.ItemsSource = _entries;
+        Refresh();
+
+        _historyService.HistoryChanged += HistoryService_HistoryChanged;
+        Closed += HistoryWindow_Closed;
+    }
+
+    /// <summary>
+    /// Opens the history window, or brings the open one forward.
+    /// </summary>
+    public static HistoryWindow ShowHistory()
+    {
+        if (App.HistoryWindow is HistoryWindow existing)
+        {
+            existing.Activate();
+            return existing;
+        }
+
+        var window = new HistoryWindow();
+        App.HistoryWindow = window;
+        window.Activate();
+        return window;
+    }
+
+    public static string FormatTime(DateTime timestamp)
+    {
+        var local = timestamp.ToLocalTime();
+        return local.Date == DateTime.Today ? local.ToString("T") : local.ToString("g");
+    }
+
+    private void Refresh()
+    {
+        _entries.Clear();
+        foreach (var entry in _historyService.GetEntries())
+        {
+            _entries.Add(entry);
+        }
+
+        EmptyText.Visibility = _entries.Count == 0 ? Visibility.Visible : Visibility.Collapsed;
+    }
+
+    private void HistoryService_HistoryChanged(object? sender, EventArgs e)
+    {
+        DispatcherQueue.TryEnqueue(Refresh);
+    }
+
+    private void ClearButton_Click(object sender, RoutedEventArgs e)
+    {
+        _historyService.Clear();
+    }
+
+    private void OpenFileButton_Click(object sender, RoutedEventArgs e)
+    {
+        if (_historyService.FilePath is not { } path) return;
+
+        try
+        {
+            _historyService.Flush();
+            Process.Start(new ProcessStartInfo("notepad.exe", $"\"{path}\"") { UseShellExecute = true });
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Open change history failed");
+        }
+    }
+
+    private void HistoryWindow_Closed(object sender, WindowEventArgs args)
+    {
+        try { _historyService.HistoryChanged -= HistoryService_HistoryChanged; } catch { }
+        App.HistoryWindow = null;
+    }
+}
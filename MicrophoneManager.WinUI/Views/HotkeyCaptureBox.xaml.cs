@@ -0,0 +1,109 @@
+using Microsoft.UI.Xaml;
+using Microsoft.UI.Xaml.Controls;
+using Microsoft.UI.Xaml.Input;
+using MicrophoneManager.WinUI.Models;
+using MicrophoneManager.WinUI.Services;
+
+namespace MicrophoneManager.WinUI.Views;
+
+/// <summary>
+/// Shows a hotkey and records a new one: click it, then press the combination. The modifiers held
+/// so far are previewed as they're pressed. What to do with the result is up to the owner.
+/// </summary>
+public sealed partial class HotkeyCaptureBox : UserControl
+{
+    private HotkeyBinding? _binding;
+    private HotkeyCapture? _capture;
+
+    /// <summary>
+    /// Raised when the box starts listening, so the owner can release the global hotkeys.
+    /// </summary>
+    public event EventHandler? CaptureStarted;
+
+    /// <summary>
+    /// Raised with the combination pressed, before <see cref="CaptureEnded"/>.
+    /// </summary>
+    public event EventHandler<HotkeyBinding>? Captured;
+
+    /// <summary>
+    /// Raised when listening stops, whether a combination was pressed or not.
+    /// </summary>
+    public event EventHandler? CaptureEnded;
+
+    public HotkeyCaptureBox()
+    {
+        InitializeComponent();
+        ShowBinding();
+    }
+
+    /// <summary>
+    /// The binding shown while not capturing; null shows "Off".
+    /// </summary>
+    public HotkeyBinding? Binding
+    {
+        get => _binding;
+        set
+        {
+            _binding = value;
+            if (_capture == null) ShowBinding();
+        }
+    }
+
+    public bool IsCapturing => _capture != null;
+
+    private void CaptureButton_Click(object sender, RoutedEventArgs e)
+    {
+        if (_capture != null) return;
+
+        _capture = new HotkeyCapture();
+        CaptureButton.Content = _capture.Preview;
+        CaptureStarted?.Invoke(this, EventArgs.Empty);
+    }
+
+    private void CaptureButton_PreviewKeyDown(object sender, KeyRoutedEventArgs e)
+    {
+        if (_capture == null) return;
+
+        e.Handled = true;
+        var complete = _capture.KeyDown((uint)e.Key);
+        CaptureButton.Content = _capture.Preview;
+        if (!complete) return;
+
+        var binding = _capture.Binding;
+        EndCapture();
+        if (binding != null)
+        {
+            Captured?.Invoke(this, binding);
+        }
+
+        CaptureEnded?.Invoke(this, EventArgs.Empty);
+    }
+
+    private void CaptureButton_PreviewKeyUp(object sender, KeyRoutedEventArgs e)
+    {
+        if (_capture == null) return;
+
+        e.Handled = true;
+        _capture.KeyUp((uint)e.Key);
+        CaptureButton.Content = _capture.Preview;
+    }
+
+    private void CaptureButton_LostFocus(object sender, RoutedEventArgs e)
+    {
+        if (_capture == null) return;
+
+        EndCapture();
+        CaptureEnded?.Invoke(this, EventArgs.Empty);
+    }
+
+    private void EndCapture()
+    {
+        _capture = null;
+        ShowBinding();
+    }
+
+    private void ShowBinding()
+    {
+        CaptureButton.Content = _binding?.ToString() ?? "Off";
+    }
+}
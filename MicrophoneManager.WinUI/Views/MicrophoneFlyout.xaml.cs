@@ -1,7 +1,9 @@
 using Microsoft.Extensions.DependencyInjection;
 using Microsoft.UI.Xaml;
 using Microsoft.UI.Xaml.Controls;
+using Microsoft.UI.Xaml.Input;
 using Microsoft.UI.Xaml.Media;
+using MicrophoneManager.WinUI.Models;
 using MicrophoneManager.WinUI.ViewModels;
 using MicrophoneManager.WinUI.Services;
 using System;
@@ -19,8 +21,14 @@ public sealed partial class MicrophoneFlyout : UserControl
     public event EventHandler? ViewportHeightChanged;
 
     private double? _cardOuterHeight;
+
+    // Outer height of a group header row (24px buttons plus margin)
+    private const double GroupHeaderOuterHeight = 30.0;
     private bool _isUnloaded;
 
+    // Card whose context menu was opened last, for menu items that act on the card itself
+    private FrameworkElement? _contextMenuCard;
+
     public double? MeasuredCardOuterHeight => _cardOuterHeight;
 
     public bool IsDockedMode { get; set; }
@@ -31,17 +39,30 @@ public sealed partial class MicrophoneFlyout : UserControl
     {
         // Get ViewModel from DI
         var audioService = App.Host.Services.GetRequiredService<MicrophoneManager.WinUI.Services.IAudioDeviceService>();
-        ViewModel = new MicrophoneListViewModel(audioService);
+        var priorityService = App.Host.Services.GetRequiredService<MicrophoneManager.WinUI.Services.DevicePriorityService>();
+        var volumeLockService = App.Host.Services.GetRequiredService<MicrophoneManager.WinUI.Services.VolumeLockService>();
+        var aliasService = App.Host.Services.GetRequiredService<MicrophoneManager.WinUI.Services.DeviceAliasService>();
+        var hiddenDeviceService = App.Host.Services.GetRequiredService<MicrophoneManager.WinUI.Services.HiddenDeviceService>();
+        var orderService = App.Host.Services.GetRequiredService<MicrophoneManager.WinUI.Services.DeviceOrderService>();
+        var undoService = App.Host.Services.GetRequiredService<MicrophoneManager.WinUI.Services.UndoService>();
+        var clippingService = App.Host.Services.GetRequiredService<MicrophoneManager.WinUI.Services.ClippingService>();
+        var meterSettings = App.Host.Services.GetRequiredService<MicrophoneManager.WinUI.Services.MeterSettingsService>();
+        var levelMeter = App.Host.Services.GetRequiredService<MicrophoneManager.WinUI.Services.LevelMeterService>();
+        var groupService = App.Host.Services.GetRequiredService<MicrophoneManager.WinUI.Services.DeviceGroupService>();
+        var appearance = App.Host.Services.GetRequiredService<MicrophoneManager.WinUI.Services.AppearanceService>();
+        ViewModel = new MicrophoneListViewModel(audioService, priorityService, volumeLockService, aliasService, hiddenDeviceService, orderService, undoService, clippingService, meterSettings, levelMeter, groupService, appearance);
 
         InitializeComponent();
 
         ViewModel.Microphones.CollectionChanged += Microphones_CollectionChanged;
+        ViewModel.PropertyChanged += ViewModel_PropertyChanged;
 
         Unloaded += (s, e) =>
         {
             _isUnloaded = true;
 
             try { ViewModel.Microphones.CollectionChanged -= Microphones_CollectionChanged; } catch { }
+            try { ViewModel.PropertyChanged -= ViewModel_PropertyChanged; } catch { }
             try { ViewModel.Dispose(); } catch { }
         };
     }
@@ -109,10 +130,20 @@ public sealed partial class MicrophoneFlyout : UserControl
         {
             if (_isUnloaded) return;
             if (args.PropertyName == nameof(MicrophoneEntryViewModel.InputLevelPercent) ||
-                args.PropertyName == nameof(MicrophoneEntryViewModel.PeakLevelPercent))
+                args.PropertyName == nameof(MicrophoneEntryViewModel.RmsLevelPercent) ||
+                args.PropertyName == nameof(MicrophoneEntryViewModel.PeakLevelPercent) ||
+                args.PropertyName == nameof(MicrophoneEntryViewModel.MeterMode))
             {
                 host.DispatcherQueue.TryEnqueue(() => UpdateMeterVisuals(host, vm));
             }
+            else if (args.PropertyName == nameof(MicrophoneEntryViewModel.HistoryDbFs))
+            {
+                host.DispatcherQueue.TryEnqueue(() => UpdateHistoryVisuals(host, vm));
+            }
+            else if (args.PropertyName == nameof(MicrophoneEntryViewModel.MicTestWaveform))
+            {
+                host.DispatcherQueue.TryEnqueue(() => UpdateMicTestWaveformVisuals(host, vm));
+            }
         };
 
         subscription.ViewModel = vm;
@@ -139,7 +170,7 @@ public sealed partial class MicrophoneFlyout : UserControl
 
     private static void UpdateMeterVisuals(FrameworkElement host, MicrophoneEntryViewModel? vm)
     {
-        const double visibleMinDb = -60.0;
+        const double visibleMinDb = ObsMeterMath.VisibleMinDb;
         const double warningDb = -20.0;
         const double errorDb = -9.0;
 
@@ -152,21 +183,18 @@ public sealed partial class MicrophoneFlyout : UserControl
         if (host.FindName("MeterFill") is not Microsoft.UI.Xaml.Shapes.Rectangle fill) return;
         if (host.FindName("PeakMarker") is not Microsoft.UI.Xaml.Shapes.Rectangle marker) return;
 
+        var peakFill = host.FindName("PeakFill") as Microsoft.UI.Xaml.Shapes.Rectangle;
+
         var width = host.ActualWidth;
         if (width <= 0)
         {
             fill.Width = 0;
+            if (peakFill != null) peakFill.Width = 0;
             Microsoft.UI.Xaml.Controls.Canvas.SetLeft(marker, 0);
             return;
         }
 
-        static double RescaleObsPercentToVisible(double obsPercent)
-        {
-            var minObsPercent = ObsMeterMath.DbToPercent(visibleMinDb);
-            obsPercent = Math.Clamp(obsPercent, 0.0, 100.0);
-            if (obsPercent <= minObsPercent) return 0.0;
-            return Math.Clamp((obsPercent - minObsPercent) / (100.0 - minObsPercent) * 100.0, 0.0, 100.0);
-        }
+        static double RescaleObsPercentToVisible(double obsPercent) => ObsMeterMath.ToVisiblePercent(obsPercent);
 
         void SetZone(Microsoft.UI.Xaml.Shapes.Rectangle? rect, double leftPercentVisible, double rightPercentVisible)
         {
@@ -266,8 +294,10 @@ public sealed partial class MicrophoneFlyout : UserControl
             }
         }
 
-        // Live fill + peak marker use the same visible min scale.
-        var inputPercentObs = vm?.InputLevelPercent ?? 0.0;
+        // Live fill + peak marker use the same visible min scale. The solid fill shows the level the
+        // meter mode asks for; with both, the peak is drawn faintly behind the RMS bar.
+        var mode = vm?.MeterMode ?? MeterMode.Peak;
+        var inputPercentObs = mode == MeterMode.Peak ? vm?.InputLevelPercent ?? 0.0 : vm?.RmsLevelPercent ?? 0.0;
         var peakPercentObs = vm?.PeakLevelPercent ?? 0.0;
 
         var inputPercent = RescaleObsPercentToVisible(inputPercentObs);
@@ -275,17 +305,98 @@ public sealed partial class MicrophoneFlyout : UserControl
 
         fill.Width = width * (inputPercent / 100.0);
 
+        if (peakFill != null)
+        {
+            var peakFillPercent = mode == MeterMode.PeakAndRms ? RescaleObsPercentToVisible(vm?.InputLevelPercent ?? 0.0) : 0.0;
+            peakFill.Width = width * (peakFillPercent / 100.0);
+        }
+
         var markerWidth = marker.Width;
         var markerX = (width * (peakPercent / 100.0)) - (markerWidth / 2.0);
         markerX = Math.Clamp(markerX, 0.0, Math.Max(0.0, width - markerWidth));
         Microsoft.UI.Xaml.Controls.Canvas.SetLeft(marker, markerX);
     }
 
+    private static void UpdateHistoryVisuals(FrameworkElement host, MicrophoneEntryViewModel? vm)
+    {
+        if (host.FindName("HistoryHost") is not FrameworkElement historyHost) return;
+        if (host.FindName("HistoryLine") is not Microsoft.UI.Xaml.Shapes.Polyline line) return;
+
+        var levels = vm?.HistoryDbFs ?? Array.Empty<double>();
+        var width = historyHost.ActualWidth;
+        var height = historyHost.ActualHeight;
+        var points = new PointCollection();
+
+        // Newest at the right edge; a short history only fills the right of the graph, so the
+        // time scale stays 30 seconds across. Same -60..0 dB range as the meter, bottom to top.
+        if (width > 0 && height > 0)
+        {
+            var step = width / (LevelHistory.Capacity - 1);
+            for (var i = 0; i < levels.Length; i++)
+            {
+                var x = width - ((levels.Length - 1 - i) * step);
+                var y = height * (1.0 - (ObsMeterMath.ToVisiblePercent(ObsMeterMath.DbToPercent(levels[i])) / 100.0));
+                points.Add(new Windows.Foundation.Point(x, y));
+            }
+        }
+
+        line.Points = points;
+    }
+
+    private void MicTestWaveHost_SizeChanged(object sender, SizeChangedEventArgs e)
+    {
+        if (_isUnloaded) return;
+        if (sender is not FrameworkElement host) return;
+        UpdateMicTestWaveformVisuals(host, host.DataContext as MicrophoneEntryViewModel);
+    }
+
+    private static void UpdateMicTestWaveformVisuals(FrameworkElement host, MicrophoneEntryViewModel? vm)
+    {
+        if (host.FindName("MicTestWaveHost") is not FrameworkElement waveHost) return;
+        if (host.FindName("MicTestWave") is not Microsoft.UI.Xaml.Shapes.Polygon wave) return;
+
+        var peaks = vm?.MicTestWaveform ?? Array.Empty<double>();
+        var width = waveHost.ActualWidth;
+        var middle = waveHost.ActualHeight / 2.0;
+        var points = new PointCollection();
+
+        // Same -60..0 dB scale as the meters, or speech would barely leave the middle line
+        static double Scale(double peak) => ObsMeterMath.ToVisiblePercent(ObsMeterMath.DbToPercent(ObsMeterMath.MulToDb(peak))) / 100.0;
+
+        // Mirrored about the middle: along the top left to right, then back along the bottom.
+        // Slots are spaced for the whole recording, so it fills in from the left as it records.
+        if (width > 0 && middle > 0 && peaks.Length > 0)
+        {
+            var step = width / Math.Max(1, MicrophoneEntryViewModel.MicTestWaveformSlots - 1);
+            for (var i = 0; i < peaks.Length; i++)
+            {
+                points.Add(new Windows.Foundation.Point(Math.Min(width, i * step), middle - (Scale(peaks[i]) * middle)));
+            }
+
+            for (var i = peaks.Length - 1; i >= 0; i--)
+            {
+                points.Add(new Windows.Foundation.Point(Math.Min(width, i * step), middle + (Scale(peaks[i]) * middle)));
+            }
+        }
+
+        wave.Points = points;
+    }
+
     private void Microphones_CollectionChanged(object? sender, NotifyCollectionChangedEventArgs e)
     {
         UpdateViewportHeight();
     }
 
+    private void ViewModel_PropertyChanged(object? sender, PropertyChangedEventArgs e)
+    {
+        if (e.PropertyName != nameof(MicrophoneListViewModel.IsCompact) &&
+            e.PropertyName != nameof(MicrophoneListViewModel.IsHistoryEnabled)) return;
+
+        // The largest card seen so far is kept, so start over or smaller rows would keep the taller height
+        _cardOuterHeight = null;
+        UpdateViewportHeight();
+    }
+
     private void MicrophoneCard_Loaded(object sender, RoutedEventArgs e)
     {
         if (_isUnloaded) return;
@@ -331,15 +442,15 @@ public sealed partial class MicrophoneFlyout : UserControl
         return false;
     }
 
-    private static T? FindFirstDescendant<T>(DependencyObject root) where T : DependencyObject
+    private static T? FindFirstDescendant<T>(DependencyObject root, Func<T, bool>? predicate = null) where T : DependencyObject
     {
         var count = VisualTreeHelper.GetChildrenCount(root);
         for (var i = 0; i < count; i++)
         {
             var child = VisualTreeHelper.GetChild(root, i);
-            if (child is T match) return match;
+            if (child is T match && (predicate == null || predicate(match))) return match;
 
-            var nested = FindFirstDescendant<T>(child);
+            var nested = FindFirstDescendant(child, predicate);
             if (nested != null) return nested;
         }
 
@@ -409,7 +520,8 @@ public sealed partial class MicrophoneFlyout : UserControl
                 var container = MicrophoneList?.ContainerFromIndex(0) as DependencyObject;
                 if (container != null)
                 {
-                    var border = FindFirstDescendant<Microsoft.UI.Xaml.Controls.Border>(container);
+                    // The group header's buttons have borders of their own
+                    var border = FindFirstDescendant<Microsoft.UI.Xaml.Controls.Border>(container, b => b.Name == "CardBorder");
                     TryCaptureCardOuterHeight(border);
                 }
             }
@@ -426,10 +538,13 @@ public sealed partial class MicrophoneFlyout : UserControl
 
         // Show all cards (clamped to screen later), but guarantee that when there are
         // 2+ microphones we request enough height for at least 2 full cards.
-        var cardsToShow = count >= 2 ? Math.Max(2, count) : 1;
+        // Cards in collapsed groups only take up their group's header.
+        var shown = ViewModel.Microphones.Count(m => m.IsCardShown);
+        var cardsToShow = shown >= 2 ? Math.Max(2, shown) : Math.Min(shown, 1);
         var cardsHeight = cardsToShow * _cardOuterHeight.Value;
+        var headersHeight = ViewModel.Microphones.Count(m => m.IsGroupStart) * GroupHeaderOuterHeight;
 
-        return baseHeight + cardsHeight;
+        return baseHeight + cardsHeight + headersHeight;
     }
 
     private void DockButton_Click(object sender, RoutedEventArgs e)
@@ -443,13 +558,7 @@ public sealed partial class MicrophoneFlyout : UserControl
         else
         {
             // Create docked window as a normal resizable window (not a popup)
-            var dockedWindow = new MicrophoneWindow(isDocked: true)
-            {
-                Title = "Microphone Manager"
-            };
-
-            App.DockedWindow = dockedWindow;
-            dockedWindow.Activate();
+            MicrophoneWindow.ShowDocked();
 
             // Close the current popup (if opened from tray)
             RequestClose?.Invoke();
@@ -460,6 +569,254 @@ public sealed partial class MicrophoneFlyout : UserControl
     {
         ViewModel.DismissError();
     }
+
+    private void EntryContextMenu_Opening(object? sender, object e)
+    {
+        if (sender is not MenuFlyout menu) return;
+        if (menu.Target?.DataContext is not MicrophoneEntryViewModel vm) return;
+
+        PopulateAudioEffectsMenu(menu, vm);
+        PopulateOrderMenu(menu, vm);
+        PopulatePriorityMenu(menu, vm);
+
+        _contextMenuCard = menu.Target;
+        if (menu.Items.OfType<MenuFlyoutItem>().FirstOrDefault(i => Equals(i.Tag, "Rename")) is { } renameItem)
+        {
+            renameItem.Visibility = ViewModel.CanRename ? Visibility.Visible : Visibility.Collapsed;
+        }
+
+        if (menu.Items.OfType<MenuFlyoutItem>().FirstOrDefault(i => Equals(i.Tag, "Hide")) is { } hideItem)
+        {
+            hideItem.Visibility = ViewModel.CanHide ? Visibility.Visible : Visibility.Collapsed;
+            hideItem.Command = ViewModel.ToggleHiddenCommand;
+            hideItem.CommandParameter = vm;
+        }
+
+        if (menu.Items.OfType<ToggleMenuFlyoutItem>().FirstOrDefault(i => Equals(i.Tag, "VolumeLock")) is { } lockItem)
+        {
+            lockItem.Visibility = ViewModel.CanLockVolume ? Visibility.Visible : Visibility.Collapsed;
+            lockItem.IsChecked = vm.IsVolumeLocked;
+            lockItem.Command = ViewModel.ToggleVolumeLockCommand;
+            lockItem.CommandParameter = vm;
+        }
+
+        if (menu.Items.OfType<MenuFlyoutSubItem>().FirstOrDefault(i => Equals(i.Tag, "DefaultFormat")) is { } formatMenu)
+        {
+            _ = PopulateFormatMenuAsync(formatMenu, vm);
+        }
+    }
+
+    private static async Task PopulateFormatMenuAsync(MenuFlyoutSubItem formatMenu, MicrophoneEntryViewModel vm)
+    {
+        // Probing opens an IAudioClient per format, so only do it when the menu is actually shown,
+        // and off the UI thread so the menu opens straight away
+        var supported = await vm.GetSupportedFormatsAsync();
+        if (supported.Count == 0) return;
+
+        var current = vm.GetDefaultFormat();
+        formatMenu.Items.Clear();
+        foreach (var format in supported)
+        {
+            formatMenu.Items.Add(new ToggleMenuFlyoutItem
+            {
+                Text = format.RateAndDepthLabel,
+                IsChecked = format == current,
+                Command = vm.SetDefaultFormatCommand,
+                CommandParameter = format.CommandParameter
+            });
+        }
+    }
+
+    private void GroupCollapseButton_Click(object sender, RoutedEventArgs e)
+    {
+        if ((sender as FrameworkElement)?.DataContext is not MicrophoneEntryViewModel vm) return;
+
+        ViewModel.ToggleGroupCollapsedCommand.Execute(vm);
+        ViewportHeightChanged?.Invoke(this, EventArgs.Empty);
+    }
+
+    private void GroupMuteButton_Click(object sender, RoutedEventArgs e)
+    {
+        if ((sender as FrameworkElement)?.DataContext is MicrophoneEntryViewModel vm)
+        {
+            ViewModel.ToggleGroupMuteCommand.Execute(vm);
+        }
+    }
+
+    private void ClipCount_Tapped(object sender, TappedRoutedEventArgs e)
+    {
+        if ((sender as FrameworkElement)?.DataContext is MicrophoneEntryViewModel vm)
+        {
+            ViewModel.ResetClipCountCommand.Execute(vm);
+            e.Handled = true;
+        }
+    }
+
+    private void CalibrateMenuItem_Click(object sender, RoutedEventArgs e)
+    {
+        if (_contextMenuCard?.DataContext is MicrophoneEntryViewModel vm)
+        {
+            CalibrationWindow.ShowCalibration(vm.Id, vm.DisplayName);
+        }
+    }
+
+    private void RenameMenuItem_Click(object sender, RoutedEventArgs e)
+    {
+        if (_contextMenuCard?.DataContext is MicrophoneEntryViewModel vm)
+        {
+            StartRename(vm, _contextMenuCard);
+        }
+    }
+
+    private void DeviceName_DoubleTapped(object sender, DoubleTappedRoutedEventArgs e)
+    {
+        if (!ViewModel.CanRename) return;
+        if (sender is FrameworkElement { Parent: FrameworkElement header, DataContext: MicrophoneEntryViewModel vm })
+        {
+            StartRename(vm, header);
+        }
+    }
+
+    private void StartRename(MicrophoneEntryViewModel vm, DependencyObject container)
+    {
+        ViewModel.BeginRenameCommand.Execute(vm);
+
+        // The box only becomes focusable once layout has made it visible
+        DispatcherQueue.TryEnqueue(() =>
+        {
+            if (FindFirstDescendant<TextBox>(container) is { } box)
+            {
+                box.Focus(FocusState.Programmatic);
+                box.SelectAll();
+            }
+        });
+    }
+
+    private void RenameBox_KeyDown(object sender, KeyRoutedEventArgs e)
+    {
+        if (sender is not FrameworkElement { DataContext: MicrophoneEntryViewModel vm }) return;
+
+        if (e.Key == Windows.System.VirtualKey.Enter)
+        {
+            ViewModel.CommitRenameCommand.Execute(vm);
+            e.Handled = true;
+        }
+        else if (e.Key == Windows.System.VirtualKey.Escape)
+        {
+            ViewModel.CancelRenameCommand.Execute(vm);
+            e.Handled = true;
+        }
+    }
+
+    private void UndoAccelerator_Invoked(KeyboardAccelerator sender, KeyboardAcceleratorInvokedEventArgs args)
+    {
+        // In the rename box Ctrl+Z undoes typing, not the last device change
+        if (XamlRoot != null && FocusManager.GetFocusedElement(XamlRoot) is TextBox) return;
+
+        args.Handled = true;
+        ViewModel.UndoCommand.Execute(null);
+    }
+
+    private void DensityAccelerator_Invoked(KeyboardAccelerator sender, KeyboardAcceleratorInvokedEventArgs args)
+    {
+        args.Handled = true;
+        ViewModel.ToggleCompactCommand.Execute(null);
+    }
+
+    private void DensityButton_Click(object sender, RoutedEventArgs e)
+    {
+        ViewModel.ToggleCompactCommand.Execute(null);
+    }
+
+    private void RenameBox_LostFocus(object sender, RoutedEventArgs e)
+    {
+        if (sender is FrameworkElement { DataContext: MicrophoneEntryViewModel vm })
+        {
+            ViewModel.CommitRenameCommand.Execute(vm);
+        }
+    }
+
+    private void PopulateOrderMenu(MenuFlyout menu, MicrophoneEntryViewModel vm)
+    {
+        var orderMenu = menu.Items.OfType<MenuFlyoutSubItem>().FirstOrDefault(i => Equals(i.Tag, "Order"));
+        if (orderMenu == null) return;
+
+        orderMenu.Visibility = ViewModel.CanReorder ? Visibility.Visible : Visibility.Collapsed;
+        orderMenu.Items.Clear();
+
+        var index = ViewModel.Microphones.IndexOf(vm);
+        orderMenu.Items.Add(new MenuFlyoutItem { Text = vm.PinToggleText, Command = ViewModel.TogglePinnedCommand, CommandParameter = vm });
+        // Moving only shows in the user's own order; the other sort modes would put the entry straight back
+        var canMove = ViewModel.CanMoveEntries;
+        orderMenu.Items.Add(new MenuFlyoutItem { Text = "Move up", IsEnabled = canMove && index > 0, Command = ViewModel.MoveUpCommand, CommandParameter = vm });
+        orderMenu.Items.Add(new MenuFlyoutItem { Text = "Move down", IsEnabled = canMove && index < ViewModel.Microphones.Count - 1, Command = ViewModel.MoveDownCommand, CommandParameter = vm });
+    }
+
+    private void SortMenu_Opening(object? sender, object e)
+    {
+        SortMenu.Items.Clear();
+
+        var current = ViewModel.SortMode;
+        foreach (var (text, mode) in MainWindow.SortModeChoices)
+        {
+            SortMenu.Items.Add(new ToggleMenuFlyoutItem
+            {
+                Text = text,
+                IsChecked = mode == current,
+                Command = ViewModel.SetSortModeCommand,
+                CommandParameter = mode
+            });
+        }
+    }
+
+    private void PopulatePriorityMenu(MenuFlyout menu, MicrophoneEntryViewModel vm)
+    {
+        var priorityMenu = menu.Items.OfType<MenuFlyoutSubItem>().FirstOrDefault(i => Equals(i.Tag, "Priority"));
+        if (priorityMenu == null) return;
+
+        priorityMenu.Visibility = ViewModel.CanEditPriority ? Visibility.Visible : Visibility.Collapsed;
+        priorityMenu.Items.Clear();
+
+        if (!vm.HasPriorityRank)
+        {
+            priorityMenu.Items.Add(new MenuFlyoutItem { Text = "Add to preferred list", Command = ViewModel.PreferMicrophoneCommand, CommandParameter = vm });
+            return;
+        }
+
+        priorityMenu.Items.Add(new MenuFlyoutItem { Text = "Move up", IsEnabled = vm.PriorityRank > 1, Command = ViewModel.RaisePriorityCommand, CommandParameter = vm });
+        priorityMenu.Items.Add(new MenuFlyoutItem { Text = "Move down", Command = ViewModel.LowerPriorityCommand, CommandParameter = vm });
+        priorityMenu.Items.Add(new MenuFlyoutItem { Text = "Remove from preferred list", Command = ViewModel.RemovePreferenceCommand, CommandParameter = vm });
+    }
+
+    private static void PopulateAudioEffectsMenu(MenuFlyout menu, MicrophoneEntryViewModel vm)
+    {
+        var effectsMenu = menu.Items.OfType<MenuFlyoutSubItem>().FirstOrDefault(i => Equals(i.Tag, "AudioEffects"));
+        if (effectsMenu == null) return;
+
+        effectsMenu.Visibility = vm.Supports(DeviceCapabilities.AudioEffects) ? Visibility.Visible : Visibility.Collapsed;
+        if (effectsMenu.Visibility == Visibility.Collapsed) return;
+
+        var effects = vm.GetAudioEffects();
+        effectsMenu.Items.Clear();
+        if (effects.Count == 0)
+        {
+            effectsMenu.Items.Add(new MenuFlyoutItem { Text = "None reported", IsEnabled = false });
+            return;
+        }
+
+        // Effects the driver won't let us change are still listed so their state is visible
+        foreach (var effect in effects)
+        {
+            effectsMenu.Items.Add(new ToggleMenuFlyoutItem
+            {
+                Text = effect.Name,
+                IsChecked = effect.IsEnabled,
+                IsEnabled = effect.CanToggle,
+                Command = vm.ToggleAudioEffectCommand,
+                CommandParameter = effect
+            });
+        }
+    }
 }
 
 // Extension methods for MicrophoneEntryViewModel to add helper functions
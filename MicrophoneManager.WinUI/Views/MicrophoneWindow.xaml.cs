@@ -1,5 +1,8 @@
+using Microsoft.Extensions.DependencyInjection;
 using Microsoft.UI.Windowing;
 using Microsoft.UI.Xaml;
+using Microsoft.UI.Xaml.Media;
+using MicrophoneManager.WinUI.Services;
 using System;
 
 namespace MicrophoneManager.WinUI.Views;
@@ -14,12 +17,18 @@ public sealed partial class MicrophoneWindow : Window
     private const int ScreenMarginPx = 12;
 
     private readonly bool _isDocked;
+    private readonly StartModeService _startModeService;
+    private readonly AppearanceService _appearance;
 
     public MicrophoneWindow(bool isDocked = false)
     {
         _isDocked = isDocked;
+        _startModeService = App.Host.Services.GetRequiredService<StartModeService>();
+        _appearance = App.Host.Services.GetRequiredService<AppearanceService>();
 
         InitializeComponent();
+        WindowTheme.Track(this);
+        ApplyUiScale();
 
         Flyout.IsDockedMode = isDocked;
 
@@ -39,6 +48,8 @@ public sealed partial class MicrophoneWindow : Window
 
         Activated += MicrophoneWindow_Activated;
         Closed += MicrophoneWindow_Closed;
+        _appearance.Changed += Appearance_Changed;
+        RootBorder.SizeChanged += (_, _) => FitScaledFlyout();
 
         RootBorder.Loaded += (_, _) =>
         {
@@ -65,6 +76,27 @@ public sealed partial class MicrophoneWindow : Window
         catch { }
     }
 
+    /// <summary>
+    /// Opens the docked window (or brings the open one forward), where it was last left.
+    /// </summary>
+    public static MicrophoneWindow ShowDocked()
+    {
+        if (App.DockedWindow is MicrophoneWindow existing)
+        {
+            existing.Activate();
+            return existing;
+        }
+
+        var dockedWindow = new MicrophoneWindow(isDocked: true)
+        {
+            Title = "Microphone Manager"
+        };
+
+        App.DockedWindow = dockedWindow;
+        dockedWindow.Activate();
+        return dockedWindow;
+    }
+
     private void ConfigureWindow()
     {
         var appWindow = AppWindow;
@@ -100,6 +132,16 @@ public sealed partial class MicrophoneWindow : Window
 
         // Initial size
         appWindow.ResizeClient(new Windows.Graphics.SizeInt32(MinClientWidth, DefaultClientHeight));
+
+        // Docked: back where it was left, unless that monitor is gone
+        if (_isDocked && _startModeService.DockedPosition is var (x, y))
+        {
+            var position = new Windows.Graphics.PointInt32(x, y);
+            if (DisplayArea.GetFromPoint(position, DisplayAreaFallback.None) != null)
+            {
+                appWindow.Move(position);
+            }
+        }
     }
 
     private void MicrophoneWindow_Activated(object sender, WindowActivatedEventArgs args)
@@ -121,12 +163,61 @@ public sealed partial class MicrophoneWindow : Window
         ResizeAndPosition();
     }
 
+    private void Appearance_Changed(object? sender, EventArgs e)
+    {
+        DispatcherQueue.TryEnqueue(() =>
+        {
+            ApplyUiScale();
+            ResizeAndPosition();
+        });
+    }
+
+    /// <summary>
+    /// Draws the flyout at the size chosen in settings. XAML has no layout transform, so the flyout
+    /// is laid out at the unscaled size and scaled up to fill the border.
+    /// </summary>
+    private void ApplyUiScale()
+    {
+        var uiScale = _appearance.Scale;
+        if (uiScale == 1.0)
+        {
+            Flyout.RenderTransform = null;
+            Flyout.Width = double.NaN;
+            Flyout.Height = double.NaN;
+            Flyout.HorizontalAlignment = HorizontalAlignment.Stretch;
+            Flyout.VerticalAlignment = VerticalAlignment.Stretch;
+            return;
+        }
+
+        Flyout.RenderTransform = new ScaleTransform { ScaleX = uiScale, ScaleY = uiScale };
+        Flyout.HorizontalAlignment = HorizontalAlignment.Left;
+        Flyout.VerticalAlignment = VerticalAlignment.Top;
+        FitScaledFlyout();
+    }
+
+    private void FitScaledFlyout()
+    {
+        var uiScale = _appearance.Scale;
+        if (uiScale == 1.0) return;
+
+        // Border padding and thickness, as in ResizeAndPosition
+        const double borderPadding = 32 + 2;
+        var width = RootBorder.ActualWidth - borderPadding;
+        var height = RootBorder.ActualHeight - borderPadding;
+        if (width <= 0 || height <= 0) return;
+
+        Flyout.Width = width / uiScale;
+        Flyout.Height = height / uiScale;
+    }
+
     private void MicrophoneWindow_Closed(object sender, WindowEventArgs args)
     {
+        try { _appearance.Changed -= Appearance_Changed; } catch { }
         try { Flyout.ViewModel.Dispose(); } catch { }
 
         if (_isDocked)
         {
+            try { _startModeService.DockedPosition = (AppWindow.Position.X, AppWindow.Position.Y); } catch { }
             App.DockedWindow = null;
         }
     }
@@ -156,8 +247,9 @@ public sealed partial class MicrophoneWindow : Window
 
             // Add RootBorder padding (16 on each side) and border thickness
             const double borderPadding = 32 + 2; // 16*2 padding + 1*2 border
-            var desiredWidthDip = contentSize.Width + borderPadding;
-            var desiredHeightDip = contentSize.Height + borderPadding;
+            var uiScale = _appearance.Scale;
+            var desiredWidthDip = (contentSize.Width * uiScale) + borderPadding;
+            var desiredHeightDip = (contentSize.Height * uiScale) + borderPadding;
 
             // ResizeClient expects a Win32 client size; the non-client area is calculated.
             var maxClientWidth = Math.Max(0, workArea.Width - (ScreenMarginPx * 2));
@@ -166,7 +258,7 @@ public sealed partial class MicrophoneWindow : Window
             var targetClientWidth = (int)Math.Ceiling(desiredWidthDip * scale);
             var targetClientHeight = (int)Math.Ceiling(desiredHeightDip * scale);
 
-            var minClientWidth = (int)Math.Ceiling(MinClientWidth * scale);
+            var minClientWidth = (int)Math.Ceiling(MinClientWidth * uiScale * scale);
 
             targetClientWidth = Math.Clamp(targetClientWidth, minClientWidth, maxClientWidth);
             targetClientHeight = Math.Clamp(targetClientHeight, 200, maxClientHeight);
@@ -0,0 +1,139 @@
+using System.Runtime.InteropServices;
+using Microsoft.Extensions.DependencyInjection;
+using Microsoft.Extensions.Logging;
+using Microsoft.UI;
+using Microsoft.UI.Dispatching;
+using Microsoft.UI.Windowing;
+using Microsoft.UI.Xaml;
+using MicrophoneManager.WinUI.Services;
+
+namespace MicrophoneManager.WinUI.Views;
+
+/// <summary>
+/// Small borderless banner on the screen the user is working on, at the top unless asked otherwise.
+/// It doesn't take focus, hides itself after a while and can carry one action button ("Unmute").
+/// </summary>
+public sealed partial class OsdWindow : Window
+{
+    private const int OsdWidth = 360;
+    private const int OsdHeight = 56;
+    // Distance from the edges of the work area
+    private const int EdgeMargin = 24;
+
+    private static readonly TimeSpan DefaultDuration = TimeSpan.FromSeconds(4);
+
+    private readonly DispatcherQueueTimer _hideTimer;
+    private readonly ILogger<OsdWindow> _logger;
+    private Action? _action;
+
+    public OsdWindow()
+    {
+        InitializeComponent();
+        _logger = App.Host.Services.GetRequiredService<ILogger<OsdWindow>>();
+        WindowTheme.Track(this);
+
+        AppWindow.IsShownInSwitchers = false;
+
+        var presenter = OverlappedPresenter.Create();
+        presenter.IsAlwaysOnTop = true;
+        presenter.IsResizable = false;
+        presenter.IsMaximizable = false;
+        presenter.IsMinimizable = false;
+        presenter.SetBorderAndTitleBar(false, false);
+        AppWindow.SetPresenter(presenter);
+
+        _hideTimer = DispatcherQueue.CreateTimer();
+        _hideTimer.IsRepeating = false;
+        _hideTimer.Tick += (s, e) => AppWindow.Hide();
+
+        Closed += OsdWindow_Closed;
+    }
+
+    /// <summary>
+    /// Shows <paramref name="message"/>, replacing whatever the banner showed before.
+    /// </summary>
+    public static void ShowMessage(string glyph, string message, string? actionText = null, Action? action = null, TimeSpan? duration = null, OsdPosition position = OsdPosition.TopCenter)
+    {
+        if (App.OsdWindow is not OsdWindow window)
+        {
+            window = new OsdWindow();
+            App.OsdWindow = window;
+        }
+
+        window.Show(glyph, message, actionText, action, duration ?? DefaultDuration, position);
+    }
+
+    private void Show(string glyph, string message, string? actionText, Action? action, TimeSpan duration, OsdPosition position)
+    {
+        GlyphIcon.Glyph = glyph;
+        MessageText.Text = message;
+        _action = action;
+        ActionButton.Content = actionText;
+        ActionButton.Visibility = action != null && actionText != null ? Visibility.Visible : Visibility.Collapsed;
+
+        PositionOnActiveDisplay(position);
+
+        // Shown without activation so it never steals focus from the call the user is in
+        AppWindow.Show(false);
+
+        _hideTimer.Stop();
+        _hideTimer.Interval = duration;
+        _hideTimer.Start();
+    }
+
+    private void PositionOnActiveDisplay(OsdPosition position)
+    {
+        var foreground = GetForegroundWindow();
+        var displayArea = foreground != IntPtr.Zero
+            ? DisplayArea.GetFromWindowId(Win32Interop.GetWindowIdFromWindow(foreground), DisplayAreaFallback.Primary)
+            : DisplayArea.Primary;
+        var workArea = displayArea.WorkArea;
+
+        // AppWindow sizes are in pixels; the layout above is in DIPs
+        var scale = RootGrid.XamlRoot?.RasterizationScale ?? GetDpiForWindow(WinRT.Interop.WindowNative.GetWindowHandle(this)) / 96.0;
+        var width = (int)Math.Round(OsdWidth * scale);
+        var height = (int)Math.Round(OsdHeight * scale);
+        var margin = (int)Math.Round(EdgeMargin * scale);
+
+        var x = position switch
+        {
+            OsdPosition.TopLeft or OsdPosition.BottomLeft => workArea.X + margin,
+            OsdPosition.TopRight or OsdPosition.BottomRight => workArea.X + workArea.Width - width - margin,
+            _ => workArea.X + (workArea.Width - width) / 2,
+        };
+        var y = position is OsdPosition.BottomCenter or OsdPosition.BottomLeft or OsdPosition.BottomRight
+            ? workArea.Y + workArea.Height - height - margin
+            : workArea.Y + margin;
+
+        AppWindow.MoveAndResize(new Windows.Graphics.RectInt32(x, y, width, height));
+    }
+
+    private void ActionButton_Click(object sender, RoutedEventArgs e)
+    {
+        var action = _action;
+        _action = null;
+        _hideTimer.Stop();
+        AppWindow.Hide();
+
+        try
+        {
+            action?.Invoke();
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "OSD action failed");
+        }
+    }
+
+    private void OsdWindow_Closed(object sender, WindowEventArgs args)
+    {
+        _hideTimer.Stop();
+        App.OsdWindow = null;
+    }
+
+    [DllImport("user32.dll")]
+    private static extern IntPtr GetForegroundWindow();
+
+    [DllImport("user32.dll")]
+    private static extern uint GetDpiForWindow(IntPtr hwnd);
+}
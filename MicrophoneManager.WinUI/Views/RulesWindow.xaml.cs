@@ -0,0 +1,181 @@
+using System.Collections.ObjectModel;
+using System.Diagnostics;
+using Microsoft.Extensions.DependencyInjection;
+using Microsoft.Extensions.Logging;
+using Microsoft.UI.Xaml;
+using Microsoft.UI.Xaml.Controls;
+using MicrophoneManager.WinUI.Models;
+using MicrophoneManager.WinUI.Services;
+
+namespace MicrophoneManager.WinUI.Views;
+
+/// <summary>
+/// An <see cref="AppRule"/> as listed in the rules window.
+/// </summary>
+public sealed record AppRuleListItem(AppRule Rule, string Title, string Details);
+
+/// <summary>
+/// Lists, adds and removes <see cref="AppRule"/>s.
+/// </summary>
+public sealed partial class RulesWindow : Window
+{
+    private const int ClientWidth = 640;
+    private const int ClientHeight = 480;
+
+    private readonly AppRuleService _ruleService;
+    private readonly IAudioDeviceService _audioService;
+    private readonly DeviceAliasService _aliasService;
+    private readonly ILogger<RulesWindow> _logger;
+    private readonly ObservableCollection<AppRuleListItem> _items = new();
+
+    public RulesWindow()
+    {
+        _ruleService = App.Host.Services.GetRequiredService<AppRuleService>();
+        _audioService = App.Host.Services.GetRequiredService<IAudioDeviceService>();
+        _aliasService = App.Host.Services.GetRequiredService<DeviceAliasService>();
+        _logger = App.Host.Services.GetRequiredService<ILogger<RulesWindow>>();
+
+        InitializeComponent();
+        WindowTheme.Track(this);
+
+        AppWindow.ResizeClient(new Windows.Graphics.SizeInt32(ClientWidth, ClientHeight));
+        RulesList.ItemsSource = _items;
+        DeviceBox.ItemsSource = _audioService.GetMicrophones()
+            .Select(m => new DeviceChoice(DevicePriorityService.KeyFor(m), _aliasService.DisplayNameFor(m)))
+            .ToList();
+        Refresh();
+
+        _ruleService.RulesChanged += RuleService_RulesChanged;
+        Closed += RulesWindow_Closed;
+    }
+
+    /// <summary>
+    /// Opens the rules window, or brings the open one forward.
+    /// </summary>
+    public static RulesWindow ShowRules()
+    {
+        if (App.RulesWindow is RulesWindow existing)
+        {
+            existing.Activate();
+            return existing;
+        }
+
+        var window = new RulesWindow();
+        App.RulesWindow = window;
+        window.Activate();
+        return window;
+    }
+
+    private void Refresh()
+    {
+        var microphones = _audioService.GetMicrophones();
+
+        _items.Clear();
+        foreach (var rule in _ruleService.GetRules())
+        {
+            var device = microphones.FirstOrDefault(m => string.Equals(DevicePriorityService.KeyFor(m), rule.DeviceKey, StringComparison.OrdinalIgnoreCase));
+            var deviceName = device != null ? _aliasService.DisplayNameFor(device) : "a microphone that isn't connected";
+            var role = rule.Role switch
+            {
+                AppRuleRole.Communications => "communications device",
+                AppRuleRole.Both => "default and communications device",
+                _ => "default device",
+            };
+
+            _items.Add(new AppRuleListItem(
+                rule,
+                rule.ProcessName,
+                $"{deviceName} as {role}{(rule.RestoreOnExit ? ", put back on exit" : "")}"));
+        }
+
+        EmptyText.Visibility = _items.Count == 0 ? Visibility.Visible : Visibility.Collapsed;
+    }
+
+    private void RuleService_RulesChanged(object? sender, EventArgs e)
+    {
+        DispatcherQueue.TryEnqueue(Refresh);
+    }
+
+    private void ProcessBox_TextChanged(AutoSuggestBox sender, AutoSuggestBoxTextChangedEventArgs args)
+    {
+        if (args.Reason != AutoSuggestionBoxTextChangeReason.UserInput) return;
+
+        var text = sender.Text.Trim();
+        sender.ItemsSource = text.Length == 0
+            ? null
+            : GetRunningApplications().Where(n => n.Contains(text, StringComparison.OrdinalIgnoreCase)).Take(10).ToList();
+    }
+
+    private void AddButton_Click(object sender, RoutedEventArgs e)
+    {
+        var process = ProcessBox.Text.Trim();
+        if (process.Length == 0 || DeviceBox.SelectedItem is not DeviceChoice device)
+        {
+            ShowError("Choose an application and a microphone.");
+            return;
+        }
+
+        var role = RoleBox.SelectedIndex switch
+        {
+            1 => AppRuleRole.Communications,
+            2 => AppRuleRole.Both,
+            _ => AppRuleRole.Console,
+        };
+
+        try
+        {
+            _ruleService.SaveRule(new AppRule(process, device.Key, role, RestoreBox.IsChecked == true));
+            ProcessBox.Text = string.Empty;
+            ErrorText.Visibility = Visibility.Collapsed;
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Save application rule failed");
+            ShowError(ex.Message);
+        }
+    }
+
+    private void RemoveButton_Click(object sender, RoutedEventArgs e)
+    {
+        if ((sender as FrameworkElement)?.Tag is string processName)
+        {
+            _ruleService.RemoveRule(processName);
+        }
+    }
+
+    private void ShowError(string message)
+    {
+        ErrorText.Text = message;
+        ErrorText.Visibility = Visibility.Visible;
+    }
+
+    private static List<string> GetRunningApplications()
+    {
+        var processes = Process.GetProcesses();
+        try
+        {
+            // Only processes with a window; services and helpers are rarely what a rule is for
+            return processes
+                .Where(p => p.MainWindowHandle != IntPtr.Zero)
+                .Select(p => p.ProcessName + ".exe")
+                .Distinct(StringComparer.OrdinalIgnoreCase)
+                .Order(StringComparer.OrdinalIgnoreCase)
+                .ToList();
+        }
+        finally
+        {
+            foreach (var process in processes) process.Dispose();
+        }
+    }
+
+    private void RulesWindow_Closed(object sender, WindowEventArgs args)
+    {
+        try { _ruleService.RulesChanged -= RuleService_RulesChanged; } catch { }
+        App.RulesWindow = null;
+    }
+
+    private sealed record DeviceChoice(string Key, string Name)
+    {
+        public override string ToString() => Name;
+    }
+}
@@ -0,0 +1,621 @@
+using Microsoft.Extensions.DependencyInjection;
+using Microsoft.Extensions.Logging;
+using Microsoft.UI.Xaml;
+using Microsoft.UI.Xaml.Controls;
+using Microsoft.UI.Xaml.Controls.Primitives;
+using Microsoft.UI.Xaml.Input;
+using MicrophoneManager.WinUI.Models;
+using MicrophoneManager.WinUI.Services;
+using MicrophoneManager.WinUI.ViewModels;
+
+namespace MicrophoneManager.WinUI.Views;
+
+/// <summary>
+/// Every setting in one place, in tabs. Each control writes through the service that owns the
+/// setting, so it is saved in the preferences store and applied straight away, exactly as the
+/// matching tray menu item would.
+/// </summary>
+public sealed partial class SettingsWindow : Window
+{
+    private const int ClientWidth = 640;
+    private const int ClientHeight = 620;
+
+    private static readonly (string Text, AppTheme Theme)[] ThemeChoices =
+    {
+        ("Use Windows setting", AppTheme.System),
+        ("Light", AppTheme.Light),
+        ("Dark", AppTheme.Dark),
+    };
+
+    private static readonly (string Text, LogLevel Level)[] LogLevelChoices =
+    {
+        ("Everything (trace)", LogLevel.Trace),
+        ("Debugging", LogLevel.Debug),
+        ("Normal", LogLevel.Information),
+        ("Warnings and errors", LogLevel.Warning),
+        ("Errors only", LogLevel.Error),
+    };
+
+    private readonly IPreferencesStore _preferences;
+    private readonly ILogger<SettingsWindow> _logger;
+    private readonly TrayViewModel _trayViewModel;
+    private readonly StartModeService _startModeService;
+    private readonly UpdateService _updateService;
+    private readonly DefaultDeviceLockService _defaultLockService;
+    private readonly VolumeLockService _volumeLockService;
+    private readonly SessionLockService _sessionLockService;
+    private readonly HiddenDeviceService _hiddenDeviceService;
+    private readonly MutedSpeechService _mutedSpeechService;
+    private readonly SilentMicrophoneService _silentMicrophoneService;
+    private readonly ClippingService _clippingService;
+    private readonly MuteOsdService _muteOsdService;
+    private readonly HotkeyService _hotkeyService;
+    private readonly AppearanceService _appearance;
+    private readonly DeviceGroupService _groupService;
+    private readonly DeviceOrderService _orderService;
+    private readonly MeterSettingsService _meterSettings;
+    private readonly LevelMeterService _levelMeter;
+    private readonly RollingFileLoggerProvider _logProvider;
+    private readonly IAudioDeviceService _audioService;
+    private readonly ProfileService _profileService;
+
+    // Set while controls are filled from the services, so their change events aren't taken as edits
+    private bool _loading;
+
+    // Why the last combination pressed for an action wasn't saved
+    private (HotkeyAction Action, string Text)? _hotkeyMessage;
+
+    public SettingsWindow()
+    {
+        _preferences = App.Host.Services.GetRequiredService<IPreferencesStore>();
+        _logger = App.Host.Services.GetRequiredService<ILogger<SettingsWindow>>();
+        _trayViewModel = App.Host.Services.GetRequiredService<TrayViewModel>();
+        _startModeService = App.Host.Services.GetRequiredService<StartModeService>();
+        _updateService = App.Host.Services.GetRequiredService<UpdateService>();
+        _defaultLockService = App.Host.Services.GetRequiredService<DefaultDeviceLockService>();
+        _volumeLockService = App.Host.Services.GetRequiredService<VolumeLockService>();
+        _sessionLockService = App.Host.Services.GetRequiredService<SessionLockService>();
+        _hiddenDeviceService = App.Host.Services.GetRequiredService<HiddenDeviceService>();
+        _mutedSpeechService = App.Host.Services.GetRequiredService<MutedSpeechService>();
+        _silentMicrophoneService = App.Host.Services.GetRequiredService<SilentMicrophoneService>();
+        _clippingService = App.Host.Services.GetRequiredService<ClippingService>();
+        _muteOsdService = App.Host.Services.GetRequiredService<MuteOsdService>();
+        _hotkeyService = App.Host.Services.GetRequiredService<HotkeyService>();
+        _appearance = App.Host.Services.GetRequiredService<AppearanceService>();
+        _groupService = App.Host.Services.GetRequiredService<DeviceGroupService>();
+        _orderService = App.Host.Services.GetRequiredService<DeviceOrderService>();
+        _meterSettings = App.Host.Services.GetRequiredService<MeterSettingsService>();
+        _levelMeter = App.Host.Services.GetRequiredService<LevelMeterService>();
+        _logProvider = App.Host.Services.GetRequiredService<RollingFileLoggerProvider>();
+        _audioService = App.Host.Services.GetRequiredService<IAudioDeviceService>();
+        _profileService = App.Host.Services.GetRequiredService<ProfileService>();
+
+        InitializeComponent();
+        WindowTheme.Track(this);
+
+        AppWindow.ResizeClient(new Windows.Graphics.SizeInt32(ClientWidth, ClientHeight));
+
+        // Setting ranges moves values too; Refresh clears this once the real ones are in
+        _loading = true;
+        StartupMethodBox.ItemsSource = MainWindow.StartupMethodChoices.Select(c => c.Text).ToList();
+        StartModeBox.ItemsSource = MainWindow.StartModeChoices.Select(c => c.Text).ToList();
+        ThemeBox.ItemsSource = ThemeChoices.Select(c => c.Text).ToList();
+        MeterModeBox.ItemsSource = MainWindow.MeterModeChoices.Select(c => c.Text).ToList();
+        SortModeBox.ItemsSource = MainWindow.SortModeChoices.Select(c => c.Text).ToList();
+        OsdPositionBox.ItemsSource = MainWindow.OsdPositionChoices.Select(c => c.Text).ToList();
+        LogLevelBox.ItemsSource = LogLevelChoices.Select(c => c.Text).ToList();
+        ScaleSlider.Minimum = AppearanceService.MinScalePercent;
+        ScaleSlider.Maximum = AppearanceService.MaxScalePercent;
+        OsdDurationBox.Minimum = MuteOsdService.MinDurationMs;
+        OsdDurationBox.Maximum = MuteOsdService.MaxDurationMs;
+        MeterRateBox.Minimum = LevelMeterService.MinRate;
+        MeterRateBox.Maximum = LevelMeterService.MaxRate;
+        ExternalPollBox.Minimum = AudioDeviceService.MinExternalPollMs;
+        ExternalPollBox.Maximum = AudioDeviceService.MaxExternalPollMs;
+
+        // Both only exist as registry settings, which a portable copy doesn't write
+        if (PortableMode.IsEnabled)
+        {
+            StartupToggle.Visibility = Visibility.Collapsed;
+            StartupMethodBox.Visibility = Visibility.Collapsed;
+            DuckingToggle.Visibility = Visibility.Collapsed;
+        }
+
+        // Only the real service polls; there is nothing to configure otherwise
+        if (_audioService is not AudioDeviceService)
+        {
+            ExternalPollBox.Visibility = Visibility.Collapsed;
+        }
+
+        Refresh();
+        RebuildHotkeyRows();
+        RebuildProfileRows();
+
+        _hotkeyService.BindingsChanged += HotkeyService_BindingsChanged;
+        _profileService.ProfilesChanged += ProfileService_ProfilesChanged;
+        _defaultLockService.LockChanged += Service_Changed;
+        _meterSettings.Changed += Service_Changed;
+        _muteOsdService.Changed += Service_Changed;
+        Activated += SettingsWindow_Activated;
+        Closed += SettingsWindow_Closed;
+    }
+
+    /// <summary>
+    /// Opens the settings window, or brings the open one forward.
+    /// </summary>
+    /// <param name="tab">Tag of the tab to show; the current tab when null.</param>
+    public static SettingsWindow ShowSettings(string? tab = null)
+    {
+        if (App.SettingsWindow is not SettingsWindow window)
+        {
+            window = new SettingsWindow();
+            App.SettingsWindow = window;
+        }
+
+        if (tab != null)
+        {
+            window.TabsView.SelectedItem = window.TabsView.MenuItems.OfType<NavigationViewItem>().FirstOrDefault(i => Equals(i.Tag, tab));
+        }
+
+        window.Activate();
+        return window;
+    }
+
+    /// <summary>
+    /// Fills every control from the services. Called on open and when the window comes back to
+    /// the front, since the tray menu can change the same settings meanwhile.
+    /// </summary>
+    private void Refresh()
+    {
+        _loading = true;
+        try
+        {
+            StartupToggle.IsOn = StartupService.KnownStartupEnabled ?? false;
+            StartupMethodBox.SelectedIndex = Array.FindIndex(MainWindow.StartupMethodChoices, c => c.Options == StartupService.GetOptions(_preferences));
+            StartModeBox.SelectedIndex = Array.FindIndex(MainWindow.StartModeChoices, c => c.Mode == _startModeService.Mode);
+            UpdateCheckToggle.IsOn = _updateService.IsEnabled;
+
+            AutoFailoverToggle.IsOn = _trayViewModel.IsAutoFailoverEnabled;
+            DefaultLockToggle.IsOn = _defaultLockService.IsLocked;
+            VolumeLockNotifyToggle.IsOn = _volumeLockService.NotifyOnRevert;
+            MuteOnLockToggle.IsOn = _sessionLockService.IsMuteOnLockEnabled;
+            DuckingToggle.IsOn = _trayViewModel.IsDuckingEnabled;
+            ShowHiddenToggle.IsOn = _hiddenDeviceService.ShowHidden;
+            MutedSpeechToggle.IsOn = _mutedSpeechService.IsEnabled;
+            SilentMicToggle.IsOn = _silentMicrophoneService.IsEnabled;
+            ClipAlertToggle.IsOn = _clippingService.IsAlertEnabled;
+            MuteOsdToggle.IsOn = _muteOsdService.IsEnabled;
+            OsdPositionBox.SelectedIndex = Array.FindIndex(MainWindow.OsdPositionChoices, c => c.Position == _muteOsdService.Position);
+            OsdDurationBox.Value = _muteOsdService.DurationMs;
+
+            ThemeBox.SelectedIndex = Array.FindIndex(ThemeChoices, c => c.Theme == _appearance.Theme);
+            ScaleSlider.Value = _appearance.ScalePercent;
+            SortModeBox.SelectedIndex = Array.FindIndex(MainWindow.SortModeChoices, c => c.Mode == _orderService.SortMode);
+            GroupByDeviceToggle.IsOn = _groupService.IsEnabled;
+            CompactToggle.IsOn = _appearance.IsCompact;
+            MeterModeBox.SelectedIndex = Array.FindIndex(MainWindow.MeterModeChoices, c => c.Mode == _meterSettings.Mode);
+            MeterAttackBox.Value = _meterSettings.AttackMs;
+            MeterDecayBox.Value = _meterSettings.DecayMs;
+            MeterHistoryToggle.IsOn = _meterSettings.ShowHistory;
+
+            LogLevelBox.SelectedIndex = Array.FindIndex(LogLevelChoices, c => c.Level == _logProvider.MinimumLevel);
+            MeterRateBox.Value = _levelMeter.Rate;
+            if (_audioService is AudioDeviceService audioDeviceService)
+            {
+                ExternalPollBox.Value = audioDeviceService.ExternalStatePollMs;
+            }
+        }
+        finally
+        {
+            _loading = false;
+        }
+
+        _ = RefreshStartupToggleAsync();
+    }
+
+    private void RebuildHotkeyRows()
+    {
+        HotkeyRows.Children.Clear();
+
+        foreach (var (action, binding) in _hotkeyService.GetBindings())
+        {
+            var failed = _hotkeyService.FailedRegistrations.Contains(action);
+
+            // A rejected combination stays explained until something else changes the bindings
+            var message = _hotkeyMessage is { } rejected && rejected.Action == action
+                ? rejected.Text
+                : failed ? "In use by another application" : null;
+
+            var row = new Grid { ColumnSpacing = 12, Padding = new Thickness(0, 4, 0, 4) };
+            row.ColumnDefinitions.Add(new ColumnDefinition { Width = new GridLength(1, GridUnitType.Star) });
+            row.ColumnDefinitions.Add(new ColumnDefinition { Width = GridLength.Auto });
+            row.ColumnDefinitions.Add(new ColumnDefinition { Width = GridLength.Auto });
+
+            var text = new StackPanel { VerticalAlignment = VerticalAlignment.Center };
+            text.Children.Add(new TextBlock { Text = action.GetDisplayName() });
+            if (message != null)
+            {
+                text.Children.Add(new TextBlock
+                {
+                    Text = message,
+                    Foreground = (Microsoft.UI.Xaml.Media.Brush)Application.Current.Resources["SystemFillColorCriticalBrush"],
+                    TextWrapping = TextWrapping.Wrap,
+                });
+            }
+            row.Children.Add(text);
+
+            var captureBox = new HotkeyCaptureBox { Binding = binding, VerticalAlignment = VerticalAlignment.Center };
+            captureBox.CaptureStarted += (s, e) => _hotkeyService.SuspendRegistrations();
+            captureBox.Captured += (s, captured) => ApplyCapturedHotkey(action, captured);
+            captureBox.CaptureEnded += (s, e) => _hotkeyService.ResumeRegistrations();
+            Grid.SetColumn(captureBox, 1);
+            row.Children.Add(captureBox);
+
+            // Off frees the keys; on brings back the default binding, as in the tray menu
+            var toggle = new ToggleSwitch { IsOn = binding != null && !failed, OnContent = null, OffContent = null, MinWidth = 0 };
+            toggle.Toggled += (s, e) =>
+            {
+                _hotkeyMessage = null;
+                _hotkeyService.SetBinding(action, toggle.IsOn ? HotkeyService.DefaultBindings[action] : null);
+            };
+            Grid.SetColumn(toggle, 2);
+            row.Children.Add(toggle);
+
+            HotkeyRows.Children.Add(row);
+        }
+    }
+
+    private void RebuildProfileRows()
+    {
+        ProfileRows.Children.Clear();
+
+        foreach (var profile in _profileService.GetProfiles())
+        {
+            var row = new Grid { ColumnSpacing = 12 };
+            row.ColumnDefinitions.Add(new ColumnDefinition { Width = new GridLength(1, GridUnitType.Star) });
+            row.ColumnDefinitions.Add(new ColumnDefinition { Width = GridLength.Auto });
+
+            var nameBox = new TextBox { Text = profile.Name };
+            nameBox.KeyDown += (s, e) =>
+            {
+                if (e.Key == Windows.System.VirtualKey.Enter)
+                {
+                    RenameProfile(profile.Name, nameBox);
+                    e.Handled = true;
+                }
+                else if (e.Key == Windows.System.VirtualKey.Escape)
+                {
+                    nameBox.Text = profile.Name;
+                    e.Handled = true;
+                }
+            };
+            nameBox.LostFocus += (s, e) => RenameProfile(profile.Name, nameBox);
+            row.Children.Add(nameBox);
+
+            var deleteButton = new Button { Content = "Delete" };
+            deleteButton.Click += (s, e) => _profileService.DeleteProfile(profile.Name);
+            Grid.SetColumn(deleteButton, 1);
+            row.Children.Add(deleteButton);
+
+            ProfileRows.Children.Add(row);
+        }
+    }
+
+    private void RenameProfile(string name, TextBox nameBox)
+    {
+        // Enter renames and the rebuilt row then loses focus; the old name is gone by then
+        var newName = nameBox.Text.Trim();
+        if (newName == name || _profileService.GetProfile(name) == null) return;
+
+        if (newName.Length == 0 || !_profileService.RenameProfile(name, newName))
+        {
+            ShowProfileMessage(newName.Length == 0 ? "A profile needs a name." : $"There is already a profile named \"{newName}\".");
+            nameBox.Text = name;
+            return;
+        }
+
+        ShowProfileMessage(null);
+    }
+
+    private void SaveProfileButton_Click(object sender, RoutedEventArgs e)
+    {
+        SaveNewProfile();
+    }
+
+    private void NewProfileNameBox_KeyDown(object sender, KeyRoutedEventArgs e)
+    {
+        if (e.Key != Windows.System.VirtualKey.Enter) return;
+
+        SaveNewProfile();
+        e.Handled = true;
+    }
+
+    private void SaveNewProfile()
+    {
+        var name = NewProfileNameBox.Text.Trim();
+        if (name.Length == 0) name = _profileService.GetNewProfileName();
+
+        // Saving over a profile from here would be too easy to do by accident; the tray menu is for that
+        if (_profileService.GetProfile(name) != null)
+        {
+            ShowProfileMessage($"There is already a profile named \"{name}\".");
+            return;
+        }
+
+        _profileService.SaveProfile(name);
+        NewProfileNameBox.Text = "";
+        ShowProfileMessage(null);
+    }
+
+    private void ShowProfileMessage(string? message)
+    {
+        ProfileMessage.Text = message ?? "";
+        ProfileMessage.Visibility = message != null ? Visibility.Visible : Visibility.Collapsed;
+    }
+
+    /// <summary>
+    /// Saves a newly pressed combination, unless it couldn't work as a global hotkey or is taken.
+    /// Runs while the hotkeys are suspended, so the check sees only other applications' hotkeys.
+    /// </summary>
+    private void ApplyCapturedHotkey(HotkeyAction action, HotkeyBinding binding)
+    {
+        var problem = HotkeyCapture.Validate(binding) ?? _hotkeyService.GetConflict(action, binding);
+        if (problem != null)
+        {
+            _hotkeyMessage = (action, $"{binding}: {problem}");
+            RebuildHotkeyRows();
+            return;
+        }
+
+        _hotkeyMessage = null;
+        _hotkeyService.SetBinding(action, binding);
+    }
+
+    private void TabsView_SelectionChanged(NavigationView sender, NavigationViewSelectionChangedEventArgs args)
+    {
+        var tag = (args.SelectedItem as NavigationViewItem)?.Tag as string;
+
+        GeneralPanel.Visibility = tag == "General" ? Visibility.Visible : Visibility.Collapsed;
+        BehaviorPanel.Visibility = tag == "Behavior" ? Visibility.Visible : Visibility.Collapsed;
+        HotkeysPanel.Visibility = tag == "Hotkeys" ? Visibility.Visible : Visibility.Collapsed;
+        ProfilesPanel.Visibility = tag == "Profiles" ? Visibility.Visible : Visibility.Collapsed;
+        AppearancePanel.Visibility = tag == "Appearance" ? Visibility.Visible : Visibility.Collapsed;
+        AdvancedPanel.Visibility = tag == "Advanced" ? Visibility.Visible : Visibility.Collapsed;
+    }
+
+    private async void StartupToggle_Toggled(object sender, RoutedEventArgs e)
+    {
+        if (_loading) return;
+
+        var enabled = StartupToggle.IsOn;
+        var options = StartupService.GetOptions(_preferences);
+        await Task.Run(() => StartupService.SetStartupEnabled(enabled, options));
+
+        // Registering can fail (policy, Task Scheduler off); show what actually happened
+        await RefreshStartupToggleAsync();
+    }
+
+    private async void StartupMethodBox_SelectionChanged(object sender, SelectionChangedEventArgs e)
+    {
+        if (_loading || StartupMethodBox.SelectedIndex < 0) return;
+
+        var options = MainWindow.StartupMethodChoices[StartupMethodBox.SelectedIndex].Options;
+        await Task.Run(() => StartupService.SetOptions(_preferences, options));
+    }
+
+    // Checking a scheduled task runs schtasks.exe; the toggle shows the last known state until it answers
+    private async Task RefreshStartupToggleAsync()
+    {
+        var enabled = await StartupService.IsStartupEnabledAsync(_preferences);
+
+        _loading = true;
+        StartupToggle.IsOn = enabled;
+        _loading = false;
+    }
+
+    private void StartModeBox_SelectionChanged(object sender, SelectionChangedEventArgs e)
+    {
+        if (_loading || StartModeBox.SelectedIndex < 0) return;
+        _startModeService.Mode = MainWindow.StartModeChoices[StartModeBox.SelectedIndex].Mode;
+    }
+
+    private void UpdateCheckToggle_Toggled(object sender, RoutedEventArgs e)
+    {
+        if (_loading) return;
+        _updateService.IsEnabled = UpdateCheckToggle.IsOn;
+    }
+
+    private void AutoFailoverToggle_Toggled(object sender, RoutedEventArgs e)
+    {
+        if (_loading || AutoFailoverToggle.IsOn == _trayViewModel.IsAutoFailoverEnabled) return;
+        _trayViewModel.ToggleAutoFailoverCommand.Execute(null);
+    }
+
+    private void DefaultLockToggle_Toggled(object sender, RoutedEventArgs e)
+    {
+        if (_loading) return;
+        _defaultLockService.SetLocked(DefaultLockToggle.IsOn);
+    }
+
+    private void VolumeLockNotifyToggle_Toggled(object sender, RoutedEventArgs e)
+    {
+        if (_loading) return;
+        _volumeLockService.NotifyOnRevert = VolumeLockNotifyToggle.IsOn;
+    }
+
+    private void MuteOnLockToggle_Toggled(object sender, RoutedEventArgs e)
+    {
+        if (_loading) return;
+        _sessionLockService.IsMuteOnLockEnabled = MuteOnLockToggle.IsOn;
+    }
+
+    private void DuckingToggle_Toggled(object sender, RoutedEventArgs e)
+    {
+        if (_loading || DuckingToggle.IsOn == _trayViewModel.IsDuckingEnabled) return;
+        _trayViewModel.ToggleDuckingCommand.Execute(null);
+    }
+
+    private void ShowHiddenToggle_Toggled(object sender, RoutedEventArgs e)
+    {
+        if (_loading) return;
+        _hiddenDeviceService.ShowHidden = ShowHiddenToggle.IsOn;
+    }
+
+    private void MutedSpeechToggle_Toggled(object sender, RoutedEventArgs e)
+    {
+        if (_loading) return;
+        _mutedSpeechService.IsEnabled = MutedSpeechToggle.IsOn;
+    }
+
+    private void SilentMicToggle_Toggled(object sender, RoutedEventArgs e)
+    {
+        if (_loading) return;
+        _silentMicrophoneService.IsEnabled = SilentMicToggle.IsOn;
+    }
+
+    private void ClipAlertToggle_Toggled(object sender, RoutedEventArgs e)
+    {
+        if (_loading) return;
+        _clippingService.IsAlertEnabled = ClipAlertToggle.IsOn;
+    }
+
+    private void MuteOsdToggle_Toggled(object sender, RoutedEventArgs e)
+    {
+        if (_loading) return;
+        _muteOsdService.IsEnabled = MuteOsdToggle.IsOn;
+    }
+
+    private void OsdPositionBox_SelectionChanged(object sender, SelectionChangedEventArgs e)
+    {
+        if (_loading || OsdPositionBox.SelectedIndex < 0) return;
+        _muteOsdService.Position = MainWindow.OsdPositionChoices[OsdPositionBox.SelectedIndex].Position;
+    }
+
+    private void OsdDurationBox_ValueChanged(NumberBox sender, NumberBoxValueChangedEventArgs args)
+    {
+        if (_loading || double.IsNaN(args.NewValue)) return;
+        _muteOsdService.DurationMs = (int)Math.Round(args.NewValue);
+    }
+
+    private void ResetHotkeysButton_Click(object sender, RoutedEventArgs e)
+    {
+        _hotkeyMessage = null;
+        _hotkeyService.ResetToDefaults();
+    }
+
+    private void ThemeBox_SelectionChanged(object sender, SelectionChangedEventArgs e)
+    {
+        if (_loading || ThemeBox.SelectedIndex < 0) return;
+        _appearance.Theme = ThemeChoices[ThemeBox.SelectedIndex].Theme;
+    }
+
+    private void ScaleSlider_ValueChanged(object sender, RangeBaseValueChangedEventArgs e)
+    {
+        if (_loading) return;
+        _appearance.ScalePercent = (int)Math.Round(e.NewValue);
+    }
+
+    private void SortModeBox_SelectionChanged(object sender, SelectionChangedEventArgs e)
+    {
+        if (_loading || SortModeBox.SelectedIndex < 0) return;
+        _orderService.SortMode = MainWindow.SortModeChoices[SortModeBox.SelectedIndex].Mode;
+    }
+
+    private void CompactToggle_Toggled(object sender, RoutedEventArgs e)
+    {
+        if (_loading) return;
+        _appearance.IsCompact = CompactToggle.IsOn;
+    }
+
+    private void GroupByDeviceToggle_Toggled(object sender, RoutedEventArgs e)
+    {
+        if (_loading) return;
+        _groupService.IsEnabled = GroupByDeviceToggle.IsOn;
+    }
+
+    private void MeterModeBox_SelectionChanged(object sender, SelectionChangedEventArgs e)
+    {
+        if (_loading || MeterModeBox.SelectedIndex < 0) return;
+        _meterSettings.Mode = MainWindow.MeterModeChoices[MeterModeBox.SelectedIndex].Mode;
+    }
+
+    private void MeterAttackBox_ValueChanged(NumberBox sender, NumberBoxValueChangedEventArgs args)
+    {
+        if (_loading || double.IsNaN(args.NewValue)) return;
+        _meterSettings.AttackMs = (int)Math.Round(args.NewValue);
+    }
+
+    private void MeterDecayBox_ValueChanged(NumberBox sender, NumberBoxValueChangedEventArgs args)
+    {
+        if (_loading || double.IsNaN(args.NewValue)) return;
+        _meterSettings.DecayMs = (int)Math.Round(args.NewValue);
+    }
+
+    private void MeterHistoryToggle_Toggled(object sender, RoutedEventArgs e)
+    {
+        if (_loading) return;
+        _meterSettings.ShowHistory = MeterHistoryToggle.IsOn;
+    }
+
+    private void LogLevelBox_SelectionChanged(object sender, SelectionChangedEventArgs e)
+    {
+        if (_loading || LogLevelBox.SelectedIndex < 0) return;
+        _logProvider.MinimumLevel = LogLevelChoices[LogLevelBox.SelectedIndex].Level;
+    }
+
+    private void MeterRateBox_ValueChanged(NumberBox sender, NumberBoxValueChangedEventArgs args)
+    {
+        if (_loading || double.IsNaN(args.NewValue)) return;
+        _levelMeter.Rate = (int)Math.Round(args.NewValue);
+    }
+
+    private void ExternalPollBox_ValueChanged(NumberBox sender, NumberBoxValueChangedEventArgs args)
+    {
+        if (_loading || double.IsNaN(args.NewValue)) return;
+
+        if (_audioService is AudioDeviceService audioDeviceService)
+        {
+            audioDeviceService.ExternalStatePollMs = (int)Math.Round(args.NewValue);
+        }
+    }
+
+    private void HotkeyService_BindingsChanged(object? sender, EventArgs e)
+    {
+        DispatcherQueue.TryEnqueue(RebuildHotkeyRows);
+    }
+
+    private void ProfileService_ProfilesChanged(object? sender, EventArgs e)
+    {
+        DispatcherQueue.TryEnqueue(RebuildProfileRows);
+    }
+
+    private void Service_Changed(object? sender, EventArgs e)
+    {
+        DispatcherQueue.TryEnqueue(Refresh);
+    }
+
+    private void SettingsWindow_Activated(object sender, WindowActivatedEventArgs args)
+    {
+        if (args.WindowActivationState == WindowActivationState.Deactivated) return;
+
+        try
+        {
+            Refresh();
+        }
+        catch (Exception ex)
+        {
+            _logger.LogWarning(ex, "Refreshing settings failed");
+        }
+    }
+
+    private void SettingsWindow_Closed(object sender, WindowEventArgs args)
+    {
+        try { _hotkeyService.BindingsChanged -= HotkeyService_BindingsChanged; } catch { }
+        try { _profileService.ProfilesChanged -= ProfileService_ProfilesChanged; } catch { }
+        try { _hotkeyService.ResumeRegistrations(); } catch { }
+        try { _defaultLockService.LockChanged -= Service_Changed; } catch { }
+        try { _meterSettings.Changed -= Service_Changed; } catch { }
+        try { _muteOsdService.Changed -= Service_Changed; } catch { }
+        App.SettingsWindow = null;
+    }
+}
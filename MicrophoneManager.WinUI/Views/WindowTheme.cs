@@ -0,0 +1,39 @@
+using Microsoft.Extensions.DependencyInjection;
+using Microsoft.UI.Xaml;
+using MicrophoneManager.WinUI.Services;
+
+namespace MicrophoneManager.WinUI.Views;
+
+/// <summary>
+/// Applies the <see cref="AppearanceService"/> theme to the app's windows.
+/// </summary>
+public static class WindowTheme
+{
+    /// <summary>
+    /// Applies the chosen theme to <paramref name="window"/> now and whenever it changes, until the window closes.
+    /// </summary>
+    public static void Track(Window window)
+    {
+        var appearance = App.Host.Services.GetRequiredService<AppearanceService>();
+        Apply(window, appearance.Theme);
+
+        EventHandler changed = (s, e) => window.DispatcherQueue.TryEnqueue(() => Apply(window, appearance.Theme));
+        appearance.Changed += changed;
+        window.Closed += (s, e) =>
+        {
+            try { appearance.Changed -= changed; } catch { }
+        };
+    }
+
+    public static void Apply(Window window, AppTheme theme)
+    {
+        if (window.Content is not FrameworkElement root) return;
+
+        root.RequestedTheme = theme switch
+        {
+            AppTheme.Light => ElementTheme.Light,
+            AppTheme.Dark => ElementTheme.Dark,
+            _ => ElementTheme.Default,
+        };
+    }
+}